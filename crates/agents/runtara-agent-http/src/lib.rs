@@ -335,6 +335,14 @@ pub struct HttpRequestInput {
     )]
     #[serde(default = "default_fail_on_error")]
     pub fail_on_error: bool,
+
+    #[field(
+        display_name = "Idempotency Key",
+        description = "Sent as the Idempotency-Key header so providers that support it deduplicate retried requests. An explicit Idempotency-Key entry in headers takes precedence.",
+        example = "3f1c9a7e5b2d48e6a0c4f8b1d2e3a4b5"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 fn default_timeout() -> u64 {
@@ -358,6 +366,7 @@ impl Default for HttpRequestInput {
             response_type: ResponseType::default(),
             timeout_ms: default_timeout(),
             fail_on_error: default_fail_on_error(),
+            idempotency_key: None,
         }
     }
 }
@@ -434,6 +443,16 @@ pub fn http_request(input: HttpRequestInput) -> Result<HttpResponse, AgentError>
             .or_insert_with(|| raw.connection_id.clone());
     }
 
+    // Header names are case-insensitive, so a caller-supplied
+    // `idempotency-key` in any casing wins over the dedicated input.
+    if let Some(ref key) = input.idempotency_key
+        && !headers
+            .keys()
+            .any(|k| k.eq_ignore_ascii_case("idempotency-key"))
+    {
+        headers.insert("Idempotency-Key".to_string(), key.clone());
+    }
+
     // Append query parameters.
     if !query_parameters.is_empty() {
         let query_string: String = query_parameters
@@ -1201,4 +1220,51 @@ mod tests {
             assert_eq!(text, "not valid json");
         }
     }
+
+    #[tokio::test]
+    async fn test_idempotency_key_sent_as_header() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/payments"))
+            .and(header("Idempotency-Key", "token-123"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&mock_server)
+            .await;
+
+        let input = HttpRequestInput {
+            method: HttpMethod::Post,
+            url: format!("{}/payments", mock_server.uri()),
+            body: HttpBody(serde_json::json!({"amount": 10})),
+            idempotency_key: Some("token-123".to_string()),
+            ..Default::default()
+        };
+
+        let response = http_request(input).unwrap();
+        assert_eq!(response.status_code, 201);
+    }
+
+    #[tokio::test]
+    async fn test_explicit_idempotency_header_takes_precedence() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/payments"))
+            .and(header("Idempotency-Key", "from-headers"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&mock_server)
+            .await;
+
+        let input = HttpRequestInput {
+            method: HttpMethod::Post,
+            url: format!("{}/payments", mock_server.uri()),
+            headers: HashMap::from([("idempotency-key".to_string(), "from-headers".to_string())]),
+            body: HttpBody(serde_json::json!({"amount": 10})),
+            idempotency_key: Some("from-input".to_string()),
+            ..Default::default()
+        };
+
+        let response = http_request(input).unwrap();
+        assert_eq!(response.status_code, 201);
+    }
 }
@@ -41,6 +41,17 @@ mod bindings {
 #[capability_input(display_name = "Random Double Input")]
 pub struct RandomDoubleInput {}
 
+#[derive(Debug, Deserialize, CapabilityInput)]
+#[capability_input(display_name = "Seeded Random Input")]
+pub struct RandomSeededInput {
+    #[field(
+        display_name = "Seed",
+        description = "Stable seed material; the same seed always yields the same value. Map it from variables._instance_id plus a per-step key (and variables._loop_indices inside Split/While) so retries and resumes reproduce the value already used",
+        example = "{\"instance\": \"inst-1\", \"step\": \"pick-sample\"}"
+    )]
+    pub seed: Value,
+}
+
 #[derive(Debug, Deserialize, CapabilityInput)]
 #[capability_input(display_name = "Random Array Input")]
 pub struct ReturnRandomArrayInput {
@@ -209,6 +220,16 @@ pub fn random_double(_input: RandomDoubleInput) -> Result<f64, String> {
     Ok(rng.r#gen())
 }
 
+#[capability(
+    module = "utils",
+    display_name = "Seeded Random Double",
+    description = "Generate a deterministic double between 0 and 1 from a seed. Use this instead of Random Double in steps that retry, loop, or perform side effects: a retried or resumed step gets the same value"
+)]
+pub fn random_seeded(input: RandomSeededInput) -> Result<f64, String> {
+    let material = serde_json::to_vec(&input.seed).map_err(|e| e.to_string())?;
+    Ok(seeded_unit_double(&material))
+}
+
 #[capability(
     module = "utils",
     display_name = "Random Array",
@@ -393,6 +414,29 @@ pub fn country_name_to_iso_code(input: CountryNameToIsoCodeInput) -> Result<Stri
     find_country_code(&input.country_name, &code_type)
 }
 
+// -----------------------------------------------------------------------------
+// Seeded randomness
+// -----------------------------------------------------------------------------
+
+/// Map seed bytes to a double in `[0, 1)`.
+///
+/// FNV-1a over the bytes, then the SplitMix64 finalizer to spread low-entropy
+/// seeds across the whole range. Fixed, dependency-free and identical on the
+/// host and in wasm, so a value computed before a restart matches the one
+/// computed after it.
+fn seeded_unit_double(material: &[u8]) -> f64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in material {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^= hash >> 31;
+    // Top 53 bits -> exactly representable f64 in [0, 1).
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
 // -----------------------------------------------------------------------------
 // Current time — WASI-compatible
 // -----------------------------------------------------------------------------
@@ -1119,6 +1163,7 @@ pub fn agent_info() -> runtara_dsl::agent_meta::AgentInfo {
 
     let caps: &[&'static CapabilityMeta] = &[
         &__CAPABILITY_META_RANDOM_DOUBLE,
+        &__CAPABILITY_META_RANDOM_SEEDED,
         &__CAPABILITY_META_RANDOM_ARRAY,
         &__CAPABILITY_META_RETURN_INPUT_STRING,
         &__CAPABILITY_META_RETURN_INPUT,
@@ -1137,6 +1182,7 @@ pub fn agent_info() -> runtara_dsl::agent_meta::AgentInfo {
             "RandomDoubleInput",
             &__INPUT_META_RandomDoubleInput as &InputTypeMeta,
         ),
+        ("RandomSeededInput", &__INPUT_META_RandomSeededInput),
         (
             "ReturnRandomArrayInput",
             &__INPUT_META_ReturnRandomArrayInput,
@@ -1218,6 +1264,7 @@ impl Guest for Component {
         let value: serde_json::Value = serde_json::from_slice(&input).map_err(bad_json)?;
         let executor_result = match capability_id.as_str() {
             "random-double" => __executor_random_double(value),
+            "random-seeded" => __executor_random_seeded(value),
             "random-array" => __executor_random_array(value),
             "return-input-string" => __executor_return_input_string(value),
             "return-input" => __executor_return_input(value),
@@ -161,6 +161,10 @@ struct CapabilityArgs {
     /// Whether this capability requires rate limiting (external API calls)
     #[darling(default)]
     rate_limited: bool,
+    /// Maximum concurrent executions per process (memory-heavy capabilities).
+    /// Example: max_concurrency = 2
+    #[darling(default)]
+    max_concurrency: Option<u32>,
 
     // === Compensation hint attributes ===
     /// Capability ID that compensates (undoes) this capability's effects.
@@ -326,6 +330,10 @@ pub fn capability(attr: TokenStream, item: TokenStream) -> TokenStream {
     let side_effects = args.side_effects;
     let idempotent = args.idempotent.unwrap_or(!side_effects);
     let rate_limited = args.rate_limited;
+    let max_concurrency_token = match args.max_concurrency {
+        Some(limit) => quote! { Some(#limit) },
+        None => quote! { None },
+    };
     let module = args.module;
 
     // Generate metadata registration
@@ -524,6 +532,7 @@ pub fn capability(attr: TokenStream, item: TokenStream) -> TokenStream {
             compensation_hint: #compensation_hint_token,
             known_errors: #known_errors_token,
             tags: #tags_token,
+            max_concurrency: #max_concurrency_token,
         };
 
         #executor_wrapper
@@ -854,6 +863,10 @@ struct ConnectionContainerArgs {
     /// the generic bring-your-own types; default false)
     #[darling(default)]
     oauth_params_driven: Option<bool>,
+    /// Version of the parameter shape (default 1); bump when stored parameters
+    /// need a registered `ConnectionMigration` to stay valid
+    #[darling(default)]
+    schema_version: Option<u32>,
 }
 
 /// Derive macro for connection parameter structs
@@ -1246,6 +1259,8 @@ pub fn derive_connection_params(input: TokenStream) -> TokenStream {
         _ => quote! { None },
     };
 
+    let schema_version = args.schema_version.unwrap_or(1);
+
     let meta_ident = format_ident!("__CONNECTION_META_{}", struct_name);
 
     let expanded = quote! {
@@ -1261,6 +1276,7 @@ pub fn derive_connection_params(input: TokenStream) -> TokenStream {
             fields: &[#(#field_metas),*],
             sections: &[#(#section_metas),*],
             oauth_config: #oauth_config_token,
+            schema_version: #schema_version,
         };
 
     };
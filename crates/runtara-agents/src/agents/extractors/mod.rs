@@ -14,6 +14,7 @@ use std::collections::HashMap;
 pub mod connection_types;
 pub(crate) mod http_api_key;
 pub(crate) mod http_bearer;
+pub mod schema;
 pub(crate) mod sftp;
 
 #[cfg(test)]
@@ -22,6 +23,10 @@ mod tests;
 // Re-export extractors to ensure they're linked and registered
 pub use http_api_key::HttpApiKeyExtractor;
 pub use http_bearer::HttpBearerExtractor;
+pub use schema::{
+    CONNECTION_SCHEMA_MISMATCH, ConnectionSchemaMismatch, current_schema_version,
+    upgrade_connection_parameters,
+};
 
 // SFTP connection type is registered for schema purposes (doesn't implement HttpConnectionExtractor)
 #[allow(unused_imports)]
@@ -141,3 +146,39 @@ pub fn extract_http_config(
         get_http_extractor_ids()
     ))
 }
+
+/// Extract HTTP connection config from a stored connection
+///
+/// Like [`extract_http_config`], but first upgrades parameters stored under an
+/// older `schema_version` and reports parameters that do not fit the current
+/// shape as a structured `CONNECTION_SCHEMA_MISMATCH` error naming the
+/// connection, integration, missing/extra fields and both versions.
+pub fn extract_connection_http_config(
+    connection_id: &str,
+    integration_id: &str,
+    schema_version: u32,
+    parameters: Value,
+    rate_limit_config: Option<Value>,
+) -> Result<HttpConnectionConfig, String> {
+    let parameters =
+        upgrade_connection_parameters(connection_id, integration_id, schema_version, parameters)
+            .map_err(|mismatch| String::from(mismatch.into_agent_error()))?;
+
+    extract_http_config(integration_id, &parameters, rate_limit_config).map_err(|error| {
+        let Some(meta) = crate::registry::find_connection_type(integration_id) else {
+            return error;
+        };
+        let mismatch = schema::schema_mismatch(
+            meta,
+            connection_id,
+            schema_version,
+            &parameters,
+            Some(error.clone()),
+        );
+        if mismatch.missing_fields.is_empty() {
+            error
+        } else {
+            String::from(mismatch.into_agent_error())
+        }
+    })
+}
@@ -0,0 +1,204 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Connection parameter schema versioning
+//!
+//! Every connection type declares a `schema_version` for the shape of its
+//! parameters, and every stored connection records the version it was written
+//! with. When a type's shape changes (e.g. a new required field), stored
+//! parameters are upgraded lazily through the registered
+//! [`ConnectionMigration`] chain. Parameters that still do not fit the current
+//! shape are reported as a structured `CONNECTION_SCHEMA_MISMATCH` error
+//! instead of a bare deserialization message.
+
+use runtara_dsl::agent_meta::{ConnectionFieldMeta, ConnectionMigration, ConnectionTypeMeta};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::registry::{find_connection_type, get_all_connection_migrations};
+use crate::types::AgentError;
+
+/// Error code reported when stored parameters do not fit the current shape.
+pub const CONNECTION_SCHEMA_MISMATCH: &str = "CONNECTION_SCHEMA_MISMATCH";
+
+/// Details of stored connection parameters that do not match the current
+/// connection type schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConnectionSchemaMismatch {
+    /// Connection whose parameters are invalid
+    pub connection_id: String,
+    /// Integration (connection type) of the connection
+    pub integration_id: String,
+    /// Schema version the parameters were stored with
+    pub stored_version: u32,
+    /// Schema version the running connection type expects
+    pub current_version: u32,
+    /// Required fields absent from the stored parameters
+    pub missing_fields: Vec<String>,
+    /// Stored keys the connection type no longer declares
+    pub extra_fields: Vec<String>,
+    /// Underlying failure (migration or deserialization), when there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ConnectionSchemaMismatch {
+    /// Convert into a permanent [`AgentError`] carrying every detail as an attribute.
+    pub fn into_agent_error(self) -> AgentError {
+        let message = self.to_string();
+        let mut error = AgentError::permanent(CONNECTION_SCHEMA_MISMATCH, message)
+            .with_attr("connection_id", self.connection_id)
+            .with_attr("integration_id", self.integration_id)
+            .with_attr_value("stored_version", self.stored_version)
+            .with_attr_value("current_version", self.current_version)
+            .with_attr_value("missing_fields", self.missing_fields)
+            .with_attr_value("extra_fields", self.extra_fields);
+        if let Some(detail) = self.detail {
+            error = error.with_attr("detail", detail);
+        }
+        error
+    }
+}
+
+impl std::fmt::Display for ConnectionSchemaMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Connection '{}' ({}) parameters stored with schema v{} do not match schema v{}",
+            self.connection_id, self.integration_id, self.stored_version, self.current_version
+        )?;
+        if !self.missing_fields.is_empty() {
+            write!(f, "; missing fields: {}", self.missing_fields.join(", "))?;
+        }
+        if !self.extra_fields.is_empty() {
+            write!(f, "; unknown fields: {}", self.extra_fields.join(", "))?;
+        }
+        if let Some(detail) = &self.detail {
+            write!(f, "; {}", detail)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConnectionSchemaMismatch {}
+
+impl From<ConnectionSchemaMismatch> for AgentError {
+    fn from(mismatch: ConnectionSchemaMismatch) -> Self {
+        mismatch.into_agent_error()
+    }
+}
+
+/// Current parameter schema version for `integration_id`.
+///
+/// Unknown integrations report version 1, the version every type starts at.
+pub fn current_schema_version(integration_id: &str) -> u32 {
+    find_connection_type(integration_id)
+        .map(|meta| meta.schema_version)
+        .unwrap_or(1)
+}
+
+/// Upgrade parameters stored under `stored_version` to the current schema of
+/// `integration_id`.
+///
+/// Parameters already at (or beyond) the current version, and parameters of
+/// unknown integrations, are returned unchanged. Otherwise every registered
+/// migration between the two versions is applied in order, and the result must
+/// carry all required fields of the current shape.
+pub fn upgrade_connection_parameters(
+    connection_id: &str,
+    integration_id: &str,
+    stored_version: u32,
+    parameters: Value,
+) -> Result<Value, Box<ConnectionSchemaMismatch>> {
+    let Some(meta) = find_connection_type(integration_id) else {
+        return Ok(parameters);
+    };
+    let migrations: Vec<&ConnectionMigration> = get_all_connection_migrations().collect();
+    upgrade_with_migrations(meta, &migrations, connection_id, stored_version, parameters)
+}
+
+/// Build the mismatch report for `parameters` against the current shape of `meta`.
+pub fn schema_mismatch(
+    meta: &ConnectionTypeMeta,
+    connection_id: &str,
+    stored_version: u32,
+    parameters: &Value,
+    detail: Option<String>,
+) -> ConnectionSchemaMismatch {
+    let object = parameters.as_object();
+    let missing_fields = meta
+        .fields
+        .iter()
+        .filter(|field| is_mandatory(field))
+        .filter(|field| !is_present(object.and_then(|o| o.get(field.name)), field))
+        .map(|field| field.name.to_string())
+        .collect();
+    let extra_fields = object
+        .map(|o| {
+            o.keys()
+                .filter(|key| !meta.fields.iter().any(|field| field.name == key.as_str()))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ConnectionSchemaMismatch {
+        connection_id: connection_id.to_string(),
+        integration_id: meta.integration_id.to_string(),
+        stored_version,
+        current_version: meta.schema_version,
+        missing_fields,
+        extra_fields,
+        detail,
+    }
+}
+
+pub(crate) fn upgrade_with_migrations(
+    meta: &ConnectionTypeMeta,
+    migrations: &[&ConnectionMigration],
+    connection_id: &str,
+    stored_version: u32,
+    mut parameters: Value,
+) -> Result<Value, Box<ConnectionSchemaMismatch>> {
+    if stored_version >= meta.schema_version {
+        return Ok(parameters);
+    }
+
+    for version in stored_version..meta.schema_version {
+        let Some(migration) = migrations
+            .iter()
+            .find(|m| m.integration_id == meta.integration_id && m.from_version == version)
+        else {
+            continue;
+        };
+        parameters = (migration.migrate)(parameters.clone()).map_err(|reason| {
+            Box::new(schema_mismatch(
+                meta,
+                connection_id,
+                stored_version,
+                &parameters,
+                Some(format!("migration from v{} failed: {}", version, reason)),
+            ))
+        })?;
+    }
+
+    let mismatch = schema_mismatch(meta, connection_id, stored_version, &parameters, None);
+    if mismatch.missing_fields.is_empty() {
+        Ok(parameters)
+    } else {
+        Err(Box::new(mismatch))
+    }
+}
+
+/// A field the stored parameters must carry: structurally non-optional
+/// without a default, or explicitly marked required.
+fn is_mandatory(field: &ConnectionFieldMeta) -> bool {
+    field.is_required || (!field.is_optional && field.default_value.is_none())
+}
+
+fn is_present(value: Option<&Value>, field: &ConnectionFieldMeta) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::String(s)) if field.is_required => !s.trim().is_empty(),
+        Some(_) => true,
+    }
+}
@@ -527,3 +527,181 @@ fn test_extract_http_config_mcp_requires_url() {
     // Either fails at serde (missing field) or at the explicit url-required check.
     assert!(err.contains("url") || err.contains("Invalid mcp"), "{err}");
 }
+
+// ============================================================================
+// Schema versioning Tests
+// ============================================================================
+
+mod schema_versioning {
+    use super::*;
+    use runtara_dsl::agent_meta::{
+        ConnectionFieldBehavior, ConnectionFieldConditions, ConnectionFieldMeta,
+        ConnectionMigration, ConnectionTypeMeta,
+    };
+    use schema::{schema_mismatch, upgrade_with_migrations};
+
+    const fn field(name: &'static str, is_optional: bool) -> ConnectionFieldMeta {
+        ConnectionFieldMeta {
+            name,
+            type_name: "String",
+            is_optional,
+            display_name: None,
+            description: None,
+            placeholder: None,
+            order: 0,
+            default_value: None,
+            is_secret: false,
+            enum_values: None,
+            is_url: false,
+            is_required: false,
+            control: None,
+            section: None,
+            access: runtara_dsl::form::FieldAccessMode::ReadWrite,
+            conditions: ConnectionFieldConditions {
+                visible: None,
+                enabled: None,
+                required: None,
+            },
+            behavior: ConnectionFieldBehavior {
+                clearable: false,
+                requires_reauthorization: false,
+            },
+        }
+    }
+
+    /// v2 of the fixture added the required `region` field.
+    static FIELDS: &[ConnectionFieldMeta] = &[
+        field("api_key", false),
+        field("region", false),
+        field("timeout", true),
+    ];
+
+    static META: ConnectionTypeMeta = ConnectionTypeMeta {
+        integration_id: "schema_fixture",
+        display_name: "Schema fixture",
+        description: None,
+        category: None,
+        service_id: None,
+        auth_type: None,
+        fields: FIELDS,
+        sections: &[],
+        oauth_config: None,
+        schema_version: 2,
+    };
+
+    fn add_default_region(mut params: Value) -> Result<Value, String> {
+        let object = params
+            .as_object_mut()
+            .ok_or_else(|| "parameters are not an object".to_string())?;
+        object
+            .entry("region")
+            .or_insert_with(|| Value::String("us".to_string()));
+        Ok(params)
+    }
+
+    static ADD_REGION: ConnectionMigration = ConnectionMigration {
+        integration_id: "schema_fixture",
+        from_version: 1,
+        migrate: add_default_region,
+    };
+
+    #[test]
+    fn migration_fills_added_required_field() {
+        let upgraded = upgrade_with_migrations(
+            &META,
+            &[&ADD_REGION],
+            "conn-1",
+            1,
+            json!({"api_key": "secret"}),
+        )
+        .expect("migration should upgrade v1 parameters");
+
+        assert_eq!(upgraded, json!({"api_key": "secret", "region": "us"}));
+    }
+
+    #[test]
+    fn missing_migration_reports_schema_mismatch() {
+        let mismatch = upgrade_with_migrations(
+            &META,
+            &[],
+            "conn-1",
+            1,
+            json!({"api_key": "secret", "legacy_host": "old.example.com"}),
+        )
+        .expect_err("v1 parameters lack the new required field");
+
+        assert_eq!(mismatch.connection_id, "conn-1");
+        assert_eq!(mismatch.integration_id, "schema_fixture");
+        assert_eq!(mismatch.stored_version, 1);
+        assert_eq!(mismatch.current_version, 2);
+        assert_eq!(mismatch.missing_fields, vec!["region".to_string()]);
+        assert_eq!(mismatch.extra_fields, vec!["legacy_host".to_string()]);
+    }
+
+    #[test]
+    fn current_version_parameters_are_left_untouched() {
+        let params = json!({"api_key": "secret"});
+        let result =
+            upgrade_with_migrations(&META, &[&ADD_REGION], "conn-1", 2, params.clone()).unwrap();
+        assert_eq!(result, params);
+    }
+
+    #[test]
+    fn failing_migration_reports_detail() {
+        let mismatch =
+            upgrade_with_migrations(&META, &[&ADD_REGION], "conn-1", 1, json!("not-an-object"))
+                .expect_err("migration should fail on non-object parameters");
+
+        assert_eq!(
+            mismatch.detail.as_deref(),
+            Some("migration from v1 failed: parameters are not an object")
+        );
+    }
+
+    #[test]
+    fn mismatch_converts_to_structured_agent_error() {
+        let error = schema_mismatch(&META, "conn-1", 1, &json!({"api_key": "secret"}), None)
+            .into_agent_error();
+
+        assert_eq!(error.code, CONNECTION_SCHEMA_MISMATCH);
+        assert_eq!(error.attributes["connection_id"], "conn-1");
+        assert_eq!(error.attributes["integration_id"], "schema_fixture");
+        assert_eq!(error.attributes["missing_fields"], json!(["region"]));
+        assert_eq!(error.attributes["extra_fields"], json!([]));
+        assert_eq!(error.attributes["stored_version"], 1);
+        assert_eq!(error.attributes["current_version"], 2);
+    }
+
+    #[test]
+    fn connection_extraction_reports_missing_fields_as_schema_mismatch() {
+        let err = extract_connection_http_config(
+            "conn-7",
+            "http_bearer",
+            current_schema_version("http_bearer"),
+            json!({"base_url": "https://api.example.com"}),
+            None,
+        )
+        .expect_err("token is required");
+
+        let error: Value = serde_json::from_str(&err).expect("structured error");
+        assert_eq!(error["code"], CONNECTION_SCHEMA_MISMATCH);
+        assert_eq!(error["attributes"]["connection_id"], "conn-7");
+        assert_eq!(error["attributes"]["missing_fields"], json!(["token"]));
+    }
+
+    #[test]
+    fn connection_extraction_passes_valid_parameters_through() {
+        let config = extract_connection_http_config(
+            "conn-7",
+            "http_bearer",
+            1,
+            json!({"token": "abc", "base_url": "https://api.example.com"}),
+            None,
+        )
+        .expect("valid parameters should extract");
+        assert_eq!(
+            config.headers.get("Authorization"),
+            Some(&"Bearer abc".to_string())
+        );
+    }
+}
@@ -147,6 +147,7 @@ fn default_true() -> bool {
     module_description = "Parse Excel and OpenDocument spreadsheets (XLSX, XLS, XLSB, ODS)",
     display_name = "Parse Spreadsheet",
     description = "Parse a spreadsheet sheet into a JSON array of objects or arrays. Supports XLSX, XLS, XLSB, and ODS formats.",
+    max_concurrency = 4,
     errors(
         permanent("XLSX_DECODE_ERROR", "Failed to decode base64 or file data"),
         permanent("XLSX_PARSE_ERROR", "Failed to open or parse the spreadsheet file"),
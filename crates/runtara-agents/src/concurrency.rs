@@ -0,0 +1,213 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Per-capability concurrency limits for native agent execution.
+//!
+//! Every workflow's native capability calls (`/api/internal/agents`) run in
+//! the same host process, so a memory-heavy capability can OOM the host when a
+//! parallel Split fans out over it. A capability that declares
+//! `max_concurrency` (or is listed in `RUNTARA_CAPABILITY_CONCURRENCY`) gets a
+//! process-wide semaphore; callers beyond the limit block until a permit frees
+//! up, and the time spent waiting is reported back with the result.
+
+use runtara_dsl::agent_meta::{
+    CAPABILITY_CONCURRENCY_ENV, effective_max_concurrency, parse_capability_concurrency_overrides,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+type CapabilityKey = (String, String);
+
+/// Counting semaphore for blocking (non-async) executors.
+struct Semaphore {
+    limit: u32,
+    in_use: Mutex<u32>,
+    released: Condvar,
+}
+
+impl Semaphore {
+    fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            in_use: Mutex::new(0),
+            released: Condvar::new(),
+        }
+    }
+
+    fn acquire(self: &Arc<Self>) -> SemaphorePermit {
+        let mut in_use = self.in_use.lock().unwrap_or_else(|e| e.into_inner());
+        while *in_use >= self.limit {
+            in_use = self
+                .released
+                .wait(in_use)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        *in_use += 1;
+        SemaphorePermit {
+            semaphore: Arc::clone(self),
+        }
+    }
+}
+
+struct SemaphorePermit {
+    semaphore: Arc<Semaphore>,
+}
+
+impl Drop for SemaphorePermit {
+    fn drop(&mut self) {
+        let mut in_use = self
+            .semaphore
+            .in_use
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        *in_use -= 1;
+        self.semaphore.released.notify_one();
+    }
+}
+
+/// Held for the duration of one capability execution.
+pub struct CapabilityPermit {
+    _permit: Option<SemaphorePermit>,
+    queue_wait: Duration,
+}
+
+impl CapabilityPermit {
+    /// Time spent waiting for a free slot (zero for unlimited capabilities).
+    pub fn queue_wait(&self) -> Duration {
+        self.queue_wait
+    }
+}
+
+/// Process-wide registry of per-capability semaphores.
+pub struct CapabilityLimiter {
+    overrides: HashMap<CapabilityKey, u32>,
+    semaphores: Mutex<HashMap<CapabilityKey, Arc<Semaphore>>>,
+}
+
+impl CapabilityLimiter {
+    /// Create a limiter with explicit `(module, capability)` overrides.
+    pub fn new(overrides: HashMap<CapabilityKey, u32>) -> Self {
+        Self {
+            overrides,
+            semaphores: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a limiter from `RUNTARA_CAPABILITY_CONCURRENCY`.
+    pub fn from_env() -> Self {
+        let spec = std::env::var(CAPABILITY_CONCURRENCY_ENV).unwrap_or_default();
+        Self::new(parse_capability_concurrency_overrides(&spec))
+    }
+
+    /// The limiter shared by every caller in this process.
+    pub fn global() -> &'static CapabilityLimiter {
+        static GLOBAL: OnceLock<CapabilityLimiter> = OnceLock::new();
+        GLOBAL.get_or_init(Self::from_env)
+    }
+
+    /// Effective limit for a capability given its declared `max_concurrency`.
+    pub fn limit(&self, module: &str, capability_id: &str, declared: Option<u32>) -> Option<u32> {
+        effective_max_concurrency(&self.overrides, module, capability_id, declared)
+    }
+
+    /// Block until the capability may run. Unlimited capabilities return
+    /// immediately without touching a semaphore.
+    pub fn acquire(
+        &self,
+        module: &str,
+        capability_id: &str,
+        declared: Option<u32>,
+    ) -> CapabilityPermit {
+        let Some(limit) = self.limit(module, capability_id, declared) else {
+            return CapabilityPermit {
+                _permit: None,
+                queue_wait: Duration::ZERO,
+            };
+        };
+        let semaphore = {
+            let mut semaphores = self.semaphores.lock().unwrap_or_else(|e| e.into_inner());
+            Arc::clone(
+                semaphores
+                    .entry((module.to_lowercase(), capability_id.to_string()))
+                    .or_insert_with(|| Arc::new(Semaphore::new(limit))),
+            )
+        };
+        let started = Instant::now();
+        let permit = semaphore.acquire();
+        CapabilityPermit {
+            _permit: Some(permit),
+            queue_wait: started.elapsed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::thread;
+
+    fn limiter(spec: &str) -> CapabilityLimiter {
+        CapabilityLimiter::new(parse_capability_concurrency_overrides(spec))
+    }
+
+    #[test]
+    fn override_wins_over_declared_limit() {
+        let limiter = limiter("xlsx.from-xlsx=2,compression.extract-archive=0");
+        assert_eq!(limiter.limit("xlsx", "from-xlsx", Some(4)), Some(2));
+        assert_eq!(
+            limiter.limit("compression", "extract-archive", Some(4)),
+            None
+        );
+        assert_eq!(limiter.limit("sftp", "sftp-list-files", Some(3)), Some(3));
+        assert_eq!(limiter.limit("sftp", "sftp-list-files", None), None);
+    }
+
+    #[test]
+    fn unlimited_capability_does_not_queue() {
+        let permit = limiter("").acquire("sftp", "sftp-list-files", None);
+        assert_eq!(permit.queue_wait(), Duration::ZERO);
+    }
+
+    #[test]
+    fn limit_caps_concurrent_holders() {
+        let limiter = Arc::new(limiter(""));
+        let running = Arc::new(AtomicU32::new(0));
+        let peak = Arc::new(AtomicU32::new(0));
+
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let limiter = Arc::clone(&limiter);
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                thread::spawn(move || {
+                    let _permit = limiter.acquire("xlsx", "from-xlsx", Some(2));
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn queued_caller_reports_wait_time() {
+        let limiter = Arc::new(limiter(""));
+        let first = limiter.acquire("xlsx", "from-xlsx", Some(1));
+
+        let waiter = {
+            let limiter = Arc::clone(&limiter);
+            thread::spawn(move || limiter.acquire("xlsx", "from-xlsx", Some(1)).queue_wait())
+        };
+        thread::sleep(Duration::from_millis(50));
+        drop(first);
+
+        assert!(waiter.join().unwrap() >= Duration::from_millis(40));
+    }
+}
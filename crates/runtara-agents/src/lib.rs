@@ -38,6 +38,8 @@ pub mod connections;
 pub mod s3_client;
 
 // Re-export shared infrastructure
+#[cfg(not(all(target_family = "wasm", not(target_os = "wasi"))))]
+pub mod concurrency;
 pub mod registry;
 mod static_registry;
 
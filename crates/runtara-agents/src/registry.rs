@@ -6,14 +6,24 @@
 use runtara_dsl::agent_meta::CapabilityExecutor;
 use runtara_dsl::agent_meta::{
     AgentInfo, AgentModuleConfig, AgentValidationError, BUILTIN_AGENT_MODULES, CapabilityField,
-    CapabilityMeta, ConnectionTypeMeta, InputTypeMeta, OutputTypeMeta, canonical_agent_id,
-    capability_to_api, input_field_to_api,
+    CapabilityMeta, ConnectionMigration, ConnectionTypeMeta, InputTypeMeta, OutputTypeMeta,
+    canonical_agent_id, capability_to_api, input_field_to_api,
 };
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 
 use crate::static_registry;
 
+/// Result of a native capability execution, with the time it spent queued
+/// behind the capability's concurrency limit.
+#[cfg(not(all(target_family = "wasm", not(target_os = "wasi"))))]
+pub struct CapabilityExecution {
+    /// Capability output or error message.
+    pub result: Result<Value, String>,
+    /// Time spent waiting for a concurrency slot before executing.
+    pub queue_wait: std::time::Duration,
+}
+
 /// Execute an agent capability synchronously.
 #[cfg(not(all(target_family = "wasm", not(target_os = "wasi"))))]
 pub fn execute_capability(
@@ -21,20 +31,42 @@ pub fn execute_capability(
     capability_id: &str,
     step_inputs: Value,
 ) -> Result<Value, String> {
+    execute_capability_queued(agent_id, capability_id, step_inputs).result
+}
+
+/// Execute an agent capability synchronously, first waiting for a slot when
+/// the capability has a concurrency limit (see [`crate::concurrency`]).
+#[cfg(not(all(target_family = "wasm", not(target_os = "wasi"))))]
+pub fn execute_capability_queued(
+    agent_id: &str,
+    capability_id: &str,
+    step_inputs: Value,
+) -> CapabilityExecution {
     let agent_lower = agent_id.to_lowercase();
 
     for registration in static_registry::CAPABILITY_REGISTRATIONS {
         if registration.executor.module == agent_lower
             && registration.executor.capability_id == capability_id
         {
-            return (registration.executor.execute)(step_inputs);
+            let permit = crate::concurrency::CapabilityLimiter::global().acquire(
+                &agent_lower,
+                capability_id,
+                registration.meta.max_concurrency,
+            );
+            return CapabilityExecution {
+                result: (registration.executor.execute)(step_inputs),
+                queue_wait: permit.queue_wait(),
+            };
         }
     }
 
-    Err(format!(
-        "Unknown capability: {}:{}",
-        agent_id, capability_id
-    ))
+    CapabilityExecution {
+        result: Err(format!(
+            "Unknown capability: {}:{}",
+            agent_id, capability_id
+        )),
+        queue_wait: std::time::Duration::ZERO,
+    }
 }
 
 /// Metadata-only builds do not link agent executors.
@@ -80,6 +112,11 @@ pub fn get_all_connection_types() -> impl Iterator<Item = &'static ConnectionTyp
     static_registry::CONNECTION_TYPES.iter().copied()
 }
 
+/// Get all statically registered connection parameter migrations.
+pub fn get_all_connection_migrations() -> impl Iterator<Item = &'static ConnectionMigration> {
+    static_registry::CONNECTION_MIGRATIONS.iter().copied()
+}
+
 /// Find input type metadata by type name.
 pub fn find_input_type(type_name: &str) -> Option<&'static InputTypeMeta> {
     get_all_input_types().find(|m| m.type_name == type_name)
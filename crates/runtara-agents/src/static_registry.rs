@@ -9,7 +9,8 @@
 #[cfg(not(all(target_family = "wasm", not(target_os = "wasi"))))]
 use runtara_dsl::agent_meta::CapabilityExecutor;
 use runtara_dsl::agent_meta::{
-    AgentModuleConfig, CapabilityMeta, ConnectionTypeMeta, InputTypeMeta, OutputTypeMeta,
+    AgentModuleConfig, CapabilityMeta, ConnectionMigration, ConnectionTypeMeta, InputTypeMeta,
+    OutputTypeMeta,
 };
 
 #[derive(Clone, Copy)]
@@ -162,6 +163,11 @@ pub static CONNECTION_TYPES: &[&ConnectionTypeMeta] = &[
     &crate::extractors::connection_types::__CONNECTION_META_McpConnectionParams,
 ];
 
+/// Stored-parameter upgrades, one entry per `(integration_id, from_version)`.
+/// Add an entry whenever a connection type bumps `schema_version` in a way
+/// that older stored parameters cannot satisfy.
+pub static CONNECTION_MIGRATIONS: &[&ConnectionMigration] = &[];
+
 #[cfg(feature = "native")]
 const XLSX_AGENT_MODULE: AgentModuleConfig = AgentModuleConfig {
     id: "xlsx",
@@ -145,6 +145,9 @@ pub trait RuntimeHost: Send + Sync {
         checkpoint_id: String,
         state: Vec<u8>,
     ) -> Result<RuntimeCheckpointResult, String>;
+    /// Delete the checkpoints matching any prefix in the JSON string array
+    /// `prefixes`, keeping the resume checkpoint; returns the count deleted.
+    async fn compact_checkpoints(&self, prefixes: Vec<u8>) -> Result<u64, String>;
     /// React to a pending signal reported by a checkpoint result; true when a
     /// stop-like signal was handled and the guest should return.
     async fn handle_checkpoint_signal(&self, signal_type: String) -> Result<bool, String>;
@@ -354,6 +357,14 @@ pub fn add_runtime_to_linker(linker: &mut Linker<WorkflowState>) -> anyhow::Resu
         },
     )?;
 
+    inst.func_wrap_async(
+        "compact-checkpoints",
+        |mut store: StoreContextMut<'_, WorkflowState>, (prefixes,): (Vec<u8>,)| {
+            let host = require_host(&mut store);
+            Box::new(async move { Ok((host?.compact_checkpoints(prefixes).await,)) })
+        },
+    )?;
+
     inst.func_wrap_async(
         "handle-checkpoint-signal",
         |mut store: StoreContextMut<'_, WorkflowState>, (signal_type,): (String,)| {
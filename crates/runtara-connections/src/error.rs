@@ -24,6 +24,11 @@ pub enum ConnectionsError {
 
     #[error("Internal error: {0}")]
     Internal(String),
+
+    /// Stored parameters do not fit the connection type's current schema and
+    /// no registered migration could upgrade them.
+    #[error("{0}")]
+    SchemaMismatch(Box<runtara_agents::extractors::ConnectionSchemaMismatch>),
 }
 
 impl ConnectionsError {
@@ -39,6 +44,7 @@ impl ConnectionsError {
             Self::OAuth(_) => StatusCode::BAD_REQUEST,
             Self::AuthResolution(_) => StatusCode::BAD_GATEWAY,
             Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::SchemaMismatch(_) => StatusCode::UNPROCESSABLE_ENTITY,
         }
     }
 }
@@ -208,10 +208,12 @@ impl ConnectionsFacade {
         id: &str,
         tenant_id: &str,
     ) -> Result<Option<ConnectionWithParameters>, ConnectionsError> {
-        self.repo()
+        let connection = self
+            .repo()
             .get_with_parameters(id, tenant_id)
             .await
-            .map_err(ConnectionsError::Database)
+            .map_err(ConnectionsError::Database)?;
+        self.upgrade_schema(connection).await
     }
 
     /// Get the default connection for an agent/operator, including secret parameters.
@@ -222,10 +224,12 @@ impl ConnectionsFacade {
         tenant_id: &str,
         default_for: &str,
     ) -> Result<Option<ConnectionWithParameters>, ConnectionsError> {
-        self.repo()
+        let connection = self
+            .repo()
             .get_default_connection_with_parameters(tenant_id, default_for)
             .await
-            .map_err(ConnectionsError::Database)
+            .map_err(ConnectionsError::Database)?;
+        self.upgrade_schema(connection).await
     }
 
     /// Ensure a tenant has a default connection for an agent/operator.
@@ -281,10 +285,12 @@ impl ConnectionsFacade {
         &self,
         id: &str,
     ) -> Result<Option<ConnectionWithParameters>, ConnectionsError> {
-        self.repo()
+        let connection = self
+            .repo()
             .get_channel_connection(id)
             .await
-            .map_err(ConnectionsError::Database)
+            .map_err(ConnectionsError::Database)?;
+        self.upgrade_schema(connection).await
     }
 
     /// Get the default file storage connection for a tenant.
@@ -294,10 +300,69 @@ impl ConnectionsFacade {
         &self,
         tenant_id: &str,
     ) -> Result<Option<ConnectionWithParameters>, ConnectionsError> {
-        self.repo()
+        let connection = self
+            .repo()
             .get_default_file_storage(tenant_id)
             .await
-            .map_err(ConnectionsError::Database)
+            .map_err(ConnectionsError::Database)?;
+        self.upgrade_schema(connection).await
+    }
+
+    /// Lazily upgrade parameters stored under an older schema version.
+    ///
+    /// Applies the registered connection migrations and persists the result so
+    /// the upgrade runs once per connection. A failed write-back is logged and
+    /// retried on the next fetch; the upgraded parameters are still returned.
+    async fn upgrade_schema(
+        &self,
+        connection: Option<ConnectionWithParameters>,
+    ) -> Result<Option<ConnectionWithParameters>, ConnectionsError> {
+        let Some(mut connection) = connection else {
+            return Ok(None);
+        };
+        let Some(integration_id) = connection.integration_id.clone() else {
+            return Ok(Some(connection));
+        };
+        let current = runtara_agents::extractors::current_schema_version(&integration_id);
+        if connection.schema_version >= current {
+            return Ok(Some(connection));
+        }
+
+        let stored = connection
+            .connection_parameters
+            .take()
+            .unwrap_or(Value::Null);
+        let upgraded = runtara_agents::extractors::upgrade_connection_parameters(
+            &connection.id,
+            &integration_id,
+            connection.schema_version,
+            stored,
+        )
+        .map_err(ConnectionsError::SchemaMismatch)?;
+
+        if let Some(tenant_id) = connection.tenant_id.as_deref()
+            && let Err(error) = self
+                .repo()
+                .update_parameters_schema_version(
+                    &connection.id,
+                    tenant_id,
+                    &upgraded,
+                    connection.schema_version,
+                    current,
+                )
+                .await
+        {
+            tracing::warn!(
+                connection_id = %connection.id,
+                integration_id = %integration_id,
+                error = %error,
+                "Failed to persist upgraded connection parameters"
+            );
+        }
+
+        connection.connection_parameters = Some(upgraded);
+        connection.schema_version = current;
+        Ok(Some(connection))
     }
 
     // ── Validation ──────────────────────────────────────────────────────
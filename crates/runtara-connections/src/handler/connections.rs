@@ -521,6 +521,7 @@ fn meta_to_dto(meta: &runtara_dsl::agent_meta::ConnectionTypeMeta) -> Connection
             token_url: c.token_url.to_string(),
             default_scopes: c.default_scopes.to_string(),
         }),
+        schema_version: meta.schema_version,
     }
 }
 
@@ -762,6 +763,9 @@ fn connection_resource_error(error: ConnectionsError) -> (StatusCode, Json<Value
         ConnectionsError::Validation(_) => "INVALID_RESOURCE_REQUEST",
         ConnectionsError::NotFound(_) => "CONNECTION_NOT_FOUND",
         ConnectionsError::AuthResolution(_) => "RESOURCE_DISCOVERY_FAILED",
+        ConnectionsError::SchemaMismatch(_) => {
+            runtara_agents::extractors::CONNECTION_SCHEMA_MISMATCH
+        }
         _ => "INTERNAL_ERROR",
     };
     let message = if status == StatusCode::INTERNAL_SERVER_ERROR {
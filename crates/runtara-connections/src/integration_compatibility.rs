@@ -133,6 +133,7 @@ mod tests {
                 compensation_hint: None,
                 known_errors: vec![],
                 tags: vec![],
+                max_concurrency: None,
             }],
         };
         let catalog = AgentCatalog::from_agents(vec![slack]);
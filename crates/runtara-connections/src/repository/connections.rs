@@ -21,6 +21,13 @@ fn parse_rate_limit_config(value: Option<serde_json::Value>) -> Option<RateLimit
     value.and_then(|v| serde_json::from_value(v).ok())
 }
 
+/// Parameter schema version new connections of `integration_id` are stored with
+fn schema_version_for(integration_id: Option<&str>) -> i32 {
+    integration_id
+        .map(runtara_agents::extractors::current_schema_version)
+        .unwrap_or(1) as i32
+}
+
 pub struct ConnectionRepository {
     pool: PgPool,
     cipher: Arc<dyn CredentialCipher>,
@@ -160,8 +167,8 @@ impl ConnectionRepository {
         sqlx::query(
             r#"
             INSERT INTO connection_data_entity
-            (id, tenant_id, title, connection_subtype, connection_parameters, integration_id, valid_until, status, rate_limit_config, is_default_file_storage)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            (id, tenant_id, title, connection_subtype, connection_parameters, integration_id, valid_until, status, rate_limit_config, is_default_file_storage, schema_version)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
         )
         .bind(connection_id)
@@ -174,6 +181,7 @@ impl ConnectionRepository {
         .bind(status.as_str())
         .bind(rate_limit_json.as_ref())
         .bind(request.is_default_file_storage.unwrap_or(false))
+        .bind(schema_version_for(request.integration_id.as_deref()))
         .execute(&mut *tx)
         .await?;
 
@@ -759,10 +767,11 @@ impl ConnectionRepository {
                 Option<String>,            // connection_subtype
                 Option<serde_json::Value>, // connection_parameters
                 Option<serde_json::Value>, // rate_limit_config
+                i32,                       // schema_version
             ),
         >(
             r#"
-            SELECT id, tenant_id, integration_id, connection_subtype, connection_parameters, rate_limit_config
+            SELECT id, tenant_id, integration_id, connection_subtype, connection_parameters, rate_limit_config, schema_version
             FROM connection_data_entity
             WHERE id = $1 AND tenant_id = $2
             "#,
@@ -781,6 +790,7 @@ impl ConnectionRepository {
                     connection_subtype,
                     connection_parameters,
                     rate_limit_config,
+                    schema_version,
                 )| {
                     Ok::<_, sqlx::Error>(ConnectionWithParameters {
                         id,
@@ -789,6 +799,7 @@ impl ConnectionRepository {
                         connection_subtype,
                         connection_parameters: self.unseal(connection_parameters)?,
                         rate_limit_config,
+                        schema_version: u32::try_from(schema_version).unwrap_or(1),
                     })
                 },
             )
@@ -914,10 +925,11 @@ impl ConnectionRepository {
                 Option<String>,            // connection_subtype
                 Option<serde_json::Value>, // connection_parameters
                 Option<serde_json::Value>, // rate_limit_config
+                i32,                       // schema_version
             ),
         >(
             r#"
-            SELECT id, tenant_id, integration_id, connection_subtype, connection_parameters, rate_limit_config
+            SELECT id, tenant_id, integration_id, connection_subtype, connection_parameters, rate_limit_config, schema_version
             FROM connection_data_entity
             WHERE id = $1
             "#,
@@ -935,6 +947,7 @@ impl ConnectionRepository {
                     connection_subtype,
                     connection_parameters,
                     rate_limit_config,
+                    schema_version,
                 )| {
                     Ok::<_, sqlx::Error>(ConnectionWithParameters {
                         id,
@@ -943,6 +956,7 @@ impl ConnectionRepository {
                         connection_subtype,
                         connection_parameters: self.unseal(connection_parameters)?,
                         rate_limit_config,
+                        schema_version: u32::try_from(schema_version).unwrap_or(1),
                     })
                 },
             )
@@ -967,10 +981,11 @@ impl ConnectionRepository {
                 Option<String>,            // connection_subtype
                 Option<serde_json::Value>, // connection_parameters
                 Option<serde_json::Value>, // rate_limit_config
+                i32,                       // schema_version
             ),
         >(
             r#"
-            SELECT id, tenant_id, integration_id, connection_subtype, connection_parameters, rate_limit_config
+            SELECT id, tenant_id, integration_id, connection_subtype, connection_parameters, rate_limit_config, schema_version
             FROM connection_data_entity
             WHERE tenant_id = $1 AND is_default_file_storage = TRUE
             LIMIT 1
@@ -989,6 +1004,7 @@ impl ConnectionRepository {
                     connection_subtype,
                     connection_parameters,
                     rate_limit_config,
+                    schema_version,
                 )| {
                     Ok::<_, sqlx::Error>(ConnectionWithParameters {
                         id,
@@ -997,6 +1013,7 @@ impl ConnectionRepository {
                         connection_subtype,
                         connection_parameters: self.unseal(connection_parameters)?,
                         rate_limit_config,
+                        schema_version: u32::try_from(schema_version).unwrap_or(1),
                     })
                 },
             )
@@ -1033,6 +1050,39 @@ impl ConnectionRepository {
         Ok(result.rows_affected())
     }
 
+    /// Persist parameters upgraded to a newer schema version by a registered
+    /// connection migration. Guarded on the previously stored version so two
+    /// concurrent lazy upgrades cannot clobber each other.
+    pub async fn update_parameters_schema_version(
+        &self,
+        id: &str,
+        tenant_id: &str,
+        parameters: &serde_json::Value,
+        from_version: u32,
+        to_version: u32,
+    ) -> Result<u64, sqlx::Error> {
+        let sealed = self
+            .seal(Some(parameters))?
+            .unwrap_or(serde_json::Value::Null);
+        let result = sqlx::query(
+            r#"
+            UPDATE connection_data_entity
+            SET connection_parameters = $3,
+                schema_version = $5,
+                updated_at = NOW()
+            WHERE id = $1 AND tenant_id = $2 AND schema_version = $4
+            "#,
+        )
+        .bind(id)
+        .bind(tenant_id)
+        .bind(&sealed)
+        .bind(from_version as i32)
+        .bind(to_version as i32)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     /// Update only the connection status (parameters untouched). Used to flip a
     /// connection to `REQUIRES_RECONNECTION` when its OAuth grant is dead.
     pub async fn update_status(
@@ -1209,6 +1259,8 @@ pub struct ConnectionWithParameters {
     pub connection_subtype: Option<String>,
     pub connection_parameters: Option<serde_json::Value>,
     pub rate_limit_config: Option<serde_json::Value>,
+    /// Parameter schema version the stored parameters were written with
+    pub schema_version: u32,
 }
 
 impl ConnectionWithParameters {
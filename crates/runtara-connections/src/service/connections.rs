@@ -1410,6 +1410,7 @@ mod tests {
             fields: FIELDS,
             sections: &[],
             oauth_config: None,
+            schema_version: 1,
         };
         let patch = ConnectionParameterPatch {
             set: HashMap::new(),
@@ -937,6 +937,8 @@ pub struct ConnectionTypeDto {
     /// OAuth2 configuration (only for auth_type = oauth2_authorization_code)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oauth_config: Option<OAuthConfigDto>,
+    /// Current version of this connection type's parameter schema
+    pub schema_version: u32,
 }
 
 /// Response for listing all connection types
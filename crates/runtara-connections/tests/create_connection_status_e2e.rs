@@ -61,7 +61,8 @@ impl PgFixture {
                 rate_limit_config JSONB,
                 is_default_file_storage BOOLEAN DEFAULT FALSE,
                 created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                schema_version INTEGER NOT NULL DEFAULT 1
             )
             "#,
         )
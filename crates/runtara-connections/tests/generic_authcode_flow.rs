@@ -53,7 +53,8 @@ impl PgFixture {
                 is_default_file_storage BOOLEAN NOT NULL DEFAULT FALSE,
                 created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
                 valid_until TIMESTAMPTZ DEFAULT NULL,
-                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                schema_version INTEGER NOT NULL DEFAULT 1
             )
             "#,
         )
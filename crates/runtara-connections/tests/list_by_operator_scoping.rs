@@ -58,7 +58,8 @@ async fn create_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
             integration_id VARCHAR(255) DEFAULT NULL,
             status VARCHAR(50) NOT NULL DEFAULT 'UNKNOWN',
             rate_limit_config JSONB DEFAULT NULL,
-            is_default_file_storage BOOLEAN NOT NULL DEFAULT FALSE
+            is_default_file_storage BOOLEAN NOT NULL DEFAULT FALSE,
+            schema_version INTEGER NOT NULL DEFAULT 1
         )
         "#,
     )
@@ -43,7 +43,8 @@ impl PgFixture {
                 integration_id VARCHAR(255),
                 connection_subtype VARCHAR(255),
                 connection_parameters JSONB,
-                rate_limit_config JSONB
+                rate_limit_config JSONB,
+                schema_version INTEGER NOT NULL DEFAULT 1
             )
             "#,
         )
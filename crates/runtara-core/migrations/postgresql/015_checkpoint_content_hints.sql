@@ -0,0 +1,10 @@
+-- Migration: content hints for checkpoint state.
+-- Checkpoint state is opaque bytes; the writer may now tag it with a MIME-ish
+-- content type and a shape so browsing tools can pick a renderer (and build a
+-- structured preview) without guessing. Both columns are nullable: rows
+-- written before this migration carry no hints and are sniffed on read.
+ALTER TABLE checkpoints ADD COLUMN content_type TEXT;
+ALTER TABLE checkpoints ADD COLUMN shape TEXT;
+
+COMMENT ON COLUMN checkpoints.content_type IS 'Writer-supplied content type of state (application/json, application/octet-stream); NULL = unknown, sniff on read';
+COMMENT ON COLUMN checkpoints.shape IS 'Writer-supplied shape tag (step_output, steps_context, durable_result, scenario_vars); NULL = unknown';
@@ -0,0 +1,7 @@
+-- Migration: status_version counter for long-poll status reads.
+-- Bumped on every write that changes an instance's status or checkpoint_id,
+-- so a client that passes back the version it last saw can wait for the next
+-- transition without missing one that happened between polls.
+ALTER TABLE instances ADD COLUMN status_version BIGINT NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN instances.status_version IS 'Incremented on every status or checkpoint_id change; clients long-poll against it';
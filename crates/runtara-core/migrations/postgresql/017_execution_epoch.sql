@@ -0,0 +1,7 @@
+-- Migration: execution epoch (fencing token) per instance.
+-- Bumped on every successful registration. The SDK sends the epoch it was
+-- issued with each write, and core refuses writes carrying an older epoch,
+-- so a superseded container cannot interleave state with its replacement.
+ALTER TABLE instances ADD COLUMN execution_epoch BIGINT NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN instances.execution_epoch IS 'Incremented on every registration; writes carrying an older epoch are refused as FENCED';
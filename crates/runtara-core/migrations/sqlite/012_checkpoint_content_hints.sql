@@ -0,0 +1,4 @@
+-- Migration: content hints for checkpoint state. See the PostgreSQL 015
+-- migration for the column semantics.
+ALTER TABLE checkpoints ADD COLUMN content_type TEXT;
+ALTER TABLE checkpoints ADD COLUMN shape TEXT;
@@ -0,0 +1,3 @@
+-- Migration: status_version counter for long-poll status reads. See the
+-- PostgreSQL 016 migration for the column semantics.
+ALTER TABLE instances ADD COLUMN status_version INTEGER NOT NULL DEFAULT 0;
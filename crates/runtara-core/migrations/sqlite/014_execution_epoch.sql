@@ -0,0 +1,3 @@
+-- Migration: execution epoch (fencing token) per instance. See the
+-- PostgreSQL 017 migration for the column semantics.
+ALTER TABLE instances ADD COLUMN execution_epoch INTEGER NOT NULL DEFAULT 0;
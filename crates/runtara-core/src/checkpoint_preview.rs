@@ -0,0 +1,353 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Structured previews of checkpoint state for browsing tools.
+//!
+//! Checkpoint state is opaque bytes, but almost everything codegen writes is
+//! `serde_json` of a handful of known shapes. Writers may tag a checkpoint
+//! with a content type and shape (see [`crate::persistence::CheckpointHints`]);
+//! checkpoints written without hints are sniffed here on a best-effort basis.
+//!
+//! A preview never contains the full state: values are cut off at a depth
+//! limit, long strings and arrays are truncated, and values under
+//! credential-like keys are replaced with [`REDACTED`].
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Content type for JSON state.
+pub const CONTENT_TYPE_JSON: &str = "application/json";
+
+/// Content type for state that is not (or not known to be) JSON.
+pub const CONTENT_TYPE_OCTET_STREAM: &str = "application/octet-stream";
+
+/// Shape tags writers are expected to use.
+pub const KNOWN_SHAPES: &[&str] = &[
+    "step_output",
+    "steps_context",
+    "durable_result",
+    "scenario_vars",
+];
+
+/// Placeholder substituted for redacted values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Key fragments (lowercase, separators stripped) whose values are redacted.
+const SENSITIVE_KEY_FRAGMENTS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "apikey",
+    "authorization",
+    "credential",
+    "privatekey",
+    "connection",
+];
+
+/// Guess the content type of unhinted state: valid UTF-8 JSON is
+/// `application/json`, anything else is `application/octet-stream`.
+pub fn sniff_content_type(state: &[u8]) -> &'static str {
+    if std::str::from_utf8(state).is_ok()
+        && serde_json::from_slice::<serde::de::IgnoredAny>(state).is_ok()
+    {
+        CONTENT_TYPE_JSON
+    } else {
+        CONTENT_TYPE_OCTET_STREAM
+    }
+}
+
+/// Resolve the content type for a checkpoint: the stored hint when present,
+/// otherwise a sniffed guess. The flag is `true` when the value was sniffed.
+pub fn resolve_content_type(hint: Option<&str>, state: &[u8]) -> (String, bool) {
+    match hint {
+        Some(content_type) => (content_type.to_string(), false),
+        None => (sniff_content_type(state).to_string(), true),
+    }
+}
+
+/// Limits applied when building a preview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewOptions {
+    /// Nesting depth below which objects and arrays are summarized.
+    pub max_depth: usize,
+    /// Maximum number of array elements or object entries kept per level.
+    pub max_items: usize,
+    /// Maximum string length (in characters) before truncation.
+    pub max_string_len: usize,
+}
+
+impl Default for PreviewOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_items: 20,
+            max_string_len: 256,
+        }
+    }
+}
+
+/// One top-level key of a JSON object state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PreviewKey {
+    /// Key name.
+    pub key: String,
+    /// JSON type of the value (`object`, `array`, `string`, `number`,
+    /// `boolean`, `null`).
+    pub value_type: &'static str,
+    /// Size of the value re-serialized as compact JSON.
+    pub size_bytes: u64,
+    /// Whether the value is redacted in the preview.
+    pub redacted: bool,
+}
+
+/// Structured preview of a checkpoint's state.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckpointPreview {
+    /// Content type (stored hint or sniffed).
+    pub content_type: String,
+    /// Whether `content_type` was sniffed rather than supplied by the writer.
+    pub content_type_sniffed: bool,
+    /// Shape tag supplied by the writer, if any.
+    pub shape: Option<String>,
+    /// Size of the full state in bytes.
+    pub size_bytes: u64,
+    /// Top-level keys when the state is a JSON object.
+    pub top_level_keys: Vec<PreviewKey>,
+    /// Depth-limited, redacted preview value. `None` for non-JSON state.
+    pub preview: Option<Value>,
+    /// Whether anything was cut from `preview`.
+    pub truncated: bool,
+}
+
+/// Build a preview of `state`.
+///
+/// A JSON content type whose bytes fail to parse is reported as
+/// `application/octet-stream` with no preview rather than as an error: the
+/// preview is a browsing aid, not a validator.
+pub fn build_preview(
+    state: &[u8],
+    content_type_hint: Option<&str>,
+    shape: Option<&str>,
+    options: PreviewOptions,
+) -> CheckpointPreview {
+    let (mut content_type, mut sniffed) = resolve_content_type(content_type_hint, state);
+    let mut preview = CheckpointPreview {
+        content_type: String::new(),
+        content_type_sniffed: false,
+        shape: shape.map(str::to_string),
+        size_bytes: state.len() as u64,
+        top_level_keys: Vec::new(),
+        preview: None,
+        truncated: false,
+    };
+
+    if content_type == CONTENT_TYPE_JSON {
+        match serde_json::from_slice::<Value>(state) {
+            Ok(value) => {
+                if let Value::Object(map) = &value {
+                    preview.top_level_keys = map
+                        .iter()
+                        .map(|(key, value)| PreviewKey {
+                            key: key.clone(),
+                            value_type: value_type(value),
+                            size_bytes: serde_json::to_vec(value)
+                                .map(|bytes| bytes.len() as u64)
+                                .unwrap_or(0),
+                            redacted: is_sensitive_key(key),
+                        })
+                        .collect();
+                }
+                let mut truncated = false;
+                preview.preview = Some(limit_value(&value, 0, &options, &mut truncated));
+                preview.truncated = truncated;
+            }
+            Err(_) => {
+                content_type = CONTENT_TYPE_OCTET_STREAM.to_string();
+                sniffed = true;
+            }
+        }
+    }
+
+    preview.content_type = content_type;
+    preview.content_type_sniffed = sniffed;
+    preview
+}
+
+/// Whether values under `key` must be redacted.
+pub fn is_sensitive_key(key: &str) -> bool {
+    let normalized: String = key
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    SENSITIVE_KEY_FRAGMENTS
+        .iter()
+        .any(|fragment| normalized.contains(fragment))
+}
+
+fn value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::String(_) => "string",
+        Value::Number(_) => "number",
+        Value::Bool(_) => "boolean",
+        Value::Null => "null",
+    }
+}
+
+fn limit_value(
+    value: &Value,
+    depth: usize,
+    options: &PreviewOptions,
+    truncated: &mut bool,
+) -> Value {
+    match value {
+        Value::Object(map) => {
+            if depth >= options.max_depth {
+                *truncated = true;
+                return Value::String(format!("{{…{} keys}}", map.len()));
+            }
+            let mut out = Map::new();
+            for (key, child) in map.iter().take(options.max_items) {
+                let child = if is_sensitive_key(key) {
+                    Value::String(REDACTED.to_string())
+                } else {
+                    limit_value(child, depth + 1, options, truncated)
+                };
+                out.insert(key.clone(), child);
+            }
+            if map.len() > options.max_items {
+                *truncated = true;
+            }
+            Value::Object(out)
+        }
+        Value::Array(items) => {
+            if depth >= options.max_depth {
+                *truncated = true;
+                return Value::String(format!("[…{} items]", items.len()));
+            }
+            if items.len() > options.max_items {
+                *truncated = true;
+            }
+            Value::Array(
+                items
+                    .iter()
+                    .take(options.max_items)
+                    .map(|item| limit_value(item, depth + 1, options, truncated))
+                    .collect(),
+            )
+        }
+        Value::String(text) if text.chars().count() > options.max_string_len => {
+            *truncated = true;
+            let mut cut: String = text.chars().take(options.max_string_len).collect();
+            cut.push('…');
+            Value::String(cut)
+        }
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sniffs_json_and_binary() {
+        assert_eq!(sniff_content_type(br#"{"a":1}"#), CONTENT_TYPE_JSON);
+        assert_eq!(sniff_content_type(b"42"), CONTENT_TYPE_JSON);
+        assert_eq!(sniff_content_type(b"not json"), CONTENT_TYPE_OCTET_STREAM);
+        assert_eq!(sniff_content_type(&[0xff, 0x00]), CONTENT_TYPE_OCTET_STREAM);
+    }
+
+    #[test]
+    fn hint_wins_over_sniffing() {
+        assert_eq!(
+            resolve_content_type(Some(CONTENT_TYPE_OCTET_STREAM), b"{}"),
+            (CONTENT_TYPE_OCTET_STREAM.to_string(), false)
+        );
+        assert_eq!(
+            resolve_content_type(None, b"{}"),
+            (CONTENT_TYPE_JSON.to_string(), true)
+        );
+    }
+
+    #[test]
+    fn preview_lists_top_level_keys_with_sizes() {
+        let state = serde_json::to_vec(&json!({"items": [1, 2, 3], "name": "x"})).unwrap();
+        let preview = build_preview(&state, None, Some("step_output"), PreviewOptions::default());
+
+        assert_eq!(preview.content_type, CONTENT_TYPE_JSON);
+        assert!(preview.content_type_sniffed);
+        assert_eq!(preview.shape.as_deref(), Some("step_output"));
+        let keys: Vec<_> = preview
+            .top_level_keys
+            .iter()
+            .map(|k| (k.key.as_str(), k.value_type, k.size_bytes))
+            .collect();
+        assert_eq!(keys, vec![("items", "array", 7), ("name", "string", 3)]);
+        assert!(!preview.truncated);
+    }
+
+    #[test]
+    fn preview_limits_depth_items_and_strings() {
+        let state = serde_json::to_vec(&json!({
+            "deep": {"a": {"b": {"c": 1}}},
+            "list": (0..30).collect::<Vec<_>>(),
+            "text": "x".repeat(10),
+        }))
+        .unwrap();
+        let options = PreviewOptions {
+            max_depth: 2,
+            max_items: 5,
+            max_string_len: 4,
+        };
+        let preview = build_preview(&state, Some(CONTENT_TYPE_JSON), None, options);
+
+        assert!(preview.truncated);
+        let value = preview.preview.unwrap();
+        assert_eq!(value["deep"]["a"], json!("{…1 keys}"));
+        assert_eq!(value["list"].as_array().unwrap().len(), 5);
+        assert_eq!(value["text"], json!("xxxx…"));
+    }
+
+    #[test]
+    fn preview_redacts_credential_like_keys() {
+        let state = serde_json::to_vec(&json!({
+            "_connection": {"host": "db"},
+            "out": {"apiKey": "abc", "Access-Token": "t", "count": 2},
+        }))
+        .unwrap();
+        let preview = build_preview(&state, None, None, PreviewOptions::default());
+
+        assert!(preview.top_level_keys[0].redacted);
+        let value = preview.preview.unwrap();
+        assert_eq!(value["_connection"], json!(REDACTED));
+        assert_eq!(value["out"]["apiKey"], json!(REDACTED));
+        assert_eq!(value["out"]["Access-Token"], json!(REDACTED));
+        assert_eq!(value["out"]["count"], json!(2));
+    }
+
+    #[test]
+    fn binary_state_has_no_preview() {
+        let preview = build_preview(&[0xde, 0xad], None, None, PreviewOptions::default());
+        assert_eq!(preview.content_type, CONTENT_TYPE_OCTET_STREAM);
+        assert_eq!(preview.size_bytes, 2);
+        assert!(preview.preview.is_none());
+        assert!(preview.top_level_keys.is_empty());
+    }
+
+    #[test]
+    fn mislabelled_json_falls_back_to_octet_stream() {
+        let preview = build_preview(
+            b"\x00",
+            Some(CONTENT_TYPE_JSON),
+            None,
+            PreviewOptions::default(),
+        );
+        assert_eq!(preview.content_type, CONTENT_TYPE_OCTET_STREAM);
+        assert!(preview.content_type_sniffed);
+        assert!(preview.preview.is_none());
+    }
+}
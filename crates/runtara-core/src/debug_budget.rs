@@ -0,0 +1,472 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Debug event governance.
+//!
+//! Debug mode emits a `step_debug_start`/`step_debug_end` pair per executed
+//! step, which on a Split over 100k items is hundreds of thousands of rows.
+//! Two layers keep that usable:
+//!
+//! - [`DebugSampler`] runs next to the emitter and holds a per-instance
+//!   budget (`DEBUG_EVENT_BUDGET`, default [`DEFAULT_DEBUG_EVENT_BUDGET`]).
+//!   The first half of the budget is emitted in full; after that steps are
+//!   sampled 1 in N, with N doubling each time the remaining budget halves,
+//!   up to [`MAX_SAMPLE_RATE`]. Sampling is per step execution, so a kept
+//!   start always gets its end, and the end of a failed step is always kept.
+//!   Sampled-away ranges are reported as [`DEBUG_EVENTS_SUPPRESSED_SUBTYPE`]
+//!   events. The sampler state is small and serializable so the emitter can
+//!   persist it as the [`DEBUG_SAMPLER_CHECKPOINT_ID`] checkpoint and pick up
+//!   where it left off after a resume.
+//! - [`DebugEventRateLimiter`] is core's backstop: a hard per-tenant cap on
+//!   debug events per second, applied at ingestion regardless of what the
+//!   emitter does.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Instance env var carrying the per-instance debug event budget.
+pub const DEBUG_EVENT_BUDGET_ENV: &str = "DEBUG_EVENT_BUDGET";
+
+/// Budget used when the instance does not set [`DEBUG_EVENT_BUDGET_ENV`].
+pub const DEFAULT_DEBUG_EVENT_BUDGET: u64 = 10_000;
+
+/// Checkpoint id under which the emitter persists [`DebugSampler`] state.
+pub const DEBUG_SAMPLER_CHECKPOINT_ID: &str = "__runtara_debug_sampler";
+
+/// Custom event subtype summarizing a sampled-away range.
+pub const DEBUG_EVENTS_SUPPRESSED_SUBTYPE: &str = "debug_events_suppressed";
+
+/// Upper bound on the sampling interval (1 in N).
+pub const MAX_SAMPLE_RATE: u64 = 1024;
+
+/// Emit a summary at least this often while a long range is being suppressed.
+const SUMMARY_INTERVAL: u64 = 1_000;
+
+/// Env var for the per-tenant debug event rate (events per second, 0 = off).
+pub const DEBUG_EVENT_RATE_ENV: &str = "RUNTARA_DEBUG_EVENTS_PER_TENANT_PER_SEC";
+
+/// Default per-tenant debug event rate.
+pub const DEFAULT_DEBUG_EVENT_RATE: u64 = 2_000;
+
+const STEP_DEBUG_START: &str = "step_debug_start";
+const STEP_DEBUG_END: &str = "step_debug_end";
+
+/// Whether a custom event subtype is a codegen-emitted step debug event.
+pub fn is_step_debug_subtype(subtype: &str) -> bool {
+    subtype == STEP_DEBUG_START || subtype == STEP_DEBUG_END
+}
+
+/// Outcome of offering one debug event to the sampler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebugDecision {
+    /// Emit the event.
+    Emit {
+        /// [`DEBUG_EVENTS_SUPPRESSED_SUBTYPE`] payload to emit first, if a
+        /// suppressed range just ended.
+        summary: Option<Vec<u8>>,
+    },
+    /// Drop the event.
+    Suppress {
+        /// Set when a long suppressed range is due for its periodic report.
+        summary: Option<Vec<u8>>,
+    },
+}
+
+/// Per-instance debug event budget and sampling state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebugSampler {
+    budget: u64,
+    /// Debug events emitted so far (all runs of this instance).
+    emitted: u64,
+    /// Step starts offered while sampling; drives the 1-in-N choice.
+    sampled_starts: u64,
+    /// Events suppressed since the last summary.
+    pending_suppressed: u64,
+    /// Events suppressed over the instance's lifetime.
+    suppressed_total: u64,
+    first_suppressed_ms: Option<i64>,
+    last_suppressed_ms: Option<i64>,
+    /// Step executions whose start was sampled away and whose end is still
+    /// outstanding, keyed by step id and scope id.
+    open_suppressed: BTreeMap<String, u32>,
+}
+
+impl DebugSampler {
+    /// Fresh sampler with the given budget (0 falls back to the default).
+    pub fn new(budget: u64) -> Self {
+        Self {
+            budget: if budget == 0 {
+                DEFAULT_DEBUG_EVENT_BUDGET
+            } else {
+                budget
+            },
+            emitted: 0,
+            sampled_starts: 0,
+            pending_suppressed: 0,
+            suppressed_total: 0,
+            first_suppressed_ms: None,
+            last_suppressed_ms: None,
+            open_suppressed: BTreeMap::new(),
+        }
+    }
+
+    /// Sampler for an instance env map, honouring [`DEBUG_EVENT_BUDGET_ENV`].
+    pub fn from_budget_var(value: Option<&str>) -> Self {
+        Self::new(value.and_then(|v| v.trim().parse().ok()).unwrap_or(0))
+    }
+
+    /// Restore persisted state; `None` when the bytes are not sampler state.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice(bytes).ok()
+    }
+
+    /// Serialize for persistence.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+
+    /// Configured budget.
+    pub fn budget(&self) -> u64 {
+        self.budget
+    }
+
+    /// Debug events emitted so far.
+    pub fn emitted(&self) -> u64 {
+        self.emitted
+    }
+
+    /// Debug events suppressed so far.
+    pub fn suppressed_total(&self) -> u64 {
+        self.suppressed_total
+    }
+
+    /// Current sampling interval: 1 while at least half the budget remains,
+    /// then doubling every time the remainder halves.
+    pub fn sample_rate(&self) -> u64 {
+        let remaining = self.budget.saturating_sub(self.emitted);
+        let mut threshold = self.budget / 2;
+        let mut rate = 1;
+        while remaining < threshold && rate < MAX_SAMPLE_RATE {
+            rate *= 2;
+            threshold /= 2;
+        }
+        if remaining == 0 {
+            MAX_SAMPLE_RATE
+        } else {
+            rate
+        }
+    }
+
+    /// Decide whether to emit one step debug event.
+    pub fn offer(&mut self, subtype: &str, payload: &[u8], now_ms: i64) -> DebugDecision {
+        let rate = self.sample_rate();
+        // Fast path: full fidelity and no suppressed start awaiting its end.
+        if rate == 1 && self.open_suppressed.is_empty() {
+            return self.emit(now_ms);
+        }
+
+        let payload: Option<Value> = serde_json::from_slice(payload).ok();
+        let key = payload.as_ref().map(pair_key).unwrap_or_default();
+
+        if subtype == STEP_DEBUG_END {
+            let start_suppressed = self.take_open(&key);
+            let failed = payload.as_ref().is_some_and(is_failed_end);
+            if failed || !start_suppressed {
+                return self.emit(now_ms);
+            }
+            return self.suppress(now_ms);
+        }
+
+        self.sampled_starts += 1;
+        if self.sampled_starts.is_multiple_of(rate) {
+            self.emit(now_ms)
+        } else {
+            *self.open_suppressed.entry(key).or_default() += 1;
+            self.suppress(now_ms)
+        }
+    }
+
+    /// Summary for any range still pending, for the end of a run.
+    pub fn flush(&mut self, now_ms: i64) -> Option<Vec<u8>> {
+        self.take_summary(now_ms)
+    }
+
+    fn emit(&mut self, now_ms: i64) -> DebugDecision {
+        self.emitted += 1;
+        DebugDecision::Emit {
+            summary: self.take_summary(now_ms),
+        }
+    }
+
+    fn suppress(&mut self, now_ms: i64) -> DebugDecision {
+        self.pending_suppressed += 1;
+        self.suppressed_total += 1;
+        self.first_suppressed_ms.get_or_insert(now_ms);
+        self.last_suppressed_ms = Some(now_ms);
+        let summary = if self.pending_suppressed >= SUMMARY_INTERVAL {
+            self.take_summary(now_ms)
+        } else {
+            None
+        };
+        DebugDecision::Suppress { summary }
+    }
+
+    fn take_open(&mut self, key: &str) -> bool {
+        match self.open_suppressed.get_mut(key) {
+            Some(count) if *count > 1 => {
+                *count -= 1;
+                true
+            }
+            Some(_) => {
+                self.open_suppressed.remove(key);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn take_summary(&mut self, now_ms: i64) -> Option<Vec<u8>> {
+        if self.pending_suppressed == 0 {
+            return None;
+        }
+        let payload = serde_json::json!({
+            "suppressed": self.pending_suppressed,
+            "from_ms": self.first_suppressed_ms.unwrap_or(now_ms),
+            "to_ms": self.last_suppressed_ms.unwrap_or(now_ms),
+            "sample_rate": self.sample_rate(),
+            "suppressed_total": self.suppressed_total,
+            "emitted_total": self.emitted,
+            "budget": self.budget,
+        });
+        self.pending_suppressed = 0;
+        self.first_suppressed_ms = None;
+        self.last_suppressed_ms = None;
+        serde_json::to_vec(&payload).ok()
+    }
+}
+
+/// Pairing key for a step debug payload: step id plus scope id, the same
+/// pair the step summary joins starts and ends on.
+fn pair_key(payload: &Value) -> String {
+    let step_id = payload.get("step_id").and_then(Value::as_str).unwrap_or("");
+    let scope_id = payload
+        .get("scope_id")
+        .map(|scope| match scope {
+            Value::String(s) => s.clone(),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        })
+        .unwrap_or_default();
+    format!("{step_id}\u{0}{scope_id}")
+}
+
+/// A `step_debug_end` for a failed step carries `outputs._error: true`.
+fn is_failed_end(payload: &Value) -> bool {
+    payload
+        .get("outputs")
+        .and_then(|outputs| outputs.get("_error"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Hard per-tenant cap on step debug events, enforced at ingestion.
+///
+/// Fixed one-second windows per tenant. Instance-to-tenant lookups are cached
+/// so the check costs one persistence read per instance, not per event.
+pub struct DebugEventRateLimiter {
+    per_second: u64,
+    windows: Mutex<HashMap<String, (i64, u64)>>,
+    tenants: Mutex<HashMap<String, String>>,
+}
+
+/// Bound on cached instance-to-tenant entries before the cache is reset.
+const TENANT_CACHE_LIMIT: usize = 10_000;
+
+impl DebugEventRateLimiter {
+    /// Limiter allowing `per_second` debug events per tenant (0 disables it).
+    pub fn new(per_second: u64) -> Self {
+        Self {
+            per_second,
+            windows: Mutex::new(HashMap::new()),
+            tenants: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Limiter configured from [`DEBUG_EVENT_RATE_ENV`].
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var(DEBUG_EVENT_RATE_ENV)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_DEBUG_EVENT_RATE),
+        )
+    }
+
+    /// Whether the limiter is active.
+    pub fn is_enabled(&self) -> bool {
+        self.per_second > 0
+    }
+
+    /// Cached tenant for an instance, if known.
+    pub fn cached_tenant(&self, instance_id: &str) -> Option<String> {
+        self.tenants.lock().ok()?.get(instance_id).cloned()
+    }
+
+    /// Remember an instance's tenant.
+    pub fn cache_tenant(&self, instance_id: &str, tenant_id: &str) {
+        if let Ok(mut tenants) = self.tenants.lock() {
+            if tenants.len() >= TENANT_CACHE_LIMIT {
+                tenants.clear();
+            }
+            tenants.insert(instance_id.to_string(), tenant_id.to_string());
+        }
+    }
+
+    /// Count one debug event for `tenant_id` at `now_ms`; false when the
+    /// tenant is over its rate for the current second.
+    pub fn admit(&self, tenant_id: &str, now_ms: i64) -> bool {
+        if !self.is_enabled() {
+            return true;
+        }
+        let second = now_ms.div_euclid(1000);
+        let Ok(mut windows) = self.windows.lock() else {
+            return true;
+        };
+        let window = windows.entry(tenant_id.to_string()).or_insert((second, 0));
+        if window.0 != second {
+            *window = (second, 0);
+        }
+        if window.1 >= self.per_second {
+            return false;
+        }
+        window.1 += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(step: &str, scope: &str) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({"step_id": step, "scope_id": scope})).unwrap()
+    }
+
+    fn end(step: &str, scope: &str, failed: bool) -> Vec<u8> {
+        let outputs = if failed {
+            serde_json::json!({"_error": true, "error": "boom"})
+        } else {
+            serde_json::json!({"ok": true})
+        };
+        serde_json::to_vec(&serde_json::json!({
+            "step_id": step,
+            "scope_id": scope,
+            "outputs": outputs,
+        }))
+        .unwrap()
+    }
+
+    fn emitted(decision: &DebugDecision) -> bool {
+        matches!(decision, DebugDecision::Emit { .. })
+    }
+
+    #[test]
+    fn sample_rate_escalates_as_budget_depletes() {
+        let mut sampler = DebugSampler::new(100);
+        assert_eq!(sampler.sample_rate(), 1);
+        sampler.emitted = 50;
+        assert_eq!(sampler.sample_rate(), 1);
+        sampler.emitted = 51;
+        assert_eq!(sampler.sample_rate(), 2);
+        sampler.emitted = 80;
+        assert_eq!(sampler.sample_rate(), 4);
+        sampler.emitted = 100;
+        assert_eq!(sampler.sample_rate(), MAX_SAMPLE_RATE);
+        sampler.emitted = 10_000;
+        assert_eq!(sampler.sample_rate(), MAX_SAMPLE_RATE);
+    }
+
+    #[test]
+    fn sampling_keeps_pairs_and_never_drops_everything() {
+        let mut sampler = DebugSampler::new(20);
+        let mut kept_starts = 0;
+        let mut kept_ends = 0;
+        for i in 0..10_000 {
+            let scope = format!("s{i}");
+            if emitted(&sampler.offer(STEP_DEBUG_START, &start("item", &scope), i)) {
+                kept_starts += 1;
+            }
+            if emitted(&sampler.offer(STEP_DEBUG_END, &end("item", &scope, false), i)) {
+                kept_ends += 1;
+            }
+        }
+        assert_eq!(kept_starts, kept_ends);
+        assert!(kept_starts > 10, "sampling must keep some events");
+        assert!(sampler.emitted() < 100, "emitted {}", sampler.emitted());
+        assert!(sampler.open_suppressed.is_empty());
+        assert_eq!(sampler.emitted() + sampler.suppressed_total(), 20_000);
+    }
+
+    #[test]
+    fn failed_step_end_is_always_kept() {
+        let mut sampler = DebugSampler::new(2);
+        sampler.emitted = 2;
+        assert!(!emitted(&sampler.offer(
+            STEP_DEBUG_START,
+            &start("call", "a"),
+            1
+        )));
+        let decision = sampler.offer(STEP_DEBUG_END, &end("call", "a", true), 2);
+        let DebugDecision::Emit {
+            summary: Some(summary),
+        } = decision
+        else {
+            panic!("expected the failed end to be emitted with a summary: {decision:?}");
+        };
+        let summary: Value = serde_json::from_slice(&summary).unwrap();
+        assert_eq!(summary["suppressed"], 1);
+        assert_eq!(summary["from_ms"], 1);
+    }
+
+    #[test]
+    fn long_suppressed_ranges_report_periodically() {
+        let mut sampler = DebugSampler::new(1);
+        sampler.emitted = 1;
+        let mut summaries = 0;
+        for i in 0..5_000 {
+            match sampler.offer(STEP_DEBUG_START, &start("x", &i.to_string()), i) {
+                DebugDecision::Emit { summary } | DebugDecision::Suppress { summary } => {
+                    summaries += usize::from(summary.is_some());
+                }
+            }
+        }
+        assert!(summaries >= 4, "got {summaries} summaries");
+    }
+
+    #[test]
+    fn state_round_trips_through_bytes() {
+        let mut sampler = DebugSampler::from_budget_var(Some("10"));
+        for i in 0..50 {
+            sampler.offer(STEP_DEBUG_START, &start("x", &i.to_string()), i);
+        }
+        let restored = DebugSampler::from_bytes(&sampler.to_bytes()).unwrap();
+        assert_eq!(restored, sampler);
+        assert_eq!(restored.budget(), 10);
+        assert_eq!(
+            DebugSampler::from_budget_var(Some("junk")).budget(),
+            DEFAULT_DEBUG_EVENT_BUDGET
+        );
+    }
+
+    #[test]
+    fn rate_limiter_caps_each_tenant_per_second() {
+        let limiter = DebugEventRateLimiter::new(3);
+        assert!((0..3).all(|_| limiter.admit("a", 1_000)));
+        assert!(!limiter.admit("a", 1_999));
+        assert!(limiter.admit("b", 1_999));
+        assert!(limiter.admit("a", 2_000));
+        assert!(DebugEventRateLimiter::new(0).admit("a", 0));
+    }
+}
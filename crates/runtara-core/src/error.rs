@@ -352,6 +352,32 @@ pub enum CoreError {
         /// Error details.
         details: String,
     },
+
+    /// Core is in maintenance mode and refuses writes until the window ends.
+    Maintenance {
+        /// Operator-supplied reason.
+        message: String,
+        /// Milliseconds until the maintenance window expires.
+        retry_after_ms: u64,
+    },
+
+    /// Request refused because its session is not bound to the instance.
+    Authorization {
+        /// The instance the request named.
+        instance_id: String,
+        /// Why the request was refused.
+        reason: String,
+    },
+
+    /// Write refused because a newer registration superseded the caller.
+    Fenced {
+        /// The instance the write named.
+        instance_id: String,
+        /// Execution epoch the caller registered with.
+        epoch: i64,
+        /// Execution epoch of the current registration.
+        current_epoch: i64,
+    },
 }
 
 impl CoreError {
@@ -366,6 +392,9 @@ impl CoreError {
             Self::SignalDeliveryFailed { .. } => "SIGNAL_DELIVERY_FAILED",
             Self::ValidationError { .. } => "VALIDATION_ERROR",
             Self::DatabaseError { .. } => "DATABASE_ERROR",
+            Self::Maintenance { .. } => "MAINTENANCE",
+            Self::Authorization { .. } => "AUTHORIZATION",
+            Self::Fenced { .. } => "FENCED",
         }
     }
 }
@@ -431,6 +460,37 @@ impl fmt::Display for CoreError {
             Self::DatabaseError { operation, details } => {
                 write!(f, "Database error during '{}': {}", operation, details)
             }
+            Self::Maintenance {
+                message,
+                retry_after_ms,
+            } => {
+                write!(
+                    f,
+                    "Core is in maintenance ({}); retry after {} ms",
+                    message, retry_after_ms
+                )
+            }
+            Self::Authorization {
+                instance_id,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Request for instance '{}' not authorized: {}",
+                    instance_id, reason
+                )
+            }
+            Self::Fenced {
+                instance_id,
+                epoch,
+                current_epoch,
+            } => {
+                write!(
+                    f,
+                    "Instance '{}' execution epoch {} was superseded by epoch {}",
+                    instance_id, epoch, current_epoch
+                )
+            }
         }
     }
 }
@@ -476,12 +536,15 @@ impl From<CoreError> for StructuredError {
             }
             CoreError::ValidationError { .. } => (ErrorCategory::Permanent, ErrorSeverity::Error),
             CoreError::DatabaseError { .. } => (ErrorCategory::Transient, ErrorSeverity::Critical),
+            CoreError::Maintenance { .. } => (ErrorCategory::Transient, ErrorSeverity::Warning),
+            CoreError::Authorization { .. } => (ErrorCategory::Permanent, ErrorSeverity::Error),
+            CoreError::Fenced { .. } => (ErrorCategory::Permanent, ErrorSeverity::Warning),
         };
 
-        let retry_hint = if category == ErrorCategory::Transient {
-            RetryHint::RetryWithBackoff
-        } else {
-            RetryHint::DoNotRetry
+        let retry_hint = match &err {
+            CoreError::Maintenance { retry_after_ms, .. } => RetryHint::RetryAfter(*retry_after_ms),
+            _ if category == ErrorCategory::Transient => RetryHint::RetryWithBackoff,
+            _ => RetryHint::DoNotRetry,
         };
 
         Self {
@@ -560,6 +623,28 @@ mod tests {
                 },
                 "DATABASE_ERROR",
             ),
+            (
+                CoreError::Maintenance {
+                    message: "pg failover".to_string(),
+                    retry_after_ms: 20_000,
+                },
+                "MAINTENANCE",
+            ),
+            (
+                CoreError::Authorization {
+                    instance_id: "test-id".to_string(),
+                    reason: "session is bound to a different instance".to_string(),
+                },
+                "AUTHORIZATION",
+            ),
+            (
+                CoreError::Fenced {
+                    instance_id: "test-id".to_string(),
+                    epoch: 1,
+                    current_epoch: 2,
+                },
+                "FENCED",
+            ),
         ];
 
         for (error, expected_code) in test_cases {
@@ -658,4 +743,15 @@ mod tests {
             "VALIDATION_ERROR"
         );
     }
+
+    #[test]
+    fn test_maintenance_maps_to_transient_retry_after() {
+        let structured = StructuredError::from(CoreError::Maintenance {
+            message: "pg failover".to_string(),
+            retry_after_ms: 20_000,
+        });
+        assert_eq!(structured.code, "MAINTENANCE");
+        assert_eq!(structured.category, ErrorCategory::Transient);
+        assert_eq!(structured.retry_hint, RetryHint::RetryAfter(20_000));
+    }
 }
@@ -0,0 +1,41 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Execution fencing.
+//!
+//! An instance can end up with two containers at once, for example when a
+//! wake fires while an operator resumes the same instance by hand. Both
+//! register and both checkpoint, and their state interleaves. To prevent
+//! that, every successful `RegisterInstance` advances the instance's
+//! execution epoch (see [`Persistence::advance_execution_epoch`]) and returns
+//! it. The SDK sends it on every later request in [`EXECUTION_EPOCH_HEADER`],
+//! and core refuses writes carrying an older epoch than the current one with
+//! [`CoreError::Fenced`]. The superseded container exits on that error, so
+//! only the latest registration can change the instance.
+//!
+//! Requests without the header come from SDKs that predate fencing and are
+//! served unfenced.
+
+use crate::error::CoreError;
+use crate::persistence::Persistence;
+
+/// Request header carrying the caller's execution epoch.
+pub const EXECUTION_EPOCH_HEADER: &str = "x-runtara-execution-epoch";
+
+/// Refuse a write from `epoch` if a newer registration has superseded it.
+///
+/// Passes when the instance does not exist or its backend does not track
+/// epochs; the write itself reports those cases.
+pub async fn check_execution_epoch(
+    persistence: &dyn Persistence,
+    instance_id: &str,
+    epoch: i64,
+) -> Result<(), CoreError> {
+    match persistence.current_execution_epoch(instance_id).await? {
+        Some(current_epoch) if epoch < current_epoch => Err(CoreError::Fenced {
+            instance_id: instance_id.to_string(),
+            epoch,
+            current_epoch,
+        }),
+        _ => Ok(()),
+    }
+}
@@ -1,20 +1,25 @@
 // Copyright (C) 2025 SyncMyOrders Sp. z o.o.
 // SPDX-License-Identifier: AGPL-3.0-or-later
-//! Checkpoint-related handlers: save/resume, read-only lookup, and durable
-//! sleep.
+//! Checkpoint-related handlers: save/resume, read-only lookup, key listing,
+//! loop compaction, and durable sleep.
 
 use std::time::Duration;
 
 use anyhow::Result;
-use tracing::{debug, instrument};
+use chrono::Utc;
+use tracing::{debug, instrument, warn};
 
 use super::state::InstanceHandlerState;
 use super::types::{
-    CheckpointRequest, CheckpointResponse, CustomSignal, GetCheckpointRequest,
-    GetCheckpointResponse, Signal, SignalType, SleepRequest, SleepResponse,
+    CheckpointRequest, CheckpointResponse, CompactCheckpointsRequest, CompactCheckpointsResponse,
+    CustomSignal, GetCheckpointRequest, GetCheckpointResponse, ListCheckpointKeysRequest,
+    ListCheckpointKeysResponse, Signal, SignalType, SleepRequest, SleepResponse,
 };
 use crate::error::CoreError;
-use crate::persistence::Persistence;
+use crate::persistence::{CheckpointHints, EventRecord, Persistence, begin_or_autocommit};
+
+/// Custom event subtype recorded when a compaction deletes checkpoints.
+pub const CHECKPOINTS_COMPACTED_EVENT: &str = "checkpoints_compacted";
 
 /// Checkpoint handler - combines save and load semantics.
 ///
@@ -33,7 +38,13 @@ pub async fn handle_checkpoint(
         "Processing checkpoint request"
     );
 
-    // 1. Validate instance exists and is running
+    // 1. Refuse writes while core is in maintenance (empty-state probes are
+    //    reads and are still served)
+    if !request.state.is_empty() {
+        state.maintenance.check()?;
+    }
+
+    // 2. Validate instance exists and is running
     let instance = state.persistence.get_instance(&request.instance_id).await?;
     match instance {
         Some(inst) => {
@@ -54,7 +65,7 @@ pub async fn handle_checkpoint(
         }
     }
 
-    // 2. Check if checkpoint already exists
+    // 3. Check if checkpoint already exists
     if let Some(existing) = state
         .persistence
         .load_checkpoint(&request.instance_id, &request.checkpoint_id)
@@ -69,14 +80,7 @@ pub async fn handle_checkpoint(
         // Check for pending signal even when returning existing checkpoint
         let pending_signal =
             get_pending_signal(state.persistence.as_ref(), &request.instance_id).await;
-        let custom_signal = state
-            .persistence
-            .take_pending_custom_signal(&request.instance_id, &request.checkpoint_id)
-            .await?
-            .map(|sig| CustomSignal {
-                checkpoint_id: request.checkpoint_id.clone(),
-                payload: sig.payload.unwrap_or_default(),
-            });
+        let custom_signal = find_custom_signal(state.persistence.as_ref(), &request).await?;
 
         return Ok(CheckpointResponse {
             found: true,
@@ -87,7 +91,7 @@ pub async fn handle_checkpoint(
         });
     }
 
-    // 3. Checkpoint doesn't exist
+    // 4. Checkpoint doesn't exist
     // Only save if state is non-empty. The SDK's get_checkpoint() calls this
     // endpoint with empty state as a read-only probe. If we saved empty state,
     // subsequent save attempts with real state would find the empty checkpoint
@@ -103,27 +107,28 @@ pub async fn handle_checkpoint(
         });
     }
 
-    state
-        .persistence
-        .save_checkpoint(&request.instance_id, &request.checkpoint_id, &request.state)
+    // The checkpoint row and the instance pointer commit together, so
+    // `instances.checkpoint_id` never references a checkpoint that was lost.
+    let mut tx = begin_or_autocommit(state.persistence.as_ref()).await?;
+    tx.save_checkpoint_with_hints(
+        &request.instance_id,
+        &request.checkpoint_id,
+        &request.state,
+        CheckpointHints {
+            content_type: request.content_type.as_deref(),
+            shape: request.shape.as_deref(),
+        },
+    )
+    .await?;
+
+    // 5. Update instance's current checkpoint_id
+    tx.update_instance_checkpoint(&request.instance_id, &request.checkpoint_id)
         .await?;
+    tx.commit().await?;
 
-    // 4. Update instance's current checkpoint_id
-    state
-        .persistence
-        .update_instance_checkpoint(&request.instance_id, &request.checkpoint_id)
-        .await?;
-
-    // 5. Check for pending signals to include in response
+    // 6. Check for pending signals to include in response
     let pending_signal = get_pending_signal(state.persistence.as_ref(), &request.instance_id).await;
-    let custom_signal = state
-        .persistence
-        .take_pending_custom_signal(&request.instance_id, &request.checkpoint_id)
-        .await?
-        .map(|sig| CustomSignal {
-            checkpoint_id: request.checkpoint_id.clone(),
-            payload: sig.payload.unwrap_or_default(),
-        });
+    let custom_signal = find_custom_signal(state.persistence.as_ref(), &request).await?;
 
     if pending_signal.is_some() || custom_signal.is_some() {
         debug!(
@@ -144,6 +149,31 @@ pub async fn handle_checkpoint(
     })
 }
 
+/// Helper to find the pending custom signal for a checkpoint.
+///
+/// The checkpoint's own ID is checked first, then each of its wait keys in
+/// order; the first match is returned under the key it was sent to. Reads are
+/// non-destructive (see `take_pending_custom_signal`), so a replayed
+/// checkpoint sees the same signal again.
+async fn find_custom_signal(
+    persistence: &dyn Persistence,
+    request: &CheckpointRequest,
+) -> Result<Option<CustomSignal>> {
+    let keys = std::iter::once(&request.checkpoint_id).chain(&request.wait_keys);
+    for key in keys {
+        if let Some(signal) = persistence
+            .take_pending_custom_signal(&request.instance_id, key)
+            .await?
+        {
+            return Ok(Some(CustomSignal {
+                checkpoint_id: key.clone(),
+                payload: signal.payload.unwrap_or_default(),
+            }));
+        }
+    }
+    Ok(None)
+}
+
 /// Helper to get the pending instance-wide signal for an instance.
 async fn get_pending_signal(persistence: &dyn Persistence, instance_id: &str) -> Option<Signal> {
     match persistence.get_pending_signal(instance_id).await {
@@ -208,6 +238,101 @@ pub async fn handle_get_checkpoint(
     })
 }
 
+/// List checkpoint keys handler - returns checkpoint ids without their state.
+///
+/// A resuming instance calls this once at registration so it can tell which
+/// durable steps already completed, instead of probing every step's
+/// checkpoint with a separate lookup.
+#[instrument(skip(state, request), fields(instance_id = %request.instance_id))]
+pub async fn handle_list_checkpoint_keys(
+    state: &InstanceHandlerState,
+    request: ListCheckpointKeysRequest,
+) -> Result<ListCheckpointKeysResponse> {
+    // 1. Validate instance exists
+    let instance = state.persistence.get_instance(&request.instance_id).await?;
+    if instance.is_none() {
+        return Err(CoreError::InstanceNotFound {
+            instance_id: request.instance_id.clone(),
+        }
+        .into());
+    }
+
+    // 2. List matching ids
+    let checkpoint_ids = state
+        .persistence
+        .list_checkpoint_ids(
+            &request.instance_id,
+            request.prefix.as_deref().filter(|p| !p.is_empty()),
+            request.since,
+        )
+        .await?;
+    debug!(count = checkpoint_ids.len(), "Checkpoint keys listed");
+
+    Ok(ListCheckpointKeysResponse { checkpoint_ids })
+}
+
+/// Compact checkpoints handler - deletes all checkpoints matching a prefix.
+///
+/// Called once a loop's aggregate checkpoint is durable so its per-iteration
+/// checkpoints no longer need to be kept. The instance's current resume
+/// checkpoint is never deleted, and re-running the same compaction deletes
+/// nothing. A `checkpoints_compacted` event records how many rows went.
+#[instrument(skip(state, request), fields(instance_id = %request.instance_id, prefix = %request.prefix))]
+pub async fn handle_compact_checkpoints(
+    state: &InstanceHandlerState,
+    request: CompactCheckpointsRequest,
+) -> Result<CompactCheckpointsResponse> {
+    // 1. An empty prefix would match every checkpoint of the instance
+    if request.prefix.is_empty() {
+        return Err(CoreError::ValidationError {
+            field: "prefix".to_string(),
+            message: "prefix is required".to_string(),
+        }
+        .into());
+    }
+
+    // 2. Refuse the delete while core is in maintenance
+    state.maintenance.check()?;
+
+    // 3. Validate instance exists
+    let instance = state.persistence.get_instance(&request.instance_id).await?;
+    if instance.is_none() {
+        return Err(CoreError::InstanceNotFound {
+            instance_id: request.instance_id.clone(),
+        }
+        .into());
+    }
+
+    // 4. Delete matching checkpoints
+    let deleted = state
+        .persistence
+        .delete_checkpoints_with_prefix(&request.instance_id, &request.prefix)
+        .await?;
+    debug!(deleted, "Checkpoints compacted");
+
+    // 5. Record the rows deleted (skipped for idempotent re-runs)
+    if deleted > 0 {
+        let event = EventRecord {
+            id: None,
+            instance_id: request.instance_id.clone(),
+            event_type: "custom".to_string(),
+            checkpoint_id: None,
+            payload: serde_json::to_vec(&serde_json::json!({
+                "prefix": request.prefix,
+                "rows_deleted": deleted,
+            }))
+            .ok(),
+            created_at: Utc::now(),
+            subtype: Some(CHECKPOINTS_COMPACTED_EVENT.to_string()),
+        };
+        if let Err(e) = state.persistence.insert_event(&event).await {
+            warn!("Failed to insert checkpoints compacted event: {}", e);
+        }
+    }
+
+    Ok(CompactCheckpointsResponse { deleted })
+}
+
 /// Handle durable sleep request.
 ///
 /// Saves the checkpoint state before sleeping, then sleeps in-process.
@@ -226,16 +351,15 @@ pub async fn handle_sleep(
 
     // 1. Save checkpoint before sleeping (for durability)
     if !request.checkpoint_id.is_empty() {
-        state
-            .persistence
-            .save_checkpoint(&request.instance_id, &request.checkpoint_id, &request.state)
+        state.maintenance.check()?;
+        let mut tx = begin_or_autocommit(state.persistence.as_ref()).await?;
+        tx.save_checkpoint(&request.instance_id, &request.checkpoint_id, &request.state)
             .await?;
 
         // Update instance's current checkpoint_id
-        state
-            .persistence
-            .update_instance_checkpoint(&request.instance_id, &request.checkpoint_id)
+        tx.update_instance_checkpoint(&request.instance_id, &request.checkpoint_id)
             .await?;
+        tx.commit().await?;
 
         debug!(checkpoint_id = %request.checkpoint_id, "Sleep checkpoint saved");
     }
@@ -266,6 +390,9 @@ mod tests {
             instance_id: "nonexistent".to_string(),
             checkpoint_id: "cp-1".to_string(),
             state: b"test state".to_vec(),
+            content_type: None,
+            shape: None,
+            wait_keys: Vec::new(),
         };
 
         let result = handle_checkpoint(&state, request).await;
@@ -285,6 +412,9 @@ mod tests {
             instance_id: "inst-1".to_string(),
             checkpoint_id: "cp-1".to_string(),
             state: b"test state".to_vec(),
+            content_type: None,
+            shape: None,
+            wait_keys: Vec::new(),
         };
 
         let result = handle_checkpoint(&state, request).await;
@@ -302,12 +432,50 @@ mod tests {
             instance_id: "inst-1".to_string(),
             checkpoint_id: "cp-1".to_string(),
             state: b"test state".to_vec(),
+            content_type: None,
+            shape: None,
+            wait_keys: Vec::new(),
         };
 
         let result = handle_checkpoint(&state, request).await.unwrap();
         assert!(!result.found); // New checkpoint, not found
     }
 
+    #[tokio::test]
+    async fn test_checkpoint_refused_during_maintenance_but_probe_served() {
+        let persistence = Arc::new(
+            MockPersistence::new().with_instance(make_instance("inst-1", "tenant-1", "running")),
+        );
+        let mut state = InstanceHandlerState::new(persistence);
+        state.maintenance = crate::maintenance::MaintenanceMode::new();
+        state
+            .maintenance
+            .set(true, Some(Duration::from_secs(20)), "pg failover");
+
+        let request = |body: &[u8]| CheckpointRequest {
+            instance_id: "inst-1".to_string(),
+            checkpoint_id: "cp-1".to_string(),
+            state: body.to_vec(),
+            content_type: None,
+            shape: None,
+            wait_keys: Vec::new(),
+        };
+
+        let Err(err) = handle_checkpoint(&state, request(b"test state")).await else {
+            panic!("checkpoint write must be refused during maintenance");
+        };
+        assert!(crate::maintenance::maintenance_retry_after(&err).is_some());
+        assert!(!handle_checkpoint(&state, request(b"")).await.unwrap().found);
+
+        state.maintenance.set(false, None, "");
+        assert!(
+            !handle_checkpoint(&state, request(b"test state"))
+                .await
+                .unwrap()
+                .found
+        );
+    }
+
     #[tokio::test]
     async fn test_checkpoint_existing_returns_state() {
         let persistence = Arc::new(
@@ -321,6 +489,9 @@ mod tests {
             instance_id: "inst-1".to_string(),
             checkpoint_id: "cp-1".to_string(),
             state: b"new state".to_vec(), // This should be ignored
+            content_type: None,
+            shape: None,
+            wait_keys: Vec::new(),
         };
 
         let result = handle_checkpoint(&state, request).await.unwrap();
@@ -341,6 +512,9 @@ mod tests {
             instance_id: "inst-1".to_string(),
             checkpoint_id: "cp-1".to_string(),
             state: b"test state".to_vec(),
+            content_type: None,
+            shape: None,
+            wait_keys: Vec::new(),
         };
 
         let result = handle_checkpoint(&state, request).await.unwrap();
@@ -368,6 +542,9 @@ mod tests {
             instance_id: "inst-1".to_string(),
             checkpoint_id: "cp-1".to_string(),
             state: b"test state".to_vec(),
+            content_type: None,
+            shape: None,
+            wait_keys: Vec::new(),
         };
 
         let result = handle_checkpoint(&state, request).await.unwrap();
@@ -376,4 +553,210 @@ mod tests {
         assert_eq!(cs.checkpoint_id, "cp-1");
         assert_eq!(cs.payload, b"custom payload");
     }
+
+    fn custom_signal_record(checkpoint_id: &str, payload: &[u8]) -> CustomSignalRecord {
+        CustomSignalRecord {
+            instance_id: "inst-1".to_string(),
+            checkpoint_id: checkpoint_id.to_string(),
+            payload: Some(payload.to_vec()),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_returns_custom_signal_for_wait_key() {
+        let persistence = Arc::new(
+            MockPersistence::new()
+                .with_instance(make_instance("inst-1", "tenant-1", "running"))
+                .with_custom_signal(custom_signal_record("payment_confirmed:order-7", b"paid")),
+        );
+        let state = InstanceHandlerState::new(persistence);
+
+        let request = |checkpoint_id: &str| CheckpointRequest {
+            instance_id: "inst-1".to_string(),
+            checkpoint_id: checkpoint_id.to_string(),
+            state: b"test state".to_vec(),
+            content_type: None,
+            shape: None,
+            wait_keys: vec![
+                "payment_failed:order-7".to_string(),
+                "payment_confirmed:order-7".to_string(),
+            ],
+        };
+
+        // A fresh checkpoint and a replayed one both carry the signal.
+        for checkpoint_id in ["poll-1", "poll-1"] {
+            let result = handle_checkpoint(&state, request(checkpoint_id))
+                .await
+                .unwrap();
+            let cs = result.custom_signal.expect("wait key signal delivered");
+            assert_eq!(cs.checkpoint_id, "payment_confirmed:order-7");
+            assert_eq!(cs.payload, b"paid");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_prefers_own_custom_signal_over_wait_keys() {
+        let persistence = Arc::new(
+            MockPersistence::new()
+                .with_instance(make_instance("inst-1", "tenant-1", "running"))
+                .with_custom_signal(custom_signal_record("cp-1", b"own"))
+                .with_custom_signal(custom_signal_record("key-a", b"keyed")),
+        );
+        let state = InstanceHandlerState::new(persistence);
+
+        let request = CheckpointRequest {
+            instance_id: "inst-1".to_string(),
+            checkpoint_id: "cp-1".to_string(),
+            state: b"test state".to_vec(),
+            content_type: None,
+            shape: None,
+            wait_keys: vec!["key-a".to_string()],
+        };
+
+        let cs = handle_checkpoint(&state, request)
+            .await
+            .unwrap()
+            .custom_signal
+            .unwrap();
+        assert_eq!(cs.checkpoint_id, "cp-1");
+        assert_eq!(cs.payload, b"own");
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_persists_content_hints() {
+        let persistence = Arc::new(
+            MockPersistence::new().with_instance(make_instance("inst-1", "tenant-1", "running")),
+        );
+        let state = InstanceHandlerState::new(persistence.clone());
+
+        let request = CheckpointRequest {
+            instance_id: "inst-1".to_string(),
+            checkpoint_id: "cp-1".to_string(),
+            state: br#"{"a":1}"#.to_vec(),
+            content_type: Some("application/json".to_string()),
+            shape: Some("step_output".to_string()),
+            wait_keys: Vec::new(),
+        };
+
+        handle_checkpoint(&state, request).await.unwrap();
+        let saved = persistence
+            .load_checkpoint("inst-1", "cp-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.content_type.as_deref(), Some("application/json"));
+        assert_eq!(saved.shape.as_deref(), Some("step_output"));
+    }
+
+    #[tokio::test]
+    async fn test_compact_checkpoints_keeps_resume_checkpoint() {
+        let mut instance = make_instance("inst-1", "tenant-1", "running");
+        instance.checkpoint_id = Some("wf::split::s::[2]".to_string());
+        let persistence = Arc::new(
+            MockPersistence::new()
+                .with_instance(instance)
+                .with_checkpoint(make_checkpoint("inst-1", "wf::split::s::[0]", b"0"))
+                .with_checkpoint(make_checkpoint("inst-1", "wf::split::s::[1]", b"1"))
+                .with_checkpoint(make_checkpoint("inst-1", "wf::split::s::[2]", b"2"))
+                .with_checkpoint(make_checkpoint("inst-1", "wf::split::s", b"agg")),
+        );
+        let state = InstanceHandlerState::new(persistence.clone());
+
+        let request = CompactCheckpointsRequest {
+            instance_id: "inst-1".to_string(),
+            prefix: "wf::split::s::[".to_string(),
+        };
+        let result = handle_compact_checkpoints(&state, request).await.unwrap();
+        assert_eq!(result.deleted, 2);
+
+        for (checkpoint_id, kept) in [
+            ("wf::split::s::[0]", false),
+            ("wf::split::s::[2]", true),
+            ("wf::split::s", true),
+        ] {
+            let found = persistence
+                .load_checkpoint("inst-1", checkpoint_id)
+                .await
+                .unwrap();
+            assert_eq!(found.is_some(), kept, "{checkpoint_id}");
+        }
+
+        let events = persistence.get_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].subtype.as_deref(),
+            Some(CHECKPOINTS_COMPACTED_EVENT)
+        );
+        let payload: serde_json::Value =
+            serde_json::from_slice(events[0].payload.as_ref().unwrap()).unwrap();
+        assert_eq!(payload["rows_deleted"], 2);
+
+        // Re-running deletes nothing and records no further event.
+        let request = CompactCheckpointsRequest {
+            instance_id: "inst-1".to_string(),
+            prefix: "wf::split::s::[".to_string(),
+        };
+        let result = handle_compact_checkpoints(&state, request).await.unwrap();
+        assert_eq!(result.deleted, 0);
+        assert_eq!(persistence.get_events().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_checkpoint_keys_returns_ids_only() {
+        let persistence = Arc::new(
+            MockPersistence::new()
+                .with_instance(make_instance("inst-1", "tenant-1", "running"))
+                .with_checkpoint(make_checkpoint("inst-1", "durable::a::1", b"a"))
+                .with_checkpoint(make_checkpoint("inst-1", "durable::b::1", b"b"))
+                .with_checkpoint(make_checkpoint("inst-1", "sleep::1", b"s"))
+                .with_checkpoint(make_checkpoint("inst-2", "durable::a::1", b"x")),
+        );
+        let state = InstanceHandlerState::new(persistence);
+
+        let request = ListCheckpointKeysRequest {
+            instance_id: "inst-1".to_string(),
+            prefix: None,
+            since: None,
+        };
+        let mut ids = handle_list_checkpoint_keys(&state, request)
+            .await
+            .unwrap()
+            .checkpoint_ids;
+        ids.sort();
+        assert_eq!(ids, ["durable::a::1", "durable::b::1", "sleep::1"]);
+
+        let request = ListCheckpointKeysRequest {
+            instance_id: "inst-1".to_string(),
+            prefix: Some("durable::".to_string()),
+            since: None,
+        };
+        let mut ids = handle_list_checkpoint_keys(&state, request)
+            .await
+            .unwrap()
+            .checkpoint_ids;
+        ids.sort();
+        assert_eq!(ids, ["durable::a::1", "durable::b::1"]);
+
+        let request = ListCheckpointKeysRequest {
+            instance_id: "missing".to_string(),
+            prefix: None,
+            since: None,
+        };
+        assert!(handle_list_checkpoint_keys(&state, request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_compact_checkpoints_rejects_empty_prefix() {
+        let persistence = Arc::new(
+            MockPersistence::new().with_instance(make_instance("inst-1", "tenant-1", "running")),
+        );
+        let state = InstanceHandlerState::new(persistence);
+
+        let request = CompactCheckpointsRequest {
+            instance_id: "inst-1".to_string(),
+            prefix: String::new(),
+        };
+        assert!(handle_compact_checkpoints(&state, request).await.is_err());
+    }
 }
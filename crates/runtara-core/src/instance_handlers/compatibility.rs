@@ -0,0 +1,163 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Version handshake between workflow binaries and core.
+//!
+//! A binary reports its instance-protocol version, SDK version and image build
+//! metadata when it registers. Core refuses protocol versions outside
+//! [`MIN_SUPPORTED_PROTOCOL_VERSION`]..=[`MAX_SUPPORTED_PROTOCOL_VERSION`] up
+//! front, instead of letting the drift surface later as decode errors
+//! mid-run. Binaries built before the handshake report nothing and are allowed
+//! with a warning.
+
+use serde::{Deserialize, Serialize};
+
+/// Oldest instance-protocol version this core accepts.
+pub const MIN_SUPPORTED_PROTOCOL_VERSION: u32 = 1;
+
+/// Newest instance-protocol version this core accepts.
+pub const MAX_SUPPORTED_PROTOCOL_VERSION: u32 = 1;
+
+/// Custom event subtype recorded when registration succeeds with warnings.
+pub const VERSION_WARNING_EVENT: &str = "version_warning";
+
+/// Version and build metadata reported by a workflow binary at registration.
+///
+/// Persisted as the payload of the instance's `started` event so operators
+/// can inventory which deployed images are running stale builds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportedVersions {
+    /// Instance-protocol version spoken by the SDK.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<u32>,
+    /// `runtara-sdk` crate version compiled into the binary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sdk_version: Option<String>,
+    /// DSL version the image was compiled from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dsl_version: Option<String>,
+    /// Checksum of the stdlib the image was linked against.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdlib_checksum: Option<String>,
+}
+
+impl ReportedVersions {
+    /// True when the binary reported nothing (pre-handshake build).
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Outcome of [`check_version_compatibility`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionCompatibility {
+    /// Versions are known and supported.
+    Compatible,
+    /// Allowed, but something is unknown or drifting; the warnings are logged
+    /// and recorded as a `version_warning` event.
+    CompatibleWithWarnings(Vec<String>),
+    /// The binary speaks a protocol version this core cannot serve.
+    Incompatible {
+        /// Human-readable reason, suitable for the binary's failure message.
+        reason: String,
+    },
+}
+
+/// Validate reported versions against what this core supports.
+pub fn check_version_compatibility(versions: &ReportedVersions) -> VersionCompatibility {
+    let mut warnings = Vec::new();
+
+    match versions.protocol_version {
+        None => warnings.push(
+            "protocol version not reported (binary predates the version handshake)".to_string(),
+        ),
+        Some(v)
+            if !(MIN_SUPPORTED_PROTOCOL_VERSION..=MAX_SUPPORTED_PROTOCOL_VERSION).contains(&v) =>
+        {
+            return VersionCompatibility::Incompatible {
+                reason: format!(
+                    "instance protocol version {} is not supported by this core (supported: {}..={}); rebuild the image against a matching runtara-sdk",
+                    v, MIN_SUPPORTED_PROTOCOL_VERSION, MAX_SUPPORTED_PROTOCOL_VERSION
+                ),
+            };
+        }
+        Some(_) => {}
+    }
+
+    match versions.sdk_version.as_deref() {
+        None => warnings.push("SDK version not reported".to_string()),
+        Some(sdk) if major(sdk) != major(env!("CARGO_PKG_VERSION")) => warnings.push(format!(
+            "SDK version {} differs in major version from core {}",
+            sdk,
+            env!("CARGO_PKG_VERSION")
+        )),
+        Some(_) => {}
+    }
+
+    if warnings.is_empty() {
+        VersionCompatibility::Compatible
+    } else {
+        VersionCompatibility::CompatibleWithWarnings(warnings)
+    }
+}
+
+fn major(version: &str) -> &str {
+    version.split('.').next().unwrap_or(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn current() -> ReportedVersions {
+        ReportedVersions {
+            protocol_version: Some(MAX_SUPPORTED_PROTOCOL_VERSION),
+            sdk_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            dsl_version: Some("3.0.0".to_string()),
+            stdlib_checksum: None,
+        }
+    }
+
+    #[test]
+    fn test_current_versions_are_compatible() {
+        assert_eq!(
+            check_version_compatibility(&current()),
+            VersionCompatibility::Compatible
+        );
+    }
+
+    #[test]
+    fn test_absent_versions_allowed_with_warnings() {
+        match check_version_compatibility(&ReportedVersions::default()) {
+            VersionCompatibility::CompatibleWithWarnings(warnings) => {
+                assert_eq!(warnings.len(), 2)
+            }
+            other => panic!("expected warnings, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sdk_major_drift_warns() {
+        let versions = ReportedVersions {
+            sdk_version: Some("0.1.0".to_string()),
+            ..current()
+        };
+        assert!(matches!(
+            check_version_compatibility(&versions),
+            VersionCompatibility::CompatibleWithWarnings(_)
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_protocol_is_incompatible() {
+        let versions = ReportedVersions {
+            protocol_version: Some(MAX_SUPPORTED_PROTOCOL_VERSION + 1),
+            ..current()
+        };
+        match check_version_compatibility(&versions) {
+            VersionCompatibility::Incompatible { reason } => {
+                assert!(reason.contains("supported: 1..=1"))
+            }
+            other => panic!("expected incompatible, got {:?}", other),
+        }
+    }
+}
@@ -9,8 +9,10 @@ use tracing::{debug, info, instrument, warn};
 use super::mappers::map_event_type;
 use super::state::InstanceHandlerState;
 use super::types::{InstanceEvent, InstanceEventResponse, InstanceEventType, RetryAttemptEvent};
+use crate::debug_budget::is_step_debug_subtype;
 use crate::error::CoreError;
-use crate::persistence::{CompleteInstanceParams, EventRecord};
+use crate::persistence::tx::AutoCommit;
+use crate::persistence::{CompleteInstanceParams, EventRecord, PersistenceTx, begin_or_autocommit};
 
 /// Handle instance event.
 ///
@@ -52,10 +54,26 @@ pub async fn handle_instance_event(
         .into());
     }
 
-    // 3. Determine timestamp
+    // 3. Refuse the write while core is in maintenance
+    state.maintenance.check()?;
+
+    // 4. Enforce the per-tenant step debug event cap. Over-limit events are
+    //    acknowledged but not stored, so a noisy debug run never fails.
+    if event.event_type() == InstanceEventType::EventCustom
+        && event.subtype.as_deref().is_some_and(is_step_debug_subtype)
+        && !admit_debug_event(state, &event.instance_id).await?
+    {
+        debug!(subtype = ?event.subtype, "Debug event dropped by tenant rate limit");
+        return Ok(InstanceEventResponse {
+            success: true,
+            error: None,
+        });
+    }
+
+    // 5. Determine timestamp
     let created_at = DateTime::from_timestamp_millis(event.timestamp_ms).unwrap_or_else(Utc::now);
 
-    // 4. Insert event record
+    // 6. Insert event record
     let event_record = EventRecord {
         id: None,
         instance_id: event.instance_id.clone(),
@@ -69,9 +87,22 @@ pub async fn handle_instance_event(
         created_at,
         subtype: event.subtype.clone(),
     };
-    state.persistence.insert_event(&event_record).await?;
+    // Status-changing events insert the record and apply the status change
+    // in one transaction, so a crash between the two never leaves a terminal
+    // event without its status (or the reverse). Heartbeats and custom events
+    // are single writes.
+    let changes_status = !matches!(
+        event.event_type(),
+        InstanceEventType::EventHeartbeat | InstanceEventType::EventCustom
+    );
+    let mut tx: Box<dyn PersistenceTx + '_> = if changes_status {
+        begin_or_autocommit(state.persistence.as_ref()).await?
+    } else {
+        Box::new(AutoCommit(state.persistence.as_ref()))
+    };
+    tx.insert_event(&event_record).await?;
 
-    // 5. Update instance status based on event type
+    // 7. Update instance status based on event type
     // All events return a response to acknowledge persistence
     match event.event_type() {
         InstanceEventType::EventHeartbeat => {
@@ -94,7 +125,7 @@ pub async fn handle_instance_event(
             if let Some(o) = output {
                 params = params.with_output(o);
             }
-            let applied = state.persistence.complete_instance(params).await?;
+            let applied = tx.complete_instance(params).await?;
             if applied {
                 info!("Instance completed successfully");
             } else {
@@ -110,8 +141,7 @@ pub async fn handle_instance_event(
             // Guard with `if_running()` to prevent race condition with PID monitor:
             // if the PID monitor already set status to "failed", don't overwrite
             // with the SDK event.
-            let applied = state
-                .persistence
+            let applied = tx
                 .complete_instance(
                     CompleteInstanceParams::new(&event.instance_id, "failed")
                         .if_running()
@@ -129,30 +159,22 @@ pub async fn handle_instance_event(
             if let (false, Some(checkpoint_id)) = (event.payload.is_empty(), &event.checkpoint_id) {
                 if let Some(sleep_data) = parse_sleep_payload(&event.payload) {
                     // Save checkpoint with state from payload
-                    state
-                        .persistence
-                        .save_checkpoint(&event.instance_id, checkpoint_id, &sleep_data.state)
+                    tx.save_checkpoint(&event.instance_id, checkpoint_id, &sleep_data.state)
                         .await?;
 
                     // Update instance checkpoint reference
-                    state
-                        .persistence
-                        .update_instance_checkpoint(&event.instance_id, checkpoint_id)
+                    tx.update_instance_checkpoint(&event.instance_id, checkpoint_id)
                         .await?;
 
                     // Set sleep_until for wake scheduler
                     if let Some(wake_at) = sleep_data.wake_at {
-                        state
-                            .persistence
-                            .set_instance_sleep(&event.instance_id, wake_at)
-                            .await?;
+                        tx.set_instance_sleep(&event.instance_id, wake_at).await?;
                     }
 
                     // Mark as suspended with termination_reason "sleeping".
                     // Guard with `if_running()` to prevent race condition with
                     // the PID monitor.
-                    let applied = state
-                        .persistence
+                    let applied = tx
                         .complete_instance(
                             CompleteInstanceParams::new(&event.instance_id, "suspended")
                                 .if_running()
@@ -177,8 +199,7 @@ pub async fn handle_instance_event(
                     // Payload present but not valid sleep data — just suspend.
                     // Guard with `if_running()` to prevent race condition with
                     // the PID monitor.
-                    let applied = state
-                        .persistence
+                    let applied = tx
                         .complete_instance(
                             CompleteInstanceParams::new(&event.instance_id, "suspended")
                                 .if_running(),
@@ -194,8 +215,7 @@ pub async fn handle_instance_event(
                 // No payload or no checkpoint_id — simple suspend.
                 // Guard with `if_running()` to prevent race condition with the
                 // PID monitor.
-                let applied = state
-                    .persistence
+                let applied = tx
                     .complete_instance(
                         CompleteInstanceParams::new(&event.instance_id, "suspended").if_running(),
                     )
@@ -213,6 +233,7 @@ pub async fn handle_instance_event(
             debug!(subtype = ?event.subtype, "Custom event received");
         }
     }
+    tx.commit().await?;
 
     Ok(InstanceEventResponse {
         success: true,
@@ -220,6 +241,25 @@ pub async fn handle_instance_event(
     })
 }
 
+/// Count a step debug event against its tenant's rate; false when over.
+async fn admit_debug_event(state: &InstanceHandlerState, instance_id: &str) -> Result<bool> {
+    let limiter = &state.debug_event_limiter;
+    if !limiter.is_enabled() {
+        return Ok(true);
+    }
+    let tenant_id = match limiter.cached_tenant(instance_id) {
+        Some(tenant_id) => tenant_id,
+        None => {
+            let Some(instance) = state.persistence.get_instance(instance_id).await? else {
+                return Ok(true);
+            };
+            limiter.cache_tenant(instance_id, &instance.tenant_id);
+            instance.tenant_id
+        }
+    };
+    Ok(limiter.admit(&tenant_id, Utc::now().timestamp_millis()))
+}
+
 /// Handle retry attempt event (fire-and-forget).
 ///
 /// Records a retry attempt for audit trail. Retry attempts are stored
@@ -238,6 +278,7 @@ pub async fn handle_retry_attempt(
     event: RetryAttemptEvent,
 ) -> Result<()> {
     debug!(timestamp_ms = event.timestamp_ms, "Recording retry attempt");
+    state.maintenance.check()?;
 
     // Save retry attempt record for audit trail
     state
@@ -353,6 +394,40 @@ mod tests {
         assert_eq!(inst.status, "completed");
     }
 
+    #[tokio::test]
+    async fn test_handle_event_refused_during_maintenance() {
+        let persistence = Arc::new(
+            MockPersistence::new().with_instance(make_instance("inst-1", "tenant-1", "running")),
+        );
+        let mut state = InstanceHandlerState::new(persistence.clone());
+        state.maintenance = crate::maintenance::MaintenanceMode::new();
+        state.maintenance.set(
+            true,
+            Some(std::time::Duration::from_secs(20)),
+            "pg failover",
+        );
+
+        let event = InstanceEvent {
+            instance_id: "inst-1".to_string(),
+            event_type: InstanceEventType::EventCompleted as i32,
+            checkpoint_id: None,
+            payload: b"result".to_vec(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+            subtype: None,
+        };
+
+        let Err(err) = handle_instance_event(&state, event).await else {
+            panic!("event write must be refused during maintenance");
+        };
+        assert!(matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::Maintenance { .. })
+        ));
+        assert!(persistence.get_events().is_empty());
+        let inst = persistence.get_instance("inst-1").await.unwrap().unwrap();
+        assert_eq!(inst.status, "running");
+    }
+
     #[tokio::test]
     async fn test_handle_event_failed() {
         let persistence = Arc::new(
@@ -507,4 +582,51 @@ mod tests {
         let result = parse_sleep_payload(&payload.to_string().into_bytes());
         assert!(result.is_none());
     }
+
+    #[tokio::test]
+    async fn test_handle_event_debug_events_capped_per_tenant() {
+        use crate::debug_budget::DebugEventRateLimiter;
+
+        let persistence = Arc::new(
+            MockPersistence::new().with_instance(make_instance("inst-1", "tenant-1", "running")),
+        );
+        let state = InstanceHandlerState::new(persistence.clone())
+            .with_debug_event_limiter(DebugEventRateLimiter::new(2));
+        let custom = |subtype: &str| InstanceEvent {
+            instance_id: "inst-1".to_string(),
+            event_type: InstanceEventType::EventCustom as i32,
+            checkpoint_id: None,
+            payload: b"{}".to_vec(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+            subtype: Some(subtype.to_string()),
+        };
+
+        for _ in 0..5 {
+            let result = handle_instance_event(&state, custom("step_debug_start"))
+                .await
+                .unwrap();
+            assert!(
+                result.success,
+                "dropped debug events are still acknowledged"
+            );
+        }
+        handle_instance_event(&state, custom("workflow_log"))
+            .await
+            .unwrap();
+
+        let events = persistence.get_events();
+        let debug_events = events
+            .iter()
+            .filter(|e| e.subtype.as_deref() == Some("step_debug_start"))
+            .count();
+        // The window is one second; a boundary crossing mid-test admits at most
+        // one more pair.
+        assert!((2..=4).contains(&debug_events), "stored {debug_events}");
+        assert!(
+            events
+                .iter()
+                .any(|e| e.subtype.as_deref() == Some("workflow_log")),
+            "non-debug events are never rate limited"
+        );
+    }
 }
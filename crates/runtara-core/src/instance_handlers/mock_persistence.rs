@@ -14,9 +14,9 @@ use chrono::{DateTime, Utc};
 
 use crate::error::CoreError;
 use crate::persistence::{
-    CheckpointRecord, CompleteInstanceGuard, CompleteInstanceParams, CustomSignalRecord,
-    EventRecord, InstanceRecord, ListEventsFilter, ListStepSummariesFilter, Persistence,
-    SignalRecord, StepSummaryRecord,
+    CheckpointHints, CheckpointRecord, CompleteInstanceGuard, CompleteInstanceParams,
+    CustomSignalRecord, EventRecord, InstanceRecord, ListEventsFilter, ListStepSummariesFilter,
+    Persistence, SignalRecord, StepSummaryRecord,
 };
 
 /// Mock persistence for handler unit tests.
@@ -86,6 +86,15 @@ impl MockPersistence {
         self
     }
 
+    /// Rewrite an instance's status and `status_version` in place, as a
+    /// status write from elsewhere would.
+    pub(crate) fn set_status_version(&self, instance_id: &str, status: &str, version: i64) {
+        let mut instances = self.instances.lock().unwrap();
+        let inst = instances.get_mut(instance_id).expect("instance exists");
+        inst.status = status.to_string();
+        inst.status_version = version;
+    }
+
     #[allow(dead_code)]
     pub(crate) fn set_fail_register(&self) {
         *self.fail_register.lock().unwrap() = true;
@@ -121,6 +130,7 @@ pub(crate) fn make_instance(instance_id: &str, tenant_id: &str, status: &str) ->
         exit_code: None,
         recovery_attempts: 0,
         recovery_marker: None,
+        status_version: 0,
     }
 }
 
@@ -140,6 +150,8 @@ pub(crate) fn make_checkpoint(
         compensation_data: None,
         compensation_state: None,
         compensation_order: 0,
+        content_type: None,
+        shape: None,
     }
 }
 
@@ -223,10 +235,17 @@ impl Persistence for MockPersistence {
                 CompleteInstanceGuard::Any => Err(CoreError::InstanceNotFound {
                     instance_id: params.instance_id.to_string(),
                 }),
-                CompleteInstanceGuard::OnlyRunning => Ok(false),
+                CompleteInstanceGuard::OnlyRunning | CompleteInstanceGuard::OnlyPending => {
+                    Ok(false)
+                }
             };
         };
-        if matches!(params.guard, CompleteInstanceGuard::OnlyRunning) && inst.status != "running" {
+        let guarded_status = match params.guard {
+            CompleteInstanceGuard::Any => None,
+            CompleteInstanceGuard::OnlyRunning => Some("running"),
+            CompleteInstanceGuard::OnlyPending => Some("pending"),
+        };
+        if guarded_status.is_some_and(|status| inst.status != status) {
             return Ok(false);
         }
         inst.status = params.status.to_string();
@@ -268,6 +287,23 @@ impl Persistence for MockPersistence {
         Ok(())
     }
 
+    async fn save_checkpoint_with_hints(
+        &self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints<'_>,
+    ) -> std::result::Result<(), CoreError> {
+        let mut cp = make_checkpoint(instance_id, checkpoint_id, state);
+        cp.content_type = hints.content_type.map(str::to_string);
+        cp.shape = hints.shape.map(str::to_string);
+        self.checkpoints
+            .lock()
+            .unwrap()
+            .insert((instance_id.to_string(), checkpoint_id.to_string()), cp);
+        Ok(())
+    }
+
     async fn load_checkpoint(
         &self,
         instance_id: &str,
@@ -303,6 +339,48 @@ impl Persistence for MockPersistence {
         Ok(0)
     }
 
+    async fn delete_checkpoints_with_prefix(
+        &self,
+        instance_id: &str,
+        prefix: &str,
+    ) -> std::result::Result<u64, CoreError> {
+        let resume = self
+            .instances
+            .lock()
+            .unwrap()
+            .get(instance_id)
+            .and_then(|inst| inst.checkpoint_id.clone());
+        let mut checkpoints = self.checkpoints.lock().unwrap();
+        let before = checkpoints.len();
+        checkpoints.retain(|(inst, id), cp| {
+            inst != instance_id
+                || !id.starts_with(prefix)
+                || cp.is_compensatable
+                || resume.as_deref() == Some(id.as_str())
+        });
+        Ok((before - checkpoints.len()) as u64)
+    }
+
+    async fn list_checkpoint_ids(
+        &self,
+        instance_id: &str,
+        prefix: Option<&str>,
+        since: Option<DateTime<Utc>>,
+    ) -> std::result::Result<Vec<String>, CoreError> {
+        let checkpoints = self.checkpoints.lock().unwrap();
+        let mut rows: Vec<_> = checkpoints
+            .iter()
+            .filter(|((inst, id), cp)| {
+                inst == instance_id
+                    && prefix.is_none_or(|p| id.starts_with(p))
+                    && since.is_none_or(|since| cp.created_at >= since)
+            })
+            .map(|((_, id), cp)| (cp.created_at, id.clone()))
+            .collect();
+        rows.sort();
+        Ok(rows.into_iter().map(|(_, id)| id).collect())
+    }
+
     async fn insert_event(&self, event: &EventRecord) -> std::result::Result<(), CoreError> {
         self.events.lock().unwrap().push(event.clone());
         Ok(())
@@ -6,7 +6,9 @@
 //! events, signals, etc.), split into focused submodules:
 //!
 //! - [`registration`]: `handle_register_instance`
-//! - [`checkpoint`]: `handle_checkpoint`, `handle_get_checkpoint`, `handle_sleep`
+//! - [`compatibility`]: the registration version handshake
+//! - [`checkpoint`]: `handle_checkpoint`, `handle_get_checkpoint`,
+//!   `handle_list_checkpoint_keys`, `handle_compact_checkpoints`, `handle_sleep`
 //! - [`signal`]: `handle_poll_signals`, `handle_signal_ack`
 //! - [`event`]: `handle_instance_event`, `handle_retry_attempt`
 //! - [`status`]: `handle_get_instance_status`
@@ -15,6 +17,7 @@
 //! - [`mappers`]: enum-to-string helpers used by the HTTP layer
 
 mod checkpoint;
+mod compatibility;
 mod event;
 mod mappers;
 mod registration;
@@ -26,7 +29,14 @@ mod types;
 #[cfg(test)]
 pub(crate) mod mock_persistence;
 
-pub use self::checkpoint::{handle_checkpoint, handle_get_checkpoint, handle_sleep};
+pub use self::checkpoint::{
+    CHECKPOINTS_COMPACTED_EVENT, handle_checkpoint, handle_compact_checkpoints,
+    handle_get_checkpoint, handle_list_checkpoint_keys, handle_sleep,
+};
+pub use self::compatibility::{
+    MAX_SUPPORTED_PROTOCOL_VERSION, MIN_SUPPORTED_PROTOCOL_VERSION, ReportedVersions,
+    VERSION_WARNING_EVENT, VersionCompatibility, check_version_compatibility,
+};
 pub use self::event::{handle_instance_event, handle_retry_attempt};
 pub use self::mappers::{map_event_type, map_signal_type, map_status};
 pub use self::registration::handle_register_instance;
@@ -6,10 +6,14 @@ use anyhow::Result;
 use chrono::Utc;
 use tracing::{debug, info, instrument, warn};
 
+use super::compatibility::{
+    MAX_SUPPORTED_PROTOCOL_VERSION, MIN_SUPPORTED_PROTOCOL_VERSION, VERSION_WARNING_EVENT,
+    VersionCompatibility, check_version_compatibility,
+};
 use super::state::InstanceHandlerState;
 use super::types::{
-    ERROR_MAX_CONCURRENT_INSTANCES, ERROR_SERVER_DRAINING, RegisterInstanceRequest,
-    RegisterInstanceResponse,
+    ERROR_MAX_CONCURRENT_INSTANCES, ERROR_SERVER_DRAINING, ERROR_TENANT_MISMATCH,
+    RegisterInstanceRequest, RegisterInstanceResponse,
 };
 use crate::persistence::EventRecord;
 
@@ -22,6 +26,8 @@ use crate::persistence::EventRecord;
 ///
 /// Returns an error response if:
 /// - `instance_id` or `tenant_id` is empty
+/// - The reported protocol version is outside the supported range
+/// - The instance exists under a different tenant
 /// - A specified `checkpoint_id` doesn't exist
 #[instrument(skip(state, request), fields(
     instance_id = %request.instance_id,
@@ -43,6 +49,7 @@ pub async fn handle_register_instance(
         return Ok(RegisterInstanceResponse {
             success: false,
             error: "instance_id is required".to_string(),
+            ..Default::default()
         });
     }
 
@@ -51,23 +58,63 @@ pub async fn handle_register_instance(
         return Ok(RegisterInstanceResponse {
             success: false,
             error: "tenant_id is required".to_string(),
+            ..Default::default()
         });
     }
 
-    // 3. Refuse new registrations when the core is draining. Existing instances
-    //    (which already have a row in persistence) can still resume.
-    let instance_exists = state
+    // Version handshake: refuse incompatible binaries before touching state.
+    let warnings = match check_version_compatibility(&request.versions) {
+        VersionCompatibility::Compatible => Vec::new(),
+        VersionCompatibility::CompatibleWithWarnings(warnings) => {
+            warn!(versions = ?request.versions, ?warnings, "Instance registering with version warnings");
+            warnings
+        }
+        VersionCompatibility::Incompatible { reason } => {
+            warn!(versions = ?request.versions, %reason, "Refusing registration: incompatible version");
+            return Ok(RegisterInstanceResponse {
+                success: false,
+                error: reason,
+                supported_protocol_versions: Some((
+                    MIN_SUPPORTED_PROTOCOL_VERSION,
+                    MAX_SUPPORTED_PROTOCOL_VERSION,
+                )),
+                ..Default::default()
+            });
+        }
+    };
+
+    // 3. An existing instance only re-registers under its own tenant, so the
+    //    tenant bound into the session token is the instance's real one.
+    let existing = state
         .persistence
         .get_instance(&request.instance_id)
         .await
-        .map(|opt| opt.is_some())
-        .unwrap_or(false);
+        .ok()
+        .flatten();
+    let instance_exists = existing.is_some();
 
+    if let Some(instance) = &existing
+        && instance.tenant_id != request.tenant_id
+    {
+        warn!(
+            owner_tenant_id = %instance.tenant_id,
+            "Refusing registration: instance belongs to a different tenant"
+        );
+        return Ok(RegisterInstanceResponse {
+            success: false,
+            error: ERROR_TENANT_MISMATCH.to_string(),
+            ..Default::default()
+        });
+    }
+
+    // Refuse new registrations when the core is draining. Existing instances
+    // (which already have a row in persistence) can still resume.
     if !instance_exists && state.is_draining() {
         info!("Refusing registration: server draining");
         return Ok(RegisterInstanceResponse {
             success: false,
             error: ERROR_SERVER_DRAINING.to_string(),
+            ..Default::default()
         });
     }
 
@@ -85,12 +132,14 @@ pub async fn handle_register_instance(
                 return Ok(RegisterInstanceResponse {
                     success: false,
                     error: format!("Checkpoint '{}' not found", cp_id),
+                    ..Default::default()
                 });
             }
             Err(e) => {
                 return Ok(RegisterInstanceResponse {
                     success: false,
                     error: format!("Failed to verify checkpoint: {}", e),
+                    ..Default::default()
                 });
             }
         }
@@ -109,6 +158,7 @@ pub async fn handle_register_instance(
                 return Ok(RegisterInstanceResponse {
                     success: false,
                     error: ERROR_MAX_CONCURRENT_INSTANCES.to_string(),
+                    ..Default::default()
                 });
             }
             Ok(_) => {}
@@ -129,6 +179,7 @@ pub async fn handle_register_instance(
             return Ok(RegisterInstanceResponse {
                 success: false,
                 error: format!("Failed to create instance: {}", e),
+                ..Default::default()
             });
         }
     }
@@ -143,16 +194,37 @@ pub async fn handle_register_instance(
         return Ok(RegisterInstanceResponse {
             success: false,
             error: format!("Failed to update instance status: {}", e),
+            ..Default::default()
         });
     }
 
-    // 6. Insert started event
+    // 6. Supersede any earlier registration: writes still carrying the
+    //    previous epoch are refused from here on (see `crate::fencing`).
+    let execution_epoch = match state
+        .persistence
+        .advance_execution_epoch(&request.instance_id)
+        .await
+    {
+        Ok(epoch) => epoch,
+        Err(e) => {
+            return Ok(RegisterInstanceResponse {
+                success: false,
+                error: format!("Failed to issue execution epoch: {}", e),
+                ..Default::default()
+            });
+        }
+    };
+
+    // 7. Insert started event. Reported versions ride along as its payload so
+    //    environment can surface them without a schema change.
     let event = EventRecord {
         id: None,
         instance_id: request.instance_id.clone(),
         event_type: "started".to_string(),
         checkpoint_id: request.checkpoint_id.clone(),
-        payload: None,
+        payload: (!request.versions.is_empty())
+            .then(|| serde_json::to_vec(&request.versions).ok())
+            .flatten(),
         created_at: started_at,
         subtype: None,
     };
@@ -161,11 +233,36 @@ pub async fn handle_register_instance(
         // Don't fail registration just because event logging failed
     }
 
-    info!("Instance registered successfully");
+    if !warnings.is_empty() {
+        let event = EventRecord {
+            id: None,
+            instance_id: request.instance_id.clone(),
+            event_type: "custom".to_string(),
+            checkpoint_id: None,
+            payload: serde_json::to_vec(&serde_json::json!({
+                "warnings": warnings,
+                "versions": request.versions,
+            }))
+            .ok(),
+            created_at: started_at,
+            subtype: Some(VERSION_WARNING_EVENT.to_string()),
+        };
+        if let Err(e) = state.persistence.insert_event(&event).await {
+            warn!("Failed to insert version warning event: {}", e);
+        }
+    }
+
+    info!(execution_epoch, "Instance registered successfully");
 
     Ok(RegisterInstanceResponse {
         success: true,
         error: String::new(),
+        warnings,
+        session_token: state
+            .sessions
+            .issue(&request.instance_id, &request.tenant_id),
+        execution_epoch: (execution_epoch > 0).then_some(execution_epoch),
+        ..Default::default()
     })
 }
 
@@ -175,9 +272,12 @@ mod tests {
     use std::sync::atomic::Ordering;
 
     use super::*;
+    use crate::instance_handlers::ReportedVersions;
     use crate::instance_handlers::mock_persistence::{
         MockPersistence, make_checkpoint, make_instance,
     };
+    use crate::persistence::Persistence;
+    use crate::session::InstanceSessions;
 
     #[tokio::test]
     async fn test_register_empty_instance_id() {
@@ -188,6 +288,7 @@ mod tests {
             instance_id: "".to_string(),
             tenant_id: "tenant-1".to_string(),
             checkpoint_id: None,
+            versions: ReportedVersions::default(),
         };
 
         let result = handle_register_instance(&state, request).await.unwrap();
@@ -204,6 +305,7 @@ mod tests {
             instance_id: "inst-1".to_string(),
             tenant_id: "".to_string(),
             checkpoint_id: None,
+            versions: ReportedVersions::default(),
         };
 
         let result = handle_register_instance(&state, request).await.unwrap();
@@ -220,6 +322,7 @@ mod tests {
             instance_id: "inst-new".to_string(),
             tenant_id: "tenant-1".to_string(),
             checkpoint_id: None,
+            versions: ReportedVersions::default(),
         };
 
         let result = handle_register_instance(&state, request).await.unwrap();
@@ -238,10 +341,55 @@ mod tests {
             instance_id: "inst-1".to_string(),
             tenant_id: "tenant-1".to_string(),
             checkpoint_id: None,
+            versions: ReportedVersions::default(),
         };
 
         let result = handle_register_instance(&state, request).await.unwrap();
         assert!(result.success);
+        assert!(result.session_token.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_register_existing_instance_under_other_tenant_refused() {
+        let persistence = Arc::new(
+            MockPersistence::new().with_instance(make_instance("inst-1", "tenant-1", "pending")),
+        );
+        let state = InstanceHandlerState::new(persistence.clone());
+
+        let request = RegisterInstanceRequest {
+            instance_id: "inst-1".to_string(),
+            tenant_id: "tenant-2".to_string(),
+            checkpoint_id: None,
+            versions: ReportedVersions::default(),
+        };
+
+        let result = handle_register_instance(&state, request).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error, ERROR_TENANT_MISMATCH);
+        let instance = persistence.get_instance("inst-1").await.unwrap().unwrap();
+        assert_eq!(instance.status, "pending");
+    }
+
+    #[tokio::test]
+    async fn test_register_issues_session_token_when_enabled() {
+        let persistence = Arc::new(MockPersistence::new());
+        let sessions = InstanceSessions::with_secret("s3cret");
+        let state = InstanceHandlerState::new(persistence).with_sessions(sessions.clone());
+
+        let request = RegisterInstanceRequest {
+            instance_id: "inst-1".to_string(),
+            tenant_id: "tenant-1".to_string(),
+            checkpoint_id: None,
+            versions: ReportedVersions::default(),
+        };
+
+        let result = handle_register_instance(&state, request).await.unwrap();
+        let token = result.session_token.expect("session token");
+        let binding = sessions
+            .authorize(Some(&token), "inst-1", Some("tenant-1"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(binding.tenant_id, "tenant-1");
     }
 
     #[tokio::test]
@@ -257,6 +405,7 @@ mod tests {
             instance_id: "inst-1".to_string(),
             tenant_id: "tenant-1".to_string(),
             checkpoint_id: Some("cp-1".to_string()),
+            versions: ReportedVersions::default(),
         };
 
         let result = handle_register_instance(&state, request).await.unwrap();
@@ -274,6 +423,7 @@ mod tests {
             instance_id: "inst-1".to_string(),
             tenant_id: "tenant-1".to_string(),
             checkpoint_id: Some("nonexistent".to_string()),
+            versions: ReportedVersions::default(),
         };
 
         let result = handle_register_instance(&state, request).await.unwrap();
@@ -291,6 +441,7 @@ mod tests {
             instance_id: "inst-1".to_string(),
             tenant_id: "tenant-1".to_string(),
             checkpoint_id: None,
+            versions: ReportedVersions::default(),
         };
 
         let result = handle_register_instance(&state, request).await.unwrap();
@@ -313,6 +464,7 @@ mod tests {
             instance_id: "new-inst".to_string(),
             tenant_id: "tenant-1".to_string(),
             checkpoint_id: None,
+            versions: ReportedVersions::default(),
         };
 
         let resp = handle_register_instance(&state, request).await.unwrap();
@@ -334,6 +486,7 @@ mod tests {
             instance_id: "inst-1".to_string(),
             tenant_id: "tenant-1".to_string(),
             checkpoint_id: None,
+            versions: ReportedVersions::default(),
         };
 
         let resp = handle_register_instance(&state, request).await.unwrap();
@@ -349,6 +502,7 @@ mod tests {
             instance_id: "new-inst".to_string(),
             tenant_id: "tenant-1".to_string(),
             checkpoint_id: None,
+            versions: ReportedVersions::default(),
         };
 
         let resp = handle_register_instance(&state, request).await.unwrap();
@@ -365,9 +519,53 @@ mod tests {
             instance_id: "new-inst".to_string(),
             tenant_id: "tenant-1".to_string(),
             checkpoint_id: None,
+            versions: ReportedVersions::default(),
         };
 
         let resp = handle_register_instance(&state, request).await.unwrap();
         assert!(resp.success);
     }
+
+    #[tokio::test]
+    async fn test_register_without_versions_allowed_with_warnings() {
+        let persistence = Arc::new(MockPersistence::new());
+        let state = InstanceHandlerState::new(persistence);
+
+        let request = RegisterInstanceRequest {
+            instance_id: "legacy-inst".to_string(),
+            tenant_id: "tenant-1".to_string(),
+            checkpoint_id: None,
+            versions: ReportedVersions::default(),
+        };
+
+        let resp = handle_register_instance(&state, request).await.unwrap();
+        assert!(resp.success);
+        assert!(!resp.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_register_rejects_incompatible_protocol() {
+        let persistence = Arc::new(MockPersistence::new());
+        let state = InstanceHandlerState::new(persistence);
+
+        let request = RegisterInstanceRequest {
+            instance_id: "future-inst".to_string(),
+            tenant_id: "tenant-1".to_string(),
+            checkpoint_id: None,
+            versions: ReportedVersions {
+                protocol_version: Some(MAX_SUPPORTED_PROTOCOL_VERSION + 1),
+                ..Default::default()
+            },
+        };
+
+        let resp = handle_register_instance(&state, request).await.unwrap();
+        assert!(!resp.success);
+        assert_eq!(
+            resp.supported_protocol_versions,
+            Some((
+                MIN_SUPPORTED_PROTOCOL_VERSION,
+                MAX_SUPPORTED_PROTOCOL_VERSION
+            ))
+        );
+    }
 }
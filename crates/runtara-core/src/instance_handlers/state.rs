@@ -5,7 +5,10 @@
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use crate::debug_budget::DebugEventRateLimiter;
+use crate::maintenance::MaintenanceMode;
 use crate::persistence::Persistence;
+use crate::session::InstanceSessions;
 
 /// Shared state for instance handlers.
 ///
@@ -20,6 +23,16 @@ pub struct InstanceHandlerState {
     /// `ERROR_SERVER_DRAINING`. In-flight handlers (checkpoint, event, signal
     /// ack) continue to serve so running instances can suspend cleanly.
     pub draining: Arc<AtomicBool>,
+    /// Maintenance switch. While a window is active, checkpoint, sleep, and
+    /// event writes are refused with `CoreError::Maintenance`. Defaults to
+    /// the process-wide [`MaintenanceMode::global`].
+    pub maintenance: MaintenanceMode,
+    /// Session tokens issued at registration and checked by the HTTP layer.
+    /// Disabled by default.
+    pub sessions: InstanceSessions,
+    /// Per-tenant cap on step debug events. Configured from
+    /// `RUNTARA_DEBUG_EVENTS_PER_TENANT_PER_SEC`.
+    pub debug_event_limiter: Arc<DebugEventRateLimiter>,
 }
 
 impl InstanceHandlerState {
@@ -31,6 +44,9 @@ impl InstanceHandlerState {
             persistence,
             max_concurrent_instances: 0,
             draining: Arc::new(AtomicBool::new(false)),
+            maintenance: MaintenanceMode::global(),
+            sessions: InstanceSessions::disabled(),
+            debug_event_limiter: Arc::new(DebugEventRateLimiter::from_env()),
         }
     }
 
@@ -40,9 +56,24 @@ impl InstanceHandlerState {
             persistence,
             max_concurrent_instances,
             draining: Arc::new(AtomicBool::new(false)),
+            maintenance: MaintenanceMode::global(),
+            sessions: InstanceSessions::disabled(),
+            debug_event_limiter: Arc::new(DebugEventRateLimiter::from_env()),
         }
     }
 
+    /// Issue and check instance session tokens.
+    pub fn with_sessions(mut self, sessions: InstanceSessions) -> Self {
+        self.sessions = sessions;
+        self
+    }
+
+    /// Override the per-tenant debug event rate limiter.
+    pub fn with_debug_event_limiter(mut self, limiter: DebugEventRateLimiter) -> Self {
+        self.debug_event_limiter = Arc::new(limiter);
+        self
+    }
+
     /// Handle to the draining flag so external coordinators (server, environment)
     /// can request drain.
     pub fn draining_handle(&self) -> Arc<AtomicBool> {
@@ -2,12 +2,15 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 //! Read-only instance status query handler.
 
+use std::time::Duration;
+
 use anyhow::Result;
 use tracing::{debug, instrument};
 
 use super::mappers::map_status;
 use super::state::InstanceHandlerState;
 use super::types::{GetInstanceStatusRequest, GetInstanceStatusResponse, InstanceStatus};
+use crate::status_watch::{StatusCursor, wait_for_instance_change};
 
 /// Handle instance status query.
 ///
@@ -16,6 +19,11 @@ use super::types::{GetInstanceStatusRequest, GetInstanceStatusResponse, Instance
 /// - Last checkpoint ID
 /// - Start/finish timestamps
 /// - Output data (if completed) or error message (if failed)
+/// - `status_version`, the change counter for status and checkpoint
+///
+/// With `wait_for_change_from` set this is a long-poll: the response is held
+/// until the instance moves past that version/status or `max_wait_ms`
+/// elapses, and then reflects whatever the instance looks like at that point.
 #[instrument(skip(state, request), fields(instance_id = %request.instance_id))]
 pub async fn handle_get_instance_status(
     state: &InstanceHandlerState,
@@ -23,7 +31,20 @@ pub async fn handle_get_instance_status(
 ) -> Result<GetInstanceStatusResponse> {
     debug!("Getting instance status");
 
-    let instance = state.persistence.get_instance(&request.instance_id).await?;
+    let instance = match request.wait_for_change_from.as_deref() {
+        Some(from) => {
+            let cursor = StatusCursor::parse(from);
+            let max_wait = Duration::from_millis(request.max_wait_ms.unwrap_or(u64::MAX));
+            wait_for_instance_change(
+                state.persistence.as_ref(),
+                &request.instance_id,
+                max_wait,
+                |inst| cursor.is_passed_by(inst, map_status(&inst.status).as_str()),
+            )
+            .await?
+        }
+        None => state.persistence.get_instance(&request.instance_id).await?,
+    };
 
     match instance {
         Some(inst) => {
@@ -37,6 +58,7 @@ pub async fn handle_get_instance_status(
                 finished_at_ms: inst.finished_at.map(|t| t.timestamp_millis()),
                 output: inst.output,
                 error: inst.error,
+                status_version: inst.status_version,
             })
         }
         None => Ok(GetInstanceStatusResponse {
@@ -47,6 +69,7 @@ pub async fn handle_get_instance_status(
             finished_at_ms: None,
             output: None,
             error: Some("Instance not found".to_string()),
+            status_version: 0,
         }),
     }
 }
@@ -65,6 +88,8 @@ mod tests {
 
         let request = GetInstanceStatusRequest {
             instance_id: "nonexistent".to_string(),
+            wait_for_change_from: None,
+            max_wait_ms: None,
         };
 
         let result = handle_get_instance_status(&state, request).await.unwrap();
@@ -81,9 +106,79 @@ mod tests {
 
         let request = GetInstanceStatusRequest {
             instance_id: "inst-1".to_string(),
+            wait_for_change_from: None,
+            max_wait_ms: None,
+        };
+
+        let result = handle_get_instance_status(&state, request).await.unwrap();
+        assert_eq!(result.status, InstanceStatus::StatusRunning as i32);
+    }
+
+    #[tokio::test]
+    async fn test_get_status_returns_immediately_when_already_changed() {
+        let persistence = Arc::new(
+            MockPersistence::new().with_instance(make_instance("inst-1", "tenant-1", "running")),
+        );
+        let state = InstanceHandlerState::new(persistence);
+
+        let request = GetInstanceStatusRequest {
+            instance_id: "inst-1".to_string(),
+            wait_for_change_from: Some("pending".to_string()),
+            max_wait_ms: Some(10_000),
+        };
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            handle_get_instance_status(&state, request),
+        )
+        .await
+        .expect("no wait when the status already differs")
+        .unwrap();
+        assert_eq!(result.status, InstanceStatus::StatusRunning as i32);
+    }
+
+    #[tokio::test]
+    async fn test_get_status_wakes_on_status_change() {
+        let persistence = Arc::new(
+            MockPersistence::new().with_instance(make_instance("inst-w", "tenant-1", "running")),
+        );
+        let state = InstanceHandlerState::new(persistence.clone());
+
+        let request = GetInstanceStatusRequest {
+            instance_id: "inst-w".to_string(),
+            wait_for_change_from: Some("0".to_string()),
+            max_wait_ms: Some(10_000),
+        };
+        let waiter = tokio::spawn(async move { handle_get_instance_status(&state, request).await });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        persistence.set_status_version("inst-w", "completed", 1);
+        crate::status_watch::notify("inst-w");
+
+        let result = tokio::time::timeout(Duration::from_secs(2), waiter)
+            .await
+            .expect("woken by notify")
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.status, InstanceStatus::StatusCompleted as i32);
+        assert_eq!(result.status_version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_status_times_out_unchanged() {
+        let persistence = Arc::new(
+            MockPersistence::new().with_instance(make_instance("inst-t", "tenant-1", "running")),
+        );
+        let state = InstanceHandlerState::new(persistence);
+
+        let request = GetInstanceStatusRequest {
+            instance_id: "inst-t".to_string(),
+            wait_for_change_from: Some("running".to_string()),
+            max_wait_ms: Some(20),
         };
 
         let result = handle_get_instance_status(&state, request).await.unwrap();
         assert_eq!(result.status, InstanceStatus::StatusRunning as i32);
+        assert_eq!(result.status_version, 0);
     }
 }
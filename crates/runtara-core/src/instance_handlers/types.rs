@@ -85,6 +85,19 @@ impl InstanceStatus {
             _ => None,
         }
     }
+
+    /// Wire name (`pending`, `running`, ...).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::StatusUnknown => "unknown",
+            Self::StatusPending => "pending",
+            Self::StatusRunning => "running",
+            Self::StatusSuspended => "suspended",
+            Self::StatusCompleted => "completed",
+            Self::StatusFailed => "failed",
+            Self::StatusCancelled => "cancelled",
+        }
+    }
 }
 
 /// Register instance request.
@@ -95,14 +108,29 @@ pub struct RegisterInstanceRequest {
     pub tenant_id: String,
     /// Optional checkpoint ID to resume from.
     pub checkpoint_id: Option<String>,
+    /// Protocol/SDK/build versions reported by the binary (all optional).
+    pub versions: super::ReportedVersions,
 }
 
 /// Register instance response.
+#[derive(Default)]
 pub struct RegisterInstanceResponse {
     /// Whether registration succeeded.
     pub success: bool,
     /// Error message if registration failed.
     pub error: String,
+    /// Version warnings when registration succeeded despite unknown or
+    /// drifting versions.
+    pub warnings: Vec<String>,
+    /// Supported protocol range, set only when registration was refused
+    /// because the binary's protocol version is incompatible.
+    pub supported_protocol_versions: Option<(u32, u32)>,
+    /// Session token binding later requests to this instance and tenant,
+    /// set when session binding is enabled.
+    pub session_token: Option<String>,
+    /// Execution epoch issued to this registration, set when the backend
+    /// tracks epochs. Writes carrying an older epoch are refused as fenced.
+    pub execution_epoch: Option<i64>,
 }
 
 /// Checkpoint request.
@@ -113,6 +141,13 @@ pub struct CheckpointRequest {
     pub checkpoint_id: String,
     /// Serialized workflow state.
     pub state: Vec<u8>,
+    /// Content type hint for `state`, persisted for checkpoint browsing.
+    pub content_type: Option<String>,
+    /// Shape tag for `state` (e.g. `step_output`), persisted alongside it.
+    pub shape: Option<String>,
+    /// Additional custom-signal keys the instance is waiting on. A pending
+    /// signal for any of them is returned with the response.
+    pub wait_keys: Vec<String>,
 }
 
 /// Signal forwarded from core to instance.
@@ -126,10 +161,10 @@ pub struct Signal {
     pub payload: Vec<u8>,
 }
 
-/// Custom signal targeted at a specific checkpoint_id.
+/// Custom signal targeted at a specific checkpoint_id or wait key.
 #[derive(Debug, Clone)]
 pub struct CustomSignal {
-    /// Checkpoint ID this signal targets.
+    /// Checkpoint ID or wait key this signal targets.
     pub checkpoint_id: String,
     /// Signal payload bytes.
     pub payload: Vec<u8>,
@@ -174,6 +209,36 @@ pub struct GetCheckpointResponse {
     pub state: Vec<u8>,
 }
 
+/// Checkpoint key listing request (ids only, no state).
+pub struct ListCheckpointKeysRequest {
+    /// Instance identifier.
+    pub instance_id: String,
+    /// Only list checkpoint IDs starting with this prefix.
+    pub prefix: Option<String>,
+    /// Only list checkpoints created at or after this time.
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Checkpoint key listing response.
+pub struct ListCheckpointKeysResponse {
+    /// Matching checkpoint IDs, oldest first.
+    pub checkpoint_ids: Vec<String>,
+}
+
+/// Checkpoint compaction request.
+pub struct CompactCheckpointsRequest {
+    /// Instance identifier.
+    pub instance_id: String,
+    /// Checkpoint ID prefix to delete (e.g. a loop's per-iteration key stem).
+    pub prefix: String,
+}
+
+/// Checkpoint compaction response.
+pub struct CompactCheckpointsResponse {
+    /// Number of checkpoints deleted.
+    pub deleted: u64,
+}
+
 /// Sleep request.
 pub struct SleepRequest {
     /// Instance identifier.
@@ -231,6 +296,13 @@ pub struct InstanceEventResponse {
 pub struct GetInstanceStatusRequest {
     /// Instance identifier.
     pub instance_id: String,
+    /// Long-poll cursor: the `status_version` (or status string) the caller
+    /// last saw. When set, the handler waits for the instance to move past
+    /// it before answering.
+    pub wait_for_change_from: Option<String>,
+    /// Upper bound on the long-poll wait, capped at
+    /// [`crate::status_watch::MAX_LONG_POLL_WAIT`].
+    pub max_wait_ms: Option<u64>,
 }
 
 /// Get instance status response.
@@ -249,6 +321,9 @@ pub struct GetInstanceStatusResponse {
     pub output: Option<Vec<u8>>,
     /// Error message if failed.
     pub error: Option<String>,
+    /// Change counter for the status and checkpoint; pass it back as
+    /// `wait_for_change_from` to long-poll for the next transition.
+    pub status_version: i64,
 }
 
 /// Poll signals request.
@@ -362,3 +437,8 @@ pub const ERROR_SERVER_DRAINING: &str = "server draining";
 /// count has reached `RUNTARA_MAX_CONCURRENT_INSTANCES`. The HTTP layer maps
 /// this to `429 Too Many Requests`.
 pub const ERROR_MAX_CONCURRENT_INSTANCES: &str = "max concurrent instances reached";
+
+/// Error string returned by `handle_register_instance` when the instance
+/// already exists under a different tenant. The HTTP layer maps this to
+/// `403 Forbidden` with code `AUTHORIZATION`.
+pub const ERROR_TENANT_MISMATCH: &str = "instance belongs to a different tenant";
@@ -58,6 +58,7 @@
 //! | `RegisterInstance` | Self-register on startup, optionally resume from checkpoint |
 //! | `Checkpoint` | Save state (or return existing if checkpoint_id exists) + signal delivery |
 //! | `GetCheckpoint` | Read-only checkpoint lookup |
+//! | `ListCheckpointKeys` | Checkpoint ids only (no state), so resume can skip absent keys |
 //! | `Sleep` | Durable sleep - stores wake time in database |
 //! | `InstanceEvent` | Fire-and-forget events (heartbeat, completed, failed, suspended) |
 //! | `GetInstanceStatus` | Query instance status |
@@ -135,6 +136,8 @@
 //! | `RUNTARA_MAX_CONCURRENT_INSTANCES` | No | `32` | Max concurrent instances. Enforced at `register_instance`; fresh registrations past the cap receive `429 Too Many Requests`. Resumes are not counted. Set to `0` to disable. |
 //! | `RUNTARA_SHUTDOWN_GRACE_MS` | No | `60000` | On SIGTERM/SIGINT, how long to wait for running instances to reach a checkpoint before force-stopping. |
 //! | `RUNTARA_SHUTDOWN_INTAKE_GRACE_MS` | No | `5000` | On SIGTERM/SIGINT, how long to wait for intake workers to finish their current unit of work. |
+//! | `RUNTARA_INSTANCE_SESSION_SECRET` | No | - | Enables [`session`] binding: registration returns a signed token and later instance requests for another instance or tenant get `403` with code `AUTHORIZATION`. Replicas must share it. |
+//! | `RUNTARA_REQUIRE_INSTANCE_SESSION` | No | `false` | With a session secret set, also refuse instance requests that carry no token (binaries built before session support send none). |
 //!
 //! # Modules
 //!
@@ -170,6 +173,24 @@ pub mod error;
 /// Compensation framework for saga pattern support.
 pub mod compensation;
 
+/// Content sniffing and structured previews of checkpoint state.
+pub mod checkpoint_preview;
+
+/// Change notification backing long-poll instance status reads.
+pub mod status_watch;
+
+/// Time-boxed maintenance mode refusing instance writes during failovers.
+pub mod maintenance;
+
+/// Session tokens binding instance requests to their registration.
+pub mod session;
+
+/// Execution epochs fencing writes from superseded instance containers.
+pub mod fencing;
+
+/// Debug event sampling budgets and the per-tenant debug event rate cap.
+pub mod debug_budget;
+
 // Server-mode modules (require HTTP transport)
 #[cfg(feature = "server")]
 /// Server configuration loaded from environment variables.
@@ -20,6 +20,7 @@ use tracing::{error, info, warn};
 use runtara_core::config::Config;
 use runtara_core::persistence::{Persistence, PostgresPersistence, SqlitePersistence};
 use runtara_core::runtime::CoreRuntime;
+use runtara_core::session::InstanceSessions;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -87,10 +88,18 @@ async fn main() -> Result<()> {
         Arc::new(SqlitePersistence::new(pool))
     };
 
+    let sessions = InstanceSessions::from_env();
+    info!(
+        enabled = sessions.is_enabled(),
+        required = sessions.is_required(),
+        "Instance session binding"
+    );
+
     // Start the runtime
     let runtime = CoreRuntime::builder()
         .persistence(persistence)
         .bind_addr(config.http_addr)
+        .instance_sessions(sessions)
         .build()?
         .start()
         .await?;
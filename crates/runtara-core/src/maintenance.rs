@@ -0,0 +1,206 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Time-boxed maintenance mode.
+//!
+//! During a planned database failover, checkpoint and event writes fail for
+//! a while and every running instance burns its retry budget at once. While
+//! maintenance mode is on, the instance handlers refuse those writes up front
+//! with [`CoreError::Maintenance`] and a retry-after hint, so SDK clients
+//! wait the window out instead. Reads are still served.
+//!
+//! The state is in-process, like [`crate::status_watch`]: every handler
+//! state in the process shares [`MaintenanceMode::global`]. A window always
+//! expires on its own after its expected duration (capped at
+//! [`MAX_MAINTENANCE_DURATION`]), so a forgotten toggle cannot wedge the core.
+
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::CoreError;
+
+/// Window length used when the caller does not say how long it expects.
+pub const DEFAULT_MAINTENANCE_DURATION: Duration = Duration::from_secs(60);
+
+/// Longest window a single `set` can open.
+pub const MAX_MAINTENANCE_DURATION: Duration = Duration::from_secs(60 * 60);
+
+static MAINTENANCE: OnceLock<MaintenanceMode> = OnceLock::new();
+
+/// Shared maintenance switch. Clones observe the same state.
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceMode {
+    window: Arc<Mutex<Option<Window>>>,
+}
+
+#[derive(Debug, Clone)]
+struct Window {
+    message: String,
+    started_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    deadline: Instant,
+}
+
+/// Snapshot of an active maintenance window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceStatus {
+    /// Operator-supplied reason.
+    pub message: String,
+    /// When the window was opened.
+    pub started_at: DateTime<Utc>,
+    /// When the window expires on its own.
+    pub ends_at: DateTime<Utc>,
+    /// Milliseconds until the window expires.
+    pub retry_after_ms: u64,
+}
+
+impl MaintenanceMode {
+    /// A switch in the off state, independent of the process-wide one.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process-wide switch consulted by default handler states.
+    pub fn global() -> Self {
+        MAINTENANCE.get_or_init(Self::new).clone()
+    }
+
+    /// Open (or replace) a window, or close the current one.
+    ///
+    /// `expected_duration` defaults to [`DEFAULT_MAINTENANCE_DURATION`] and is
+    /// capped at [`MAX_MAINTENANCE_DURATION`]. Returns the window now in
+    /// effect.
+    pub fn set(
+        &self,
+        enabled: bool,
+        expected_duration: Option<Duration>,
+        message: impl Into<String>,
+    ) -> Option<MaintenanceStatus> {
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        if !enabled {
+            *window = None;
+            return None;
+        }
+
+        let duration = expected_duration
+            .filter(|d| !d.is_zero())
+            .unwrap_or(DEFAULT_MAINTENANCE_DURATION)
+            .min(MAX_MAINTENANCE_DURATION);
+        let started_at = Utc::now();
+        let opened = Window {
+            message: message.into(),
+            started_at,
+            ends_at: started_at
+                + chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::zero()),
+            deadline: Instant::now() + duration,
+        };
+        let status = opened.status();
+        *window = Some(opened);
+        status
+    }
+
+    /// The active window, if any. An expired window is cleared here.
+    pub fn status(&self) -> Option<MaintenanceStatus> {
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        let status = window.as_ref().and_then(Window::status);
+        if status.is_none() {
+            *window = None;
+        }
+        status
+    }
+
+    /// Whether a window is currently active.
+    pub fn is_active(&self) -> bool {
+        self.status().is_some()
+    }
+
+    /// Refuse a write while a window is active.
+    pub fn check(&self) -> Result<(), CoreError> {
+        match self.status() {
+            Some(status) => Err(CoreError::Maintenance {
+                message: status.message,
+                retry_after_ms: status.retry_after_ms,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Window {
+    fn status(&self) -> Option<MaintenanceStatus> {
+        let remaining = self.deadline.checked_duration_since(Instant::now())?;
+        if remaining.is_zero() {
+            return None;
+        }
+        Some(MaintenanceStatus {
+            message: self.message.clone(),
+            started_at: self.started_at,
+            ends_at: self.ends_at,
+            // Round up so a client never retries before the window ends.
+            retry_after_ms: remaining.as_micros().div_ceil(1000) as u64,
+        })
+    }
+}
+
+/// Retry-after hint carried by a handler error, when it is a maintenance
+/// refusal.
+pub fn maintenance_retry_after(error: &anyhow::Error) -> Option<Duration> {
+    match error.downcast_ref::<CoreError>() {
+        Some(CoreError::Maintenance { retry_after_ms, .. }) => {
+            Some(Duration::from_millis(*retry_after_ms))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_opens_caps_and_closes() {
+        let mode = MaintenanceMode::new();
+        assert!(mode.status().is_none());
+        assert!(mode.check().is_ok());
+
+        let status = mode
+            .set(true, Some(Duration::from_secs(20)), "pg failover")
+            .expect("window open");
+        assert_eq!(status.message, "pg failover");
+        assert!(status.retry_after_ms > 19_000 && status.retry_after_ms <= 20_000);
+
+        let err = mode.check().unwrap_err();
+        assert_eq!(err.error_code(), "MAINTENANCE");
+        let err = anyhow::Error::from(err);
+        assert!(maintenance_retry_after(&err).is_some());
+
+        let capped = mode
+            .set(true, Some(Duration::from_secs(24 * 60 * 60)), "")
+            .unwrap();
+        assert!(capped.retry_after_ms <= MAX_MAINTENANCE_DURATION.as_millis() as u64);
+
+        assert!(mode.set(false, None, "").is_none());
+        assert!(!mode.is_active());
+    }
+
+    #[test]
+    fn window_expires_on_its_own() {
+        let mode = MaintenanceMode::new();
+        mode.set(true, Some(Duration::from_millis(20)), "short");
+        let clone = mode.clone();
+        assert!(clone.is_active());
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(!mode.is_active());
+        assert!(clone.check().is_ok());
+    }
+
+    #[test]
+    fn other_errors_carry_no_retry_after() {
+        let err = anyhow::Error::from(CoreError::InstanceNotFound {
+            instance_id: "i".to_string(),
+        });
+        assert!(maintenance_retry_after(&err).is_none());
+    }
+}
@@ -3,7 +3,8 @@
 //! Checkpoint-family operations shared by both backends.
 //!
 //! Migrates: `save_checkpoint`, `load_checkpoint`, `list_checkpoints`,
-//! `count_checkpoints`.
+//! `count_checkpoints`. Also hosts `delete_checkpoints_with_prefix` and
+//! `list_checkpoint_ids`, which were written against the shared layer.
 //!
 //! Phase 3 (SYN-394) applies `CoreError::CheckpointSaveFailed` wrapping
 //! to `op_save_checkpoint` on both backends via
@@ -31,12 +32,43 @@ macro_rules! impl_checkpoint_ops {
             /// INSERT (or UPSERT on Postgres) a checkpoint row. Wraps any
             /// sqlx error into `CoreError::CheckpointSaveFailed` with the
             /// instance ID attached.
-            pub(crate) async fn op_save_checkpoint(
-                pool: &$Pool,
+            pub(crate) async fn op_save_checkpoint<'e, E>(
+                pool: E,
+                instance_id: &str,
+                checkpoint_id: &str,
+                state: &[u8],
+            ) -> ::core::result::Result<(), $crate::error::CoreError>
+            where
+                E: ::sqlx::Executor<
+                        'e,
+                        Database = <$Dialect as $crate::persistence::dialect::Dialect>::Database,
+                    >,
+            {
+                Self::op_save_checkpoint_with_hints(
+                    pool,
+                    instance_id,
+                    checkpoint_id,
+                    state,
+                    $crate::persistence::CheckpointHints::default(),
+                )
+                .await
+            }
+
+            /// Same as `op_save_checkpoint`, additionally persisting the
+            /// writer's content type / shape hints.
+            pub(crate) async fn op_save_checkpoint_with_hints<'e, E>(
+                pool: E,
                 instance_id: &str,
                 checkpoint_id: &str,
                 state: &[u8],
-            ) -> ::core::result::Result<(), $crate::error::CoreError> {
+                hints: $crate::persistence::CheckpointHints<'_>,
+            ) -> ::core::result::Result<(), $crate::error::CoreError>
+            where
+                E: ::sqlx::Executor<
+                        'e,
+                        Database = <$Dialect as $crate::persistence::dialect::Dialect>::Database,
+                    >,
+            {
                 use $crate::persistence::common::error::wrap_checkpoint_save;
                 use $crate::persistence::dialect::Dialect;
                 let sql = <$Dialect>::sql_save_checkpoint();
@@ -44,6 +76,8 @@ macro_rules! impl_checkpoint_ops {
                     .bind(instance_id)
                     .bind(checkpoint_id)
                     .bind(state)
+                    .bind(hints.content_type)
+                    .bind(hints.shape)
                     .execute(pool)
                     .await
                     .map_err(|e| wrap_checkpoint_save(e, instance_id))?;
@@ -63,7 +97,7 @@ macro_rules! impl_checkpoint_ops {
                 let p1 = <$Dialect>::placeholder(1);
                 let p2 = <$Dialect>::placeholder(2);
                 let sql = format!(
-                    "SELECT id, instance_id, checkpoint_id, state, created_at \
+                    "SELECT id, instance_id, checkpoint_id, state, created_at, content_type, shape \
                      FROM checkpoints \
                      WHERE instance_id = {p1} AND checkpoint_id = {p2}"
                 );
@@ -128,6 +162,42 @@ macro_rules! impl_checkpoint_ops {
                     .await?;
                 Ok(count.0)
             }
+
+            /// DELETE checkpoints whose id starts with `prefix`, keeping the
+            /// instance's current resume checkpoint. Returns rows deleted.
+            pub(crate) async fn op_delete_checkpoints_with_prefix(
+                pool: &$Pool,
+                instance_id: &str,
+                prefix: &str,
+            ) -> ::core::result::Result<u64, $crate::error::CoreError> {
+                use $crate::persistence::dialect::Dialect;
+                let sql = <$Dialect>::sql_delete_checkpoints_with_prefix();
+                let result = ::sqlx::query(sql)
+                    .bind(instance_id)
+                    .bind(prefix)
+                    .execute(pool)
+                    .await?;
+                Ok(result.rows_affected())
+            }
+
+            /// SELECT just the checkpoint ids of an instance, optionally
+            /// restricted to a prefix and to rows created at/after `since`.
+            pub(crate) async fn op_list_checkpoint_ids(
+                pool: &$Pool,
+                instance_id: &str,
+                prefix: ::core::option::Option<&str>,
+                since: ::core::option::Option<::chrono::DateTime<::chrono::Utc>>,
+            ) -> ::core::result::Result<::std::vec::Vec<String>, $crate::error::CoreError> {
+                use $crate::persistence::dialect::Dialect;
+                let sql = <$Dialect>::sql_list_checkpoint_ids();
+                let rows: ::std::vec::Vec<(String,)> = ::sqlx::query_as(sql)
+                    .bind(instance_id)
+                    .bind(prefix)
+                    .bind(since)
+                    .fetch_all(pool)
+                    .await?;
+                Ok(rows.into_iter().map(|(id,)| id).collect())
+            }
         }
     };
 }
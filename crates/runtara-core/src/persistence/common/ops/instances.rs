@@ -72,7 +72,7 @@ macro_rules! impl_instance_ops {
                     "SELECT instance_id, tenant_id, definition_version, \
                             {status_col}, {termination_col}, checkpoint_id, attempt, max_attempts, \
                             created_at, started_at, finished_at, input, output, error, sleep_until, \
-                            recovery_attempts, recovery_marker \
+                            recovery_attempts, recovery_marker, status_version \
                      FROM instances \
                      WHERE instance_id = {p1}"
                 );
@@ -114,7 +114,8 @@ macro_rules! impl_instance_ops {
                     let sql = format!(
                         "UPDATE instances \
                          SET status = {p2}{status_cast}, started_at = {p3}, \
-                             finished_at = NULL, termination_reason = NULL \
+                             finished_at = NULL, termination_reason = NULL, \
+                             status_version = status_version + 1 \
                          WHERE instance_id = {p1}"
                     );
                     ::sqlx::query(&sql)
@@ -130,7 +131,8 @@ macro_rules! impl_instance_ops {
                 } else {
                     let sql = format!(
                         "UPDATE instances \
-                         SET status = {p2}{status_cast} \
+                         SET status = {p2}{status_cast}, \
+                             status_version = status_version + 1 \
                          WHERE instance_id = {p1}"
                     );
                     ::sqlx::query(&sql)
@@ -143,29 +145,41 @@ macro_rules! impl_instance_ops {
                             details: e.to_string(),
                         })?
                 };
-                not_found_if_empty::<<$Dialect as Dialect>::Database>(&result, instance_id)
+                not_found_if_empty::<<$Dialect as Dialect>::Database>(&result, instance_id)?;
+                $crate::status_watch::notify(instance_id);
+                Ok(())
             }
 
             /// UPDATE the instance's `checkpoint_id`. Errors with
             /// `InstanceNotFound` if no row matched.
-            pub(crate) async fn op_update_instance_checkpoint(
-                pool: &$Pool,
+            pub(crate) async fn op_update_instance_checkpoint<'e, E>(
+                pool: E,
                 instance_id: &str,
                 checkpoint_id: &str,
-            ) -> ::core::result::Result<(), $crate::error::CoreError> {
+            ) -> ::core::result::Result<(), $crate::error::CoreError>
+            where
+                E: ::sqlx::Executor<
+                        'e,
+                        Database = <$Dialect as $crate::persistence::dialect::Dialect>::Database,
+                    >,
+            {
                 use $crate::persistence::common::error::not_found_if_empty;
                 use $crate::persistence::dialect::Dialect;
                 let p1 = <$Dialect>::placeholder(1);
                 let p2 = <$Dialect>::placeholder(2);
                 let sql = format!(
-                    "UPDATE instances SET checkpoint_id = {p2} WHERE instance_id = {p1}"
+                    "UPDATE instances \
+                     SET checkpoint_id = {p2}, status_version = status_version + 1 \
+                     WHERE instance_id = {p1}"
                 );
                 let result = ::sqlx::query(&sql)
                     .bind(instance_id)
                     .bind(checkpoint_id)
                     .execute(pool)
                     .await?;
-                not_found_if_empty::<<$Dialect as Dialect>::Database>(&result, instance_id)
+                not_found_if_empty::<<$Dialect as Dialect>::Database>(&result, instance_id)?;
+                $crate::status_watch::notify(instance_id);
+                Ok(())
             }
 
             /// Unified `complete_instance` op covering all five legacy
@@ -185,13 +199,20 @@ macro_rules! impl_instance_ops {
             /// - [`CompleteInstanceGuard::OnlyRunning`] appends
             ///   `AND status = 'running'` to the `WHERE` clause, turning
             ///   a zero-row result into `Ok(false)` instead of
-            ///   `InstanceNotFound`.
+            ///   `InstanceNotFound`. `OnlyPending` does the same for
+            ///   `'pending'`.
             /// - [`CompleteInstanceGuard::Any`] returns `Ok(true)` on
             ///   success or `Err(InstanceNotFound)` on miss.
-            pub(crate) async fn op_complete_instance_unified(
-                pool: &$Pool,
+            pub(crate) async fn op_complete_instance_unified<'e, E>(
+                pool: E,
                 params: $crate::persistence::CompleteInstanceParams<'_>,
-            ) -> ::core::result::Result<bool, $crate::error::CoreError> {
+            ) -> ::core::result::Result<bool, $crate::error::CoreError>
+            where
+                E: ::sqlx::Executor<
+                        'e,
+                        Database = <$Dialect as $crate::persistence::dialect::Dialect>::Database,
+                    >,
+            {
                 use $crate::persistence::CompleteInstanceGuard;
                 use $crate::persistence::common::error::{RowsAffected, not_found_if_empty};
                 use $crate::persistence::dialect::{Dialect, EnumKind};
@@ -209,6 +230,7 @@ macro_rules! impl_instance_ops {
                 let guard_clause = match params.guard {
                     CompleteInstanceGuard::Any => "",
                     CompleteInstanceGuard::OnlyRunning => " AND status = 'running'",
+                    CompleteInstanceGuard::OnlyPending => " AND status = 'pending'",
                 };
                 let sql = format!(
                     "UPDATE instances \
@@ -222,7 +244,8 @@ macro_rules! impl_instance_ops {
                          finished_at = CASE \
                              WHEN {p2} IN ('completed', 'failed', 'cancelled', 'suspended') THEN {now} \
                              ELSE finished_at \
-                         END \
+                         END, \
+                         status_version = status_version + 1 \
                      WHERE instance_id = {p1}{guard_clause}"
                 );
                 let result = ::sqlx::query(&sql)
@@ -240,16 +263,149 @@ macro_rules! impl_instance_ops {
                         operation: "complete_instance".into(),
                         details: e.to_string(),
                     })?;
-                match params.guard {
-                    CompleteInstanceGuard::OnlyRunning => Ok(result.rows_affected_generic() > 0),
+                let updated = match params.guard {
+                    CompleteInstanceGuard::OnlyRunning | CompleteInstanceGuard::OnlyPending => {
+                        result.rows_affected_generic() > 0
+                    }
                     CompleteInstanceGuard::Any => {
                         not_found_if_empty::<<$Dialect as Dialect>::Database>(
                             &result,
                             params.instance_id,
                         )?;
-                        Ok(true)
+                        true
                     }
+                };
+                if updated {
+                    $crate::status_watch::notify(params.instance_id);
+                }
+                Ok(updated)
+            }
+
+            /// Atomically claim a `pending` instance for launch.
+            ///
+            /// Conditional `UPDATE status = 'running', started_at = NOW
+            /// WHERE instance_id = ? AND status = 'pending'`. Returns `true`
+            /// when this caller won the row, `false` when it was already
+            /// claimed or cancelled before start. Row-level locking makes the
+            /// claim and a guarded cancel mutually exclusive.
+            pub(crate) async fn op_claim_pending_instance(
+                pool: &$Pool,
+                instance_id: &str,
+            ) -> ::core::result::Result<bool, $crate::error::CoreError> {
+                use $crate::persistence::dialect::{Dialect, EnumKind};
+                let p1 = <$Dialect>::placeholder(1);
+                let status_cast = <$Dialect>::enum_cast(EnumKind::InstanceStatus);
+                let now = <$Dialect>::NOW;
+                let sql = format!(
+                    "UPDATE instances \
+                     SET status = 'running'{status_cast}, started_at = {now}, \
+                         status_version = status_version + 1 \
+                     WHERE instance_id = {p1} AND status = 'pending'"
+                );
+                let result = ::sqlx::query(&sql)
+                    .bind(instance_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| $crate::error::CoreError::DatabaseError {
+                        operation: "claim_pending_instance".into(),
+                        details: e.to_string(),
+                    })?;
+                let claimed = result.rows_affected() == 1;
+                if claimed {
+                    $crate::status_watch::notify(instance_id);
+                }
+                Ok(claimed)
+            }
+
+            /// Atomically claim a stopped instance for a resume launch.
+            ///
+            /// Conditional `UPDATE status = 'running', started_at = NOW,
+            /// sleep_until = NULL WHERE instance_id = ? AND status IN
+            /// ('suspended', 'failed', 'cancelled')`, also clearing
+            /// `finished_at` and `termination_reason` like the plain running
+            /// transition. Returns `true` when this caller won the row and
+            /// `false` when another resume or wake already moved it to
+            /// `running`, so only one launch path proceeds.
+            pub(crate) async fn op_claim_resumable_instance(
+                pool: &$Pool,
+                instance_id: &str,
+            ) -> ::core::result::Result<bool, $crate::error::CoreError> {
+                use $crate::persistence::dialect::{Dialect, EnumKind};
+                let p1 = <$Dialect>::placeholder(1);
+                let status_cast = <$Dialect>::enum_cast(EnumKind::InstanceStatus);
+                let now = <$Dialect>::NOW;
+                let sql = format!(
+                    "UPDATE instances \
+                     SET status = 'running'{status_cast}, started_at = {now}, \
+                         finished_at = NULL, termination_reason = NULL, \
+                         sleep_until = NULL, status_version = status_version + 1 \
+                     WHERE instance_id = {p1} \
+                       AND status IN ('suspended', 'failed', 'cancelled')"
+                );
+                let result = ::sqlx::query(&sql)
+                    .bind(instance_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| $crate::error::CoreError::DatabaseError {
+                        operation: "claim_resumable_instance".into(),
+                        details: e.to_string(),
+                    })?;
+                let claimed = result.rows_affected() == 1;
+                if claimed {
+                    $crate::status_watch::notify(instance_id);
                 }
+                Ok(claimed)
+            }
+
+            /// Increment `execution_epoch` and return the new value in one
+            /// statement, so concurrent registrations always receive distinct
+            /// epochs. Errors with `InstanceNotFound` if no row matched.
+            pub(crate) async fn op_advance_execution_epoch(
+                pool: &$Pool,
+                instance_id: &str,
+            ) -> ::core::result::Result<i64, $crate::error::CoreError> {
+                use $crate::persistence::dialect::Dialect;
+                let p1 = <$Dialect>::placeholder(1);
+                let sql = format!(
+                    "UPDATE instances SET execution_epoch = execution_epoch + 1 \
+                     WHERE instance_id = {p1} RETURNING execution_epoch"
+                );
+                let row: ::core::option::Option<(i64,)> = ::sqlx::query_as(&sql)
+                    .bind(instance_id)
+                    .fetch_optional(pool)
+                    .await
+                    .map_err(|e| $crate::error::CoreError::DatabaseError {
+                        operation: "advance_execution_epoch".into(),
+                        details: e.to_string(),
+                    })?;
+                row.map(|(epoch,)| epoch)
+                    .ok_or_else(|| $crate::error::CoreError::InstanceNotFound {
+                        instance_id: instance_id.to_string(),
+                    })
+            }
+
+            /// SELECT the instance's current `execution_epoch`, or `None`
+            /// when the instance does not exist.
+            pub(crate) async fn op_current_execution_epoch(
+                pool: &$Pool,
+                instance_id: &str,
+            ) -> ::core::result::Result<
+                ::core::option::Option<i64>,
+                $crate::error::CoreError,
+            > {
+                use $crate::persistence::dialect::Dialect;
+                let p1 = <$Dialect>::placeholder(1);
+                let sql =
+                    format!("SELECT execution_epoch FROM instances WHERE instance_id = {p1}");
+                let row: ::core::option::Option<(i64,)> = ::sqlx::query_as(&sql)
+                    .bind(instance_id)
+                    .fetch_optional(pool)
+                    .await
+                    .map_err(|e| $crate::error::CoreError::DatabaseError {
+                        operation: "current_execution_epoch".into(),
+                        details: e.to_string(),
+                    })?;
+                Ok(row.map(|(epoch,)| epoch))
             }
 
             /// Mark an instance for automatic recovery after an Environment
@@ -277,7 +433,8 @@ macro_rules! impl_instance_ops {
                          termination_reason = 'environment_restart'{term_cast}, \
                          sleep_until = {now}, \
                          recovery_attempts = {p2}, \
-                         recovery_marker = {p3} \
+                         recovery_marker = {p3}, \
+                         status_version = status_version + 1 \
                      WHERE instance_id = {p1}"
                 );
                 ::sqlx::query(&sql)
@@ -290,6 +447,7 @@ macro_rules! impl_instance_ops {
                         operation: "mark_for_recovery".into(),
                         details: e.to_string(),
                     })?;
+                $crate::status_watch::notify(instance_id);
                 Ok(())
             }
 
@@ -339,7 +497,8 @@ macro_rules! impl_instance_ops {
                 let sql = format!(
                     "SELECT instance_id, tenant_id, definition_version, \
                             {status_col}, {termination_col}, checkpoint_id, attempt, max_attempts, \
-                            created_at, started_at, finished_at, output, error, sleep_until \
+                            created_at, started_at, finished_at, output, error, sleep_until, \
+                            status_version \
                      FROM instances \
                      WHERE ({p1} IS NULL OR tenant_id = {p1}) \
                        AND ({p2} IS NULL OR status = {p2}{status_cast}) \
@@ -9,9 +9,13 @@
 //! shared layer, the same script will also be pointed at a Postgres
 //! backend via testcontainers to catch SQL-generation drift.
 
+use std::sync::Arc;
+
 use chrono::{Duration, Utc};
 use uuid::Uuid;
 
+use crate::error::CoreError;
+use crate::instance_handlers::{CheckpointRequest, InstanceHandlerState, handle_checkpoint};
 use crate::persistence::{
     CompleteInstanceParams, EventRecord, ListEventsFilter, ListStepSummariesFilter, Persistence,
     StepStatus,
@@ -260,6 +264,53 @@ pub async fn run_parity_sequence<P: Persistence>(backend: &P) {
         .await
         .expect("clear_instance_sleep failed");
 
+    // --- pending start claim vs. cancel-before-start ------------------------
+    // A guarded cancel of a still-pending instance wins and blocks the launch
+    // claim; once an instance is claimed, the guarded cancel must lose.
+    let cancelled_id = Uuid::new_v4().to_string();
+    backend
+        .register_instance(&cancelled_id, tenant_id)
+        .await
+        .expect("register_instance (cancel-before-start) failed");
+    let cancelled = backend
+        .complete_instance(CompleteInstanceParams::new(&cancelled_id, "cancelled").if_pending())
+        .await
+        .expect("guarded cancel of pending instance failed");
+    assert!(cancelled, "cancel of a pending instance must win");
+    let claimed = backend
+        .claim_pending_instance(&cancelled_id)
+        .await
+        .expect("claim_pending_instance (after cancel) failed");
+    assert!(!claimed, "a cancelled instance must not be claimable");
+
+    let claimed_id = Uuid::new_v4().to_string();
+    backend
+        .register_instance(&claimed_id, tenant_id)
+        .await
+        .expect("register_instance (claim) failed");
+    let claimed = backend
+        .claim_pending_instance(&claimed_id)
+        .await
+        .expect("claim_pending_instance failed");
+    assert!(claimed, "first claim of a pending instance must win");
+    let record = backend
+        .get_instance(&claimed_id)
+        .await
+        .expect("get_instance after claim failed")
+        .expect("claimed instance must exist");
+    assert_eq!(record.status, "running");
+    assert!(record.started_at.is_some());
+    let cancelled = backend
+        .complete_instance(CompleteInstanceParams::new(&claimed_id, "cancelled").if_pending())
+        .await
+        .expect("guarded cancel of claimed instance failed");
+    assert!(!cancelled, "cancel after claim must lose");
+    let removed = backend
+        .delete_instances_batch(&[cancelled_id, claimed_id])
+        .await
+        .expect("delete_instances_batch (claim fixtures) failed");
+    assert_eq!(removed, 2);
+
     // --- listing ------------------------------------------------------------
     let active = backend
         .count_active_instances()
@@ -338,6 +389,310 @@ pub async fn run_parity_sequence<P: Persistence>(backend: &P) {
     );
 }
 
+/// Run the checkpoint wait-key delivery sequence against `backend`.
+///
+/// Covers both orderings of a business signal and the checkpoint waiting on
+/// it: a signal sent before the instance reaches its checkpoint is delivered
+/// by that checkpoint, and a signal sent while the instance is polling is
+/// delivered by its next checkpoint.
+pub async fn run_wait_key_sequence(backend: Arc<dyn Persistence>) {
+    let state = InstanceHandlerState::new(backend.clone());
+    let checkpoint = |instance_id: &str, checkpoint_id: &str| CheckpointRequest {
+        instance_id: instance_id.to_string(),
+        checkpoint_id: checkpoint_id.to_string(),
+        state: b"poll-state".to_vec(),
+        content_type: None,
+        shape: None,
+        wait_keys: vec!["payment_confirmed:order-7".to_string()],
+    };
+    let running_instance = || async {
+        let instance_id = Uuid::new_v4().to_string();
+        backend
+            .register_instance(&instance_id, "parity-tenant")
+            .await
+            .expect("register_instance failed");
+        backend
+            .update_instance_status(&instance_id, "running", Some(Utc::now()))
+            .await
+            .expect("update_instance_status running failed");
+        instance_id
+    };
+
+    // --- deliver before wait -------------------------------------------------
+    let instance_id = running_instance().await;
+    backend
+        .insert_custom_signal(&instance_id, "payment_confirmed:order-7", b"paid")
+        .await
+        .expect("insert_custom_signal failed");
+    let response = handle_checkpoint(&state, checkpoint(&instance_id, "poll::[0]"))
+        .await
+        .expect("checkpoint failed");
+    let signal = response
+        .custom_signal
+        .expect("signal sent before the wait is delivered");
+    assert_eq!(signal.checkpoint_id, "payment_confirmed:order-7");
+    assert_eq!(signal.payload, b"paid");
+
+    // --- wait before deliver -------------------------------------------------
+    let instance_id = running_instance().await;
+    let response = handle_checkpoint(&state, checkpoint(&instance_id, "poll::[0]"))
+        .await
+        .expect("checkpoint failed");
+    assert!(response.custom_signal.is_none());
+    backend
+        .insert_custom_signal(&instance_id, "payment_confirmed:order-7", b"paid")
+        .await
+        .expect("insert_custom_signal failed");
+    let response = handle_checkpoint(&state, checkpoint(&instance_id, "poll::[1]"))
+        .await
+        .expect("checkpoint failed");
+    let signal = response
+        .custom_signal
+        .expect("signal sent during the wait is delivered by the next poll");
+    assert_eq!(signal.checkpoint_id, "payment_confirmed:order-7");
+    assert_eq!(signal.payload, b"paid");
+
+    // Replaying the polling checkpoint re-reads the same signal.
+    let response = handle_checkpoint(&state, checkpoint(&instance_id, "poll::[1]"))
+        .await
+        .expect("replayed checkpoint failed");
+    assert!(response.found);
+    assert_eq!(response.custom_signal.expect("re-read").payload, b"paid");
+}
+
+/// Run the resume-claim and execution-epoch sequence against `backend`.
+///
+/// Two launch paths claiming the same suspended instance must produce exactly
+/// one winner, and every registration must be issued a fresh, larger epoch.
+pub async fn run_execution_fencing_sequence(backend: &dyn Persistence) {
+    let instance_id = Uuid::new_v4().to_string();
+    backend
+        .register_instance(&instance_id, "parity-tenant")
+        .await
+        .expect("register_instance failed");
+    assert_eq!(
+        backend
+            .current_execution_epoch(&instance_id)
+            .await
+            .expect("current_execution_epoch failed"),
+        Some(0)
+    );
+
+    // --- claim resume --------------------------------------------------------
+    // A pending instance was never started, so it cannot be resumed.
+    assert!(
+        !backend
+            .claim_resumable_instance(&instance_id)
+            .await
+            .expect("claim_resumable_instance failed")
+    );
+    backend
+        .update_instance_status(&instance_id, "suspended", None)
+        .await
+        .expect("update_instance_status suspended failed");
+    backend
+        .set_instance_sleep(&instance_id, Utc::now())
+        .await
+        .expect("set_instance_sleep failed");
+    assert!(
+        backend
+            .claim_resumable_instance(&instance_id)
+            .await
+            .expect("claim_resumable_instance failed")
+    );
+    assert!(
+        !backend
+            .claim_resumable_instance(&instance_id)
+            .await
+            .expect("claim_resumable_instance failed"),
+        "a second launch path must lose the claim"
+    );
+    let instance = backend
+        .get_instance(&instance_id)
+        .await
+        .expect("get_instance failed")
+        .expect("instance exists");
+    assert_eq!(instance.status, "running");
+    assert!(instance.sleep_until.is_none());
+
+    // --- execution epoch -----------------------------------------------------
+    let first = backend
+        .advance_execution_epoch(&instance_id)
+        .await
+        .expect("advance_execution_epoch failed");
+    let second = backend
+        .advance_execution_epoch(&instance_id)
+        .await
+        .expect("advance_execution_epoch failed");
+    assert!(0 < first && first < second);
+    assert_eq!(
+        backend
+            .current_execution_epoch(&instance_id)
+            .await
+            .expect("current_execution_epoch failed"),
+        Some(second)
+    );
+    assert!(matches!(
+        backend.advance_execution_epoch("missing-instance").await,
+        Err(CoreError::InstanceNotFound { .. })
+    ));
+    assert_eq!(
+        backend
+            .current_execution_epoch("missing-instance")
+            .await
+            .expect("current_execution_epoch failed"),
+        None
+    );
+}
+
+/// Run the transactional multi-write sequence against `backend`.
+///
+/// Simulates a crash between "save checkpoint" and "point the instance at
+/// it" by dropping an uncommitted transaction, and checks that
+/// `instances.checkpoint_id` always references a checkpoint that exists.
+/// Terminal event plus status change gets the same all-or-nothing treatment.
+pub async fn run_transaction_sequence(backend: &dyn Persistence) {
+    let instance_id = Uuid::new_v4().to_string();
+    backend
+        .register_instance(&instance_id, "parity-tenant")
+        .await
+        .expect("register_instance failed");
+    backend
+        .update_instance_status(&instance_id, "running", Some(Utc::now()))
+        .await
+        .expect("update_instance_status running failed");
+
+    // --- committed checkpoint + pointer ---------------------------------------
+    let mut tx = backend
+        .begin()
+        .await
+        .expect("begin failed")
+        .expect("backend supports transactions");
+    tx.save_checkpoint(&instance_id, "tx-cp-1", b"one")
+        .await
+        .expect("tx save_checkpoint failed");
+    tx.update_instance_checkpoint(&instance_id, "tx-cp-1")
+        .await
+        .expect("tx update_instance_checkpoint failed");
+    tx.commit().await.expect("commit failed");
+    assert_checkpoint_pointer_valid(backend, &instance_id, "tx-cp-1").await;
+
+    // --- crash after the pointer update, before commit ------------------------
+    let mut tx = backend
+        .begin()
+        .await
+        .expect("begin failed")
+        .expect("backend supports transactions");
+    tx.save_checkpoint(&instance_id, "tx-cp-2", b"two")
+        .await
+        .expect("tx save_checkpoint failed");
+    tx.update_instance_checkpoint(&instance_id, "tx-cp-2")
+        .await
+        .expect("tx update_instance_checkpoint failed");
+    drop(tx);
+    assert_checkpoint_pointer_valid(backend, &instance_id, "tx-cp-1").await;
+    assert!(
+        backend
+            .load_checkpoint(&instance_id, "tx-cp-2")
+            .await
+            .expect("load_checkpoint failed")
+            .is_none(),
+        "an uncommitted checkpoint must not survive the crash"
+    );
+
+    // --- terminal event + status roll back together ---------------------------
+    let completed = EventRecord {
+        id: None,
+        instance_id: instance_id.clone(),
+        event_type: "completed".to_string(),
+        checkpoint_id: None,
+        payload: None,
+        created_at: Utc::now(),
+        subtype: None,
+    };
+    let completed_only = ListEventsFilter {
+        event_type: Some("completed".to_string()),
+        ..Default::default()
+    };
+    let mut tx = backend
+        .begin()
+        .await
+        .expect("begin failed")
+        .expect("backend supports transactions");
+    tx.insert_event(&completed)
+        .await
+        .expect("tx insert_event failed");
+    assert!(
+        tx.complete_instance(CompleteInstanceParams::new(&instance_id, "completed").if_running())
+            .await
+            .expect("tx complete_instance failed")
+    );
+    tx.rollback().await.expect("rollback failed");
+    let record = backend
+        .get_instance(&instance_id)
+        .await
+        .expect("get_instance failed")
+        .expect("instance exists");
+    assert_eq!(record.status, "running");
+    assert!(
+        backend
+            .list_events(&instance_id, &completed_only, 10, 0)
+            .await
+            .expect("list_events failed")
+            .is_empty(),
+        "a rolled-back terminal event must not be visible"
+    );
+
+    let mut tx = backend
+        .begin()
+        .await
+        .expect("begin failed")
+        .expect("backend supports transactions");
+    tx.insert_event(&completed)
+        .await
+        .expect("tx insert_event failed");
+    tx.complete_instance(CompleteInstanceParams::new(&instance_id, "completed").if_running())
+        .await
+        .expect("tx complete_instance failed");
+    tx.commit().await.expect("commit failed");
+    let record = backend
+        .get_instance(&instance_id)
+        .await
+        .expect("get_instance failed")
+        .expect("instance exists");
+    assert_eq!(record.status, "completed");
+    assert_eq!(
+        backend
+            .list_events(&instance_id, &completed_only, 10, 0)
+            .await
+            .expect("list_events failed")
+            .len(),
+        1
+    );
+}
+
+/// Assert the instance points at `expected` and that checkpoint exists.
+async fn assert_checkpoint_pointer_valid(
+    backend: &dyn Persistence,
+    instance_id: &str,
+    expected: &str,
+) {
+    let record = backend
+        .get_instance(instance_id)
+        .await
+        .expect("get_instance failed")
+        .expect("instance exists");
+    assert_eq!(record.checkpoint_id.as_deref(), Some(expected));
+    assert!(
+        backend
+            .load_checkpoint(instance_id, expected)
+            .await
+            .expect("load_checkpoint failed")
+            .is_some(),
+        "instances.checkpoint_id must reference an existing checkpoint"
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -369,6 +724,9 @@ mod tests {
 
         let backend = SqlitePersistence::new(pool);
         run_parity_sequence(&backend).await;
+        run_execution_fencing_sequence(&backend).await;
+        run_transaction_sequence(&backend).await;
+        run_wait_key_sequence(Arc::new(backend)).await;
     }
 
     /// Run the same parity sequence against Postgres. Uses
@@ -381,6 +739,9 @@ mod tests {
         let (pool, _container) = postgres_test_pool().await;
         let backend = PostgresPersistence::new(pool);
         run_parity_sequence(&backend).await;
+        run_execution_fencing_sequence(&backend).await;
+        run_transaction_sequence(&backend).await;
+        run_wait_key_sequence(Arc::new(backend)).await;
     }
 
     /// Obtain a Postgres pool for the parity test. Prefers
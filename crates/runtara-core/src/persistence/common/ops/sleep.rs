@@ -27,11 +27,17 @@ macro_rules! impl_sleep_ops {
         impl $Backend {
             /// UPDATE `sleep_until`. Errors with `InstanceNotFound` if no
             /// row matched.
-            pub(crate) async fn op_set_instance_sleep(
-                pool: &$Pool,
+            pub(crate) async fn op_set_instance_sleep<'e, E>(
+                pool: E,
                 instance_id: &str,
                 sleep_until: ::chrono::DateTime<::chrono::Utc>,
-            ) -> ::core::result::Result<(), $crate::error::CoreError> {
+            ) -> ::core::result::Result<(), $crate::error::CoreError>
+            where
+                E: ::sqlx::Executor<
+                        'e,
+                        Database = <$Dialect as $crate::persistence::dialect::Dialect>::Database,
+                    >,
+            {
                 use $crate::persistence::common::error::not_found_if_empty;
                 use $crate::persistence::dialect::Dialect;
                 let p1 = <$Dialect>::placeholder(1);
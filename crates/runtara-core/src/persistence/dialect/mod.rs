@@ -133,7 +133,8 @@ pub trait Dialect: Send + Sync + 'static {
 
     /// SQL for inserting/upserting a checkpoint row.
     ///
-    /// Binds (in order): instance_id, checkpoint_id, state.
+    /// Binds (in order): instance_id, checkpoint_id, state, content_type,
+    /// shape.
     ///
     /// - Postgres: `INSERT ... ON CONFLICT DO UPDATE` (idempotent upsert).
     /// - SQLite: plain `INSERT` — a duplicate `(instance_id, checkpoint_id)`
@@ -149,6 +150,15 @@ pub trait Dialect: Send + Sync + 'static {
     /// checkpoint_id_filter, created_after, created_before).
     fn sql_count_checkpoints() -> &'static str;
 
+    /// SQL for `delete_checkpoints_with_prefix` (binds: instance_id,
+    /// prefix). Never deletes the instance's current resume checkpoint
+    /// (`instances.checkpoint_id`); Postgres also keeps compensatable rows.
+    fn sql_delete_checkpoints_with_prefix() -> &'static str;
+
+    /// SQL for `list_checkpoint_ids` (binds: instance_id, prefix filter,
+    /// created_since filter). Selects only `checkpoint_id`, oldest first.
+    fn sql_list_checkpoint_ids() -> &'static str;
+
     /// SQL for selecting the pending signal for an instance (bind:
     /// instance_id). Postgres returns only unacknowledged signals;
     /// SQLite returns any signal row (legacy behavior preserved).
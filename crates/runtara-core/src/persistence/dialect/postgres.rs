@@ -89,14 +89,15 @@ impl Dialect for PostgresDialect {
     }
 
     fn sql_save_checkpoint() -> &'static str {
-        "INSERT INTO checkpoints (instance_id, checkpoint_id, state, created_at) \
-         VALUES ($1, $2, $3, NOW()) \
+        "INSERT INTO checkpoints (instance_id, checkpoint_id, state, created_at, content_type, shape) \
+         VALUES ($1, $2, $3, NOW(), $4, $5) \
          ON CONFLICT (instance_id, checkpoint_id) DO UPDATE \
-         SET state = EXCLUDED.state, created_at = NOW()"
+         SET state = EXCLUDED.state, created_at = NOW(), \
+             content_type = EXCLUDED.content_type, shape = EXCLUDED.shape"
     }
 
     fn sql_list_checkpoints() -> &'static str {
-        "SELECT id, instance_id, checkpoint_id, state, created_at \
+        "SELECT id, instance_id, checkpoint_id, state, created_at, content_type, shape \
          FROM checkpoints \
          WHERE instance_id = $1 \
            AND ($2::TEXT IS NULL OR checkpoint_id = $2) \
@@ -115,6 +116,24 @@ impl Dialect for PostgresDialect {
            AND ($4::TIMESTAMPTZ IS NULL OR created_at < $4)"
     }
 
+    fn sql_delete_checkpoints_with_prefix() -> &'static str {
+        "DELETE FROM checkpoints \
+         WHERE instance_id = $1 \
+           AND left(checkpoint_id, length($2)) = $2 \
+           AND is_compensatable = false \
+           AND checkpoint_id IS DISTINCT FROM \
+               (SELECT checkpoint_id FROM instances WHERE instance_id = $1)"
+    }
+
+    fn sql_list_checkpoint_ids() -> &'static str {
+        "SELECT checkpoint_id \
+         FROM checkpoints \
+         WHERE instance_id = $1 \
+           AND ($2::TEXT IS NULL OR left(checkpoint_id, length($2)) = $2) \
+           AND ($3::TIMESTAMPTZ IS NULL OR created_at >= $3) \
+         ORDER BY created_at ASC, checkpoint_id ASC"
+    }
+
     fn sql_get_pending_signal() -> &'static str {
         "SELECT instance_id, signal_type::text as signal_type, payload, created_at, acknowledged_at \
          FROM pending_signals \
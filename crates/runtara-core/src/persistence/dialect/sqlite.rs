@@ -108,12 +108,12 @@ impl Dialect for SqliteDialect {
         // Plain INSERT (no ON CONFLICT) — preserves legacy SQLite semantics
         // where a duplicate `(instance_id, checkpoint_id)` raises a UNIQUE
         // violation. Unifying to upsert is a separate decision.
-        "INSERT INTO checkpoints (instance_id, checkpoint_id, state, created_at) \
-         VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP)"
+        "INSERT INTO checkpoints (instance_id, checkpoint_id, state, created_at, content_type, shape) \
+         VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP, ?4, ?5)"
     }
 
     fn sql_list_checkpoints() -> &'static str {
-        "SELECT id, instance_id, checkpoint_id, state, created_at \
+        "SELECT id, instance_id, checkpoint_id, state, created_at, content_type, shape \
          FROM checkpoints \
          WHERE instance_id = ?1 \
            AND (?2 IS NULL OR checkpoint_id = ?2) \
@@ -132,6 +132,23 @@ impl Dialect for SqliteDialect {
            AND (?4 IS NULL OR created_at < ?4)"
     }
 
+    fn sql_delete_checkpoints_with_prefix() -> &'static str {
+        "DELETE FROM checkpoints \
+         WHERE instance_id = ?1 \
+           AND substr(checkpoint_id, 1, length(?2)) = ?2 \
+           AND checkpoint_id IS NOT \
+               (SELECT checkpoint_id FROM instances WHERE instance_id = ?1)"
+    }
+
+    fn sql_list_checkpoint_ids() -> &'static str {
+        "SELECT checkpoint_id \
+         FROM checkpoints \
+         WHERE instance_id = ?1 \
+           AND (?2 IS NULL OR substr(checkpoint_id, 1, length(?2)) = ?2) \
+           AND (?3 IS NULL OR created_at >= ?3) \
+         ORDER BY created_at ASC, checkpoint_id ASC"
+    }
+
     fn sql_get_pending_signal() -> &'static str {
         // Legacy SQLite behavior: returns any row for the instance, including
         // already-acknowledged ones. Postgres filters `acknowledged_at IS NULL`.
@@ -6,9 +6,11 @@ pub mod common;
 pub mod dialect;
 pub mod postgres;
 pub mod sqlite;
+pub mod tx;
 
 pub use self::postgres::PostgresPersistence;
 pub use self::sqlite::SqlitePersistence;
+pub use self::tx::{PersistenceTx, begin_or_autocommit};
 
 use crate::error::CoreError;
 
@@ -59,6 +61,10 @@ pub struct InstanceRecord {
     /// against the current count to distinguish "made progress" from "stuck".
     #[sqlx(default)]
     pub recovery_marker: Option<String>,
+    /// Incremented on every status or checkpoint change. Long-poll status
+    /// reads compare against it so no transition is missed between polls.
+    #[sqlx(default)]
+    pub status_version: i64,
 }
 
 /// Checkpoint record from the persistence layer.
@@ -89,6 +95,27 @@ pub struct CheckpointRecord {
     /// Order in which to execute compensation (higher = compensate first).
     #[sqlx(default)]
     pub compensation_order: i32,
+    /// Content type hint supplied by the writer (`application/json`,
+    /// `application/octet-stream`). `None` for checkpoints written without
+    /// hints; readers sniff those (see [`crate::checkpoint_preview`]).
+    #[sqlx(default)]
+    pub content_type: Option<String>,
+    /// Shape tag supplied by the writer (`step_output`, `steps_context`,
+    /// `durable_result`, `scenario_vars`).
+    #[sqlx(default)]
+    pub shape: Option<String>,
+}
+
+/// Writer-supplied hints stored alongside checkpoint state.
+///
+/// Purely descriptive: they never affect replay, only how browsing tools
+/// render the state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CheckpointHints<'a> {
+    /// Content type of the state bytes.
+    pub content_type: Option<&'a str>,
+    /// Shape tag describing what the state represents.
+    pub shape: Option<&'a str>,
 }
 
 /// Event record from the persistence layer.
@@ -323,11 +350,13 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 
 /// Whether a `complete_instance` call should apply unconditionally or only
-/// when the target row is still in the `running` state.
+/// when the target row is still in a given state.
 ///
 /// The `OnlyRunning` guard exists to prevent races between two independent
 /// writers (typically: the SDK reporting a terminal status, and the
 /// container monitor observing a process exit) from clobbering one another.
+/// `OnlyPending` is the cancel side of the start claim: it races
+/// [`Persistence::claim_pending_instance`] for a reserved-but-unlaunched row.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum CompleteInstanceGuard {
     /// No guard — the update applies regardless of the current status.
@@ -338,6 +367,9 @@ pub enum CompleteInstanceGuard {
     /// (row exists but has a different status) is reported as `Ok(false)`
     /// rather than an error.
     OnlyRunning,
+    /// Apply the update only if the current status is `pending`. A miss is
+    /// reported as `Ok(false)`, as with `OnlyRunning`.
+    OnlyPending,
 }
 
 /// Parameters for [`Persistence::complete_instance`], transitioning an
@@ -394,6 +426,14 @@ impl<'a> CompleteInstanceParams<'a> {
         self
     }
 
+    /// Guard the update against the launcher: only apply when the instance
+    /// is still `pending` (reserved but not yet claimed for launch).
+    #[must_use]
+    pub fn if_pending(mut self) -> Self {
+        self.guard = CompleteInstanceGuard::OnlyPending;
+        self
+    }
+
     /// Attach an output blob.
     #[must_use]
     pub fn with_output(mut self, output: &'a [u8]) -> Self {
@@ -463,8 +503,9 @@ pub trait Persistence: Send + Sync {
     /// Return value:
     /// - `Ok(true)` — the update matched a row.
     /// - `Ok(false)` — guarded update
-    ///   ([`CompleteInstanceGuard::OnlyRunning`]) skipped because the
-    ///   current status is not `running`. This is an expected outcome
+    ///   ([`CompleteInstanceGuard::OnlyRunning`] /
+    ///   [`CompleteInstanceGuard::OnlyPending`]) skipped because the
+    ///   current status is not the guarded one. This is an expected outcome
     ///   during races, not an error.
     /// - `Err(CoreError::InstanceNotFound)` — unguarded update against
     ///   a missing row.
@@ -520,6 +561,22 @@ pub trait Persistence: Send + Sync {
         state: &[u8],
     ) -> Result<(), CoreError>;
 
+    /// Save a checkpoint together with its content hints.
+    ///
+    /// Default: drops the hints and delegates to `save_checkpoint`, so
+    /// backends without hint columns keep working unchanged.
+    async fn save_checkpoint_with_hints(
+        &self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints<'_>,
+    ) -> Result<(), CoreError> {
+        let _ = hints;
+        self.save_checkpoint(instance_id, checkpoint_id, state)
+            .await
+    }
+
     async fn load_checkpoint(
         &self,
         instance_id: &str,
@@ -544,6 +601,42 @@ pub trait Persistence: Send + Sync {
         created_before: Option<DateTime<Utc>>,
     ) -> Result<i64, CoreError>;
 
+    /// Delete every checkpoint of an instance whose id starts with `prefix`.
+    ///
+    /// Used to compact per-iteration loop checkpoints once the loop's
+    /// aggregate checkpoint is durable. The instance's current resume
+    /// checkpoint (and, where supported, compensatable checkpoints) are never
+    /// deleted, so re-running the same compaction is a no-op.
+    ///
+    /// Returns the count of deleted checkpoints.
+    async fn delete_checkpoints_with_prefix(
+        &self,
+        _instance_id: &str,
+        _prefix: &str,
+    ) -> Result<u64, CoreError> {
+        // Default: no-op (compaction not supported)
+        Ok(0)
+    }
+
+    /// List only the checkpoint ids of an instance, oldest first.
+    ///
+    /// `prefix` keeps ids starting with it; `since` keeps checkpoints created
+    /// at or after that time. Lets a resuming instance learn which durable
+    /// steps already completed without loading any state. Callers treat the
+    /// result as complete, so a backend that cannot list reliably must fail.
+    async fn list_checkpoint_ids(
+        &self,
+        _instance_id: &str,
+        _prefix: Option<&str>,
+        _since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<String>, CoreError> {
+        // Default: unsupported (callers fall back to per-key lookups)
+        Err(CoreError::DatabaseError {
+            operation: "list_checkpoint_ids".into(),
+            details: "checkpoint key listing is not supported by this backend".into(),
+        })
+    }
+
     async fn insert_event(&self, event: &EventRecord) -> Result<(), CoreError>;
 
     async fn insert_signal(
@@ -622,6 +715,77 @@ pub trait Persistence: Send + Sync {
         Ok(true)
     }
 
+    /// Atomically claim a freshly reserved (`pending`) instance for launch.
+    ///
+    /// Transitions `pending` → `running` and stamps `started_at`, returning
+    /// `true` when this caller won the row. Returns `false` when the row is
+    /// no longer `pending` — in particular when a cancel-before-start
+    /// ([`CompleteInstanceGuard::OnlyPending`]) got there first. Callers MUST
+    /// launch only when this returns `true`.
+    ///
+    /// The default implementation is a non-atomic best-effort fallback for
+    /// in-memory/mock backends; the SQL backends override it with a single
+    /// conditional UPDATE whose row-count is the claim outcome.
+    async fn claim_pending_instance(&self, instance_id: &str) -> Result<bool, CoreError> {
+        match self.get_instance(instance_id).await? {
+            Some(instance) if instance.status == "pending" => {
+                self.update_instance_status(instance_id, "running", Some(Utc::now()))
+                    .await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Atomically claim a stopped instance for a resume launch.
+    ///
+    /// Transitions `suspended`, `failed` or `cancelled` → `running`, stamps
+    /// `started_at` and clears `sleep_until`, returning `true` when this
+    /// caller won the row. Every path that relaunches an existing instance
+    /// (manual resume, wake) claims through this, so when two race only one
+    /// launches; the loser gets `false` and must not launch.
+    ///
+    /// The default implementation is a non-atomic best-effort fallback for
+    /// in-memory/mock backends; the SQL backends override it with a single
+    /// conditional UPDATE whose row-count is the claim outcome.
+    async fn claim_resumable_instance(&self, instance_id: &str) -> Result<bool, CoreError> {
+        match self.get_instance(instance_id).await? {
+            Some(instance)
+                if matches!(
+                    instance.status.as_str(),
+                    "suspended" | "failed" | "cancelled"
+                ) =>
+            {
+                self.update_instance_status(instance_id, "running", Some(Utc::now()))
+                    .await?;
+                self.clear_instance_sleep(instance_id).await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Start a new execution epoch for an instance and return it.
+    ///
+    /// Called on every successful registration. The epoch is a fencing
+    /// token: writes carrying an older epoch than
+    /// [`Persistence::current_execution_epoch`] come from a superseded
+    /// container and are refused. Concurrent calls must return distinct,
+    /// increasing values.
+    ///
+    /// The default implementation returns `0` and
+    /// `current_execution_epoch` returns `None`, which disables fencing for
+    /// backends without an epoch column; the SQL backends override both.
+    async fn advance_execution_epoch(&self, _instance_id: &str) -> Result<i64, CoreError> {
+        Ok(0)
+    }
+
+    /// The instance's current execution epoch, or `None` when the instance
+    /// does not exist or the backend does not track epochs.
+    async fn current_execution_epoch(&self, _instance_id: &str) -> Result<Option<i64>, CoreError> {
+        Ok(None)
+    }
+
     /// Mark an instance for automatic recovery after an Environment restart.
     ///
     /// Sets `status='suspended'`, `termination_reason='environment_restart'`,
@@ -847,4 +1011,13 @@ pub trait Persistence: Send + Sync {
         // Default: no-op (no deletion supported)
         Ok(0)
     }
+
+    /// Begin a transaction over the writes in [`PersistenceTx`].
+    ///
+    /// Default: `None` (no transaction support). Callers go through
+    /// [`begin_or_autocommit`], which falls back to applying each write
+    /// immediately.
+    async fn begin(&self) -> Result<Option<Box<dyn PersistenceTx>>, CoreError> {
+        Ok(None)
+    }
 }
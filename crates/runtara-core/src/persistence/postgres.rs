@@ -54,8 +54,8 @@ impl From<InstanceMetricRow> for InstanceCompletionMetrics {
     }
 }
 
-async fn fetch_instance_status(
-    pool: &PgPool,
+async fn fetch_instance_status<'e, E: sqlx::PgExecutor<'e>>(
+    pool: E,
     instance_id: &str,
 ) -> Result<Option<String>, sqlx::Error> {
     sqlx::query_scalar(
@@ -128,9 +128,9 @@ async fn record_resources_from_db(pool: &PgPool, instance_id: &str) {
 // ============================================================================
 
 use super::{
-    CheckpointRecord, CompleteInstanceParams, CustomSignalRecord, EventRecord, InstanceRecord,
-    ListEventsFilter, ListStepSummariesFilter, Persistence, SignalRecord, StepSummaryRecord,
-    WakeEntry,
+    CheckpointHints, CheckpointRecord, CompleteInstanceParams, CustomSignalRecord, EventRecord,
+    InstanceRecord, ListEventsFilter, ListStepSummariesFilter, Persistence, PersistenceTx,
+    SignalRecord, StepSummaryRecord, WakeEntry,
 };
 
 // ============================================================================
@@ -250,6 +250,9 @@ pub async fn update_instance_stderr(
 // (crate::persistence::common::ops::checkpoints).
 
 /// Load the latest checkpoint for an instance.
+///
+/// Skips the debug sampler's bookkeeping checkpoint, which is rewritten
+/// during debug runs but is never a resume point.
 pub async fn load_latest_checkpoint(
     pool: &PgPool,
     instance_id: &str,
@@ -259,11 +262,13 @@ pub async fn load_latest_checkpoint(
         SELECT id, instance_id, checkpoint_id, state, created_at
         FROM checkpoints
         WHERE instance_id = $1
+          AND checkpoint_id <> $2
         ORDER BY created_at DESC
         LIMIT 1
         "#,
     )
     .bind(instance_id)
+    .bind(crate::debug_budget::DEBUG_SAMPLER_CHECKPOINT_ID)
     .fetch_optional(pool)
     .await?;
 
@@ -421,7 +426,10 @@ pub async fn clear_wake(pool: &PgPool, instance_id: &str) -> Result<(), CoreErro
 // ============================================================================
 
 /// Insert an instance event.
-pub async fn insert_event(pool: &PgPool, event: &EventRecord) -> Result<(), CoreError> {
+pub async fn insert_event<'e, E: sqlx::PgExecutor<'e>>(
+    pool: E,
+    event: &EventRecord,
+) -> Result<(), CoreError> {
     sqlx::query(
         r#"
         INSERT INTO instance_events (instance_id, event_type, checkpoint_id, payload, created_at, subtype)
@@ -525,6 +533,106 @@ pub async fn insert_custom_signal(
 // op_set_instance_sleep, op_clear_instance_sleep, op_get_sleeping_instances_due
 // (crate::persistence::common::ops::{instances, sleep}).
 
+/// Transaction handed out by [`PostgresPersistence`]'s `begin`.
+pub struct PostgresTx {
+    pool: PgPool,
+    tx: sqlx::Transaction<'static, sqlx::Postgres>,
+    /// Instances moved into a recorded terminal status; their completion
+    /// metrics are recorded once the transaction commits.
+    completed: Vec<String>,
+    /// Instances whose status or checkpoint changed, re-notified on commit.
+    touched: Vec<String>,
+}
+
+#[async_trait::async_trait]
+impl PersistenceTx for PostgresTx {
+    async fn save_checkpoint_with_hints(
+        &mut self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints<'_>,
+    ) -> Result<(), CoreError> {
+        PostgresPersistence::op_save_checkpoint_with_hints(
+            &mut *self.tx,
+            instance_id,
+            checkpoint_id,
+            state,
+            hints,
+        )
+        .await
+    }
+
+    async fn update_instance_checkpoint(
+        &mut self,
+        instance_id: &str,
+        checkpoint_id: &str,
+    ) -> Result<(), CoreError> {
+        PostgresPersistence::op_update_instance_checkpoint(
+            &mut *self.tx,
+            instance_id,
+            checkpoint_id,
+        )
+        .await?;
+        self.touched.push(instance_id.to_string());
+        Ok(())
+    }
+
+    async fn insert_event(&mut self, event: &EventRecord) -> Result<(), CoreError> {
+        insert_event(&mut *self.tx, event).await
+    }
+
+    async fn complete_instance(
+        &mut self,
+        params: CompleteInstanceParams<'_>,
+    ) -> Result<bool, CoreError> {
+        let instance_id = params.instance_id.to_string();
+        let target_status = params.status.to_string();
+        let previous_was_terminal = fetch_instance_status(&mut *self.tx, &instance_id)
+            .await?
+            .is_some_and(|status| is_recorded_terminal_status(&status));
+        let applied =
+            PostgresPersistence::op_complete_instance_unified(&mut *self.tx, params).await?;
+        if applied {
+            if is_recorded_terminal_status(&target_status) && !previous_was_terminal {
+                self.completed.push(instance_id.clone());
+            }
+            self.touched.push(instance_id);
+        }
+        Ok(applied)
+    }
+
+    async fn set_instance_sleep(
+        &mut self,
+        instance_id: &str,
+        sleep_until: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        PostgresPersistence::op_set_instance_sleep(&mut *self.tx, instance_id, sleep_until).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), CoreError> {
+        let Self {
+            pool,
+            tx,
+            completed,
+            touched,
+        } = *self;
+        tx.commit().await?;
+        for instance_id in &touched {
+            crate::status_watch::notify(instance_id);
+        }
+        for instance_id in &completed {
+            record_completion_from_db(&pool, instance_id).await;
+        }
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), CoreError> {
+        self.tx.rollback().await?;
+        Ok(())
+    }
+}
+
 #[async_trait::async_trait]
 impl Persistence for PostgresPersistence {
     async fn register_instance(&self, instance_id: &str, tenant_id: &str) -> Result<(), CoreError> {
@@ -588,6 +696,17 @@ impl Persistence for PostgresPersistence {
         Self::op_save_checkpoint(&self.pool, instance_id, checkpoint_id, state).await
     }
 
+    async fn save_checkpoint_with_hints(
+        &self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints<'_>,
+    ) -> Result<(), CoreError> {
+        Self::op_save_checkpoint_with_hints(&self.pool, instance_id, checkpoint_id, state, hints)
+            .await
+    }
+
     async fn load_checkpoint(
         &self,
         instance_id: &str,
@@ -634,10 +753,36 @@ impl Persistence for PostgresPersistence {
         .await
     }
 
+    async fn delete_checkpoints_with_prefix(
+        &self,
+        instance_id: &str,
+        prefix: &str,
+    ) -> Result<u64, CoreError> {
+        Self::op_delete_checkpoints_with_prefix(&self.pool, instance_id, prefix).await
+    }
+
+    async fn list_checkpoint_ids(
+        &self,
+        instance_id: &str,
+        prefix: Option<&str>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<String>, CoreError> {
+        Self::op_list_checkpoint_ids(&self.pool, instance_id, prefix, since).await
+    }
+
     async fn insert_event(&self, event: &EventRecord) -> Result<(), CoreError> {
         insert_event(&self.pool, event).await
     }
 
+    async fn begin(&self) -> Result<Option<Box<dyn PersistenceTx>>, CoreError> {
+        Ok(Some(Box::new(PostgresTx {
+            pool: self.pool.clone(),
+            tx: self.pool.begin().await?,
+            completed: Vec::new(),
+            touched: Vec::new(),
+        })))
+    }
+
     async fn insert_signal(
         &self,
         instance_id: &str,
@@ -726,6 +871,22 @@ impl Persistence for PostgresPersistence {
         Self::op_claim_sleeping_instance(&self.pool, instance_id).await
     }
 
+    async fn claim_pending_instance(&self, instance_id: &str) -> Result<bool, CoreError> {
+        Self::op_claim_pending_instance(&self.pool, instance_id).await
+    }
+
+    async fn claim_resumable_instance(&self, instance_id: &str) -> Result<bool, CoreError> {
+        Self::op_claim_resumable_instance(&self.pool, instance_id).await
+    }
+
+    async fn advance_execution_epoch(&self, instance_id: &str) -> Result<i64, CoreError> {
+        Self::op_advance_execution_epoch(&self.pool, instance_id).await
+    }
+
+    async fn current_execution_epoch(&self, instance_id: &str) -> Result<Option<i64>, CoreError> {
+        Self::op_current_execution_epoch(&self.pool, instance_id).await
+    }
+
     async fn mark_for_recovery(
         &self,
         instance_id: &str,
@@ -9,8 +9,9 @@ use sqlx::sqlite::SqlitePoolOptions;
 use crate::error::CoreError;
 
 use super::{
-    CheckpointRecord, CompleteInstanceParams, CustomSignalRecord, EventRecord, InstanceRecord,
-    ListEventsFilter, ListStepSummariesFilter, Persistence, SignalRecord, StepSummaryRecord,
+    CheckpointHints, CheckpointRecord, CompleteInstanceParams, CustomSignalRecord, EventRecord,
+    InstanceRecord, ListEventsFilter, ListStepSummariesFilter, Persistence, PersistenceTx,
+    SignalRecord, StepSummaryRecord,
 };
 
 static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations/sqlite");
@@ -121,6 +122,104 @@ crate::persistence::common::ops::impl_retention_ops!(
     crate::persistence::dialect::SqliteDialect
 );
 
+async fn insert_event<'e, E: sqlx::SqliteExecutor<'e>>(
+    executor: E,
+    event: &EventRecord,
+) -> Result<(), CoreError> {
+    sqlx::query(
+        r#"
+        INSERT INTO instance_events (instance_id, event_type, checkpoint_id, payload, created_at, subtype)
+        VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP, ?)
+        "#,
+    )
+    .bind(&event.instance_id)
+    .bind(&event.event_type)
+    .bind(&event.checkpoint_id)
+    .bind(&event.payload)
+    .bind(&event.subtype)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Transaction handed out by [`SqlitePersistence`]'s `begin`.
+pub struct SqliteTx {
+    tx: sqlx::Transaction<'static, sqlx::Sqlite>,
+    /// Instances whose status or checkpoint changed, re-notified on commit.
+    touched: Vec<String>,
+}
+
+#[async_trait::async_trait]
+impl PersistenceTx for SqliteTx {
+    async fn save_checkpoint_with_hints(
+        &mut self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints<'_>,
+    ) -> Result<(), CoreError> {
+        SqlitePersistence::op_save_checkpoint_with_hints(
+            &mut *self.tx,
+            instance_id,
+            checkpoint_id,
+            state,
+            hints,
+        )
+        .await
+    }
+
+    async fn update_instance_checkpoint(
+        &mut self,
+        instance_id: &str,
+        checkpoint_id: &str,
+    ) -> Result<(), CoreError> {
+        SqlitePersistence::op_update_instance_checkpoint(&mut *self.tx, instance_id, checkpoint_id)
+            .await?;
+        self.touched.push(instance_id.to_string());
+        Ok(())
+    }
+
+    async fn insert_event(&mut self, event: &EventRecord) -> Result<(), CoreError> {
+        insert_event(&mut *self.tx, event).await
+    }
+
+    async fn complete_instance(
+        &mut self,
+        params: CompleteInstanceParams<'_>,
+    ) -> Result<bool, CoreError> {
+        let instance_id = params.instance_id.to_string();
+        let applied =
+            SqlitePersistence::op_complete_instance_unified(&mut *self.tx, params).await?;
+        if applied {
+            self.touched.push(instance_id);
+        }
+        Ok(applied)
+    }
+
+    async fn set_instance_sleep(
+        &mut self,
+        instance_id: &str,
+        sleep_until: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        SqlitePersistence::op_set_instance_sleep(&mut *self.tx, instance_id, sleep_until).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), CoreError> {
+        let Self { tx, touched } = *self;
+        tx.commit().await?;
+        for instance_id in &touched {
+            crate::status_watch::notify(instance_id);
+        }
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), CoreError> {
+        self.tx.rollback().await?;
+        Ok(())
+    }
+}
+
 #[async_trait::async_trait]
 impl Persistence for SqlitePersistence {
     async fn register_instance(&self, instance_id: &str, tenant_id: &str) -> Result<(), CoreError> {
@@ -211,6 +310,17 @@ impl Persistence for SqlitePersistence {
         Self::op_save_checkpoint(&self.pool, instance_id, checkpoint_id, state).await
     }
 
+    async fn save_checkpoint_with_hints(
+        &self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints<'_>,
+    ) -> Result<(), CoreError> {
+        Self::op_save_checkpoint_with_hints(&self.pool, instance_id, checkpoint_id, state, hints)
+            .await
+    }
+
     async fn load_checkpoint(
         &self,
         instance_id: &str,
@@ -257,22 +367,32 @@ impl Persistence for SqlitePersistence {
         .await
     }
 
+    async fn delete_checkpoints_with_prefix(
+        &self,
+        instance_id: &str,
+        prefix: &str,
+    ) -> Result<u64, CoreError> {
+        Self::op_delete_checkpoints_with_prefix(&self.pool, instance_id, prefix).await
+    }
+
+    async fn list_checkpoint_ids(
+        &self,
+        instance_id: &str,
+        prefix: Option<&str>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<String>, CoreError> {
+        Self::op_list_checkpoint_ids(&self.pool, instance_id, prefix, since).await
+    }
+
     async fn insert_event(&self, event: &EventRecord) -> Result<(), CoreError> {
-        sqlx::query(
-            r#"
-            INSERT INTO instance_events (instance_id, event_type, checkpoint_id, payload, created_at, subtype)
-            VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP, ?)
-            "#,
-        )
-        .bind(&event.instance_id)
-        .bind(&event.event_type)
-        .bind(&event.checkpoint_id)
-        .bind(&event.payload)
-        .bind(&event.subtype)
-        .execute(&self.pool)
-        .await?;
+        insert_event(&self.pool, event).await
+    }
 
-        Ok(())
+    async fn begin(&self) -> Result<Option<Box<dyn PersistenceTx>>, CoreError> {
+        Ok(Some(Box::new(SqliteTx {
+            tx: self.pool.begin().await?,
+            touched: Vec::new(),
+        })))
     }
 
     async fn insert_signal(
@@ -408,6 +528,22 @@ impl Persistence for SqlitePersistence {
         Self::op_claim_sleeping_instance(&self.pool, instance_id).await
     }
 
+    async fn claim_pending_instance(&self, instance_id: &str) -> Result<bool, CoreError> {
+        Self::op_claim_pending_instance(&self.pool, instance_id).await
+    }
+
+    async fn claim_resumable_instance(&self, instance_id: &str) -> Result<bool, CoreError> {
+        Self::op_claim_resumable_instance(&self.pool, instance_id).await
+    }
+
+    async fn advance_execution_epoch(&self, instance_id: &str) -> Result<i64, CoreError> {
+        Self::op_advance_execution_epoch(&self.pool, instance_id).await
+    }
+
+    async fn current_execution_epoch(&self, instance_id: &str) -> Result<Option<i64>, CoreError> {
+        Self::op_current_execution_epoch(&self.pool, instance_id).await
+    }
+
     async fn mark_for_recovery(
         &self,
         instance_id: &str,
@@ -580,6 +716,47 @@ mod tests {
         assert_eq!(instance.checkpoint_id, Some("checkpoint-1".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_status_version_bumps_on_status_and_checkpoint_changes() {
+        let pool = test_pool().await;
+        let persistence = SqlitePersistence::new(pool);
+
+        let instance_id = Uuid::new_v4().to_string();
+        persistence
+            .register_instance(&instance_id, "test-tenant")
+            .await
+            .unwrap();
+        async fn version(p: &SqlitePersistence, id: &str) -> i64 {
+            p.get_instance(id).await.unwrap().unwrap().status_version
+        }
+        assert_eq!(version(&persistence, &instance_id).await, 0);
+
+        persistence
+            .update_instance_status(&instance_id, "running", Some(Utc::now()))
+            .await
+            .unwrap();
+        assert_eq!(version(&persistence, &instance_id).await, 1);
+
+        persistence
+            .update_instance_checkpoint(&instance_id, "checkpoint-1")
+            .await
+            .unwrap();
+        assert_eq!(version(&persistence, &instance_id).await, 2);
+
+        persistence
+            .complete_instance(CompleteInstanceParams::new(&instance_id, "completed"))
+            .await
+            .unwrap();
+        assert_eq!(version(&persistence, &instance_id).await, 3);
+
+        // Writes that don't touch status or checkpoint leave it alone.
+        persistence
+            .store_instance_input(&instance_id, b"{}")
+            .await
+            .unwrap();
+        assert_eq!(version(&persistence, &instance_id).await, 3);
+    }
+
     #[tokio::test]
     async fn test_complete_instance_success() {
         let pool = test_pool().await;
@@ -747,6 +924,45 @@ mod tests {
         assert_eq!(checkpoints[0].checkpoint_id, "cp-1");
     }
 
+    #[tokio::test]
+    async fn test_list_checkpoint_ids() {
+        let pool = test_pool().await;
+        let persistence = SqlitePersistence::new(pool);
+
+        let instance_id = Uuid::new_v4().to_string();
+        persistence
+            .register_instance(&instance_id, "test-tenant")
+            .await
+            .unwrap();
+
+        for id in ["durable::fetch::1", "durable::fetch::2", "sleep::1"] {
+            persistence
+                .save_checkpoint(&instance_id, id, b"state")
+                .await
+                .unwrap();
+        }
+
+        let mut all = persistence
+            .list_checkpoint_ids(&instance_id, None, None)
+            .await
+            .expect("Failed to list checkpoint ids");
+        all.sort();
+        assert_eq!(all, ["durable::fetch::1", "durable::fetch::2", "sleep::1"]);
+
+        let mut durable = persistence
+            .list_checkpoint_ids(&instance_id, Some("durable::"), None)
+            .await
+            .expect("Failed to list checkpoint ids");
+        durable.sort();
+        assert_eq!(durable, ["durable::fetch::1", "durable::fetch::2"]);
+
+        let other = persistence
+            .list_checkpoint_ids(&Uuid::new_v4().to_string(), None, None)
+            .await
+            .expect("Failed to list checkpoint ids");
+        assert!(other.is_empty());
+    }
+
     #[tokio::test]
     async fn test_count_checkpoints() {
         let pool = test_pool().await;
@@ -775,6 +991,58 @@ mod tests {
         assert_eq!(count, 2);
     }
 
+    #[tokio::test]
+    async fn test_delete_checkpoints_with_prefix() {
+        let pool = test_pool().await;
+        let persistence = SqlitePersistence::new(pool);
+
+        let instance_id = Uuid::new_v4().to_string();
+        persistence
+            .register_instance(&instance_id, "test-tenant")
+            .await
+            .unwrap();
+
+        for checkpoint_id in [
+            "wf::agent::http::get::fetch::[0]",
+            "wf::agent::http::get::fetch::[1]",
+            "wf::agent::http::get::fetch::[2]",
+            "wf::split::loop",
+        ] {
+            persistence
+                .save_checkpoint(&instance_id, checkpoint_id, b"state")
+                .await
+                .unwrap();
+        }
+        persistence
+            .update_instance_checkpoint(&instance_id, "wf::agent::http::get::fetch::[2]")
+            .await
+            .unwrap();
+
+        let deleted = persistence
+            .delete_checkpoints_with_prefix(&instance_id, "wf::agent::http::get::fetch::[")
+            .await
+            .expect("Failed to compact checkpoints");
+        assert_eq!(deleted, 2, "the resume checkpoint must be kept");
+
+        let remaining: Vec<String> = persistence
+            .list_checkpoints(&instance_id, None, 10, 0, None, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.checkpoint_id)
+            .collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.contains(&"wf::split::loop".to_string()));
+        assert!(remaining.contains(&"wf::agent::http::get::fetch::[2]".to_string()));
+
+        // Re-running the same compaction is a no-op.
+        let deleted = persistence
+            .delete_checkpoints_with_prefix(&instance_id, "wf::agent::http::get::fetch::[")
+            .await
+            .unwrap();
+        assert_eq!(deleted, 0);
+    }
+
     #[tokio::test]
     async fn test_insert_event() {
         let pool = test_pool().await;
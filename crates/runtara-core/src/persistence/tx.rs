@@ -0,0 +1,154 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Transactions spanning several persistence writes.
+//!
+//! Handler flows such as "save checkpoint, then point the instance at it" or
+//! "record the terminal event, then complete the instance" must land together:
+//! a crash between the two writes used to leave rows that needed hand repair.
+//! [`Persistence::begin`] opens a [`PersistenceTx`] exposing the writes those
+//! flows use; dropping it without [`PersistenceTx::commit`] rolls back.
+//!
+//! Backends without transactions keep the default `begin` (returning `None`);
+//! [`begin_or_autocommit`] then hands out an [`AutoCommit`] wrapper that
+//! applies each write immediately, so handlers need only one code path.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::{CheckpointHints, CompleteInstanceParams, EventRecord, Persistence};
+use crate::error::CoreError;
+
+/// Writes that commit or roll back together.
+#[async_trait]
+pub trait PersistenceTx: Send {
+    /// See [`Persistence::save_checkpoint_with_hints`].
+    async fn save_checkpoint_with_hints(
+        &mut self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints<'_>,
+    ) -> Result<(), CoreError>;
+
+    /// See [`Persistence::save_checkpoint`].
+    async fn save_checkpoint(
+        &mut self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        state: &[u8],
+    ) -> Result<(), CoreError> {
+        self.save_checkpoint_with_hints(
+            instance_id,
+            checkpoint_id,
+            state,
+            CheckpointHints::default(),
+        )
+        .await
+    }
+
+    /// See [`Persistence::update_instance_checkpoint`].
+    async fn update_instance_checkpoint(
+        &mut self,
+        instance_id: &str,
+        checkpoint_id: &str,
+    ) -> Result<(), CoreError>;
+
+    /// See [`Persistence::insert_event`].
+    async fn insert_event(&mut self, event: &EventRecord) -> Result<(), CoreError>;
+
+    /// See [`Persistence::complete_instance`].
+    async fn complete_instance(
+        &mut self,
+        params: CompleteInstanceParams<'_>,
+    ) -> Result<bool, CoreError>;
+
+    /// See [`Persistence::set_instance_sleep`].
+    async fn set_instance_sleep(
+        &mut self,
+        instance_id: &str,
+        sleep_until: DateTime<Utc>,
+    ) -> Result<(), CoreError>;
+
+    /// Make every write in the transaction durable.
+    async fn commit(self: Box<Self>) -> Result<(), CoreError>;
+
+    /// Discard every write in the transaction. Dropping has the same effect.
+    async fn rollback(self: Box<Self>) -> Result<(), CoreError>;
+}
+
+/// Open a transaction on `persistence`, or an [`AutoCommit`] stand-in when
+/// the backend has none.
+pub async fn begin_or_autocommit(
+    persistence: &dyn Persistence,
+) -> Result<Box<dyn PersistenceTx + '_>, CoreError> {
+    Ok(match persistence.begin().await? {
+        Some(tx) => tx,
+        None => Box::new(AutoCommit(persistence)),
+    })
+}
+
+/// Applies each write immediately; commit and rollback are no-ops.
+pub struct AutoCommit<'a>(pub &'a dyn Persistence);
+
+#[async_trait]
+impl PersistenceTx for AutoCommit<'_> {
+    async fn save_checkpoint_with_hints(
+        &mut self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints<'_>,
+    ) -> Result<(), CoreError> {
+        self.0
+            .save_checkpoint_with_hints(instance_id, checkpoint_id, state, hints)
+            .await
+    }
+
+    async fn save_checkpoint(
+        &mut self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        state: &[u8],
+    ) -> Result<(), CoreError> {
+        self.0
+            .save_checkpoint(instance_id, checkpoint_id, state)
+            .await
+    }
+
+    async fn update_instance_checkpoint(
+        &mut self,
+        instance_id: &str,
+        checkpoint_id: &str,
+    ) -> Result<(), CoreError> {
+        self.0
+            .update_instance_checkpoint(instance_id, checkpoint_id)
+            .await
+    }
+
+    async fn insert_event(&mut self, event: &EventRecord) -> Result<(), CoreError> {
+        self.0.insert_event(event).await
+    }
+
+    async fn complete_instance(
+        &mut self,
+        params: CompleteInstanceParams<'_>,
+    ) -> Result<bool, CoreError> {
+        self.0.complete_instance(params).await
+    }
+
+    async fn set_instance_sleep(
+        &mut self,
+        instance_id: &str,
+        sleep_until: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        self.0.set_instance_sleep(instance_id, sleep_until).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), CoreError> {
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), CoreError> {
+        Ok(())
+    }
+}
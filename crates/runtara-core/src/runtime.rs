@@ -43,12 +43,14 @@ use tracing::{error, info};
 use crate::instance_handlers::InstanceHandlerState;
 use crate::persistence::Persistence;
 use crate::server::InstanceServerState;
+use crate::session::InstanceSessions;
 
 /// Builder for creating a [`CoreRuntime`].
 pub struct CoreRuntimeBuilder {
     persistence: Option<Arc<dyn Persistence>>,
     bind_addr: SocketAddr,
     max_concurrent_instances: u32,
+    sessions: InstanceSessions,
 }
 
 impl std::fmt::Debug for CoreRuntimeBuilder {
@@ -57,6 +59,7 @@ impl std::fmt::Debug for CoreRuntimeBuilder {
             .field("persistence", &self.persistence.as_ref().map(|_| "..."))
             .field("bind_addr", &self.bind_addr)
             .field("max_concurrent_instances", &self.max_concurrent_instances)
+            .field("sessions", &self.sessions)
             .finish()
     }
 }
@@ -67,6 +70,7 @@ impl Default for CoreRuntimeBuilder {
             persistence: None,
             bind_addr: "0.0.0.0:8001".parse().unwrap(),
             max_concurrent_instances: 0,
+            sessions: InstanceSessions::disabled(),
         }
     }
 }
@@ -99,6 +103,13 @@ impl CoreRuntimeBuilder {
         self
     }
 
+    /// Bind instance requests to their registration with session tokens
+    /// (see [`crate::session`]). Default: disabled.
+    pub fn instance_sessions(mut self, sessions: InstanceSessions) -> Self {
+        self.sessions = sessions;
+        self
+    }
+
     /// Build the runtime configuration.
     ///
     /// Returns an error if required fields are missing.
@@ -111,6 +122,7 @@ impl CoreRuntimeBuilder {
             persistence,
             bind_addr: self.bind_addr,
             max_concurrent_instances: self.max_concurrent_instances,
+            sessions: self.sessions,
         })
     }
 }
@@ -120,6 +132,7 @@ pub struct CoreRuntimeConfig {
     persistence: Arc<dyn Persistence>,
     bind_addr: SocketAddr,
     max_concurrent_instances: u32,
+    sessions: InstanceSessions,
 }
 
 impl std::fmt::Debug for CoreRuntimeConfig {
@@ -128,6 +141,7 @@ impl std::fmt::Debug for CoreRuntimeConfig {
             .field("persistence", &"...")
             .field("bind_addr", &self.bind_addr)
             .field("max_concurrent_instances", &self.max_concurrent_instances)
+            .field("sessions", &self.sessions)
             .finish()
     }
 }
@@ -135,10 +149,10 @@ impl std::fmt::Debug for CoreRuntimeConfig {
 impl CoreRuntimeConfig {
     /// Start the runtime, spawning the HTTP server task.
     pub async fn start(self) -> Result<CoreRuntime> {
-        let state = Arc::new(InstanceHandlerState::with_limits(
-            self.persistence,
-            self.max_concurrent_instances,
-        ));
+        let state = Arc::new(
+            InstanceHandlerState::with_limits(self.persistence, self.max_concurrent_instances)
+                .with_sessions(self.sessions),
+        );
         let draining = state.draining_handle();
 
         let bind_addr = self.bind_addr;
@@ -6,15 +6,17 @@
 //! This enables workflows (native or WASM) using the HTTP SDK backend
 //! to communicate with runtara-core.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
 use axum::extract::DefaultBodyLimit;
 use axum::{
     Router,
-    extract::{Path, State},
-    http::StatusCode,
-    response::{IntoResponse, Json},
+    extract::{Path, Query, Request, State},
+    http::{Method, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
 };
 use base64::Engine;
@@ -22,15 +24,21 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use tracing::{error, info, warn};
 
+use crate::error::CoreError;
+use crate::fencing::{EXECUTION_EPOCH_HEADER, check_execution_epoch};
 use crate::instance_handlers::{
     self, CheckpointRequest as HandlerCheckpointRequest,
+    CompactCheckpointsRequest as HandlerCompactCheckpointsRequest,
     GetInstanceStatusRequest as HandlerGetStatusRequest, InstanceEvent as HandlerInstanceEvent,
     InstanceEventType as HandlerEventType, InstanceHandlerState, InstanceStatus,
+    ListCheckpointKeysRequest as HandlerListCheckpointKeysRequest,
     PollSignalsRequest as HandlerPollSignalsRequest,
     RegisterInstanceRequest as HandlerRegisterRequest,
     RetryAttemptEvent as HandlerRetryAttemptEvent, SignalAck as HandlerSignalAck, SignalType,
     SleepRequest as HandlerSleepRequest,
 };
+use crate::maintenance::maintenance_retry_after;
+use crate::session::{SESSION_HEADER, TENANT_HEADER};
 
 // ============================================================================
 // JSON request/response types (mirror the protobuf types)
@@ -44,6 +52,9 @@ pub struct RegisterRequest {
     /// Optional checkpoint ID to resume from
     #[serde(default)]
     pub checkpoint_id: Option<String>,
+    /// Protocol/SDK/build versions (absent from pre-handshake binaries)
+    #[serde(flatten)]
+    pub versions: instance_handlers::ReportedVersions,
 }
 
 /// Register instance response
@@ -52,6 +63,24 @@ pub struct RegisterResponse {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// `INCOMPATIBLE_VERSION` when the binary's protocol version is refused
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supported_protocol_min: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supported_protocol_max: Option<u32>,
+    /// Version warnings for an accepted registration
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Session token to send as `X-Runtara-Session` on every later request,
+    /// present when session binding is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_token: Option<String>,
+    /// Execution epoch to send as `X-Runtara-Execution-Epoch` on every later
+    /// request; writes carrying an older epoch are refused as `FENCED`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_epoch: Option<i64>,
 }
 
 /// Checkpoint request
@@ -61,6 +90,15 @@ pub struct CheckpointRequest {
     pub checkpoint_id: String,
     /// Serialized workflow state (base64-encoded)
     pub state: String,
+    /// Content type hint for the state (e.g. "application/json")
+    #[serde(default)]
+    pub content_type: Option<String>,
+    /// Shape tag for the state (e.g. "step_output")
+    #[serde(default)]
+    pub shape: Option<String>,
+    /// Custom-signal keys to check besides `checkpoint_id`
+    #[serde(default)]
+    pub wait_keys: Vec<String>,
 }
 
 /// Checkpoint response
@@ -74,7 +112,7 @@ pub struct CheckpointResponse {
     /// Pending instance-wide signal (cancel/pause)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signal: Option<SignalInfo>,
-    /// Pending custom signal (WaitForSignal)
+    /// Pending custom signal (WaitForSignal or one of the wait keys)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_signal: Option<CustomSignalInfo>,
     /// Last error from a previous checkpoint attempt
@@ -128,6 +166,40 @@ pub struct InstanceEventRequest {
     pub subtype: Option<String>,
 }
 
+/// Query parameters for the checkpoint key listing.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListCheckpointKeysQuery {
+    /// Only list checkpoint IDs starting with this prefix
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// Only list checkpoints created at or after this time (RFC 3339)
+    #[serde(default)]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Checkpoint key listing response
+#[derive(Debug, Serialize)]
+pub struct ListCheckpointKeysResponse {
+    pub success: bool,
+    /// Matching checkpoint IDs, oldest first
+    pub checkpoint_ids: Vec<String>,
+}
+
+/// Checkpoint compaction request
+#[derive(Debug, Deserialize)]
+pub struct CompactCheckpointsRequest {
+    /// Checkpoint ID prefix to delete
+    pub prefix: String,
+}
+
+/// Checkpoint compaction response
+#[derive(Debug, Serialize)]
+pub struct CompactCheckpointsResponse {
+    pub success: bool,
+    /// Number of checkpoints deleted
+    pub deleted: u64,
+}
+
 /// Sleep request
 #[derive(Debug, Deserialize)]
 pub struct SleepRequest {
@@ -197,6 +269,7 @@ async fn register_handler(
         instance_id,
         tenant_id: body.tenant_id,
         checkpoint_id: body.checkpoint_id,
+        versions: body.versions,
     };
 
     match instance_handlers::handle_register_instance(&state, request).await {
@@ -205,19 +278,47 @@ async fn register_handler(
                 Json(RegisterResponse {
                     success: true,
                     error: None,
+                    code: None,
+                    supported_protocol_min: None,
+                    supported_protocol_max: None,
+                    warnings: resp.warnings,
+                    session_token: resp.session_token,
+                    execution_epoch: resp.execution_epoch,
                 })
                 .into_response()
+            } else if let Some((min, max)) = resp.supported_protocol_versions {
+                (
+                    StatusCode::CONFLICT,
+                    Json(RegisterResponse {
+                        success: false,
+                        error: Some(resp.error),
+                        code: Some("INCOMPATIBLE_VERSION".to_string()),
+                        supported_protocol_min: Some(min),
+                        supported_protocol_max: Some(max),
+                        warnings: Vec::new(),
+                        session_token: None,
+                        execution_epoch: None,
+                    }),
+                )
+                    .into_response()
             } else {
                 let status = match resp.error.as_str() {
                     instance_handlers::ERROR_SERVER_DRAINING => StatusCode::SERVICE_UNAVAILABLE,
                     instance_handlers::ERROR_MAX_CONCURRENT_INSTANCES => {
                         StatusCode::TOO_MANY_REQUESTS
                     }
+                    instance_handlers::ERROR_TENANT_MISMATCH => StatusCode::FORBIDDEN,
                     _ => StatusCode::BAD_REQUEST,
                 };
                 let body = Json(RegisterResponse {
                     success: false,
                     error: Some(resp.error),
+                    code: (status == StatusCode::FORBIDDEN).then(|| "AUTHORIZATION".to_string()),
+                    supported_protocol_min: None,
+                    supported_protocol_max: None,
+                    warnings: Vec::new(),
+                    session_token: None,
+                    execution_epoch: None,
                 });
                 // Surface Retry-After for the rate-limited/draining cases so SDK
                 // clients can back off sensibly.
@@ -270,6 +371,9 @@ async fn checkpoint_handler(
         instance_id,
         checkpoint_id: body.checkpoint_id,
         state: state_bytes,
+        content_type: body.content_type,
+        shape: body.shape,
+        wait_keys: body.wait_keys,
     };
 
     match instance_handlers::handle_checkpoint(&state, request).await {
@@ -311,6 +415,9 @@ async fn checkpoint_handler(
             .into_response()
         }
         Err(e) => {
+            if let Some(resp) = maintenance_response(&e) {
+                return resp;
+            }
             error!("Checkpoint handler error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -324,6 +431,72 @@ async fn checkpoint_handler(
     }
 }
 
+/// GET /api/v1/instances/{instance_id}/checkpoints/keys
+async fn list_checkpoint_keys_handler(
+    State(state): State<Arc<InstanceHandlerState>>,
+    Path(instance_id): Path<String>,
+    Query(query): Query<ListCheckpointKeysQuery>,
+) -> impl IntoResponse {
+    let request = HandlerListCheckpointKeysRequest {
+        instance_id,
+        prefix: query.prefix,
+        since: query.since,
+    };
+
+    match instance_handlers::handle_list_checkpoint_keys(&state, request).await {
+        Ok(resp) => Json(ListCheckpointKeysResponse {
+            success: true,
+            checkpoint_ids: resp.checkpoint_ids,
+        })
+        .into_response(),
+        Err(e) => {
+            error!("List checkpoint keys handler error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": e.to_string(),
+                    "code": "LIST_CHECKPOINT_KEYS_ERROR"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// POST /api/v1/instances/{instance_id}/checkpoints/compact
+async fn compact_checkpoints_handler(
+    State(state): State<Arc<InstanceHandlerState>>,
+    Path(instance_id): Path<String>,
+    Json(body): Json<CompactCheckpointsRequest>,
+) -> impl IntoResponse {
+    let request = HandlerCompactCheckpointsRequest {
+        instance_id,
+        prefix: body.prefix,
+    };
+
+    match instance_handlers::handle_compact_checkpoints(&state, request).await {
+        Ok(resp) => Json(CompactCheckpointsResponse {
+            success: true,
+            deleted: resp.deleted,
+        })
+        .into_response(),
+        Err(e) => {
+            if let Some(resp) = maintenance_response(&e) {
+                return resp;
+            }
+            error!("Compact checkpoints handler error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": e.to_string(),
+                    "code": "COMPACT_CHECKPOINTS_ERROR"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// GET /api/v1/instances/{instance_id}/signals
 async fn poll_signals_handler(
     State(state): State<Arc<InstanceHandlerState>>,
@@ -467,6 +640,9 @@ async fn instance_event_handler(
             }
         }
         Err(e) => {
+            if let Some(resp) = maintenance_response(&e) {
+                return resp;
+            }
             error!("Instance event error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -518,6 +694,9 @@ async fn completed_handler(
     match instance_handlers::handle_instance_event(&state, event).await {
         Ok(_) => Json(SuccessResponse { success: true }).into_response(),
         Err(e) => {
+            if let Some(resp) = maintenance_response(&e) {
+                return resp;
+            }
             error!("Completed handler error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -554,6 +733,9 @@ async fn failed_handler(
     match instance_handlers::handle_instance_event(&state, event).await {
         Ok(_) => Json(SuccessResponse { success: true }).into_response(),
         Err(e) => {
+            if let Some(resp) = maintenance_response(&e) {
+                return resp;
+            }
             error!("Failed handler error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -584,6 +766,9 @@ async fn suspended_handler(
     match instance_handlers::handle_instance_event(&state, event).await {
         Ok(_) => Json(SuccessResponse { success: true }).into_response(),
         Err(e) => {
+            if let Some(resp) = maintenance_response(&e) {
+                return resp;
+            }
             error!("Suspended handler error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -627,6 +812,9 @@ async fn sleep_handler(
     match instance_handlers::handle_sleep(&state, request).await {
         Ok(_) => Json(SuccessResponse { success: true }).into_response(),
         Err(e) => {
+            if let Some(resp) = maintenance_response(&e) {
+                return resp;
+            }
             error!("Sleep handler error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -703,6 +891,9 @@ async fn retry_handler(
     match instance_handlers::handle_retry_attempt(&state, event).await {
         Ok(()) => Json(SuccessResponse { success: true }).into_response(),
         Err(e) => {
+            if let Some(resp) = maintenance_response(&e) {
+                return resp;
+            }
             warn!("Retry attempt error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -729,28 +920,38 @@ pub struct InstanceStatusResponse {
     pub output: Option<String>, // base64
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Change counter; pass back as `wait_for_change_from` to long-poll.
+    pub status_version: i64,
+}
+
+/// Query parameters for the status endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct StatusQuery {
+    /// Long-poll cursor: the `status_version` or status last seen.
+    #[serde(default)]
+    pub wait_for_change_from: Option<String>,
+    /// Upper bound on the long-poll wait in milliseconds.
+    #[serde(default)]
+    pub max_wait_ms: Option<u64>,
 }
 
 /// GET /api/v1/instances/{instance_id}/status
 async fn status_handler(
     State(state): State<Arc<InstanceHandlerState>>,
     Path(instance_id): Path<String>,
+    Query(query): Query<StatusQuery>,
 ) -> impl IntoResponse {
     let request = HandlerGetStatusRequest {
         instance_id: instance_id.clone(),
+        wait_for_change_from: query.wait_for_change_from,
+        max_wait_ms: query.max_wait_ms,
     };
 
     match instance_handlers::handle_get_instance_status(&state, request).await {
         Ok(resp) => {
-            let status_str = match InstanceStatus::try_from_i32(resp.status) {
-                Some(InstanceStatus::StatusPending) => "pending",
-                Some(InstanceStatus::StatusRunning) => "running",
-                Some(InstanceStatus::StatusSuspended) => "suspended",
-                Some(InstanceStatus::StatusCompleted) => "completed",
-                Some(InstanceStatus::StatusFailed) => "failed",
-                Some(InstanceStatus::StatusCancelled) => "cancelled",
-                _ => "unknown",
-            };
+            let status_str = InstanceStatus::try_from_i32(resp.status)
+                .unwrap_or(InstanceStatus::StatusUnknown)
+                .as_str();
 
             let output = resp
                 .output
@@ -767,6 +968,7 @@ async fn status_handler(
                 checkpoint_id: resp.checkpoint_id,
                 output,
                 error: resp.error,
+                status_version: resp.status_version,
             })
             .into_response()
         }
@@ -831,17 +1033,108 @@ async fn input_handler(
     }
 }
 
+/// 503 with a `Retry-After` header for writes refused during maintenance.
+fn maintenance_response(err: &anyhow::Error) -> Option<Response> {
+    let retry_after = maintenance_retry_after(err)?;
+    let retry_after_secs = retry_after.as_millis().div_ceil(1000).max(1).to_string();
+    Some(
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [("Retry-After", retry_after_secs)],
+            Json(json!({
+                "error": err.to_string(),
+                "code": "MAINTENANCE",
+                "retry_after_ms": retry_after.as_millis() as u64
+            })),
+        )
+            .into_response(),
+    )
+}
+
+/// Refuse instance requests whose session token is not bound to the
+/// instance in the path (see [`crate::session`]), and writes from an
+/// execution epoch a newer registration has superseded (see
+/// [`crate::fencing`]).
+async fn instance_session_guard(
+    State(state): State<Arc<InstanceHandlerState>>,
+    Path(params): Path<HashMap<String, String>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let instance_id = params
+        .get("instance_id")
+        .map(String::as_str)
+        .unwrap_or_default();
+    // Scoped so no borrow of the (non-Sync) request is held across `next`.
+    let (authorized, epoch) = {
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        let authorized =
+            state
+                .sessions
+                .authorize(header(SESSION_HEADER), instance_id, header(TENANT_HEADER));
+        // Every POST on an instance route writes; reads are never fenced.
+        let epoch = (request.method() == Method::POST)
+            .then(|| header(EXECUTION_EPOCH_HEADER)?.parse::<i64>().ok())
+            .flatten();
+        (authorized, epoch)
+    };
+
+    if let Err(e) = authorized {
+        warn!(instance_id, error = %e, "Refusing instance request");
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": e.to_string(),
+                "code": e.error_code()
+            })),
+        )
+            .into_response();
+    }
+
+    if let Some(epoch) = epoch
+        && let Err(e) = check_execution_epoch(state.persistence.as_ref(), instance_id, epoch).await
+    {
+        let status = match e {
+            CoreError::Fenced { .. } => {
+                warn!(instance_id, error = %e, "Refusing write from superseded execution");
+                StatusCode::CONFLICT
+            }
+            _ => {
+                error!(instance_id, error = %e, "Failed to check execution epoch");
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        return (
+            status,
+            Json(json!({
+                "error": e.to_string(),
+                "code": e.error_code()
+            })),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
 /// GET /health
 async fn health_handler(State(state): State<Arc<InstanceHandlerState>>) -> impl IntoResponse {
     let db_ok = state.persistence.health_check_db().await.unwrap_or(false);
+    let maintenance = state.maintenance.status();
     if db_ok {
-        Json(json!({"status": "healthy"})).into_response()
+        Json(json!({"status": "healthy", "maintenance": maintenance})).into_response()
     } else {
         (
             StatusCode::SERVICE_UNAVAILABLE,
             Json(json!({
                 "status": "unhealthy",
-                "error": "database check failed"
+                "error": "database check failed",
+                "maintenance": maintenance
             })),
         )
             .into_response()
@@ -856,17 +1149,22 @@ async fn health_handler(State(state): State<Arc<InstanceHandlerState>>) -> impl
 ///
 /// All routes are prefixed with `/api/v1`.
 pub fn instance_http_router(state: Arc<InstanceHandlerState>) -> Router {
-    Router::new()
-        // Instance lifecycle
-        .route(
-            "/api/v1/instances/{instance_id}/register",
-            post(register_handler),
-        )
+    // Everything an instance does after registering is checked against its
+    // session binding.
+    let instance_routes = Router::new()
         // Checkpointing
         .route(
             "/api/v1/instances/{instance_id}/checkpoint",
             post(checkpoint_handler),
         )
+        .route(
+            "/api/v1/instances/{instance_id}/checkpoints/keys",
+            get(list_checkpoint_keys_handler),
+        )
+        .route(
+            "/api/v1/instances/{instance_id}/checkpoints/compact",
+            post(compact_checkpoints_handler),
+        )
         // Signal polling
         .route(
             "/api/v1/instances/{instance_id}/signals",
@@ -908,6 +1206,18 @@ pub fn instance_http_router(state: Arc<InstanceHandlerState>) -> Router {
         )
         // Instance input
         .route("/api/v1/instances/{instance_id}/input", get(input_handler))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            instance_session_guard,
+        ));
+
+    Router::new()
+        // Instance lifecycle
+        .route(
+            "/api/v1/instances/{instance_id}/register",
+            post(register_handler),
+        )
+        .merge(instance_routes)
         // Health check
         .route("/health", get(health_handler))
         .layer(DefaultBodyLimit::max(64 * 1024 * 1024))
@@ -934,3 +1244,297 @@ pub async fn run_http_server(
     info!("Instance HTTP server stopped");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::body::{Body, to_bytes};
+    use axum::http::{Request as HttpRequest, header};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::instance_handlers::mock_persistence::MockPersistence;
+    use crate::persistence::SqlitePersistence;
+    use crate::session::InstanceSessions;
+
+    fn router(sessions: InstanceSessions) -> Router {
+        let state =
+            InstanceHandlerState::new(Arc::new(MockPersistence::new())).with_sessions(sessions);
+        instance_http_router(Arc::new(state))
+    }
+
+    async fn send(
+        router: &Router,
+        method: &str,
+        uri: &str,
+        headers: &[(&str, &str)],
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let mut builder = HttpRequest::builder().method(method).uri(uri);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        let request = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    async fn register(router: &Router, instance_id: &str, tenant_id: &str) -> (StatusCode, Value) {
+        send(
+            router,
+            "POST",
+            &format!("/api/v1/instances/{instance_id}/register"),
+            &[],
+            Some(json!({ "tenant_id": tenant_id })),
+        )
+        .await
+    }
+
+    async fn session_for(router: &Router, instance_id: &str, tenant_id: &str) -> String {
+        let (status, body) = register(router, instance_id, tenant_id).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        body["session_token"]
+            .as_str()
+            .expect("session token")
+            .to_string()
+    }
+
+    fn checkpoint_body() -> Option<Value> {
+        Some(json!({ "checkpoint_id": "cp-1", "state": "e30=" }))
+    }
+
+    #[tokio::test]
+    async fn cross_instance_checkpoint_is_refused() {
+        let router = router(InstanceSessions::with_secret("s3cret"));
+        let token_a = session_for(&router, "inst-a", "tenant-1").await;
+        session_for(&router, "inst-b", "tenant-2").await;
+
+        let (status, body) = send(
+            &router,
+            "POST",
+            "/api/v1/instances/inst-b/checkpoint",
+            &[(SESSION_HEADER, &token_a)],
+            checkpoint_body(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "AUTHORIZATION");
+
+        let (status, _) = send(
+            &router,
+            "POST",
+            "/api/v1/instances/inst-a/checkpoint",
+            &[(SESSION_HEADER, &token_a)],
+            checkpoint_body(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn cross_instance_signal_polling_is_refused() {
+        let router = router(InstanceSessions::with_secret("s3cret"));
+        let token_a = session_for(&router, "inst-a", "tenant-1").await;
+        session_for(&router, "inst-b", "tenant-2").await;
+
+        for uri in [
+            "/api/v1/instances/inst-b/signals",
+            "/api/v1/instances/inst-b/signals/approval",
+        ] {
+            let (status, body) =
+                send(&router, "GET", uri, &[(SESSION_HEADER, &token_a)], None).await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{uri}");
+            assert_eq!(body["code"], "AUTHORIZATION", "{uri}");
+        }
+
+        let (status, _) = send(
+            &router,
+            "GET",
+            "/api/v1/instances/inst-a/signals",
+            &[(SESSION_HEADER, &token_a)],
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn stated_tenant_must_match_the_session() {
+        let router = router(InstanceSessions::with_secret("s3cret"));
+        let token_a = session_for(&router, "inst-a", "tenant-1").await;
+
+        let (status, body) = send(
+            &router,
+            "GET",
+            "/api/v1/instances/inst-a/signals",
+            &[(SESSION_HEADER, &token_a), (TENANT_HEADER, "tenant-2")],
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "AUTHORIZATION");
+    }
+
+    #[tokio::test]
+    async fn registering_another_tenants_instance_is_refused() {
+        let router = router(InstanceSessions::with_secret("s3cret"));
+        session_for(&router, "inst-a", "tenant-1").await;
+
+        let (status, body) = register(&router, "inst-a", "tenant-2").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "AUTHORIZATION");
+        assert!(body.get("session_token").is_none());
+    }
+
+    #[tokio::test]
+    async fn missing_session_is_refused_only_when_required() {
+        let optional = router(InstanceSessions::with_secret("s3cret"));
+        session_for(&optional, "inst-a", "tenant-1").await;
+        let (status, _) = send(
+            &optional,
+            "GET",
+            "/api/v1/instances/inst-a/signals",
+            &[],
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let required = router(InstanceSessions::with_secret("s3cret").require(true));
+        session_for(&required, "inst-a", "tenant-1").await;
+        let (status, body) = send(
+            &required,
+            "GET",
+            "/api/v1/instances/inst-a/signals",
+            &[],
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "AUTHORIZATION");
+    }
+
+    #[tokio::test]
+    async fn disabled_sessions_issue_no_token() {
+        let router = router(InstanceSessions::disabled());
+        let (status, body) = register(&router, "inst-a", "tenant-1").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.get("session_token").is_none());
+
+        let (status, _) = send(
+            &router,
+            "POST",
+            "/api/v1/instances/inst-b/checkpoint",
+            &[(SESSION_HEADER, "not-a-token")],
+            checkpoint_body(),
+        )
+        .await;
+        assert_ne!(status, StatusCode::FORBIDDEN);
+    }
+
+    /// Router over in-memory SQLite, which tracks execution epochs.
+    async fn sqlite_router() -> Router {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::migrations::SQLITE.run(&pool).await.unwrap();
+        let state = InstanceHandlerState::new(Arc::new(SqlitePersistence::new(pool)));
+        instance_http_router(Arc::new(state))
+    }
+
+    fn epoch_of(body: &Value) -> i64 {
+        body["execution_epoch"].as_i64().expect("execution epoch")
+    }
+
+    #[tokio::test]
+    async fn superseded_registration_is_fenced() {
+        let router = sqlite_router().await;
+        let (status, first) = register(&router, "inst-a", "tenant-1").await;
+        assert_eq!(status, StatusCode::OK, "{first}");
+
+        // Two containers re-register the same instance at once.
+        let ((status_a, body_a), (status_b, body_b)) = tokio::join!(
+            register(&router, "inst-a", "tenant-1"),
+            register(&router, "inst-a", "tenant-1")
+        );
+        assert_eq!(status_a, StatusCode::OK, "{body_a}");
+        assert_eq!(status_b, StatusCode::OK, "{body_b}");
+        let (stale, current) = if epoch_of(&body_a) < epoch_of(&body_b) {
+            (epoch_of(&body_a), epoch_of(&body_b))
+        } else {
+            (epoch_of(&body_b), epoch_of(&body_a))
+        };
+        assert!(epoch_of(&first) < stale && stale < current);
+
+        for epoch in [epoch_of(&first), stale] {
+            let epoch = epoch.to_string();
+            for (uri, body) in [
+                ("/api/v1/instances/inst-a/checkpoint", checkpoint_body()),
+                (
+                    "/api/v1/instances/inst-a/completed",
+                    Some(json!({ "output": "e30=" })),
+                ),
+            ] {
+                let (status, body) = send(
+                    &router,
+                    "POST",
+                    uri,
+                    &[(EXECUTION_EPOCH_HEADER, &epoch)],
+                    body,
+                )
+                .await;
+                assert_eq!(status, StatusCode::CONFLICT, "{uri}: {body}");
+                assert_eq!(body["code"], "FENCED");
+            }
+            // Reads are never fenced.
+            let (status, _) = send(
+                &router,
+                "GET",
+                "/api/v1/instances/inst-a/status",
+                &[(EXECUTION_EPOCH_HEADER, &epoch)],
+                None,
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        let (status, body) = send(
+            &router,
+            "POST",
+            "/api/v1/instances/inst-a/checkpoint",
+            &[(EXECUTION_EPOCH_HEADER, &current.to_string())],
+            checkpoint_body(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+    }
+
+    #[tokio::test]
+    async fn writes_without_an_epoch_are_not_fenced() {
+        let router = sqlite_router().await;
+        register(&router, "inst-a", "tenant-1").await;
+        register(&router, "inst-a", "tenant-1").await;
+
+        let (status, body) = send(
+            &router,
+            "POST",
+            "/api/v1/instances/inst-a/checkpoint",
+            &[],
+            checkpoint_body(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+    }
+}
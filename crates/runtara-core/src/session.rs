@@ -0,0 +1,273 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Instance session binding.
+//!
+//! The instance protocol is stateless HTTP and every request names its
+//! instance in the URL, so without binding a buggy or compromised workflow
+//! binary could checkpoint into, or poll signals of, any other instance it
+//! can name. When a session secret is configured, a successful
+//! `RegisterInstance` returns a session token that binds the caller to the
+//! `(instance_id, tenant_id)` it registered as. The HTTP layer checks it on
+//! every later request and refuses a token bound to a different instance or
+//! tenant with [`CoreError::Authorization`].
+//!
+//! Tokens are HMAC-signed rather than stored, so they survive a core restart
+//! and work across replicas that share the secret. Registration itself
+//! refuses an existing instance under a different tenant, which is what makes
+//! the tenant in a token trustworthy. It does not authenticate the binary
+//! that registers: the binding only stops a registered caller from reaching
+//! past the instance it registered as.
+//!
+//! Binaries built against an SDK that predates sessions send no token. They
+//! are served unless [`InstanceSessions::require`] is set.
+
+use std::fmt;
+use std::sync::Arc;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::error::CoreError;
+
+/// Request header carrying the session token.
+pub const SESSION_HEADER: &str = "x-runtara-session";
+
+/// Request header the SDK uses to state its tenant.
+pub const TENANT_HEADER: &str = "x-runtara-tenant-id";
+
+const TOKEN_VERSION: &str = "v1";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// What a verified session token is bound to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionBinding {
+    /// Instance the token was issued to.
+    pub instance_id: String,
+    /// Tenant the instance was registered under.
+    pub tenant_id: String,
+}
+
+/// Issues and checks instance session tokens.
+///
+/// The default value is disabled: no tokens are issued and none are checked.
+#[derive(Clone, Default)]
+pub struct InstanceSessions {
+    key: Option<Arc<[u8]>>,
+    required: bool,
+}
+
+impl fmt::Debug for InstanceSessions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstanceSessions")
+            .field("enabled", &self.is_enabled())
+            .field("required", &self.required)
+            .finish()
+    }
+}
+
+impl InstanceSessions {
+    /// Sessions off: nothing is issued or checked.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Sign tokens with `secret`. An empty secret leaves sessions disabled.
+    pub fn with_secret(secret: impl AsRef<[u8]>) -> Self {
+        let secret = secret.as_ref();
+        Self {
+            key: (!secret.is_empty()).then(|| Arc::from(secret)),
+            required: false,
+        }
+    }
+
+    /// Refuse instance requests that carry no token. Has no effect while
+    /// sessions are disabled.
+    pub fn require(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Load from the environment.
+    ///
+    /// - `RUNTARA_INSTANCE_SESSION_SECRET`: signing secret; unset or empty
+    ///   disables sessions. Replicas behind one address must share it.
+    /// - `RUNTARA_REQUIRE_INSTANCE_SESSION`: `true`/`1` refuses requests
+    ///   without a token (default: off, so older binaries keep working).
+    pub fn from_env() -> Self {
+        let sessions = std::env::var("RUNTARA_INSTANCE_SESSION_SECRET")
+            .map(Self::with_secret)
+            .unwrap_or_default();
+        let required = std::env::var("RUNTARA_REQUIRE_INSTANCE_SESSION")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false);
+        sessions.require(required)
+    }
+
+    /// Whether tokens are issued and checked.
+    pub fn is_enabled(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Whether requests without a token are refused.
+    pub fn is_required(&self) -> bool {
+        self.is_enabled() && self.required
+    }
+
+    /// Issue a token binding `instance_id` to `tenant_id`, or `None` while
+    /// sessions are disabled.
+    pub fn issue(&self, instance_id: &str, tenant_id: &str) -> Option<String> {
+        let key = self.key.as_ref()?;
+        let signature = sign(key, instance_id, tenant_id).finalize().into_bytes();
+        Some(format!(
+            "{}.{}.{}.{}",
+            TOKEN_VERSION,
+            URL_SAFE_NO_PAD.encode(instance_id),
+            URL_SAFE_NO_PAD.encode(tenant_id),
+            URL_SAFE_NO_PAD.encode(signature)
+        ))
+    }
+
+    /// Check a request for `instance_id`.
+    ///
+    /// `token` is the session header, `tenant_id` the tenant the caller
+    /// states, if any. Returns the verified binding, or `None` when sessions
+    /// are disabled or an optional token is absent.
+    pub fn authorize(
+        &self,
+        token: Option<&str>,
+        instance_id: &str,
+        tenant_id: Option<&str>,
+    ) -> Result<Option<SessionBinding>, CoreError> {
+        let Some(key) = self.key.as_ref() else {
+            return Ok(None);
+        };
+        let refuse = |reason: &str| CoreError::Authorization {
+            instance_id: instance_id.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let Some(token) = token else {
+            return if self.required {
+                Err(refuse("missing session token; register the instance first"))
+            } else {
+                Ok(None)
+            };
+        };
+
+        let binding = verify(key, token).ok_or_else(|| refuse("invalid session token"))?;
+        if binding.instance_id != instance_id {
+            return Err(refuse("session is bound to a different instance"));
+        }
+        if tenant_id.is_some_and(|tenant| tenant != binding.tenant_id) {
+            return Err(refuse("session is bound to a different tenant"));
+        }
+        Ok(Some(binding))
+    }
+}
+
+fn sign(key: &[u8], instance_id: &str, tenant_id: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(TOKEN_VERSION.as_bytes());
+    mac.update(&[0]);
+    mac.update(instance_id.as_bytes());
+    mac.update(&[0]);
+    mac.update(tenant_id.as_bytes());
+    mac
+}
+
+fn verify(key: &[u8], token: &str) -> Option<SessionBinding> {
+    let mut parts = token.split('.');
+    let (version, instance, tenant, signature) =
+        (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    if version != TOKEN_VERSION || parts.next().is_some() {
+        return None;
+    }
+    let decode = |part: &str| URL_SAFE_NO_PAD.decode(part).ok();
+    let instance_id = String::from_utf8(decode(instance)?).ok()?;
+    let tenant_id = String::from_utf8(decode(tenant)?).ok()?;
+    sign(key, &instance_id, &tenant_id)
+        .verify_slice(&decode(signature)?)
+        .ok()?;
+    Some(SessionBinding {
+        instance_id,
+        tenant_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issued_token_authorizes_its_own_instance() {
+        let sessions = InstanceSessions::with_secret("s3cret");
+        let token = sessions.issue("inst-a", "tenant-1").unwrap();
+
+        let binding = sessions
+            .authorize(Some(&token), "inst-a", Some("tenant-1"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(binding.instance_id, "inst-a");
+        assert_eq!(binding.tenant_id, "tenant-1");
+    }
+
+    #[test]
+    fn token_for_another_instance_or_tenant_is_refused() {
+        let sessions = InstanceSessions::with_secret("s3cret");
+        let token = sessions.issue("inst-a", "tenant-1").unwrap();
+
+        let err = sessions
+            .authorize(Some(&token), "inst-b", None)
+            .unwrap_err();
+        assert_eq!(err.error_code(), "AUTHORIZATION");
+
+        let err = sessions
+            .authorize(Some(&token), "inst-a", Some("tenant-2"))
+            .unwrap_err();
+        assert_eq!(err.error_code(), "AUTHORIZATION");
+    }
+
+    #[test]
+    fn forged_or_foreign_tokens_are_refused() {
+        let sessions = InstanceSessions::with_secret("s3cret");
+        let token = sessions.issue("inst-a", "tenant-1").unwrap();
+
+        // Swap the instance segment but keep the signature.
+        let mut parts: Vec<&str> = token.split('.').collect();
+        let forged_instance = URL_SAFE_NO_PAD.encode("inst-b");
+        parts[1] = &forged_instance;
+        let forged = parts.join(".");
+        assert!(sessions.authorize(Some(&forged), "inst-b", None).is_err());
+
+        let other = InstanceSessions::with_secret("other")
+            .issue("inst-a", "tenant-1")
+            .unwrap();
+        assert!(sessions.authorize(Some(&other), "inst-a", None).is_err());
+        assert!(sessions.authorize(Some("garbage"), "inst-a", None).is_err());
+    }
+
+    #[test]
+    fn missing_token_is_refused_only_when_required() {
+        let optional = InstanceSessions::with_secret("s3cret");
+        assert_eq!(optional.authorize(None, "inst-a", None).unwrap(), None);
+
+        let required = InstanceSessions::with_secret("s3cret").require(true);
+        assert!(required.is_required());
+        assert!(required.authorize(None, "inst-a", None).is_err());
+    }
+
+    #[test]
+    fn disabled_sessions_issue_and_check_nothing() {
+        let sessions = InstanceSessions::disabled().require(true);
+        assert!(!sessions.is_required());
+        assert!(sessions.issue("inst-a", "tenant-1").is_none());
+        assert_eq!(
+            sessions.authorize(Some("garbage"), "inst-b", None).unwrap(),
+            None
+        );
+        assert!(!InstanceSessions::with_secret("").is_enabled());
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Change notification for instance status, backing long-poll status reads.
+//!
+//! Every write that changes an instance's `status` or `checkpoint_id` bumps
+//! its `status_version` column and calls [`notify`]. A status reader that
+//! was handed the version (or status) the client last saw can then
+//! [`wait_for_instance_change`] instead of answering immediately.
+//!
+//! Notifications are in-process only. Writes made by another process (a
+//! second environment replica sharing the database) are picked up by a
+//! short re-read interval, so a waiter never sleeps through a change for
+//! longer than [`FALLBACK_POLL_INTERVAL`].
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use tokio::sync::watch;
+
+use crate::error::CoreError;
+use crate::persistence::{InstanceRecord, Persistence};
+
+/// Upper bound on how long a single long-poll may wait.
+pub const MAX_LONG_POLL_WAIT: Duration = Duration::from_secs(30);
+
+/// How often a waiter re-reads the row to catch out-of-process writes.
+pub const FALLBACK_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+static STATUS_WATCH: OnceLock<StatusWatch> = OnceLock::new();
+
+/// Per-instance change channels.
+///
+/// Channels are created on first subscribe and dropped on the next notify
+/// after their last subscriber went away, so the map only holds instances
+/// somebody is currently watching.
+#[derive(Default)]
+pub struct StatusWatch {
+    channels: Mutex<HashMap<String, watch::Sender<u64>>>,
+}
+
+impl StatusWatch {
+    /// Process-wide instance.
+    pub fn global() -> &'static StatusWatch {
+        STATUS_WATCH.get_or_init(StatusWatch::default)
+    }
+
+    /// Receiver that observes every [`notify`](Self::notify) for
+    /// `instance_id` issued after this call.
+    pub fn subscribe(&self, instance_id: &str) -> watch::Receiver<u64> {
+        let mut channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        channels
+            .entry(instance_id.to_string())
+            .or_insert_with(|| watch::channel(0).0)
+            .subscribe()
+    }
+
+    /// Wake every subscriber of `instance_id`.
+    pub fn notify(&self, instance_id: &str) {
+        let mut channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(sender) = channels.get(instance_id) {
+            if sender.receiver_count() == 0 {
+                channels.remove(instance_id);
+            } else {
+                sender.send_modify(|generation| *generation = generation.wrapping_add(1));
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn channel_count(&self) -> usize {
+        self.channels.lock().unwrap().len()
+    }
+}
+
+/// Signal that `instance_id`'s status or checkpoint changed in this process.
+pub fn notify(instance_id: &str) {
+    StatusWatch::global().notify(instance_id);
+}
+
+/// Where a client's last status read left off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusCursor {
+    /// The `status_version` the client last saw.
+    Version(i64),
+    /// The status string the client last saw.
+    Status(String),
+}
+
+impl StatusCursor {
+    /// Parse a `wait_for_change_from` value: an integer is a version,
+    /// anything else a status string.
+    pub fn parse(value: &str) -> Self {
+        match value.trim().parse::<i64>() {
+            Ok(version) => StatusCursor::Version(version),
+            Err(_) => StatusCursor::Status(value.trim().to_string()),
+        }
+    }
+
+    /// Whether `record` has moved past this cursor. `status` is the record's
+    /// status as the caller presents it to clients.
+    pub fn is_passed_by(&self, record: &InstanceRecord, status: &str) -> bool {
+        match self {
+            StatusCursor::Version(version) => record.status_version != *version,
+            StatusCursor::Status(seen) => status != seen,
+        }
+    }
+}
+
+/// Wait until `changed` holds for `instance_id`'s row or `max_wait` (capped
+/// at [`MAX_LONG_POLL_WAIT`]) elapses, and return the latest row either way.
+///
+/// Returns immediately when the instance does not exist or already
+/// satisfies `changed`.
+pub async fn wait_for_instance_change<F>(
+    persistence: &dyn Persistence,
+    instance_id: &str,
+    max_wait: Duration,
+    changed: F,
+) -> Result<Option<InstanceRecord>, CoreError>
+where
+    F: Fn(&InstanceRecord) -> bool,
+{
+    let deadline = tokio::time::Instant::now() + max_wait.min(MAX_LONG_POLL_WAIT);
+    // Subscribe before the first read so a write landing between the read
+    // and the wait still wakes us.
+    let mut rx = StatusWatch::global().subscribe(instance_id);
+    loop {
+        let record = persistence.get_instance(instance_id).await?;
+        let Some(record) = record else {
+            return Ok(None);
+        };
+        if changed(&record) || tokio::time::Instant::now() >= deadline {
+            return Ok(Some(record));
+        }
+        let wake_at = deadline.min(tokio::time::Instant::now() + FALLBACK_POLL_INTERVAL);
+        tokio::select! {
+            _ = rx.changed() => {}
+            _ = tokio::time::sleep_until(wake_at) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_parse() {
+        assert_eq!(StatusCursor::parse("7"), StatusCursor::Version(7));
+        assert_eq!(
+            StatusCursor::parse("running"),
+            StatusCursor::Status("running".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_wakes_subscriber() {
+        let watch = StatusWatch::default();
+        let mut rx = watch.subscribe("inst-1");
+        watch.notify("inst-1");
+        tokio::time::timeout(Duration::from_secs(1), rx.changed())
+            .await
+            .expect("notified")
+            .expect("sender alive");
+    }
+
+    #[test]
+    fn test_notify_drops_unwatched_channels() {
+        let watch = StatusWatch::default();
+        drop(watch.subscribe("inst-1"));
+        assert_eq!(watch.channel_count(), 1);
+        watch.notify("inst-1");
+        assert_eq!(watch.channel_count(), 0);
+        // Instances nobody watches never get an entry.
+        watch.notify("inst-2");
+        assert_eq!(watch.channel_count(), 0);
+    }
+}
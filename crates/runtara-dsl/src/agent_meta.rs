@@ -90,6 +90,47 @@ pub struct CapabilityMeta {
     /// Semantic tags for capability classification and filtering.
     /// Well-known tags: "memory:read", "memory:write".
     pub tags: &'static [&'static str],
+    /// Maximum number of concurrent executions of this capability within one
+    /// process. `None` means unlimited. Ops can override it per capability via
+    /// [`CAPABILITY_CONCURRENCY_ENV`].
+    pub max_concurrency: Option<u32>,
+}
+
+/// Environment variable overriding capability concurrency limits without
+/// rebuilding agents: `module.capability=N[,module.capability=N...]`.
+/// `N = 0` removes the limit.
+pub const CAPABILITY_CONCURRENCY_ENV: &str = "RUNTARA_CAPABILITY_CONCURRENCY";
+
+/// Parse a [`CAPABILITY_CONCURRENCY_ENV`] value into `(module, capability)`
+/// keys. Module names are lowercased to match registry lookups; malformed
+/// entries are skipped.
+pub fn parse_capability_concurrency_overrides(
+    spec: &str,
+) -> std::collections::HashMap<(String, String), u32> {
+    spec.split(',')
+        .filter_map(|entry| {
+            let (key, limit) = entry.trim().split_once('=')?;
+            let (module, capability) = key.trim().split_once('.')?;
+            let limit = limit.trim().parse::<u32>().ok()?;
+            (!module.is_empty() && !capability.is_empty())
+                .then(|| ((module.to_lowercase(), capability.to_string()), limit))
+        })
+        .collect()
+}
+
+/// Effective concurrency limit for a capability: the override when present
+/// (with `0` meaning unlimited), otherwise the declared `max_concurrency`.
+pub fn effective_max_concurrency(
+    overrides: &std::collections::HashMap<(String, String), u32>,
+    module: &str,
+    capability_id: &str,
+    declared: Option<u32>,
+) -> Option<u32> {
+    match overrides.get(&(module.to_lowercase(), capability_id.to_string())) {
+        Some(0) => None,
+        Some(limit) => Some(*limit),
+        None => declared.filter(|limit| *limit > 0),
+    }
 }
 
 /// Well-known capability tags
@@ -347,6 +388,13 @@ pub struct CapabilityInfo {
     /// Well-known tags: "memory:read", "memory:write".
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    /// Declared per-process concurrency limit; absent means unlimited.
+    #[serde(
+        default,
+        rename = "maxConcurrency",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_concurrency: Option<u32>,
 }
 
 /// API-compatible capability field info.
@@ -1035,6 +1083,25 @@ pub struct ConnectionTypeMeta {
     pub sections: &'static [ConnectionSectionMeta],
     /// OAuth2 configuration (only for auth_type = Oauth2AuthorizationCode)
     pub oauth_config: Option<&'static OAuthConfig>,
+    /// Version of the parameter shape described by `fields`. Starts at 1 and is
+    /// bumped whenever a change would break parameters stored under the
+    /// previous shape (e.g. a new required field).
+    pub schema_version: u32,
+}
+
+/// Upgrade step for stored connection parameters of one integration.
+///
+/// Transforms parameter JSON written under `from_version` into the shape of
+/// `from_version + 1`. Steps are chained until the current
+/// [`ConnectionTypeMeta::schema_version`] is reached.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionMigration {
+    /// Integration the migration applies to (matches `ConnectionTypeMeta::integration_id`)
+    pub integration_id: &'static str,
+    /// Schema version the input parameters were stored with
+    pub from_version: u32,
+    /// Upgrade function; an `Err` aborts the chain with the given reason
+    pub migrate: fn(serde_json::Value) -> Result<serde_json::Value, String>,
 }
 
 /// Get all registered connection type metadata
@@ -1534,6 +1601,7 @@ pub fn capability_to_api_with_types(
         compensation_hint,
         known_errors,
         tags: cap.tags.iter().map(|s| s.to_string()).collect(),
+        max_concurrency: cap.max_concurrency,
     }
 }
 
@@ -2071,6 +2139,7 @@ pub fn workflow_agent_info(
                 capability_tags::WORKFLOW_AGENT.to_string(),
                 capability_tags::WORKFLOW_AGENT_CHECKPOINT_SCOPE.to_string(),
             ],
+            max_concurrency: None,
         }],
     }
 }
@@ -2517,6 +2586,7 @@ mod output_schema_tests {
             compensation_hint: None,
             known_errors: &[],
             tags: &[],
+            max_concurrency: None,
         }
     }
 
@@ -2591,6 +2661,7 @@ mod catalog_tests {
                 compensation_hint: None,
                 known_errors: vec![],
                 tags: vec![],
+                max_concurrency: None,
             }],
         }
     }
@@ -2934,6 +3005,29 @@ mod slug_tests {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_capability_concurrency_overrides() {
+        let overrides =
+            parse_capability_concurrency_overrides(" XLSX.from-xlsx=2, bad, sftp.=3,x.y=z,a.b=0");
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(
+            overrides.get(&("xlsx".to_string(), "from-xlsx".to_string())),
+            Some(&2)
+        );
+        assert_eq!(
+            effective_max_concurrency(&overrides, "a", "b", Some(5)),
+            None
+        );
+        assert_eq!(
+            effective_max_concurrency(&overrides, "xlsx", "from-xlsx", None),
+            Some(2)
+        );
+        assert_eq!(
+            effective_max_concurrency(&overrides, "c", "d", Some(0)),
+            None
+        );
+    }
+
     #[test]
     fn test_builtin_agent_modules_count() {
         // Verify we have the expected number of built-in modules
@@ -3311,6 +3405,7 @@ mod tests {
                 },
             ],
             tags: vec![],
+            max_concurrency: None,
         };
 
         let json = serde_json::to_value(&info).unwrap();
@@ -3350,6 +3445,7 @@ mod tests {
             compensation_hint: None,
             known_errors: vec![],
             tags: vec![],
+            max_concurrency: None,
         };
 
         let json = serde_json::to_value(&info).unwrap();
@@ -731,6 +731,7 @@ mod tests {
             allow_null: None,
             convert_single_value: None,
             batch_size: None,
+            adaptive_parallelism: None,
         };
 
         let json = serde_json::to_value(&config).unwrap();
@@ -740,6 +741,30 @@ mod tests {
         assert_eq!(json.get("dontStopOnFailed").unwrap(), true);
     }
 
+    #[test]
+    fn test_split_config_adaptive_parallelism_round_trip() {
+        let json = r#"{
+            "value": {"valueType": "reference", "value": "data.items"},
+            "adaptiveParallelism": {"min": 1, "max": 8}
+        }"#;
+        let parsed: SplitConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed.adaptive_parallelism,
+            Some(AdaptiveParallelism { min: 1, max: 8 })
+        );
+        assert!(parsed.parallelism.is_none());
+
+        let value = serde_json::to_value(&parsed).unwrap();
+        assert_eq!(value["adaptiveParallelism"]["min"], 1);
+        assert_eq!(value["adaptiveParallelism"]["max"], 8);
+
+        let typo = r#"{
+            "value": {"valueType": "reference", "value": "data.items"},
+            "adaptiveParallelism": {"min": 1, "maximum": 8}
+        }"#;
+        assert!(serde_json::from_str::<SplitConfig>(typo).is_err());
+    }
+
     #[test]
     fn test_split_config_with_allow_null_and_convert_single_value() {
         let config = SplitConfig {
@@ -758,6 +783,7 @@ mod tests {
             allow_null: Some(true),
             convert_single_value: Some(true),
             batch_size: None,
+            adaptive_parallelism: None,
         };
 
         let json = serde_json::to_value(&config).unwrap();
@@ -790,6 +816,7 @@ mod tests {
             allow_null: None,
             convert_single_value: None,
             batch_size: None,
+            adaptive_parallelism: None,
         };
 
         let json = serde_json::to_value(&config).unwrap();
@@ -859,6 +886,7 @@ mod tests {
                 allow_null: None,
                 convert_single_value: None,
                 batch_size: None,
+                adaptive_parallelism: None,
             }),
             input_schema: HashMap::new(),
             output_schema: HashMap::new(),
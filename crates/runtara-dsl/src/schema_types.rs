@@ -127,19 +127,17 @@ pub struct ExecutionGraph {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<Vec<Note>>,
 
-    /// UI node positions for the visual workflow editor.
-    /// This is opaque data managed by the UI - the runtime does not interpret this field.
-    /// Typically contains an array of node objects with position coordinates.
-    /// Not used in compilation or execution.
+    /// Node layout for the visual workflow editor, one entry per canvas node.
+    /// Never required and never interpreted by compilation or execution; keys
+    /// not modelled by [`LayoutNode`] are kept as written.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub nodes: Option<serde_json::Value>,
+    pub nodes: Option<Vec<LayoutNode>>,
 
-    /// UI edge positions for the visual workflow editor.
-    /// This is opaque data managed by the UI - the runtime does not interpret this field.
-    /// Typically contains an array of edge objects connecting nodes.
-    /// Not used in compilation or execution.
+    /// Edge layout for the visual workflow editor, one entry per canvas edge.
+    /// Never required and never interpreted by compilation or execution; keys
+    /// not modelled by [`LayoutEdge`] are kept as written.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub edges: Option<serde_json::Value>,
+    pub edges: Option<Vec<LayoutEdge>>,
 
     /// Maximum cumulative time (in milliseconds) that rate-limited retries may
     /// durable-sleep before giving up.  Applies to all steps in this workflow.
@@ -278,9 +276,84 @@ pub struct ExecutionPlanEdge {
     pub priority: Option<i32>,
 }
 
+/// Editor layout of a workflow: the `notes`, `nodes` and `edges` of an
+/// [`ExecutionGraph`] without its executable part.
+///
+/// Saved on its own by the layout endpoint, so moving a node never touches
+/// the definition that gets compiled. Unknown top-level keys are refused so a
+/// layout cannot carry steps past compilation.
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowLayout {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Vec<Note>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nodes: Option<Vec<LayoutNode>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edges: Option<Vec<LayoutEdge>>,
+}
+
+/// Canvas position and size of one node in the workflow editor
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LayoutNode {
+    /// Node ID; matches a step ID for step nodes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<Position>,
+
+    /// Node width in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+
+    /// Node height in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+
+    /// Whether the node is collapsed on the canvas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collapsed: Option<bool>,
+
+    /// Editor keys not modelled above (node type, parent ID, ...), kept verbatim
+    #[serde(flatten)]
+    pub extra: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
+/// Canvas routing of one edge in the workflow editor
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LayoutEdge {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// ID of the node the edge leaves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// ID of the node the edge enters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+
+    /// Bend points between source and target, in drawing order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waypoints: Option<Vec<Position>>,
+
+    /// Editor keys not modelled above (handles, styling, ...), kept verbatim
+    #[serde(flatten)]
+    pub extra: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
 /// Visual annotation for workflow editor UI
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct Note {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -296,11 +369,15 @@ pub struct Note {
     /// Not used in compilation or execution.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<NoteMetadata>,
+
+    /// Editor keys not modelled above, kept verbatim
+    #[serde(flatten)]
+    pub extra: std::collections::BTreeMap<String, serde_json::Value>,
 }
 
 /// Sizing metadata for a note, managed by the workflow editor UI
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct NoteMetadata {
     /// Note width in pixels
@@ -310,15 +387,23 @@ pub struct NoteMetadata {
     /// Note height in pixels
     #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<f64>,
+
+    /// Editor keys not modelled above, kept verbatim
+    #[serde(flatten)]
+    pub extra: std::collections::BTreeMap<String, serde_json::Value>,
 }
 
 /// Position coordinates for UI elements
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct Position {
     pub x: f64,
     pub y: f64,
+
+    /// Editor keys not modelled above, kept verbatim
+    #[serde(flatten)]
+    pub extra: std::collections::BTreeMap<String, serde_json::Value>,
 }
 
 // ============================================================================
@@ -367,6 +452,9 @@ pub enum Step {
     /// Pause workflow execution for a specified duration (durable)
     Delay(DelayStep),
 
+    /// Assign new values to declared workflow variables
+    SetVariable(SetVariableStep),
+
     /// Wait for an external signal before continuing
     WaitForSignal(WaitForSignalStep),
 
@@ -494,6 +582,11 @@ pub struct AgentStep {
     pub input_mapping: Option<InputMapping>,
 
     /// Maximum retry attempts (default: 3)
+    ///
+    /// Each retry re-runs the capability. Random-number and current-time
+    /// capabilities then return a fresh value, which a side-effecting retry
+    /// must not depend on: use `utils:random-seeded`, or capture the time once
+    /// in a `maxRetries: 0` step (validation warns with W074).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_retries: Option<u32>,
 
@@ -821,8 +914,9 @@ pub enum LogLevel {
 /// with structured metadata. This is the primary mechanism for business
 /// logic errors that should be distinguishable from technical errors.
 ///
-/// `message` is a verbatim string — no interpolation is performed. Put
-/// dynamic values in `context`, which is a regular input mapping:
+/// Dynamic values go in `context`, which is a regular input mapping.
+/// `message` may name its entries as `{{ key }}` placeholders; a message
+/// without placeholders is used verbatim.
 ///
 /// Example:
 /// ```json
@@ -831,7 +925,7 @@ pub enum LogLevel {
 ///   "id": "credit_limit_error",
 ///   "category": "permanent",
 ///   "code": "CREDIT_LIMIT_EXCEEDED",
-///   "message": "Order total exceeds credit limit",
+///   "message": "Order total {{ total }} exceeds credit limit {{ limit }}",
 ///   "context": {
 ///     "total": { "valueType": "reference", "value": "data.total" },
 ///     "limit": { "valueType": "reference", "value": "data.limit" }
@@ -862,8 +956,8 @@ pub struct ErrorStep {
     /// Machine-readable error code (e.g., "CREDIT_LIMIT_EXCEEDED", "INVALID_ACCOUNT")
     pub code: String,
 
-    /// Human-readable error message (static string).
-    /// For dynamic data, use the `context` field with mappings.
+    /// Human-readable error message. `{{ key }}` placeholders are filled
+    /// from the resolved `context` entries.
     pub message: String,
 
     /// Error severity for logging/alerting:
@@ -1098,6 +1192,51 @@ pub struct DelayStep {
     pub durable: Option<bool>,
 }
 
+/// Assign new values to declared workflow variables.
+///
+/// Each key in `values` names a variable declared in the graph's `variables`
+/// block; the value is resolved with the standard mapping system and must
+/// match the declared type. Later steps see the new value through
+/// `variables.<name>`. Assignments are checkpointed in durable workflows, so a
+/// resumed instance continues with the variables as they were at suspension.
+///
+/// Only allowed in a workflow's top-level graph: Split and While iterations
+/// get their own variable scope, so an assignment there would be lost when
+/// the iteration ends. A child workflow's variables are separate from its
+/// parent's.
+///
+/// Example:
+/// ```json
+/// {
+///   "stepType": "SetVariable",
+///   "id": "add-to-total",
+///   "values": {
+///     "total": { "valueType": "reference", "value": "steps.sum.outputs.result" }
+///   }
+/// }
+/// ```
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "json-schema", schemars(title = "SetVariableStep"))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SetVariableStep {
+    /// Unique step identifier
+    pub id: String,
+
+    /// Human-readable step name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// New variable values. Keys are declared variable names, values specify
+    /// how to obtain the data.
+    pub values: InputMapping,
+
+    /// When true, execution pauses before this step in debug mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breakpoint: Option<bool>,
+}
+
 /// Wait for an external signal before continuing execution.
 ///
 /// This step pauses workflow execution until an external system sends a signal
@@ -2207,6 +2346,31 @@ pub struct SplitConfig {
     /// subgraph receives an array value instead of an individual element.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub batch_size: Option<u32>,
+
+    /// Adaptive concurrency window, used instead of a fixed `parallelism`.
+    ///
+    /// The window starts at `min` and is re-evaluated after every chunk of
+    /// items: it grows by one while items complete without rate-limit errors
+    /// and chunk latency stays near the best observed, and halves (never below
+    /// `min`) as soon as an item reports a `RATE_LIMITED` / 429-derived
+    /// error. Only scheduling changes — results stay in input order and
+    /// per-item checkpoints are keyed exactly as in sequential execution.
+    /// Eligibility is the same as for `parallelism`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_parallelism: Option<AdaptiveParallelism>,
+}
+
+/// Bounds for a Split step's adaptive concurrency window.
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AdaptiveParallelism {
+    /// Starting (and lowest) number of concurrent iterations. Must be >= 1.
+    pub min: u32,
+
+    /// Highest number of concurrent iterations. Must be >= `min`.
+    pub max: u32,
 }
 
 #[cfg(test)]
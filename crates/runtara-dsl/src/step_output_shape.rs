@@ -222,6 +222,11 @@ pub fn step_output_shape(step_type: &str) -> Option<StepOutputShape> {
             outputs: OutputsShape::Dynamic,
             siblings: &[],
         },
+        "SetVariable" => StepOutputShape {
+            summary: "`outputs` is `{<variable>: <new value>}` for each assigned variable; later steps usually read `variables.<name>` instead.",
+            outputs: OutputsShape::Dynamic,
+            siblings: &[],
+        },
         "Error" => StepOutputShape {
             summary: "Terminates the workflow with a structured error; writes no referenceable `outputs`.",
             outputs: OutputsShape::Dynamic,
@@ -299,6 +304,7 @@ mod tests {
         "WaitForSignal",
         "AiAgent",
         "Delay",
+        "SetVariable",
     ];
 
     fn field_names(fields: &[ShapeField]) -> Vec<&'static str> {
@@ -12,7 +12,8 @@
 use crate::agent_meta::StepTypeMeta;
 use crate::{
     AgentStep, AiAgentStep, ConditionalStep, DelayStep, EmbedWorkflowStep, ErrorStep, FilterStep,
-    FinishStep, GroupByStep, LogStep, SplitStep, SwitchStep, WaitForSignalStep, WhileStep,
+    FinishStep, GroupByStep, LogStep, SetVariableStep, SplitStep, SwitchStep, WaitForSignalStep,
+    WhileStep,
 };
 
 // ========================================================================
@@ -75,6 +76,10 @@ fn schema_delay_step() -> schemars::Schema {
     schemars::schema_for!(DelayStep)
 }
 
+fn schema_set_variable_step() -> schemars::Schema {
+    schemars::schema_for!(SetVariableStep)
+}
+
 // ========================================================================
 // Step Type Metadata Registrations
 // ========================================================================
@@ -191,6 +196,14 @@ static DELAY_STEP_META: StepTypeMeta = StepTypeMeta {
     schema_fn: schema_delay_step,
 };
 
+static SET_VARIABLE_STEP_META: StepTypeMeta = StepTypeMeta {
+    id: "SetVariable",
+    display_name: "Set Variable",
+    description: "Assign new values to declared workflow variables",
+    category: "utility",
+    schema_fn: schema_set_variable_step,
+};
+
 pub(crate) static STEP_TYPES: &[&StepTypeMeta] = &[
     &FINISH_STEP_META,
     &AGENT_STEP_META,
@@ -206,4 +219,5 @@ pub(crate) static STEP_TYPES: &[&StepTypeMeta] = &[
     &WAIT_FOR_SIGNAL_STEP_META,
     &AI_AGENT_STEP_META,
     &DELAY_STEP_META,
+    &SET_VARIABLE_STEP_META,
 ];
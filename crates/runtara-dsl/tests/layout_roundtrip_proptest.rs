@@ -0,0 +1,265 @@
+//! Editor layout round-trip proptest.
+//!
+//! Generates randomized `notes` / `nodes` / `edges` payloads of the shape the
+//! visual editor writes, including keys the DSL does not model, and asserts
+//! that the backend never loses any of it:
+//!
+//! 1. **Parsing an execution graph keeps the layout.** `parse_execution_graph`
+//!    followed by serialization yields the layout exactly as written, up to
+//!    the JSON spelling of numbers (`100` may come back as `100.0`).
+//! 2. **A typed layout is a serde fixed point.** `WorkflowLayout` →
+//!    JSON → `WorkflowLayout` returns an equal value.
+
+use proptest::prelude::*;
+use runtara_dsl::{WorkflowLayout, parse_execution_graph, parse_workflow};
+use serde_json::{Map, Number, Value, json};
+
+const LAYOUT_KEYS: [&str; 3] = ["notes", "nodes", "edges"];
+
+/// Keys the typed layout structs model; generated extras avoid them so a
+/// random extra never collides with a typed field of the wrong type.
+const MODELLED_KEYS: [&str; 12] = [
+    "id",
+    "text",
+    "position",
+    "metadata",
+    "width",
+    "height",
+    "collapsed",
+    "source",
+    "target",
+    "waypoints",
+    "x",
+    "y",
+];
+
+fn arb_leaf() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<i64>().prop_map(|n| Value::Number(n.into())),
+        arb_coordinate(),
+        "[a-zA-Z0-9_ ./:-]{0,16}".prop_map(Value::String),
+    ]
+}
+
+fn arb_json() -> impl Strategy<Value = Value> {
+    arb_leaf().prop_recursive(3, 16, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+            prop::collection::btree_map("[a-zA-Z][a-zA-Z0-9_]{0,10}", inner, 0..4)
+                .prop_map(|m| Value::Object(m.into_iter().collect())),
+        ]
+    })
+}
+
+/// Unmodelled keys: the ones the editor writes today plus arbitrary ones.
+fn arb_extras() -> impl Strategy<Value = Map<String, Value>> {
+    let key = prop_oneof![
+        Just("type".to_string()),
+        Just("parentId".to_string()),
+        Just("sourceHandle".to_string()),
+        Just("targetHandle".to_string()),
+        Just("selected".to_string()),
+        Just("style".to_string()),
+        "[a-zA-Z][a-zA-Z0-9_]{0,10}"
+            .prop_filter("modelled key", |k| { !MODELLED_KEYS.contains(&k.as_str()) }),
+    ];
+    prop::collection::btree_map(key, arb_json(), 0..4).prop_map(|m| m.into_iter().collect())
+}
+
+/// Canvas coordinates: whole pixels (written as JSON integers) and fractions.
+fn arb_coordinate() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        (-5000i64..5000).prop_map(|n| json!(n)),
+        (-5000.0f64..5000.0).prop_map(|f| json!(f)),
+    ]
+}
+
+fn arb_position() -> impl Strategy<Value = Value> {
+    (arb_coordinate(), arb_coordinate(), arb_extras()).prop_map(|(x, y, mut extra)| {
+        extra.insert("x".to_string(), x);
+        extra.insert("y".to_string(), y);
+        Value::Object(extra)
+    })
+}
+
+fn arb_id() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9_-]{0,12}"
+}
+
+/// Insert `value` under `key` when present; absent fields stay absent.
+fn put(object: &mut Map<String, Value>, key: &str, value: Option<Value>) {
+    if let Some(value) = value {
+        object.insert(key.to_string(), value);
+    }
+}
+
+fn arb_node() -> impl Strategy<Value = Value> {
+    (
+        proptest::option::of(arb_id()),
+        proptest::option::of(arb_position()),
+        proptest::option::of(arb_coordinate()),
+        proptest::option::of(arb_coordinate()),
+        proptest::option::of(any::<bool>()),
+        arb_extras(),
+    )
+        .prop_map(|(id, position, width, height, collapsed, mut node)| {
+            put(&mut node, "id", id.map(Value::String));
+            put(&mut node, "position", position);
+            put(&mut node, "width", width);
+            put(&mut node, "height", height);
+            put(&mut node, "collapsed", collapsed.map(Value::Bool));
+            Value::Object(node)
+        })
+}
+
+fn arb_edge() -> impl Strategy<Value = Value> {
+    (
+        proptest::option::of(arb_id()),
+        proptest::option::of(arb_id()),
+        proptest::option::of(arb_id()),
+        proptest::option::of(prop::collection::vec(arb_position(), 0..4)),
+        arb_extras(),
+    )
+        .prop_map(|(id, source, target, waypoints, mut edge)| {
+            put(&mut edge, "id", id.map(Value::String));
+            put(&mut edge, "source", source.map(Value::String));
+            put(&mut edge, "target", target.map(Value::String));
+            put(&mut edge, "waypoints", waypoints.map(Value::Array));
+            Value::Object(edge)
+        })
+}
+
+fn arb_note() -> impl Strategy<Value = Value> {
+    let metadata = (
+        proptest::option::of(arb_coordinate()),
+        proptest::option::of(arb_coordinate()),
+        arb_extras(),
+    )
+        .prop_map(|(width, height, mut metadata)| {
+            put(&mut metadata, "width", width);
+            put(&mut metadata, "height", height);
+            Value::Object(metadata)
+        });
+    (
+        proptest::option::of(arb_id()),
+        proptest::option::of("\\PC{0,32}"),
+        proptest::option::of(arb_position()),
+        proptest::option::of(metadata),
+        arb_extras(),
+    )
+        .prop_map(|(id, text, position, metadata, mut note)| {
+            put(&mut note, "id", id.map(Value::String));
+            put(&mut note, "text", text.map(Value::String));
+            put(&mut note, "position", position);
+            put(&mut note, "metadata", metadata);
+            Value::Object(note)
+        })
+}
+
+/// A layout object; each of the three lists may be absent.
+fn arb_layout() -> impl Strategy<Value = Value> {
+    (
+        proptest::option::of(prop::collection::vec(arb_note(), 0..4)),
+        proptest::option::of(prop::collection::vec(arb_node(), 0..6)),
+        proptest::option::of(prop::collection::vec(arb_edge(), 0..6)),
+    )
+        .prop_map(|(notes, nodes, edges)| {
+            let mut layout = Map::new();
+            put(&mut layout, "notes", notes.map(Value::Array));
+            put(&mut layout, "nodes", nodes.map(Value::Array));
+            put(&mut layout, "edges", edges.map(Value::Array));
+            Value::Object(layout)
+        })
+}
+
+/// Compare numbers by value rather than by JSON spelling.
+fn normalize_numbers(value: &Value) -> Value {
+    match value {
+        Value::Number(n) => n
+            .as_f64()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or_else(|| value.clone()),
+        Value::Array(items) => Value::Array(items.iter().map(normalize_numbers).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), normalize_numbers(v)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn graph_with_layout(layout: &Value) -> Value {
+    let mut graph = json!({
+        "name": "layout",
+        "entryPoint": "finish",
+        "steps": {
+            "finish": { "stepType": "Finish", "id": "finish" }
+        }
+    });
+    for (key, value) in layout.as_object().unwrap() {
+        graph[key] = value.clone();
+    }
+    graph
+}
+
+fn layout_of(graph: &Value) -> Value {
+    Value::Object(
+        LAYOUT_KEYS
+            .iter()
+            .filter_map(|key| graph.get(*key).map(|v| (key.to_string(), v.clone())))
+            .collect(),
+    )
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 256,
+        ..ProptestConfig::default()
+    })]
+
+    /// Parsing a graph and writing it back returns the layout as written.
+    #[test]
+    fn execution_graph_keeps_layout(layout in arb_layout()) {
+        let graph = parse_execution_graph(&graph_with_layout(&layout)).unwrap();
+        let written = serde_json::to_value(&graph).unwrap();
+        prop_assert_eq!(
+            normalize_numbers(&layout_of(&written)),
+            normalize_numbers(&layout)
+        );
+    }
+
+    /// The same holds when the graph arrives wrapped as a full workflow.
+    #[test]
+    fn workflow_keeps_layout(layout in arb_layout()) {
+        let wrapped = json!({ "executionGraph": graph_with_layout(&layout) });
+        let workflow = parse_workflow(&wrapped).unwrap();
+        let written = serde_json::to_value(&workflow.execution_graph).unwrap();
+        prop_assert_eq!(
+            normalize_numbers(&layout_of(&written)),
+            normalize_numbers(&layout)
+        );
+    }
+
+    /// A typed layout survives serialization unchanged.
+    #[test]
+    fn workflow_layout_is_a_fixed_point(layout in arb_layout()) {
+        let typed: WorkflowLayout = serde_json::from_value(layout).unwrap();
+        let again: WorkflowLayout =
+            serde_json::from_value(serde_json::to_value(&typed).unwrap()).unwrap();
+        prop_assert_eq!(again, typed);
+    }
+}
+
+#[test]
+fn layout_refuses_executable_keys() {
+    let err = serde_json::from_value::<WorkflowLayout>(json!({
+        "nodes": [],
+        "steps": {}
+    }))
+    .unwrap_err();
+    assert!(err.to_string().contains("steps"), "{err}");
+}
@@ -0,0 +1,24 @@
+-- Audit trail of management operations (image registration/deletion,
+-- instance start/stop/resume, signals). Written best-effort by the HTTP
+-- handlers after the operation ran; pruned by db_cleanup_worker on its own
+-- retention setting (RUNTARA_DB_CLEANUP_AUDIT_LOG_MAX_AGE_DAYS).
+--
+-- No foreign keys: entries must outlive the images and instances they name.
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    audit_id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    actor TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    tenant_id TEXT,
+    image_id TEXT,
+    instance_id TEXT,
+    params JSONB NOT NULL DEFAULT '{}'::jsonb,
+    succeeded BOOLEAN NOT NULL,
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log (created_at);
+CREATE INDEX IF NOT EXISTS idx_audit_log_actor ON audit_log (actor, created_at);
+CREATE INDEX IF NOT EXISTS idx_audit_log_instance ON audit_log (instance_id) WHERE instance_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_audit_log_image ON audit_log (image_id) WHERE image_id IS NOT NULL;
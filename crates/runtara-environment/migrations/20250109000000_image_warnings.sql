@@ -0,0 +1,15 @@
+-- Compile warnings persisted with each image, and per-tenant settings.
+--
+-- `images.warnings` holds the structured warnings ({code, step_id, message,
+-- severity}) the compiler reported when the image was registered.
+-- `tenant_settings.strict_deployment_severity`, when set, makes StartInstance
+-- refuse images carrying a warning at or above that severity.
+
+ALTER TABLE images ADD COLUMN IF NOT EXISTS warnings JSONB NOT NULL DEFAULT '[]'::jsonb;
+
+CREATE TABLE IF NOT EXISTS tenant_settings (
+    tenant_id TEXT PRIMARY KEY,
+    strict_deployment_severity TEXT
+        CHECK (strict_deployment_severity IN ('info', 'warning', 'error')),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
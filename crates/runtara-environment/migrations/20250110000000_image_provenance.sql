@@ -0,0 +1,36 @@
+-- Build provenance persisted with each image.
+--
+-- `image_source_blobs` holds gzip-compressed scenario JSON keyed by the
+-- SHA-256 of its content, so identical sources (a child scenario shared by
+-- many parent versions) are stored once. `image_provenance` records the
+-- root scenario and the compiler fingerprint for one image, and
+-- `image_child_sources` the child scenarios compiled into it. Both cascade
+-- with the image; unreferenced blobs are pruned by the environment.
+
+CREATE TABLE IF NOT EXISTS image_source_blobs (
+    source_hash TEXT PRIMARY KEY,
+    content BYTEA NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS image_provenance (
+    image_id TEXT PRIMARY KEY REFERENCES images(image_id) ON DELETE CASCADE,
+    source_hash TEXT REFERENCES image_source_blobs(source_hash),
+    graph_hash TEXT,
+    compiler_version TEXT,
+    rustc_version TEXT,
+    agent_library_checksum TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS image_child_sources (
+    image_id TEXT NOT NULL REFERENCES images(image_id) ON DELETE CASCADE,
+    workflow_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    source_hash TEXT NOT NULL REFERENCES image_source_blobs(source_hash),
+    PRIMARY KEY (image_id, workflow_id, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_provenance_source_hash ON image_provenance(source_hash);
+CREATE INDEX IF NOT EXISTS idx_image_child_sources_source_hash ON image_child_sources(source_hash);
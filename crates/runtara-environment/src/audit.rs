@@ -0,0 +1,337 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Audit trail of management operations.
+//!
+//! Every mutating HTTP handler records one [`AuditEvent`] once the operation
+//! has run: who asked ([`actor_from_headers`]), what ([`ops`]), which
+//! tenant/image/instance it touched, the request parameters with secrets
+//! redacted ([`redact`]), and whether it succeeded. Requests rejected before
+//! the operation starts (malformed bodies, unknown ids) are not recorded.
+//!
+//! Recording is best-effort: a failed insert is logged and swallowed so the
+//! audit table can never fail the operation it describes. Entries are pruned
+//! by [`crate::db_cleanup_worker`] on their own retention setting.
+
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+use runtara_core::checkpoint_preview::{REDACTED, is_sensitive_key};
+use serde_json::Value;
+use sqlx::PgPool;
+use tracing::error;
+
+/// Header carrying the caller's identity (the API key's principal, set by
+/// the authenticating gateway in front of the environment).
+pub const ACTOR_HEADER: &str = "x-runtara-actor";
+
+/// Actor recorded when the request carries no [`ACTOR_HEADER`].
+pub const ANONYMOUS_ACTOR: &str = "anonymous";
+
+/// Operation names stored in `audit_log.operation`.
+pub mod ops {
+    /// Image registered (JSON or multipart upload).
+    pub const IMAGE_REGISTER: &str = "image.register";
+    /// Image deleted.
+    pub const IMAGE_DELETE: &str = "image.delete";
+    /// Instance started.
+    pub const INSTANCE_START: &str = "instance.start";
+    /// Instance stopped.
+    pub const INSTANCE_STOP: &str = "instance.stop";
+    /// Pending start withdrawn.
+    pub const INSTANCE_CANCEL_PENDING_START: &str = "instance.cancel_pending_start";
+    /// Suspended instance resumed.
+    pub const INSTANCE_RESUME: &str = "instance.resume";
+    /// Cancel/pause/resume signal sent.
+    pub const INSTANCE_SIGNAL: &str = "instance.signal";
+    /// Custom signal sent.
+    pub const INSTANCE_CUSTOM_SIGNAL: &str = "instance.custom_signal";
+    /// Core maintenance window opened or closed.
+    pub const MAINTENANCE_SET: &str = "maintenance.set";
+    /// Tenant settings changed.
+    pub const TENANT_SETTINGS_SET: &str = "tenant_settings.set";
+}
+
+/// The caller named by [`ACTOR_HEADER`], or [`ANONYMOUS_ACTOR`].
+pub fn actor_from_headers(headers: &HeaderMap) -> String {
+    headers
+        .get(ACTOR_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or(ANONYMOUS_ACTOR)
+        .to_string()
+}
+
+/// Replace values under credential-like keys with [`REDACTED`], at any depth.
+///
+/// Uses the same key heuristics as checkpoint previews.
+pub fn redact(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    if is_sensitive_key(&key) {
+                        (key, Value::String(REDACTED.to_string()))
+                    } else {
+                        (key, redact(value))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        other => other,
+    }
+}
+
+/// One management operation to record.
+#[derive(Debug, Clone)]
+pub struct AuditEvent {
+    /// Who performed it.
+    pub actor: String,
+    /// What was done, one of [`ops`].
+    pub operation: &'static str,
+    /// Tenant the target belongs to, when known.
+    pub tenant_id: Option<String>,
+    /// Image the operation targeted.
+    pub image_id: Option<String>,
+    /// Instance the operation targeted.
+    pub instance_id: Option<String>,
+    /// Request parameters. Passed through [`redact`] before storage.
+    pub params: Value,
+    /// Whether the operation succeeded.
+    pub succeeded: bool,
+    /// Failure message when it did not.
+    pub error: Option<String>,
+}
+
+impl AuditEvent {
+    /// A successful `operation` by `actor` with no targets or parameters.
+    pub fn new(actor: impl Into<String>, operation: &'static str) -> Self {
+        Self {
+            actor: actor.into(),
+            operation,
+            tenant_id: None,
+            image_id: None,
+            instance_id: None,
+            params: Value::Object(Default::default()),
+            succeeded: true,
+            error: None,
+        }
+    }
+
+    /// Set the tenant.
+    pub fn tenant(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Set the target image.
+    pub fn image(mut self, image_id: impl Into<String>) -> Self {
+        self.image_id = Some(image_id.into());
+        self
+    }
+
+    /// Set the target instance.
+    pub fn instance(mut self, instance_id: impl Into<String>) -> Self {
+        self.instance_id = Some(instance_id.into());
+        self
+    }
+
+    /// Set the request parameters.
+    pub fn params(mut self, params: Value) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Mark the operation failed with `error`, or leave it successful when
+    /// `error` is `None`.
+    pub fn outcome(mut self, error: Option<String>) -> Self {
+        self.succeeded = error.is_none();
+        self.error = error;
+        self
+    }
+}
+
+/// Record `event`. Never fails: insert errors are logged and dropped.
+pub async fn record(pool: &PgPool, event: AuditEvent) {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO audit_log
+            (actor, operation, tenant_id, image_id, instance_id, params, succeeded, error)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+    )
+    .bind(&event.actor)
+    .bind(event.operation)
+    .bind(event.tenant_id.as_deref())
+    .bind(event.image_id.as_deref())
+    .bind(event.instance_id.as_deref())
+    .bind(redact(event.params))
+    .bind(event.succeeded)
+    .bind(event.error.as_deref())
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        error!(
+            operation = event.operation,
+            actor = %event.actor,
+            error = %e,
+            "Failed to write audit log entry"
+        );
+    }
+}
+
+/// A stored audit log entry.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AuditLogEntry {
+    /// Sequential entry ID.
+    pub audit_id: i64,
+    /// When the operation was recorded.
+    pub created_at: DateTime<Utc>,
+    /// Who performed it.
+    pub actor: String,
+    /// What was done.
+    pub operation: String,
+    /// Tenant the target belongs to.
+    pub tenant_id: Option<String>,
+    /// Target image.
+    pub image_id: Option<String>,
+    /// Target instance.
+    pub instance_id: Option<String>,
+    /// Redacted request parameters.
+    pub params: Value,
+    /// Whether the operation succeeded.
+    pub succeeded: bool,
+    /// Failure message.
+    pub error: Option<String>,
+}
+
+/// Filters for [`list_audit_log`]. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct ListAuditLogOptions {
+    /// Filter by actor (exact match).
+    pub actor: Option<String>,
+    /// Filter by operation (exact match).
+    pub operation: Option<String>,
+    /// Filter by target: matches the image ID or the instance ID.
+    pub target_id: Option<String>,
+    /// Filter by tenant ID.
+    pub tenant_id: Option<String>,
+    /// Filter by created_at >= value.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Filter by created_at < value.
+    pub created_before: Option<DateTime<Utc>>,
+    /// Maximum results to return.
+    pub limit: i64,
+    /// Pagination offset.
+    pub offset: i64,
+}
+
+const AUDIT_LOG_FILTER: &str = r#"
+    WHERE ($1::TEXT IS NULL OR actor = $1)
+      AND ($2::TEXT IS NULL OR operation = $2)
+      AND ($3::TEXT IS NULL OR image_id = $3 OR instance_id = $3)
+      AND ($4::TEXT IS NULL OR tenant_id = $4)
+      AND ($5::TIMESTAMPTZ IS NULL OR created_at >= $5)
+      AND ($6::TIMESTAMPTZ IS NULL OR created_at < $6)
+"#;
+
+/// List audit log entries, newest first.
+pub async fn list_audit_log(
+    pool: &PgPool,
+    options: &ListAuditLogOptions,
+) -> Result<Vec<AuditLogEntry>, sqlx::Error> {
+    let query = format!(
+        "SELECT audit_id, created_at, actor, operation, tenant_id, image_id, instance_id, \
+                params, succeeded, error \
+         FROM audit_log {AUDIT_LOG_FILTER} \
+         ORDER BY created_at DESC, audit_id DESC \
+         LIMIT $7 OFFSET $8"
+    );
+    sqlx::query_as::<_, AuditLogEntry>(&query)
+        .bind(options.actor.as_deref())
+        .bind(options.operation.as_deref())
+        .bind(options.target_id.as_deref())
+        .bind(options.tenant_id.as_deref())
+        .bind(options.created_after)
+        .bind(options.created_before)
+        .bind(options.limit)
+        .bind(options.offset)
+        .fetch_all(pool)
+        .await
+}
+
+/// Count audit log entries matching the filters (for pagination total_count).
+pub async fn count_audit_log(
+    pool: &PgPool,
+    options: &ListAuditLogOptions,
+) -> Result<i64, sqlx::Error> {
+    let query = format!("SELECT COUNT(*) FROM audit_log {AUDIT_LOG_FILTER}");
+    let count: (i64,) = sqlx::query_as(&query)
+        .bind(options.actor.as_deref())
+        .bind(options.operation.as_deref())
+        .bind(options.target_id.as_deref())
+        .bind(options.tenant_id.as_deref())
+        .bind(options.created_after)
+        .bind(options.created_before)
+        .fetch_one(pool)
+        .await?;
+    Ok(count.0)
+}
+
+/// Delete entries recorded before `cutoff`. Returns the number deleted.
+pub async fn delete_audit_log_before(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM audit_log WHERE created_at < $1")
+        .bind(cutoff)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_actor_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(actor_from_headers(&headers), ANONYMOUS_ACTOR);
+
+        headers.insert(ACTOR_HEADER, " key:ops-team ".parse().unwrap());
+        assert_eq!(actor_from_headers(&headers), "key:ops-team");
+
+        headers.insert(ACTOR_HEADER, "".parse().unwrap());
+        assert_eq!(actor_from_headers(&headers), ANONYMOUS_ACTOR);
+    }
+
+    #[test]
+    fn test_redact_nested_secrets() {
+        let redacted = redact(json!({
+            "name": "orders",
+            "metadata": {"api_key": "abc", "nested": [{"password": "p"}, {"region": "eu"}]},
+            "env": {"DB_PASSWORD": "hunter2", "LOG_LEVEL": "debug"},
+        }));
+        assert_eq!(
+            redacted,
+            json!({
+                "name": "orders",
+                "metadata": {"api_key": REDACTED, "nested": [{"password": REDACTED}, {"region": "eu"}]},
+                "env": {"DB_PASSWORD": REDACTED, "LOG_LEVEL": "debug"},
+            })
+        );
+    }
+
+    #[test]
+    fn test_event_outcome() {
+        let event = AuditEvent::new("alice", ops::INSTANCE_STOP).instance("inst-1");
+        assert!(event.succeeded);
+
+        let event = event.outcome(Some("not running".to_string()));
+        assert!(!event.succeeded);
+        assert_eq!(event.error.as_deref(), Some("not running"));
+    }
+}
@@ -113,6 +113,10 @@ pub struct InstanceFull {
     pub termination_reason: Option<String>,
     /// Process exit code (if available).
     pub exit_code: Option<i32>,
+    /// Change counter for status and checkpoint (see
+    /// [`runtara_core::status_watch`]).
+    #[sqlx(default)]
+    pub status_version: i64,
 }
 
 /// Get an instance by ID.
@@ -146,7 +150,8 @@ pub async fn get_instance_full(
                i.created_at, i.started_at, i.finished_at,
                ch.last_heartbeat as heartbeat_at, i.attempt, i.max_attempts,
                i.memory_peak_bytes, i.cpu_usage_usec,
-               i.termination_reason::TEXT as termination_reason, i.exit_code
+               i.termination_reason::TEXT as termination_reason, i.exit_code,
+               i.status_version
         FROM instances i
         LEFT JOIN instance_images ii ON i.instance_id = ii.instance_id
         LEFT JOIN images img ON ii.image_id = img.image_id
@@ -879,6 +884,7 @@ mod tests {
             cpu_usage_usec: Some(1_500_000),      // 1.5 seconds
             termination_reason: None,
             exit_code: None,
+            status_version: 0,
         };
 
         let debug_str = format!("{:?}", instance);
@@ -912,6 +918,7 @@ mod tests {
             cpu_usage_usec: Some(5_000_000),        // 5 seconds
             termination_reason: None,
             exit_code: None,
+            status_version: 0,
         };
 
         let cloned = instance.clone();
@@ -946,6 +953,7 @@ mod tests {
             cpu_usage_usec: None,
             termination_reason: None,
             exit_code: None,
+            status_version: 0,
         };
 
         assert!(instance.heartbeat_at.is_none());
@@ -977,6 +985,7 @@ mod tests {
             cpu_usage_usec: Some(120_000_000),      // 2 minutes
             termination_reason: Some("completed".to_string()),
             exit_code: Some(0),
+            status_version: 0,
         };
 
         assert_eq!(instance.memory_peak_bytes, Some(2_147_483_648));
@@ -1008,6 +1017,7 @@ mod tests {
             cpu_usage_usec: None,
             termination_reason: None,
             exit_code: None,
+            status_version: 0,
         };
 
         assert!(instance.memory_peak_bytes.is_none());
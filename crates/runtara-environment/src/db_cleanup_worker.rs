@@ -16,6 +16,9 @@
 //! - `container_cancellations`
 //! - `container_heartbeats`
 //! - `instance_images`
+//!
+//! Audit log entries are pruned on their own, longer retention
+//! (`audit_log_max_age`), independent of instance age.
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -40,6 +43,8 @@ pub struct DbCleanupWorkerConfig {
     pub max_age: Duration,
     /// Maximum instances to delete per batch (prevents long transactions).
     pub batch_size: i64,
+    /// Maximum age for audit log entries before cleanup.
+    pub audit_log_max_age: Duration,
 }
 
 impl Default for DbCleanupWorkerConfig {
@@ -50,6 +55,7 @@ impl Default for DbCleanupWorkerConfig {
             poll_interval: Duration::from_secs(3600), // 1 hour
             max_age: Duration::from_secs(3 * 24 * 3600), // 3 days
             batch_size: 100,
+            audit_log_max_age: Duration::from_secs(90 * 24 * 3600), // 90 days
         }
     }
 }
@@ -65,6 +71,7 @@ impl DbCleanupWorkerConfig {
     /// - `RUNTARA_DB_CLEANUP_POLL_INTERVAL_SECS`: seconds between cleanup runs (default: 3600)
     /// - `RUNTARA_DB_CLEANUP_MAX_AGE_DAYS`: days before terminal instances are deleted (default: 3)
     /// - `RUNTARA_DB_CLEANUP_BATCH_SIZE`: max instances per batch (default: 100)
+    /// - `RUNTARA_DB_CLEANUP_AUDIT_LOG_MAX_AGE_DAYS`: days before audit log entries are deleted (default: 90)
     pub fn from_env() -> Self {
         let enabled = parse_enabled_env("RUNTARA_DB_CLEANUP_ENABLED");
 
@@ -83,11 +90,17 @@ impl DbCleanupWorkerConfig {
             .and_then(|v| v.parse().ok())
             .unwrap_or(100);
 
+        let audit_log_max_age_days = std::env::var("RUNTARA_DB_CLEANUP_AUDIT_LOG_MAX_AGE_DAYS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(90);
+
         Self {
             enabled,
             poll_interval: Duration::from_secs(poll_interval_secs),
             max_age: Duration::from_secs(max_age_days * 24 * 3600),
             batch_size,
+            audit_log_max_age: Duration::from_secs(audit_log_max_age_days * 24 * 3600),
         }
     }
 }
@@ -134,6 +147,7 @@ impl DbCleanupWorker {
             poll_interval_secs = self.config.poll_interval.as_secs(),
             max_age_days = self.config.max_age.as_secs() / 86400,
             batch_size = self.config.batch_size,
+            audit_log_max_age_days = self.config.audit_log_max_age.as_secs() / 86400,
             "Database cleanup worker started"
         );
 
@@ -149,11 +163,7 @@ impl DbCleanupWorker {
                 return;
             }
 
-            res = self.cleanup_old_instances() => {
-                if let Err(e) = res {
-                    error!(error = %e, "Failed to cleanup old instances");
-                }
-            }
+            _ = self.run_cycle() => {}
         }
 
         loop {
@@ -166,9 +176,7 @@ impl DbCleanupWorker {
                 }
 
                 _ = tokio::time::sleep(self.config.poll_interval) => {
-                    if let Err(e) = self.cleanup_old_instances().await {
-                        error!(error = %e, "Failed to cleanup old instances");
-                    }
+                    self.run_cycle().await;
                 }
             }
         }
@@ -176,6 +184,29 @@ impl DbCleanupWorker {
         info!("Database cleanup worker stopped");
     }
 
+    /// One cleanup pass over instances and the audit log. Errors are logged.
+    async fn run_cycle(&self) {
+        if let Err(e) = self.cleanup_old_instances().await {
+            error!(error = %e, "Failed to cleanup old instances");
+        }
+        if let Err(e) = self.cleanup_old_audit_log().await {
+            error!(error = %e, "Failed to cleanup old audit log entries");
+        }
+    }
+
+    /// Delete audit log entries older than `audit_log_max_age`.
+    async fn cleanup_old_audit_log(&self) -> Result<()> {
+        let cutoff = Utc::now()
+            - chrono::Duration::from_std(self.config.audit_log_max_age)
+                .map_err(|e| crate::error::Error::Other(format!("Invalid duration: {}", e)))?;
+
+        let deleted = crate::audit::delete_audit_log_before(&self.pool, cutoff).await?;
+        if deleted > 0 {
+            info!(deleted = deleted, cutoff = %cutoff, "Audit log cleanup completed");
+        }
+        Ok(())
+    }
+
     /// Cleanup old terminal instances.
     async fn cleanup_old_instances(&self) -> Result<()> {
         let cutoff = Utc::now()
@@ -302,6 +333,10 @@ mod tests {
         assert_eq!(config.poll_interval, Duration::from_secs(3600));
         assert_eq!(config.max_age, Duration::from_secs(3 * 24 * 3600));
         assert_eq!(config.batch_size, 100);
+        assert_eq!(
+            config.audit_log_max_age,
+            Duration::from_secs(90 * 24 * 3600)
+        );
     }
 
     #[test]
@@ -2,8 +2,11 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 //! Error types for runtara-environment.
 
+use serde::Serialize;
 use thiserror::Error;
 
+use crate::runner::StartFailureReason;
+
 /// Environment errors.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -55,3 +58,170 @@ pub enum Error {
 
 /// Result type using Environment Error.
 pub type Result<T> = std::result::Result<T, Error>;
+
+/// Symbolic error code carried on every failed environment response.
+///
+/// Per-operation `code` strings (`START_INSTANCE_ERROR`, ...) stay for
+/// logging; `error_code` is the stable class clients branch on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// The addressed resource does not exist (or is not visible to the tenant).
+    NotFound,
+    /// The request conflicts with the resource's current state.
+    Conflict,
+    /// A tenant or server limit was hit; retry later.
+    QuotaExceeded,
+    /// The caller is not allowed to perform the operation.
+    Unauthorized,
+    /// The request is malformed or missing required fields.
+    Validation,
+    /// A dependency (database, core) is temporarily unavailable.
+    Unavailable,
+    /// Anything else.
+    Internal,
+}
+
+impl ErrorCode {
+    /// Wire representation (`NOT_FOUND`, `CONFLICT`, ...).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotFound => "NOT_FOUND",
+            Self::Conflict => "CONFLICT",
+            Self::QuotaExceeded => "QUOTA_EXCEEDED",
+            Self::Unauthorized => "UNAUTHORIZED",
+            Self::Validation => "VALIDATION",
+            Self::Unavailable => "UNAVAILABLE",
+            Self::Internal => "INTERNAL",
+        }
+    }
+}
+
+/// One invalid request field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    /// Field name as it appears in the request.
+    pub field: String,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+/// Typed failure attached to an unsuccessful response, serialized alongside
+/// the legacy `error` message as `error_code` plus optional subject fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Failure {
+    /// Failure class.
+    #[serde(rename = "error_code")]
+    pub code: ErrorCode,
+    /// Resource kind for [`ErrorCode::NotFound`] (`instance`, `image`, ...).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<&'static str>,
+    /// Resource identifier for [`ErrorCode::NotFound`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+    /// Offending fields for [`ErrorCode::Validation`].
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub field_errors: Vec<FieldError>,
+    /// Why the runner could not start the instance, on `StartInstance`
+    /// launch failures.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_failure: Option<StartFailureReason>,
+}
+
+impl Failure {
+    /// Failure with only a code.
+    pub fn new(code: ErrorCode) -> Self {
+        Self {
+            code,
+            resource: None,
+            resource_id: None,
+            field_errors: Vec::new(),
+            start_failure: None,
+        }
+    }
+
+    /// `resource` with `id` does not exist.
+    pub fn not_found(resource: &'static str, id: impl Into<String>) -> Self {
+        Self {
+            resource: Some(resource),
+            resource_id: Some(id.into()),
+            ..Self::new(ErrorCode::NotFound)
+        }
+    }
+
+    /// `field` is invalid.
+    pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field_errors: vec![FieldError {
+                field: field.into(),
+                message: message.into(),
+            }],
+            ..Self::new(ErrorCode::Validation)
+        }
+    }
+
+    /// Request conflicts with current state.
+    pub fn conflict() -> Self {
+        Self::new(ErrorCode::Conflict)
+    }
+
+    /// Unclassified server-side failure.
+    pub fn internal() -> Self {
+        Self::new(ErrorCode::Internal)
+    }
+
+    /// The runner could not start an instance of `image_id`.
+    ///
+    /// The code follows the reason: a missing binary is the image's
+    /// `NOT_FOUND`, node problems are `UNAVAILABLE` or `QUOTA_EXCEEDED` so
+    /// clients that only know the code still retry them, and the rest is
+    /// `INTERNAL`.
+    pub fn start_failed(reason: StartFailureReason, image_id: impl Into<String>) -> Self {
+        let failure = match reason {
+            StartFailureReason::ImageBinaryMissing => Self::not_found("image", image_id),
+            StartFailureReason::BundleCreationFailed | StartFailureReason::RuntimeUnavailable => {
+                Self::new(ErrorCode::Unavailable)
+            }
+            StartFailureReason::ResourceLimit => Self::new(ErrorCode::QuotaExceeded),
+            StartFailureReason::Unknown => Self::internal(),
+        };
+        Self {
+            start_failure: Some(reason),
+            ..failure
+        }
+    }
+}
+
+impl Error {
+    /// Typed failure for this error.
+    pub fn failure(&self) -> Failure {
+        use runtara_core::error::CoreError;
+        match self {
+            Error::ImageNotFound(id) => Failure::not_found("image", id.clone()),
+            Error::InstanceNotFound(id) => Failure::not_found("instance", id.clone()),
+            Error::InvalidRequest(_) => Failure::new(ErrorCode::Validation),
+            Error::CoreProxy(_) => Failure::new(ErrorCode::Unavailable),
+            Error::Database(
+                sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_),
+            ) => Failure::new(ErrorCode::Unavailable),
+            Error::Core(core) => match core {
+                CoreError::InstanceNotFound { instance_id } => {
+                    Failure::not_found("instance", instance_id.clone())
+                }
+                CoreError::CheckpointNotFound { checkpoint_id, .. } => {
+                    Failure::not_found("checkpoint", checkpoint_id.clone().unwrap_or_default())
+                }
+                CoreError::InstanceAlreadyExists { .. }
+                | CoreError::InvalidInstanceState { .. } => Failure::conflict(),
+                CoreError::ValidationError { field, message } => {
+                    Failure::validation(field.clone(), message.clone())
+                }
+                CoreError::DatabaseError { .. }
+                | CoreError::CheckpointSaveFailed { .. }
+                | CoreError::Maintenance { .. } => Failure::new(ErrorCode::Unavailable),
+                _ => Failure::internal(),
+            },
+            _ => Failure::internal(),
+        }
+    }
+}
@@ -11,13 +11,19 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use tracing::{debug, error, info, instrument, warn};
 
-use runtara_core::persistence::{CompleteInstanceParams, Persistence};
+use runtara_core::maintenance::{MaintenanceMode, MaintenanceStatus};
+use runtara_core::persistence::{CompleteInstanceParams, EventRecord, Persistence};
 
 use crate::container_registry::{ContainerInfo, ContainerRegistry};
 use crate::db;
-use crate::error::Result;
-use crate::image_registry::{ImageBuilder, ImageRegistry, RunnerType};
+use crate::error::{Failure, Result};
+use crate::image_provenance::{self, ImageProvenance};
+use crate::image_registry::{
+    ImageBuilder, ImageRegistry, ImageWarning, RunnerType, WarningSeverity,
+};
 use crate::runner::{LaunchOptions, Runner, RunnerHandle};
+use crate::simulation::{self, SimulationLimits, SimulationResult};
+use crate::tenant_settings::{self, TenantSettings};
 
 /// Shared drain state for the environment runtime.
 ///
@@ -107,6 +113,8 @@ pub struct EnvironmentHandlerState {
     pub request_timeout: Duration,
     /// Drain signal observed by container monitors and workers.
     pub drain: DrainController,
+    /// Core maintenance switch toggled through the management API.
+    pub maintenance: MaintenanceMode,
 }
 
 /// Default request timeout for database operations (30 seconds).
@@ -159,6 +167,7 @@ impl EnvironmentHandlerState {
             data_dir: ensure_absolute_path(data_dir),
             request_timeout: DEFAULT_REQUEST_TIMEOUT,
             drain: DrainController::new(),
+            maintenance: MaintenanceMode::global(),
         }
     }
 
@@ -174,6 +183,12 @@ impl EnvironmentHandlerState {
         self
     }
 
+    /// Toggle a specific maintenance switch instead of the process-wide one.
+    pub fn with_maintenance(mut self, maintenance: MaintenanceMode) -> Self {
+        self.maintenance = maintenance;
+        self
+    }
+
     /// Get the server uptime in milliseconds.
     pub fn uptime_ms(&self) -> i64 {
         self.start_time.elapsed().as_millis() as i64
@@ -192,6 +207,7 @@ pub async fn handle_health_check(state: &EnvironmentHandlerState) -> Result<Heal
         healthy: db_healthy,
         version: state.version.clone(),
         uptime_ms: state.uptime_ms(),
+        maintenance: state.maintenance.status(),
     })
 }
 
@@ -204,6 +220,95 @@ pub struct HealthCheckResponse {
     pub version: String,
     /// Server uptime in milliseconds.
     pub uptime_ms: i64,
+    /// Active core maintenance window, if any.
+    pub maintenance: Option<MaintenanceStatus>,
+}
+
+// ============================================================================
+// Maintenance Mode
+// ============================================================================
+
+/// Request to open or close a core maintenance window.
+pub struct SetMaintenanceRequest {
+    /// Whether maintenance should be on.
+    pub enabled: bool,
+    /// Expected window length in milliseconds (default 60s, capped at 1h).
+    pub expected_duration_ms: Option<u64>,
+    /// Operator-supplied reason, echoed to refused instances.
+    pub message: String,
+}
+
+/// Handle set maintenance request.
+///
+/// While a window is open, core refuses instance checkpoint and event writes
+/// with a retry-after hint and the wake scheduler leaves sleepers asleep.
+/// Returns the window now in effect.
+#[instrument(skip(state, request), fields(enabled = request.enabled))]
+pub async fn handle_set_maintenance(
+    state: &EnvironmentHandlerState,
+    request: SetMaintenanceRequest,
+) -> Result<Option<MaintenanceStatus>> {
+    let status = state.maintenance.set(
+        request.enabled,
+        request.expected_duration_ms.map(Duration::from_millis),
+        request.message,
+    );
+    match &status {
+        Some(window) => info!(
+            ends_at = %window.ends_at,
+            message = %window.message,
+            "Core maintenance window opened"
+        ),
+        None => info!("Core maintenance window closed"),
+    }
+    Ok(status)
+}
+
+/// Handle get maintenance request: the active window, if any.
+pub async fn handle_get_maintenance(
+    state: &EnvironmentHandlerState,
+) -> Result<Option<MaintenanceStatus>> {
+    Ok(state.maintenance.status())
+}
+
+// ============================================================================
+// Tenant Settings
+// ============================================================================
+
+/// Request to change a tenant's settings.
+pub struct SetTenantSettingsRequest {
+    /// Tenant whose settings change.
+    pub tenant_id: String,
+    /// Refuse to start images with a warning at or above this severity;
+    /// `None` turns strict deployments off.
+    pub strict_deployment_severity: Option<WarningSeverity>,
+}
+
+/// Handle get tenant settings request (defaults when never stored).
+pub async fn handle_get_tenant_settings(
+    state: &EnvironmentHandlerState,
+    tenant_id: &str,
+) -> Result<TenantSettings> {
+    tenant_settings::get(&state.pool, tenant_id).await
+}
+
+/// Handle set tenant settings request. Returns the settings now in effect.
+#[instrument(skip(state, request), fields(tenant_id = %request.tenant_id))]
+pub async fn handle_set_tenant_settings(
+    state: &EnvironmentHandlerState,
+    request: SetTenantSettingsRequest,
+) -> Result<TenantSettings> {
+    let settings = tenant_settings::set_strict_deployment_severity(
+        &state.pool,
+        &request.tenant_id,
+        request.strict_deployment_severity,
+    )
+    .await?;
+    info!(
+        strict_deployment_severity = ?settings.strict_deployment_severity,
+        "Tenant settings updated"
+    );
+    Ok(settings)
 }
 
 // ============================================================================
@@ -224,6 +329,11 @@ pub struct RegisterImageRequest {
     pub runner_type: RunnerType,
     /// Optional metadata.
     pub metadata: Option<serde_json::Value>,
+    /// Compile warnings to persist with the image.
+    pub warnings: Vec<ImageWarning>,
+    /// Build provenance to store with the image. Re-registering without it
+    /// clears the provenance of the image being replaced.
+    pub provenance: Option<ImageProvenance>,
 }
 
 /// Response from image registration.
@@ -234,6 +344,8 @@ pub struct RegisterImageResponse {
     pub image_id: String,
     /// Error message if failed.
     pub error: Option<String>,
+    /// Typed failure (code and subject) when `success` is false.
+    pub failure: Option<Failure>,
 }
 
 /// Handle image registration request.
@@ -258,6 +370,7 @@ pub async fn handle_register_image(
             success: false,
             image_id: String::new(),
             error: Some("tenant_id is required".to_string()),
+            failure: Some(Failure::validation("tenant_id", "tenant_id is required")),
         });
     }
 
@@ -266,6 +379,7 @@ pub async fn handle_register_image(
             success: false,
             image_id: String::new(),
             error: Some("name is required".to_string()),
+            failure: Some(Failure::validation("name", "name is required")),
         });
     }
 
@@ -274,6 +388,7 @@ pub async fn handle_register_image(
             success: false,
             image_id: String::new(),
             error: Some("binary is required".to_string()),
+            failure: Some(Failure::validation("binary", "binary is required")),
         });
     }
 
@@ -289,6 +404,7 @@ pub async fn handle_register_image(
                 success: false,
                 image_id: String::new(),
                 error: Some(format!("Failed to look up existing image: {}", e)),
+                failure: Some(Failure::internal()),
             });
         }
     };
@@ -308,6 +424,7 @@ pub async fn handle_register_image(
             success: false,
             image_id: String::new(),
             error: Some(format!("Failed to create image directory: {}", e)),
+            failure: Some(Failure::internal()),
         });
     }
 
@@ -318,6 +435,7 @@ pub async fn handle_register_image(
             success: false,
             image_id: String::new(),
             error: Some(format!("Failed to write binary: {}", e)),
+            failure: Some(Failure::internal()),
         });
     }
 
@@ -358,7 +476,7 @@ pub async fn handle_register_image(
         builder = builder.metadata(meta);
     }
 
-    let mut image = builder.build();
+    let mut image = builder.warnings(request.warnings).build();
     image.image_id = image_id.clone();
 
     // Register in database
@@ -371,6 +489,19 @@ pub async fn handle_register_image(
             success: false,
             image_id: String::new(),
             error: Some(format!("Failed to register image: {}", e)),
+            failure: Some(Failure::internal()),
+        });
+    }
+
+    if let Err(e) =
+        image_provenance::replace(&state.pool, &image_id, request.provenance.as_ref()).await
+    {
+        error!(error = %e, "Failed to store image provenance");
+        return Ok(RegisterImageResponse {
+            success: false,
+            image_id: String::new(),
+            error: Some(format!("Failed to store image provenance: {}", e)),
+            failure: Some(Failure::internal()),
         });
     }
 
@@ -380,6 +511,7 @@ pub async fn handle_register_image(
         success: true,
         image_id,
         error: None,
+        failure: None,
     })
 }
 
@@ -414,6 +546,8 @@ pub struct StartInstanceResponse {
     pub deduplicated: bool,
     /// Error message if failed.
     pub error: Option<String>,
+    /// Typed failure (code and subject) when `success` is false.
+    pub failure: Option<Failure>,
 }
 
 async fn existing_start_response(
@@ -438,6 +572,7 @@ async fn existing_start_response(
             instance_id: String::new(),
             deduplicated: false,
             error: Some(format!("Instance '{}' already exists", instance_id)),
+            failure: Some(Failure::conflict()),
         }));
     }
 
@@ -455,6 +590,7 @@ async fn existing_start_response(
                 instance_id: String::new(),
                 deduplicated: false,
                 error: Some(format!("Instance '{}' already exists", instance_id)),
+                failure: Some(Failure::conflict()),
             }));
         }
         None => {
@@ -471,6 +607,7 @@ async fn existing_start_response(
                 instance_id: String::new(),
                 deduplicated: false,
                 error: Some(format!("Instance '{}' already exists", instance_id)),
+                failure: Some(Failure::conflict()),
             }));
         }
     }
@@ -487,6 +624,7 @@ async fn existing_start_response(
         instance_id: instance_id.to_string(),
         deduplicated: true,
         error: None,
+        failure: None,
     }))
 }
 
@@ -550,6 +688,7 @@ pub async fn handle_start_instance(
             instance_id: String::new(),
             deduplicated: false,
             error: Some("image_id is required".to_string()),
+            failure: Some(Failure::validation("image_id", "image_id is required")),
         });
     }
 
@@ -563,6 +702,7 @@ pub async fn handle_start_instance(
                 instance_id: String::new(),
                 deduplicated: false,
                 error: Some(format!("Image '{}' not found", request.image_id)),
+                failure: Some(Failure::not_found("image", request.image_id.clone())),
             });
         }
         Err(e) => {
@@ -572,6 +712,7 @@ pub async fn handle_start_instance(
                 instance_id: String::new(),
                 deduplicated: false,
                 error: Some(format!("Database error: {}", e)),
+                failure: Some(Failure::internal()),
             });
         }
     };
@@ -589,6 +730,7 @@ pub async fn handle_start_instance(
             instance_id: String::new(),
             deduplicated: false,
             error: Some(format!("Image '{}' not found", request.image_id)),
+            failure: Some(Failure::not_found("image", request.image_id.clone())),
         });
     }
 
@@ -611,6 +753,48 @@ pub async fn handle_start_instance(
         return Ok(response);
     }
 
+    // Strict deployments: the tenant may refuse images whose compile
+    // warnings reach a configured severity. Clean images skip the lookup.
+    if !image.warnings.is_empty() {
+        let settings = match tenant_settings::get(&state.pool, &request.tenant_id).await {
+            Ok(settings) => settings,
+            Err(e) => {
+                error!(error = %e, "Failed to load tenant settings");
+                return Ok(StartInstanceResponse {
+                    success: false,
+                    instance_id: String::new(),
+                    deduplicated: false,
+                    error: Some(format!("Database error: {}", e)),
+                    failure: Some(Failure::internal()),
+                });
+            }
+        };
+        if let Some(threshold) = settings.strict_deployment_severity {
+            let blocking: Vec<&str> = image
+                .warnings_at_or_above(threshold)
+                .map(|w| w.code.as_str())
+                .collect();
+            if !blocking.is_empty() {
+                let message = format!(
+                    "Image '{}' has {} compile warning(s) at or above '{}' ({}); \
+                     strict deployments are enabled for this tenant",
+                    request.image_id,
+                    blocking.len(),
+                    threshold,
+                    blocking.join(", ")
+                );
+                warn!(image_id = %request.image_id, %threshold, "{}", message);
+                return Ok(StartInstanceResponse {
+                    success: false,
+                    instance_id: String::new(),
+                    deduplicated: false,
+                    error: Some(message.clone()),
+                    failure: Some(Failure::validation("image_id", message)),
+                });
+            }
+        }
+    }
+
     // Validate the filesystem half of the image registration before writing
     // any instance state. Otherwise a stale image row creates a failed instance
     // and the trigger retry collides with that row after recompilation.
@@ -625,6 +809,7 @@ pub async fn handle_start_instance(
             instance_id: String::new(),
             deduplicated: false,
             error: Some(format!("Image '{}' artifact not found", request.image_id)),
+            failure: Some(Failure::not_found("image", request.image_id.clone())),
         });
     }
 
@@ -663,6 +848,7 @@ pub async fn handle_start_instance(
             instance_id: String::new(),
             deduplicated: false,
             error: Some(format!("Failed to create instance: {}", e)),
+            failure: Some(Failure::internal()),
         });
     }
 
@@ -702,9 +888,45 @@ pub async fn handle_start_instance(
             instance_id: String::new(),
             deduplicated: false,
             error: Some(format!("Failed to create instance: {}", e)),
+            failure: Some(Failure::internal()),
         });
     }
 
+    // Claim the reserved instance for launch. This is the same conditional
+    // transition `handle_cancel_pending_start` races against: whichever side
+    // moves the row out of `pending` first wins, so a start that was cancelled
+    // in the meantime never launches.
+    match state.persistence.claim_pending_instance(&instance_id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            info!(instance_id = %instance_id, "Instance cancelled before start; not launching");
+            return Ok(StartInstanceResponse {
+                success: false,
+                instance_id,
+                deduplicated: false,
+                error: Some("Instance was cancelled before start".to_string()),
+                failure: Some(Failure::conflict()),
+            });
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to claim instance for launch");
+            let claim_error = format!("Failed to claim instance for launch: {}", e);
+            let _ = state
+                .persistence
+                .complete_instance(
+                    CompleteInstanceParams::new(&instance_id, "failed").with_error(&claim_error),
+                )
+                .await;
+            return Ok(StartInstanceResponse {
+                success: false,
+                instance_id,
+                deduplicated: false,
+                error: Some(claim_error),
+                failure: Some(Failure::internal()),
+            });
+        }
+    }
+
     // Build launch options (using the shared image bundle)
     let options = LaunchOptions {
         instance_id: instance_id.clone(),
@@ -757,19 +979,6 @@ pub async fn handle_start_instance(
                 warn!(error = %e, "Failed to register container (instance still running)");
             }
 
-            // Update instance status to running via Persistence trait
-            if let Err(e) = state
-                .persistence
-                .update_instance_status(&instance_id, "running", Some(chrono::Utc::now()))
-                .await
-            {
-                error!(
-                    error = %e,
-                    instance_id = %instance_id,
-                    "Failed to update instance status to running (instance launched but status may be incorrect)"
-                );
-            }
-
             // Spawn background task to monitor container and process output when done
             spawn_container_monitor(
                 state.pool.clone(),
@@ -788,11 +997,15 @@ pub async fn handle_start_instance(
                 instance_id,
                 deduplicated: false,
                 error: None,
+                failure: None,
             })
         }
         Err(e) => {
-            error!(error = %e, "Failed to launch instance");
-            let launch_error = format!("Launch failed: {}", e);
+            let reason = e.start_failure_reason();
+            error!(error = %e, %reason, "Failed to launch instance");
+            // The reason code leads the stored error so the instance row says
+            // why it never ran, not just that it failed.
+            let launch_error = format!("Launch failed [{}]: {}", reason, e);
             let _ = state
                 .persistence
                 .complete_instance(
@@ -805,6 +1018,7 @@ pub async fn handle_start_instance(
                 instance_id,
                 deduplicated: false,
                 error: Some(launch_error),
+                failure: Some(Failure::start_failed(reason, request.image_id)),
             })
         }
     }
@@ -830,6 +1044,8 @@ pub struct StopInstanceResponse {
     pub success: bool,
     /// Error message if failed.
     pub error: Option<String>,
+    /// Typed failure (code and subject) when `success` is false.
+    pub failure: Option<Failure>,
 }
 
 /// Handle stop instance request.
@@ -858,6 +1074,7 @@ pub async fn handle_stop_instance(
                     "Instance '{}' not found in container registry",
                     request.instance_id
                 )),
+                failure: Some(Failure::not_found("instance", request.instance_id.clone())),
             });
         }
         Err(e) => {
@@ -865,6 +1082,7 @@ pub async fn handle_stop_instance(
             return Ok(StopInstanceResponse {
                 success: false,
                 error: Some(format!("Database error: {}", e)),
+                failure: Some(Failure::internal()),
             });
         }
     };
@@ -910,6 +1128,120 @@ pub async fn handle_stop_instance(
     Ok(StopInstanceResponse {
         success: true,
         error: None,
+        failure: None,
+    })
+}
+
+// ============================================================================
+// Cancel Pending Start
+// ============================================================================
+
+/// Request to withdraw a start that has not launched yet.
+pub struct CancelPendingStartRequest {
+    /// Instance ID whose start should be withdrawn.
+    pub instance_id: String,
+    /// Optional human-readable reason, recorded on the instance and event.
+    pub reason: Option<String>,
+}
+
+/// Outcome of a cancel-pending-start request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelPendingStartOutcome {
+    /// The instance was still `pending`; it is now `cancelled` and will
+    /// never launch.
+    CancelledBeforeStart,
+    /// The launcher already claimed the instance. Use the normal cancel
+    /// signal (or stop) path instead.
+    AlreadyStarted,
+    /// No instance with this ID exists.
+    NotFound,
+}
+
+impl CancelPendingStartOutcome {
+    /// Wire representation used by the HTTP API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CancelledBeforeStart => "cancelled_before_start",
+            Self::AlreadyStarted => "already_started",
+            Self::NotFound => "not_found",
+        }
+    }
+}
+
+/// Response from cancelling a pending start.
+pub struct CancelPendingStartResponse {
+    /// What happened to the instance.
+    pub outcome: CancelPendingStartOutcome,
+    /// Current status when the outcome is `AlreadyStarted`.
+    pub status: Option<String>,
+}
+
+/// Event subtype recorded when a start is withdrawn before launch.
+pub const CANCELLED_BEFORE_START_EVENT: &str = "cancelled_before_start";
+
+/// Handle cancel-pending-start request.
+///
+/// An instance is `pending` from the moment its ID is reserved until the
+/// launcher claims it via [`Persistence::claim_pending_instance`]. The cancel
+/// is a `complete_instance` guarded on `pending`, so the two conditional
+/// updates race on the same row and exactly one of them wins.
+#[instrument(skip(state, request), fields(instance_id = %request.instance_id))]
+pub async fn handle_cancel_pending_start(
+    state: &EnvironmentHandlerState,
+    request: CancelPendingStartRequest,
+) -> Result<CancelPendingStartResponse> {
+    info!(
+        instance_id = %request.instance_id,
+        "Cancel pending start request received"
+    );
+
+    let error = match request.reason.as_deref() {
+        Some(reason) if !reason.is_empty() => format!("Cancelled before start: {}", reason),
+        _ => "Cancelled before start".to_string(),
+    };
+    let cancelled = state
+        .persistence
+        .complete_instance(
+            CompleteInstanceParams::new(&request.instance_id, "cancelled")
+                .if_pending()
+                .with_error(&error)
+                .with_termination("cancelled", None),
+        )
+        .await?;
+
+    if !cancelled {
+        return Ok(
+            match state.persistence.get_instance(&request.instance_id).await? {
+                Some(instance) => CancelPendingStartResponse {
+                    outcome: CancelPendingStartOutcome::AlreadyStarted,
+                    status: Some(instance.status),
+                },
+                None => CancelPendingStartResponse {
+                    outcome: CancelPendingStartOutcome::NotFound,
+                    status: None,
+                },
+            },
+        );
+    }
+
+    let event = EventRecord {
+        id: None,
+        instance_id: request.instance_id.clone(),
+        event_type: "custom".to_string(),
+        checkpoint_id: None,
+        payload: serde_json::to_vec(&serde_json::json!({ "reason": request.reason })).ok(),
+        created_at: chrono::Utc::now(),
+        subtype: Some(CANCELLED_BEFORE_START_EVENT.to_string()),
+    };
+    if let Err(e) = state.persistence.insert_event(&event).await {
+        warn!(error = %e, "Failed to record cancelled-before-start event");
+    }
+
+    info!("Instance cancelled before start");
+
+    Ok(CancelPendingStartResponse {
+        outcome: CancelPendingStartOutcome::CancelledBeforeStart,
+        status: Some("cancelled".to_string()),
     })
 }
 
@@ -929,6 +1261,8 @@ pub struct ResumeInstanceResponse {
     pub success: bool,
     /// Error message if failed.
     pub error: Option<String>,
+    /// Typed failure (code and subject) when `success` is false.
+    pub failure: Option<Failure>,
 }
 
 /// Handle resume instance request.
@@ -946,6 +1280,7 @@ pub async fn handle_resume_instance(
             return Ok(ResumeInstanceResponse {
                 success: false,
                 error: Some(format!("Instance '{}' not found", request.instance_id)),
+                failure: Some(Failure::not_found("instance", request.instance_id.clone())),
             });
         }
     };
@@ -961,6 +1296,7 @@ pub async fn handle_resume_instance(
                 "Cannot resume instance in '{}' state (must be suspended, failed, or cancelled)",
                 instance.status
             )),
+            failure: Some(Failure::conflict()),
         });
     }
 
@@ -1008,6 +1344,7 @@ pub async fn handle_resume_instance(
                 return Ok(ResumeInstanceResponse {
                     success: false,
                     error: Some("Instance has no associated image".to_string()),
+                    failure: Some(Failure::internal()),
                 });
             }
         };
@@ -1019,6 +1356,7 @@ pub async fn handle_resume_instance(
             return Ok(ResumeInstanceResponse {
                 success: false,
                 error: Some(format!("Image '{}' not found", image_id)),
+                failure: Some(Failure::not_found("image", image_id.clone())),
             });
         }
     };
@@ -1034,6 +1372,7 @@ pub async fn handle_resume_instance(
         return Ok(ResumeInstanceResponse {
             success: false,
             error: Some(format!("Image '{}' not found", image_id)),
+            failure: Some(Failure::not_found("image", image_id.clone())),
         });
     }
 
@@ -1062,6 +1401,41 @@ pub async fn handle_resume_instance(
         env: stored_env, // Restore env from initial launch
     };
 
+    // Claim the instance for this launch: atomically flip it to "running"
+    // (the Core checkpoint handler rejects requests from non-running
+    // instances) and clear any pending wake. A concurrent wake or resume that
+    // got there first leaves the row running, so this path must not launch a
+    // second container for it.
+    match state
+        .persistence
+        .claim_resumable_instance(&request.instance_id)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            warn!(
+                instance_id = %request.instance_id,
+                "Instance was claimed by a concurrent launch; aborting resume"
+            );
+            return Ok(ResumeInstanceResponse {
+                success: false,
+                error: Some(format!(
+                    "Instance '{}' is already being resumed",
+                    request.instance_id
+                )),
+                failure: Some(Failure::conflict()),
+            });
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to claim instance for resume");
+            return Ok(ResumeInstanceResponse {
+                success: false,
+                error: Some(format!("Resume failed: {}", e)),
+                failure: Some(Failure::internal()),
+            });
+        }
+    }
+
     // Remove the old container registry entry BEFORE launching the new process.
     // This ensures any still-running old monitor will see its handle_id is gone
     // and skip crash detection, preventing a race where the old monitor marks the
@@ -1071,16 +1445,6 @@ pub async fn handle_resume_instance(
         let _ = container_registry.cleanup(&request.instance_id).await;
     }
 
-    // Update status to "running" BEFORE launch so the WASM process can
-    // immediately perform checkpoint lookups (the Core checkpoint handler
-    // rejects requests from non-running instances).
-    if let Err(e) = state
-        .persistence
-        .update_instance_status(&request.instance_id, "running", Some(chrono::Utc::now()))
-        .await
-    {
-        warn!(error = %e, "Failed to update instance status to running before launch");
-    }
     // Also update checkpoint_id on the instance record
     if let Some(cp_id) = checkpoint_id.as_deref()
         && let Err(e) = state
@@ -1090,16 +1454,6 @@ pub async fn handle_resume_instance(
     {
         warn!(error = %e, "Failed to update instance checkpoint before launch");
     }
-    // Clear any pending wake so the wake scheduler doesn't relaunch an
-    // instance we're resuming manually (shutdown-suspended instances carry
-    // sleep_until = now for post-restart recovery).
-    if let Err(e) = state
-        .persistence
-        .clear_instance_sleep(&request.instance_id)
-        .await
-    {
-        warn!(error = %e, "Failed to clear sleep_until before resume");
-    }
 
     // Launch
     match state.runner.launch_detached(&options).await {
@@ -1158,13 +1512,23 @@ pub async fn handle_resume_instance(
             Ok(ResumeInstanceResponse {
                 success: true,
                 error: None,
+                failure: None,
             })
         }
         Err(e) => {
             error!(error = %e, "Failed to resume instance");
+            // Release the claim so the instance can be resumed again.
+            if let Err(e) = state
+                .persistence
+                .update_instance_status(&request.instance_id, &instance.status, None)
+                .await
+            {
+                warn!(error = %e, "Failed to restore instance status after failed resume");
+            }
             Ok(ResumeInstanceResponse {
                 success: false,
                 error: Some(format!("Resume failed: {}", e)),
+                failure: Some(Failure::internal()),
             })
         }
     }
@@ -1477,6 +1841,55 @@ pub fn spawn_container_monitor(
     });
 }
 
+// ============================================================================
+// Scenario Simulation
+// ============================================================================
+
+/// Request to simulate a scenario's control flow.
+pub struct SimulateScenarioRequest {
+    /// Workflow definition JSON (full workflow or bare execution graph).
+    pub scenario: serde_json::Value,
+    /// Input payload, in the same shape as a start-instance input.
+    pub input: serde_json::Value,
+    /// Stubbed outputs for side-effecting steps, keyed by step ID.
+    pub stubs: std::collections::HashMap<String, serde_json::Value>,
+    /// Wall-clock budget in milliseconds (default 5000, capped at 30000).
+    pub timeout_ms: Option<u64>,
+}
+
+/// Handle simulate scenario request.
+///
+/// Runs entirely in-process on a blocking thread: no instance is registered
+/// and nothing is written to core. Invalid scenarios and exceeded limits are
+/// reported as [`crate::error::Error::InvalidRequest`].
+#[instrument(skip(_state, request))]
+pub async fn handle_simulate_scenario(
+    _state: &EnvironmentHandlerState,
+    request: SimulateScenarioRequest,
+) -> Result<SimulationResult> {
+    let graph = simulation::parse_scenario(&request.scenario)
+        .map_err(|e| crate::error::Error::InvalidRequest(e.to_string()))?;
+    let timeout = request
+        .timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(simulation::DEFAULT_SIMULATION_TIMEOUT)
+        .min(simulation::MAX_SIMULATION_TIMEOUT);
+    let limits = SimulationLimits {
+        timeout,
+        ..SimulationLimits::default()
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        simulation::simulate(&graph, &request.input, &request.stubs, limits)
+    })
+    .await
+    .map_err(|e| crate::error::Error::Other(format!("Simulation task failed: {}", e)))?
+    .map_err(|e| crate::error::Error::InvalidRequest(e.to_string()))?;
+
+    debug!(steps = result.steps.len(), "Scenario simulated");
+    Ok(result)
+}
+
 // ============================================================================
 // Agent Testing
 // ============================================================================
@@ -1625,6 +2038,7 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             metadata,
+            warnings: Vec::new(),
         }
     }
 
@@ -7,12 +7,13 @@
 
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::extract::DefaultBodyLimit;
 use axum::{
     Router,
     extract::{Multipart, Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Json},
     routing::{get, post},
 };
@@ -21,12 +22,20 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use tracing::{error, info, warn};
 
+use crate::audit::{self, AuditEvent, actor_from_headers};
 use crate::db;
+use crate::error::{ErrorCode, Failure};
 use crate::handlers::{
-    self, EnvironmentHandlerState, GetCapabilityRequest, RegisterImageRequest,
-    ResumeInstanceRequest, StartInstanceRequest, StopInstanceRequest, TestCapabilityRequest,
+    self, CancelPendingStartOutcome, CancelPendingStartRequest, EnvironmentHandlerState,
+    GetCapabilityRequest, RegisterImageRequest, ResumeInstanceRequest, SetMaintenanceRequest,
+    SetTenantSettingsRequest, SimulateScenarioRequest, StartInstanceRequest, StopInstanceRequest,
+    TestCapabilityRequest,
 };
-use crate::image_registry::{ImageRegistry, RunnerType};
+use crate::image_provenance::{self, ImageProvenance};
+use crate::image_registry::{Image, ImageRegistry, ImageWarning, RunnerType, WarningSeverity};
+use runtara_core::instance_handlers::ReportedVersions;
+use runtara_core::persistence::ListEventsFilter;
+use runtara_core::status_watch::{StatusCursor, wait_for_instance_change};
 
 /// Maximum body size for image uploads (64 MB).
 const MAX_BODY_SIZE: usize = 64 * 1024 * 1024;
@@ -48,6 +57,12 @@ struct RegisterImageJsonRequest {
     runner_type: Option<String>,
     #[serde(default)]
     metadata: Option<Value>,
+    /// Compile warnings to persist with the image.
+    #[serde(default)]
+    warnings: Vec<ImageWarning>,
+    /// Build provenance to store with the image.
+    #[serde(default)]
+    provenance: Option<ImageProvenance>,
 }
 
 /// Register image response.
@@ -58,6 +73,8 @@ struct RegisterImageJsonResponse {
     image_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
+    #[serde(flatten)]
+    failure: Option<Failure>,
 }
 
 /// Image summary (used in list/get responses).
@@ -72,6 +89,30 @@ struct ImageSummaryJson {
     created_at_ms: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     metadata: Option<Value>,
+    warning_count: usize,
+    /// Full warning list; only filled when the caller asked for it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warnings: Option<Vec<ImageWarning>>,
+    /// Build provenance; only filled when the caller asked for it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provenance: Option<ImageProvenance>,
+}
+
+impl ImageSummaryJson {
+    fn from_image(img: Image, include_warnings: bool) -> Self {
+        Self {
+            image_id: img.image_id,
+            tenant_id: img.tenant_id,
+            name: img.name,
+            description: img.description,
+            runner_type: runner_type_to_string(img.runner_type).to_string(),
+            created_at_ms: img.created_at.timestamp_millis(),
+            metadata: img.metadata,
+            warning_count: img.warnings.len(),
+            warnings: include_warnings.then_some(img.warnings),
+            provenance: None,
+        }
+    }
 }
 
 /// List images query parameters.
@@ -94,6 +135,19 @@ struct ImageTenantQuery {
     tenant_id: Option<String>,
 }
 
+/// Get image query parameters.
+#[derive(Debug, Deserialize)]
+struct GetImageQuery {
+    #[serde(default)]
+    tenant_id: Option<String>,
+    /// Include the full compile warning list, not just the count.
+    #[serde(default)]
+    include_warnings: bool,
+    /// Include the build provenance (scenario sources can be large).
+    #[serde(default)]
+    include_provenance: bool,
+}
+
 /// Start instance request (JSON body).
 #[derive(Debug, Deserialize)]
 struct StartInstanceJsonRequest {
@@ -119,6 +173,8 @@ struct StartInstanceJsonResponse {
     deduplicated: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
+    #[serde(flatten)]
+    failure: Option<Failure>,
 }
 
 /// Stop instance request (JSON body).
@@ -130,12 +186,35 @@ struct StopInstanceJsonRequest {
     grace_period_seconds: Option<u64>,
 }
 
+/// Cancel pending start request (JSON body).
+#[derive(Debug, Deserialize)]
+struct CancelPendingStartJsonRequest {
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// Cancel pending start response.
+#[derive(Debug, Serialize)]
+struct CancelPendingStartJsonResponse {
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    outcome: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(flatten)]
+    failure: Option<Failure>,
+}
+
 /// Resume instance response.
 #[derive(Debug, Serialize)]
 struct SimpleSuccessResponse {
     success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
+    #[serde(flatten)]
+    failure: Option<Failure>,
 }
 
 /// Instance status response.
@@ -167,6 +246,9 @@ struct InstanceStatusJsonResponse {
     input: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
+    /// `code` of a structured error, such as one raised by an Error step.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stderr: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -183,6 +265,22 @@ struct InstanceStatusJsonResponse {
     termination_reason: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     exit_code: Option<i32>,
+    /// Protocol/SDK/build versions the binary reported when it last registered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reported_versions: Option<ReportedVersions>,
+    /// Change counter for status and checkpoint; pass back as
+    /// `wait_for_change_from` to long-poll for the next transition.
+    status_version: i64,
+}
+
+/// Get instance status query parameters (long-poll).
+#[derive(Debug, Deserialize)]
+struct InstanceStatusQuery {
+    /// `status_version` or status string the caller last saw.
+    #[serde(default)]
+    wait_for_change_from: Option<String>,
+    #[serde(default)]
+    max_wait_ms: Option<u64>,
 }
 
 /// List instances query parameters.
@@ -226,6 +324,50 @@ struct InstanceSummaryJson {
     #[serde(skip_serializing_if = "Option::is_none")]
     finished_at_ms: Option<i64>,
     has_error: bool,
+    /// `code` of a structured error, such as one raised by an Error step.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<String>,
+}
+
+/// List audit log query parameters.
+#[derive(Debug, Deserialize)]
+struct ListAuditLogQuery {
+    #[serde(default)]
+    actor: Option<String>,
+    #[serde(default)]
+    operation: Option<String>,
+    /// Image or instance ID.
+    #[serde(default)]
+    target_id: Option<String>,
+    #[serde(default)]
+    tenant_id: Option<String>,
+    #[serde(default)]
+    created_after_ms: Option<i64>,
+    #[serde(default)]
+    created_before_ms: Option<i64>,
+    #[serde(default)]
+    limit: Option<u32>,
+    #[serde(default)]
+    offset: Option<u32>,
+}
+
+/// Audit log entry for list responses.
+#[derive(Debug, Serialize)]
+struct AuditLogEntryJson {
+    audit_id: i64,
+    created_at_ms: i64,
+    actor: String,
+    operation: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tenant_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instance_id: Option<String>,
+    params: Value,
+    succeeded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
 }
 
 /// Send signal request (JSON body).
@@ -266,6 +408,10 @@ struct CheckpointSummaryJson {
     instance_id: String,
     created_at_ms: i64,
     data_size_bytes: u64,
+    /// Stored content type hint, or a sniffed guess for unhinted checkpoints.
+    content_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shape: Option<String>,
 }
 
 /// Full checkpoint response.
@@ -280,6 +426,34 @@ struct CheckpointDetailJson {
     data: Option<String>,
 }
 
+/// Checkpoint preview query parameters.
+#[derive(Debug, Deserialize)]
+struct CheckpointPreviewQuery {
+    #[serde(default)]
+    max_depth: Option<u32>,
+    #[serde(default)]
+    max_items: Option<u32>,
+    #[serde(default)]
+    max_string_len: Option<u32>,
+}
+
+/// Upper bound for caller-supplied preview limits, so a preview can't be
+/// coaxed into returning the whole state.
+const MAX_PREVIEW_DEPTH: u32 = 10;
+const MAX_PREVIEW_ITEMS: u32 = 200;
+const MAX_PREVIEW_STRING_LEN: u32 = 4096;
+
+/// Checkpoint preview response.
+#[derive(Debug, Serialize)]
+struct CheckpointPreviewJson {
+    found: bool,
+    checkpoint_id: String,
+    instance_id: String,
+    created_at_ms: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preview: Option<runtara_core::checkpoint_preview::CheckpointPreview>,
+}
+
 /// List events query parameters.
 #[derive(Debug, Deserialize)]
 struct ListEventsQuery {
@@ -441,6 +615,36 @@ struct TestCapabilityJsonRequest {
     timeout_ms: Option<u32>,
 }
 
+/// Simulate scenario request (JSON body).
+#[derive(Debug, Deserialize)]
+struct SimulateScenarioJsonRequest {
+    scenario: Value,
+    #[serde(default)]
+    input: Value,
+    #[serde(default)]
+    stubs: std::collections::HashMap<String, Value>,
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+}
+
+/// Set maintenance request (JSON body).
+#[derive(Debug, Deserialize)]
+struct SetMaintenanceJsonRequest {
+    enabled: bool,
+    #[serde(default)]
+    expected_duration_ms: Option<u64>,
+    #[serde(default)]
+    message: String,
+}
+
+/// Set tenant settings request (JSON body). A `null` or missing
+/// `strict_deployment_severity` turns strict deployments off.
+#[derive(Debug, Deserialize)]
+struct SetTenantSettingsJsonRequest {
+    #[serde(default)]
+    strict_deployment_severity: Option<WarningSeverity>,
+}
+
 // ============================================================================
 // Helper functions
 // ============================================================================
@@ -473,6 +677,42 @@ fn error_response(code: &str, message: &str, status: StatusCode) -> (StatusCode,
     build_error_response(code, message, status, ErrorDetail::default())
 }
 
+/// 400 for a missing or malformed request field, with `field_errors` naming it.
+fn validation_error_response(code: &str, field: &str, message: &str) -> (StatusCode, Json<Value>) {
+    let detail = ErrorDetail {
+        failure: Some(Failure::validation(field, message)),
+        ..Default::default()
+    };
+    build_error_response(code, message, StatusCode::BAD_REQUEST, detail)
+}
+
+/// HTTP status implied by a symbolic error code.
+fn status_for_error_code(code: ErrorCode) -> StatusCode {
+    match code {
+        ErrorCode::NotFound => StatusCode::NOT_FOUND,
+        ErrorCode::Conflict => StatusCode::CONFLICT,
+        ErrorCode::QuotaExceeded => StatusCode::TOO_MANY_REQUESTS,
+        ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+        ErrorCode::Validation => StatusCode::BAD_REQUEST,
+        ErrorCode::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Symbolic error code for a bare HTTP status (responses built without a
+/// typed error).
+fn error_code_for_status(status: StatusCode) -> ErrorCode {
+    match status {
+        StatusCode::NOT_FOUND => ErrorCode::NotFound,
+        StatusCode::CONFLICT => ErrorCode::Conflict,
+        StatusCode::TOO_MANY_REQUESTS => ErrorCode::QuotaExceeded,
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ErrorCode::Unauthorized,
+        StatusCode::SERVICE_UNAVAILABLE => ErrorCode::Unavailable,
+        s if s.is_client_error() => ErrorCode::Validation,
+        _ => ErrorCode::Internal,
+    }
+}
+
 /// Emit an error response derived from an error value. Accepts anything
 /// that converts into `crate::error::Error` (so callers can pass sqlx,
 /// io, or core errors directly). Preserves the legacy `{error, code}`
@@ -488,6 +728,12 @@ fn error_response_from<E: Into<crate::error::Error>>(
 ) -> (StatusCode, Json<Value>) {
     let err: crate::error::Error = err.into();
     let detail = detail_from_error(&err);
+    // A typed error (not found, conflict, ...) overrides the caller's
+    // catch-all status.
+    let status = match detail.failure.as_ref().map(|f| f.code) {
+        Some(ErrorCode::Internal) | None => status,
+        Some(code) => status_for_error_code(code),
+    };
     build_error_response(code, &err.to_string(), status, detail)
 }
 
@@ -500,6 +746,12 @@ fn build_error_response(
     let mut body = serde_json::Map::new();
     body.insert("error".into(), json!(message));
     body.insert("code".into(), json!(code));
+    let failure = detail
+        .failure
+        .unwrap_or_else(|| Failure::new(error_code_for_status(status)));
+    if let Ok(Value::Object(fields)) = serde_json::to_value(&failure) {
+        body.extend(fields);
+    }
     if let Some(v) = detail.category {
         body.insert("category".into(), json!(v));
     }
@@ -525,13 +777,16 @@ struct ErrorDetail {
     retry_hint: Option<&'static str>,
     retry_after_ms: Option<u64>,
     attributes: Option<Value>,
+    failure: Option<Failure>,
 }
 
 fn detail_from_error(err: &crate::error::Error) -> ErrorDetail {
     use runtara_core::error::StructuredError;
+    let failure = Some(err.failure());
     if let crate::error::Error::Core(core) = err {
         let s: StructuredError = core.clone().into();
         ErrorDetail {
+            failure,
             category: Some(s.category.as_str()),
             severity: Some(s.severity.as_str()),
             retry_hint: Some(s.retry_hint.as_str()),
@@ -543,7 +798,10 @@ fn detail_from_error(err: &crate::error::Error) -> ErrorDetail {
             },
         }
     } else {
-        ErrorDetail::default()
+        ErrorDetail {
+            failure,
+            ..Default::default()
+        }
     }
 }
 
@@ -560,6 +818,7 @@ async fn handle_health_check(
             "healthy": resp.healthy,
             "version": resp.version,
             "uptime_ms": resp.uptime_ms,
+            "maintenance": resp.maintenance,
         }))
         .into_response(),
         Err(e) => {
@@ -573,6 +832,7 @@ async fn handle_health_check(
 /// POST /api/v1/images — register image (JSON with base64 binary)
 async fn handle_register_image(
     State(state): State<Arc<EnvironmentHandlerState>>,
+    headers: HeaderMap,
     Json(body): Json<RegisterImageJsonRequest>,
 ) -> impl IntoResponse {
     let binary = match base64::engine::general_purpose::STANDARD.decode(&body.binary) {
@@ -593,6 +853,18 @@ async fn handle_register_image(
         .map(runner_type_from_string)
         .unwrap_or_default();
 
+    let audit_event = AuditEvent::new(actor_from_headers(&headers), audit::ops::IMAGE_REGISTER)
+        .tenant(&body.tenant_id)
+        .params(json!({
+            "name": body.name,
+            "description": body.description,
+            "runner_type": body.runner_type,
+            "metadata": body.metadata,
+            "binary_bytes": binary.len(),
+            "warning_count": body.warnings.len(),
+            "has_provenance": body.provenance.is_some(),
+        }));
+
     let req = RegisterImageRequest {
         tenant_id: body.tenant_id,
         name: body.name,
@@ -600,9 +872,19 @@ async fn handle_register_image(
         binary,
         runner_type,
         metadata: body.metadata,
+        warnings: body.warnings,
+        provenance: body.provenance,
     };
 
-    match handlers::handle_register_image(&state, req).await {
+    let result = handlers::handle_register_image(&state, req).await;
+    let audit_event = match &result {
+        Ok(resp) if resp.success => audit_event.image(&resp.image_id),
+        Ok(resp) => audit_event.outcome(Some(resp.error.clone().unwrap_or_default())),
+        Err(e) => audit_event.outcome(Some(e.to_string())),
+    };
+    audit::record(&state.pool, audit_event).await;
+
+    match result {
         Ok(resp) => {
             if resp.success {
                 (
@@ -611,6 +893,7 @@ async fn handle_register_image(
                         success: true,
                         image_id: Some(resp.image_id),
                         error: None,
+                        failure: None,
                     }),
                 )
                     .into_response()
@@ -621,6 +904,7 @@ async fn handle_register_image(
                         success: false,
                         image_id: None,
                         error: resp.error,
+                        failure: resp.failure,
                     }),
                 )
                     .into_response()
@@ -637,6 +921,7 @@ async fn handle_register_image(
 /// POST /api/v1/images/upload — multipart upload for large images
 async fn handle_register_image_upload(
     State(state): State<Arc<EnvironmentHandlerState>>,
+    headers: HeaderMap,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
     use sha2::{Digest, Sha256};
@@ -647,6 +932,8 @@ async fn handle_register_image_upload(
     let mut description: Option<String> = None;
     let mut runner_type_str: Option<String> = None;
     let mut metadata: Option<Value> = None;
+    let mut warnings: Vec<ImageWarning> = Vec::new();
+    let mut provenance: Option<ImageProvenance> = None;
     let mut sha256_expected: Option<String> = None;
     let mut binary_data: Option<Vec<u8>> = None;
 
@@ -670,6 +957,16 @@ async fn handle_register_image_upload(
                     metadata = serde_json::from_str(&text).ok();
                 }
             }
+            "warnings" => {
+                if let Ok(text) = field.text().await {
+                    warnings = serde_json::from_str(&text).unwrap_or_default();
+                }
+            }
+            "provenance" => {
+                if let Ok(text) = field.text().await {
+                    provenance = serde_json::from_str(&text).ok();
+                }
+            }
             "sha256" => {
                 sha256_expected = Some(field.text().await.unwrap_or_default());
             }
@@ -693,31 +990,27 @@ async fn handle_register_image_upload(
     let binary = match binary_data {
         Some(b) => b,
         None => {
-            return error_response(
+            return validation_error_response(
                 "MISSING_BINARY",
+                "binary",
                 "binary field is required",
-                StatusCode::BAD_REQUEST,
             )
             .into_response();
         }
     };
 
     if tenant_id.is_empty() {
-        return error_response(
+        return validation_error_response(
             "MISSING_TENANT_ID",
+            "tenant_id",
             "tenant_id field is required",
-            StatusCode::BAD_REQUEST,
         )
         .into_response();
     }
 
     if name.is_empty() {
-        return error_response(
-            "MISSING_NAME",
-            "name field is required",
-            StatusCode::BAD_REQUEST,
-        )
-        .into_response();
+        return validation_error_response("MISSING_NAME", "name", "name field is required")
+            .into_response();
     }
 
     // Verify SHA-256 if provided
@@ -813,11 +1106,25 @@ async fn handle_register_image_upload(
         builder = builder.metadata(meta);
     }
 
-    let mut image = builder.build();
+    let mut image = builder.warnings(warnings).build();
     image.image_id = image_id.clone();
 
+    let audit_event = AuditEvent::new(actor_from_headers(&headers), audit::ops::IMAGE_REGISTER)
+        .tenant(&tenant_id)
+        .image(&image_id)
+        .params(json!({
+            "name": name,
+            "description": description,
+            "runner_type": runner_type_str,
+            "metadata": image.metadata,
+            "binary_bytes": binary.len(),
+            "warning_count": image.warnings.len(),
+            "has_provenance": provenance.is_some(),
+        }));
+
     // Register in database
     if let Err(e) = image_registry.register(&image).await {
+        audit::record(&state.pool, audit_event.outcome(Some(e.to_string()))).await;
         if !replacing_existing {
             let _ = std::fs::remove_dir_all(&images_dir);
         }
@@ -829,7 +1136,14 @@ async fn handle_register_image_upload(
         .into_response();
     }
 
+    if let Err(e) = image_provenance::replace(&state.pool, &image_id, provenance.as_ref()).await {
+        audit::record(&state.pool, audit_event.outcome(Some(e.to_string()))).await;
+        return error_response_from("REGISTER_IMAGE_ERROR", e, StatusCode::INTERNAL_SERVER_ERROR)
+            .into_response();
+    }
+
     info!(image_id = %image_id, bytes = binary.len(), "Streaming image registration complete (HTTP)");
+    audit::record(&state.pool, audit_event).await;
 
     (
         StatusCode::CREATED,
@@ -837,6 +1151,7 @@ async fn handle_register_image_upload(
             success: true,
             image_id: Some(image_id),
             error: None,
+            failure: None,
         }),
     )
         .into_response()
@@ -873,15 +1188,7 @@ async fn handle_list_images(
         Ok(images) => {
             let summaries: Vec<ImageSummaryJson> = images
                 .into_iter()
-                .map(|img| ImageSummaryJson {
-                    image_id: img.image_id,
-                    tenant_id: img.tenant_id,
-                    name: img.name,
-                    description: img.description,
-                    runner_type: runner_type_to_string(img.runner_type).to_string(),
-                    created_at_ms: img.created_at.timestamp_millis(),
-                    metadata: img.metadata,
-                })
+                .map(|img| ImageSummaryJson::from_image(img, false))
                 .collect();
             Json(json!({
                 "images": summaries,
@@ -901,17 +1208,13 @@ async fn handle_list_images(
 async fn handle_get_image(
     State(state): State<Arc<EnvironmentHandlerState>>,
     Path(image_id): Path<String>,
-    Query(query): Query<ImageTenantQuery>,
+    Query(query): Query<GetImageQuery>,
 ) -> impl IntoResponse {
     let image_registry = ImageRegistry::new(state.pool.clone());
 
     if image_id.is_empty() {
-        return error_response(
-            "INVALID_REQUEST",
-            "image_id is required",
-            StatusCode::BAD_REQUEST,
-        )
-        .into_response();
+        return validation_error_response("INVALID_REQUEST", "image_id", "image_id is required")
+            .into_response();
     }
 
     match image_registry.get(&image_id).await {
@@ -923,17 +1226,25 @@ async fn handle_get_image(
                 return Json(json!({ "found": false })).into_response();
             }
 
+            let mut summary = ImageSummaryJson::from_image(img, query.include_warnings);
+            if query.include_provenance {
+                match image_provenance::get(&state.pool, &image_id).await {
+                    Ok(provenance) => summary.provenance = provenance,
+                    Err(e) => {
+                        error!("Get image provenance error: {}", e);
+                        return error_response_from(
+                            "GET_IMAGE_ERROR",
+                            e,
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                        )
+                        .into_response();
+                    }
+                }
+            }
+
             Json(json!({
                 "found": true,
-                "image": ImageSummaryJson {
-                    image_id: img.image_id,
-                    tenant_id: img.tenant_id,
-                    name: img.name,
-                    description: img.description,
-                    runner_type: runner_type_to_string(img.runner_type).to_string(),
-                    created_at_ms: img.created_at.timestamp_millis(),
-                    metadata: img.metadata,
-                }
+                "image": summary,
             }))
             .into_response()
         }
@@ -946,21 +1257,69 @@ async fn handle_get_image(
     }
 }
 
+/// GET /api/v1/images/{image_id}/source — scenario sources the image was built from
+async fn handle_get_image_source(
+    State(state): State<Arc<EnvironmentHandlerState>>,
+    Path(image_id): Path<String>,
+    Query(query): Query<ImageTenantQuery>,
+) -> impl IntoResponse {
+    let image_registry = ImageRegistry::new(state.pool.clone());
+
+    if image_id.is_empty() {
+        return validation_error_response("INVALID_REQUEST", "image_id", "image_id is required")
+            .into_response();
+    }
+
+    match image_registry.get(&image_id).await {
+        Ok(Some(img)) => {
+            // Tenant isolation
+            if let Some(ref tenant_id) = query.tenant_id
+                && img.tenant_id != *tenant_id
+            {
+                return Json(json!({ "found": false })).into_response();
+            }
+
+            match image_provenance::get_source(&state.pool, &image_id).await {
+                Ok(Some(source)) => {
+                    Json(json!({ "found": true, "source": source })).into_response()
+                }
+                Ok(None) => Json(json!({ "found": false })).into_response(),
+                Err(e) => {
+                    error!("Get image source error: {}", e);
+                    error_response_from(
+                        "GET_IMAGE_SOURCE_ERROR",
+                        e,
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    )
+                    .into_response()
+                }
+            }
+        }
+        Ok(None) => Json(json!({ "found": false })).into_response(),
+        Err(e) => {
+            error!("Get image source error: {}", e);
+            error_response_from(
+                "GET_IMAGE_SOURCE_ERROR",
+                e,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    }
+}
+
 /// DELETE /api/v1/images/{image_id} — delete image
 async fn handle_delete_image(
     State(state): State<Arc<EnvironmentHandlerState>>,
+    headers: HeaderMap,
     Path(image_id): Path<String>,
     Query(query): Query<ImageTenantQuery>,
 ) -> impl IntoResponse {
     let image_registry = ImageRegistry::new(state.pool.clone());
 
     if image_id.is_empty() {
-        return error_response(
-            "INVALID_REQUEST",
-            "image_id is required",
-            StatusCode::BAD_REQUEST,
-        )
-        .into_response();
+        return validation_error_response("INVALID_REQUEST", "image_id", "image_id is required")
+            .into_response();
     }
 
     match image_registry.get(&image_id).await {
@@ -973,13 +1332,23 @@ async fn handle_delete_image(
                     StatusCode::NOT_FOUND,
                     Json(json!({
                         "success": false,
-                        "error": format!("Image '{}' not found", image_id)
+                        "error": format!("Image '{}' not found", image_id),
+                        "error_code": ErrorCode::NotFound.as_str(),
+                        "resource": "image",
+                        "resource_id": image_id,
                     })),
                 )
                     .into_response();
             }
 
+            let audit_event =
+                AuditEvent::new(actor_from_headers(&headers), audit::ops::IMAGE_DELETE)
+                    .tenant(&img.tenant_id)
+                    .image(&image_id)
+                    .params(json!({ "name": img.name }));
+
             if let Err(e) = image_registry.delete(&image_id).await {
+                audit::record(&state.pool, audit_event.outcome(Some(e.to_string()))).await;
                 return error_response_from(
                     "DELETE_IMAGE_ERROR",
                     e,
@@ -991,6 +1360,7 @@ async fn handle_delete_image(
             // Delete files
             let images_dir = state.data_dir.join("images").join(&image_id);
             let _ = std::fs::remove_dir_all(&images_dir);
+            audit::record(&state.pool, audit_event).await;
 
             Json(json!({ "success": true })).into_response()
         }
@@ -998,7 +1368,10 @@ async fn handle_delete_image(
             StatusCode::NOT_FOUND,
             Json(json!({
                 "success": false,
-                "error": format!("Image '{}' not found", image_id)
+                "error": format!("Image '{}' not found", image_id),
+                "error_code": ErrorCode::NotFound.as_str(),
+                "resource": "image",
+                "resource_id": image_id,
             })),
         )
             .into_response(),
@@ -1013,8 +1386,23 @@ async fn handle_delete_image(
 /// POST /api/v1/instances — start instance
 async fn handle_start_instance(
     State(state): State<Arc<EnvironmentHandlerState>>,
+    headers: HeaderMap,
     Json(body): Json<StartInstanceJsonRequest>,
 ) -> impl IntoResponse {
+    // Env values routinely carry credentials under innocuous names, and
+    // inputs can be large: record env keys and input presence only.
+    let mut env_keys: Vec<&String> = body.env.keys().collect();
+    env_keys.sort();
+    let audit_event = AuditEvent::new(actor_from_headers(&headers), audit::ops::INSTANCE_START)
+        .tenant(&body.tenant_id)
+        .image(&body.image_id)
+        .params(json!({
+            "instance_id": body.instance_id,
+            "timeout_seconds": body.timeout_seconds,
+            "env_keys": env_keys,
+            "has_input": body.input.is_some(),
+        }));
+
     let req = StartInstanceRequest {
         image_id: body.image_id,
         tenant_id: body.tenant_id,
@@ -1024,7 +1412,15 @@ async fn handle_start_instance(
         env: body.env,
     };
 
-    match handlers::handle_start_instance(&state, req).await {
+    let result = handlers::handle_start_instance(&state, req).await;
+    let audit_event = match &result {
+        Ok(resp) if resp.success => audit_event.instance(&resp.instance_id),
+        Ok(resp) => audit_event.outcome(Some(resp.error.clone().unwrap_or_default())),
+        Err(e) => audit_event.outcome(Some(e.to_string())),
+    };
+    audit::record(&state.pool, audit_event).await;
+
+    match result {
         Ok(resp) => {
             if resp.success {
                 (
@@ -1038,6 +1434,7 @@ async fn handle_start_instance(
                         instance_id: Some(resp.instance_id),
                         deduplicated: resp.deduplicated,
                         error: None,
+                        failure: None,
                     }),
                 )
                     .into_response()
@@ -1053,6 +1450,7 @@ async fn handle_start_instance(
                         },
                         deduplicated: false,
                         error: resp.error,
+                        failure: resp.failure,
                     }),
                 )
                     .into_response()
@@ -1069,6 +1467,7 @@ async fn handle_start_instance(
 /// POST /api/v1/instances/{instance_id}/stop — stop instance
 async fn handle_stop_instance(
     State(state): State<Arc<EnvironmentHandlerState>>,
+    headers: HeaderMap,
     Path(instance_id): Path<String>,
     Json(body): Json<StopInstanceJsonRequest>,
 ) -> impl IntoResponse {
@@ -1077,11 +1476,26 @@ async fn handle_stop_instance(
         reason: body.reason.unwrap_or_default(),
         grace_period_seconds: body.grace_period_seconds.unwrap_or(5),
     };
+    let audit_event = AuditEvent::new(actor_from_headers(&headers), audit::ops::INSTANCE_STOP)
+        .instance(&req.instance_id)
+        .params(json!({
+            "reason": req.reason,
+            "grace_period_seconds": req.grace_period_seconds,
+        }));
+
+    let result = handlers::handle_stop_instance(&state, req).await;
+    let audit_event = match &result {
+        Ok(resp) if resp.success => audit_event,
+        Ok(resp) => audit_event.outcome(Some(resp.error.clone().unwrap_or_default())),
+        Err(e) => audit_event.outcome(Some(e.to_string())),
+    };
+    audit::record(&state.pool, audit_event).await;
 
-    match handlers::handle_stop_instance(&state, req).await {
+    match result {
         Ok(resp) => Json(SimpleSuccessResponse {
             success: resp.success,
             error: resp.error,
+            failure: resp.failure,
         })
         .into_response(),
         Err(e) => {
@@ -1092,17 +1506,94 @@ async fn handle_stop_instance(
     }
 }
 
+/// POST /api/v1/instances/{instance_id}/cancel-pending-start — withdraw a
+/// start that has not launched yet
+async fn handle_cancel_pending_start(
+    State(state): State<Arc<EnvironmentHandlerState>>,
+    headers: HeaderMap,
+    Path(instance_id): Path<String>,
+    Json(body): Json<CancelPendingStartJsonRequest>,
+) -> impl IntoResponse {
+    let audit_event = AuditEvent::new(
+        actor_from_headers(&headers),
+        audit::ops::INSTANCE_CANCEL_PENDING_START,
+    )
+    .instance(&instance_id)
+    .params(json!({ "reason": body.reason }));
+
+    let req = CancelPendingStartRequest {
+        instance_id: instance_id.clone(),
+        reason: body.reason,
+    };
+
+    let result = handlers::handle_cancel_pending_start(&state, req).await;
+    let audit_event = match &result {
+        Ok(resp) if resp.outcome == CancelPendingStartOutcome::NotFound => {
+            audit_event.outcome(Some(format!("Instance '{}' not found", instance_id)))
+        }
+        Ok(resp) => {
+            let mut audit_event = audit_event;
+            audit_event.params["outcome"] = json!(resp.outcome.as_str());
+            audit_event
+        }
+        Err(e) => audit_event.outcome(Some(e.to_string())),
+    };
+    audit::record(&state.pool, audit_event).await;
+
+    match result {
+        Ok(resp) if resp.outcome == CancelPendingStartOutcome::NotFound => {
+            Json(CancelPendingStartJsonResponse {
+                success: false,
+                outcome: Some(resp.outcome.as_str()),
+                status: None,
+                error: Some(format!("Instance '{}' not found", instance_id)),
+                failure: Some(Failure::not_found("instance", instance_id)),
+            })
+            .into_response()
+        }
+        Ok(resp) => Json(CancelPendingStartJsonResponse {
+            success: true,
+            outcome: Some(resp.outcome.as_str()),
+            status: resp.status,
+            error: None,
+            failure: None,
+        })
+        .into_response(),
+        Err(e) => {
+            error!("Cancel pending start error: {}", e);
+            error_response_from(
+                "CANCEL_PENDING_START_ERROR",
+                e,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    }
+}
+
 /// POST /api/v1/instances/{instance_id}/resume — resume instance
 async fn handle_resume_instance(
     State(state): State<Arc<EnvironmentHandlerState>>,
+    headers: HeaderMap,
     Path(instance_id): Path<String>,
 ) -> impl IntoResponse {
+    let audit_event = AuditEvent::new(actor_from_headers(&headers), audit::ops::INSTANCE_RESUME)
+        .instance(&instance_id);
     let req = ResumeInstanceRequest { instance_id };
 
-    match handlers::handle_resume_instance(&state, req).await {
+    let result = handlers::handle_resume_instance(&state, req).await;
+    let audit_event = match &result {
+        Ok(resp) if resp.success => audit_event,
+        Ok(resp) => audit_event.outcome(Some(resp.error.clone().unwrap_or_default())),
+        Err(e) => audit_event.outcome(Some(e.to_string())),
+    };
+    audit::record(&state.pool, audit_event).await;
+
+    match result {
         Ok(resp) => Json(SimpleSuccessResponse {
             success: resp.success,
             error: resp.error,
+            failure: resp.failure,
         })
         .into_response(),
         Err(e) => {
@@ -1118,13 +1609,38 @@ async fn handle_resume_instance(
 }
 
 /// GET /api/v1/instances/{instance_id} — get instance status
+///
+/// With `wait_for_change_from` this long-polls: the response is held until
+/// the instance's status or checkpoint moves past the given cursor or
+/// `max_wait_ms` elapses.
 async fn handle_get_instance_status(
     State(state): State<Arc<EnvironmentHandlerState>>,
     Path(instance_id): Path<String>,
+    Query(query): Query<InstanceStatusQuery>,
 ) -> impl IntoResponse {
+    if let Some(from) = query.wait_for_change_from.as_deref() {
+        let cursor = StatusCursor::parse(from);
+        let max_wait = Duration::from_millis(query.max_wait_ms.unwrap_or(u64::MAX));
+        if let Err(e) =
+            wait_for_instance_change(state.persistence.as_ref(), &instance_id, max_wait, |inst| {
+                cursor.is_passed_by(inst, instance_status_to_string(&inst.status))
+            })
+            .await
+        {
+            error!("Get instance status wait error: {}", e);
+            return error_response_from(
+                "GET_INSTANCE_STATUS_ERROR",
+                e,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response();
+        }
+    }
+
     match db::get_instance_full(&state.pool, &instance_id).await {
         Ok(Some(inst)) => {
             let status_str = instance_status_to_string(&inst.status);
+            let reported_versions = latest_reported_versions(&state, &inst.instance_id).await;
 
             Json(InstanceStatusJsonResponse {
                 found: true,
@@ -1143,6 +1659,7 @@ async fn handle_get_instance_status(
                 input: inst
                     .input
                     .map(|i| base64::engine::general_purpose::STANDARD.encode(&i)),
+                error_code: inst.error.as_deref().and_then(structured_error_code),
                 error: inst.error,
                 stderr: inst.stderr,
                 heartbeat_at_ms: inst.heartbeat_at.map(|t| t.timestamp_millis()),
@@ -1152,6 +1669,8 @@ async fn handle_get_instance_status(
                 cpu_usage_usec: inst.cpu_usage_usec.map(|v| v as u64),
                 termination_reason: inst.termination_reason,
                 exit_code: inst.exit_code,
+                reported_versions,
+                status_version: inst.status_version,
             })
             .into_response()
         }
@@ -1169,6 +1688,7 @@ async fn handle_get_instance_status(
             output: None,
             input: None,
             error: None,
+            error_code: None,
             stderr: None,
             heartbeat_at_ms: None,
             retry_count: None,
@@ -1177,6 +1697,8 @@ async fn handle_get_instance_status(
             cpu_usage_usec: None,
             termination_reason: None,
             exit_code: None,
+            reported_versions: None,
+            status_version: 0,
         })
         .into_response(),
         Err(e) => {
@@ -1191,6 +1713,26 @@ async fn handle_get_instance_status(
     }
 }
 
+/// Versions reported at the most recent registration, carried as the payload
+/// of the `started` event. `None` for binaries that predate the handshake.
+async fn latest_reported_versions(
+    state: &EnvironmentHandlerState,
+    instance_id: &str,
+) -> Option<ReportedVersions> {
+    let filter = ListEventsFilter {
+        event_type: Some("started".to_string()),
+        ..Default::default()
+    };
+    let events = state
+        .persistence
+        .list_events(instance_id, &filter, 1, 0)
+        .await
+        .map_err(|e| warn!(instance_id = %instance_id, error = %e, "Failed to load started event"))
+        .ok()?;
+    let payload = events.into_iter().next()?.payload?;
+    serde_json::from_slice(&payload).ok()
+}
+
 /// GET /api/v1/instances — list instances
 async fn handle_list_instances(
     State(state): State<Arc<EnvironmentHandlerState>>,
@@ -1264,6 +1806,7 @@ async fn handle_list_instances(
             started_at_ms: inst.started_at.map(|t| t.timestamp_millis()),
             finished_at_ms: inst.finished_at.map(|t| t.timestamp_millis()),
             has_error: inst.error.is_some(),
+            error_code: inst.error.as_deref().and_then(structured_error_code),
         })
         .collect();
 
@@ -1274,9 +1817,87 @@ async fn handle_list_instances(
     .into_response()
 }
 
+/// The `code` of an instance error stored as a structured JSON error, which
+/// is how Error steps and agent failures report. Lets operators tell a
+/// deliberate business abort from a crash without parsing the message.
+fn structured_error_code(error: &str) -> Option<String> {
+    serde_json::from_str::<Value>(error)
+        .ok()?
+        .get("code")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// GET /api/v1/audit-log — list recorded management operations
+async fn handle_list_audit_log(
+    State(state): State<Arc<EnvironmentHandlerState>>,
+    Query(query): Query<ListAuditLogQuery>,
+) -> impl IntoResponse {
+    use chrono::TimeZone;
+
+    let options = audit::ListAuditLogOptions {
+        actor: query.actor,
+        operation: query.operation,
+        target_id: query.target_id,
+        tenant_id: query.tenant_id,
+        created_after: query
+            .created_after_ms
+            .and_then(|ms| chrono::Utc.timestamp_millis_opt(ms).single()),
+        created_before: query
+            .created_before_ms
+            .and_then(|ms| chrono::Utc.timestamp_millis_opt(ms).single()),
+        limit: query.limit.unwrap_or(100) as i64,
+        offset: query.offset.unwrap_or(0) as i64,
+    };
+
+    let entries = match audit::list_audit_log(&state.pool, &options).await {
+        Ok(v) => v,
+        Err(e) => {
+            error!("List audit log error: {}", e);
+            return error_response_from(
+                "LIST_AUDIT_LOG_ERROR",
+                e,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response();
+        }
+    };
+
+    let total_count = match audit::count_audit_log(&state.pool, &options).await {
+        Ok(c) => c,
+        Err(e) => {
+            warn!("Count audit log error: {}", e);
+            0
+        }
+    };
+
+    let entries: Vec<AuditLogEntryJson> = entries
+        .into_iter()
+        .map(|entry| AuditLogEntryJson {
+            audit_id: entry.audit_id,
+            created_at_ms: entry.created_at.timestamp_millis(),
+            actor: entry.actor,
+            operation: entry.operation,
+            tenant_id: entry.tenant_id,
+            image_id: entry.image_id,
+            instance_id: entry.instance_id,
+            params: entry.params,
+            succeeded: entry.succeeded,
+            error: entry.error,
+        })
+        .collect();
+
+    Json(json!({
+        "entries": entries,
+        "total_count": total_count,
+    }))
+    .into_response()
+}
+
 /// POST /api/v1/instances/{instance_id}/signals — send signal
 async fn handle_send_signal(
     State(state): State<Arc<EnvironmentHandlerState>>,
+    headers: HeaderMap,
     Path(instance_id): Path<String>,
     Json(body): Json<SendSignalJsonRequest>,
 ) -> impl IntoResponse {
@@ -1288,7 +1909,10 @@ async fn handle_send_signal(
                 StatusCode::NOT_FOUND,
                 Json(json!({
                     "success": false,
-                    "error": format!("Instance '{}' not found", instance_id)
+                    "error": format!("Instance '{}' not found", instance_id),
+                    "error_code": ErrorCode::NotFound.as_str(),
+                    "resource": "instance",
+                    "resource_id": instance_id,
                 })),
             )
                 .into_response();
@@ -1308,7 +1932,8 @@ async fn handle_send_signal(
             StatusCode::CONFLICT,
             Json(json!({
                 "success": false,
-                "error": format!("Cannot send signal to instance in '{}' state", instance.status)
+                "error": format!("Cannot send signal to instance in '{}' state", instance.status),
+                "error_code": ErrorCode::Conflict.as_str(),
             })),
         )
             .into_response();
@@ -1335,11 +1960,21 @@ async fn handle_send_signal(
         .map(|p| p.as_bytes().to_vec())
         .unwrap_or_default();
 
-    match state
+    let result = state
         .persistence
         .insert_signal(&instance_id, signal_type, &payload)
-        .await
-    {
+        .await;
+    let audit_event = AuditEvent::new(actor_from_headers(&headers), audit::ops::INSTANCE_SIGNAL)
+        .tenant(&instance.tenant_id)
+        .instance(&instance_id)
+        .params(json!({
+            "signal_type": signal_type,
+            "payload_bytes": payload.len(),
+        }))
+        .outcome(result.as_ref().err().map(|e| e.to_string()));
+    audit::record(&state.pool, audit_event).await;
+
+    match result {
         Ok(()) => Json(json!({ "success": true })).into_response(),
         Err(e) => {
             error!("Send signal error: {}", e);
@@ -1352,6 +1987,7 @@ async fn handle_send_signal(
 /// POST /api/v1/instances/{instance_id}/signals/custom — send custom signal
 async fn handle_send_custom_signal(
     State(state): State<Arc<EnvironmentHandlerState>>,
+    headers: HeaderMap,
     Path(instance_id): Path<String>,
     Json(body): Json<SendCustomSignalJsonRequest>,
 ) -> impl IntoResponse {
@@ -1363,7 +1999,10 @@ async fn handle_send_custom_signal(
                 StatusCode::NOT_FOUND,
                 Json(json!({
                     "success": false,
-                    "error": format!("Instance '{}' not found", instance_id)
+                    "error": format!("Instance '{}' not found", instance_id),
+                    "error_code": ErrorCode::NotFound.as_str(),
+                    "resource": "instance",
+                    "resource_id": instance_id,
                 })),
             )
                 .into_response();
@@ -1378,13 +2017,11 @@ async fn handle_send_custom_signal(
         }
     };
 
-    let _ = instance; // Validate existence
-
     if body.checkpoint_id.is_empty() {
-        return error_response(
+        return validation_error_response(
             "INVALID_REQUEST",
+            "checkpoint_id",
             "checkpoint_id is required",
-            StatusCode::BAD_REQUEST,
         )
         .into_response();
     }
@@ -1395,11 +2032,24 @@ async fn handle_send_custom_signal(
         .map(|p| p.as_bytes().to_vec())
         .unwrap_or_default();
 
-    match state
+    let result = state
         .persistence
         .insert_custom_signal(&instance_id, &body.checkpoint_id, &payload)
-        .await
-    {
+        .await;
+    let audit_event = AuditEvent::new(
+        actor_from_headers(&headers),
+        audit::ops::INSTANCE_CUSTOM_SIGNAL,
+    )
+    .tenant(&instance.tenant_id)
+    .instance(&instance_id)
+    .params(json!({
+        "checkpoint_id": body.checkpoint_id,
+        "payload_bytes": payload.len(),
+    }))
+    .outcome(result.as_ref().err().map(|e| e.to_string()));
+    audit::record(&state.pool, audit_event).await;
+
+    match result {
         Ok(()) => {
             wake_suspended_on_signal(state.persistence.as_ref(), &instance_id).await;
             Json(json!({ "success": true })).into_response()
@@ -1513,6 +2163,12 @@ async fn handle_list_checkpoints(
             instance_id: cp.instance_id,
             created_at_ms: cp.created_at.timestamp_millis(),
             data_size_bytes: cp.state.len() as u64,
+            content_type: runtara_core::checkpoint_preview::resolve_content_type(
+                cp.content_type.as_deref(),
+                &cp.state,
+            )
+            .0,
+            shape: cp.shape,
         })
         .collect();
 
@@ -1564,6 +2220,70 @@ async fn handle_get_checkpoint(
     }
 }
 
+/// GET /api/v1/instances/{instance_id}/checkpoints/{checkpoint_id}/preview —
+/// structured, depth-limited and redacted preview of checkpoint state
+async fn handle_get_checkpoint_preview(
+    State(state): State<Arc<EnvironmentHandlerState>>,
+    Path((instance_id, checkpoint_id)): Path<(String, String)>,
+    Query(query): Query<CheckpointPreviewQuery>,
+) -> impl IntoResponse {
+    use runtara_core::checkpoint_preview::{PreviewOptions, build_preview};
+
+    let checkpoint_id = percent_encoding::percent_decode_str(&checkpoint_id)
+        .decode_utf8_lossy()
+        .to_string();
+
+    let defaults = PreviewOptions::default();
+    let options = PreviewOptions {
+        max_depth: query
+            .max_depth
+            .map_or(defaults.max_depth, |v| v.min(MAX_PREVIEW_DEPTH) as usize),
+        max_items: query
+            .max_items
+            .map_or(defaults.max_items, |v| v.min(MAX_PREVIEW_ITEMS) as usize),
+        max_string_len: query.max_string_len.map_or(defaults.max_string_len, |v| {
+            v.min(MAX_PREVIEW_STRING_LEN) as usize
+        }),
+    };
+
+    match state
+        .persistence
+        .load_checkpoint(&instance_id, &checkpoint_id)
+        .await
+    {
+        Ok(Some(cp)) => Json(CheckpointPreviewJson {
+            found: true,
+            preview: Some(build_preview(
+                &cp.state,
+                cp.content_type.as_deref(),
+                cp.shape.as_deref(),
+                options,
+            )),
+            checkpoint_id: cp.checkpoint_id,
+            instance_id: cp.instance_id,
+            created_at_ms: cp.created_at.timestamp_millis(),
+        })
+        .into_response(),
+        Ok(None) => Json(CheckpointPreviewJson {
+            found: false,
+            checkpoint_id,
+            instance_id,
+            created_at_ms: 0,
+            preview: None,
+        })
+        .into_response(),
+        Err(e) => {
+            error!("Get checkpoint preview error: {}", e);
+            error_response_from(
+                "GET_CHECKPOINT_PREVIEW_ERROR",
+                e,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    }
+}
+
 /// GET /api/v1/instances/{instance_id}/events — list events
 async fn handle_list_events(
     State(state): State<Arc<EnvironmentHandlerState>>,
@@ -1651,10 +2371,10 @@ async fn handle_list_step_summaries(
     use runtara_core::persistence::{EventSortOrder, ListStepSummariesFilter, StepStatus};
 
     if instance_id.is_empty() {
-        return error_response(
+        return validation_error_response(
             "INVALID_REQUEST",
+            "instance_id",
             "instance_id is required",
-            StatusCode::BAD_REQUEST,
         )
         .into_response();
     }
@@ -1763,10 +2483,10 @@ async fn handle_get_scope_ancestors(
     use runtara_core::persistence::{EventSortOrder, ListEventsFilter};
 
     if instance_id.is_empty() || scope_id.is_empty() {
-        return error_response(
+        return validation_error_response(
             "INVALID_REQUEST",
+            "scope_id",
             "instance_id and scope_id are required",
-            StatusCode::BAD_REQUEST,
         )
         .into_response();
     }
@@ -1876,12 +2596,8 @@ async fn handle_get_tenant_metrics(
     Query(query): Query<TenantMetricsQuery>,
 ) -> impl IntoResponse {
     if tenant_id.is_empty() {
-        return error_response(
-            "INVALID_REQUEST",
-            "tenant_id is required",
-            StatusCode::BAD_REQUEST,
-        )
-        .into_response();
+        return validation_error_response("INVALID_REQUEST", "tenant_id", "tenant_id is required")
+            .into_response();
     }
 
     let now = chrono::Utc::now();
@@ -1960,6 +2676,132 @@ async fn handle_get_tenant_metrics(
     .into_response()
 }
 
+/// POST /api/v1/scenarios/simulate — walk a scenario's control flow with
+/// stubbed step outputs, without launching an instance
+async fn handle_simulate_scenario(
+    State(state): State<Arc<EnvironmentHandlerState>>,
+    Json(body): Json<SimulateScenarioJsonRequest>,
+) -> impl IntoResponse {
+    let req = SimulateScenarioRequest {
+        scenario: body.scenario,
+        input: body.input,
+        stubs: body.stubs,
+        timeout_ms: body.timeout_ms,
+    };
+
+    match handlers::handle_simulate_scenario(&state, req).await {
+        Ok(result) => Json(result).into_response(),
+        Err(e) => {
+            warn!("Simulate scenario error: {}", e);
+            error_response_from("SIMULATE_SCENARIO_ERROR", e, StatusCode::BAD_REQUEST)
+                .into_response()
+        }
+    }
+}
+
+/// GET /api/v1/maintenance — active core maintenance window, if any
+async fn handle_get_maintenance(
+    State(state): State<Arc<EnvironmentHandlerState>>,
+) -> impl IntoResponse {
+    match handlers::handle_get_maintenance(&state).await {
+        Ok(window) => Json(json!({ "maintenance": window })).into_response(),
+        Err(e) => {
+            error!("Get maintenance error: {}", e);
+            error_response_from("MAINTENANCE_ERROR", e, StatusCode::INTERNAL_SERVER_ERROR)
+                .into_response()
+        }
+    }
+}
+
+/// POST /api/v1/maintenance — open or close a core maintenance window
+async fn handle_set_maintenance(
+    State(state): State<Arc<EnvironmentHandlerState>>,
+    headers: HeaderMap,
+    Json(body): Json<SetMaintenanceJsonRequest>,
+) -> impl IntoResponse {
+    let params = json!({
+        "enabled": body.enabled,
+        "expected_duration_ms": body.expected_duration_ms,
+        "message": body.message,
+    });
+    let req = SetMaintenanceRequest {
+        enabled: body.enabled,
+        expected_duration_ms: body.expected_duration_ms,
+        message: body.message,
+    };
+
+    let result = handlers::handle_set_maintenance(&state, req).await;
+    let audit_event = AuditEvent::new(actor_from_headers(&headers), audit::ops::MAINTENANCE_SET)
+        .params(params)
+        .outcome(result.as_ref().err().map(|e| e.to_string()));
+    audit::record(&state.pool, audit_event).await;
+
+    match result {
+        Ok(window) => Json(json!({ "success": true, "maintenance": window })).into_response(),
+        Err(e) => {
+            error!("Set maintenance error: {}", e);
+            error_response_from("MAINTENANCE_ERROR", e, StatusCode::INTERNAL_SERVER_ERROR)
+                .into_response()
+        }
+    }
+}
+
+/// GET /api/v1/tenants/{tenant_id}/settings — tenant settings (defaults when unset)
+async fn handle_get_tenant_settings(
+    State(state): State<Arc<EnvironmentHandlerState>>,
+    Path(tenant_id): Path<String>,
+) -> impl IntoResponse {
+    match handlers::handle_get_tenant_settings(&state, &tenant_id).await {
+        Ok(settings) => Json(json!({ "settings": settings })).into_response(),
+        Err(e) => {
+            error!("Get tenant settings error: {}", e);
+            error_response_from(
+                "TENANT_SETTINGS_ERROR",
+                e,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    }
+}
+
+/// PUT /api/v1/tenants/{tenant_id}/settings — change tenant settings
+async fn handle_set_tenant_settings(
+    State(state): State<Arc<EnvironmentHandlerState>>,
+    Path(tenant_id): Path<String>,
+    headers: HeaderMap,
+    Json(body): Json<SetTenantSettingsJsonRequest>,
+) -> impl IntoResponse {
+    let params = json!({ "strict_deployment_severity": body.strict_deployment_severity });
+    let req = SetTenantSettingsRequest {
+        tenant_id: tenant_id.clone(),
+        strict_deployment_severity: body.strict_deployment_severity,
+    };
+
+    let result = handlers::handle_set_tenant_settings(&state, req).await;
+    let audit_event = AuditEvent::new(
+        actor_from_headers(&headers),
+        audit::ops::TENANT_SETTINGS_SET,
+    )
+    .tenant(&tenant_id)
+    .params(params)
+    .outcome(result.as_ref().err().map(|e| e.to_string()));
+    audit::record(&state.pool, audit_event).await;
+
+    match result {
+        Ok(settings) => Json(json!({ "success": true, "settings": settings })).into_response(),
+        Err(e) => {
+            error!("Set tenant settings error: {}", e);
+            error_response_from(
+                "TENANT_SETTINGS_ERROR",
+                e,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    }
+}
+
 /// POST /api/v1/agents/test — test capability
 async fn handle_test_capability(
     State(state): State<Arc<EnvironmentHandlerState>>,
@@ -1989,6 +2831,7 @@ async fn handle_test_capability(
                     Json(json!({
                         "success": false,
                         "error": resp.error,
+                        "error_code": ErrorCode::Internal.as_str(),
                         "execution_time_ms": resp.execution_time_ms,
                     })),
                 )
@@ -2090,6 +2933,10 @@ pub fn environment_http_router(state: Arc<EnvironmentHandlerState>) -> Router {
             "/api/v1/images/{image_id}",
             get(handle_get_image).delete(handle_delete_image),
         )
+        .route(
+            "/api/v1/images/{image_id}/source",
+            get(handle_get_image_source),
+        )
         // Instance lifecycle
         .route(
             "/api/v1/instances",
@@ -2103,6 +2950,10 @@ pub fn environment_http_router(state: Arc<EnvironmentHandlerState>) -> Router {
             "/api/v1/instances/{instance_id}/stop",
             post(handle_stop_instance),
         )
+        .route(
+            "/api/v1/instances/{instance_id}/cancel-pending-start",
+            post(handle_cancel_pending_start),
+        )
         .route(
             "/api/v1/instances/{instance_id}/resume",
             post(handle_resume_instance),
@@ -2125,6 +2976,10 @@ pub fn environment_http_router(state: Arc<EnvironmentHandlerState>) -> Router {
             "/api/v1/instances/{instance_id}/checkpoints/{checkpoint_id}",
             get(handle_get_checkpoint),
         )
+        .route(
+            "/api/v1/instances/{instance_id}/checkpoints/{checkpoint_id}/preview",
+            get(handle_get_checkpoint_preview),
+        )
         // Events
         .route(
             "/api/v1/instances/{instance_id}/events",
@@ -2145,6 +3000,20 @@ pub fn environment_http_router(state: Arc<EnvironmentHandlerState>) -> Router {
             "/api/v1/tenants/{tenant_id}/metrics",
             get(handle_get_tenant_metrics),
         )
+        // Audit log
+        .route("/api/v1/audit-log", get(handle_list_audit_log))
+        // Simulation
+        .route("/api/v1/scenarios/simulate", post(handle_simulate_scenario))
+        // Maintenance
+        .route(
+            "/api/v1/maintenance",
+            get(handle_get_maintenance).post(handle_set_maintenance),
+        )
+        // Tenant settings
+        .route(
+            "/api/v1/tenants/{tenant_id}/settings",
+            get(handle_get_tenant_settings).put(handle_set_tenant_settings),
+        )
         // Agent testing
         .route("/api/v1/agents/test", post(handle_test_capability))
         .route("/api/v1/agents", get(handle_list_agents))
@@ -2340,4 +3209,58 @@ mod tests {
         assert_eq!(body["error"], "unexpected state");
         assert!(body.get("category").is_none());
     }
+
+    #[test]
+    fn error_response_from_tags_not_found_with_resource() {
+        let err = crate::error::Error::from(CoreError::InstanceNotFound {
+            instance_id: "inst-7".to_string(),
+        });
+        let (status, Json(body)) = error_response_from(
+            "GET_INSTANCE_STATUS_ERROR",
+            err,
+            StatusCode::INTERNAL_SERVER_ERROR,
+        );
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error_code"], "NOT_FOUND");
+        assert_eq!(body["resource"], "instance");
+        assert_eq!(body["resource_id"], "inst-7");
+    }
+
+    #[test]
+    fn error_response_derives_error_code_from_status() {
+        let body = body_of(error_response(
+            "HEALTH_CHECK_ERROR",
+            "database down",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ));
+        assert_eq!(body["error_code"], "INTERNAL");
+
+        let body = body_of(error_response(
+            "SIGNAL_ERROR",
+            "instance is not running",
+            StatusCode::CONFLICT,
+        ));
+        assert_eq!(body["error_code"], "CONFLICT");
+    }
+
+    #[test]
+    fn structured_error_code_reads_error_step_failures() {
+        let failure = r#"{"stepId":"abort","code":"ORDER_ALREADY_SHIPPED","message":"Order already shipped","category":"permanent"}"#;
+        assert_eq!(
+            structured_error_code(failure).as_deref(),
+            Some("ORDER_ALREADY_SHIPPED")
+        );
+        assert_eq!(structured_error_code("process exited with code 137"), None);
+        assert_eq!(structured_error_code(r#"{"message":"no code"}"#), None);
+    }
+
+    #[test]
+    fn validation_error_response_names_the_field() {
+        let (status, Json(body)) =
+            validation_error_response("INVALID_REQUEST", "tenant_id", "tenant_id is required");
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "tenant_id is required");
+        assert_eq!(body["error_code"], "VALIDATION");
+        assert_eq!(body["field_errors"][0]["field"], "tenant_id");
+    }
 }
@@ -0,0 +1,314 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Image build provenance.
+//!
+//! An image may carry the scenario source it was compiled from (plus the
+//! sources of the child scenarios inlined into it) and the fingerprint of the
+//! toolchain that built it. Sources are stored gzip-compressed in
+//! `image_source_blobs`, keyed by the SHA-256 of their JSON, so the many
+//! versions sharing a child scenario store it once. Blobs no image references
+//! any more are pruned when provenance is replaced or the image is deleted.
+
+use std::io::{Read, Write};
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+
+use crate::error::{Error, Result};
+
+/// Build provenance recorded with an image.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImageProvenance {
+    /// Scenario (execution graph) JSON the image was compiled from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<Value>,
+    /// Child scenarios compiled into the image
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub child_sources: Vec<ImageChildSource>,
+    /// Content hash of the scenario graph
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph_hash: Option<String>,
+    /// `runtara-workflows` version that compiled the image
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler_version: Option<String>,
+    /// `rustc --version` of the toolchain that built the compiler
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rustc_version: Option<String>,
+    /// Checksum over the agent/stdlib components linked into the image
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_library_checksum: Option<String>,
+}
+
+/// Source of one child scenario compiled into an image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageChildSource {
+    /// Child workflow id
+    pub workflow_id: String,
+    /// Resolved child workflow version
+    pub version: i32,
+    /// Child scenario JSON
+    pub source: Value,
+}
+
+/// The scenario sources stored with an image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageSource {
+    /// Scenario the image was compiled from
+    pub source: Value,
+    /// Child scenarios compiled into the image
+    #[serde(default)]
+    pub child_sources: Vec<ImageChildSource>,
+}
+
+/// SHA-256 (hex) of a source's JSON encoding; the blob deduplication key.
+pub fn source_hash(source: &Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(source.to_string().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn compress(source: &Value) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(source.to_string().as_bytes())?;
+    Ok(encoder.finish()?)
+}
+
+fn decompress(content: &[u8]) -> Result<Value> {
+    let mut json = Vec::new();
+    GzDecoder::new(content).read_to_end(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// Store `source` unless a blob with the same hash already exists.
+async fn put_blob(conn: &mut PgConnection, source: &Value) -> Result<String> {
+    let hash = source_hash(source);
+    let content = compress(source)?;
+    sqlx::query(
+        r#"
+        INSERT INTO image_source_blobs (source_hash, content, size_bytes)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (source_hash) DO NOTHING
+        "#,
+    )
+    .bind(&hash)
+    .bind(&content)
+    .bind(source.to_string().len() as i64)
+    .execute(&mut *conn)
+    .await?;
+    Ok(hash)
+}
+
+async fn get_blob(pool: &PgPool, hash: &str) -> Result<Value> {
+    let content: Option<Vec<u8>> =
+        sqlx::query_scalar("SELECT content FROM image_source_blobs WHERE source_hash = $1")
+            .bind(hash)
+            .fetch_optional(pool)
+            .await?;
+    let content =
+        content.ok_or_else(|| Error::Other(format!("image source blob '{}' is missing", hash)))?;
+    decompress(&content)
+}
+
+/// Blob hashes `image_id` references.
+pub(crate) async fn referenced_hashes(
+    conn: &mut PgConnection,
+    image_id: &str,
+) -> Result<Vec<String>> {
+    Ok(sqlx::query_scalar(
+        r#"
+        SELECT source_hash FROM image_provenance
+        WHERE image_id = $1 AND source_hash IS NOT NULL
+        UNION
+        SELECT source_hash FROM image_child_sources WHERE image_id = $1
+        "#,
+    )
+    .bind(image_id)
+    .fetch_all(&mut *conn)
+    .await?)
+}
+
+/// Delete the blobs among `hashes` that no image references any more.
+pub(crate) async fn prune_blobs(conn: &mut PgConnection, hashes: &[String]) -> Result<u64> {
+    if hashes.is_empty() {
+        return Ok(0);
+    }
+    let result = sqlx::query(
+        r#"
+        DELETE FROM image_source_blobs b
+        WHERE b.source_hash = ANY($1)
+          AND NOT EXISTS (SELECT 1 FROM image_provenance p WHERE p.source_hash = b.source_hash)
+          AND NOT EXISTS (SELECT 1 FROM image_child_sources c WHERE c.source_hash = b.source_hash)
+        "#,
+    )
+    .bind(hashes)
+    .execute(&mut *conn)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Replace the provenance of `image_id` (`None` clears it). Re-registering an
+/// image under the same name must not keep the previous build's source.
+pub async fn replace(
+    pool: &PgPool,
+    image_id: &str,
+    provenance: Option<&ImageProvenance>,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    let previous = referenced_hashes(&mut tx, image_id).await?;
+
+    sqlx::query("DELETE FROM image_child_sources WHERE image_id = $1")
+        .bind(image_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM image_provenance WHERE image_id = $1")
+        .bind(image_id)
+        .execute(&mut *tx)
+        .await?;
+
+    if let Some(provenance) = provenance {
+        let source_hash = match &provenance.source {
+            Some(source) => Some(put_blob(&mut tx, source).await?),
+            None => None,
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO image_provenance (
+                image_id, source_hash, graph_hash, compiler_version,
+                rustc_version, agent_library_checksum
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(image_id)
+        .bind(&source_hash)
+        .bind(&provenance.graph_hash)
+        .bind(&provenance.compiler_version)
+        .bind(&provenance.rustc_version)
+        .bind(&provenance.agent_library_checksum)
+        .execute(&mut *tx)
+        .await?;
+
+        for child in &provenance.child_sources {
+            let hash = put_blob(&mut tx, &child.source).await?;
+            sqlx::query(
+                r#"
+                INSERT INTO image_child_sources (image_id, workflow_id, version, source_hash)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (image_id, workflow_id, version) DO NOTHING
+                "#,
+            )
+            .bind(image_id)
+            .bind(&child.workflow_id)
+            .bind(child.version)
+            .bind(&hash)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    prune_blobs(&mut tx, &previous).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+#[derive(sqlx::FromRow)]
+struct ProvenanceRow {
+    source_hash: Option<String>,
+    graph_hash: Option<String>,
+    compiler_version: Option<String>,
+    rustc_version: Option<String>,
+    agent_library_checksum: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ChildSourceRow {
+    workflow_id: String,
+    version: i32,
+    source_hash: String,
+}
+
+/// Load the provenance of `image_id`, sources decompressed.
+pub async fn get(pool: &PgPool, image_id: &str) -> Result<Option<ImageProvenance>> {
+    let row: Option<ProvenanceRow> = sqlx::query_as(
+        r#"
+        SELECT source_hash, graph_hash, compiler_version, rustc_version, agent_library_checksum
+        FROM image_provenance
+        WHERE image_id = $1
+        "#,
+    )
+    .bind(image_id)
+    .fetch_optional(pool)
+    .await?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let source = match &row.source_hash {
+        Some(hash) => Some(get_blob(pool, hash).await?),
+        None => None,
+    };
+
+    let child_rows: Vec<ChildSourceRow> = sqlx::query_as(
+        r#"
+        SELECT workflow_id, version, source_hash
+        FROM image_child_sources
+        WHERE image_id = $1
+        ORDER BY workflow_id, version
+        "#,
+    )
+    .bind(image_id)
+    .fetch_all(pool)
+    .await?;
+    let mut child_sources = Vec::with_capacity(child_rows.len());
+    for child in child_rows {
+        child_sources.push(ImageChildSource {
+            source: get_blob(pool, &child.source_hash).await?,
+            workflow_id: child.workflow_id,
+            version: child.version,
+        });
+    }
+
+    Ok(Some(ImageProvenance {
+        source,
+        child_sources,
+        graph_hash: row.graph_hash,
+        compiler_version: row.compiler_version,
+        rustc_version: row.rustc_version,
+        agent_library_checksum: row.agent_library_checksum,
+    }))
+}
+
+/// Load the scenario sources of `image_id`; `None` when no source was stored.
+pub async fn get_source(pool: &PgPool, image_id: &str) -> Result<Option<ImageSource>> {
+    Ok(get(pool, image_id).await?.and_then(|provenance| {
+        provenance.source.map(|source| ImageSource {
+            source,
+            child_sources: provenance.child_sources,
+        })
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn compressed_source_round_trips() {
+        let source = json!({"steps": {"finish": {"stepType": "Finish"}}, "entryPoint": "finish"});
+        let content = compress(&source).unwrap();
+        assert_eq!(decompress(&content).unwrap(), source);
+    }
+
+    #[test]
+    fn identical_sources_share_a_hash() {
+        let source = json!({"entryPoint": "finish", "steps": {}});
+        assert_eq!(source_hash(&source), source_hash(&source.clone()));
+        assert_ne!(source_hash(&source), source_hash(&json!({"steps": {}})));
+        assert_eq!(source_hash(&source).len(), 64);
+    }
+}
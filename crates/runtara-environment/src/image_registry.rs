@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 
 use crate::error::Result;
+use crate::image_provenance;
 
 /// Type of runner that should be used for an image.
 ///
@@ -49,6 +50,55 @@ impl std::str::FromStr for RunnerType {
     }
 }
 
+/// Severity of an image warning. Ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WarningSeverity {
+    /// Informational only.
+    Info,
+    /// Likely authoring mistake.
+    Warning,
+    /// Tolerated by the compiler but expected to misbehave at runtime.
+    Error,
+}
+
+impl std::fmt::Display for WarningSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WarningSeverity::Info => write!(f, "info"),
+            WarningSeverity::Warning => write!(f, "warning"),
+            WarningSeverity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl std::str::FromStr for WarningSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(WarningSeverity::Info),
+            "warning" => Ok(WarningSeverity::Warning),
+            "error" => Ok(WarningSeverity::Error),
+            _ => Err(format!("Unknown warning severity: {}", s)),
+        }
+    }
+}
+
+/// A compile warning recorded with an image at registration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageWarning {
+    /// Stable warning code (e.g. `W090`)
+    pub code: String,
+    /// Step the warning is about, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_id: Option<String>,
+    /// Human-readable message
+    pub message: String,
+    /// Severity of the warning
+    pub severity: WarningSeverity,
+}
+
 /// An image that can be launched as an instance.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Image {
@@ -72,6 +122,21 @@ pub struct Image {
     pub updated_at: DateTime<Utc>,
     /// Optional metadata (JSON)
     pub metadata: Option<serde_json::Value>,
+    /// Compile warnings recorded at registration
+    #[serde(default)]
+    pub warnings: Vec<ImageWarning>,
+}
+
+impl Image {
+    /// Warnings at or above `threshold`.
+    pub fn warnings_at_or_above(
+        &self,
+        threshold: WarningSeverity,
+    ) -> impl Iterator<Item = &ImageWarning> {
+        self.warnings
+            .iter()
+            .filter(move |w| w.severity >= threshold)
+    }
 }
 
 /// Image registry - manages available images in the database.
@@ -93,15 +158,16 @@ impl ImageRegistry {
             r#"
             INSERT INTO images (
                 image_id, tenant_id, name, description, binary_path, bundle_path,
-                runner_type, created_at, updated_at, metadata
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                runner_type, created_at, updated_at, metadata, warnings
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             ON CONFLICT (tenant_id, name) DO UPDATE SET
                 description = EXCLUDED.description,
                 binary_path = EXCLUDED.binary_path,
                 bundle_path = EXCLUDED.bundle_path,
                 runner_type = EXCLUDED.runner_type,
                 updated_at = EXCLUDED.updated_at,
-                metadata = EXCLUDED.metadata
+                metadata = EXCLUDED.metadata,
+                warnings = EXCLUDED.warnings
             "#,
         )
         .bind(&image.image_id)
@@ -114,6 +180,7 @@ impl ImageRegistry {
         .bind(image.created_at)
         .bind(image.updated_at)
         .bind(&image.metadata)
+        .bind(sqlx::types::Json(&image.warnings))
         .execute(&self.pool)
         .await?;
 
@@ -121,6 +188,7 @@ impl ImageRegistry {
             image_id = %image.image_id,
             name = %image.name,
             runner_type = %runner_type_str,
+            warnings = image.warnings.len(),
             "Registered image"
         );
 
@@ -132,7 +200,7 @@ impl ImageRegistry {
         let row: Option<ImageRow> = sqlx::query_as(
             r#"
             SELECT image_id, tenant_id, name, description, binary_path, bundle_path,
-                   runner_type, created_at, updated_at, metadata
+                   runner_type, created_at, updated_at, metadata, warnings
             FROM images
             WHERE image_id = $1
             "#,
@@ -149,7 +217,7 @@ impl ImageRegistry {
         let row: Option<ImageRow> = sqlx::query_as(
             r#"
             SELECT image_id, tenant_id, name, description, binary_path, bundle_path,
-                   runner_type, created_at, updated_at, metadata
+                   runner_type, created_at, updated_at, metadata, warnings
             FROM images
             WHERE tenant_id = $1 AND name = $2
             "#,
@@ -167,7 +235,7 @@ impl ImageRegistry {
         let rows: Vec<ImageRow> = sqlx::query_as(
             r#"
             SELECT image_id, tenant_id, name, description, binary_path, bundle_path,
-                   runner_type, created_at, updated_at, metadata
+                   runner_type, created_at, updated_at, metadata, warnings
             FROM images
             WHERE tenant_id = $1
             ORDER BY name
@@ -190,7 +258,7 @@ impl ImageRegistry {
         let rows: Vec<ImageRow> = sqlx::query_as(
             r#"
             SELECT image_id, tenant_id, name, description, binary_path, bundle_path,
-                   runner_type, created_at, updated_at, metadata
+                   runner_type, created_at, updated_at, metadata, warnings
             FROM images
             WHERE tenant_id = $1
             ORDER BY created_at DESC
@@ -211,7 +279,7 @@ impl ImageRegistry {
         let rows: Vec<ImageRow> = sqlx::query_as(
             r#"
             SELECT image_id, tenant_id, name, description, binary_path, bundle_path,
-                   runner_type, created_at, updated_at, metadata
+                   runner_type, created_at, updated_at, metadata, warnings
             FROM images
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
@@ -227,10 +295,16 @@ impl ImageRegistry {
 
     /// Delete an image
     pub async fn delete(&self, image_id: &str) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+        // Provenance rows cascade with the image; the source blobs they
+        // pointed at are shared, so only drop the ones now unreferenced.
+        let source_hashes = image_provenance::referenced_hashes(&mut tx, image_id).await?;
         let result = sqlx::query("DELETE FROM images WHERE image_id = $1")
             .bind(image_id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
+        image_provenance::prune_blobs(&mut tx, &source_hashes).await?;
+        tx.commit().await?;
 
         Ok(result.rows_affected() > 0)
     }
@@ -273,6 +347,7 @@ struct ImageRow {
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     metadata: Option<serde_json::Value>,
+    warnings: serde_json::Value,
 }
 
 impl From<ImageRow> for Image {
@@ -288,6 +363,9 @@ impl From<ImageRow> for Image {
             created_at: row.created_at,
             updated_at: row.updated_at,
             metadata: row.metadata,
+            // Rows written before warnings existed hold `[]`; anything
+            // unreadable is treated the same rather than failing the read.
+            warnings: serde_json::from_value(row.warnings).unwrap_or_default(),
         }
     }
 }
@@ -302,6 +380,7 @@ pub struct ImageBuilder {
     bundle_path: Option<String>,
     runner_type: RunnerType,
     metadata: Option<serde_json::Value>,
+    warnings: Vec<ImageWarning>,
 }
 
 impl ImageBuilder {
@@ -320,6 +399,7 @@ impl ImageBuilder {
             bundle_path: None,
             runner_type: RunnerType::default(),
             metadata: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -353,6 +433,12 @@ impl ImageBuilder {
         self
     }
 
+    /// Set compile warnings
+    pub fn warnings(mut self, warnings: Vec<ImageWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
     /// Build the image
     pub fn build(self) -> Image {
         let now = Utc::now();
@@ -369,6 +455,7 @@ impl ImageBuilder {
             created_at: now,
             updated_at: now,
             metadata: self.metadata,
+            warnings: self.warnings,
         }
     }
 }
@@ -51,14 +51,21 @@
 //! | `RegisterImageStream` | Register a large image via streaming upload |
 //! | `ListImages` | List images with optional tenant filter and pagination |
 //! | `GetImage` | Get image details by ID |
+//! | `GetImageSource` | Get the scenario sources an image was compiled from |
 //! | `DeleteImage` | Delete an image |
 //!
+//! Registration may attach build provenance: the scenario source (and child
+//! scenario sources), graph hash, compiler and rustc versions, and an agent
+//! library checksum. `GetImage` includes it on request (see
+//! [`image_provenance`]).
+//!
 //! ## Instance Operations
 //!
 //! | Operation | Description |
 //! |-----------|-------------|
 //! | `StartInstance` | Start a new instance from an image |
 //! | `StopInstance` | Stop a running instance with grace period |
+//! | `CancelPendingStart` | Withdraw a start that has not launched yet |
 //! | `ResumeInstance` | Resume a suspended instance |
 //! | `GetInstanceStatus` | Query instance status |
 //! | `ListInstances` | List instances with filtering and pagination |
@@ -71,6 +78,43 @@
 //!
 //! Signals are proxied to runtara-core which stores them for the instance.
 //!
+//! ## Audit Operations
+//!
+//! | Operation | Description |
+//! |-----------|-------------|
+//! | `ListAuditLog` | List recorded management operations by actor, operation, target, time |
+//!
+//! ## Simulation Operations
+//!
+//! | Operation | Description |
+//! |-----------|-------------|
+//! | `SimulateScenario` | Predict the executed path of a scenario for an input, with stubbed agent outputs |
+//!
+//! ## Maintenance Operations
+//!
+//! | Operation | Description |
+//! |-----------|-------------|
+//! | `SetMaintenance` | Open or close a time-boxed core maintenance window (failovers) |
+//! | `GetMaintenance` | Query the active maintenance window |
+//!
+//! While a window is open, core refuses instance checkpoint and event writes
+//! with a retry-after hint, and the wake scheduler leaves sleepers asleep.
+//!
+//! ## Tenant Settings
+//!
+//! | Operation | Description |
+//! |-----------|-------------|
+//! | `GetTenantSettings` | Query a tenant's settings |
+//! | `SetTenantSettings` | Set the strict-deployment warning severity |
+//!
+//! Images carry the compile warnings reported at registration (`GetImage`
+//! returns the count, and the full list on request). With strict deployments
+//! on, `StartInstance` refuses images with a warning at or above the
+//! configured severity.
+//!
+//! Every mutating operation above is recorded in the audit log with the
+//! caller from the `X-Runtara-Actor` header (see [`audit`]).
+//!
 //! # Runner Types
 //!
 //! Environment supports multiple runner backends for executing workflow binaries:
@@ -131,6 +175,7 @@
 //!
 //! # Modules
 //!
+//! - [`audit`]: Audit trail of management operations
 //! - [`config`]: Server configuration from environment variables
 //! - [`db`]: PostgreSQL persistence for images, instances, and wake queue
 //! - [`error`]: Error types for Environment operations
@@ -141,6 +186,7 @@
 //! - [`runner`]: Container/process execution backends
 //! - [`http_server`]: HTTP server implementation
 //! - [`wake_scheduler`]: Durable sleep wake scheduling
+//! - [`simulation`]: Control-flow simulation with stubbed step outputs
 
 #![deny(missing_docs)]
 
@@ -157,6 +203,9 @@
 /// ```
 pub mod migrations;
 
+/// Audit trail of management operations.
+pub mod audit;
+
 /// Server configuration loaded from environment variables.
 pub mod config;
 
@@ -172,6 +221,9 @@ pub mod handlers;
 /// Image storage and retrieval.
 pub mod image_registry;
 
+/// Build provenance (scenario sources, compiler fingerprint) stored with images.
+pub mod image_provenance;
+
 /// Running container tracking and management.
 pub mod container_registry;
 
@@ -205,6 +257,12 @@ pub mod recovery;
 /// Embeddable runtime for runtara-environment.
 pub mod runtime;
 
+/// Control-flow simulation of workflow definitions with stubbed step outputs.
+pub mod simulation;
+
+/// Per-tenant settings (strict deployments).
+pub mod tenant_settings;
+
 /// Persistence-backed implementation of the component host's `RuntimeHost` —
 /// the native replacement for the composed guest runtime's HTTP loopback.
 pub mod runtime_host;
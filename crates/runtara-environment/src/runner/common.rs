@@ -133,7 +133,9 @@ pub(crate) async fn ensure_run_dir(
     instance_id: &str,
 ) -> Result<()> {
     let dir = run_dir(data_dir, tenant_id, instance_id);
-    fs::create_dir_all(&dir).await?;
+    fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| RunnerError::from_bundle_io("create run directory", e))?;
     debug!(instance_id = %instance_id, "Run directory created");
     Ok(())
 }
@@ -62,11 +62,18 @@ pub struct EmbeddedWasmRunner {
 impl EmbeddedWasmRunner {
     /// Build the runner with its own engine + epoch ticker.
     pub fn new(config: WorkflowRunnerConfig, persistence: Arc<dyn Persistence>) -> Result<Self> {
-        let engine = build_engine(&EngineConfig::default())
-            .map_err(|e| RunnerError::Other(format!("build wasmtime engine: {e:#}")))?;
+        let engine = build_engine(&EngineConfig::default()).map_err(|e| {
+            RunnerError::RuntimeUnavailable {
+                which: "wasmtime".to_string(),
+                detail: format!("build engine: {e:#}"),
+            }
+        })?;
         spawn_epoch_ticker(Arc::clone(&engine));
-        let executor = WorkflowExecutor::new(engine)
-            .map_err(|e| RunnerError::Other(format!("build workflow executor: {e:#}")))?;
+        let executor =
+            WorkflowExecutor::new(engine).map_err(|e| RunnerError::RuntimeUnavailable {
+                which: "wasmtime".to_string(),
+                detail: format!("build workflow executor: {e:#}"),
+            })?;
         let handler_state = Arc::new(runtara_core::instance_handlers::InstanceHandlerState::new(
             Arc::clone(&persistence),
         ));
@@ -106,11 +113,18 @@ impl EmbeddedWasmRunner {
         // that indifference is the dual-ABI story: old workflows run
         // unchanged, without a rebuild, through the same spec.
         let debug_mode = env.get("DEBUG_MODE").is_some_and(|value| value == "true");
-        let runtime = Arc::new(crate::runtime_host::PersistenceRuntimeHost::new(
-            Arc::clone(&self.handler_state),
-            options.instance_id.clone(),
-            debug_mode,
-        ));
+        let debug_event_budget = env
+            .get(runtara_core::debug_budget::DEBUG_EVENT_BUDGET_ENV)
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(0);
+        let runtime = Arc::new(
+            crate::runtime_host::PersistenceRuntimeHost::new(
+                Arc::clone(&self.handler_state),
+                options.instance_id.clone(),
+                debug_mode,
+            )
+            .with_debug_event_budget(debug_event_budget),
+        );
         WorkflowRunSpec {
             env,
             stderr,
@@ -130,7 +144,10 @@ impl EmbeddedWasmRunner {
             .persistence
             .get_instance(instance_id)
             .await
-            .map_err(|e| RunnerError::StartFailed(format!("load instance input: {e:#}")))?
+            .map_err(|e| RunnerError::RuntimeUnavailable {
+                which: "persistence".to_string(),
+                detail: format!("load instance input: {e:#}"),
+            })?
             .ok_or_else(|| RunnerError::StartFailed(format!("instance {instance_id} not found")))?;
         Ok(instance.input.unwrap_or_else(|| b"{}".to_vec()))
     }
@@ -36,8 +36,14 @@ pub struct MockRunner {
     /// If true, detached instances will stay running indefinitely until explicitly stopped.
     /// This is useful for testing timeout enforcement.
     pub never_complete: bool,
+    /// If set, every detached launch fails with the error this returns.
+    /// This is useful for testing how start failures are reported.
+    pub launch_failure: Option<LaunchFailure>,
 }
 
+/// Builds the error a [`MockRunner`] launch fails with.
+pub type LaunchFailure = Arc<dyn Fn() -> RunnerError + Send + Sync>;
+
 impl Default for MockRunner {
     fn default() -> Self {
         Self::new()
@@ -53,6 +59,7 @@ impl MockRunner {
             execution_delay_ms: 10,
             fail_by_default: false,
             never_complete: false,
+            launch_failure: None,
         }
     }
 
@@ -64,6 +71,7 @@ impl MockRunner {
             execution_delay_ms: 10,
             fail_by_default: true,
             never_complete: false,
+            launch_failure: None,
         }
     }
 
@@ -77,6 +85,15 @@ impl MockRunner {
             execution_delay_ms: 0,
             fail_by_default: false,
             never_complete: true,
+            launch_failure: None,
+        }
+    }
+
+    /// Create a mock runner whose detached launches fail with `error()`.
+    pub fn failing_launch(error: impl Fn() -> RunnerError + Send + Sync + 'static) -> Self {
+        Self {
+            launch_failure: Some(Arc::new(error)),
+            ..Self::new()
         }
     }
 
@@ -159,6 +176,9 @@ impl Runner for MockRunner {
 
     async fn launch_detached(&self, options: &LaunchOptions) -> Result<RunnerHandle> {
         self.launch_count.fetch_add(1, Ordering::SeqCst);
+        if let Some(failure) = &self.launch_failure {
+            return Err(failure());
+        }
         let handle = RunnerHandle {
             handle_id: format!("mock_{}", &options.instance_id[..8]),
             instance_id: options.instance_id.clone(),
@@ -295,6 +315,61 @@ mod tests {
         assert!(result.error.is_some());
     }
 
+    #[tokio::test]
+    async fn test_mock_runner_launch_failure_classes() {
+        let cases: [(LaunchFailure, StartFailureReason); 5] = [
+            (
+                Arc::new(|| RunnerError::BinaryNotFound("/images/x.wasm".to_string())),
+                StartFailureReason::ImageBinaryMissing,
+            ),
+            (
+                Arc::new(|| {
+                    RunnerError::from_bundle_io(
+                        "create run directory",
+                        std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+                    )
+                }),
+                StartFailureReason::BundleCreationFailed,
+            ),
+            (
+                Arc::new(|| RunnerError::RuntimeUnavailable {
+                    which: "wasmtime".to_string(),
+                    detail: "engine gone".to_string(),
+                }),
+                StartFailureReason::RuntimeUnavailable,
+            ),
+            (
+                Arc::new(|| {
+                    RunnerError::from_bundle_io(
+                        "create run directory",
+                        std::io::Error::from(std::io::ErrorKind::StorageFull),
+                    )
+                }),
+                StartFailureReason::ResourceLimit,
+            ),
+            (
+                Arc::new(|| RunnerError::StartFailed("exit 139".to_string())),
+                StartFailureReason::Unknown,
+            ),
+        ];
+
+        for (failure, expected) in cases {
+            let runner = MockRunner {
+                launch_failure: Some(failure),
+                ..MockRunner::new()
+            };
+            let err = runner.launch_detached(&test_options()).await.unwrap_err();
+            assert_eq!(err.start_failure_reason(), expected, "{err}");
+            assert_eq!(
+                expected.is_retryable(),
+                !matches!(
+                    expected,
+                    StartFailureReason::ImageBinaryMissing | StartFailureReason::Unknown
+                )
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_mock_runner_cancellation() {
         let runner = MockRunner {
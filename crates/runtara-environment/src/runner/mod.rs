@@ -11,7 +11,7 @@ mod traits;
 
 pub use common::WorkflowRunnerConfig;
 pub use embedded::EmbeddedWasmRunner;
-pub use mock::MockRunner;
+pub use mock::{LaunchFailure, MockRunner};
 pub use traits::*;
 
 /// Build the workflow runner: the in-process embedded wasmtime engine.
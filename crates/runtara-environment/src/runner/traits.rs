@@ -40,6 +40,24 @@ pub enum RunnerError {
     #[error("Container start failed: {0}")]
     StartFailed(String),
 
+    /// The execution runtime on this node cannot run instances right now.
+    #[error("Runtime unavailable ({which}): {detail}")]
+    RuntimeUnavailable {
+        /// Which part of the runtime failed (`wasmtime`, `persistence`, ...).
+        which: String,
+        /// What went wrong.
+        detail: String,
+    },
+
+    /// A node resource ran out while preparing the instance.
+    #[error("Resource limit reached ({what}): {detail}")]
+    ResourceLimit {
+        /// Which resource (`disk`, `memory`, ...).
+        what: String,
+        /// What went wrong.
+        detail: String,
+    },
+
     /// Process exited with non-zero code.
     #[error("Exit code {exit_code}: {stderr}")]
     ExitCode {
@@ -69,6 +87,92 @@ pub enum RunnerError {
 /// Result type for runner operations.
 pub type Result<T> = std::result::Result<T, RunnerError>;
 
+/// Why an instance failed to start, as reported to `StartInstance` callers.
+///
+/// Separates problems with the image, which fail the same way on every node,
+/// from problems with the node, which another node may not have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StartFailureReason {
+    /// The image's binary is missing from disk.
+    ImageBinaryMissing,
+    /// The per-instance run directory or bundle could not be prepared.
+    BundleCreationFailed,
+    /// The execution runtime on this node is unusable.
+    RuntimeUnavailable,
+    /// A node resource (disk, memory) ran out.
+    ResourceLimit,
+    /// Anything the runner could not classify.
+    Unknown,
+}
+
+impl StartFailureReason {
+    /// Wire representation (`IMAGE_BINARY_MISSING`, ...).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ImageBinaryMissing => "IMAGE_BINARY_MISSING",
+            Self::BundleCreationFailed => "BUNDLE_CREATION_FAILED",
+            Self::RuntimeUnavailable => "RUNTIME_UNAVAILABLE",
+            Self::ResourceLimit => "RESOURCE_LIMIT",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// Whether the failure is the node's rather than the image's, so starting
+    /// the same image elsewhere (or later) may succeed.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            Self::BundleCreationFailed | Self::RuntimeUnavailable | Self::ResourceLimit
+        )
+    }
+}
+
+impl std::fmt::Display for StartFailureReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl RunnerError {
+    /// Classify this error as a start failure.
+    pub fn start_failure_reason(&self) -> StartFailureReason {
+        match self {
+            RunnerError::BinaryNotFound(_) | RunnerError::BundleNotFound(_) => {
+                StartFailureReason::ImageBinaryMissing
+            }
+            RunnerError::BundleCreation(_) => StartFailureReason::BundleCreationFailed,
+            RunnerError::RuntimeUnavailable { .. } => StartFailureReason::RuntimeUnavailable,
+            RunnerError::ResourceLimit { .. } => StartFailureReason::ResourceLimit,
+            RunnerError::Io(e) if is_resource_exhaustion(e) => StartFailureReason::ResourceLimit,
+            RunnerError::Io(_) => StartFailureReason::BundleCreationFailed,
+            _ => StartFailureReason::Unknown,
+        }
+    }
+
+    /// Wrap an I/O failure while preparing an instance's run directory,
+    /// keeping disk exhaustion apart from other bundle errors.
+    pub fn from_bundle_io(context: &str, e: std::io::Error) -> Self {
+        if is_resource_exhaustion(&e) {
+            RunnerError::ResourceLimit {
+                what: "disk".to_string(),
+                detail: format!("{context}: {e}"),
+            }
+        } else {
+            RunnerError::BundleCreation(format!("{context}: {e}"))
+        }
+    }
+}
+
+fn is_resource_exhaustion(e: &std::io::Error) -> bool {
+    matches!(
+        e.kind(),
+        std::io::ErrorKind::StorageFull
+            | std::io::ErrorKind::QuotaExceeded
+            | std::io::ErrorKind::OutOfMemory
+    )
+}
+
 /// Options for launching an instance.
 #[derive(Debug, Clone)]
 pub struct LaunchOptions {
@@ -28,6 +28,9 @@
 //! - A local cancelled flag mirrors `runtara_sdk::INSTANCE_CANCELLED` so
 //!   `is_cancelled` short-circuits after a consumed cancel/shutdown, exactly
 //!   like `runtara_sdk::is_cancelled()`.
+//! - Step debug events pass through a [`DebugSampler`] holding the instance's
+//!   debug event budget. Its state is persisted as a small checkpoint so a
+//!   resumed run continues the same budget instead of starting over.
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -36,12 +39,18 @@ use std::time::{Duration, Instant};
 use runtara_component_host::runtime_host::{
     RuntimeCheckpointResult, RuntimeCustomSignalInfo, RuntimeHost, RuntimeSignalInfo,
 };
+use runtara_core::debug_budget::{
+    DEBUG_EVENTS_SUPPRESSED_SUBTYPE, DEBUG_SAMPLER_CHECKPOINT_ID, DebugDecision, DebugSampler,
+    is_step_debug_subtype,
+};
 use runtara_core::instance_handlers::{
-    CheckpointRequest, GetCheckpointRequest, InstanceEvent, InstanceEventType,
-    InstanceHandlerState, PollSignalsRequest, RetryAttemptEvent, Signal, SignalAck, SignalType,
-    SleepRequest, handle_checkpoint, handle_get_checkpoint, handle_instance_event,
-    handle_poll_signals, handle_retry_attempt, handle_signal_ack, handle_sleep,
+    CheckpointRequest, CompactCheckpointsRequest, GetCheckpointRequest, InstanceEvent,
+    InstanceEventType, InstanceHandlerState, PollSignalsRequest, RetryAttemptEvent, Signal,
+    SignalAck, SignalType, SleepRequest, handle_checkpoint, handle_compact_checkpoints,
+    handle_get_checkpoint, handle_instance_event, handle_poll_signals, handle_retry_attempt,
+    handle_signal_ack, handle_sleep,
 };
+use runtara_core::maintenance::maintenance_retry_after;
 use runtara_core::persistence::Persistence;
 
 /// Default minimum interval between signal polls, mirroring the SDK's
@@ -51,6 +60,15 @@ use runtara_core::persistence::Persistence;
 /// hammering the HTTP API.
 const DEFAULT_SIGNAL_POLL_INTERVAL: Duration = Duration::from_millis(1000);
 
+/// Longest single pause while core is in maintenance, mirroring the SDK.
+const MAINTENANCE_POLL_CAP: Duration = Duration::from_secs(5);
+
+/// Stop waiting for maintenance to end after this long, mirroring the SDK.
+const MAINTENANCE_MAX_WAIT: Duration = Duration::from_secs(60 * 60);
+
+/// Persist the debug sampler at least once per this many step debug events.
+const DEBUG_SAMPLER_SAVE_INTERVAL: u32 = 256;
+
 /// Persistence-backed runtime host for one workflow instance run.
 pub struct PersistenceRuntimeHost {
     state: Arc<InstanceHandlerState>,
@@ -61,6 +79,11 @@ pub struct PersistenceRuntimeHost {
     /// Signal-poll rate limiter state (mirrors the SDK's `last_signal_poll`).
     last_signal_poll: std::sync::Mutex<Option<Instant>>,
     signal_poll_interval: Duration,
+    /// Budget for a sampler that has no persisted state yet.
+    debug_event_budget: u64,
+    /// Loaded on the first step debug event of the run.
+    debug_sampler: tokio::sync::OnceCell<std::sync::Mutex<DebugSampler>>,
+    debug_events_since_save: std::sync::atomic::AtomicU32,
 }
 
 impl PersistenceRuntimeHost {
@@ -73,6 +96,9 @@ impl PersistenceRuntimeHost {
             cancelled: AtomicBool::new(false),
             last_signal_poll: std::sync::Mutex::new(None),
             signal_poll_interval: DEFAULT_SIGNAL_POLL_INTERVAL,
+            debug_event_budget: 0,
+            debug_sampler: tokio::sync::OnceCell::new(),
+            debug_events_since_save: std::sync::atomic::AtomicU32::new(0),
         }
     }
 
@@ -95,10 +121,46 @@ impl PersistenceRuntimeHost {
         self
     }
 
+    /// Debug event budget for this instance (0 keeps the default).
+    pub fn with_debug_event_budget(mut self, budget: u64) -> Self {
+        self.debug_event_budget = budget;
+        self
+    }
+
     fn err(error: impl std::fmt::Display) -> String {
         error.to_string()
     }
 
+    /// Run a handler write, pausing and retrying while core is in
+    /// maintenance, as the SDK client does for guests.
+    async fn through_maintenance<T, F, Fut>(mut op: F) -> anyhow::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<T>>,
+    {
+        let started = Instant::now();
+        loop {
+            match op().await {
+                Err(e) if started.elapsed() < MAINTENANCE_MAX_WAIT => {
+                    let Some(retry_after) = maintenance_retry_after(&e) else {
+                        return Err(e);
+                    };
+                    tracing::info!(
+                        retry_after_ms = retry_after.as_millis() as u64,
+                        "Core in maintenance; waiting before retrying write"
+                    );
+                    tokio::time::sleep(
+                        retry_after
+                            .max(Duration::from_millis(1))
+                            .min(MAINTENANCE_POLL_CAP),
+                    )
+                    .await;
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// Rate-limited lifecycle-signal poll, mirroring `RuntaraSdk::poll_signal`:
     /// returns `None` without touching persistence when called again within
     /// the poll interval.
@@ -159,6 +221,7 @@ impl PersistenceRuntimeHost {
     /// The `sdk.suspended()` equivalent: record a suspended instance event
     /// (status transition guarded by `if_running` inside the handler).
     async fn suspended_event(&self) -> Result<(), String> {
+        self.flush_debug_sampler().await?;
         self.event(InstanceEventType::EventSuspended, None, Vec::new(), None)
             .await
     }
@@ -170,22 +233,133 @@ impl PersistenceRuntimeHost {
         payload: Vec<u8>,
         subtype: Option<String>,
     ) -> Result<(), String> {
-        handle_instance_event(
-            &self.state,
-            InstanceEvent {
-                instance_id: self.instance_id.clone(),
-                event_type: event_type as i32,
-                checkpoint_id,
-                payload,
-                timestamp_ms: chrono::Utc::now().timestamp_millis(),
-                subtype,
-            },
-        )
+        Self::through_maintenance(|| {
+            handle_instance_event(
+                &self.state,
+                InstanceEvent {
+                    instance_id: self.instance_id.clone(),
+                    event_type: event_type as i32,
+                    checkpoint_id: checkpoint_id.clone(),
+                    payload: payload.clone(),
+                    timestamp_ms: chrono::Utc::now().timestamp_millis(),
+                    subtype: subtype.clone(),
+                },
+            )
+        })
         .await
         .map(|_| ())
         .map_err(Self::err)
     }
 
+    async fn debug_sampler(&self) -> &std::sync::Mutex<DebugSampler> {
+        self.debug_sampler
+            .get_or_init(|| async {
+                let persisted = self
+                    .state
+                    .persistence
+                    .load_checkpoint(&self.instance_id, DEBUG_SAMPLER_CHECKPOINT_ID)
+                    .await
+                    .ok()
+                    .flatten()
+                    .and_then(|record| DebugSampler::from_bytes(&record.state));
+                std::sync::Mutex::new(
+                    persisted.unwrap_or_else(|| DebugSampler::new(self.debug_event_budget)),
+                )
+            })
+            .await
+    }
+
+    /// Run a step debug event through the sampler, emitting it (and any
+    /// suppressed-range summary) as the sampler decides.
+    async fn sampled_debug_event(&self, kind: String, payload: Vec<u8>) -> Result<(), String> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let (decision, snapshot) = {
+            let mut sampler = self
+                .debug_sampler()
+                .await
+                .lock()
+                .map_err(|e| format!("debug sampler poisoned: {e}"))?;
+            let decision = sampler.offer(&kind, &payload, now_ms);
+            let since_save = self.debug_events_since_save.fetch_add(1, Ordering::Relaxed) + 1;
+            let has_summary = matches!(
+                &decision,
+                DebugDecision::Emit { summary: Some(_) }
+                    | DebugDecision::Suppress { summary: Some(_) }
+            );
+            let snapshot = (has_summary || since_save >= DEBUG_SAMPLER_SAVE_INTERVAL)
+                .then(|| sampler.to_bytes());
+            (decision, snapshot)
+        };
+        if let Some(state) = snapshot {
+            self.save_debug_sampler(state).await;
+        }
+        let (emit, summary) = match decision {
+            DebugDecision::Emit { summary } => (true, summary),
+            DebugDecision::Suppress { summary } => (false, summary),
+        };
+        if let Some(summary) = summary {
+            self.suppressed_summary_event(summary).await?;
+        }
+        if emit {
+            self.event(InstanceEventType::EventCustom, None, payload, Some(kind))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Report any suppressed range still pending and persist the sampler,
+    /// before the run ends.
+    async fn flush_debug_sampler(&self) -> Result<(), String> {
+        let Some(sampler) = self.debug_sampler.get() else {
+            return Ok(());
+        };
+        let (summary, state) = {
+            let mut sampler = sampler
+                .lock()
+                .map_err(|e| format!("debug sampler poisoned: {e}"))?;
+            let summary = sampler.flush(chrono::Utc::now().timestamp_millis());
+            (summary, sampler.to_bytes())
+        };
+        self.save_debug_sampler(state).await;
+        if let Some(summary) = summary {
+            self.suppressed_summary_event(summary).await?;
+        }
+        Ok(())
+    }
+
+    async fn suppressed_summary_event(&self, summary: Vec<u8>) -> Result<(), String> {
+        self.event(
+            InstanceEventType::EventCustom,
+            None,
+            summary,
+            Some(DEBUG_EVENTS_SUPPRESSED_SUBTYPE.to_string()),
+        )
+        .await
+    }
+
+    /// Best-effort: a failed save only means a resumed run recounts some
+    /// of its budget.
+    async fn save_debug_sampler(&self, state: Vec<u8>) {
+        self.debug_events_since_save.store(0, Ordering::Relaxed);
+        let persistence = &self.state.persistence;
+        let result = async {
+            persistence
+                .delete_checkpoints_with_prefix(&self.instance_id, DEBUG_SAMPLER_CHECKPOINT_ID)
+                .await?;
+            persistence
+                .save_checkpoint(&self.instance_id, DEBUG_SAMPLER_CHECKPOINT_ID, &state)
+                .await
+        }
+        .await;
+        if let Err(error) = result {
+            tracing::warn!(
+                instance_id = %self.instance_id,
+                %error,
+                "failed to persist debug sampler state"
+            );
+        }
+    }
+
     /// Decode a handler-layer signal-type discriminant (the enum only
     /// implements the encoding direction).
     fn signal_type_of(value: i32) -> Option<SignalType> {
@@ -241,16 +415,21 @@ impl RuntimeHost for PersistenceRuntimeHost {
     }
 
     async fn complete(&self, output: Vec<u8>) -> Result<(), String> {
+        self.flush_debug_sampler().await?;
         self.event(InstanceEventType::EventCompleted, None, output, None)
             .await
     }
 
     async fn fail(&self, error: Vec<u8>) -> Result<(), String> {
+        self.flush_debug_sampler().await?;
         self.event(InstanceEventType::EventFailed, None, error, None)
             .await
     }
 
     async fn custom_event(&self, kind: String, payload: Vec<u8>) -> Result<(), String> {
+        if self.debug_mode && is_step_debug_subtype(&kind) {
+            return self.sampled_debug_event(kind, payload).await;
+        }
         // SDK wire shape: event_type "custom", subtype = kind.
         self.event(InstanceEventType::EventCustom, None, payload, Some(kind))
             .await
@@ -346,14 +525,20 @@ impl RuntimeHost for PersistenceRuntimeHost {
         checkpoint_id: String,
         state: Vec<u8>,
     ) -> Result<RuntimeCheckpointResult, String> {
-        let response = handle_checkpoint(
-            &self.state,
-            CheckpointRequest {
-                instance_id: self.instance_id.clone(),
-                checkpoint_id,
-                state,
-            },
-        )
+        let response = Self::through_maintenance(|| {
+            handle_checkpoint(
+                &self.state,
+                CheckpointRequest {
+                    instance_id: self.instance_id.clone(),
+                    checkpoint_id: checkpoint_id.clone(),
+                    state: state.clone(),
+                    // Guest state carries no hints; browsing sniffs it on read.
+                    content_type: None,
+                    shape: None,
+                    wait_keys: Vec::new(),
+                },
+            )
+        })
         .await
         .map_err(Self::err)?;
         Ok(RuntimeCheckpointResult {
@@ -369,6 +554,33 @@ impl RuntimeHost for PersistenceRuntimeHost {
         })
     }
 
+    async fn compact_checkpoints(&self, prefixes: Vec<u8>) -> Result<u64, String> {
+        // Mirrors the guest: debug runs keep every per-iteration checkpoint
+        // for time-travel inspection.
+        if self.debug_mode {
+            return Ok(0);
+        }
+        let prefixes: Vec<String> = serde_json::from_slice(&prefixes).map_err(|e| {
+            format!("checkpoint compaction prefixes must be a JSON string array: {e}")
+        })?;
+        let mut deleted = 0;
+        for prefix in prefixes {
+            deleted += Self::through_maintenance(|| {
+                handle_compact_checkpoints(
+                    &self.state,
+                    CompactCheckpointsRequest {
+                        instance_id: self.instance_id.clone(),
+                        prefix: prefix.clone(),
+                    },
+                )
+            })
+            .await
+            .map_err(Self::err)?
+            .deleted;
+        }
+        Ok(deleted)
+    }
+
     async fn handle_checkpoint_signal(&self, signal_type: String) -> Result<bool, String> {
         // Mirrors the guest runtime's checkpoint_signal_action dispatch.
         match signal_type.as_str() {
@@ -398,17 +610,19 @@ impl RuntimeHost for PersistenceRuntimeHost {
         attempt_number: u32,
         error_message: Option<String>,
     ) -> Result<(), String> {
-        handle_retry_attempt(
-            &self.state,
-            RetryAttemptEvent {
-                instance_id: self.instance_id.clone(),
-                checkpoint_id,
-                attempt_number,
-                timestamp_ms: chrono::Utc::now().timestamp_millis(),
-                error_message,
-                error_metadata: None,
-            },
-        )
+        Self::through_maintenance(|| {
+            handle_retry_attempt(
+                &self.state,
+                RetryAttemptEvent {
+                    instance_id: self.instance_id.clone(),
+                    checkpoint_id: checkpoint_id.clone(),
+                    attempt_number,
+                    timestamp_ms: chrono::Utc::now().timestamp_millis(),
+                    error_message: error_message.clone(),
+                    error_metadata: None,
+                },
+            )
+        })
         .await
         .map(|_| ())
         .map_err(Self::err)
@@ -420,15 +634,17 @@ impl RuntimeHost for PersistenceRuntimeHost {
         state: Vec<u8>,
         ms: u64,
     ) -> Result<(), String> {
-        handle_sleep(
-            &self.state,
-            SleepRequest {
-                instance_id: self.instance_id.clone(),
-                duration_ms: ms,
-                checkpoint_id,
-                state,
-            },
-        )
+        Self::through_maintenance(|| {
+            handle_sleep(
+                &self.state,
+                SleepRequest {
+                    instance_id: self.instance_id.clone(),
+                    duration_ms: ms,
+                    checkpoint_id: checkpoint_id.clone(),
+                    state: state.clone(),
+                },
+            )
+        })
         .await
         .map(|_| ())
         .map_err(Self::err)
@@ -510,6 +726,28 @@ mod tests {
         assert_eq!(host.get_checkpoint("absent".into()).await.unwrap(), None);
     }
 
+    #[tokio::test]
+    async fn checkpoint_waits_out_maintenance_window() {
+        let (persistence, _host, _dir) = setup().await;
+        let mut state = InstanceHandlerState::new(Arc::clone(&persistence));
+        state.maintenance = runtara_core::maintenance::MaintenanceMode::new();
+        let maintenance = state.maintenance.clone();
+        let host = PersistenceRuntimeHost::new(Arc::new(state), INSTANCE.to_string(), false);
+
+        maintenance.set(true, Some(Duration::from_millis(50)), "failover");
+        let started = Instant::now();
+        let result = host
+            .checkpoint("cp-maint".into(), b"state".to_vec())
+            .await
+            .unwrap();
+        assert!(!result.found);
+        assert!(started.elapsed() >= Duration::from_millis(40));
+        assert_eq!(
+            host.get_checkpoint("cp-maint".into()).await.unwrap(),
+            Some(b"state".to_vec())
+        );
+    }
+
     #[tokio::test]
     async fn empty_state_checkpoint_is_a_read_only_probe() {
         let (_p, host, _dir) = setup().await;
@@ -530,6 +768,35 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn compact_checkpoints_deletes_prefixed_rows_unless_debug() {
+        let (p, host, _dir) = setup().await;
+        for checkpoint_id in ["wf::split::s::[0]", "wf::split::s::[1]", "wf::split::s"] {
+            host.checkpoint(checkpoint_id.into(), b"state".to_vec())
+                .await
+                .unwrap();
+        }
+        let prefixes = br#"["wf::split::s::["]"#.to_vec();
+
+        let debug_host =
+            PersistenceRuntimeHost::from_persistence(Arc::clone(&p), INSTANCE.to_string(), true);
+        assert_eq!(
+            debug_host
+                .compact_checkpoints(prefixes.clone())
+                .await
+                .unwrap(),
+            0,
+            "debug mode keeps per-iteration checkpoints"
+        );
+
+        assert_eq!(host.compact_checkpoints(prefixes.clone()).await.unwrap(), 2);
+        assert_eq!(host.compact_checkpoints(prefixes).await.unwrap(), 0);
+        assert_eq!(
+            host.get_checkpoint("wf::split::s".into()).await.unwrap(),
+            Some(b"state".to_vec())
+        );
+    }
+
     #[tokio::test]
     async fn custom_signal_poll_is_idempotent_rereads() {
         let (p, host, _dir) = setup().await;
@@ -711,4 +978,63 @@ mod tests {
         assert!(!host.is_cancelled().await.unwrap());
         assert!(p.get_pending_signal(INSTANCE).await.unwrap().is_some());
     }
+
+    async fn emit_debug_pair(host: &PersistenceRuntimeHost, i: usize) {
+        let start = serde_json::json!({"step_id": "item", "scope_id": format!("s{i}")});
+        let end = serde_json::json!({
+            "step_id": "item",
+            "scope_id": format!("s{i}"),
+            "outputs": {"ok": true},
+        });
+        host.custom_event("step_debug_start".into(), start.to_string().into_bytes())
+            .await
+            .unwrap();
+        host.custom_event("step_debug_end".into(), end.to_string().into_bytes())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn debug_events_are_sampled_past_the_budget_and_survive_resume() {
+        let (p, _host, _dir) = setup().await;
+        let debug_host = || {
+            PersistenceRuntimeHost::from_persistence(Arc::clone(&p), INSTANCE.to_string(), true)
+                .with_debug_event_budget(10)
+        };
+        let count = |subtype: &'static str| {
+            let p = Arc::clone(&p);
+            async move {
+                let filter = runtara_core::persistence::ListEventsFilter {
+                    subtype: Some(subtype.to_string()),
+                    ..Default::default()
+                };
+                p.count_events(INSTANCE, &filter).await.unwrap()
+            }
+        };
+
+        let host = debug_host();
+        for i in 0..200 {
+            emit_debug_pair(&host, i).await;
+        }
+        host.breakpoint_pause().await.unwrap();
+        let starts = count("step_debug_start").await;
+        assert_eq!(starts, count("step_debug_end").await, "pairs stay matched");
+        assert!((5..40).contains(&starts), "kept {starts} of 200 starts");
+        assert!(count(DEBUG_EVENTS_SUPPRESSED_SUBTYPE).await >= 1);
+
+        // A resumed run continues the persisted budget rather than starting
+        // over with full fidelity.
+        let persisted = p
+            .load_checkpoint(INSTANCE, DEBUG_SAMPLER_CHECKPOINT_ID)
+            .await
+            .unwrap()
+            .expect("sampler state persisted");
+        let sampler = DebugSampler::from_bytes(&persisted.state).unwrap();
+        assert_eq!(sampler.emitted() as i64, starts * 2);
+        let resumed = debug_host();
+        for i in 200..210 {
+            emit_debug_pair(&resumed, i).await;
+        }
+        assert!(count("step_debug_start").await < starts + 10);
+    }
 }
@@ -0,0 +1,1322 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Control-flow simulation of a workflow definition.
+//!
+//! Walks a workflow's execution graph in-process, with every side-effecting
+//! step (Agent, AiAgent, EmbedWorkflow, WaitForSignal, ...) replaced by a
+//! caller-supplied stub output, so authors can see which path a given input
+//! takes without deploying. Mappings, conditions and Switch cases are
+//! evaluated by the workflow stdlib — the same evaluators the emitted workflow
+//! calls — so a predicted branch matches the real one. Nothing touches core,
+//! checkpoints or containers.
+//!
+//! Simulation is bounded by [`SimulationLimits`]: a wall-clock deadline, a
+//! cap on executed steps, and a budget on the bytes of resolved inputs and
+//! outputs kept in the trace.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use runtara_dsl::{ExecutionGraph, ExecutionPlanEdge, Step};
+use runtara_workflow_stdlib::direct_json::{
+    evaluate_condition, evaluate_input_mapping, evaluate_mapping_value, evaluate_switch,
+};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+/// Default wall-clock budget for one simulation.
+pub const DEFAULT_SIMULATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Upper bound a caller may raise the wall-clock budget to.
+pub const MAX_SIMULATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default cap on executed steps, counting every Split/While iteration.
+pub const DEFAULT_MAX_SIMULATED_STEPS: usize = 10_000;
+
+/// Default budget for the serialized size of the recorded trace.
+pub const DEFAULT_MAX_SIMULATION_BYTES: usize = 16 * 1024 * 1024;
+
+/// Resource limits for one simulation.
+#[derive(Debug, Clone, Copy)]
+pub struct SimulationLimits {
+    /// Wall-clock budget.
+    pub timeout: Duration,
+    /// Maximum number of executed steps.
+    pub max_steps: usize,
+    /// Maximum serialized size of recorded inputs and outputs.
+    pub max_bytes: usize,
+}
+
+impl Default for SimulationLimits {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_SIMULATION_TIMEOUT,
+            max_steps: DEFAULT_MAX_SIMULATED_STEPS,
+            max_bytes: DEFAULT_MAX_SIMULATION_BYTES,
+        }
+    }
+}
+
+/// A simulation that could not run to a verdict.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SimulationError {
+    /// The scenario JSON is not a valid workflow definition.
+    #[error("invalid scenario: {0}")]
+    InvalidScenario(String),
+    /// The wall-clock budget ran out.
+    #[error("simulation exceeded its {0} ms time limit")]
+    TimedOut(u128),
+    /// More steps executed than the step cap allows.
+    #[error("simulation exceeded its limit of {0} executed steps")]
+    StepLimitExceeded(usize),
+    /// The recorded trace grew past the byte budget.
+    #[error("simulation exceeded its memory limit of {0} bytes")]
+    MemoryLimitExceeded(usize),
+}
+
+/// One evaluated routing decision.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BranchDecision {
+    /// What was decided: `condition` (Conditional step), `switch`, `edge`
+    /// (conditioned execution-plan edge) or `while` (loop check).
+    pub kind: String,
+    /// The evaluated value: the boolean result of a condition, or the
+    /// switched-on value of a Switch.
+    pub value: Value,
+    /// Selected label: `true`/`false`, a Switch route, `continue`/`exit`/
+    /// `max_iterations` for While.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Edge target the decision concerns.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_step: Option<String>,
+}
+
+/// One step the simulated run executed, in execution order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimulatedStep {
+    /// Step ID.
+    pub step_id: String,
+    /// Step type (`Agent`, `Conditional`, ...).
+    pub step_type: String,
+    /// Indices of the enclosing Split/While iterations, outermost first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub loop_indices: Vec<u32>,
+    /// Resolved step inputs (`null` for steps without input mappings).
+    pub inputs: Value,
+    /// Step output as later steps see it under `steps.<id>.outputs`.
+    pub output: Value,
+    /// Whether the output came from the caller's stubs.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stubbed: bool,
+    /// Routing decisions taken at this step.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub decisions: Vec<BranchDecision>,
+}
+
+/// Failure the simulated run ended with.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimulatedFailure {
+    /// Step that failed.
+    pub step_id: String,
+    /// Error code (`Error` step code, or `EVALUATION_ERROR`).
+    pub code: String,
+    /// Human-readable reason.
+    pub message: String,
+}
+
+/// Predicted execution of a scenario.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimulationResult {
+    /// Executed steps in order.
+    pub steps: Vec<SimulatedStep>,
+    /// Workflow output from the reached Finish step.
+    pub output: Option<Value>,
+    /// Set when the run ends in an unhandled failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<SimulatedFailure>,
+}
+
+/// Parse a scenario: either a full workflow (`{"executionGraph": ...}`) or a
+/// bare execution graph.
+pub fn parse_scenario(scenario: &Value) -> Result<ExecutionGraph, SimulationError> {
+    let graph = if scenario.get("executionGraph").is_some() {
+        runtara_dsl::parse_workflow(scenario).map(|workflow| workflow.execution_graph)
+    } else {
+        runtara_dsl::parse_execution_graph(scenario)
+    };
+    graph.map_err(SimulationError::InvalidScenario)
+}
+
+/// Simulate `graph` for `input`, taking side-effecting step outputs from
+/// `stubs` (step ID -> output). A step without a stub outputs `null`.
+///
+/// `input` follows the start-instance envelope: `{"data": ..., "variables":
+/// ...}` where runtime variables override declared ones, or bare data.
+pub fn simulate(
+    graph: &ExecutionGraph,
+    input: &Value,
+    stubs: &HashMap<String, Value>,
+    limits: SimulationLimits,
+) -> Result<SimulationResult, SimulationError> {
+    if !graph.steps.contains_key(&graph.entry_point) {
+        return Err(SimulationError::InvalidScenario(format!(
+            "entry point '{}' is not a step",
+            graph.entry_point
+        )));
+    }
+
+    let mut variables = declared_variables(graph);
+    let data = match input {
+        Value::Object(envelope) if envelope.contains_key("data") => {
+            if let Some(Value::Object(runtime)) = envelope.get("variables") {
+                for (key, value) in runtime {
+                    if !key.starts_with('_') {
+                        variables.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            envelope.get("data").cloned().unwrap_or(Value::Null)
+        }
+        other => other.clone(),
+    };
+
+    let mut simulator = Simulator {
+        stubs,
+        limits,
+        deadline: Instant::now() + limits.timeout,
+        bytes: 0,
+        steps: Vec::new(),
+    };
+    let workflow_inputs = json!({ "data": data, "variables": Value::Object(variables.clone()) });
+    let scope = Scope {
+        data,
+        variables,
+        workflow_inputs,
+        loop_indices: Vec::new(),
+    };
+    let outcome = simulator.run_graph(graph, scope)?;
+    let (output, failure) = match outcome {
+        GraphOutcome::Finished(output) => (Some(output), None),
+        GraphOutcome::Completed => (None, None),
+        GraphOutcome::Failed(failure) => (None, Some(failure)),
+    };
+    Ok(SimulationResult {
+        steps: simulator.steps,
+        output,
+        failure,
+    })
+}
+
+fn declared_variables(graph: &ExecutionGraph) -> Map<String, Value> {
+    graph
+        .variables
+        .iter()
+        .map(|(name, variable)| (name.clone(), variable.value.clone()))
+        .collect()
+}
+
+/// Data visible to one (sub)graph run.
+struct Scope {
+    data: Value,
+    variables: Map<String, Value>,
+    workflow_inputs: Value,
+    loop_indices: Vec<u32>,
+}
+
+enum GraphOutcome {
+    /// A Finish step ran; carries its output.
+    Finished(Value),
+    /// The graph ran out of steps without reaching Finish.
+    Completed,
+    /// A step failed and no `onError` edge handled it.
+    Failed(SimulatedFailure),
+}
+
+enum StepOutcome {
+    /// Continue along the selected edges.
+    Next(Vec<String>),
+    Finished(Value),
+    Failed(SimulatedFailure),
+}
+
+struct Simulator<'a> {
+    stubs: &'a HashMap<String, Value>,
+    limits: SimulationLimits,
+    deadline: Instant,
+    bytes: usize,
+    steps: Vec<SimulatedStep>,
+}
+
+impl Simulator<'_> {
+    fn run_graph(
+        &mut self,
+        graph: &ExecutionGraph,
+        mut scope: Scope,
+    ) -> Result<GraphOutcome, SimulationError> {
+        let mut steps_ctx = Map::new();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([graph.entry_point.clone()]);
+
+        while let Some(step_id) = queue.pop_front() {
+            if visited.contains(&step_id) {
+                continue;
+            }
+            // A join waits until no other pending branch can still reach it,
+            // mirroring the topological linearisation of fan-out.
+            if queue
+                .iter()
+                .any(|other| other != &step_id && reaches(graph, other, &step_id))
+            {
+                queue.push_back(step_id);
+                continue;
+            }
+            visited.insert(step_id.clone());
+
+            let Some(step) = graph.steps.get(&step_id) else {
+                return Err(SimulationError::InvalidScenario(format!(
+                    "execution plan references unknown step '{step_id}'"
+                )));
+            };
+            match self.run_step(graph, step, &mut scope, &mut steps_ctx)? {
+                StepOutcome::Next(next) => queue.extend(next),
+                StepOutcome::Finished(output) => return Ok(GraphOutcome::Finished(output)),
+                StepOutcome::Failed(failure) => return Ok(GraphOutcome::Failed(failure)),
+            }
+        }
+        Ok(GraphOutcome::Completed)
+    }
+
+    fn run_step(
+        &mut self,
+        graph: &ExecutionGraph,
+        step: &Step,
+        scope: &mut Scope,
+        steps_ctx: &mut Map<String, Value>,
+    ) -> Result<StepOutcome, SimulationError> {
+        self.check_limits()?;
+
+        let step_id = step_id(step).to_string();
+        let source = build_source(scope, steps_ctx);
+        // Reserve the step's place so a Split/While precedes its iterations.
+        let slot = self.steps.len();
+        let mut record = SimulatedStep {
+            step_id: step_id.clone(),
+            step_type: step_type_name(step).to_string(),
+            loop_indices: scope.loop_indices.clone(),
+            inputs: Value::Null,
+            output: Value::Null,
+            stubbed: false,
+            decisions: Vec::new(),
+        };
+
+        let evaluated = self.evaluate_step(step, scope, &source, &mut record);
+        let (label, route) = match evaluated {
+            Ok(Evaluated::Continue { label, route }) => (label, route),
+            Ok(Evaluated::Finish) => {
+                let output = record.output.clone();
+                self.record(slot, record)?;
+                return Ok(StepOutcome::Finished(output));
+            }
+            Ok(Evaluated::Fail(failure)) => {
+                self.record(slot, record)?;
+                return Ok(StepOutcome::Failed(failure));
+            }
+            Err(Interrupt::Limit(err)) => return Err(err),
+            Err(Interrupt::Failed(failure)) => {
+                return self.route_error(graph, scope, steps_ctx, slot, record, failure);
+            }
+        };
+
+        // A SetVariable's assignments are visible to every later step.
+        if let (Step::SetVariable(_), Value::Object(assigned)) = (step, &record.output) {
+            scope.variables.extend(assigned.clone());
+        }
+        steps_ctx.insert(
+            step_id.clone(),
+            step_envelope(step, record.output.clone(), route.as_deref()),
+        );
+        let source = build_source(scope, steps_ctx);
+        let next = select_edges(graph, &step_id, label.as_deref(), &source, &mut record);
+        self.record(slot, record)?;
+        match next {
+            Ok(next) => Ok(StepOutcome::Next(next)),
+            Err(message) => Ok(StepOutcome::Failed(evaluation_failure(&step_id, message))),
+        }
+    }
+
+    /// Follow `onError` edges for a failed step, or propagate the failure.
+    fn route_error(
+        &mut self,
+        graph: &ExecutionGraph,
+        scope: &Scope,
+        steps_ctx: &mut Map<String, Value>,
+        slot: usize,
+        mut record: SimulatedStep,
+        failure: SimulatedFailure,
+    ) -> Result<StepOutcome, SimulationError> {
+        let step_id = record.step_id.clone();
+        let has_handler = graph
+            .execution_plan
+            .iter()
+            .any(|edge| edge.from_step == step_id && edge.label.as_deref() == Some("onError"));
+        if !has_handler {
+            self.record(slot, record)?;
+            return Ok(StepOutcome::Failed(failure));
+        }
+
+        let error = json!({
+            "stepId": failure.step_id,
+            "code": failure.code,
+            "message": failure.message,
+            "category": "permanent",
+            "severity": "error",
+        });
+        steps_ctx.insert("__error".to_string(), error.clone());
+        steps_ctx.insert("error".to_string(), error);
+        let source = build_source(scope, steps_ctx);
+        let next = select_edges(graph, &step_id, Some("onError"), &source, &mut record);
+        self.record(slot, record)?;
+        match next {
+            Ok(next) => Ok(StepOutcome::Next(next)),
+            Err(message) => Ok(StepOutcome::Failed(evaluation_failure(&step_id, message))),
+        }
+    }
+
+    fn evaluate_step(
+        &mut self,
+        step: &Step,
+        scope: &Scope,
+        source: &Value,
+        record: &mut SimulatedStep,
+    ) -> Result<Evaluated, Interrupt> {
+        let step_id = record.step_id.clone();
+        let fail = |message: String| Interrupt::Failed(evaluation_failure(&step_id, message));
+
+        match step {
+            Step::Finish(finish) => {
+                let output = match &finish.input_mapping {
+                    Some(mapping) => {
+                        evaluate_input_mapping(&to_json(mapping), source).map_err(fail)?
+                    }
+                    None => Value::Object(Map::new()),
+                };
+                record.inputs = output.clone();
+                record.output = output;
+                Ok(Evaluated::Finish)
+            }
+            Step::Conditional(conditional) => {
+                let result =
+                    evaluate_condition(&to_json(&conditional.condition), source).map_err(fail)?;
+                let label = result.to_string();
+                record.output = json!({ "result": result });
+                record.decisions.push(BranchDecision {
+                    kind: "condition".to_string(),
+                    value: Value::Bool(result),
+                    label: Some(label.clone()),
+                    to_step: None,
+                });
+                Ok(Evaluated::Continue {
+                    label: Some(label),
+                    route: None,
+                })
+            }
+            Step::Switch(switch) => {
+                let Some(config) = &switch.config else {
+                    return Ok(Evaluated::Continue {
+                        label: None,
+                        route: None,
+                    });
+                };
+                let config_json = to_json(config);
+                let switched_on =
+                    evaluate_mapping_value(&to_json(&config.value), source).map_err(fail)?;
+                let (output, route) = evaluate_switch(&config_json, source).map_err(fail)?;
+                record.inputs = json!({ "value": switched_on });
+                record.output = output;
+                record.decisions.push(BranchDecision {
+                    kind: "switch".to_string(),
+                    value: switched_on,
+                    label: Some(route.clone()),
+                    to_step: None,
+                });
+                if config.is_routing() {
+                    Ok(Evaluated::Continue {
+                        label: Some(route.clone()),
+                        route: Some(route),
+                    })
+                } else {
+                    Ok(Evaluated::Continue {
+                        label: None,
+                        route: None,
+                    })
+                }
+            }
+            Step::Split(split) => self.evaluate_split(split, scope, source, record),
+            Step::While(while_step) => self.evaluate_while(while_step, scope, source, record),
+            Step::Error(error) => {
+                if let Some(context) = &error.context {
+                    record.inputs =
+                        evaluate_input_mapping(&to_json(context), source).map_err(fail)?;
+                }
+                Ok(Evaluated::Fail(SimulatedFailure {
+                    step_id: step_id.clone(),
+                    code: error.code.clone(),
+                    message: error.message.clone(),
+                }))
+            }
+            Step::Agent(agent) => {
+                if let Some(mapping) = &agent.input_mapping {
+                    record.inputs =
+                        evaluate_input_mapping(&to_json(mapping), source).map_err(fail)?;
+                }
+                self.apply_stub(record);
+                Ok(Evaluated::next())
+            }
+            Step::EmbedWorkflow(embed) => {
+                if let Some(mapping) = &embed.input_mapping {
+                    record.inputs =
+                        evaluate_input_mapping(&to_json(mapping), source).map_err(fail)?;
+                }
+                self.apply_stub(record);
+                Ok(Evaluated::next())
+            }
+            Step::Log(log) => {
+                if let Some(context) = &log.context {
+                    record.inputs =
+                        evaluate_input_mapping(&to_json(context), source).map_err(fail)?;
+                }
+                self.apply_stub(record);
+                Ok(Evaluated::next())
+            }
+            Step::SetVariable(set_variable) => {
+                let values =
+                    evaluate_input_mapping(&to_json(&set_variable.values), source).map_err(fail)?;
+                record.inputs = values.clone();
+                record.output = values;
+                Ok(Evaluated::next())
+            }
+            Step::AiAgent(_)
+            | Step::Filter(_)
+            | Step::GroupBy(_)
+            | Step::Delay(_)
+            | Step::WaitForSignal(_) => {
+                self.apply_stub(record);
+                Ok(Evaluated::next())
+            }
+        }
+    }
+
+    fn evaluate_split(
+        &mut self,
+        split: &runtara_dsl::SplitStep,
+        scope: &Scope,
+        source: &Value,
+        record: &mut SimulatedStep,
+    ) -> Result<Evaluated, Interrupt> {
+        let step_id = record.step_id.clone();
+        let fail = |message: String| Interrupt::Failed(evaluation_failure(&step_id, message));
+        let Some(config) = &split.config else {
+            return Err(fail("Split step config missing value".to_string()));
+        };
+
+        let value = evaluate_mapping_value(&to_json(&config.value), source).map_err(fail)?;
+        let mut items = match value {
+            Value::Array(items) => items,
+            Value::Null if config.allow_null.unwrap_or(false) => Vec::new(),
+            Value::Null => return Err(fail("Split value is null".to_string())),
+            other if config.convert_single_value.unwrap_or(false) => vec![other],
+            _ => return Err(fail("Split value is not an array".to_string())),
+        };
+        if let Some(batch_size) = config.batch_size.filter(|size| *size > 0) {
+            items = items
+                .chunks(batch_size as usize)
+                .map(|chunk| Value::Array(chunk.to_vec()))
+                .collect();
+        }
+        let extra_variables = match &config.variables {
+            Some(mapping) => evaluate_input_mapping(&to_json(mapping), source).map_err(fail)?,
+            None => Value::Null,
+        };
+        record.inputs = json!({ "value": items, "variables": extra_variables });
+
+        let dont_stop = config.dont_stop_on_failed.unwrap_or(false);
+        let mut results = Vec::with_capacity(items.len());
+        for (index, item) in items.into_iter().enumerate() {
+            let index = index as u32;
+            let mut variables = scope.variables.clone();
+            if let Value::Object(extra) = &extra_variables {
+                variables.extend(extra.clone());
+            }
+            variables.insert("_item".to_string(), item.clone());
+            variables.insert("_index".to_string(), json!(index));
+            let mut loop_indices = scope.loop_indices.clone();
+            loop_indices.push(index);
+            variables.insert("_loop_indices".to_string(), json!(loop_indices));
+            let iteration = Scope {
+                data: item,
+                variables,
+                workflow_inputs: scope.workflow_inputs.clone(),
+                loop_indices,
+            };
+            match self
+                .run_graph(&split.subgraph, iteration)
+                .map_err(Interrupt::Limit)?
+            {
+                GraphOutcome::Finished(output) => results.push(output),
+                GraphOutcome::Completed => results.push(Value::Null),
+                GraphOutcome::Failed(_) if dont_stop => {}
+                GraphOutcome::Failed(failure) => return Err(Interrupt::Failed(failure)),
+            }
+        }
+        record.output = Value::Array(results);
+        Ok(Evaluated::next())
+    }
+
+    fn evaluate_while(
+        &mut self,
+        while_step: &runtara_dsl::WhileStep,
+        scope: &Scope,
+        source: &Value,
+        record: &mut SimulatedStep,
+    ) -> Result<Evaluated, Interrupt> {
+        let step_id = record.step_id.clone();
+        let fail = |message: String| Interrupt::Failed(evaluation_failure(&step_id, message));
+        let config = while_step.config.clone().unwrap_or_default();
+        let max_iterations = config.max_iterations.unwrap_or(10);
+        let extra_variables = match &config.variables {
+            Some(mapping) => evaluate_input_mapping(&to_json(mapping), source).map_err(fail)?,
+            None => Value::Null,
+        };
+        record.inputs = json!({ "maxIterations": max_iterations, "variables": extra_variables });
+
+        let condition = to_json(&while_step.condition);
+        let mut index = 0u32;
+        let mut outputs = Value::Null;
+        loop {
+            let loop_ctx = json!({ "index": index, "outputs": outputs });
+            let mut loop_indices = scope.loop_indices.clone();
+            loop_indices.push(index);
+            let mut condition_source = source.clone();
+            if let Value::Object(map) = &mut condition_source {
+                map.insert("loop".to_string(), loop_ctx.clone());
+            }
+            let keep_going = evaluate_condition(&condition, &condition_source).map_err(fail)?;
+            let label = if !keep_going {
+                "exit"
+            } else if index >= max_iterations {
+                "max_iterations"
+            } else {
+                "continue"
+            };
+            record.decisions.push(BranchDecision {
+                kind: "while".to_string(),
+                value: Value::Bool(keep_going),
+                label: Some(label.to_string()),
+                to_step: None,
+            });
+            if label != "continue" {
+                break;
+            }
+
+            let mut variables = scope.variables.clone();
+            if let Value::Object(extra) = &extra_variables {
+                variables.extend(extra.clone());
+            }
+            variables.insert("_index".to_string(), json!(index));
+            variables.insert("_loop_indices".to_string(), json!(loop_indices));
+            variables.insert("_loop".to_string(), loop_ctx);
+            if !outputs.is_null() {
+                variables.insert("_previousOutputs".to_string(), outputs.clone());
+            }
+            let iteration = Scope {
+                data: scope.data.clone(),
+                variables,
+                workflow_inputs: scope.workflow_inputs.clone(),
+                loop_indices,
+            };
+            outputs = match self
+                .run_graph(&while_step.subgraph, iteration)
+                .map_err(Interrupt::Limit)?
+            {
+                GraphOutcome::Finished(output) => output,
+                GraphOutcome::Completed => Value::Null,
+                GraphOutcome::Failed(failure) => return Err(Interrupt::Failed(failure)),
+            };
+            index += 1;
+        }
+        record.output = json!({ "iterations": index, "outputs": outputs });
+        Ok(Evaluated::next())
+    }
+
+    fn apply_stub(&self, record: &mut SimulatedStep) {
+        if let Some(stub) = self.stubs.get(&record.step_id) {
+            record.output = stub.clone();
+            record.stubbed = true;
+        }
+    }
+
+    fn check_limits(&self) -> Result<(), SimulationError> {
+        if Instant::now() >= self.deadline {
+            return Err(SimulationError::TimedOut(self.limits.timeout.as_millis()));
+        }
+        if self.steps.len() >= self.limits.max_steps {
+            return Err(SimulationError::StepLimitExceeded(self.limits.max_steps));
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, slot: usize, step: SimulatedStep) -> Result<(), SimulationError> {
+        self.bytes += json_size(&step.inputs) + json_size(&step.output);
+        if self.bytes > self.limits.max_bytes {
+            return Err(SimulationError::MemoryLimitExceeded(self.limits.max_bytes));
+        }
+        self.steps.insert(slot, step);
+        Ok(())
+    }
+}
+
+enum Evaluated {
+    Continue {
+        /// Edge label to follow (`None` for normal flow).
+        label: Option<String>,
+        /// Route stored on the step envelope for routing Switches.
+        route: Option<String>,
+    },
+    Finish,
+    Fail(SimulatedFailure),
+}
+
+impl Evaluated {
+    fn next() -> Self {
+        Self::Continue {
+            label: None,
+            route: None,
+        }
+    }
+}
+
+enum Interrupt {
+    /// A simulation limit tripped; aborts the whole run.
+    Limit(SimulationError),
+    /// The step failed; eligible for `onError` routing.
+    Failed(SimulatedFailure),
+}
+
+/// Pick the successors of `from_step` for `label`.
+///
+/// Conditioned edges are checked by descending priority and the first match
+/// wins; otherwise every unconditioned edge is taken (a fan-out when there
+/// are several).
+fn select_edges(
+    graph: &ExecutionGraph,
+    from_step: &str,
+    label: Option<&str>,
+    source: &Value,
+    record: &mut SimulatedStep,
+) -> Result<Vec<String>, String> {
+    let candidates: Vec<&ExecutionPlanEdge> = graph
+        .execution_plan
+        .iter()
+        .filter(|edge| edge.from_step == from_step)
+        .filter(|edge| match (label, edge.label.as_deref()) {
+            (Some(wanted), Some(actual)) => wanted == actual,
+            (None, None) | (None, Some("")) => true,
+            _ => false,
+        })
+        .collect();
+
+    let mut conditioned: Vec<&ExecutionPlanEdge> = candidates
+        .iter()
+        .copied()
+        .filter(|edge| edge.condition.is_some())
+        .collect();
+    conditioned.sort_by_key(|edge| -i64::from(edge.priority.unwrap_or(0)));
+    for edge in conditioned {
+        let condition = edge.condition.as_ref().map(to_json).unwrap_or(Value::Null);
+        let matched = evaluate_condition(&condition, source)?;
+        record.decisions.push(BranchDecision {
+            kind: "edge".to_string(),
+            value: Value::Bool(matched),
+            label: edge.label.clone(),
+            to_step: Some(edge.to_step.clone()),
+        });
+        if matched {
+            return Ok(vec![edge.to_step.clone()]);
+        }
+    }
+
+    Ok(candidates
+        .into_iter()
+        .filter(|edge| edge.condition.is_none())
+        .map(|edge| edge.to_step.clone())
+        .collect())
+}
+
+/// Whether `to` is reachable from `from` along any execution-plan edge.
+fn reaches(graph: &ExecutionGraph, from: &str, to: &str) -> bool {
+    let mut seen = HashSet::new();
+    let mut stack = vec![from];
+    while let Some(current) = stack.pop() {
+        if !seen.insert(current) {
+            continue;
+        }
+        for edge in graph
+            .execution_plan
+            .iter()
+            .filter(|edge| edge.from_step == current)
+        {
+            if edge.to_step == to {
+                return true;
+            }
+            stack.push(edge.to_step.as_str());
+        }
+    }
+    false
+}
+
+/// The source envelope mappings resolve against, shaped like the stdlib's
+/// `build_source` output.
+fn build_source(scope: &Scope, steps_ctx: &Map<String, Value>) -> Value {
+    let mut source = Map::new();
+    source.insert("data".to_string(), scope.data.clone());
+    source.insert(
+        "variables".to_string(),
+        Value::Object(scope.variables.clone()),
+    );
+    source.insert("steps".to_string(), Value::Object(steps_ctx.clone()));
+    source.insert(
+        "workflow".to_string(),
+        json!({ "inputs": scope.workflow_inputs }),
+    );
+    if let Some(error) = steps_ctx.get("__error") {
+        source.insert("__error".to_string(), error.clone());
+        source.insert("error".to_string(), error.clone());
+    }
+    if let Some(index) = scope.loop_indices.last() {
+        source.insert(
+            "iteration".to_string(),
+            json!({
+                "index": index,
+                "indices": scope.loop_indices,
+                "item": scope.variables.get("_item").cloned().unwrap_or(Value::Null),
+            }),
+        );
+    }
+    if let Some(loop_ctx) = scope.variables.get("_loop") {
+        source.insert("loop".to_string(), loop_ctx.clone());
+    }
+    if let Some(item) = scope.variables.get("_item") {
+        source.insert("item".to_string(), item.clone());
+    }
+    Value::Object(source)
+}
+
+fn step_envelope(step: &Step, output: Value, route: Option<&str>) -> Value {
+    let mut envelope = json!({
+        "stepId": step_id(step),
+        "stepName": step_name(step).unwrap_or("Unnamed"),
+        "stepType": step_type_name(step),
+        "outputs": output,
+    });
+    if let (Some(route), Some(map)) = (route, envelope.as_object_mut()) {
+        map.insert("route".to_string(), Value::String(route.to_string()));
+    }
+    envelope
+}
+
+fn evaluation_failure(step_id: &str, message: String) -> SimulatedFailure {
+    SimulatedFailure {
+        step_id: step_id.to_string(),
+        code: "EVALUATION_ERROR".to_string(),
+        message,
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+/// Approximate serialized size of `value`, without serializing it.
+fn json_size(value: &Value) -> usize {
+    match value {
+        Value::Null | Value::Bool(_) => 5,
+        Value::Number(number) => number.to_string().len(),
+        Value::String(string) => string.len() + 2,
+        Value::Array(items) => 2 + items.iter().map(|item| json_size(item) + 1).sum::<usize>(),
+        Value::Object(map) => {
+            2 + map
+                .iter()
+                .map(|(key, value)| key.len() + 4 + json_size(value))
+                .sum::<usize>()
+        }
+    }
+}
+
+fn step_id(step: &Step) -> &str {
+    match step {
+        Step::Finish(step) => &step.id,
+        Step::Agent(step) => &step.id,
+        Step::Conditional(step) => &step.id,
+        Step::Split(step) => &step.id,
+        Step::Switch(step) => &step.id,
+        Step::EmbedWorkflow(step) => &step.id,
+        Step::While(step) => &step.id,
+        Step::Log(step) => &step.id,
+        Step::Error(step) => &step.id,
+        Step::Filter(step) => &step.id,
+        Step::GroupBy(step) => &step.id,
+        Step::Delay(step) => &step.id,
+        Step::SetVariable(step) => &step.id,
+        Step::WaitForSignal(step) => &step.id,
+        Step::AiAgent(step) => &step.id,
+    }
+}
+
+fn step_name(step: &Step) -> Option<&str> {
+    match step {
+        Step::Finish(step) => step.name.as_deref(),
+        Step::Agent(step) => step.name.as_deref(),
+        Step::Conditional(step) => step.name.as_deref(),
+        Step::Split(step) => step.name.as_deref(),
+        Step::Switch(step) => step.name.as_deref(),
+        Step::EmbedWorkflow(step) => step.name.as_deref(),
+        Step::While(step) => step.name.as_deref(),
+        Step::Log(step) => step.name.as_deref(),
+        Step::Error(step) => step.name.as_deref(),
+        Step::Filter(step) => step.name.as_deref(),
+        Step::GroupBy(step) => step.name.as_deref(),
+        Step::Delay(step) => step.name.as_deref(),
+        Step::SetVariable(step) => step.name.as_deref(),
+        Step::WaitForSignal(step) => step.name.as_deref(),
+        Step::AiAgent(step) => step.name.as_deref(),
+    }
+}
+
+fn step_type_name(step: &Step) -> &'static str {
+    match step {
+        Step::Finish(_) => "Finish",
+        Step::Agent(_) => "Agent",
+        Step::Conditional(_) => "Conditional",
+        Step::Split(_) => "Split",
+        Step::Switch(_) => "Switch",
+        Step::EmbedWorkflow(_) => "EmbedWorkflow",
+        Step::While(_) => "While",
+        Step::Log(_) => "Log",
+        Step::Error(_) => "Error",
+        Step::Filter(_) => "Filter",
+        Step::GroupBy(_) => "GroupBy",
+        Step::Delay(_) => "Delay",
+        Step::SetVariable(_) => "SetVariable",
+        Step::WaitForSignal(_) => "WaitForSignal",
+        Step::AiAgent(_) => "AiAgent",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(path: &str) -> Value {
+        json!({ "valueType": "reference", "value": path })
+    }
+
+    fn immediate(value: Value) -> Value {
+        json!({ "valueType": "immediate", "value": value })
+    }
+
+    fn graph(value: Value) -> ExecutionGraph {
+        parse_scenario(&value).expect("valid scenario")
+    }
+
+    fn path(result: &SimulationResult) -> Vec<&str> {
+        result
+            .steps
+            .iter()
+            .map(|step| step.step_id.as_str())
+            .collect()
+    }
+
+    fn branching_scenario() -> Value {
+        json!({
+            "executionGraph": {
+                "entryPoint": "lookup",
+                "steps": {
+                    "lookup": {
+                        "stepType": "Agent",
+                        "id": "lookup",
+                        "agentId": "http",
+                        "capabilityId": "http-request",
+                        "inputMapping": { "url": reference("data.url") }
+                    },
+                    "is_vip": {
+                        "stepType": "Conditional",
+                        "id": "is_vip",
+                        "condition": {
+                            "type": "operation",
+                            "op": "GT",
+                            "arguments": [
+                                reference("steps.lookup.outputs.spend"),
+                                immediate(json!(1000))
+                            ]
+                        }
+                    },
+                    "vip": {
+                        "stepType": "Finish",
+                        "id": "vip",
+                        "inputMapping": { "tier": immediate(json!("vip")) }
+                    },
+                    "regular": {
+                        "stepType": "Finish",
+                        "id": "regular",
+                        "inputMapping": { "tier": immediate(json!("regular")) }
+                    }
+                },
+                "executionPlan": [
+                    { "fromStep": "lookup", "toStep": "is_vip" },
+                    { "fromStep": "is_vip", "toStep": "vip", "label": "true" },
+                    { "fromStep": "is_vip", "toStep": "regular", "label": "false" }
+                ]
+            }
+        })
+    }
+
+    #[test]
+    fn parse_scenario_keeps_editor_layout() {
+        let layout = json!({
+            "notes": [{ "id": "n1", "text": "VIP path", "position": { "x": 10.0, "y": 20.0 } }],
+            "nodes": [{
+                "id": "lookup",
+                "type": "BasicNode",
+                "position": { "x": 120.5, "y": 40.0 },
+                "width": 200.0,
+                "collapsed": true
+            }],
+            "edges": [{
+                "id": "e1",
+                "source": "lookup",
+                "target": "is_vip",
+                "sourceHandle": "source",
+                "waypoints": [{ "x": 150.0, "y": 90.0 }]
+            }]
+        });
+        let mut scenario = branching_scenario();
+        for (key, value) in layout.as_object().unwrap() {
+            scenario["executionGraph"][key] = value.clone();
+        }
+
+        let written = serde_json::to_value(graph(scenario)).unwrap();
+        for key in ["notes", "nodes", "edges"] {
+            assert_eq!(written[key], layout[key], "{key}");
+        }
+    }
+
+    #[test]
+    fn conditional_branch_follows_stubbed_agent_output() {
+        let graph = graph(branching_scenario());
+        let stubs = HashMap::from([("lookup".to_string(), json!({ "spend": 5000 }))]);
+
+        let result = simulate(
+            &graph,
+            &json!({ "data": { "url": "https://crm.example/c/1" } }),
+            &stubs,
+            SimulationLimits::default(),
+        )
+        .unwrap();
+
+        assert_eq!(path(&result), ["lookup", "is_vip", "vip"]);
+        assert_eq!(result.output, Some(json!({ "tier": "vip" })));
+        assert!(result.failure.is_none());
+        let lookup = &result.steps[0];
+        assert!(lookup.stubbed);
+        assert_eq!(lookup.inputs, json!({ "url": "https://crm.example/c/1" }));
+        assert_eq!(
+            result.steps[1].decisions,
+            [BranchDecision {
+                kind: "condition".to_string(),
+                value: Value::Bool(true),
+                label: Some("true".to_string()),
+                to_step: None,
+            }]
+        );
+
+        // Without a stub the agent outputs null and the other branch runs.
+        let result = simulate(
+            &graph,
+            &json!({}),
+            &HashMap::new(),
+            SimulationLimits::default(),
+        )
+        .unwrap();
+        assert_eq!(path(&result), ["lookup", "is_vip", "regular"]);
+        assert!(!result.steps[0].stubbed);
+    }
+
+    #[test]
+    fn set_variable_is_visible_to_later_steps() {
+        let graph = graph(json!({
+            "entryPoint": "set",
+            "steps": {
+                "set": {
+                    "stepType": "SetVariable",
+                    "id": "set",
+                    "values": { "total": reference("data.amount") }
+                },
+                "finish": {
+                    "stepType": "Finish",
+                    "id": "finish",
+                    "inputMapping": { "total": reference("variables.total") }
+                }
+            },
+            "executionPlan": [{ "fromStep": "set", "toStep": "finish" }],
+            "variables": { "total": { "type": "number", "value": 0 } }
+        }));
+
+        let result = simulate(
+            &graph,
+            &json!({ "data": { "amount": 12 }, "variables": { "total": 1 } }),
+            &HashMap::new(),
+            SimulationLimits::default(),
+        )
+        .unwrap();
+
+        assert_eq!(path(&result), ["set", "finish"]);
+        assert_eq!(result.steps[0].output, json!({ "total": 12 }));
+        assert_eq!(result.output, Some(json!({ "total": 12 })));
+    }
+
+    #[test]
+    fn split_and_while_iterate_within_their_caps() {
+        let graph = graph(json!({
+            "entryPoint": "each",
+            "steps": {
+                "each": {
+                    "stepType": "Split",
+                    "id": "each",
+                    "config": { "value": reference("data.orders") },
+                    "subgraph": {
+                        "entryPoint": "done",
+                        "steps": {
+                            "done": {
+                                "stepType": "Finish",
+                                "id": "done",
+                                "inputMapping": { "order": reference("data.id") }
+                            }
+                        }
+                    }
+                },
+                "poll": {
+                    "stepType": "While",
+                    "id": "poll",
+                    "condition": {
+                        "type": "operation",
+                        "op": "LT",
+                        "arguments": [reference("loop.index"), immediate(json!(100))]
+                    },
+                    "config": { "maxIterations": 3 },
+                    "subgraph": {
+                        "entryPoint": "check",
+                        "steps": {
+                            "check": {
+                                "stepType": "Agent",
+                                "id": "check",
+                                "agentId": "http",
+                                "capabilityId": "http-request"
+                            }
+                        }
+                    }
+                },
+                "finish": {
+                    "stepType": "Finish",
+                    "id": "finish",
+                    "inputMapping": {
+                        "orders": reference("steps.each.outputs"),
+                        "polls": reference("steps.poll.outputs.iterations")
+                    }
+                }
+            },
+            "executionPlan": [
+                { "fromStep": "each", "toStep": "poll" },
+                { "fromStep": "poll", "toStep": "finish" }
+            ]
+        }));
+
+        let result = simulate(
+            &graph,
+            &json!({ "orders": [{ "id": "a" }, { "id": "b" }] }),
+            &HashMap::new(),
+            SimulationLimits::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            path(&result),
+            [
+                "each", "done", "done", "poll", "check", "check", "check", "finish"
+            ]
+        );
+        assert_eq!(result.steps[2].loop_indices, [1]);
+        let poll = &result.steps[3];
+        assert_eq!(poll.decisions.len(), 4);
+        assert_eq!(
+            poll.decisions.last().unwrap().label.as_deref(),
+            Some("max_iterations")
+        );
+        assert_eq!(
+            result.output,
+            Some(json!({ "orders": [{ "order": "a" }, { "order": "b" }], "polls": 3 }))
+        );
+    }
+
+    #[test]
+    fn routing_switch_and_conditioned_edges_record_decisions() {
+        let graph = graph(json!({
+            "entryPoint": "route",
+            "steps": {
+                "route": {
+                    "stepType": "Switch",
+                    "id": "route",
+                    "config": {
+                        "value": reference("data.country"),
+                        "cases": [
+                            { "matchType": "EQ", "match": "PL", "output": {}, "route": "domestic" }
+                        ]
+                    }
+                },
+                "domestic": {
+                    "stepType": "Log",
+                    "id": "domestic",
+                    "message": "domestic"
+                },
+                "big": { "stepType": "Finish", "id": "big" },
+                "small": { "stepType": "Finish", "id": "small" }
+            },
+            "executionPlan": [
+                { "fromStep": "route", "toStep": "domestic", "label": "domestic" },
+                {
+                    "fromStep": "domestic",
+                    "toStep": "big",
+                    "priority": 10,
+                    "condition": {
+                        "type": "operation",
+                        "op": "GTE",
+                        "arguments": [reference("data.total"), immediate(json!(100))]
+                    }
+                },
+                { "fromStep": "domestic", "toStep": "small" }
+            ]
+        }));
+
+        let result = simulate(
+            &graph,
+            &json!({ "country": "PL", "total": 20 }),
+            &HashMap::new(),
+            SimulationLimits::default(),
+        )
+        .unwrap();
+
+        assert_eq!(path(&result), ["route", "domestic", "small"]);
+        assert_eq!(result.steps[0].decisions[0].value, json!("PL"));
+        assert_eq!(
+            result.steps[0].decisions[0].label.as_deref(),
+            Some("domestic")
+        );
+        assert_eq!(
+            result.steps[1].decisions,
+            [BranchDecision {
+                kind: "edge".to_string(),
+                value: Value::Bool(false),
+                label: None,
+                to_step: Some("big".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn failures_route_through_on_error_edges() {
+        let graph = graph(json!({
+            "entryPoint": "each",
+            "steps": {
+                "each": {
+                    "stepType": "Split",
+                    "id": "each",
+                    "config": { "value": reference("data.missing") },
+                    "subgraph": {
+                        "entryPoint": "done",
+                        "steps": { "done": { "stepType": "Finish", "id": "done" } }
+                    }
+                },
+                "recover": {
+                    "stepType": "Finish",
+                    "id": "recover",
+                    "inputMapping": { "failed": reference("steps.__error.stepId") }
+                },
+                "stop": {
+                    "stepType": "Error",
+                    "id": "stop",
+                    "category": "permanent",
+                    "code": "NO_ITEMS",
+                    "message": "nothing to do"
+                }
+            },
+            "executionPlan": [
+                { "fromStep": "each", "toStep": "recover", "label": "onError" }
+            ]
+        }));
+
+        let result = simulate(
+            &graph,
+            &json!({}),
+            &HashMap::new(),
+            SimulationLimits::default(),
+        )
+        .unwrap();
+        assert_eq!(path(&result), ["each", "recover"]);
+        assert_eq!(result.output, Some(json!({ "failed": "each" })));
+
+        let mut unhandled = graph.clone();
+        unhandled.execution_plan.clear();
+        unhandled.entry_point = "stop".to_string();
+        let result = simulate(
+            &unhandled,
+            &json!({}),
+            &HashMap::new(),
+            SimulationLimits::default(),
+        )
+        .unwrap();
+        assert_eq!(result.output, None);
+        assert_eq!(result.failure.unwrap().code, "NO_ITEMS");
+    }
+
+    #[test]
+    fn limits_abort_the_simulation() {
+        let graph = graph(branching_scenario());
+        let limits = SimulationLimits {
+            max_steps: 2,
+            ..SimulationLimits::default()
+        };
+        assert_eq!(
+            simulate(&graph, &json!({}), &HashMap::new(), limits),
+            Err(SimulationError::StepLimitExceeded(2))
+        );
+
+        let stubs = HashMap::from([("lookup".to_string(), json!("x".repeat(4096)))]);
+        let limits = SimulationLimits {
+            max_bytes: 1024,
+            ..SimulationLimits::default()
+        };
+        assert_eq!(
+            simulate(&graph, &json!({}), &stubs, limits),
+            Err(SimulationError::MemoryLimitExceeded(1024))
+        );
+
+        let limits = SimulationLimits {
+            timeout: Duration::ZERO,
+            ..SimulationLimits::default()
+        };
+        assert_eq!(
+            simulate(&graph, &json!({}), &HashMap::new(), limits),
+            Err(SimulationError::TimedOut(0))
+        );
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Per-tenant environment settings.
+//!
+//! Tenants without a `tenant_settings` row get [`TenantSettings::default_for`].
+//! Today the only setting is the strict-deployment threshold: when set,
+//! `StartInstance` refuses images whose compile warnings include one at or
+//! above that severity.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::error::Result;
+use crate::image_registry::WarningSeverity;
+
+/// Settings for one tenant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantSettings {
+    /// Tenant the settings belong to
+    pub tenant_id: String,
+    /// Refuse to start images carrying a warning at or above this severity
+    /// ("strict deployments"). `None` starts every image.
+    pub strict_deployment_severity: Option<WarningSeverity>,
+    /// When the settings were last changed (`None` when never stored)
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl TenantSettings {
+    /// Settings in effect for a tenant that never stored any.
+    pub fn default_for(tenant_id: impl Into<String>) -> Self {
+        Self {
+            tenant_id: tenant_id.into(),
+            strict_deployment_severity: None,
+            updated_at: None,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct TenantSettingsRow {
+    tenant_id: String,
+    strict_deployment_severity: Option<String>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<TenantSettingsRow> for TenantSettings {
+    fn from(row: TenantSettingsRow) -> Self {
+        TenantSettings {
+            tenant_id: row.tenant_id,
+            strict_deployment_severity: row.strict_deployment_severity.and_then(|s| s.parse().ok()),
+            updated_at: Some(row.updated_at),
+        }
+    }
+}
+
+/// Load the settings for `tenant_id`, falling back to the defaults.
+pub async fn get(pool: &PgPool, tenant_id: &str) -> Result<TenantSettings> {
+    let row: Option<TenantSettingsRow> = sqlx::query_as(
+        r#"
+        SELECT tenant_id, strict_deployment_severity, updated_at
+        FROM tenant_settings
+        WHERE tenant_id = $1
+        "#,
+    )
+    .bind(tenant_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row
+        .map(Into::into)
+        .unwrap_or_else(|| TenantSettings::default_for(tenant_id)))
+}
+
+/// Set (or clear, with `None`) the strict-deployment threshold for `tenant_id`.
+pub async fn set_strict_deployment_severity(
+    pool: &PgPool,
+    tenant_id: &str,
+    severity: Option<WarningSeverity>,
+) -> Result<TenantSettings> {
+    let row: TenantSettingsRow = sqlx::query_as(
+        r#"
+        INSERT INTO tenant_settings (tenant_id, strict_deployment_severity, updated_at)
+        VALUES ($1, $2, NOW())
+        ON CONFLICT (tenant_id) DO UPDATE SET
+            strict_deployment_severity = EXCLUDED.strict_deployment_severity,
+            updated_at = EXCLUDED.updated_at
+        RETURNING tenant_id, strict_deployment_severity, updated_at
+        "#,
+    )
+    .bind(tenant_id)
+    .bind(severity.map(|s| s.to_string()))
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.into())
+}
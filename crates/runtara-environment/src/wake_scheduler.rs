@@ -6,6 +6,7 @@
 //! when their wake time arrives. Queries `sleep_until` column via
 //! Core's Persistence trait.
 
+use runtara_core::maintenance::MaintenanceMode;
 use runtara_core::persistence::Persistence;
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -53,6 +54,7 @@ pub struct WakeScheduler {
     config: WakeSchedulerConfig,
     shutdown: Arc<Notify>,
     drain: DrainController,
+    maintenance: MaintenanceMode,
 }
 
 impl WakeScheduler {
@@ -75,6 +77,7 @@ impl WakeScheduler {
             config,
             shutdown: Arc::new(Notify::new()),
             drain: DrainController::new(),
+            maintenance: MaintenanceMode::global(),
         }
     }
 
@@ -85,6 +88,12 @@ impl WakeScheduler {
         self
     }
 
+    /// Consult a specific maintenance switch instead of the process-wide one.
+    pub fn with_maintenance(mut self, maintenance: MaintenanceMode) -> Self {
+        self.maintenance = maintenance;
+        self
+    }
+
     /// Get a handle to signal shutdown.
     pub fn shutdown_handle(&self) -> Arc<Notify> {
         self.shutdown.clone()
@@ -123,6 +132,13 @@ impl WakeScheduler {
             return Ok(());
         }
 
+        // A woken instance would only hit refused checkpoint writes; leave
+        // it asleep until core is out of maintenance.
+        if self.maintenance.is_active() {
+            debug!("Core in maintenance; skipping wake processing");
+            return Ok(());
+        }
+
         let sleeping_instances = self
             .persistence
             .get_sleeping_instances_due(self.config.batch_size)
@@ -250,6 +266,33 @@ impl WakeScheduler {
             }
         }
 
+        // Then claim it against manual resume, which does not go through
+        // sleep_until: flipping the row to 'running' here is what a racing
+        // resume's claim loses to, and vice versa.
+        match self
+            .persistence
+            .claim_resumable_instance(&instance.instance_id)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
+                info!(
+                    instance_id = %instance.instance_id,
+                    "Instance already resumed by a concurrent launch; aborting wake"
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                warn!(
+                    instance_id = %instance.instance_id,
+                    error = %e,
+                    "Failed to claim instance for wake"
+                );
+                self.release_wake_claim(&instance.instance_id).await;
+                return Err(e.into());
+            }
+        }
+
         // Launch the instance
         match self.runner.launch_detached(&options).await {
             Ok(handle) => {
@@ -307,25 +350,39 @@ impl WakeScheduler {
                     error = %e,
                     "Failed to wake instance"
                 );
-                // The claim cleared sleep_until; the launch never started, so
-                // re-stamp it (status is still 'suspended') to make the wake
-                // scan re-select this instance on a later poll instead of
-                // stranding it.
-                if let Err(restore_err) = self
-                    .persistence
-                    .set_instance_sleep(&instance.instance_id, chrono::Utc::now())
-                    .await
-                {
-                    warn!(
-                        instance_id = %instance.instance_id,
-                        error = %restore_err,
-                        "Failed to restore sleep_until after launch failure; instance may not retry"
-                    );
-                }
+                self.release_wake_claim(&instance.instance_id).await;
                 return Err(e.into());
             }
         }
 
         Ok(())
     }
+
+    /// Undo the wake claims after the launch never started: put the instance
+    /// back to 'suspended' and re-stamp `sleep_until` so the wake scan
+    /// re-selects it on a later poll instead of stranding it.
+    async fn release_wake_claim(&self, instance_id: &str) {
+        if let Err(e) = self
+            .persistence
+            .update_instance_status(instance_id, "suspended", None)
+            .await
+        {
+            warn!(
+                instance_id = %instance_id,
+                error = %e,
+                "Failed to restore suspended status after launch failure"
+            );
+        }
+        if let Err(e) = self
+            .persistence
+            .set_instance_sleep(instance_id, chrono::Utc::now())
+            .await
+        {
+            warn!(
+                instance_id = %instance_id,
+                error = %e,
+                "Failed to restore sleep_until after launch failure; instance may not retry"
+            );
+        }
+    }
 }
@@ -0,0 +1,239 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Tests for the management operation audit log.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use runtara_core::persistence::PostgresPersistence;
+use runtara_environment::audit::{self, ACTOR_HEADER, ListAuditLogOptions};
+use runtara_environment::handlers::EnvironmentHandlerState;
+use runtara_environment::http_server::environment_http_router;
+use runtara_environment::runner::MockRunner;
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+/// Required preflight for the explicitly feature-gated database suite.
+macro_rules! skip_if_no_db {
+    () => {
+        assert!(
+            std::env::var("TEST_ENVIRONMENT_DATABASE_URL").is_ok()
+                || std::env::var("RUNTARA_ENVIRONMENT_DATABASE_URL").is_ok(),
+            "db-integration-tests requires TEST_ENVIRONMENT_DATABASE_URL or RUNTARA_ENVIRONMENT_DATABASE_URL"
+        );
+    };
+}
+
+/// Get a database pool for testing
+async fn get_test_pool() -> PgPool {
+    let database_url = std::env::var("TEST_ENVIRONMENT_DATABASE_URL")
+        .or_else(|_| std::env::var("RUNTARA_ENVIRONMENT_DATABASE_URL"))
+        .expect("db-integration-tests requires an environment database URL");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("required environment test database must accept connections");
+    runtara_environment::migrations::run(&pool)
+        .await
+        .expect("required combined core/environment migrations must succeed");
+    pool
+}
+
+/// Handler state whose instances stay running until stopped.
+fn create_test_state(pool: PgPool) -> Arc<EnvironmentHandlerState> {
+    let persistence = Arc::new(PostgresPersistence::new(pool.clone()));
+    Arc::new(EnvironmentHandlerState::new(
+        pool,
+        persistence,
+        Arc::new(MockRunner::never_completing()),
+        "127.0.0.1:8001".to_string(),
+        std::env::temp_dir(),
+    ))
+}
+
+async fn insert_test_image(pool: &PgPool, tenant_id: &str) -> String {
+    let image_id = Uuid::new_v4().to_string();
+    sqlx::query(
+        r#"
+        INSERT INTO images (image_id, tenant_id, name, description, binary_path, bundle_path, runner_type)
+        VALUES ($1, $2, $3, 'desc', $4, NULL, 'mock')
+        "#,
+    )
+    .bind(&image_id)
+    .bind(tenant_id)
+    .bind(format!("audit-test-{}", image_id))
+    .bind(
+        std::env::current_exe()
+            .expect("the running test binary must have a path")
+            .to_string_lossy()
+            .into_owned(),
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    image_id
+}
+
+async fn send(
+    state: &Arc<EnvironmentHandlerState>,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(ACTOR_HEADER, "key:audit-test")
+        .header("content-type", "application/json");
+    let request = match body {
+        Some(body) => request.body(Body::from(body.to_string())).unwrap(),
+        None => request.body(Body::empty()).unwrap(),
+    };
+    let response = environment_http_router(state.clone())
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+async fn cleanup(pool: &PgPool, instance_id: &str, image_id: &str) {
+    for query in [
+        "DELETE FROM audit_log WHERE instance_id = $1",
+        "DELETE FROM container_registry WHERE instance_id = $1",
+        "DELETE FROM instance_images WHERE instance_id = $1",
+        "DELETE FROM instances WHERE instance_id = $1",
+    ] {
+        let _ = sqlx::query(query).bind(instance_id).execute(pool).await;
+    }
+    let _ = sqlx::query("DELETE FROM audit_log WHERE image_id = $1")
+        .bind(image_id)
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM images WHERE image_id = $1")
+        .bind(image_id)
+        .execute(pool)
+        .await;
+}
+
+#[tokio::test]
+async fn test_instance_operations_are_audited() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+    let state = create_test_state(pool.clone());
+    let image_id = insert_test_image(&pool, "audit-tenant").await;
+    let instance_id = format!("audit-{}", Uuid::new_v4());
+
+    let (status, body) = send(
+        &state,
+        "POST",
+        "/api/v1/instances",
+        Some(json!({
+            "image_id": image_id,
+            "tenant_id": "audit-tenant",
+            "instance_id": instance_id,
+            "input": {"order": 42},
+            "env": {"API_TOKEN": "s3cret", "LOG_LEVEL": "debug"},
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "start failed: {body}");
+
+    let (status, body) = send(
+        &state,
+        "POST",
+        &format!("/api/v1/instances/{}/stop", instance_id),
+        Some(json!({"reason": "audit test"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "stop failed: {body}");
+
+    let entries = audit::list_audit_log(
+        &pool,
+        &ListAuditLogOptions {
+            target_id: Some(instance_id.clone()),
+            limit: 10,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let operations: Vec<&str> = entries.iter().map(|e| e.operation.as_str()).collect();
+    assert_eq!(
+        operations,
+        vec![audit::ops::INSTANCE_STOP, audit::ops::INSTANCE_START]
+    );
+    for entry in &entries {
+        assert_eq!(entry.actor, "key:audit-test");
+        assert!(entry.succeeded, "{:?}", entry.error);
+    }
+
+    // Only env var names are kept; secrets never reach the audit table.
+    let start = &entries[1];
+    assert_eq!(start.tenant_id.as_deref(), Some("audit-tenant"));
+    assert_eq!(start.image_id.as_deref(), Some(image_id.as_str()));
+    assert_eq!(start.params["env_keys"], json!(["API_TOKEN", "LOG_LEVEL"]));
+    assert!(!start.params.to_string().contains("s3cret"));
+
+    // The same entries are served over HTTP.
+    let (status, body) = send(
+        &state,
+        "GET",
+        &format!(
+            "/api/v1/audit-log?target_id={}&actor=key:audit-test",
+            instance_id
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 2);
+    assert_eq!(body["entries"][0]["operation"], audit::ops::INSTANCE_STOP);
+
+    cleanup(&pool, &instance_id, &image_id).await;
+}
+
+#[tokio::test]
+async fn test_delete_audit_log_before() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+    let target = format!("audit-prune-{}", Uuid::new_v4());
+
+    audit::record(
+        &pool,
+        audit::AuditEvent::new("alice", audit::ops::IMAGE_DELETE).image(&target),
+    )
+    .await;
+    sqlx::query(
+        "UPDATE audit_log SET created_at = NOW() - INTERVAL '100 days' WHERE image_id = $1",
+    )
+    .bind(&target)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let deleted =
+        audit::delete_audit_log_before(&pool, chrono::Utc::now() - chrono::Duration::days(90))
+            .await
+            .unwrap();
+    assert!(deleted >= 1);
+
+    let remaining = audit::count_audit_log(
+        &pool,
+        &ListAuditLogOptions {
+            target_id: Some(target),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(remaining, 0);
+}
@@ -8,16 +8,23 @@ use chrono::Utc;
 use runtara_core::persistence::{CompleteInstanceParams, Persistence, PostgresPersistence};
 use runtara_environment::container_registry::{ContainerInfo, ContainerRegistry};
 use runtara_environment::db;
+use runtara_environment::error::ErrorCode;
 use runtara_environment::handlers::{
+    CANCELLED_BEFORE_START_EVENT, CancelPendingStartOutcome, CancelPendingStartRequest,
     DrainController, EnvironmentHandlerState, GetCapabilityRequest, RegisterImageRequest,
     ResumeInstanceRequest, StartInstanceRequest, StopInstanceRequest, TestCapabilityRequest,
-    detect_stale_monitor, handle_get_capability, handle_health_check, handle_list_agents,
-    handle_register_image, handle_resume_instance, handle_start_instance, handle_stop_instance,
-    handle_test_capability, spawn_container_monitor,
+    detect_stale_monitor, handle_cancel_pending_start, handle_get_capability, handle_health_check,
+    handle_list_agents, handle_register_image, handle_resume_instance, handle_start_instance,
+    handle_stop_instance, handle_test_capability, spawn_container_monitor,
+};
+use runtara_environment::image_provenance::{self, ImageChildSource, ImageProvenance};
+use runtara_environment::image_registry::{
+    ImageRegistry, ImageWarning, RunnerType, WarningSeverity,
 };
-use runtara_environment::image_registry::{ImageRegistry, RunnerType};
 use runtara_environment::runner::MockRunner;
-use runtara_environment::runner::{LaunchOptions, Runner, RunnerHandle};
+use runtara_environment::runner::{
+    LaunchOptions, Runner, RunnerError, RunnerHandle, StartFailureReason,
+};
 use sqlx::PgPool;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -220,6 +227,13 @@ async fn test_register_image_success() {
         binary: vec![0x7f, 0x45, 0x4c, 0x46], // ELF magic bytes
         runner_type: RunnerType::Wasm,
         metadata: Some(serde_json::json!({"key": "value"})),
+        warnings: vec![ImageWarning {
+            code: "W090".to_string(),
+            step_id: Some("finish".to_string()),
+            message: "large literal".to_string(),
+            severity: WarningSeverity::Warning,
+        }],
+        provenance: None,
     };
 
     let response = handle_register_image(&state, request)
@@ -239,10 +253,106 @@ async fn test_register_image_success() {
 
     assert_eq!(image.tenant_id, "test-tenant");
     assert_eq!(image.name, "test-image");
+    assert_eq!(image.warnings.len(), 1);
+    assert_eq!(image.warnings[0].code, "W090");
+    assert_eq!(image.warnings[0].severity, WarningSeverity::Warning);
 
     cleanup(&pool, None, Some(&response.image_id)).await;
 }
 
+#[tokio::test]
+async fn test_register_image_stores_deduplicated_provenance() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let state = create_test_state(pool.clone(), temp_dir.path().to_path_buf());
+
+    let tenant_id = format!("provenance-tenant-{}", Uuid::new_v4());
+    let child_source = serde_json::json!({"steps": {"done": {"stepType": "Finish"}}, "nonce": Uuid::new_v4().to_string()});
+    let provenance = |version: u32| ImageProvenance {
+        source: Some(serde_json::json!({"entryPoint": "call", "version": version})),
+        child_sources: vec![ImageChildSource {
+            workflow_id: "child".to_string(),
+            version: 1,
+            source: child_source.clone(),
+        }],
+        graph_hash: Some(format!("graph-{version}")),
+        compiler_version: Some("8.6.3".to_string()),
+        rustc_version: Some("rustc 1.97.0".to_string()),
+        agent_library_checksum: Some("agents-sha".to_string()),
+    };
+    let register = |name: &str, provenance: Option<ImageProvenance>| RegisterImageRequest {
+        tenant_id: tenant_id.clone(),
+        name: name.to_string(),
+        description: None,
+        binary: vec![1, 2, 3],
+        runner_type: RunnerType::Wasm,
+        metadata: None,
+        warnings: Vec::new(),
+        provenance,
+    };
+
+    let first = handle_register_image(&state, register("parent:1", Some(provenance(1))))
+        .await
+        .unwrap();
+    let second = handle_register_image(&state, register("parent:2", Some(provenance(2))))
+        .await
+        .unwrap();
+    assert!(first.success && second.success);
+
+    let stored = image_provenance::get(&pool, &second.image_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored, provenance(2));
+    let source = image_provenance::get_source(&pool, &first.image_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(source.source["version"], 1);
+    assert_eq!(source.child_sources[0].source, child_source);
+
+    // Both versions share the child scenario: one blob.
+    let child_hash = image_provenance::source_hash(&child_source);
+    let blob_count = |hash: String| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM image_source_blobs WHERE source_hash = $1",
+            )
+            .bind(hash)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        }
+    };
+    assert_eq!(blob_count(child_hash.clone()).await, 1);
+
+    // Re-registering without provenance clears it; the shared child blob
+    // survives while another image still references it.
+    let replaced = handle_register_image(&state, register("parent:1", None))
+        .await
+        .unwrap();
+    assert_eq!(replaced.image_id, first.image_id);
+    assert!(
+        image_provenance::get(&pool, &first.image_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(blob_count(child_hash.clone()).await, 1);
+
+    // Deleting the last referencing image prunes the blob.
+    ImageRegistry::new(pool.clone())
+        .delete(&second.image_id)
+        .await
+        .unwrap();
+    assert_eq!(blob_count(child_hash).await, 0);
+
+    cleanup(&pool, None, Some(&first.image_id)).await;
+}
+
 #[tokio::test]
 async fn test_register_image_empty_tenant_id() {
     skip_if_no_db!();
@@ -258,6 +368,8 @@ async fn test_register_image_empty_tenant_id() {
         binary: vec![1, 2, 3],
         runner_type: RunnerType::Wasm,
         metadata: None,
+        warnings: Vec::new(),
+        provenance: None,
     };
 
     let response = handle_register_image(&state, request).await.unwrap();
@@ -281,6 +393,8 @@ async fn test_register_image_empty_name() {
         binary: vec![1, 2, 3],
         runner_type: RunnerType::Wasm,
         metadata: None,
+        warnings: Vec::new(),
+        provenance: None,
     };
 
     let response = handle_register_image(&state, request).await.unwrap();
@@ -304,6 +418,8 @@ async fn test_register_image_empty_binary() {
         binary: vec![], // Empty
         runner_type: RunnerType::Wasm,
         metadata: None,
+        warnings: Vec::new(),
+        provenance: None,
     };
 
     let response = handle_register_image(&state, request).await.unwrap();
@@ -366,6 +482,88 @@ async fn test_start_instance_success() {
     cleanup(&pool, Some(&response.instance_id), Some(&image_id)).await;
 }
 
+#[tokio::test]
+async fn test_start_instance_strict_deployment_refuses_warned_image() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let state = create_test_state(pool.clone(), temp_dir.path().to_path_buf());
+
+    let tenant_id = format!("strict-tenant-{}", Uuid::new_v4());
+    let image_id = Uuid::new_v4().to_string();
+    sqlx::query(
+        r#"
+        INSERT INTO images (image_id, tenant_id, name, description, binary_path, bundle_path, runner_type, warnings)
+        VALUES ($1, $2, $3, 'desc', $4, '/tmp/test-bundle', 'mock', $5)
+        "#,
+    )
+    .bind(&image_id)
+    .bind(&tenant_id)
+    .bind(format!("test-image-{}", image_id))
+    .bind(test_artifact_path())
+    .bind(serde_json::json!([
+        {"code": "W091", "message": "no description", "severity": "info"},
+        {"code": "W090", "step_id": "finish", "message": "large literal", "severity": "warning"}
+    ]))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let start = |instance_id: &str| StartInstanceRequest {
+        image_id: image_id.clone(),
+        tenant_id: tenant_id.clone(),
+        instance_id: Some(instance_id.to_string()),
+        input: None,
+        timeout_seconds: Some(60),
+        env: std::collections::HashMap::new(),
+    };
+
+    // Threshold at `warning`: the W090 entry blocks the start.
+    runtara_environment::tenant_settings::set_strict_deployment_severity(
+        &pool,
+        &tenant_id,
+        Some(WarningSeverity::Warning),
+    )
+    .await
+    .unwrap();
+    let refused_id = format!("strict-{}", Uuid::new_v4());
+    let response = handle_start_instance(&state, start(&refused_id))
+        .await
+        .unwrap();
+    assert!(!response.success);
+    let error = response.error.unwrap();
+    assert!(error.contains("W090"), "{error}");
+    assert!(!error.contains("W091"), "{error}");
+    assert!(
+        db::get_instance(&pool, &refused_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Threshold at `error`: nothing reaches it, the start goes through.
+    runtara_environment::tenant_settings::set_strict_deployment_severity(
+        &pool,
+        &tenant_id,
+        Some(WarningSeverity::Error),
+    )
+    .await
+    .unwrap();
+    let allowed_id = format!("strict-{}", Uuid::new_v4());
+    let response = handle_start_instance(&state, start(&allowed_id))
+        .await
+        .unwrap();
+    assert!(response.success, "Error: {:?}", response.error);
+
+    sqlx::query("DELETE FROM tenant_settings WHERE tenant_id = $1")
+        .bind(&tenant_id)
+        .execute(&pool)
+        .await
+        .ok();
+    cleanup(&pool, Some(&allowed_id), Some(&image_id)).await;
+}
+
 #[tokio::test]
 async fn test_start_instance_with_custom_id() {
     skip_if_no_db!();
@@ -515,6 +713,114 @@ async fn test_start_instance_missing_artifact_does_not_reserve_instance_id() {
     cleanup(&pool, Some(&instance_id), Some(&image_id)).await;
 }
 
+/// Start an instance of a fresh image on a runner whose launches fail with
+/// `error()`, returning the response and the stored instance error.
+async fn start_with_failing_launch(
+    pool: &PgPool,
+    error: impl Fn() -> RunnerError + Send + Sync + 'static,
+) -> (
+    runtara_environment::handlers::StartInstanceResponse,
+    Option<String>,
+) {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let persistence = Arc::new(PostgresPersistence::new(pool.clone()));
+    let state = EnvironmentHandlerState::new(
+        pool.clone(),
+        persistence,
+        Arc::new(MockRunner::failing_launch(error)),
+        "127.0.0.1:8001".to_string(),
+        temp_dir.path().to_path_buf(),
+    );
+    let image_id = create_test_image(pool).await;
+    let instance_id = format!("launch-failure-{}", Uuid::new_v4());
+
+    let response = handle_start_instance(
+        &state,
+        StartInstanceRequest {
+            image_id: image_id.clone(),
+            tenant_id: "test-tenant".to_string(),
+            instance_id: Some(instance_id.clone()),
+            input: None,
+            timeout_seconds: None,
+            env: std::collections::HashMap::new(),
+        },
+    )
+    .await
+    .unwrap();
+    let stored_error = db::get_instance(pool, &instance_id)
+        .await
+        .unwrap()
+        .and_then(|instance| instance.error);
+
+    cleanup(pool, Some(&instance_id), Some(&image_id)).await;
+    (response, stored_error)
+}
+
+/// A runner error, the reason it should classify as, and the error code the
+/// caller should see.
+type LaunchFailureCase = (fn() -> RunnerError, StartFailureReason, ErrorCode);
+
+#[tokio::test]
+async fn test_start_instance_reports_launch_failure_reason() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+
+    let cases: [LaunchFailureCase; 5] = [
+        (
+            || RunnerError::BinaryNotFound("/images/gone.wasm".to_string()),
+            StartFailureReason::ImageBinaryMissing,
+            ErrorCode::NotFound,
+        ),
+        (
+            || {
+                RunnerError::from_bundle_io(
+                    "create run directory",
+                    std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+                )
+            },
+            StartFailureReason::BundleCreationFailed,
+            ErrorCode::Unavailable,
+        ),
+        (
+            || RunnerError::RuntimeUnavailable {
+                which: "wasmtime".to_string(),
+                detail: "engine unavailable".to_string(),
+            },
+            StartFailureReason::RuntimeUnavailable,
+            ErrorCode::Unavailable,
+        ),
+        (
+            || {
+                RunnerError::from_bundle_io(
+                    "create run directory",
+                    std::io::Error::from(std::io::ErrorKind::StorageFull),
+                )
+            },
+            StartFailureReason::ResourceLimit,
+            ErrorCode::QuotaExceeded,
+        ),
+        (
+            || RunnerError::StartFailed("seccomp denied clone3".to_string()),
+            StartFailureReason::Unknown,
+            ErrorCode::Internal,
+        ),
+    ];
+
+    for (error, reason, code) in cases {
+        let (response, stored_error) = start_with_failing_launch(&pool, error).await;
+
+        assert!(!response.success);
+        let failure = response.failure.expect("launch failures are typed");
+        assert_eq!(failure.start_failure, Some(reason));
+        assert_eq!(failure.code, code, "{reason}");
+        let stored_error = stored_error.expect("the failed instance records its error");
+        assert!(
+            stored_error.contains(reason.as_str()),
+            "instance error should name {reason}: {stored_error}"
+        );
+    }
+}
+
 #[tokio::test]
 async fn test_start_instance_rejects_same_id_for_different_image() {
     skip_if_no_db!();
@@ -705,6 +1011,150 @@ async fn test_stop_instance_with_registered_container() {
     cleanup(&pool, Some(&instance_id), Some(&image_id)).await;
 }
 
+// ============================================================================
+// Cancel Pending Start Tests
+// ============================================================================
+
+/// Insert a mock-runner image row and return its ID.
+async fn create_test_image(pool: &PgPool) -> String {
+    let image_id = Uuid::new_v4().to_string();
+    sqlx::query(
+        r#"
+        INSERT INTO images (image_id, tenant_id, name, description, binary_path, bundle_path, runner_type)
+        VALUES ($1, 'test-tenant', $2, 'desc', $3, '/tmp/test-bundle', 'mock')
+        "#,
+    )
+    .bind(&image_id)
+    .bind(format!("test-image-{}", image_id))
+    .bind(test_artifact_path())
+    .execute(pool)
+    .await
+    .unwrap();
+    image_id
+}
+
+#[tokio::test]
+async fn test_cancel_pending_start_before_claim() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let state = create_test_state(pool.clone(), temp_dir.path().to_path_buf());
+
+    let instance_id = Uuid::new_v4().to_string();
+    let image_id = create_test_image(&pool).await;
+    create_test_instance(&pool, &instance_id, "test-tenant", &image_id).await;
+
+    let response = handle_cancel_pending_start(
+        &state,
+        CancelPendingStartRequest {
+            instance_id: instance_id.clone(),
+            reason: Some("no longer needed".to_string()),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        response.outcome,
+        CancelPendingStartOutcome::CancelledBeforeStart
+    );
+    let instance = db::get_instance(&pool, &instance_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(instance.status, "cancelled");
+
+    let events: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM instance_events WHERE instance_id = $1 AND subtype = $2",
+    )
+    .bind(&instance_id)
+    .bind(CANCELLED_BEFORE_START_EVENT)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(events, 1);
+
+    // The launcher's claim must now lose, so the start never runs.
+    let persistence = PostgresPersistence::new(pool.clone());
+    assert!(
+        !persistence
+            .claim_pending_instance(&instance_id)
+            .await
+            .unwrap()
+    );
+
+    sqlx::query("DELETE FROM instance_events WHERE instance_id = $1")
+        .bind(&instance_id)
+        .execute(&pool)
+        .await
+        .ok();
+    cleanup(&pool, Some(&instance_id), Some(&image_id)).await;
+}
+
+#[tokio::test]
+async fn test_cancel_pending_start_after_claim() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let state = create_test_state(pool.clone(), temp_dir.path().to_path_buf());
+
+    let instance_id = Uuid::new_v4().to_string();
+    let image_id = create_test_image(&pool).await;
+    create_test_instance(&pool, &instance_id, "test-tenant", &image_id).await;
+
+    let persistence = PostgresPersistence::new(pool.clone());
+    assert!(
+        persistence
+            .claim_pending_instance(&instance_id)
+            .await
+            .unwrap()
+    );
+
+    let response = handle_cancel_pending_start(
+        &state,
+        CancelPendingStartRequest {
+            instance_id: instance_id.clone(),
+            reason: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(response.outcome, CancelPendingStartOutcome::AlreadyStarted);
+    assert_eq!(response.status.as_deref(), Some("running"));
+    let instance = db::get_instance(&pool, &instance_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(instance.status, "running");
+
+    cleanup(&pool, Some(&instance_id), Some(&image_id)).await;
+}
+
+#[tokio::test]
+async fn test_cancel_pending_start_unknown_instance() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let state = create_test_state(pool, temp_dir.path().to_path_buf());
+
+    let response = handle_cancel_pending_start(
+        &state,
+        CancelPendingStartRequest {
+            instance_id: "nonexistent-instance".to_string(),
+            reason: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(response.outcome, CancelPendingStartOutcome::NotFound);
+    assert!(response.status.is_none());
+}
+
 // ============================================================================
 // Resume Instance Tests
 // ============================================================================
@@ -872,6 +1322,7 @@ fn test_health_check_response_debug() {
         healthy: true,
         version: "1.0.0".to_string(),
         uptime_ms: 12345,
+        maintenance: None,
     };
     let debug_str = format!("{:?}", response);
     assert!(debug_str.contains("healthy"));
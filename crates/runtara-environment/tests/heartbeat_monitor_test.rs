@@ -246,6 +246,7 @@ impl MockPersistence {
             exit_code: None,
             recovery_attempts: 0,
             recovery_marker: None,
+            status_version: 0,
         };
         self.instances
             .lock()
@@ -1029,6 +1030,7 @@ async fn test_completed_instance_in_core_not_flagged() {
             exit_code: None,
             recovery_attempts: 0,
             recovery_marker: None,
+            status_version: 0,
         };
         persistence
             .instances
@@ -6,27 +6,36 @@
 //! HTTP server defined in `runtara-environment/src/http_server.rs`.
 
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use base64::Engine;
 use chrono::{TimeZone, Utc};
+use futures::Stream;
 use reqwest::Client;
 use serde::Deserialize;
 use tracing::{debug, info, instrument};
 
 use crate::config::SdkConfig;
-use crate::error::{Result, SdkError};
+use crate::error::{FieldError, Result, SdkError, StartFailureReason};
 use crate::types::{
-    AgentInfo, CapabilityField, Checkpoint, CheckpointSummary, EventSummary,
-    GetTenantMetricsOptions, HealthStatus, ImageSummary, InstanceInfo, InstanceStatus,
-    InstanceSummary, ListCheckpointsOptions, ListCheckpointsResult, ListEventsOptions,
+    AgentInfo, AuditLogEntry, CancelPendingStartOutcome, CapabilityField, Checkpoint,
+    CheckpointPreview, CheckpointPreviewKey, CheckpointPreviewOptions, CheckpointSummary,
+    EventSummary, GetTenantMetricsOptions, HealthStatus, ImageProvenance, ImageSource,
+    ImageSummary, ImageWarning, InstanceInfo, InstanceStatus, InstanceSummary, ListAuditLogOptions,
+    ListAuditLogResult, ListCheckpointsOptions, ListCheckpointsResult, ListEventsOptions,
     ListEventsResult, ListImagesOptions, ListImagesResult, ListInstancesOptions,
-    ListInstancesResult, ListStepSummariesOptions, ListStepSummariesResult, MetricsBucket,
-    MetricsGranularity, RegisterImageOptions, RegisterImageResult, RegisterImageStreamOptions,
-    RunnerType, ScopeInfo, SignalType, StartInstanceOptions, StartInstanceResult, StepStatus,
-    StepSummary, StopInstanceOptions, TenantMetricsResult, TerminationReason,
-    TestCapabilityOptions, TestCapabilityResult,
+    ListInstancesResult, ListStepSummariesOptions, ListStepSummariesResult, MaintenanceStatus,
+    MetricsBucket, MetricsGranularity, RegisterImageOptions, RegisterImageResult,
+    RegisterImageStreamOptions, ReportedVersions, RunnerType, ScopeInfo, SetMaintenanceOptions,
+    SignalType, SimulateScenarioOptions, SimulateScenarioResult, StartInstanceOptions,
+    StartInstanceResult, StepStatus, StepSummary, StopInstanceOptions, TenantMetricsResult,
+    TenantSettings, TerminationReason, TestCapabilityOptions, TestCapabilityResult,
+    WarningSeverity,
 };
 
+/// Per-request wait used by [`ManagementSdk::watch_instance_status`].
+const WATCH_POLL_WAIT: Duration = Duration::from_secs(25);
+
 // ============================================================================
 // Intermediate JSON response structs (match HTTP server's JSON format)
 // ============================================================================
@@ -37,6 +46,14 @@ struct HealthCheckJson {
     version: String,
     #[serde(default)]
     uptime_ms: i64,
+    #[serde(default)]
+    maintenance: Option<MaintenanceStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MaintenanceJson {
+    #[serde(default)]
+    maintenance: Option<MaintenanceStatus>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -66,6 +83,8 @@ struct InstanceStatusJson {
     #[serde(default)]
     error: Option<String>,
     #[serde(default)]
+    error_code: Option<String>,
+    #[serde(default)]
     stderr: Option<String>,
     #[serde(default)]
     heartbeat_at_ms: Option<i64>,
@@ -81,6 +100,35 @@ struct InstanceStatusJson {
     termination_reason: Option<String>,
     #[serde(default)]
     exit_code: Option<i32>,
+    #[serde(default)]
+    reported_versions: Option<ReportedVersions>,
+    #[serde(default)]
+    status_version: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListAuditLogJson {
+    entries: Vec<AuditLogEntryJson>,
+    total_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuditLogEntryJson {
+    audit_id: i64,
+    created_at_ms: i64,
+    actor: String,
+    operation: String,
+    #[serde(default)]
+    tenant_id: Option<String>,
+    #[serde(default)]
+    image_id: Option<String>,
+    #[serde(default)]
+    instance_id: Option<String>,
+    #[serde(default)]
+    params: serde_json::Value,
+    succeeded: bool,
+    #[serde(default)]
+    error: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -103,6 +151,8 @@ struct InstanceSummaryJson {
     finished_at_ms: Option<i64>,
     #[serde(default)]
     has_error: bool,
+    #[serde(default)]
+    error_code: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -114,6 +164,8 @@ struct StartInstanceJson {
     deduplicated: bool,
     #[serde(default)]
     error: Option<String>,
+    #[serde(flatten)]
+    failure: FailureJson,
 }
 
 #[derive(Debug, Deserialize)]
@@ -121,6 +173,19 @@ struct SimpleSuccessJson {
     success: bool,
     #[serde(default)]
     error: Option<String>,
+    #[serde(flatten)]
+    failure: FailureJson,
+}
+
+#[derive(Debug, Deserialize)]
+struct CancelPendingStartJson {
+    success: bool,
+    #[serde(default)]
+    outcome: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(flatten)]
+    failure: FailureJson,
 }
 
 #[derive(Debug, Deserialize)]
@@ -150,6 +215,41 @@ struct ImageSummaryJson {
     created_at_ms: i64,
     #[serde(default)]
     metadata: Option<serde_json::Value>,
+    #[serde(default)]
+    warning_count: usize,
+    #[serde(default)]
+    warnings: Option<Vec<ImageWarning>>,
+    #[serde(default)]
+    provenance: Option<ImageProvenance>,
+}
+
+impl From<ImageSummaryJson> for ImageSummary {
+    fn from(img: ImageSummaryJson) -> Self {
+        ImageSummary {
+            image_id: img.image_id,
+            tenant_id: img.tenant_id,
+            name: img.name,
+            description: img.description,
+            runner_type: runner_type_from_string(&img.runner_type),
+            created_at: ms_to_datetime(img.created_at_ms),
+            metadata: img.metadata,
+            warning_count: img.warning_count,
+            warnings: img.warnings,
+            provenance: img.provenance,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GetImageSourceJson {
+    found: bool,
+    #[serde(default)]
+    source: Option<ImageSource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TenantSettingsJson {
+    settings: TenantSettings,
 }
 
 #[derive(Debug, Deserialize)]
@@ -173,6 +273,10 @@ struct CheckpointSummaryJson {
     instance_id: String,
     created_at_ms: i64,
     data_size_bytes: u64,
+    #[serde(default)]
+    content_type: Option<String>,
+    #[serde(default)]
+    shape: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -185,6 +289,32 @@ struct CheckpointDetailJson {
     data: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct CheckpointPreviewResponseJson {
+    found: bool,
+    checkpoint_id: String,
+    instance_id: String,
+    created_at_ms: i64,
+    #[serde(default)]
+    preview: Option<CheckpointPreviewJson>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckpointPreviewJson {
+    content_type: String,
+    #[serde(default)]
+    content_type_sniffed: bool,
+    #[serde(default)]
+    shape: Option<String>,
+    size_bytes: u64,
+    #[serde(default)]
+    top_level_keys: Vec<CheckpointPreviewKey>,
+    #[serde(default)]
+    preview: Option<serde_json::Value>,
+    #[serde(default)]
+    truncated: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct ListEventsJson {
     events: Vec<EventSummaryJson>,
@@ -322,6 +452,83 @@ struct ErrorResponseJson {
     error: Option<String>,
     #[serde(default)]
     code: Option<String>,
+    #[serde(flatten)]
+    failure: FailureJson,
+}
+
+/// Typed failure fields the server attaches to error bodies and to
+/// `success: false` envelopes. All optional so older servers still parse.
+#[derive(Debug, Default, Deserialize)]
+struct FailureJson {
+    #[serde(default)]
+    error_code: Option<String>,
+    #[serde(default)]
+    resource: Option<String>,
+    #[serde(default)]
+    resource_id: Option<String>,
+    #[serde(default)]
+    field_errors: Vec<FieldError>,
+    #[serde(default)]
+    start_failure: Option<StartFailureReason>,
+}
+
+impl FailureJson {
+    /// Map onto an [`SdkError`] variant.
+    ///
+    /// `code` is the operation-specific code kept on variants that carry one;
+    /// `subject` names the resource the request addressed and fills in
+    /// `NotFound` when the server did not.
+    fn into_error(self, code: String, message: String, subject: Option<(&str, &str)>) -> SdkError {
+        if let Some(reason) = self.start_failure {
+            return SdkError::StartFailed { reason, message };
+        }
+        match self.error_code.as_deref().unwrap_or("INTERNAL") {
+            "NOT_FOUND" => {
+                let (resource, id) = match (self.resource, self.resource_id, subject) {
+                    (Some(resource), Some(id), _) => (resource, id),
+                    (resource, id, Some((subject_resource, subject_id))) => (
+                        resource.unwrap_or_else(|| subject_resource.to_string()),
+                        id.unwrap_or_else(|| subject_id.to_string()),
+                    ),
+                    (resource, id, None) => (
+                        resource.unwrap_or_else(|| "resource".to_string()),
+                        id.unwrap_or_default(),
+                    ),
+                };
+                SdkError::NotFound {
+                    resource,
+                    id,
+                    message,
+                }
+            }
+            "CONFLICT" => SdkError::Conflict { code, message },
+            "QUOTA_EXCEEDED" => SdkError::QuotaExceeded { code, message },
+            "UNAUTHORIZED" => SdkError::Unauthorized { code, message },
+            "VALIDATION" => SdkError::Validation {
+                message,
+                field_errors: self.field_errors,
+            },
+            "UNAVAILABLE" => SdkError::Transport {
+                message,
+                retryable: true,
+            },
+            _ => SdkError::Internal { code, message },
+        }
+    }
+}
+
+/// Failure class implied by an HTTP status, for servers that predate
+/// `error_code`.
+fn error_code_for_status(status: reqwest::StatusCode) -> &'static str {
+    match status.as_u16() {
+        400 | 422 => "VALIDATION",
+        401 | 403 => "UNAUTHORIZED",
+        404 => "NOT_FOUND",
+        409 => "CONFLICT",
+        429 => "QUOTA_EXCEEDED",
+        502..=504 => "UNAVAILABLE",
+        _ => "INTERNAL",
+    }
 }
 
 // ============================================================================
@@ -400,11 +607,18 @@ pub struct ManagementSdk {
 impl ManagementSdk {
     /// Create a new HTTP SDK with the given configuration.
     pub fn new(config: SdkConfig) -> Result<Self> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(ref actor) = config.actor {
+            let value = reqwest::header::HeaderValue::from_str(actor)
+                .map_err(|e| SdkError::Config(format!("invalid actor: {}", e)))?;
+            headers.insert("x-runtara-actor", value);
+        }
         let client = Client::builder()
             .timeout(config.request_timeout)
             .connect_timeout(config.connect_timeout)
+            .default_headers(headers)
             .build()
-            .map_err(|e| SdkError::Connection(format!("Failed to create HTTP client: {}", e)))?;
+            .map_err(|e| SdkError::Config(format!("Failed to create HTTP client: {}", e)))?;
 
         let base_url = format!("http://{}", config.server_addr);
 
@@ -464,20 +678,34 @@ impl ManagementSdk {
 
     /// Parse an error response body from the server.
     async fn parse_error_response(resp: reqwest::Response) -> SdkError {
+        Self::parse_error_response_for(resp, None).await
+    }
+
+    /// Parse an error response body for a request addressed at `subject`
+    /// (`(resource, id)`), used when the body doesn't name the resource.
+    async fn parse_error_response_for(
+        resp: reqwest::Response,
+        subject: Option<(&str, &str)>,
+    ) -> SdkError {
         let status = resp.status();
-        match resp.json::<ErrorResponseJson>().await {
-            Ok(err_body) => {
-                let message = err_body
+        let (message, code, mut failure) = match resp.json::<ErrorResponseJson>().await {
+            Ok(err_body) => (
+                err_body
                     .error
-                    .unwrap_or_else(|| format!("HTTP {} error", status));
-                let code = err_body.code.unwrap_or_else(|| status.as_str().to_string());
-                SdkError::Server { code, message }
-            }
-            Err(_) => SdkError::Server {
-                code: status.as_str().to_string(),
-                message: format!("HTTP {} error", status),
-            },
+                    .unwrap_or_else(|| format!("HTTP {} error", status)),
+                err_body.code.unwrap_or_else(|| status.as_str().to_string()),
+                err_body.failure,
+            ),
+            Err(_) => (
+                format!("HTTP {} error", status),
+                status.as_str().to_string(),
+                FailureJson::default(),
+            ),
+        };
+        if failure.error_code.is_none() {
+            failure.error_code = Some(error_code_for_status(status).to_string());
         }
+        failure.into_error(code, message, subject)
     }
 
     // =========================================================================
@@ -503,6 +731,7 @@ impl ManagementSdk {
             version: json.version,
             uptime_ms: json.uptime_ms,
             active_instances: 0,
+            maintenance: json.maintenance,
         })
     }
 
@@ -514,12 +743,82 @@ impl ManagementSdk {
     #[instrument(skip(self), fields(instance_id = %instance_id), level = "debug")]
     pub async fn get_instance_status(&self, instance_id: &str) -> Result<InstanceInfo> {
         debug!("Getting instance status");
+        self.fetch_instance_status(instance_id, None).await
+    }
 
-        let resp = self
+    /// Long-poll for the next status or checkpoint change.
+    ///
+    /// Returns as soon as the instance's `status_version` differs from
+    /// `from_version`, or after `max_wait` (the server caps it at 30s) with
+    /// the unchanged state. Compare the returned `status_version` to tell
+    /// the two apart.
+    #[instrument(skip(self), fields(instance_id = %instance_id), level = "debug")]
+    pub async fn wait_for_status_change(
+        &self,
+        instance_id: &str,
+        from_version: i64,
+        max_wait: Duration,
+    ) -> Result<InstanceInfo> {
+        debug!(from_version, "Waiting for instance status change");
+        self.fetch_instance_status(instance_id, Some((from_version, max_wait)))
+            .await
+    }
+
+    /// Stream of an instance's status changes, built on repeated long-polls.
+    ///
+    /// Yields the current state first, then one item per observed change
+    /// of status or checkpoint. Changes that land between two polls are
+    /// never lost, though several of them may collapse into one item. The
+    /// stream ends after yielding a terminal status or an error.
+    pub fn watch_instance_status<'a>(
+        &'a self,
+        instance_id: &'a str,
+    ) -> impl Stream<Item = Result<InstanceInfo>> + 'a {
+        futures::stream::unfold(
+            Some(None),
+            move |last_version: Option<Option<i64>>| async move {
+                let last_version = last_version?;
+                let next = match last_version {
+                    None => self.get_instance_status(instance_id).await,
+                    Some(version) => loop {
+                        match self
+                            .wait_for_status_change(instance_id, version, WATCH_POLL_WAIT)
+                            .await
+                        {
+                            Ok(info) if info.status_version == version => continue,
+                            other => break other,
+                        }
+                    },
+                };
+                let state = match &next {
+                    Ok(info) if !info.status.is_terminal() => Some(Some(info.status_version)),
+                    _ => None,
+                };
+                Some((next, state))
+            },
+        )
+    }
+
+    /// GET an instance's status, long-polling from `wait` =
+    /// `(from_version, max_wait)` when given.
+    async fn fetch_instance_status(
+        &self,
+        instance_id: &str,
+        wait: Option<(i64, Duration)>,
+    ) -> Result<InstanceInfo> {
+        let mut request = self
             .client
-            .get(self.url(&format!("/api/v1/instances/{}", instance_id)))
-            .send()
-            .await?;
+            .get(self.url(&format!("/api/v1/instances/{}", instance_id)));
+        if let Some((from_version, max_wait)) = wait {
+            request = request
+                .query(&[
+                    ("wait_for_change_from", from_version.to_string()),
+                    ("max_wait_ms", max_wait.as_millis().to_string()),
+                ])
+                // The held request must outlive the configured request timeout.
+                .timeout(self.config.request_timeout + max_wait);
+        }
+        let resp = request.send().await?;
 
         if !resp.status().is_success() {
             return Err(Self::parse_error_response(resp).await);
@@ -528,7 +827,11 @@ impl ManagementSdk {
         let json: InstanceStatusJson = resp.json().await?;
 
         if !json.found {
-            return Err(SdkError::InstanceNotFound(instance_id.to_string()));
+            return Err(SdkError::not_found(
+                "instance",
+                instance_id,
+                format!("Instance not found: {}", instance_id),
+            ));
         }
 
         Ok(InstanceInfo {
@@ -548,6 +851,7 @@ impl ManagementSdk {
             input: json.input.as_deref().and_then(decode_base64_json),
             output: json.output.as_deref().and_then(decode_base64_json),
             error: json.error,
+            error_code: json.error_code,
             stderr: json.stderr,
             retry_count: json.retry_count.unwrap_or(0),
             max_retries: json.max_retries.unwrap_or(0),
@@ -557,6 +861,8 @@ impl ManagementSdk {
                 .termination_reason
                 .and_then(|s| TerminationReason::from_str(&s)),
             exit_code: json.exit_code,
+            reported_versions: json.reported_versions,
+            status_version: json.status_version,
         })
     }
 
@@ -646,6 +952,7 @@ impl ManagementSdk {
                 started_at: opt_ms_to_datetime(inst.started_at_ms),
                 finished_at: opt_ms_to_datetime(inst.finished_at_ms),
                 has_error: inst.has_error,
+                error_code: inst.error_code,
             })
             .collect();
 
@@ -655,6 +962,76 @@ impl ManagementSdk {
         })
     }
 
+    /// List recorded management operations, newest first.
+    #[instrument(skip(self, options), level = "debug")]
+    pub async fn list_audit_log(&self, options: ListAuditLogOptions) -> Result<ListAuditLogResult> {
+        debug!("Listing audit log");
+
+        let mut query: Vec<(String, String)> = Vec::new();
+
+        if let Some(ref actor) = options.actor {
+            query.push(("actor".to_string(), actor.clone()));
+        }
+        if let Some(ref operation) = options.operation {
+            query.push(("operation".to_string(), operation.clone()));
+        }
+        if let Some(ref target_id) = options.target_id {
+            query.push(("target_id".to_string(), target_id.clone()));
+        }
+        if let Some(ref tenant_id) = options.tenant_id {
+            query.push(("tenant_id".to_string(), tenant_id.clone()));
+        }
+        if let Some(created_after) = options.created_after {
+            query.push((
+                "created_after_ms".to_string(),
+                created_after.timestamp_millis().to_string(),
+            ));
+        }
+        if let Some(created_before) = options.created_before {
+            query.push((
+                "created_before_ms".to_string(),
+                created_before.timestamp_millis().to_string(),
+            ));
+        }
+        query.push(("limit".to_string(), options.limit.to_string()));
+        query.push(("offset".to_string(), options.offset.to_string()));
+
+        let resp = self
+            .client
+            .get(self.url("/api/v1/audit-log"))
+            .query(&query)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let json: ListAuditLogJson = resp.json().await?;
+
+        let entries = json
+            .entries
+            .into_iter()
+            .map(|entry| AuditLogEntry {
+                audit_id: entry.audit_id,
+                created_at: ms_to_datetime(entry.created_at_ms),
+                actor: entry.actor,
+                operation: entry.operation,
+                tenant_id: entry.tenant_id,
+                image_id: entry.image_id,
+                instance_id: entry.instance_id,
+                params: entry.params,
+                succeeded: entry.succeeded,
+                error: entry.error,
+            })
+            .collect();
+
+        Ok(ListAuditLogResult {
+            entries,
+            total_count: json.total_count,
+        })
+    }
+
     /// Start a new instance.
     #[instrument(skip(self, options), fields(image_id = %options.image_id, tenant_id = %options.tenant_id))]
     pub async fn start_instance(
@@ -688,11 +1065,22 @@ impl ManagementSdk {
             return Err(Self::parse_error_response(resp).await);
         };
 
+        // Classified failures, including runner start failures, surface as
+        // typed errors; unclassified failures stay in the result as before.
         if !json.success
-            && let Some(ref error) = json.error
-            && error.contains("not found")
+            && (json.failure.start_failure.is_some()
+                || json
+                    .failure
+                    .error_code
+                    .as_deref()
+                    .is_some_and(|code| code != "INTERNAL"))
         {
-            return Err(SdkError::ImageNotFound(error.clone()));
+            let message = json.error.unwrap_or_default();
+            return Err(json.failure.into_error(
+                "START_FAILED".to_string(),
+                message,
+                Some(("image", &options.image_id)),
+            ));
         }
 
         Ok(StartInstanceResult {
@@ -727,18 +1115,66 @@ impl ManagementSdk {
         let json: SimpleSuccessJson = resp.json().await?;
 
         if !json.success {
-            let error = json.error.unwrap_or_default();
-            if error.contains("not found") {
-                return Err(SdkError::InstanceNotFound(options.instance_id));
-            }
-            return Err(SdkError::Server {
-                code: "STOP_FAILED".to_string(),
-                message: error,
-            });
+            return Err(json.failure.into_error(
+                "STOP_FAILED".to_string(),
+                json.error.unwrap_or_default(),
+                Some(("instance", &options.instance_id)),
+            ));
         }
         Ok(())
     }
 
+    /// Withdraw a start that has not launched yet.
+    ///
+    /// Returns [`CancelPendingStartOutcome::CancelledBeforeStart`] when the
+    /// instance was still pending and is now cancelled, or
+    /// [`CancelPendingStartOutcome::AlreadyStarted`] when the launcher claimed
+    /// it first — in that case use [`cancel_instance`](Self::cancel_instance).
+    /// An unknown instance ID is reported as [`SdkError::NotFound`].
+    #[instrument(skip(self), fields(instance_id = %instance_id))]
+    pub async fn cancel_pending_start(
+        &self,
+        instance_id: &str,
+        reason: Option<&str>,
+    ) -> Result<CancelPendingStartOutcome> {
+        info!("Cancelling pending start");
+
+        let body = serde_json::json!({ "reason": reason });
+
+        let resp = self
+            .client
+            .post(self.url(&format!(
+                "/api/v1/instances/{}/cancel-pending-start",
+                instance_id
+            )))
+            .json(&body)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let json: CancelPendingStartJson = resp.json().await?;
+
+        if !json.success {
+            return Err(json.failure.into_error(
+                "CANCEL_PENDING_START_FAILED".to_string(),
+                json.error.unwrap_or_default(),
+                Some(("instance", instance_id)),
+            ));
+        }
+        json.outcome
+            .as_deref()
+            .and_then(CancelPendingStartOutcome::from_str)
+            .ok_or_else(|| {
+                SdkError::UnexpectedResponse(format!(
+                    "unknown cancel-pending-start outcome: {:?}",
+                    json.outcome
+                ))
+            })
+    }
+
     /// Resume a suspended instance.
     #[instrument(skip(self), fields(instance_id = %instance_id))]
     pub async fn resume_instance(&self, instance_id: &str) -> Result<()> {
@@ -757,14 +1193,11 @@ impl ManagementSdk {
         let json: SimpleSuccessJson = resp.json().await?;
 
         if !json.success {
-            let error = json.error.unwrap_or_default();
-            if error.contains("not found") {
-                return Err(SdkError::InstanceNotFound(instance_id.to_string()));
-            }
-            return Err(SdkError::Server {
-                code: "RESUME_FAILED".to_string(),
-                message: error,
-            });
+            return Err(json.failure.into_error(
+                "RESUME_FAILED".to_string(),
+                json.error.unwrap_or_default(),
+                Some(("instance", instance_id)),
+            ));
         }
         Ok(())
     }
@@ -794,6 +1227,8 @@ impl ManagementSdk {
             "binary": binary_b64,
             "runner_type": runner_type_to_string(options.runner_type),
             "metadata": options.metadata,
+            "warnings": options.warnings,
+            "provenance": options.provenance,
         });
 
         let resp = self
@@ -853,6 +1288,14 @@ impl ManagementSdk {
             form = form.text("metadata", serde_json::to_string(&metadata)?);
         }
 
+        if !options.warnings.is_empty() {
+            form = form.text("warnings", serde_json::to_string(&options.warnings)?);
+        }
+
+        if let Some(provenance) = &options.provenance {
+            form = form.text("provenance", serde_json::to_string(provenance)?);
+        }
+
         if let Some(sha256) = options.sha256 {
             form = form.text("sha256", sha256);
         }
@@ -860,7 +1303,7 @@ impl ManagementSdk {
         let binary_part = reqwest::multipart::Part::bytes(binary_data)
             .file_name("binary")
             .mime_str("application/octet-stream")
-            .map_err(|e| SdkError::Connection(format!("Failed to set MIME type: {}", e)))?;
+            .map_err(|e| SdkError::Protocol(format!("Failed to set MIME type: {}", e)))?;
         form = form.part("binary", binary_part);
 
         let resp = self
@@ -910,19 +1353,7 @@ impl ManagementSdk {
 
         let json: ListImagesJson = resp.json().await?;
 
-        let images = json
-            .images
-            .into_iter()
-            .map(|img| ImageSummary {
-                image_id: img.image_id,
-                tenant_id: img.tenant_id,
-                name: img.name,
-                description: img.description,
-                runner_type: runner_type_from_string(&img.runner_type),
-                created_at: ms_to_datetime(img.created_at_ms),
-                metadata: img.metadata,
-            })
-            .collect();
+        let images = json.images.into_iter().map(ImageSummary::from).collect();
 
         Ok(ListImagesResult {
             images,
@@ -931,14 +1362,58 @@ impl ManagementSdk {
     }
 
     /// Get information about a specific image.
+    ///
+    /// The summary carries the compile warning count; use
+    /// [`get_image_with_warnings`](Self::get_image_with_warnings) for the list.
     #[instrument(skip(self), fields(image_id = %image_id, tenant_id = %tenant_id), level = "debug")]
     pub async fn get_image(&self, image_id: &str, tenant_id: &str) -> Result<Option<ImageSummary>> {
         debug!("Getting image");
+        self.fetch_image(image_id, tenant_id, false, false).await
+    }
+
+    /// Get information about a specific image, including the full list of
+    /// compile warnings stored with it.
+    #[instrument(skip(self), fields(image_id = %image_id, tenant_id = %tenant_id), level = "debug")]
+    pub async fn get_image_with_warnings(
+        &self,
+        image_id: &str,
+        tenant_id: &str,
+    ) -> Result<Option<ImageSummary>> {
+        debug!("Getting image with warnings");
+        self.fetch_image(image_id, tenant_id, true, false).await
+    }
+
+    /// Get information about a specific image, including the build
+    /// provenance (scenario sources and compiler fingerprint) stored with it.
+    #[instrument(skip(self), fields(image_id = %image_id, tenant_id = %tenant_id), level = "debug")]
+    pub async fn get_image_with_provenance(
+        &self,
+        image_id: &str,
+        tenant_id: &str,
+    ) -> Result<Option<ImageSummary>> {
+        debug!("Getting image with provenance");
+        self.fetch_image(image_id, tenant_id, false, true).await
+    }
+
+    async fn fetch_image(
+        &self,
+        image_id: &str,
+        tenant_id: &str,
+        include_warnings: bool,
+        include_provenance: bool,
+    ) -> Result<Option<ImageSummary>> {
+        let mut query = vec![("tenant_id", tenant_id)];
+        if include_warnings {
+            query.push(("include_warnings", "true"));
+        }
+        if include_provenance {
+            query.push(("include_provenance", "true"));
+        }
 
         let resp = self
             .client
             .get(self.url(&format!("/api/v1/images/{}", image_id)))
-            .query(&[("tenant_id", tenant_id)])
+            .query(&query)
             .send()
             .await?;
 
@@ -952,18 +1427,40 @@ impl ManagementSdk {
             return Ok(None);
         }
 
-        match json.image {
-            Some(img) => Ok(Some(ImageSummary {
-                image_id: img.image_id,
-                tenant_id: img.tenant_id,
-                name: img.name,
-                description: img.description,
-                runner_type: runner_type_from_string(&img.runner_type),
-                created_at: ms_to_datetime(img.created_at_ms),
-                metadata: img.metadata,
-            })),
-            None => Ok(None),
+        Ok(json.image.map(ImageSummary::from))
+    }
+
+    /// Get the scenario source an image was compiled from, with the child
+    /// scenarios compiled into it — for diffing or recompiling.
+    ///
+    /// Returns `None` when the image does not exist or was registered
+    /// without a source.
+    #[instrument(skip(self), fields(image_id = %image_id, tenant_id = %tenant_id), level = "debug")]
+    pub async fn get_image_source(
+        &self,
+        image_id: &str,
+        tenant_id: &str,
+    ) -> Result<Option<ImageSource>> {
+        debug!("Getting image source");
+
+        let resp = self
+            .client
+            .get(self.url(&format!("/api/v1/images/{}/source", image_id)))
+            .query(&[("tenant_id", tenant_id)])
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let json: GetImageSourceJson = resp.json().await?;
+
+        if !json.found {
+            return Ok(None);
         }
+
+        Ok(json.source)
     }
 
     /// Delete an image.
@@ -978,25 +1475,18 @@ impl ManagementSdk {
             .send()
             .await?;
 
-        if resp.status().as_u16() == 404 {
-            return Err(SdkError::ImageNotFound(image_id.to_string()));
-        }
-
         if !resp.status().is_success() {
-            return Err(Self::parse_error_response(resp).await);
+            return Err(Self::parse_error_response_for(resp, Some(("image", image_id))).await);
         }
 
         let json: SimpleSuccessJson = resp.json().await?;
 
         if !json.success {
-            let error = json.error.unwrap_or_default();
-            if error.contains("not found") {
-                return Err(SdkError::ImageNotFound(image_id.to_string()));
-            }
-            return Err(SdkError::Server {
-                code: "DELETE_FAILED".to_string(),
-                message: error,
-            });
+            return Err(json.failure.into_error(
+                "DELETE_FAILED".to_string(),
+                json.error.unwrap_or_default(),
+                Some(("image", image_id)),
+            ));
         }
         Ok(())
     }
@@ -1041,25 +1531,20 @@ impl ManagementSdk {
             .send()
             .await?;
 
-        if resp.status().as_u16() == 404 {
-            return Err(SdkError::InstanceNotFound(instance_id.to_string()));
-        }
-
         if !resp.status().is_success() {
-            return Err(Self::parse_error_response(resp).await);
+            return Err(
+                Self::parse_error_response_for(resp, Some(("instance", instance_id))).await,
+            );
         }
 
         let json: SimpleSuccessJson = resp.json().await?;
 
         if !json.success {
-            let error = json.error.unwrap_or_default();
-            if error.contains("not found") {
-                return Err(SdkError::InstanceNotFound(instance_id.to_string()));
-            }
-            return Err(SdkError::Server {
-                code: "SIGNAL_FAILED".to_string(),
-                message: error,
-            });
+            return Err(json.failure.into_error(
+                "SIGNAL_FAILED".to_string(),
+                json.error.unwrap_or_default(),
+                Some(("instance", instance_id)),
+            ));
         }
         Ok(())
     }
@@ -1108,25 +1593,20 @@ impl ManagementSdk {
             .send()
             .await?;
 
-        if resp.status().as_u16() == 404 {
-            return Err(SdkError::InstanceNotFound(instance_id.to_string()));
-        }
-
         if !resp.status().is_success() {
-            return Err(Self::parse_error_response(resp).await);
+            return Err(
+                Self::parse_error_response_for(resp, Some(("instance", instance_id))).await,
+            );
         }
 
         let json: SimpleSuccessJson = resp.json().await?;
 
         if !json.success {
-            let error = json.error.unwrap_or_default();
-            if error.contains("not found") {
-                return Err(SdkError::InstanceNotFound(instance_id.to_string()));
-            }
-            return Err(SdkError::Server {
-                code: "CUSTOM_SIGNAL_FAILED".to_string(),
-                message: error,
-            });
+            return Err(json.failure.into_error(
+                "CUSTOM_SIGNAL_FAILED".to_string(),
+                json.error.unwrap_or_default(),
+                Some(("instance", instance_id)),
+            ));
         }
         Ok(())
     }
@@ -1189,6 +1669,8 @@ impl ManagementSdk {
                 instance_id: cp.instance_id,
                 created_at: ms_to_datetime(cp.created_at_ms),
                 data_size_bytes: cp.data_size_bytes,
+                content_type: cp.content_type,
+                shape: cp.shape,
             })
             .collect();
 
@@ -1268,6 +1750,75 @@ impl ManagementSdk {
         }))
     }
 
+    /// Get a structured preview of a checkpoint's state.
+    ///
+    /// For JSON state this returns the top-level keys with their sizes and a
+    /// depth-limited, redacted preview; the full state is never transferred.
+    /// Returns `None` if the checkpoint does not exist.
+    #[instrument(skip(self, options), fields(instance_id = %instance_id, checkpoint_id = %checkpoint_id), level = "debug")]
+    pub async fn get_checkpoint_preview(
+        &self,
+        instance_id: &str,
+        checkpoint_id: &str,
+        options: CheckpointPreviewOptions,
+    ) -> Result<Option<CheckpointPreview>> {
+        debug!("Getting checkpoint preview");
+
+        let encoded_checkpoint_id = percent_encoding::utf8_percent_encode(
+            checkpoint_id,
+            percent_encoding::NON_ALPHANUMERIC,
+        )
+        .to_string();
+
+        let mut query: Vec<(String, String)> = Vec::new();
+        if let Some(max_depth) = options.max_depth {
+            query.push(("max_depth".to_string(), max_depth.to_string()));
+        }
+        if let Some(max_items) = options.max_items {
+            query.push(("max_items".to_string(), max_items.to_string()));
+        }
+        if let Some(max_string_len) = options.max_string_len {
+            query.push(("max_string_len".to_string(), max_string_len.to_string()));
+        }
+
+        let resp = self
+            .client
+            .get(self.url(&format!(
+                "/api/v1/instances/{}/checkpoints/{}/preview",
+                instance_id, encoded_checkpoint_id
+            )))
+            .query(&query)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let json: CheckpointPreviewResponseJson = resp.json().await?;
+
+        if !json.found {
+            return Ok(None);
+        }
+
+        let preview = json.preview.ok_or_else(|| {
+            SdkError::UnexpectedResponse("Checkpoint preview missing from response".to_string())
+        })?;
+
+        Ok(Some(CheckpointPreview {
+            checkpoint_id: json.checkpoint_id,
+            instance_id: json.instance_id,
+            created_at: ms_to_datetime(json.created_at_ms),
+            content_type: preview.content_type,
+            content_type_sniffed: preview.content_type_sniffed,
+            shape: preview.shape,
+            size_bytes: preview.size_bytes,
+            top_level_keys: preview.top_level_keys,
+            preview: preview.preview,
+            truncated: preview.truncated,
+        }))
+    }
+
     // =========================================================================
     // Events
     // =========================================================================
@@ -1490,6 +2041,135 @@ impl ManagementSdk {
         })
     }
 
+    // =========================================================================
+    // Scenario Simulation
+    // =========================================================================
+
+    /// Predict a scenario's execution path without launching an instance.
+    ///
+    /// Side-effecting steps (agents, embedded workflows, AI agents) return the
+    /// outputs given in `options.stubs`; conditions, switches, and loops are
+    /// evaluated with the runtime's own semantics.
+    #[instrument(skip(self, options), fields(stubs = options.stubs.len()))]
+    pub async fn simulate_scenario(
+        &self,
+        options: SimulateScenarioOptions,
+    ) -> Result<SimulateScenarioResult> {
+        info!("Simulating scenario");
+
+        let resp = self
+            .client
+            .post(self.url("/api/v1/scenarios/simulate"))
+            .json(&options)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        Ok(resp.json().await?)
+    }
+
+    // =========================================================================
+    // Maintenance
+    // =========================================================================
+
+    /// Open or close a core maintenance window.
+    ///
+    /// While a window is open, core refuses instance checkpoint and event
+    /// writes with a retry-after hint (instances wait instead of failing) and
+    /// the wake scheduler leaves sleepers asleep. Returns the window now in
+    /// effect.
+    #[instrument(skip(self, options), fields(enabled = options.enabled))]
+    pub async fn set_maintenance(
+        &self,
+        options: SetMaintenanceOptions,
+    ) -> Result<Option<MaintenanceStatus>> {
+        info!("Setting maintenance mode");
+
+        let resp = self
+            .client
+            .post(self.url("/api/v1/maintenance"))
+            .json(&options)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let json: MaintenanceJson = resp.json().await?;
+        Ok(json.maintenance)
+    }
+
+    /// Get the active core maintenance window, if any.
+    #[instrument(skip(self), level = "debug")]
+    pub async fn get_maintenance(&self) -> Result<Option<MaintenanceStatus>> {
+        debug!("Getting maintenance mode");
+
+        let resp = self
+            .client
+            .get(self.url("/api/v1/maintenance"))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let json: MaintenanceJson = resp.json().await?;
+        Ok(json.maintenance)
+    }
+
+    // =========================================================================
+    // Tenant Settings
+    // =========================================================================
+
+    /// Get a tenant's settings (defaults when never set).
+    #[instrument(skip(self), fields(tenant_id = %tenant_id), level = "debug")]
+    pub async fn get_tenant_settings(&self, tenant_id: &str) -> Result<TenantSettings> {
+        debug!("Getting tenant settings");
+
+        let resp = self
+            .client
+            .get(self.url(&format!("/api/v1/tenants/{}/settings", tenant_id)))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let json: TenantSettingsJson = resp.json().await?;
+        Ok(json.settings)
+    }
+
+    /// Turn strict deployments on (refuse to start images carrying a compile
+    /// warning at or above `severity`) or off (`None`).
+    #[instrument(skip(self), fields(tenant_id = %tenant_id))]
+    pub async fn set_strict_deployment_severity(
+        &self,
+        tenant_id: &str,
+        severity: Option<WarningSeverity>,
+    ) -> Result<TenantSettings> {
+        info!(?severity, "Setting strict deployment severity");
+
+        let resp = self
+            .client
+            .put(self.url(&format!("/api/v1/tenants/{}/settings", tenant_id)))
+            .json(&serde_json::json!({ "strict_deployment_severity": severity }))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let json: TenantSettingsJson = resp.json().await?;
+        Ok(json.settings)
+    }
+
     // =========================================================================
     // Agent Testing
     // =========================================================================
@@ -1603,7 +2283,7 @@ impl ManagementSdk {
         debug!("Getting tenant metrics");
 
         if options.tenant_id.is_empty() {
-            return Err(SdkError::InvalidInput("tenant_id is required".to_string()));
+            return Err(SdkError::invalid_input("tenant_id is required"));
         }
 
         let mut query: Vec<(String, String)> = Vec::new();
@@ -1702,7 +2382,7 @@ impl ManagementSdk {
     ) -> Result<InstanceInfo> {
         let result = self.start_instance(options).await?;
         if !result.success {
-            return Err(SdkError::Server {
+            return Err(SdkError::Internal {
                 code: "START_FAILED".to_string(),
                 message: result.error.unwrap_or_else(|| "Unknown error".to_string()),
             });
@@ -1712,3 +2392,75 @@ impl ManagementSdk {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start_error(body: serde_json::Value) -> SdkError {
+        let json: StartInstanceJson = serde_json::from_value(body).unwrap();
+        json.failure.into_error(
+            "START_FAILED".to_string(),
+            json.error.unwrap_or_default(),
+            Some(("image", "img-1")),
+        )
+    }
+
+    #[test]
+    fn start_failure_reason_becomes_typed_error() {
+        let err = start_error(serde_json::json!({
+            "success": false,
+            "instance_id": "inst-1",
+            "error": "Launch failed [RUNTIME_UNAVAILABLE]: Runtime unavailable (wasmtime): gone",
+            "error_code": "UNAVAILABLE",
+            "start_failure": "RUNTIME_UNAVAILABLE"
+        }));
+        match &err {
+            SdkError::StartFailed { reason, message } => {
+                assert_eq!(*reason, StartFailureReason::RuntimeUnavailable);
+                assert!(message.contains("wasmtime"));
+            }
+            other => panic!("expected StartFailed, got {other:?}"),
+        }
+        assert!(err.is_retryable());
+        assert_eq!(err.code(), "START_FAILED");
+    }
+
+    #[test]
+    fn image_start_failures_are_not_retryable() {
+        let err = start_error(serde_json::json!({
+            "success": false,
+            "error": "Launch failed [IMAGE_BINARY_MISSING]: Binary not found: /x.wasm",
+            "error_code": "NOT_FOUND",
+            "resource": "image",
+            "resource_id": "img-1",
+            "start_failure": "IMAGE_BINARY_MISSING"
+        }));
+        assert!(matches!(
+            err,
+            SdkError::StartFailed {
+                reason: StartFailureReason::ImageBinaryMissing,
+                ..
+            }
+        ));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn unknown_start_failure_reason_is_tolerated() {
+        let err = start_error(serde_json::json!({
+            "success": false,
+            "error": "Launch failed",
+            "error_code": "INTERNAL",
+            "start_failure": "SOMETHING_NEWER"
+        }));
+        assert!(matches!(
+            err,
+            SdkError::StartFailed {
+                reason: StartFailureReason::Unknown,
+                ..
+            }
+        ));
+        assert!(!err.is_retryable());
+    }
+}
@@ -16,6 +16,9 @@ pub struct SdkConfig {
     pub connect_timeout: Duration,
     /// Request timeout.
     pub request_timeout: Duration,
+    /// Caller identity sent as `X-Runtara-Actor` and recorded in the
+    /// environment's audit log.
+    pub actor: Option<String>,
 }
 
 impl Default for SdkConfig {
@@ -24,6 +27,7 @@ impl Default for SdkConfig {
             server_addr: "127.0.0.1:8002".parse().unwrap(), // Environment server default port
             connect_timeout: Duration::from_secs(10),
             request_timeout: Duration::from_secs(30),
+            actor: None,
         }
     }
 }
@@ -45,6 +49,7 @@ impl SdkConfig {
     /// - `RUNTARA_ENVIRONMENT_ADDR`: Server address (default: "127.0.0.1:8002")
     /// - `RUNTARA_CONNECT_TIMEOUT_MS`: Connection timeout in milliseconds (default: 10000)
     /// - `RUNTARA_REQUEST_TIMEOUT_MS`: Request timeout in milliseconds (default: 30000)
+    /// - `RUNTARA_ACTOR`: Caller identity for the audit log (default: unset)
     pub fn from_env() -> Result<Self> {
         let server_addr = std::env::var("RUNTARA_ENVIRONMENT_ADDR")
             .unwrap_or_else(|_| "127.0.0.1:8002".to_string())
@@ -65,6 +70,9 @@ impl SdkConfig {
             server_addr,
             connect_timeout: Duration::from_millis(connect_timeout_ms),
             request_timeout: Duration::from_millis(request_timeout_ms),
+            actor: std::env::var("RUNTARA_ACTOR")
+                .ok()
+                .filter(|a| !a.is_empty()),
         })
     }
 
@@ -85,6 +93,12 @@ impl SdkConfig {
         self.request_timeout = timeout;
         self
     }
+
+    /// Set the caller identity recorded in the audit log.
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -108,10 +122,12 @@ mod tests {
         let config = SdkConfig::new()
             .with_server_addr("192.168.1.100:8000".parse().unwrap())
             .with_connect_timeout(Duration::from_secs(5))
-            .with_request_timeout(Duration::from_secs(60));
+            .with_request_timeout(Duration::from_secs(60))
+            .with_actor("key:ops");
 
         assert_eq!(config.server_addr, "192.168.1.100:8000".parse().unwrap());
         assert_eq!(config.connect_timeout, Duration::from_secs(5));
         assert_eq!(config.request_timeout, Duration::from_secs(60));
+        assert_eq!(config.actor.as_deref(), Some("key:ops"));
     }
 }
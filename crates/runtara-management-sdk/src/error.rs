@@ -1,12 +1,57 @@
 // Copyright (C) 2025 SyncMyOrders Sp. z o.o.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 //! Error types for runtara-management-sdk.
+//!
+//! Environment tags every failed response with a symbolic `error_code`
+//! (`NOT_FOUND`, `CONFLICT`, `QUOTA_EXCEEDED`, `UNAUTHORIZED`, `VALIDATION`,
+//! `UNAVAILABLE`, `INTERNAL`). The client maps it onto a variant here, so
+//! callers match on variants instead of message substrings. The server's
+//! message is always kept inside the variant for logging.
 
+use serde::Deserialize;
 use thiserror::Error;
 
 /// Result type using SdkError.
 pub type Result<T> = std::result::Result<T, SdkError>;
 
+/// One invalid request field reported by the server.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct FieldError {
+    /// Field name as it appears in the request.
+    pub field: String,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+/// Why environment could not start an instance, from the `start_failure`
+/// field of a failed `StartInstance`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StartFailureReason {
+    /// The image's binary is missing; starting it again will fail the same way.
+    ImageBinaryMissing,
+    /// The node could not prepare the instance's run directory.
+    BundleCreationFailed,
+    /// The node's execution runtime is unusable.
+    RuntimeUnavailable,
+    /// A node resource (disk, memory) ran out.
+    ResourceLimit,
+    /// Unclassified, including reasons newer than this SDK.
+    #[serde(other)]
+    Unknown,
+}
+
+impl StartFailureReason {
+    /// Whether the failure is the node's rather than the image's, so starting
+    /// the same image again (possibly elsewhere) may succeed.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            Self::BundleCreationFailed | Self::RuntimeUnavailable | Self::ResourceLimit
+        )
+    }
+}
+
 /// Errors that can occur when using the management SDK.
 #[derive(Debug, Error)]
 pub enum SdkError {
@@ -14,34 +59,85 @@ pub enum SdkError {
     #[error("configuration error: {0}")]
     Config(String),
 
-    /// Connection to runtara-core failed.
-    #[error("connection error: {0}")]
-    Connection(String),
+    /// The request never got a usable answer: connection refused, timeout,
+    /// or the server/dependency is temporarily unavailable.
+    #[error("transport error: {message}")]
+    Transport {
+        /// Underlying error message.
+        message: String,
+        /// Whether retrying the same request may succeed.
+        retryable: bool,
+    },
+
+    /// The addressed resource does not exist.
+    #[error("{resource} not found: {id}")]
+    NotFound {
+        /// Resource kind (`instance`, `image`, `checkpoint`, ...).
+        resource: String,
+        /// Resource identifier.
+        id: String,
+        /// Server message.
+        message: String,
+    },
+
+    /// The request conflicts with the resource's current state.
+    #[error("conflict [{code}]: {message}")]
+    Conflict {
+        /// Operation-specific server code.
+        code: String,
+        /// Server message.
+        message: String,
+    },
+
+    /// A tenant or server limit was hit.
+    #[error("quota exceeded [{code}]: {message}")]
+    QuotaExceeded {
+        /// Operation-specific server code.
+        code: String,
+        /// Server message.
+        message: String,
+    },
+
+    /// The caller is not allowed to perform the operation.
+    #[error("unauthorized [{code}]: {message}")]
+    Unauthorized {
+        /// Operation-specific server code.
+        code: String,
+        /// Server message.
+        message: String,
+    },
+
+    /// The request was rejected as invalid.
+    #[error("invalid input: {message}")]
+    Validation {
+        /// Server (or client-side) message.
+        message: String,
+        /// Offending fields, when the server named them.
+        field_errors: Vec<FieldError>,
+    },
 
-    /// Request timed out.
-    #[error("request timed out after {0}ms")]
-    Timeout(u64),
+    /// The runner could not start the instance.
+    #[error("instance start failed ({reason:?}): {message}")]
+    StartFailed {
+        /// Why it failed; decides [`SdkError::is_retryable`].
+        reason: StartFailureReason,
+        /// Server message.
+        message: String,
+    },
 
-    /// Server returned an error response.
+    /// The server failed for a reason the caller can't act on.
     #[error("server error [{code}]: {message}")]
-    Server { code: String, message: String },
+    Internal {
+        /// Operation-specific server code.
+        code: String,
+        /// Server message.
+        message: String,
+    },
 
     /// Unexpected response from server.
     #[error("unexpected response: {0}")]
     UnexpectedResponse(String),
 
-    /// Instance not found.
-    #[error("instance not found: {0}")]
-    InstanceNotFound(String),
-
-    /// Image not found.
-    #[error("image not found: {0}")]
-    ImageNotFound(String),
-
-    /// Invalid input.
-    #[error("invalid input: {0}")]
-    InvalidInput(String),
-
     /// Serialization/deserialization error.
     #[error("serialization error: {0}")]
     Serialization(String),
@@ -51,6 +147,62 @@ pub enum SdkError {
     Protocol(String),
 }
 
+impl SdkError {
+    /// Whether retrying the same request may succeed.
+    ///
+    /// True for retryable transport failures, quota rejections and start
+    /// failures caused by the node; false for everything that will fail the
+    /// same way again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SdkError::Transport { retryable, .. } => *retryable,
+            SdkError::QuotaExceeded { .. } => true,
+            SdkError::StartFailed { reason, .. } => reason.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Stable symbolic code for this error, matching the server's
+    /// `error_code` vocabulary where one applies.
+    pub fn code(&self) -> &'static str {
+        match self {
+            SdkError::Config(_) => "CONFIG",
+            SdkError::Transport { .. } => "TRANSPORT",
+            SdkError::NotFound { .. } => "NOT_FOUND",
+            SdkError::Conflict { .. } => "CONFLICT",
+            SdkError::QuotaExceeded { .. } => "QUOTA_EXCEEDED",
+            SdkError::Unauthorized { .. } => "UNAUTHORIZED",
+            SdkError::Validation { .. } => "VALIDATION",
+            SdkError::StartFailed { .. } => "START_FAILED",
+            SdkError::Internal { .. } => "INTERNAL",
+            SdkError::UnexpectedResponse(_) => "UNEXPECTED_RESPONSE",
+            SdkError::Serialization(_) => "SERIALIZATION",
+            SdkError::Protocol(_) => "PROTOCOL",
+        }
+    }
+
+    /// Client-side validation failure with no field detail.
+    pub(crate) fn invalid_input(message: impl Into<String>) -> Self {
+        SdkError::Validation {
+            message: message.into(),
+            field_errors: Vec::new(),
+        }
+    }
+
+    /// `resource` `id` was not found.
+    pub(crate) fn not_found(
+        resource: impl Into<String>,
+        id: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        SdkError::NotFound {
+            resource: resource.into(),
+            id: id.into(),
+            message: message.into(),
+        }
+    }
+}
+
 impl From<serde_json::Error> for SdkError {
     fn from(err: serde_json::Error) -> Self {
         SdkError::Serialization(err.to_string())
@@ -59,12 +211,24 @@ impl From<serde_json::Error> for SdkError {
 
 impl From<std::io::Error> for SdkError {
     fn from(err: std::io::Error) -> Self {
-        SdkError::Connection(err.to_string())
+        SdkError::Transport {
+            message: err.to_string(),
+            retryable: true,
+        }
     }
 }
 
 impl From<reqwest::Error> for SdkError {
     fn from(err: reqwest::Error) -> Self {
-        SdkError::Connection(err.to_string())
+        // Connect failures and timeouts never reached (or never finished on)
+        // the server; body decode errors mean a malformed answer.
+        if err.is_decode() {
+            return SdkError::UnexpectedResponse(err.to_string());
+        }
+        let retryable = err.is_connect() || err.is_timeout() || err.is_request();
+        SdkError::Transport {
+            message: err.to_string(),
+            retryable,
+        }
     }
 }
@@ -10,7 +10,7 @@
 //! # Architecture
 //!
 //! The Management SDK talks ONLY to runtara-environment:
-//! - Image management (register, list, delete)
+//! - Image management (register, list, delete, build provenance)
 //! - Instance lifecycle (start, stop, resume, status)
 //! - Signals (pause, cancel - proxied to runtara-core by Environment)
 //!
@@ -50,15 +50,20 @@ mod types;
 
 pub use client::ManagementSdk;
 pub use config::SdkConfig;
-pub use error::{Result, SdkError};
+pub use error::{FieldError, Result, SdkError, StartFailureReason};
 pub use types::{
-    AgentInfo, CapabilityField, CapabilityInfo, Checkpoint, CheckpointSummary, EventSortOrder,
-    EventSummary, GetTenantMetricsOptions, HealthStatus, ImageSummary, InstanceInfo,
-    InstanceStatus, InstanceSummary, ListCheckpointsOptions, ListCheckpointsResult,
-    ListEventsOptions, ListEventsResult, ListImagesOptions, ListImagesResult, ListInstancesOptions,
-    ListInstancesOrder, ListInstancesResult, ListStepSummariesOptions, ListStepSummariesResult,
+    AgentInfo, AuditLogEntry, BranchDecision, CancelPendingStartOutcome, CapabilityField,
+    CapabilityInfo, Checkpoint, CheckpointPreview, CheckpointPreviewKey, CheckpointPreviewOptions,
+    CheckpointSummary, EventSortOrder, EventSummary, GetTenantMetricsOptions, HealthStatus,
+    ImageChildSource, ImageProvenance, ImageSource, ImageSummary, ImageWarning, InstanceInfo,
+    InstanceStatus, InstanceSummary, ListAuditLogOptions, ListAuditLogResult,
+    ListCheckpointsOptions, ListCheckpointsResult, ListEventsOptions, ListEventsResult,
+    ListImagesOptions, ListImagesResult, ListInstancesOptions, ListInstancesOrder,
+    ListInstancesResult, ListStepSummariesOptions, ListStepSummariesResult, MaintenanceStatus,
     MetricsBucket, MetricsGranularity, RegisterImageOptions, RegisterImageResult,
-    RegisterImageStreamOptions, RunnerType, ScopeInfo, SignalType, StartInstanceOptions,
-    StartInstanceResult, StepSortOrder, StepStatus, StepSummary, StopInstanceOptions,
-    TenantMetricsResult, TerminationReason, TestCapabilityOptions, TestCapabilityResult,
+    RegisterImageStreamOptions, ReportedVersions, RunnerType, ScopeInfo, SetMaintenanceOptions,
+    SignalType, SimulateScenarioOptions, SimulateScenarioResult, SimulatedFailure, SimulatedStep,
+    StartInstanceOptions, StartInstanceResult, StepSortOrder, StepStatus, StepSummary,
+    StopInstanceOptions, TenantMetricsResult, TenantSettings, TerminationReason,
+    TestCapabilityOptions, TestCapabilityResult, WarningSeverity,
 };
@@ -163,6 +163,9 @@ pub struct HealthStatus {
     pub uptime_ms: i64,
     /// Number of active instances.
     pub active_instances: u32,
+    /// Active core maintenance window, if any.
+    #[serde(default)]
+    pub maintenance: Option<MaintenanceStatus>,
 }
 
 /// Instance status response with full details.
@@ -201,6 +204,10 @@ pub struct InstanceInfo {
     pub output: Option<serde_json::Value>,
     /// Error message (if failed).
     pub error: Option<String>,
+    /// Machine-readable code of a structured error, e.g. the `code` of the
+    /// Error step that failed the workflow. Distinguishes deliberate business
+    /// aborts from crashes, which carry no code.
+    pub error_code: Option<String>,
     /// Raw stderr output from the container (for debugging/logging).
     /// This is separate from `error` to allow product to decide whether to show it to users.
     pub stderr: Option<String>,
@@ -222,6 +229,34 @@ pub struct InstanceInfo {
     pub termination_reason: Option<TerminationReason>,
     /// Process exit code (if available).
     pub exit_code: Option<i32>,
+
+    // Version handshake
+    /// Versions the workflow binary reported when it registered with core.
+    /// `None` for binaries built before the version handshake.
+    pub reported_versions: Option<ReportedVersions>,
+
+    /// Change counter for status and checkpoint. Pass it to
+    /// [`ManagementSdk::wait_for_status_change`](crate::ManagementSdk::wait_for_status_change)
+    /// to wait for the next transition. 0 from servers without long-poll.
+    pub status_version: i64,
+}
+
+/// Protocol/SDK/build versions a workflow binary reports to core at
+/// registration. Useful for spotting deployed images built against an old SDK.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportedVersions {
+    /// Instance-protocol version.
+    #[serde(default)]
+    pub protocol_version: Option<u32>,
+    /// `runtara-sdk` version compiled into the binary.
+    #[serde(default)]
+    pub sdk_version: Option<String>,
+    /// DSL version the image was compiled from.
+    #[serde(default)]
+    pub dsl_version: Option<String>,
+    /// Checksum of the stdlib the image was linked against.
+    #[serde(default)]
+    pub stdlib_checksum: Option<String>,
 }
 
 /// Summary of an instance (used in list results).
@@ -243,6 +278,8 @@ pub struct InstanceSummary {
     pub finished_at: Option<DateTime<Utc>>,
     /// Whether the instance has an error.
     pub has_error: bool,
+    /// Machine-readable code of a structured error, if any.
+    pub error_code: Option<String>,
 }
 
 /// Result of listing instances.
@@ -310,6 +347,14 @@ impl StartInstanceOptions {
         self.env.insert(key.into(), value.into());
         self
     }
+
+    /// Cap the step debug events a debug-mode run emits in full. Past half
+    /// the budget, steps are sampled at an increasing rate and the skipped
+    /// ranges are summarized as `debug_events_suppressed` events. The budget
+    /// carries over when the instance resumes.
+    pub fn with_debug_event_budget(self, max_events: u64) -> Self {
+        self.with_env_var("DEBUG_EVENT_BUDGET", max_events.to_string())
+    }
 }
 
 /// Result of starting an instance.
@@ -363,6 +408,37 @@ impl StopInstanceOptions {
     }
 }
 
+/// Result of withdrawing a start that has not launched yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CancelPendingStartOutcome {
+    /// The instance was still pending; it is now cancelled and will never run.
+    CancelledBeforeStart,
+    /// The instance had already been claimed for launch. Cancel it through the
+    /// normal signal path (`cancel_instance`) instead.
+    AlreadyStarted,
+}
+
+impl CancelPendingStartOutcome {
+    /// Parse from string (protocol uses string representation).
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "cancelled_before_start" => Some(Self::CancelledBeforeStart),
+            "already_started" => Some(Self::AlreadyStarted),
+            _ => None,
+        }
+    }
+
+    /// Convert to string for protocol.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CancelledBeforeStart => "cancelled_before_start",
+            Self::AlreadyStarted => "already_started",
+        }
+    }
+}
+
 /// Sort order for listing instances.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -492,6 +568,119 @@ impl ListInstancesOptions {
     }
 }
 
+/// Options for listing audit log entries.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListAuditLogOptions {
+    /// Filter by actor (exact match).
+    pub actor: Option<String>,
+    /// Filter by operation (e.g. `instance.start`).
+    pub operation: Option<String>,
+    /// Filter by target: matches the image ID or the instance ID.
+    pub target_id: Option<String>,
+    /// Filter by tenant ID.
+    pub tenant_id: Option<String>,
+    /// Filter by created_at >= value.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Filter by created_at < value.
+    pub created_before: Option<DateTime<Utc>>,
+    /// Maximum results to return.
+    pub limit: u32,
+    /// Pagination offset.
+    pub offset: u32,
+}
+
+impl ListAuditLogOptions {
+    /// Create new options.
+    pub fn new() -> Self {
+        Self {
+            limit: 100,
+            ..Default::default()
+        }
+    }
+
+    /// Filter by actor.
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    /// Filter by operation.
+    pub fn with_operation(mut self, operation: impl Into<String>) -> Self {
+        self.operation = Some(operation.into());
+        self
+    }
+
+    /// Filter by target image or instance ID.
+    pub fn with_target_id(mut self, target_id: impl Into<String>) -> Self {
+        self.target_id = Some(target_id.into());
+        self
+    }
+
+    /// Filter by tenant ID.
+    pub fn with_tenant_id(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Filter by created_at >= value.
+    pub fn with_created_after(mut self, created_after: DateTime<Utc>) -> Self {
+        self.created_after = Some(created_after);
+        self
+    }
+
+    /// Filter by created_at < value.
+    pub fn with_created_before(mut self, created_before: DateTime<Utc>) -> Self {
+        self.created_before = Some(created_before);
+        self
+    }
+
+    /// Set the limit.
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set the offset.
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// A recorded management operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    /// Sequential entry ID.
+    pub audit_id: i64,
+    /// When the operation was recorded.
+    pub created_at: DateTime<Utc>,
+    /// Who performed it (`anonymous` when the caller sent no identity).
+    pub actor: String,
+    /// What was done (e.g. `image.register`, `instance.stop`).
+    pub operation: String,
+    /// Tenant the target belongs to, when known.
+    pub tenant_id: Option<String>,
+    /// Target image.
+    pub image_id: Option<String>,
+    /// Target instance.
+    pub instance_id: Option<String>,
+    /// Request parameters, with secrets redacted.
+    pub params: serde_json::Value,
+    /// Whether the operation succeeded.
+    pub succeeded: bool,
+    /// Failure message when it did not.
+    pub error: Option<String>,
+}
+
+/// Result of listing audit log entries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListAuditLogResult {
+    /// Entries, newest first.
+    pub entries: Vec<AuditLogEntry>,
+    /// Total number of entries matching the filters.
+    pub total_count: u32,
+}
+
 /// Runner type for images.
 /// Type of runner. Only `Wasm` exists today; the OCI and native
 /// variants were removed in Phase 3 step 11. The enum is kept (rather
@@ -517,6 +706,77 @@ impl From<RunnerType> for i32 {
     }
 }
 
+/// Severity of an image's compile warning, ordered least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WarningSeverity {
+    /// Informational only.
+    Info,
+    /// Likely authoring mistake.
+    Warning,
+    /// Tolerated by the compiler but expected to misbehave at runtime.
+    Error,
+}
+
+/// A compile warning stored with an image.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageWarning {
+    /// Stable warning code (e.g. `W090`).
+    pub code: String,
+    /// Step the warning is about, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_id: Option<String>,
+    /// Human-readable message.
+    pub message: String,
+    /// Severity of the warning.
+    pub severity: WarningSeverity,
+}
+
+/// Build provenance stored with an image: what it was compiled from and by.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImageProvenance {
+    /// Scenario (execution graph) JSON the image was compiled from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<serde_json::Value>,
+    /// Child scenarios compiled into the image.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub child_sources: Vec<ImageChildSource>,
+    /// Content hash of the scenario graph.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph_hash: Option<String>,
+    /// `runtara-workflows` version that compiled the image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler_version: Option<String>,
+    /// `rustc --version` of the toolchain that built the compiler.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rustc_version: Option<String>,
+    /// Checksum over the agent/stdlib components linked into the image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_library_checksum: Option<String>,
+}
+
+/// Source of one child scenario compiled into an image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageChildSource {
+    /// Child workflow ID.
+    pub workflow_id: String,
+    /// Resolved child workflow version.
+    pub version: i32,
+    /// Child scenario JSON.
+    pub source: serde_json::Value,
+}
+
+/// The scenario sources an image was compiled from
+/// (see [`ManagementSdk::get_image_source`](crate::ManagementSdk::get_image_source)).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageSource {
+    /// Scenario the image was compiled from.
+    pub source: serde_json::Value,
+    /// Child scenarios compiled into the image.
+    #[serde(default)]
+    pub child_sources: Vec<ImageChildSource>,
+}
+
 /// Options for registering an image.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RegisterImageOptions {
@@ -532,6 +792,12 @@ pub struct RegisterImageOptions {
     pub runner_type: RunnerType,
     /// Optional metadata (JSON).
     pub metadata: Option<serde_json::Value>,
+    /// Compile warnings to persist with the image.
+    #[serde(default)]
+    pub warnings: Vec<ImageWarning>,
+    /// Build provenance to store with the image.
+    #[serde(default)]
+    pub provenance: Option<ImageProvenance>,
 }
 
 impl RegisterImageOptions {
@@ -562,6 +828,18 @@ impl RegisterImageOptions {
         self.metadata = Some(metadata);
         self
     }
+
+    /// Set the compile warnings to persist with the image.
+    pub fn with_warnings(mut self, warnings: Vec<ImageWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Set the build provenance to store with the image.
+    pub fn with_provenance(mut self, provenance: ImageProvenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
 }
 
 /// Result of registering an image.
@@ -594,6 +872,10 @@ pub struct RegisterImageStreamOptions {
     pub metadata: Option<serde_json::Value>,
     /// Optional SHA256 checksum for verification.
     pub sha256: Option<String>,
+    /// Compile warnings to persist with the image.
+    pub warnings: Vec<ImageWarning>,
+    /// Build provenance to store with the image.
+    pub provenance: Option<ImageProvenance>,
 }
 
 impl RegisterImageStreamOptions {
@@ -607,6 +889,8 @@ impl RegisterImageStreamOptions {
             runner_type: RunnerType::default(),
             metadata: None,
             sha256: None,
+            warnings: Vec::new(),
+            provenance: None,
         }
     }
 
@@ -633,6 +917,18 @@ impl RegisterImageStreamOptions {
         self.sha256 = Some(sha256.into());
         self
     }
+
+    /// Set the compile warnings to persist with the image.
+    pub fn with_warnings(mut self, warnings: Vec<ImageWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Set the build provenance to store with the image.
+    pub fn with_provenance(mut self, provenance: ImageProvenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
 }
 
 /// Summary of an image (used in list results).
@@ -653,6 +949,17 @@ pub struct ImageSummary {
     /// Optional metadata stored with the image.
     #[serde(default)]
     pub metadata: Option<serde_json::Value>,
+    /// Number of compile warnings stored with the image.
+    #[serde(default)]
+    pub warning_count: usize,
+    /// The stored warnings; only present when requested
+    /// (see [`ManagementSdk::get_image_with_warnings`](crate::ManagementSdk::get_image_with_warnings)).
+    #[serde(default)]
+    pub warnings: Option<Vec<ImageWarning>>,
+    /// The stored build provenance; only present when requested
+    /// (see [`ManagementSdk::get_image_with_provenance`](crate::ManagementSdk::get_image_with_provenance)).
+    #[serde(default)]
+    pub provenance: Option<ImageProvenance>,
 }
 
 /// Options for listing images.
@@ -825,6 +1132,189 @@ pub struct CapabilityField {
     pub example: Option<String>,
 }
 
+// ============================================================================
+// Scenario Simulation Types
+// ============================================================================
+
+/// Options for simulating a scenario's control flow.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimulateScenarioOptions {
+    /// Workflow definition JSON (full workflow or bare execution graph).
+    pub scenario: serde_json::Value,
+    /// Input payload, shaped like a start-instance input.
+    pub input: serde_json::Value,
+    /// Stubbed outputs for side-effecting steps, keyed by step ID.
+    pub stubs: std::collections::HashMap<String, serde_json::Value>,
+    /// Wall-clock budget in milliseconds (server default 5000, max 30000).
+    pub timeout_ms: Option<u64>,
+}
+
+impl SimulateScenarioOptions {
+    /// Create new options for a scenario definition.
+    pub fn new(scenario: serde_json::Value) -> Self {
+        Self {
+            scenario,
+            ..Default::default()
+        }
+    }
+
+    /// Set the input payload.
+    pub fn with_input(mut self, input: serde_json::Value) -> Self {
+        self.input = input;
+        self
+    }
+
+    /// Stub one step's output.
+    pub fn with_stub(mut self, step_id: impl Into<String>, output: serde_json::Value) -> Self {
+        self.stubs.insert(step_id.into(), output);
+        self
+    }
+
+    /// Replace all stubbed outputs.
+    pub fn with_stubs(
+        mut self,
+        stubs: std::collections::HashMap<String, serde_json::Value>,
+    ) -> Self {
+        self.stubs = stubs;
+        self
+    }
+
+    /// Set the wall-clock budget in milliseconds.
+    pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = Some(timeout_ms);
+        self
+    }
+}
+
+/// One routing decision taken during a simulation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchDecision {
+    /// Decision kind: `condition`, `switch`, `edge` or `while`.
+    pub kind: String,
+    /// Evaluated value (condition result or switched-on value).
+    pub value: serde_json::Value,
+    /// Selected label (`true`/`false`, a Switch route, `continue`/`exit`/`max_iterations`).
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Edge target the decision concerns.
+    #[serde(default)]
+    pub to_step: Option<String>,
+}
+
+/// One step executed by a simulation, in execution order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedStep {
+    /// Step ID.
+    pub step_id: String,
+    /// Step type.
+    pub step_type: String,
+    /// Indices of the enclosing Split/While iterations, outermost first.
+    #[serde(default)]
+    pub loop_indices: Vec<u32>,
+    /// Resolved step inputs.
+    pub inputs: serde_json::Value,
+    /// Step output.
+    pub output: serde_json::Value,
+    /// Whether the output came from the supplied stubs.
+    #[serde(default)]
+    pub stubbed: bool,
+    /// Routing decisions taken at this step.
+    #[serde(default)]
+    pub decisions: Vec<BranchDecision>,
+}
+
+/// Unhandled failure a simulation ended with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedFailure {
+    /// Step that failed.
+    pub step_id: String,
+    /// Error code.
+    pub code: String,
+    /// Human-readable reason.
+    pub message: String,
+}
+
+/// Result of simulating a scenario.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulateScenarioResult {
+    /// Executed steps in order.
+    pub steps: Vec<SimulatedStep>,
+    /// Workflow output from the reached Finish step.
+    #[serde(default)]
+    pub output: Option<serde_json::Value>,
+    /// Set when the run ends in an unhandled failure.
+    #[serde(default)]
+    pub failure: Option<SimulatedFailure>,
+}
+
+// ============================================================================
+// Maintenance Types
+// ============================================================================
+
+/// Options for opening or closing a core maintenance window.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SetMaintenanceOptions {
+    /// Whether maintenance should be on.
+    pub enabled: bool,
+    /// Expected window length in milliseconds (server default 60s, max 1h).
+    pub expected_duration_ms: Option<u64>,
+    /// Reason echoed to instances whose writes are refused.
+    pub message: String,
+}
+
+impl SetMaintenanceOptions {
+    /// Open a window with the given reason.
+    pub fn enable(message: impl Into<String>) -> Self {
+        Self {
+            enabled: true,
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Close the current window.
+    pub fn disable() -> Self {
+        Self::default()
+    }
+
+    /// Set the expected window length; the window expires on its own after it.
+    pub fn with_expected_duration(mut self, duration: std::time::Duration) -> Self {
+        self.expected_duration_ms = Some(duration.as_millis() as u64);
+        self
+    }
+}
+
+/// An active core maintenance window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceStatus {
+    /// Operator-supplied reason.
+    pub message: String,
+    /// When the window was opened.
+    pub started_at: DateTime<Utc>,
+    /// When the window expires on its own.
+    pub ends_at: DateTime<Utc>,
+    /// Milliseconds until the window expires.
+    pub retry_after_ms: u64,
+}
+
+// ============================================================================
+// Tenant Settings Types
+// ============================================================================
+
+/// Per-tenant environment settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantSettings {
+    /// Tenant the settings belong to.
+    pub tenant_id: String,
+    /// Strict deployments: instances of images carrying a compile warning at
+    /// or above this severity are refused. `None` starts every image.
+    #[serde(default)]
+    pub strict_deployment_severity: Option<WarningSeverity>,
+    /// When the settings were last changed (`None` when never stored).
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
 // ============================================================================
 // Checkpoint Types
 // ============================================================================
@@ -892,6 +1382,15 @@ pub struct CheckpointSummary {
     pub created_at: DateTime<Utc>,
     /// Size of checkpoint data in bytes (for UI display).
     pub data_size_bytes: u64,
+    /// Content type of the state (`application/json`,
+    /// `application/octet-stream`). Sniffed by the server when the writer
+    /// supplied no hint; `None` only when talking to an older server.
+    #[serde(default)]
+    pub content_type: Option<String>,
+    /// Shape tag supplied by the writer (`step_output`, `steps_context`,
+    /// `durable_result`, `scenario_vars`), if any.
+    #[serde(default)]
+    pub shape: Option<String>,
 }
 
 /// Result of listing checkpoints.
@@ -920,6 +1419,82 @@ pub struct Checkpoint {
     pub data: serde_json::Value,
 }
 
+/// Options for previewing a checkpoint.
+///
+/// Unset limits use the server defaults; the server also caps every limit.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckpointPreviewOptions {
+    /// Nesting depth below which objects and arrays are summarized.
+    pub max_depth: Option<u32>,
+    /// Maximum array elements / object entries kept per level.
+    pub max_items: Option<u32>,
+    /// Maximum string length (in characters) before truncation.
+    pub max_string_len: Option<u32>,
+}
+
+impl CheckpointPreviewOptions {
+    /// Create new options with defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the depth limit.
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Set the per-level item limit.
+    pub fn with_max_items(mut self, max_items: u32) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Set the string length limit.
+    pub fn with_max_string_len(mut self, max_string_len: u32) -> Self {
+        self.max_string_len = Some(max_string_len);
+        self
+    }
+}
+
+/// A top-level key of a JSON checkpoint state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointPreviewKey {
+    /// Key name.
+    pub key: String,
+    /// JSON type of the value (`object`, `array`, `string`, ...).
+    pub value_type: String,
+    /// Size of the value serialized as compact JSON.
+    pub size_bytes: u64,
+    /// Whether the value is redacted in the preview.
+    pub redacted: bool,
+}
+
+/// Structured preview of a checkpoint's state, without the full data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointPreview {
+    /// Checkpoint ID.
+    pub checkpoint_id: String,
+    /// Instance ID this checkpoint belongs to.
+    pub instance_id: String,
+    /// When the checkpoint was created.
+    pub created_at: DateTime<Utc>,
+    /// Content type of the state (writer hint or sniffed).
+    pub content_type: String,
+    /// Whether `content_type` was sniffed rather than supplied by the writer.
+    pub content_type_sniffed: bool,
+    /// Shape tag supplied by the writer, if any.
+    pub shape: Option<String>,
+    /// Size of the full state in bytes.
+    pub size_bytes: u64,
+    /// Top-level keys when the state is a JSON object.
+    pub top_level_keys: Vec<CheckpointPreviewKey>,
+    /// Depth-limited, redacted preview. `None` for non-JSON state.
+    pub preview: Option<serde_json::Value>,
+    /// Whether anything was cut from `preview`.
+    pub truncated: bool,
+}
+
 // ============================================================================
 // Event Types
 // ============================================================================
@@ -1639,6 +2214,19 @@ mod tests {
         assert!(opts.timeout_seconds.is_none());
     }
 
+    #[test]
+    fn test_start_instance_options_debug_event_budget() {
+        let opts = StartInstanceOptions::new("image-123", "tenant-1")
+            .with_env_var("DEBUG_MODE", "true")
+            .with_debug_event_budget(500);
+
+        assert_eq!(
+            opts.env.get("DEBUG_EVENT_BUDGET").map(String::as_str),
+            Some("500")
+        );
+        assert_eq!(opts.env.get("DEBUG_MODE").map(String::as_str), Some("true"));
+    }
+
     // ========================================================================
     // StopInstanceOptions tests
     // ========================================================================
@@ -1834,6 +2422,131 @@ mod tests {
         assert_eq!(opts.offset, 0);
     }
 
+    #[test]
+    fn test_register_image_options_with_warnings() {
+        let warning = ImageWarning {
+            code: "W090".to_string(),
+            step_id: Some("finish".to_string()),
+            message: "large literal".to_string(),
+            severity: WarningSeverity::Warning,
+        };
+        let opts = RegisterImageOptions::new("tenant", "image", vec![1])
+            .with_warnings(vec![warning.clone()]);
+        assert_eq!(opts.warnings, vec![warning.clone()]);
+
+        let stream =
+            RegisterImageStreamOptions::new("tenant", "image", 1).with_warnings(vec![warning]);
+        assert_eq!(stream.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_register_image_options_with_provenance() {
+        let provenance = ImageProvenance {
+            source: Some(json!({"entryPoint": "finish"})),
+            child_sources: vec![ImageChildSource {
+                workflow_id: "child".to_string(),
+                version: 2,
+                source: json!({"entryPoint": "done"}),
+            }],
+            graph_hash: Some("abc".to_string()),
+            compiler_version: Some("8.6.3".to_string()),
+            ..Default::default()
+        };
+        let opts = RegisterImageOptions::new("tenant", "image", vec![1])
+            .with_provenance(provenance.clone());
+        assert_eq!(opts.provenance.as_ref(), Some(&provenance));
+
+        let stream = RegisterImageStreamOptions::new("tenant", "image", 1)
+            .with_provenance(provenance.clone());
+        assert_eq!(stream.provenance, Some(provenance.clone()));
+
+        // Unset fingerprint fields stay off the wire.
+        let wire = serde_json::to_value(&provenance).unwrap();
+        assert!(wire.get("rustc_version").is_none());
+        assert_eq!(wire["child_sources"][0]["version"], 2);
+    }
+
+    #[test]
+    fn test_image_summary_warning_fields_default() {
+        let summary: ImageSummary = serde_json::from_value(json!({
+            "image_id": "img",
+            "tenant_id": "t",
+            "name": "n",
+            "description": null,
+            "runner_type": "wasm",
+            "created_at": "2026-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(summary.warning_count, 0);
+        assert!(summary.warnings.is_none());
+        assert!(summary.provenance.is_none());
+    }
+
+    #[test]
+    fn test_warning_severity_ordering_and_wire_format() {
+        assert!(WarningSeverity::Info < WarningSeverity::Warning);
+        assert!(WarningSeverity::Warning < WarningSeverity::Error);
+        assert_eq!(json!(WarningSeverity::Error), json!("error"));
+    }
+
+    // ========================================================================
+    // SetMaintenanceOptions tests
+    // ========================================================================
+
+    #[test]
+    fn test_set_maintenance_options_builder() {
+        let opts = SetMaintenanceOptions::enable("pg failover")
+            .with_expected_duration(std::time::Duration::from_secs(90));
+        assert!(opts.enabled);
+        assert_eq!(opts.message, "pg failover");
+        assert_eq!(opts.expected_duration_ms, Some(90_000));
+
+        let off = SetMaintenanceOptions::disable();
+        assert!(!off.enabled);
+        assert_eq!(off.expected_duration_ms, None);
+    }
+
+    // ========================================================================
+    // SimulateScenarioOptions tests
+    // ========================================================================
+
+    #[test]
+    fn test_simulate_scenario_options_builder() {
+        let opts = SimulateScenarioOptions::new(json!({"steps": {}}))
+            .with_input(json!({"data": {"amount": 5}}))
+            .with_stub("fetch", json!({"status": 200}))
+            .with_timeout(1000);
+
+        assert_eq!(opts.scenario, json!({"steps": {}}));
+        assert_eq!(opts.input["data"]["amount"], 5);
+        assert_eq!(opts.stubs["fetch"], json!({"status": 200}));
+        assert_eq!(opts.timeout_ms, Some(1000));
+    }
+
+    #[test]
+    fn test_simulate_scenario_result_deserializes_sparse_steps() {
+        let result: SimulateScenarioResult = serde_json::from_value(json!({
+            "steps": [
+                {"step_id": "fetch", "step_type": "Agent", "inputs": {}, "output": {}, "stubbed": true},
+                {
+                    "step_id": "check",
+                    "step_type": "Conditional",
+                    "inputs": null,
+                    "output": {"result": true},
+                    "decisions": [{"kind": "condition", "value": true, "label": "true"}]
+                }
+            ],
+            "output": {"ok": true}
+        }))
+        .unwrap();
+
+        assert!(result.steps[0].stubbed);
+        assert!(result.steps[0].loop_indices.is_empty());
+        assert_eq!(result.steps[1].decisions[0].label.as_deref(), Some("true"));
+        assert!(result.steps[1].decisions[0].to_step.is_none());
+        assert!(result.failure.is_none());
+    }
+
     // ========================================================================
     // TestCapabilityOptions tests
     // ========================================================================
@@ -2041,6 +2754,7 @@ mod tests {
             input: None,
             output: Some(json!({"result": "success"})),
             error: None,
+            error_code: None,
             stderr: None,
             retry_count: 0,
             max_retries: 3,
@@ -2048,6 +2762,8 @@ mod tests {
             cpu_usage_usec: Some(1_500_000),      // 1.5 seconds
             termination_reason: Some(TerminationReason::Completed),
             exit_code: Some(0),
+            reported_versions: None,
+            status_version: 0,
         };
 
         assert_eq!(info.memory_peak_bytes, Some(536_870_912));
@@ -2072,6 +2788,7 @@ mod tests {
             input: Some(json!({"key": "value"})),
             output: None,
             error: None,
+            error_code: None,
             stderr: None,
             retry_count: 0,
             max_retries: 3,
@@ -2079,6 +2796,8 @@ mod tests {
             cpu_usage_usec: None,
             termination_reason: None, // Running, no termination yet
             exit_code: None,
+            reported_versions: None,
+            status_version: 0,
         };
 
         assert!(info.memory_peak_bytes.is_none());
@@ -2103,6 +2822,7 @@ mod tests {
             input: None,
             output: Some(json!("done")),
             error: None,
+            error_code: None,
             stderr: None,
             retry_count: 1,
             max_retries: 3,
@@ -2110,6 +2830,8 @@ mod tests {
             cpu_usage_usec: Some(5_000_000),        // 5 seconds
             termination_reason: Some(TerminationReason::Completed),
             exit_code: Some(0),
+            reported_versions: None,
+            status_version: 0,
         };
 
         let json_str = serde_json::to_string(&info).unwrap();
@@ -2141,6 +2863,7 @@ mod tests {
             input: None,
             output: None,
             error: Some("Connection refused".to_string()),
+            error_code: None,
             stderr: Some("thread 'main' panicked at 'assertion failed'".to_string()),
             retry_count: 3,
             max_retries: 3,
@@ -2148,6 +2871,8 @@ mod tests {
             cpu_usage_usec: None,
             termination_reason: Some(TerminationReason::ApplicationError),
             exit_code: Some(1),
+            reported_versions: None,
+            status_version: 0,
         };
 
         assert_eq!(info.error, Some("Connection refused".to_string()));
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 //! Error type tests for runtara-management-sdk.
 
-use runtara_management_sdk::SdkError;
+use runtara_management_sdk::{FieldError, SdkError};
 
 #[test]
 fn test_config_error_display() {
@@ -12,22 +12,18 @@ fn test_config_error_display() {
 }
 
 #[test]
-fn test_connection_error_display() {
-    let err = SdkError::Connection("connection refused".to_string());
-    assert!(err.to_string().contains("connection error"));
+fn test_transport_error_display() {
+    let err = SdkError::Transport {
+        message: "connection refused".to_string(),
+        retryable: true,
+    };
+    assert!(err.to_string().contains("transport error"));
     assert!(err.to_string().contains("connection refused"));
 }
 
 #[test]
-fn test_timeout_error_display() {
-    let err = SdkError::Timeout(5000);
-    assert!(err.to_string().contains("timed out"));
-    assert!(err.to_string().contains("5000"));
-}
-
-#[test]
-fn test_server_error_display() {
-    let err = SdkError::Server {
+fn test_internal_error_display() {
+    let err = SdkError::Internal {
         code: "500".to_string(),
         message: "Internal error".to_string(),
     };
@@ -45,24 +41,92 @@ fn test_unexpected_response_error_display() {
 }
 
 #[test]
-fn test_instance_not_found_error_display() {
-    let err = SdkError::InstanceNotFound("inst-123".to_string());
+fn test_not_found_error_display() {
+    let err = SdkError::NotFound {
+        resource: "instance".to_string(),
+        id: "inst-123".to_string(),
+        message: "Instance not found".to_string(),
+    };
     assert!(err.to_string().contains("instance not found"));
     assert!(err.to_string().contains("inst-123"));
 }
 
 #[test]
-fn test_image_not_found_error_display() {
-    let err = SdkError::ImageNotFound("img-456".to_string());
-    assert!(err.to_string().contains("image not found"));
-    assert!(err.to_string().contains("img-456"));
+fn test_validation_error_keeps_field_errors() {
+    let err = SdkError::Validation {
+        message: "bad json".to_string(),
+        field_errors: vec![FieldError {
+            field: "image_id".to_string(),
+            message: "image_id is required".to_string(),
+        }],
+    };
+    assert!(err.to_string().contains("invalid input"));
+    assert!(err.to_string().contains("bad json"));
+    match err {
+        SdkError::Validation { field_errors, .. } => {
+            assert_eq!(field_errors[0].field, "image_id");
+        }
+        other => panic!("expected Validation, got {other:?}"),
+    }
 }
 
 #[test]
-fn test_invalid_input_error_display() {
-    let err = SdkError::InvalidInput("bad json".to_string());
-    assert!(err.to_string().contains("invalid input"));
-    assert!(err.to_string().contains("bad json"));
+fn test_is_retryable() {
+    assert!(
+        SdkError::Transport {
+            message: "timed out".to_string(),
+            retryable: true,
+        }
+        .is_retryable()
+    );
+    assert!(
+        !SdkError::Transport {
+            message: "bad url".to_string(),
+            retryable: false,
+        }
+        .is_retryable()
+    );
+    assert!(
+        SdkError::QuotaExceeded {
+            code: "QUOTA_EXCEEDED".to_string(),
+            message: "too many instances".to_string(),
+        }
+        .is_retryable()
+    );
+    assert!(
+        !SdkError::NotFound {
+            resource: "image".to_string(),
+            id: "img-1".to_string(),
+            message: String::new(),
+        }
+        .is_retryable()
+    );
+    assert!(
+        !SdkError::Conflict {
+            code: "CONFLICT".to_string(),
+            message: "already exists".to_string(),
+        }
+        .is_retryable()
+    );
+}
+
+#[test]
+fn test_code() {
+    let not_found = SdkError::NotFound {
+        resource: "image".to_string(),
+        id: "img-1".to_string(),
+        message: String::new(),
+    };
+    assert_eq!(not_found.code(), "NOT_FOUND");
+    assert_eq!(
+        SdkError::Unauthorized {
+            code: "AUTH".to_string(),
+            message: String::new(),
+        }
+        .code(),
+        "UNAUTHORIZED"
+    );
+    assert_eq!(SdkError::Config(String::new()).code(), "CONFIG");
 }
 
 #[test]
@@ -87,9 +151,12 @@ fn test_error_is_send_sync() {
 
 #[test]
 fn test_error_debug() {
-    let err = SdkError::Timeout(1000);
+    let err = SdkError::Transport {
+        message: "timed out after 1000ms".to_string(),
+        retryable: true,
+    };
     let debug_str = format!("{:?}", err);
-    assert!(debug_str.contains("Timeout"));
+    assert!(debug_str.contains("Transport"));
     assert!(debug_str.contains("1000"));
 }
 
@@ -106,5 +173,11 @@ fn test_from_serde_json_error() {
 fn test_from_io_error() {
     let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
     let sdk_err: SdkError = io_err.into();
-    assert!(matches!(sdk_err, SdkError::Connection(_)));
+    assert!(matches!(
+        sdk_err,
+        SdkError::Transport {
+            retryable: true,
+            ..
+        }
+    ));
 }
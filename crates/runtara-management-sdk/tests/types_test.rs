@@ -3,9 +3,10 @@
 //! Type conversion and serialization tests for runtara-management-sdk.
 
 use runtara_management_sdk::{
-    HealthStatus, InstanceStatus, ListImagesOptions, ListInstancesOptions, RegisterImageOptions,
-    RegisterImageResult, RegisterImageStreamOptions, RunnerType, SignalType, StartInstanceOptions,
-    StartInstanceResult, StopInstanceOptions,
+    CancelPendingStartOutcome, CheckpointPreviewOptions, CheckpointSummary, HealthStatus,
+    InstanceStatus, ListImagesOptions, ListInstancesOptions, RegisterImageOptions,
+    RegisterImageResult, RegisterImageStreamOptions, ReportedVersions, RunnerType, SignalType,
+    StartInstanceOptions, StartInstanceResult, StopInstanceOptions,
 };
 
 #[test]
@@ -95,6 +96,24 @@ fn test_stop_instance_options_defaults() {
     assert_eq!(opts.reason, "");
 }
 
+#[test]
+fn test_cancel_pending_start_outcome_round_trip() {
+    for outcome in [
+        CancelPendingStartOutcome::CancelledBeforeStart,
+        CancelPendingStartOutcome::AlreadyStarted,
+    ] {
+        assert_eq!(
+            CancelPendingStartOutcome::from_str(outcome.as_str()),
+            Some(outcome)
+        );
+        assert_eq!(
+            serde_json::to_value(outcome).unwrap(),
+            serde_json::json!(outcome.as_str())
+        );
+    }
+    assert_eq!(CancelPendingStartOutcome::from_str("not_found"), None);
+}
+
 #[test]
 fn test_list_instances_options_builder() {
     let opts = ListInstancesOptions::new()
@@ -173,6 +192,18 @@ fn test_list_images_options_defaults() {
     assert_eq!(opts.offset, 0);
 }
 
+#[test]
+fn test_checkpoint_preview_options_builder() {
+    let opts = CheckpointPreviewOptions::new()
+        .with_max_depth(2)
+        .with_max_items(10)
+        .with_max_string_len(64);
+
+    assert_eq!(opts.max_depth, Some(2));
+    assert_eq!(opts.max_items, Some(10));
+    assert_eq!(opts.max_string_len, Some(64));
+}
+
 // Serialization tests
 #[test]
 fn test_instance_status_serialize() {
@@ -208,6 +239,7 @@ fn test_health_status_serialize_deserialize() {
         version: "1.0.0".to_string(),
         uptime_ms: 1000000,
         active_instances: 5,
+        maintenance: None,
     };
 
     let json = serde_json::to_string(&status).unwrap();
@@ -259,3 +291,25 @@ fn test_register_image_result_serialize_deserialize() {
     assert_eq!(parsed.success, result.success);
     assert_eq!(parsed.image_id, result.image_id);
 }
+
+#[test]
+fn test_checkpoint_summary_defaults_hints_for_older_servers() {
+    let parsed: CheckpointSummary = serde_json::from_str(
+        r#"{"checkpoint_id":"cp-1","instance_id":"inst-1","created_at":"2025-01-01T00:00:00Z","data_size_bytes":4}"#,
+    )
+    .unwrap();
+
+    assert!(parsed.content_type.is_none());
+    assert!(parsed.shape.is_none());
+}
+
+#[test]
+fn test_reported_versions_tolerates_partial_payload() {
+    let parsed: ReportedVersions =
+        serde_json::from_str(r#"{"protocol_version":1,"sdk_version":"8.6.3"}"#).unwrap();
+
+    assert_eq!(parsed.protocol_version, Some(1));
+    assert_eq!(parsed.sdk_version.as_deref(), Some("8.6.3"));
+    assert!(parsed.dsl_version.is_none());
+    assert!(parsed.stdlib_checksum.is_none());
+}
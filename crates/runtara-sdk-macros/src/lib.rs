@@ -241,12 +241,13 @@ fn generate_no_retry_wrapper(
         #vis #sig {
             let __cache_key = format!("durable::{}::{}", #fn_name_str, #idempotency_key_ident);
 
-            // Step 1: Check if we have a cached result (read-only lookup)
+            // Step 1: Check if we have a cached result (read-only lookup;
+            // keys the SDK knows were never written skip the round trip)
             {
                 let __sdk = ::runtara_sdk::sdk();
                 let __sdk_guard = __sdk.lock().unwrap();
 
-                match __sdk_guard.get_checkpoint(&__cache_key) {
+                match __sdk_guard.get_checkpoint_cached(&__cache_key) {
                     Ok(Some(cached_bytes)) => {
                         // Found cached result - deserialize and return
                         drop(__sdk_guard);
@@ -278,7 +279,12 @@ fn generate_no_retry_wrapper(
                         let __sdk_guard = __sdk.lock().unwrap();
 
                         // Use checkpoint to save - it won't overwrite if already exists
-                        match __sdk_guard.checkpoint(&__cache_key, &result_bytes) {
+                        match __sdk_guard
+                            .with_hints(::runtara_sdk::CheckpointHints::json(
+                                ::runtara_sdk::CheckpointShape::DurableResult,
+                            ))
+                            .checkpoint(&__cache_key, &result_bytes)
+                        {
                             Ok(checkpoint_result) => {
 
                                 // Release SDK mutex BEFORE calling acknowledge_cancellation()
@@ -467,12 +473,13 @@ fn generate_retry_wrapper(
             let __max_retries: u32 = #max_retries;
             let __base_delay_ms: u64 = #base_delay_ms;
 
-            // Step 1: Check if we have a cached result (read-only lookup)
+            // Step 1: Check if we have a cached result (read-only lookup;
+            // keys the SDK knows were never written skip the round trip)
             {
                 let __sdk = ::runtara_sdk::sdk();
                 let __sdk_guard = __sdk.lock().unwrap();
 
-                match __sdk_guard.get_checkpoint(&__cache_key) {
+                match __sdk_guard.get_checkpoint_cached(&__cache_key) {
                     Ok(Some(cached_bytes)) => {
                         // Found cached result - deserialize and return
                         drop(__sdk_guard);
@@ -579,7 +586,12 @@ fn generate_retry_wrapper(
                                 let __sdk = ::runtara_sdk::sdk();
                                 let __sdk_guard = __sdk.lock().unwrap();
 
-                                match __sdk_guard.checkpoint(&__cache_key, &result_bytes) {
+                                match __sdk_guard
+                            .with_hints(::runtara_sdk::CheckpointHints::json(
+                                ::runtara_sdk::CheckpointShape::DurableResult,
+                            ))
+                            .checkpoint(&__cache_key, &result_bytes)
+                        {
                                     Ok(checkpoint_result) => {
 
                                         // Release SDK mutex BEFORE calling acknowledge_cancellation()
@@ -1354,13 +1366,17 @@ mod tests {
             .unwrap()
             .to_string();
         assert!(
-            tokens.contains("get_checkpoint"),
-            "durable path must emit get_checkpoint"
+            tokens.contains("get_checkpoint_cached"),
+            "durable path must look up through the known-key cache"
         );
         assert!(
             tokens.contains(". checkpoint ("),
             "durable path must emit checkpoint() call"
         );
+        assert!(
+            tokens.contains("DurableResult"),
+            "durable path must tag cached results with their shape"
+        );
         assert!(
             tokens.contains("record_retry_attempt"),
             "durable path must emit record_retry_attempt"
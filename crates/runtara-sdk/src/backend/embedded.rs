@@ -10,12 +10,16 @@ use std::time::Duration;
 
 use crate::tracing_compat::{debug, info};
 use chrono::{DateTime, Utc};
-use runtara_core::persistence::{CompleteInstanceParams, EventRecord, Persistence};
+use runtara_core::maintenance::MaintenanceMode;
+use runtara_core::persistence::{
+    CompleteInstanceParams, EventRecord, Persistence, begin_or_autocommit,
+};
 
 use super::SdkBackend;
 use crate::error::{Result, SdkError};
 use crate::types::{
-    CheckpointResult, CustomSignal, InstanceStatus, Signal, SignalType, StatusResponse,
+    CheckpointHints, CheckpointResult, CustomSignal, InstanceStatus, Signal, SignalType,
+    StatusResponse,
 };
 
 /// Embedded backend for SDK operations.
@@ -59,6 +63,21 @@ impl EmbeddedBackend {
         }
     }
 
+    /// Refuse a write while the in-process core is in maintenance, the same
+    /// way core's instance handlers do.
+    fn check_maintenance(&self) -> Result<()> {
+        MaintenanceMode::global().check().map_err(|e| match e {
+            runtara_core::error::CoreError::Maintenance {
+                message,
+                retry_after_ms,
+            } => SdkError::Maintenance {
+                message,
+                retry_after_ms,
+            },
+            other => SdkError::Internal(other.to_string()),
+        })
+    }
+
     /// Fetch the instance-wide pending lifecycle signal (cancel/pause/shutdown)
     /// from core persistence and acknowledge it.
     ///
@@ -134,6 +153,15 @@ impl SdkBackend for EmbeddedBackend {
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, state), fields(instance_id = %self.instance_id, checkpoint_id = %checkpoint_id, state_size = state.len())))]
     fn checkpoint(&self, checkpoint_id: &str, state: &[u8]) -> Result<CheckpointResult> {
+        self.checkpoint_with_hints(checkpoint_id, state, CheckpointHints::default())
+    }
+
+    fn checkpoint_with_hints(
+        &self,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints,
+    ) -> Result<CheckpointResult> {
         // Check if checkpoint exists
         let existing = self
             .rt
@@ -156,20 +184,25 @@ impl SdkBackend for EmbeddedBackend {
             });
         }
 
-        // Save new checkpoint
+        // Save new checkpoint and point the instance at it in one transaction
+        self.check_maintenance()?;
         self.rt
-            .block_on(
-                self.persistence
-                    .save_checkpoint(&self.instance_id, checkpoint_id, state),
-            )
-            .map_err(|e| SdkError::Internal(e.to_string()))?;
-
-        // Update instance's current checkpoint
-        self.rt
-            .block_on(
-                self.persistence
-                    .update_instance_checkpoint(&self.instance_id, checkpoint_id),
-            )
+            .block_on(async {
+                let mut tx = begin_or_autocommit(self.persistence.as_ref()).await?;
+                tx.save_checkpoint_with_hints(
+                    &self.instance_id,
+                    checkpoint_id,
+                    state,
+                    runtara_core::persistence::CheckpointHints {
+                        content_type: hints.content_type,
+                        shape: hints.shape.map(|shape| shape.as_str()),
+                    },
+                )
+                .await?;
+                tx.update_instance_checkpoint(&self.instance_id, checkpoint_id)
+                    .await?;
+                tx.commit().await
+            })
             .map_err(|e| SdkError::Internal(e.to_string()))?;
 
         debug!(checkpoint_id = %checkpoint_id, "New checkpoint saved");
@@ -195,6 +228,47 @@ impl SdkBackend for EmbeddedBackend {
         Ok(result.map(|c| c.state))
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.instance_id)))]
+    fn list_checkpoint_keys(
+        &self,
+        prefix: Option<&str>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<String>> {
+        self.rt
+            .block_on(
+                self.persistence
+                    .list_checkpoint_ids(&self.instance_id, prefix, since),
+            )
+            .map_err(|e| SdkError::Internal(e.to_string()))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.instance_id, prefix = %prefix)))]
+    fn compact_checkpoints(&self, prefix: &str) -> Result<u64> {
+        if prefix.is_empty() {
+            return Err(SdkError::Internal(
+                "checkpoint compaction requires a non-empty prefix".to_string(),
+            ));
+        }
+
+        self.check_maintenance()?;
+        let deleted = self
+            .rt
+            .block_on(
+                self.persistence
+                    .delete_checkpoints_with_prefix(&self.instance_id, prefix),
+            )
+            .map_err(|e| SdkError::Internal(e.to_string()))?;
+
+        // Same event core's compact handler records.
+        if deleted > 0 {
+            let payload = serde_json::json!({ "prefix": prefix, "rows_deleted": deleted });
+            self.send_custom_event("checkpoints_compacted", payload.to_string().into_bytes())?;
+        }
+
+        debug!(deleted, "Checkpoints compacted");
+        Ok(deleted)
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.instance_id)))]
     fn heartbeat(&self) -> Result<()> {
         let event = EventRecord {
@@ -217,12 +291,7 @@ impl SdkBackend for EmbeddedBackend {
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, output), fields(instance_id = %self.instance_id, output_size = output.len())))]
     fn completed(&self, output: &[u8]) -> Result<()> {
-        self.rt
-            .block_on(self.persistence.complete_instance(
-                CompleteInstanceParams::new(&self.instance_id, "completed").with_output(output),
-            ))
-            .map_err(|e| SdkError::Internal(e.to_string()))?;
-
+        self.check_maintenance()?;
         let event = EventRecord {
             id: None,
             instance_id: self.instance_id.clone(),
@@ -234,7 +303,15 @@ impl SdkBackend for EmbeddedBackend {
         };
 
         self.rt
-            .block_on(self.persistence.insert_event(&event))
+            .block_on(async {
+                let mut tx = begin_or_autocommit(self.persistence.as_ref()).await?;
+                tx.complete_instance(
+                    CompleteInstanceParams::new(&self.instance_id, "completed").with_output(output),
+                )
+                .await?;
+                tx.insert_event(&event).await?;
+                tx.commit().await
+            })
             .map_err(|e| SdkError::Internal(e.to_string()))?;
 
         info!("Instance completed");
@@ -243,12 +320,7 @@ impl SdkBackend for EmbeddedBackend {
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.instance_id)))]
     fn failed(&self, error: &str) -> Result<()> {
-        self.rt
-            .block_on(self.persistence.complete_instance(
-                CompleteInstanceParams::new(&self.instance_id, "failed").with_error(error),
-            ))
-            .map_err(|e| SdkError::Internal(e.to_string()))?;
-
+        self.check_maintenance()?;
         let event = EventRecord {
             id: None,
             instance_id: self.instance_id.clone(),
@@ -260,7 +332,15 @@ impl SdkBackend for EmbeddedBackend {
         };
 
         self.rt
-            .block_on(self.persistence.insert_event(&event))
+            .block_on(async {
+                let mut tx = begin_or_autocommit(self.persistence.as_ref()).await?;
+                tx.complete_instance(
+                    CompleteInstanceParams::new(&self.instance_id, "failed").with_error(error),
+                )
+                .await?;
+                tx.insert_event(&event).await?;
+                tx.commit().await
+            })
             .map_err(|e| SdkError::Internal(e.to_string()))?;
 
         info!(error = %error, "Instance failed");
@@ -269,6 +349,7 @@ impl SdkBackend for EmbeddedBackend {
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.instance_id)))]
     fn suspended(&self) -> Result<()> {
+        self.check_maintenance()?;
         self.rt
             .block_on(
                 self.persistence
@@ -296,28 +377,20 @@ impl SdkBackend for EmbeddedBackend {
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, state), fields(instance_id = %self.instance_id, checkpoint_id = %checkpoint_id)))]
     fn sleep_until(&self, checkpoint_id: &str, wake_at: DateTime<Utc>, state: &[u8]) -> Result<()> {
-        // Save checkpoint first
-        self.rt
-            .block_on(
-                self.persistence
-                    .save_checkpoint(&self.instance_id, checkpoint_id, state),
-            )
-            .map_err(|e| SdkError::Internal(e.to_string()))?;
+        self.check_maintenance()?;
 
-        // Update checkpoint reference
+        // Save checkpoint, update the reference and set sleep_until for the
+        // wake scheduler together
         self.rt
-            .block_on(
-                self.persistence
-                    .update_instance_checkpoint(&self.instance_id, checkpoint_id),
-            )
-            .map_err(|e| SdkError::Internal(e.to_string()))?;
-
-        // Set sleep_until for wake scheduler
-        self.rt
-            .block_on(
-                self.persistence
-                    .set_instance_sleep(&self.instance_id, wake_at),
-            )
+            .block_on(async {
+                let mut tx = begin_or_autocommit(self.persistence.as_ref()).await?;
+                tx.save_checkpoint(&self.instance_id, checkpoint_id, state)
+                    .await?;
+                tx.update_instance_checkpoint(&self.instance_id, checkpoint_id)
+                    .await?;
+                tx.set_instance_sleep(&self.instance_id, wake_at).await?;
+                tx.commit().await
+            })
             .map_err(|e| SdkError::Internal(e.to_string()))?;
 
         // Mark as suspended
@@ -349,6 +422,7 @@ impl SdkBackend for EmbeddedBackend {
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, payload), fields(instance_id = %self.instance_id, subtype = %subtype, payload_size = payload.len())))]
     fn send_custom_event(&self, subtype: &str, payload: Vec<u8>) -> Result<()> {
+        self.check_maintenance()?;
         let event = EventRecord {
             id: None,
             instance_id: self.instance_id.clone(),
@@ -374,6 +448,7 @@ impl SdkBackend for EmbeddedBackend {
         attempt_number: u32,
         error_message: Option<&str>,
     ) -> Result<()> {
+        self.check_maintenance()?;
         self.rt
             .block_on(self.persistence.save_retry_attempt(
                 &self.instance_id,
@@ -588,6 +663,12 @@ mod tests {
     struct MockPersistence {
         instances: tokio::sync::RwLock<std::collections::HashMap<String, MockInstance>>,
         checkpoints: tokio::sync::RwLock<std::collections::HashMap<String, Vec<u8>>>,
+        /// Number of `load_checkpoint` calls (round trips in HTTP mode).
+        checkpoint_loads: std::sync::atomic::AtomicUsize,
+        /// Make `list_checkpoint_ids` fail, like a core without the endpoint.
+        listing_fails: std::sync::atomic::AtomicBool,
+        /// Simulated latency of every checkpoint read (network round trip).
+        read_latency: std::sync::Mutex<Duration>,
     }
 
     struct MockInstance {
@@ -608,6 +689,21 @@ mod tests {
             Self {
                 instances: tokio::sync::RwLock::new(std::collections::HashMap::new()),
                 checkpoints: tokio::sync::RwLock::new(std::collections::HashMap::new()),
+                checkpoint_loads: std::sync::atomic::AtomicUsize::new(0),
+                listing_fails: std::sync::atomic::AtomicBool::new(false),
+                read_latency: std::sync::Mutex::new(Duration::ZERO),
+            }
+        }
+
+        fn loads(&self) -> usize {
+            self.checkpoint_loads
+                .load(std::sync::atomic::Ordering::SeqCst)
+        }
+
+        fn simulate_read_latency(&self) {
+            let latency = *self.read_latency.lock().unwrap();
+            if !latency.is_zero() {
+                std::thread::sleep(latency);
             }
         }
 
@@ -662,6 +758,7 @@ mod tests {
                     exit_code: None,
                     recovery_attempts: 0,
                     recovery_marker: None,
+                    status_version: 0,
                 }))
         }
 
@@ -717,6 +814,9 @@ mod tests {
             instance_id: &str,
             checkpoint_id: &str,
         ) -> CoreResult<Option<runtara_core::persistence::CheckpointRecord>> {
+            self.checkpoint_loads
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.simulate_read_latency();
             let checkpoints = self.checkpoints.read().await;
             let key = Self::checkpoint_key(instance_id, checkpoint_id);
             Ok(checkpoints
@@ -732,6 +832,8 @@ mod tests {
                     compensation_data: None,
                     compensation_state: None,
                     compensation_order: 0,
+                    content_type: None,
+                    shape: None,
                 }))
         }
 
@@ -891,6 +993,122 @@ mod tests {
         ) -> CoreResult<i64> {
             Ok(0)
         }
+
+        async fn list_checkpoint_ids(
+            &self,
+            instance_id: &str,
+            prefix: Option<&str>,
+            _since: Option<DateTime<Utc>>,
+        ) -> CoreResult<Vec<String>> {
+            if self.listing_fails.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(CoreError::DatabaseError {
+                    operation: "list_checkpoint_ids".into(),
+                    details: "unavailable".into(),
+                });
+            }
+            self.simulate_read_latency();
+            let instance_prefix = Self::checkpoint_key(instance_id, "");
+            let checkpoints = self.checkpoints.read().await;
+            Ok(checkpoints
+                .keys()
+                .filter_map(|key| key.strip_prefix(&instance_prefix))
+                .filter(|id| prefix.is_none_or(|p| id.starts_with(p)))
+                .map(str::to_string)
+                .collect())
+        }
+    }
+
+    fn seeded_sdk(persistence: &Arc<MockPersistence>, completed: usize) -> crate::RuntaraSdk {
+        let backend = EmbeddedBackend::new(persistence.clone(), "test-instance", "test-tenant");
+        backend.register(None).unwrap();
+        for step in 0..completed {
+            backend
+                .checkpoint(&format!("durable::step::{step}"), b"1")
+                .unwrap();
+        }
+        crate::RuntaraSdk::embedded(persistence.clone(), "test-instance", "test-tenant")
+    }
+
+    #[test]
+    fn test_known_checkpoints_skip_lookups_for_absent_keys() {
+        let persistence = Arc::new(MockPersistence::new());
+        let mut sdk = seeded_sdk(&persistence, 2);
+        assert_eq!(sdk.checkpoint_known("durable::step::0"), None);
+        sdk.register(None).unwrap();
+
+        assert_eq!(sdk.checkpoint_known("durable::step::0"), Some(true));
+        assert_eq!(sdk.checkpoint_known("durable::step::2"), Some(false));
+
+        let before = persistence.loads();
+        assert_eq!(
+            sdk.get_checkpoint_cached("durable::step::0").unwrap(),
+            Some(b"1".to_vec())
+        );
+        assert_eq!(sdk.get_checkpoint_cached("durable::step::2").unwrap(), None);
+        assert_eq!(persistence.loads() - before, 1, "absent key cost a lookup");
+
+        // Writes after registration update the cache.
+        sdk.checkpoint("durable::step::2", b"2").unwrap();
+        assert_eq!(sdk.checkpoint_known("durable::step::2"), Some(true));
+        assert_eq!(
+            sdk.get_checkpoint_cached("durable::step::2").unwrap(),
+            Some(b"2".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_known_checkpoints_bypassed_when_listing_fails() {
+        let persistence = Arc::new(MockPersistence::new());
+        let mut sdk = seeded_sdk(&persistence, 1);
+        persistence
+            .listing_fails
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        sdk.register(None).unwrap();
+
+        assert_eq!(sdk.checkpoint_known("durable::step::0"), None);
+        assert_eq!(
+            sdk.get_checkpoint_cached("durable::step::0").unwrap(),
+            Some(b"1".to_vec())
+        );
+        let before = persistence.loads();
+        assert_eq!(sdk.get_checkpoint_cached("durable::step::1").unwrap(), None);
+        assert_eq!(persistence.loads() - before, 1);
+    }
+
+    /// Resume of a synthetic 5k-step instance with a simulated 200µs round
+    /// trip per checkpoint read, with and without the known-key cache.
+    /// Run with `cargo test -p runtara-sdk --lib resume_5k -- --ignored --nocapture`.
+    #[test]
+    #[ignore = "benchmark; run with --ignored --nocapture"]
+    fn bench_resume_5k_steps() {
+        const STEPS: usize = 5_000;
+        let run = |completed: usize, cached: bool| {
+            let persistence = Arc::new(MockPersistence::new());
+            let mut sdk = seeded_sdk(&persistence, completed);
+            *persistence.read_latency.lock().unwrap() = Duration::from_micros(200);
+            let started = std::time::Instant::now();
+            sdk.register(None).unwrap();
+            let loads_before = persistence.loads();
+            for step in 0..STEPS {
+                let key = format!("durable::step::{step}");
+                let found = if cached {
+                    sdk.get_checkpoint_cached(&key).unwrap()
+                } else {
+                    sdk.get_checkpoint(&key).unwrap()
+                };
+                assert_eq!(found.is_some(), step < completed);
+            }
+            (started.elapsed(), persistence.loads() - loads_before)
+        };
+
+        for completed in [STEPS, STEPS / 2, 0] {
+            let (before, before_loads) = run(completed, false);
+            let (after, after_loads) = run(completed, true);
+            eprintln!(
+                "{STEPS} steps, {completed} completed: per-key {before:?} ({before_loads} reads) \
+                 -> cached {after:?} ({after_loads} reads)"
+            );
+        }
     }
 
     #[test]
@@ -934,6 +1152,99 @@ mod tests {
         assert!(result.pending_signal.is_none());
     }
 
+    #[test]
+    fn test_durable_now_replays_first_observed_time() {
+        let persistence = Arc::new(MockPersistence::new());
+        let mut sdk = crate::RuntaraSdk::embedded(persistence, "test-instance", "test-tenant");
+        sdk.register(None).unwrap();
+
+        let first = sdk.durable_now("order-created").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let replayed = sdk.durable_now("order-created").unwrap();
+        let other = sdk.durable_now("order-shipped").unwrap();
+
+        assert_eq!(first, replayed);
+        assert!(other > first);
+    }
+
+    #[test]
+    fn test_execute_once_crash_between_intent_and_call() {
+        let persistence = Arc::new(MockPersistence::new());
+        let mut sdk =
+            crate::RuntaraSdk::embedded(persistence.clone(), "test-instance", "test-tenant");
+        sdk.register(None).unwrap();
+
+        let ticket = sdk
+            .execute_once("charge", |_| Ok(b"charge 10".to_vec()))
+            .unwrap();
+        assert!(!ticket.is_resumed());
+        let token = ticket.token().to_string();
+        // Crash: the process dies before the call goes out.
+        drop(ticket);
+        drop(sdk);
+
+        let mut sdk = crate::RuntaraSdk::embedded(persistence, "test-instance", "test-tenant");
+        sdk.register(None).unwrap();
+        let ticket = sdk
+            .execute_once("charge", |_| panic!("prepare must not rerun"))
+            .unwrap();
+        assert!(ticket.is_resumed());
+        assert_eq!(ticket.token(), token);
+        assert_eq!(ticket.intent(), b"charge 10");
+        assert!(ticket.result().is_none());
+        assert_eq!(ticket.confirm(b"ok").unwrap(), b"ok");
+    }
+
+    #[test]
+    fn test_execute_once_crash_between_call_and_confirm() {
+        let persistence = Arc::new(MockPersistence::new());
+        let mut sdk =
+            crate::RuntaraSdk::embedded(persistence.clone(), "test-instance", "test-tenant");
+        sdk.register(None).unwrap();
+
+        // The provider dedupes on the idempotency key.
+        let mut provider: std::collections::HashMap<String, Vec<u8>> = Default::default();
+        let mut charges = 0;
+        let mut call = |token: &str| {
+            provider
+                .entry(token.to_string())
+                .or_insert_with(|| {
+                    charges += 1;
+                    format!("charge-{charges}").into_bytes()
+                })
+                .clone()
+        };
+
+        let ticket = sdk
+            .execute_once("charge", |_| Ok(b"charge 10".to_vec()))
+            .unwrap();
+        let first_response = call(ticket.token());
+        // Crash: the call went through, the confirm never ran.
+        drop(ticket);
+        drop(sdk);
+
+        let mut sdk =
+            crate::RuntaraSdk::embedded(persistence.clone(), "test-instance", "test-tenant");
+        sdk.register(None).unwrap();
+        let ticket = sdk
+            .execute_once("charge", |_| panic!("prepare must not rerun"))
+            .unwrap();
+        assert!(ticket.result().is_none());
+        let retried_response = call(ticket.token());
+        assert_eq!(retried_response, first_response);
+        assert_eq!(ticket.confirm(&retried_response).unwrap(), first_response);
+        drop(sdk);
+
+        // After the confirm, a replay gets the recorded result and skips the call.
+        let mut sdk = crate::RuntaraSdk::embedded(persistence, "test-instance", "test-tenant");
+        sdk.register(None).unwrap();
+        let ticket = sdk
+            .execute_once("charge", |_| panic!("prepare must not rerun"))
+            .unwrap();
+        assert_eq!(ticket.result(), Some(first_response.as_slice()));
+        assert_eq!(charges, 1);
+    }
+
     #[test]
     fn test_embedded_backend_checkpoint_resume() {
         let persistence = Arc::new(MockPersistence::new());
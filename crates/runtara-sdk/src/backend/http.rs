@@ -9,10 +9,11 @@
 //! - Native workflows with `RUNTARA_SDK_BACKEND=http`
 //! - WASM workflows (future, via wasi-http)
 
+use std::sync::RwLock;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
-use crate::tracing_compat::{debug, info, warn};
+use crate::tracing_compat::{debug, error, info, warn};
 use base64::Engine;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -20,9 +21,14 @@ use serde::{Deserialize, Serialize};
 use crate::backend::SdkBackend;
 use crate::error::{Result, SdkError};
 use crate::types::{
-    CheckpointResult, CustomSignal, InstanceStatus, Signal, SignalType, StatusResponse,
+    BuildInfo, CheckpointHints, CheckpointResult, CustomSignal, InstanceStatus, PROTOCOL_VERSION,
+    Signal, SignalType, StatusResponse,
 };
 
+/// Process exit code used when core fences this execution because a newer
+/// one of the same instance has registered.
+pub const FENCED_EXIT_CODE: i32 = 75;
+
 /// Configuration for the HTTP backend.
 #[derive(Debug, Clone)]
 pub struct HttpSdkConfig {
@@ -90,6 +96,11 @@ pub struct HttpBackend {
     base_url: String,
     client: runtara_http::HttpClient,
     connected: AtomicBool,
+    build_info: BuildInfo,
+    /// Session token issued at registration, sent on every later request.
+    session_token: RwLock<Option<String>>,
+    /// Execution epoch issued at registration, sent on every later request.
+    execution_epoch: RwLock<Option<i64>>,
 }
 
 impl HttpBackend {
@@ -105,6 +116,9 @@ impl HttpBackend {
             base_url: config.base_url.trim_end_matches('/').to_string(),
             client,
             connected: AtomicBool::new(false),
+            build_info: BuildInfo::from_env(),
+            session_token: RwLock::new(None),
+            execution_epoch: RwLock::new(None),
         })
     }
 
@@ -116,26 +130,49 @@ impl HttpBackend {
         )
     }
 
+    /// Start a request carrying the instance identity headers and, once
+    /// registered, the session token and execution epoch.
+    fn request(&self, method: &str, url: &str) -> runtara_http::RequestBuilder {
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("X-Runtara-Tenant-Id", &self.tenant_id)
+            .header("X-Runtara-Instance-Id", &self.instance_id);
+        let token = self
+            .session_token
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        if let Some(token) = token {
+            request = request.header("X-Runtara-Session", &token);
+        }
+        let epoch = *self
+            .execution_epoch
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(epoch) = epoch {
+            request = request.header("X-Runtara-Execution-Epoch", &epoch.to_string());
+        }
+        request
+    }
+
     /// POST JSON to an endpoint and deserialize the response.
     fn post<T: Serialize, R: for<'de> Deserialize<'de>>(&self, url: &str, body: &T) -> Result<R> {
         let json_value = serde_json::to_value(body)
             .map_err(|e| SdkError::Internal(format!("Failed to serialize request body: {}", e)))?;
 
         let response = self
-            .client
             .request("POST", url)
             .header("Content-Type", "application/json")
-            .header("X-Runtara-Tenant-Id", &self.tenant_id)
-            .header("X-Runtara-Instance-Id", &self.instance_id)
             .body_json(&json_value)
             .call()
             .map_err(|e| SdkError::Internal(format!("HTTP request failed: {}", e)))?;
 
         if response.status >= 400 {
             let body_text = String::from_utf8_lossy(&response.body).to_string();
-            return Err(SdkError::Internal(format!(
-                "HTTP request failed with status {}: {}",
-                response.status, body_text
+            return Err(exit_if_fenced(error_from_status(
+                response.status,
+                body_text,
             )));
         }
 
@@ -149,18 +186,15 @@ impl HttpBackend {
     /// GET from an endpoint and deserialize the response.
     fn get<R: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<R> {
         let response = self
-            .client
             .request("GET", url)
-            .header("X-Runtara-Tenant-Id", &self.tenant_id)
-            .header("X-Runtara-Instance-Id", &self.instance_id)
             .call()
             .map_err(|e| SdkError::Internal(format!("HTTP request failed: {}", e)))?;
 
         if response.status >= 400 {
             let body_text = String::from_utf8_lossy(&response.body).to_string();
-            return Err(SdkError::Internal(format!(
-                "HTTP request failed with status {}: {}",
-                response.status, body_text
+            return Err(exit_if_fenced(error_from_status(
+                response.status,
+                body_text,
             )));
         }
 
@@ -178,14 +212,16 @@ impl HttpBackend {
             .map_err(|e| SdkError::Internal(format!("Failed to serialize request body: {}", e)))?;
 
         match self
-            .client
             .request("POST", url)
             .header("Content-Type", "application/json")
-            .header("X-Runtara-Tenant-Id", &self.tenant_id)
-            .header("X-Runtara-Instance-Id", &self.instance_id)
             .body_json(&json_value)
             .call()
         {
+            Ok(response) if response.status >= 400 => {
+                let body_text = String::from_utf8_lossy(&response.body).to_string();
+                // Only a fence is acted on; other failures stay fire-and-forget.
+                exit_if_fenced(error_from_status(response.status, body_text));
+            }
             Ok(_) => {}
             Err(e) => {
                 warn!("Fire-and-forget request failed: {}", e);
@@ -196,15 +232,74 @@ impl HttpBackend {
     }
 }
 
+/// Terminate the process when core has fenced this execution. A fenced
+/// container is a stale duplicate of a newer one, so nothing it does from here
+/// on may touch the instance; any other error is handed back unchanged.
+fn exit_if_fenced(err: SdkError) -> SdkError {
+    if let SdkError::Fenced(message) = &err {
+        error!(
+            error = %message,
+            exit_code = FENCED_EXIT_CODE,
+            "Execution fenced by a newer registration; exiting"
+        );
+        std::process::exit(FENCED_EXIT_CODE);
+    }
+    err
+}
+
+/// Map an error response to an SDK error. A 503 with code `MAINTENANCE` is
+/// surfaced as [`SdkError::Maintenance`] so callers can wait it out, and a
+/// 403 with code `AUTHORIZATION` as [`SdkError::Server`] with that code, and a
+/// 409 with code `FENCED` as [`SdkError::Fenced`].
+fn error_from_status(status: u16, body_text: String) -> SdkError {
+    if let Ok(body) = serde_json::from_str::<ErrorBody>(&body_text) {
+        match (status, body.code.as_str()) {
+            (503, "MAINTENANCE") => {
+                return SdkError::Maintenance {
+                    message: body.error,
+                    retry_after_ms: body.retry_after_ms,
+                };
+            }
+            (409, "FENCED") => return SdkError::Fenced(body.error),
+            (403, "AUTHORIZATION") => {
+                return SdkError::Server {
+                    code: body.code,
+                    message: body.error,
+                };
+            }
+            _ => {}
+        }
+    }
+    SdkError::Internal(format!(
+        "HTTP request failed with status {}: {}",
+        status, body_text
+    ))
+}
+
 // ============================================================================
 // JSON types for HTTP API communication
 // ============================================================================
 
+#[derive(Deserialize)]
+struct ErrorBody {
+    code: String,
+    #[serde(default)]
+    error: String,
+    #[serde(default)]
+    retry_after_ms: u64,
+}
+
 #[derive(Serialize)]
 struct RegisterBody {
     tenant_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     checkpoint_id: Option<String>,
+    protocol_version: u32,
+    sdk_version: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dsl_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stdlib_checksum: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -212,12 +307,30 @@ struct RegisterResp {
     success: bool,
     #[serde(default)]
     error: Option<String>,
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    supported_protocol_min: Option<u32>,
+    #[serde(default)]
+    supported_protocol_max: Option<u32>,
+    #[serde(default)]
+    warnings: Vec<String>,
+    #[serde(default)]
+    session_token: Option<String>,
+    #[serde(default)]
+    execution_epoch: Option<i64>,
 }
 
 #[derive(Serialize)]
-struct CheckpointBody {
+struct CheckpointBody<'a> {
     checkpoint_id: String,
     state: String, // base64
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shape: Option<&'static str>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    wait_keys: &'a [String],
 }
 
 #[derive(Deserialize)]
@@ -284,6 +397,21 @@ struct RetryBody {
     error_message: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct ListCheckpointKeysResp {
+    checkpoint_ids: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct CompactCheckpointsBody<'a> {
+    prefix: &'a str,
+}
+
+#[derive(Deserialize)]
+struct CompactCheckpointsResp {
+    deleted: u64,
+}
+
 #[derive(Deserialize)]
 struct SuccessResp {
     success: bool,
@@ -367,6 +495,11 @@ fn encode_url_path(s: &str) -> String {
     utf8_percent_encode(s, PATH_SEGMENT).to_string()
 }
 
+/// Percent-encode a string for use as a URL query value.
+fn encode_query_value(s: &str) -> String {
+    percent_encoding::utf8_percent_encode(s, percent_encoding::NON_ALPHANUMERIC).to_string()
+}
+
 fn decode_b64(s: &str) -> Vec<u8> {
     base64::engine::general_purpose::STANDARD
         .decode(s)
@@ -425,17 +558,70 @@ impl SdkBackend for HttpBackend {
         debug!("HTTP backend closed");
     }
 
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
     fn register(&self, checkpoint_id: Option<&str>) -> Result<()> {
         let body = RegisterBody {
             tenant_id: self.tenant_id.clone(),
             checkpoint_id: checkpoint_id.map(|s| s.to_string()),
+            protocol_version: PROTOCOL_VERSION,
+            sdk_version: env!("CARGO_PKG_VERSION"),
+            dsl_version: self.build_info.dsl_version.clone(),
+            stdlib_checksum: self.build_info.stdlib_checksum.clone(),
         };
 
-        let resp: RegisterResp = self.post(&self.url("register"), &body)?;
+        // Core answers an incompatible version with 409 and a typed body, so
+        // read the body regardless of status instead of using `post`.
+        let json_value = serde_json::to_value(&body)
+            .map_err(|e| SdkError::Internal(format!("Failed to serialize request body: {}", e)))?;
+        let response = self
+            .request("POST", &self.url("register"))
+            .header("Content-Type", "application/json")
+            .body_json(&json_value)
+            .call()
+            .map_err(|e| SdkError::Internal(format!("HTTP request failed: {}", e)))?;
+        let status = response.status;
+        let body_text = String::from_utf8_lossy(&response.body).to_string();
+        let resp: RegisterResp = match serde_json::from_str(&body_text) {
+            Ok(resp) => resp,
+            Err(_) if status >= 400 => {
+                return Err(SdkError::Internal(format!(
+                    "HTTP request failed with status {}: {}",
+                    status, body_text
+                )));
+            }
+            Err(e) => {
+                return Err(SdkError::UnexpectedResponse(format!(
+                    "Failed to parse response: {}",
+                    e
+                )));
+            }
+        };
 
         if resp.success {
+            *self
+                .session_token
+                .write()
+                .unwrap_or_else(|e| e.into_inner()) = resp.session_token;
+            *self
+                .execution_epoch
+                .write()
+                .unwrap_or_else(|e| e.into_inner()) = resp.execution_epoch;
+            for warning in &resp.warnings {
+                warn!(warning = %warning, "Core reported a version warning at registration");
+            }
             info!("Instance registered via HTTP");
             Ok(())
+        } else if resp.code.as_deref() == Some("INCOMPATIBLE_VERSION") {
+            Err(SdkError::IncompatibleVersion {
+                message: resp.error.unwrap_or_default(),
+                supported_min: resp.supported_protocol_min.unwrap_or_default(),
+                supported_max: resp.supported_protocol_max.unwrap_or_default(),
+            })
+        } else if status >= 400 {
+            Err(SdkError::Internal(format!(
+                "HTTP request failed with status {}: {}",
+                status, body_text
+            )))
         } else {
             Err(SdkError::UnexpectedResponse(format!(
                 "Registration failed: {}",
@@ -453,9 +639,31 @@ impl SdkBackend for HttpBackend {
     }
 
     fn checkpoint(&self, checkpoint_id: &str, state: &[u8]) -> Result<CheckpointResult> {
+        self.checkpoint_with_hints(checkpoint_id, state, CheckpointHints::default())
+    }
+
+    fn checkpoint_with_hints(
+        &self,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints,
+    ) -> Result<CheckpointResult> {
+        self.checkpoint_with_wait_keys(checkpoint_id, state, hints, &[])
+    }
+
+    fn checkpoint_with_wait_keys(
+        &self,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints,
+        wait_keys: &[String],
+    ) -> Result<CheckpointResult> {
         let body = CheckpointBody {
             checkpoint_id: checkpoint_id.to_string(),
             state: encode_b64(state),
+            content_type: hints.content_type,
+            shape: hints.shape.map(|shape| shape.as_str()),
+            wait_keys,
         };
 
         let resp: CheckpointResp = self.post(&self.url("checkpoint"), &body)?;
@@ -474,6 +682,9 @@ impl SdkBackend for HttpBackend {
         let body = CheckpointBody {
             checkpoint_id: checkpoint_id.to_string(),
             state: encode_b64(&[]),
+            content_type: None,
+            shape: None,
+            wait_keys: &[],
         };
 
         let resp: CheckpointResp = self.post(&self.url("checkpoint"), &body)?;
@@ -487,6 +698,34 @@ impl SdkBackend for HttpBackend {
         }
     }
 
+    fn list_checkpoint_keys(
+        &self,
+        prefix: Option<&str>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<String>> {
+        let mut query = Vec::new();
+        if let Some(prefix) = prefix {
+            query.push(format!("prefix={}", encode_query_value(prefix)));
+        }
+        if let Some(since) = since {
+            query.push(format!("since={}", encode_query_value(&since.to_rfc3339())));
+        }
+        let mut url = self.url("checkpoints/keys");
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query.join("&"));
+        }
+
+        let resp: ListCheckpointKeysResp = self.get(&url)?;
+        Ok(resp.checkpoint_ids)
+    }
+
+    fn compact_checkpoints(&self, prefix: &str) -> Result<u64> {
+        let body = CompactCheckpointsBody { prefix };
+        let resp: CompactCheckpointsResp = self.post(&self.url("checkpoints/compact"), &body)?;
+        Ok(resp.deleted)
+    }
+
     fn heartbeat(&self) -> Result<()> {
         let body = EventBody {
             event_type: "heartbeat".to_string(),
@@ -758,3 +997,83 @@ mod config_tests {
         assert_eq!(cfg.base_url, "http://example.test:1234");
     }
 }
+
+#[cfg(test)]
+mod error_tests {
+    use super::{SdkError, error_from_status};
+
+    #[test]
+    fn maintenance_body_maps_to_maintenance_error() {
+        let body = r#"{"error":"pg failover","code":"MAINTENANCE","retry_after_ms":2500}"#;
+        match error_from_status(503, body.to_string()) {
+            SdkError::Maintenance {
+                message,
+                retry_after_ms,
+            } => {
+                assert_eq!(message, "pg failover");
+                assert_eq!(retry_after_ms, 2500);
+            }
+            other => panic!("expected maintenance, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn authorization_body_maps_to_server_error() {
+        let body = r#"{"error":"session is bound to a different instance","code":"AUTHORIZATION"}"#;
+        match error_from_status(403, body.to_string()) {
+            SdkError::Server { code, message } => {
+                assert_eq!(code, "AUTHORIZATION");
+                assert_eq!(message, "session is bound to a different instance");
+            }
+            other => panic!("expected server error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn fenced_body_maps_to_fenced_error() {
+        let body = r#"{"error":"Instance 'inst-a' execution epoch 1 was superseded by epoch 2","code":"FENCED"}"#;
+        match error_from_status(409, body.to_string()) {
+            SdkError::Fenced(message) => assert!(message.contains("superseded by epoch 2")),
+            other => panic!("expected fenced, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn other_errors_stay_internal() {
+        let body = r#"{"error":"boom","code":"CHECKPOINT_ERROR"}"#;
+        assert!(matches!(
+            error_from_status(503, body.to_string()),
+            SdkError::Internal(_)
+        ));
+        assert!(matches!(
+            error_from_status(500, "not json".to_string()),
+            SdkError::Internal(_)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod body_tests {
+    use super::CheckpointBody;
+
+    #[test]
+    fn checkpoint_body_sends_wait_keys_only_when_set() {
+        let body = |wait_keys| CheckpointBody {
+            checkpoint_id: "poll::[0]".to_string(),
+            state: String::new(),
+            content_type: None,
+            shape: None,
+            wait_keys,
+        };
+
+        let plain = serde_json::to_value(body(&[])).unwrap();
+        assert!(plain.get("wait_keys").is_none());
+
+        let keys = ["payment_confirmed:order-7".to_string()];
+        let waiting = serde_json::to_value(body(&keys)).unwrap();
+        assert_eq!(
+            waiting["wait_keys"],
+            serde_json::json!(["payment_confirmed:order-7"])
+        );
+    }
+}
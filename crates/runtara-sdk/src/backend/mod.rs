@@ -19,7 +19,9 @@ use std::time::Duration;
 use chrono::{DateTime, Utc};
 
 use crate::error::Result;
-use crate::types::{CheckpointResult, CustomSignal, Signal, SignalType, StatusResponse};
+use crate::types::{
+    CheckpointHints, CheckpointResult, CustomSignal, Signal, SignalType, StatusResponse,
+};
 
 /// Backend trait for SDK operations.
 ///
@@ -41,9 +43,49 @@ pub trait SdkBackend: Send + Sync {
     /// Checkpoint with the given ID and state.
     fn checkpoint(&self, checkpoint_id: &str, state: &[u8]) -> Result<CheckpointResult>;
 
+    /// Checkpoint with content hints persisted alongside the state.
+    ///
+    /// Default: ignores the hints and delegates to `checkpoint`.
+    fn checkpoint_with_hints(
+        &self,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints,
+    ) -> Result<CheckpointResult> {
+        let _ = hints;
+        self.checkpoint(checkpoint_id, state)
+    }
+
+    /// Checkpoint that also asks for a pending custom signal sent to any of
+    /// `wait_keys`, returned in [`CheckpointResult::custom_signal`].
+    ///
+    /// Default: ignores the wait keys and delegates to `checkpoint_with_hints`.
+    fn checkpoint_with_wait_keys(
+        &self,
+        checkpoint_id: &str,
+        state: &[u8],
+        hints: CheckpointHints,
+        wait_keys: &[String],
+    ) -> Result<CheckpointResult> {
+        let _ = wait_keys;
+        self.checkpoint_with_hints(checkpoint_id, state, hints)
+    }
+
     /// Get a checkpoint by ID (read-only).
     fn get_checkpoint(&self, checkpoint_id: &str) -> Result<Option<Vec<u8>>>;
 
+    /// List the IDs (no state) of this instance's checkpoints, optionally
+    /// only those starting with `prefix` and created at or after `since`.
+    fn list_checkpoint_keys(
+        &self,
+        prefix: Option<&str>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<String>>;
+
+    /// Delete all checkpoints whose ID starts with `prefix`, except the
+    /// instance's current resume checkpoint. Returns the count deleted.
+    fn compact_checkpoints(&self, prefix: &str) -> Result<u64>;
+
     /// Send a heartbeat event.
     fn heartbeat(&self) -> Result<()>;
 
@@ -2,14 +2,50 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 //! Main SDK client for instance communication with runtara-core.
 
+use std::collections::HashSet;
+use std::sync::Mutex;
 use std::time::Duration;
 use std::time::Instant;
 
-use crate::tracing_compat::{debug, info};
+use crate::tracing_compat::{debug, info, warn};
 
 use crate::backend::SdkBackend;
 use crate::error::{Result, SdkError};
-use crate::types::{CheckpointResult, Signal, SignalType, StatusResponse};
+use crate::types::{
+    CheckpointHints, CheckpointResult, CheckpointShape, Signal, SignalType, StatusResponse,
+};
+
+/// Longest single pause while core is in maintenance, so the instance notices
+/// an early end of the window.
+const MAINTENANCE_POLL_CAP: Duration = Duration::from_secs(5);
+
+/// Give up waiting for maintenance to end after this long.
+const MAINTENANCE_MAX_WAIT: Duration = Duration::from_secs(60 * 60);
+
+/// Run a write, pausing and retrying while core reports maintenance.
+///
+/// Maintenance refusals are not failures: waiting here keeps them from
+/// consuming the `#[resilient]` retry budget of the step that issued the write.
+fn wait_out_maintenance<T>(mut op: impl FnMut() -> Result<T>) -> Result<T> {
+    let started = Instant::now();
+    loop {
+        match op() {
+            Err(SdkError::Maintenance {
+                message,
+                retry_after_ms,
+            }) if started.elapsed() < MAINTENANCE_MAX_WAIT => {
+                let pause = Duration::from_millis(retry_after_ms.max(1)).min(MAINTENANCE_POLL_CAP);
+                info!(
+                    message = %message,
+                    retry_after_ms,
+                    "Core in maintenance; waiting before retrying write"
+                );
+                std::thread::sleep(pause);
+            }
+            result => return result,
+        }
+    }
+}
 
 /// High-level SDK client for instance communication with runtara-core.
 ///
@@ -75,6 +111,10 @@ pub struct RuntaraSdk {
     signal_poll_interval_ms: u64,
     /// Background heartbeat interval (ms). 0 = disabled.
     heartbeat_interval_ms: u64,
+    /// Checkpoint IDs known to exist, loaded once at registration and kept
+    /// current by this client's own writes. `None` when the listing failed
+    /// (or registration has not happened): every lookup then goes to core.
+    known_checkpoints: Mutex<Option<HashSet<String>>>,
 }
 
 impl RuntaraSdk {
@@ -100,6 +140,7 @@ impl RuntaraSdk {
             pending_signal: None,
             signal_poll_interval_ms,
             heartbeat_interval_ms,
+            known_checkpoints: Mutex::new(None),
         })
     }
 
@@ -140,6 +181,7 @@ impl RuntaraSdk {
             pending_signal: None,
             signal_poll_interval_ms: 1_000,
             heartbeat_interval_ms: 30_000,
+            known_checkpoints: Mutex::new(None),
         }
     }
 
@@ -168,6 +210,7 @@ impl RuntaraSdk {
             pending_signal: None,
             signal_poll_interval_ms,
             heartbeat_interval_ms,
+            known_checkpoints: Mutex::new(None),
         }
     }
 
@@ -231,14 +274,57 @@ impl RuntaraSdk {
     ///
     /// This should be called at instance startup. If `checkpoint_id` is provided,
     /// the instance is resuming from a checkpoint.
+    ///
+    /// Also loads the IDs of the instance's existing checkpoints in one call,
+    /// so [`get_checkpoint_cached`](Self::get_checkpoint_cached) can answer
+    /// for steps that never ran without asking core. A failed listing only
+    /// disables that shortcut.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.backend.instance_id())))]
     pub fn register(&mut self, checkpoint_id: Option<&str>) -> Result<()> {
         self.backend.register(checkpoint_id)?;
         self.registered = true;
         info!("Instance registered");
+        self.load_known_checkpoints();
         Ok(())
     }
 
+    /// (Re)load the known-checkpoint cache from core.
+    fn load_known_checkpoints(&self) {
+        let known = match self.backend.list_checkpoint_keys(None, None) {
+            Ok(ids) => {
+                debug!(count = ids.len(), "Loaded known checkpoint keys");
+                Some(ids.into_iter().collect())
+            }
+            Err(e) => {
+                warn!(error = %e, "Checkpoint key listing failed; looking up checkpoints individually");
+                None
+            }
+        };
+        *self.known_checkpoints_guard() = known;
+    }
+
+    fn known_checkpoints_guard(&self) -> std::sync::MutexGuard<'_, Option<HashSet<String>>> {
+        self.known_checkpoints
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Record a checkpoint written by this client in the known-key cache.
+    fn remember_checkpoint(&self, checkpoint_id: &str) {
+        if let Some(known) = self.known_checkpoints_guard().as_mut() {
+            known.insert(checkpoint_id.to_string());
+        }
+    }
+
+    /// Whether a checkpoint exists according to the known-key cache:
+    /// `Some(false)` means it certainly does not, `None` that the cache
+    /// cannot tell (listing failed or registration has not happened).
+    pub fn checkpoint_known(&self, checkpoint_id: &str) -> Option<bool> {
+        self.known_checkpoints_guard()
+            .as_ref()
+            .map(|known| known.contains(checkpoint_id))
+    }
+
     // ========== Checkpointing ==========
 
     /// Checkpoint with the given ID and state.
@@ -253,7 +339,51 @@ impl RuntaraSdk {
     /// that the instance should handle after processing the checkpoint.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, state), fields(instance_id = %self.backend.instance_id(), checkpoint_id = %checkpoint_id, state_size = state.len())))]
     pub fn checkpoint(&self, checkpoint_id: &str, state: &[u8]) -> Result<CheckpointResult> {
-        self.backend.checkpoint(checkpoint_id, state)
+        let result = wait_out_maintenance(|| self.backend.checkpoint(checkpoint_id, state))?;
+        self.remember_checkpoint(checkpoint_id);
+        Ok(result)
+    }
+
+    /// Attach content hints to the next checkpoint write.
+    ///
+    /// The hints are stored alongside the state so checkpoint browsing can
+    /// render it; save/resume semantics are exactly those of [`checkpoint`].
+    ///
+    /// ```ignore
+    /// sdk.with_hints(CheckpointHints::json(CheckpointShape::StepOutput))
+    ///     .checkpoint("step-1", &bytes)?;
+    /// ```
+    ///
+    /// [`checkpoint`]: RuntaraSdk::checkpoint
+    pub fn with_hints(&self, hints: CheckpointHints) -> HintedCheckpoint<'_> {
+        HintedCheckpoint { sdk: self, hints }
+    }
+
+    /// [`checkpoint`](Self::checkpoint) that also delivers a custom signal
+    /// sent to any of `wait_keys`.
+    ///
+    /// Lets a step wait on a business event (for example
+    /// `payment_confirmed:order-7`) by checkpointing in a polling loop until
+    /// [`CheckpointResult::custom_signal`] returns the payload. A signal sent
+    /// before the first poll is delivered too; signals are sent with
+    /// `ManagementSdk::send_custom_signal`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, state), fields(instance_id = %self.backend.instance_id(), checkpoint_id = %checkpoint_id, state_size = state.len())))]
+    pub fn checkpoint_with_wait_keys(
+        &self,
+        checkpoint_id: &str,
+        state: &[u8],
+        wait_keys: &[String],
+    ) -> Result<CheckpointResult> {
+        let result = wait_out_maintenance(|| {
+            self.backend.checkpoint_with_wait_keys(
+                checkpoint_id,
+                state,
+                CheckpointHints::default(),
+                wait_keys,
+            )
+        })?;
+        self.remember_checkpoint(checkpoint_id);
+        Ok(result)
     }
 
     /// Get a checkpoint by ID without saving (read-only lookup).
@@ -264,6 +394,76 @@ impl RuntaraSdk {
         self.backend.get_checkpoint(checkpoint_id)
     }
 
+    /// [`get_checkpoint`](Self::get_checkpoint) that consults the known-key
+    /// cache first.
+    ///
+    /// A key the cache knows is absent returns `None` without a round trip;
+    /// only keys that exist (or that the cache cannot vouch for) have their
+    /// state fetched. This is the `#[durable]` fast path: a resumed instance
+    /// no longer pays a lookup for every step it has not reached yet.
+    pub fn get_checkpoint_cached(&self, checkpoint_id: &str) -> Result<Option<Vec<u8>>> {
+        if self.checkpoint_known(checkpoint_id) == Some(false) {
+            return Ok(None);
+        }
+        self.get_checkpoint(checkpoint_id)
+    }
+
+    /// List the IDs of this instance's checkpoints without their state,
+    /// optionally only those starting with `prefix` and created at or after
+    /// `since`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.backend.instance_id())))]
+    pub fn list_checkpoint_keys(
+        &self,
+        prefix: Option<&str>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>> {
+        self.backend.list_checkpoint_keys(prefix, since)
+    }
+
+    /// Delete every checkpoint whose ID starts with `prefix`.
+    ///
+    /// Used to compact a loop's per-iteration checkpoints once its aggregate
+    /// checkpoint is durable. The instance's current resume checkpoint is
+    /// never deleted, so calling this again is a no-op. Returns the number
+    /// of checkpoints deleted.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.backend.instance_id(), prefix = %prefix)))]
+    pub fn compact_checkpoints(&self, prefix: &str) -> Result<u64> {
+        wait_out_maintenance(|| self.backend.compact_checkpoints(prefix))
+    }
+
+    /// Wall-clock time that is stable across replays.
+    ///
+    /// The first call for `key` records the current time in a checkpoint;
+    /// every later call with the same key (a retry, or a replay after resume)
+    /// returns that recorded time instead of reading the clock again. Use it
+    /// wherever a timestamp feeds a side effect, so a half-applied step is
+    /// finished with the same value it started with.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.backend.instance_id(), key = %key)))]
+    pub fn durable_now(&self, key: &str) -> Result<chrono::DateTime<chrono::Utc>> {
+        let checkpoint_id = format!("durable_now::{key}");
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let state =
+            serde_json::to_vec(&now_ms).map_err(|e| SdkError::Serialization(e.to_string()))?;
+
+        let result = self
+            .with_hints(CheckpointHints::json(CheckpointShape::DurableResult))
+            .checkpoint(&checkpoint_id, &state)?;
+        let recorded_ms = match result.existing_state() {
+            Some(existing) => serde_json::from_slice::<i64>(existing).map_err(|e| {
+                SdkError::Checkpoint(format!(
+                    "checkpoint '{checkpoint_id}' does not hold a durable timestamp: {e}"
+                ))
+            })?,
+            None => now_ms,
+        };
+
+        chrono::DateTime::from_timestamp_millis(recorded_ms).ok_or_else(|| {
+            SdkError::Checkpoint(format!(
+                "checkpoint '{checkpoint_id}' holds an out-of-range timestamp: {recorded_ms}"
+            ))
+        })
+    }
+
     // ========== Sleep/Wake ==========
 
     /// Request to sleep for the specified duration.
@@ -274,7 +474,8 @@ impl RuntaraSdk {
     /// - On resume, calculates remaining time and only sleeps for the remainder
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, state), fields(instance_id = %self.backend.instance_id(), duration_ms = duration.as_millis() as u64)))]
     pub fn sleep(&self, duration: Duration, checkpoint_id: &str, state: &[u8]) -> Result<()> {
-        self.backend.durable_sleep(duration, checkpoint_id, state)
+        self.remember_checkpoint(checkpoint_id);
+        wait_out_maintenance(|| self.backend.durable_sleep(duration, checkpoint_id, state))
     }
 
     // ========== Events ==========
@@ -296,19 +497,19 @@ impl RuntaraSdk {
     /// Send a completed event with output.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, output), fields(instance_id = %self.backend.instance_id(), output_size = output.len())))]
     pub fn completed(&self, output: &[u8]) -> Result<()> {
-        self.backend.completed(output)
+        wait_out_maintenance(|| self.backend.completed(output))
     }
 
     /// Send a failed event with error message.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.backend.instance_id())))]
     pub fn failed(&self, error: &str) -> Result<()> {
-        self.backend.failed(error)
+        wait_out_maintenance(|| self.backend.failed(error))
     }
 
     /// Send a suspended event (for pause signals).
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(instance_id = %self.backend.instance_id())))]
     pub fn suspended(&self) -> Result<()> {
-        self.backend.suspended()
+        wait_out_maintenance(|| self.backend.suspended())
     }
 
     /// Suspend with durable sleep - saves checkpoint and schedules wake.
@@ -319,13 +520,14 @@ impl RuntaraSdk {
         wake_at: chrono::DateTime<chrono::Utc>,
         state: &[u8],
     ) -> Result<()> {
-        self.backend.sleep_until(checkpoint_id, wake_at, state)
+        self.remember_checkpoint(checkpoint_id);
+        wait_out_maintenance(|| self.backend.sleep_until(checkpoint_id, wake_at, state))
     }
 
     /// Send a custom event with arbitrary subtype and payload.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, payload), fields(instance_id = %self.backend.instance_id(), subtype = %subtype)))]
     pub fn custom_event(&self, subtype: &str, payload: Vec<u8>) -> Result<()> {
-        self.backend.send_custom_event(subtype, payload)
+        wait_out_maintenance(|| self.backend.send_custom_event(subtype, payload.clone()))
     }
 
     // ========== Signals ==========
@@ -446,8 +648,10 @@ impl RuntaraSdk {
         attempt_number: u32,
         error_message: Option<&str>,
     ) -> Result<()> {
-        self.backend
-            .record_retry_attempt(checkpoint_id, attempt_number, error_message)
+        wait_out_maintenance(|| {
+            self.backend
+                .record_retry_attempt(checkpoint_id, attempt_number, error_message)
+        })
     }
 
     // ========== Status ==========
@@ -487,10 +691,58 @@ impl RuntaraSdk {
     }
 }
 
+/// A checkpoint write with content hints attached, created by
+/// [`RuntaraSdk::with_hints`].
+pub struct HintedCheckpoint<'a> {
+    sdk: &'a RuntaraSdk,
+    hints: CheckpointHints,
+}
+
+impl HintedCheckpoint<'_> {
+    /// Same as [`RuntaraSdk::checkpoint`], persisting the hints with the state.
+    pub fn checkpoint(&self, checkpoint_id: &str, state: &[u8]) -> Result<CheckpointResult> {
+        let result = wait_out_maintenance(|| {
+            self.sdk
+                .backend
+                .checkpoint_with_hints(checkpoint_id, state, self.hints)
+        })?;
+        self.sdk.remember_checkpoint(checkpoint_id);
+        Ok(result)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_wait_out_maintenance_retries_until_write_succeeds() {
+        let mut calls = 0;
+        let result = wait_out_maintenance(|| {
+            calls += 1;
+            if calls < 3 {
+                Err(SdkError::Maintenance {
+                    message: "pg failover".to_string(),
+                    retry_after_ms: 1,
+                })
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_wait_out_maintenance_passes_other_errors_through() {
+        let mut calls = 0;
+        let result: Result<()> = wait_out_maintenance(|| {
+            calls += 1;
+            Err(SdkError::Checkpoint("boom".to_string()))
+        });
+        assert!(matches!(result, Err(SdkError::Checkpoint(_))));
+        assert_eq!(calls, 1);
+    }
+
     #[cfg(feature = "http")]
     #[test]
     fn test_sdk_creation_http() {
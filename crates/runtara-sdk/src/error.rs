@@ -270,6 +270,20 @@ pub enum SdkError {
     #[error("registration failed: {0}")]
     Registration(String),
 
+    /// Core refused registration because this binary's protocol version is
+    /// outside the range it supports. Rebuild the image against a matching SDK.
+    #[error(
+        "incompatible version: {message} (core supports protocol {supported_min}..={supported_max})"
+    )]
+    IncompatibleVersion {
+        /// Reason reported by core
+        message: String,
+        /// Oldest protocol version core accepts
+        supported_min: u32,
+        /// Newest protocol version core accepts
+        supported_max: u32,
+    },
+
     /// Checkpoint operation failed
     #[error("checkpoint error: {0}")]
     Checkpoint(String),
@@ -316,6 +330,21 @@ pub enum SdkError {
     #[error("server shutting down; instance suspended")]
     ShuttingDown,
 
+    /// Core is in maintenance and refused the write. The instance should wait
+    /// `retry_after_ms` and try again; this does not count as a failure.
+    #[error("core in maintenance: {message} (retry after {retry_after_ms} ms)")]
+    Maintenance {
+        /// Reason given by the operator
+        message: String,
+        /// Milliseconds until the maintenance window ends
+        retry_after_ms: u64,
+    },
+
+    /// A newer execution of this instance has registered; this process is a
+    /// stale duplicate and must stop without writing further state.
+    #[error("execution fenced: {0}")]
+    Fenced(String),
+
     /// Serialization/deserialization error
     #[error("serialization error: {0}")]
     Serialization(String),
@@ -354,6 +383,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_incompatible_version_error_display() {
+        let err = SdkError::IncompatibleVersion {
+            message: "protocol 2 not supported".to_string(),
+            supported_min: 1,
+            supported_max: 1,
+        };
+        assert_eq!(
+            format!("{}", err),
+            "incompatible version: protocol 2 not supported (core supports protocol 1..=1)"
+        );
+    }
+
     #[test]
     fn test_checkpoint_error_display() {
         let err = SdkError::Checkpoint("failed to save state".to_string());
@@ -408,6 +450,27 @@ mod tests {
         assert_eq!(format!("{}", err), "instance paused");
     }
 
+    #[test]
+    fn test_maintenance_error_display() {
+        let err = SdkError::Maintenance {
+            message: "pg failover".to_string(),
+            retry_after_ms: 1500,
+        };
+        assert_eq!(
+            format!("{}", err),
+            "core in maintenance: pg failover (retry after 1500 ms)"
+        );
+    }
+
+    #[test]
+    fn test_fenced_error_display() {
+        let err = SdkError::Fenced("superseded by epoch 3".to_string());
+        assert_eq!(
+            format!("{}", err),
+            "execution fenced: superseded by epoch 3"
+        );
+    }
+
     #[test]
     fn test_serialization_error_display() {
         let err = SdkError::Serialization("invalid JSON".to_string());
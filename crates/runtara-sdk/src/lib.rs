@@ -10,6 +10,7 @@
 //!
 //! - **Instance Registration**: Self-register with runtara-core on startup
 //! - **Checkpointing**: Save state for durability with automatic resume handling
+//! - **Exactly-once Side Effects**: Outbox-style idempotency tokens for external calls
 //! - **Durable Sleep**: Request sleep with automatic checkpoint/wake
 //! - **Lifecycle Events**: Send heartbeat, completed, failed events
 //! - **Signal Handling**: Poll and handle cancel, pause, resume signals
@@ -68,6 +69,8 @@
 //! | `RUNTARA_HTTP_URL` | No | `http://127.0.0.1:8003` | HTTP API URL |
 //! | `RUNTARA_REQUEST_TIMEOUT_MS` | No | `30000` | Request timeout |
 //! | `RUNTARA_SIGNAL_POLL_INTERVAL_MS` | No | `1000` | Signal poll rate limit |
+//! | `RUNTARA_IMAGE_DSL_VERSION` | No | - | DSL version reported at registration |
+//! | `RUNTARA_IMAGE_STDLIB_CHECKSUM` | No | - | Stdlib checksum reported at registration |
 //!
 //! ## Programmatic Configuration
 //!
@@ -89,16 +92,19 @@
 mod backend;
 mod client;
 mod error;
+mod outbox;
 mod registry;
 mod tracing_compat;
 mod types;
 
 // Main types
+pub use client::HintedCheckpoint;
 pub use client::RuntaraSdk;
 pub use error::{Result, SdkError};
+pub use outbox::ExecutionTicket;
 pub use types::{
-    CheckpointResult, CustomSignal, InstanceStatus, RetryConfig, RetryStrategy, Signal, SignalType,
-    StatusResponse,
+    BuildInfo, CheckpointHints, CheckpointResult, CheckpointShape, CustomSignal, InstanceStatus,
+    PROTOCOL_VERSION, RetryConfig, RetryStrategy, Signal, SignalType, StatusResponse,
 };
 
 // HTTP config export
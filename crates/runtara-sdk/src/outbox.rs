@@ -0,0 +1,249 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Outbox-style helper for external side effects, see
+//! [`RuntaraSdk::execute_once`](crate::RuntaraSdk::execute_once).
+//!
+//! "Call an API, then checkpoint" repeats the call when the process dies in
+//! between. The outbox splits the side effect into two checkpoints around the
+//! call:
+//!
+//! 1. `execute_once::{key}::intent` records a generated idempotency token (and
+//!    whatever the prepare function returned) *before* the call is made.
+//! 2. `execute_once::{key}::result` records the call's result once it is
+//!    known, via [`ExecutionTicket::confirm`].
+//!
+//! The call itself is sent with the token as its idempotency key, so the
+//! provider deduplicates it. How each crash point plays out on resume:
+//!
+//! - **Crash before the intent is written**: nothing was recorded and nothing
+//!   was sent. `execute_once` generates a token as if for the first time.
+//! - **Crash between intent and call**: the intent exists, no result. The
+//!   ticket carries the recorded token, the call is sent for the first time.
+//! - **Crash between call and confirm**: the intent exists, no result. The
+//!   ticket carries the same token, the call is re-sent, and the provider
+//!   recognises the key and replays its original response instead of
+//!   performing the effect twice.
+//! - **Crash after confirm**: the ticket already holds the result
+//!   ([`ExecutionTicket::result`]); the call should be skipped.
+//!
+//! Deduplication of the re-sent call is up to the provider. The helper only
+//! guarantees the same token goes out every time.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::client::RuntaraSdk;
+use crate::error::{Result, SdkError};
+use crate::types::{CheckpointHints, CheckpointShape};
+
+/// Intent checkpoint state.
+#[derive(Debug, Serialize, Deserialize)]
+struct IntentRecord {
+    /// Idempotency token sent with the external call.
+    token: String,
+    /// Base64 of the bytes the prepare function returned.
+    intent: String,
+}
+
+/// A recorded intent to perform an external side effect, returned by
+/// [`RuntaraSdk::execute_once`].
+///
+/// ```ignore
+/// let ticket = sdk.execute_once("charge-order-42", |_token| {
+///     Ok(serde_json::to_vec(&charge_request)?)
+/// })?;
+/// let response = match ticket.result() {
+///     Some(recorded) => recorded.to_vec(),
+///     None => {
+///         let response = payments.charge(&charge_request, ticket.token())?;
+///         ticket.confirm(&response)?
+///     }
+/// };
+/// ```
+pub struct ExecutionTicket<'a> {
+    sdk: &'a RuntaraSdk,
+    key: String,
+    token: String,
+    intent: Vec<u8>,
+    resumed: bool,
+    result: Option<Vec<u8>>,
+}
+
+impl ExecutionTicket<'_> {
+    /// Idempotency token to send with the external call. Identical on every
+    /// attempt for the same key.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Bytes the prepare function returned when the intent was first
+    /// recorded.
+    pub fn intent(&self) -> &[u8] {
+        &self.intent
+    }
+
+    /// Whether the intent was recorded by an earlier attempt, i.e. the call
+    /// may already have reached the provider.
+    pub fn is_resumed(&self) -> bool {
+        self.resumed
+    }
+
+    /// The confirmed result, if an earlier attempt already confirmed the
+    /// call. When set, the call must not be made again.
+    pub fn result(&self) -> Option<&[u8]> {
+        self.result.as_deref()
+    }
+
+    /// Record the call's result.
+    ///
+    /// Returns the recorded result: `result` itself, or the one a previous
+    /// attempt confirmed if there is one.
+    pub fn confirm(self, result: &[u8]) -> Result<Vec<u8>> {
+        if let Some(recorded) = self.result {
+            return Ok(recorded);
+        }
+        let checkpoint = self
+            .sdk
+            .with_hints(CheckpointHints::octet_stream())
+            .checkpoint(&result_checkpoint_id(&self.key), result)?;
+        Ok(match checkpoint.existing_state() {
+            Some(existing) => existing.to_vec(),
+            None => result.to_vec(),
+        })
+    }
+}
+
+impl RuntaraSdk {
+    /// Record the intent to perform an external side effect exactly once.
+    ///
+    /// On the first call for `key`, generates an idempotency token, passes it
+    /// to `prepare`, and checkpoints the token together with the bytes
+    /// `prepare` returned before handing back the ticket. Later calls for the
+    /// same key (retries, or replays after resume) skip `prepare` and return
+    /// a ticket with the recorded token and intent, plus the confirmed result
+    /// if there is one.
+    ///
+    /// Send [`ExecutionTicket::token`] as the external call's idempotency key
+    /// and record the response with [`ExecutionTicket::confirm`]. A crash
+    /// between recording the intent and confirming the result re-sends the
+    /// call with the same token, so the provider can deduplicate it; a crash
+    /// after confirming returns the recorded result and the call is skipped.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, prepare), fields(instance_id = %self.instance_id(), key = %key)))]
+    pub fn execute_once<F>(&self, key: &str, prepare: F) -> Result<ExecutionTicket<'_>>
+    where
+        F: FnOnce(&str) -> Result<Vec<u8>>,
+    {
+        let intent_id = intent_checkpoint_id(key);
+
+        let (record, resumed) = match self.get_checkpoint(&intent_id)? {
+            Some(existing) => (decode_intent(&intent_id, &existing)?, true),
+            None => {
+                let token = generate_token(self.instance_id(), key);
+                let intent = prepare(&token)?;
+                let record = IntentRecord {
+                    token,
+                    intent: base64::engine::general_purpose::STANDARD.encode(&intent),
+                };
+                let state = serde_json::to_vec(&record)
+                    .map_err(|e| SdkError::Serialization(e.to_string()))?;
+                let checkpoint = self
+                    .with_hints(CheckpointHints::json(CheckpointShape::DurableResult))
+                    .checkpoint(&intent_id, &state)?;
+                // A concurrent writer got there first: its token wins.
+                match checkpoint.existing_state() {
+                    Some(existing) => (decode_intent(&intent_id, existing)?, true),
+                    None => (record, false),
+                }
+            }
+        };
+
+        let intent = base64::engine::general_purpose::STANDARD
+            .decode(&record.intent)
+            .map_err(|e| {
+                SdkError::Checkpoint(format!(
+                    "checkpoint '{intent_id}' holds an undecodable intent: {e}"
+                ))
+            })?;
+        let result = if resumed {
+            self.get_checkpoint(&result_checkpoint_id(key))?
+        } else {
+            None
+        };
+
+        Ok(ExecutionTicket {
+            sdk: self,
+            key: key.to_string(),
+            token: record.token,
+            intent,
+            resumed,
+            result,
+        })
+    }
+}
+
+fn intent_checkpoint_id(key: &str) -> String {
+    format!("execute_once::{key}::intent")
+}
+
+fn result_checkpoint_id(key: &str) -> String {
+    format!("execute_once::{key}::result")
+}
+
+fn decode_intent(checkpoint_id: &str, state: &[u8]) -> Result<IntentRecord> {
+    serde_json::from_slice(state).map_err(|e| {
+        SdkError::Checkpoint(format!(
+            "checkpoint '{checkpoint_id}' does not hold an execute_once intent: {e}"
+        ))
+    })
+}
+
+/// 128-bit hex token from the instance, key and current time.
+///
+/// Only needs to be unique, not unpredictable: it is persisted before use and
+/// read back on every later attempt. Two FNV-1a passes with different offset
+/// bases keep it dependency-free on every SDK target.
+fn generate_token(instance_id: &str, key: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let fnv = |mut hash: u64| {
+        for byte in instance_id
+            .bytes()
+            .chain([0])
+            .chain(key.bytes())
+            .chain([0])
+            .chain(nanos.to_le_bytes())
+        {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        hash
+    };
+    format!(
+        "{:016x}{:016x}",
+        fnv(0xcbf2_9ce4_8422_2325),
+        fnv(0x6c62_272e_07bb_0142)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_token_shape() {
+        let token = generate_token("inst-1", "charge");
+        assert_eq!(token.len(), 32);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_token("inst-1", "refund"));
+    }
+
+    #[test]
+    fn test_checkpoint_ids_are_scoped_by_key() {
+        assert_eq!(intent_checkpoint_id("a"), "execute_once::a::intent");
+        assert_eq!(result_checkpoint_id("a"), "execute_once::a::result");
+    }
+}
@@ -81,6 +81,12 @@ impl CheckpointResult {
         if self.found { Some(&self.state) } else { None }
     }
 
+    /// The custom signal delivered with this checkpoint, if any. Its
+    /// `checkpoint_id` is the checkpoint ID or wait key it was sent to.
+    pub fn custom_signal(&self) -> Option<&CustomSignal> {
+        self.custom_signal.as_ref()
+    }
+
     /// Check if the instance should pause.
     pub fn should_pause(&self) -> bool {
         matches!(
@@ -116,6 +122,61 @@ impl CheckpointResult {
     }
 }
 
+/// What a checkpoint's state represents.
+///
+/// Stored alongside the state as a tag so checkpoint browsing tools can pick
+/// a renderer; it has no effect on replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointShape {
+    /// Output of a single workflow step.
+    StepOutput,
+    /// Snapshot of the accumulated steps context.
+    StepsContext,
+    /// Cached result of a durable function (`#[resilient]`, `durable_now`).
+    DurableResult,
+    /// Scenario variables.
+    ScenarioVars,
+}
+
+impl CheckpointShape {
+    /// Tag stored with the checkpoint.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckpointShape::StepOutput => "step_output",
+            CheckpointShape::StepsContext => "steps_context",
+            CheckpointShape::DurableResult => "durable_result",
+            CheckpointShape::ScenarioVars => "scenario_vars",
+        }
+    }
+}
+
+/// Content hints attached to a checkpoint write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CheckpointHints {
+    /// Content type of the state bytes.
+    pub content_type: Option<&'static str>,
+    /// What the state represents.
+    pub shape: Option<CheckpointShape>,
+}
+
+impl CheckpointHints {
+    /// `application/json` state of the given shape.
+    pub fn json(shape: CheckpointShape) -> Self {
+        Self {
+            content_type: Some("application/json"),
+            shape: Some(shape),
+        }
+    }
+
+    /// Opaque binary state.
+    pub fn octet_stream() -> Self {
+        Self {
+            content_type: Some("application/octet-stream"),
+            shape: None,
+        }
+    }
+}
+
 /// Instance status response with full details.
 #[derive(Debug, Clone)]
 pub struct StatusResponse {
@@ -131,6 +192,37 @@ pub struct StatusResponse {
     pub error: Option<String>,
 }
 
+// ============================================================================
+// Version Handshake
+// ============================================================================
+
+/// Instance-protocol version this SDK speaks, reported to core at
+/// registration so version drift fails fast instead of mid-run.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Image build metadata reported to core at registration.
+///
+/// Read from `RUNTARA_IMAGE_DSL_VERSION` and `RUNTARA_IMAGE_STDLIB_CHECKSUM`;
+/// both are optional and only used for inventory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// DSL version the image was compiled from.
+    pub dsl_version: Option<String>,
+    /// Checksum of the stdlib the image was linked against.
+    pub stdlib_checksum: Option<String>,
+}
+
+impl BuildInfo {
+    /// Read build metadata from the environment.
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        Self {
+            dsl_version: var("RUNTARA_IMAGE_DSL_VERSION"),
+            stdlib_checksum: var("RUNTARA_IMAGE_STDLIB_CHECKSUM"),
+        }
+    }
+}
+
 // ============================================================================
 // Retry Configuration
 // ============================================================================
@@ -232,6 +324,22 @@ mod tests {
         assert_eq!(signal, cloned);
     }
 
+    // ============================================================================
+    // CheckpointHints Tests
+    // ============================================================================
+
+    #[test]
+    fn test_checkpoint_hints_constructors() {
+        let hints = CheckpointHints::json(CheckpointShape::DurableResult);
+        assert_eq!(hints.content_type, Some("application/json"));
+        assert_eq!(hints.shape.map(|s| s.as_str()), Some("durable_result"));
+
+        let hints = CheckpointHints::octet_stream();
+        assert_eq!(hints.content_type, Some("application/octet-stream"));
+        assert!(hints.shape.is_none());
+        assert_eq!(CheckpointHints::default().content_type, None);
+    }
+
     // ============================================================================
     // Signal Tests
     // ============================================================================
@@ -401,7 +509,7 @@ mod tests {
         assert!(!result.should_exit());
 
         // But we can access it
-        let custom = result.custom_signal.as_ref().unwrap();
+        let custom = result.custom_signal().unwrap();
         assert_eq!(custom.checkpoint_id, "wait-key");
         assert_eq!(custom.payload, vec![10, 20, 30]);
     }
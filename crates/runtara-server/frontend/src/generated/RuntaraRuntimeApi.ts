@@ -2081,8 +2081,9 @@ export interface ErrorResponse {
  * with structured metadata. This is the primary mechanism for business
  * logic errors that should be distinguishable from technical errors.
  *
- * `message` is a verbatim string — no interpolation is performed. Put
- * dynamic values in `context`, which is a regular input mapping:
+ * Dynamic values go in `context`, which is a regular input mapping.
+ * `message` may name its entries as `{{ key }}` placeholders; a message
+ * without placeholders is used verbatim.
  *
  * Example:
  * ```json
@@ -2091,7 +2092,7 @@ export interface ErrorResponse {
  *   "id": "credit_limit_error",
  *   "category": "permanent",
  *   "code": "CREDIT_LIMIT_EXCEEDED",
- *   "message": "Order total exceeds credit limit",
+ *   "message": "Order total {{ total }} exceeds credit limit {{ limit }}",
  *   "context": {
  *     "total": { "valueType": "reference", "value": "data.total" },
  *     "limit": { "valueType": "reference", "value": "data.limit" }
@@ -2120,8 +2121,8 @@ export interface ErrorStep {
   /** Unique step identifier */
   id: string;
   /**
-   * Human-readable error message (static string).
-   * For dynamic data, use the `context` field with mappings.
+   * Human-readable error message. `{{ key }}` placeholders are filled
+   * from the resolved `context` entries.
    */
   message: string;
   /** Human-readable step name */
@@ -0,0 +1,9 @@
+-- Parameter schema version each connection was stored with.
+--
+-- Connection types declare a `schema_version` for the shape of their
+-- parameters. When a type changes shape (e.g. gains a required field), rows
+-- stored under an older version are upgraded lazily on fetch by the registered
+-- connection migrations and written back with the new version. Existing rows
+-- predate versioning and therefore start at version 1.
+ALTER TABLE connection_data_entity
+    ADD COLUMN IF NOT EXISTS schema_version INTEGER NOT NULL DEFAULT 1;
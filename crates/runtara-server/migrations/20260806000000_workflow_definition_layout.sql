@@ -0,0 +1,9 @@
+-- Editor layout saved separately from the executable definition.
+--
+-- `layout` holds the `notes` / `nodes` / `edges` the visual editor writes via
+-- the layout endpoint. Reads overlay it onto `definition`; compilation and the
+-- image freshness checksum only ever see `definition`, so moving a node on
+-- the canvas neither creates a version nor invalidates the compiled image.
+-- Saving the whole graph writes the layout into `definition` again and clears
+-- this column. NULL means the definition's own layout is current.
+ALTER TABLE workflow_definitions ADD COLUMN IF NOT EXISTS layout JSONB;
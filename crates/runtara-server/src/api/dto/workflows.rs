@@ -340,6 +340,17 @@ impl ValidationErrorDto {
                 Some("executionPlan".to_string()),
                 Some(vec![to_step.clone()]),
             ),
+            ValidationError::ErrorStepNormalEdge {
+                from_step, to_step, ..
+            } => (
+                format!(
+                    "Error step '{}' has a non-onError edge to '{}'; an Error step always fails",
+                    from_step, to_step
+                ),
+                Some(from_step.clone()),
+                Some("executionPlan".to_string()),
+                Some(vec![to_step.clone()]),
+            ),
             ValidationError::UndefinedDataReference {
                 step_id,
                 reference,
@@ -639,6 +650,40 @@ impl ValidationErrorDto {
                 None,
                 None,
             ),
+            ValidationError::InvalidAdaptiveParallelism { step_id, min, max } => (
+                format!(
+                    "Split step '{}' has adaptiveParallelism min={} max={}. Both bounds must be at least 1 and min must not exceed max.",
+                    step_id, min, max
+                ),
+                Some(step_id.clone()),
+                Some("adaptiveParallelism".to_string()),
+                None,
+            ),
+            ValidationError::SetVariableUnknownVariable {
+                step_id,
+                variable_name,
+                ..
+            } => (
+                format!(
+                    "SetVariable step '{}' assigns undeclared variable '{}'. Declare it under the workflow's variables first.",
+                    step_id, variable_name
+                ),
+                Some(step_id.clone()),
+                Some(format!("values.{}", variable_name)),
+                None,
+            ),
+            ValidationError::SetVariableInSubgraph {
+                step_id,
+                parent_step_id,
+            } => (
+                format!(
+                    "SetVariable step '{}' is inside the subgraph of '{}'. Subgraphs run against a copy of the variables; move the step to the top-level graph.",
+                    step_id, parent_step_id
+                ),
+                Some(step_id.clone()),
+                None,
+                Some(vec![parent_step_id.clone()]),
+            ),
         };
 
         Self {
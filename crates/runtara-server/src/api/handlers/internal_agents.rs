@@ -9,6 +9,10 @@
 //! Connection resolution: if the input contains a `connection_id` field,
 //! the handler fetches full credentials from the connection service and
 //! injects them as `_connection` before calling the agent.
+//!
+//! Capabilities with a concurrency limit (`max_concurrency` or
+//! `RUNTARA_CAPABILITY_CONCURRENCY`) queue here; the envelope reports the wait
+//! as `queue_wait_ms`.
 
 use axum::{extract::Path, http::StatusCode, response::Json};
 use serde_json::{Value, json};
@@ -164,19 +168,33 @@ async fn run_agent(
     let capability_id = capability_id.to_string();
 
     let result = tokio::task::spawn_blocking(move || {
-        runtara_agents::registry::execute_capability(&module, &capability_id, input)
+        let execution =
+            runtara_agents::registry::execute_capability_queued(&module, &capability_id, input);
+        if !execution.queue_wait.is_zero() {
+            tracing::info!(
+                module = %module,
+                capability_id = %capability_id,
+                queue_wait_ms = execution.queue_wait.as_millis() as u64,
+                "Agent capability waited for a concurrency slot"
+            );
+        }
+        execution
     })
     .await;
 
     match result {
-        Ok(Ok(output)) => (
-            StatusCode::OK,
-            Json(json!({ "success": true, "output": output })),
-        ),
-        Ok(Err(error)) => (
-            StatusCode::OK,
-            Json(json!({ "success": false, "error": error })),
-        ),
+        Ok(execution) => {
+            let queue_wait_ms = execution.queue_wait.as_millis() as u64;
+            let body = match execution.result {
+                Ok(output) => {
+                    json!({ "success": true, "output": output, "queue_wait_ms": queue_wait_ms })
+                }
+                Err(error) => {
+                    json!({ "success": false, "error": error, "queue_wait_ms": queue_wait_ms })
+                }
+            };
+            (StatusCode::OK, Json(body))
+        }
         Err(join_err) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "success": false, "error": format!("Task panicked: {}", join_err) })),
@@ -422,11 +422,19 @@ pub async fn execute_proxy_request(
         let conn = facade
             .get_with_parameters(connection_id, tenant_id)
             .await
-            .map_err(|e| {
-                (
+            .map_err(|e| match e {
+                runtara_connections::ConnectionsError::SchemaMismatch(mismatch) => (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(json!({
+                        "error": mismatch.to_string(),
+                        "code": runtara_agents::extractors::CONNECTION_SCHEMA_MISMATCH,
+                        "details": mismatch,
+                    })),
+                ),
+                e => (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(json!({"error": format!("Database error fetching connection: {}", e)})),
-                )
+                ),
             })?
             .ok_or_else(|| {
                 (
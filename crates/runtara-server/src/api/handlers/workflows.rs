@@ -519,6 +519,70 @@ pub async fn patch_version_graph_handler(
     (StatusCode::OK, Json(response))
 }
 
+/// Save a workflow version's editor layout without touching its graph
+#[utoipa::path(
+    put,
+    path = "/api/runtime/workflows/{id}/versions/{version}/layout",
+    request_body = runtara_dsl::WorkflowLayout,
+    params(
+        ("id" = String, Path, description = "Workflow identifier"),
+        ("version" = i32, Path, description = "Version number")
+    ),
+    responses(
+        (status = 200, description = "Layout saved; no new version, compiled image kept", body = Value),
+        (status = 400, description = "Validation error", body = Value),
+        (status = 404, description = "Version not found", body = Value),
+        (status = 500, description = "Internal server error", body = Value)
+    ),
+    tag = "workflow-controller"
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_version_layout_handler(
+    crate::middleware::tenant_auth::OrgId(tenant_id): crate::middleware::tenant_auth::OrgId,
+    crate::middleware::tenant_auth::Caller { user_id, role }: crate::middleware::tenant_auth::Caller,
+    State(pool): State<PgPool>,
+    State(connections): State<Arc<ConnectionsFacade>>,
+    State(agent_catalog): State<Arc<runtara_dsl::agent_meta::AgentCatalog>>,
+    Path((workflow_id, version)): Path<(String, i32)>,
+    Json(layout): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    let repository = Arc::new(WorkflowRepository::new(pool.clone()));
+
+    // Own-scoped authorization: a Member may edit only workflows they created.
+    let owner = repository
+        .owner(&tenant_id, &workflow_id)
+        .await
+        .ok()
+        .flatten();
+    if let Err(denial) = crate::middleware::authorization::require_ownership(
+        crate::auth::membership_policy(),
+        &tenant_id,
+        role,
+        crate::authz::Permission::WorkflowUpdate,
+        owner.as_deref(),
+        &user_id,
+    ) {
+        return (StatusCode::FORBIDDEN, Json(denial.json_body()));
+    }
+
+    let service = WorkflowService::new(repository, connections, agent_catalog);
+    if let Err(e) = service
+        .update_version_layout(&tenant_id, &workflow_id, version, layout)
+        .await
+    {
+        return map_service_error_to_response(e);
+    }
+
+    let response = json!({
+        "success": true,
+        "message": "Version layout saved",
+        "workflowId": workflow_id,
+        "version": version.to_string(),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    });
+    (StatusCode::OK, Json(response))
+}
+
 /// Toggle step-event tracking for a specific workflow version
 #[utoipa::path(
     put,
@@ -595,6 +595,8 @@ impl WorkflowRepository {
         // Query workflow definition and metadata
         // The definition column in workflow_definitions IS the execution_graph
         // name/description are now extracted from the definition JSON
+        // A layout saved on its own (update_version_layout) overlays the
+        // definition's notes/nodes/edges
         let row: Option<(
             Value,          // definition (execution_graph)
             DateTime<Utc>,  // created_at
@@ -607,7 +609,8 @@ impl WorkflowRepository {
             Option<String>, // slug
         )> = sqlx::query_as(
             r#"
-            SELECT sd.definition, sd.created_at, sd.updated_at, sd.memory_tier, sd.track_events,
+            SELECT sd.definition || COALESCE(sd.layout, '{}'::jsonb),
+                   sd.created_at, sd.updated_at, sd.memory_tier, sd.track_events,
                    s.latest_version, s.current_version, s.path, s.slug
             FROM workflow_definitions sd
             JOIN workflows s ON sd.tenant_id = s.tenant_id AND sd.workflow_id = s.workflow_id
@@ -1426,6 +1429,21 @@ impl WorkflowRepository {
         .await?;
 
         if result.rows_affected() > 0 {
+            // The new graph carries its own layout, which supersedes any saved
+            // separately
+            sqlx::query(
+                r#"
+                UPDATE workflow_definitions
+                SET layout = NULL
+                WHERE tenant_id = $1 AND workflow_id = $2 AND version = $3
+                "#,
+            )
+            .bind(tenant_id)
+            .bind(workflow_id)
+            .bind(version)
+            .execute(&self.pool)
+            .await?;
+
             // Invalidate compilation since the graph changed
             self.invalidate_compilation(tenant_id, workflow_id, version)
                 .await?;
@@ -1434,6 +1452,34 @@ impl WorkflowRepository {
         Ok(result.rows_affected())
     }
 
+    /// Save a version's editor layout (`notes`/`nodes`/`edges`) apart from its
+    /// definition. Reads overlay it onto the definition; compilation and the
+    /// image checksum never see it, so the compiled image stays fresh.
+    /// Returns the number of rows affected (0 if version not found).
+    pub async fn update_version_layout(
+        &self,
+        tenant_id: &str,
+        workflow_id: &str,
+        version: i32,
+        layout: &Value,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE workflow_definitions
+            SET layout = $4
+            WHERE tenant_id = $1 AND workflow_id = $2 AND version = $3 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(workflow_id)
+        .bind(version)
+        .bind(layout)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Invalidate (delete) the compiled binary for a workflow version
     /// This forces recompilation on next execution
     pub async fn invalidate_compilation(
@@ -1561,10 +1607,11 @@ impl WorkflowRepository {
             None => return Ok(0), // Source workflow not found
         };
 
-        // Fetch the version definition to clone
+        // Fetch the version definition to clone, with any separately saved
+        // layout folded in
         let source_version: Option<(Value, i32, String, bool)> = sqlx::query_as(
             r#"
-            SELECT definition, file_size, memory_tier, track_events
+            SELECT definition || COALESCE(layout, '{}'::jsonb), file_size, memory_tier, track_events
             FROM workflow_definitions
             WHERE tenant_id = $1 AND workflow_id = $2 AND version = $3 AND deleted_at IS NULL
             "#,
@@ -14,15 +14,20 @@ use crate::valkey::compilation_progress::{CompilationStage, ProgressReporter};
 use opentelemetry::KeyValue;
 use redis::aio::ConnectionManager;
 use runtara_dsl::parse_execution_graph;
-use runtara_management_sdk::{ImageSummary, RegisterImageStreamOptions, RunnerType};
+use runtara_management_sdk::{
+    ImageChildSource, ImageProvenance, ImageSummary, ImageWarning, RegisterImageStreamOptions,
+    RunnerType, WarningSeverity,
+};
 use runtara_workflows::compile::ProgressCallback;
 use runtara_workflows::direct_wasm::{
     DIRECT_WORKFLOW_ARTIFACT_METADATA_FILENAME, DirectArtifactMetadata,
 };
 use runtara_workflows::{
-    ChildWorkflowInput, CompilationInput, DirectWorkflowCompileOptions, NativeCompilationResult,
-    ValidationError, WorkflowCompilerMode, compile_workflow_direct,
+    COMPILER_VERSION, ChildWorkflowInput, CompilationInput, DirectWorkflowCompileOptions,
+    NativeCompilationResult, RUSTC_VERSION, ValidationError, WorkflowCompilerMode,
+    compile_workflow_direct,
 };
+use sha2::{Digest, Sha256};
 
 /// Global semaphore limiting concurrent compilations across all code paths.
 /// Prevents OOM when multiple compilations are triggered simultaneously.
@@ -85,6 +90,64 @@ fn image_cache_hits(
         && image_compiler_mode(image) == Some(compiler_mode.as_str())
 }
 
+/// Compile warnings in the shape the environment stores with the image.
+fn image_warnings(compilation_result: &NativeCompilationResult) -> Vec<ImageWarning> {
+    compilation_result
+        .warnings
+        .iter()
+        .map(|w| ImageWarning {
+            code: w.code.clone(),
+            step_id: w.step_id.clone(),
+            message: w.message.clone(),
+            severity: match w.severity {
+                runtara_workflows::WarningSeverity::Info => WarningSeverity::Info,
+                runtara_workflows::WarningSeverity::Warning => WarningSeverity::Warning,
+                runtara_workflows::WarningSeverity::Error => WarningSeverity::Error,
+            },
+        })
+        .collect()
+}
+
+/// Build provenance registered with the image: the exact scenario sources it
+/// was compiled from and the fingerprint of the toolchain that compiled it.
+fn image_provenance(
+    registration: WorkflowImageRegistration<'_>,
+    direct_artifact: Option<&DirectArtifactMetadata>,
+) -> ImageProvenance {
+    ImageProvenance {
+        source: Some(registration.source.clone()),
+        child_sources: registration.child_sources.to_vec(),
+        graph_hash: Some(registration.source_checksum.to_string()),
+        compiler_version: Some(COMPILER_VERSION.to_string()),
+        rustc_version: Some(RUSTC_VERSION.to_string()),
+        agent_library_checksum: direct_artifact.and_then(agent_library_checksum),
+    }
+}
+
+/// SHA-256 over the stdlib/runtime and agent components composed into the
+/// image, keyed by package so the order they were resolved in doesn't matter.
+fn agent_library_checksum(direct_artifact: &DirectArtifactMetadata) -> Option<String> {
+    let mut components: Vec<String> = direct_artifact
+        .shared_components
+        .iter()
+        .chain(&direct_artifact.agent_components)
+        .filter_map(|component| {
+            let wasm = component.wasm.as_ref()?;
+            Some(format!(
+                "{}={}",
+                component.package_with_version, wasm.sha256
+            ))
+        })
+        .collect();
+    if components.is_empty() {
+        return None;
+    }
+    components.sort();
+    Some(hex::encode(Sha256::digest(
+        components.join("\n").as_bytes(),
+    )))
+}
+
 fn workflow_image_metadata(
     compilation_result: &NativeCompilationResult,
     workflow_id: &str,
@@ -157,6 +220,10 @@ struct WorkflowImageRegistration<'a> {
     workflow_id: &'a str,
     version: u32,
     source_checksum: &'a str,
+    /// Workflow definition the image was compiled from.
+    source: &'a serde_json::Value,
+    /// Definitions of the child workflows compiled into the image.
+    child_sources: &'a [ImageChildSource],
 }
 
 /// Direct WASM compilation settings.
@@ -410,7 +477,7 @@ impl CompilationService {
         // 3. Load child workflows from database
         let step_start = std::time::Instant::now();
         debug!("compile: step 3 - loading child workflows from database");
-        let child_workflows = self
+        let (child_workflows, child_sources) = self
             .load_child_workflows_as_input(tenant_id, workflow_id, version, &definition)
             .await?;
         debug!(
@@ -700,6 +767,8 @@ impl CompilationService {
                     workflow_id,
                     version: version_u32,
                     source_checksum: &source_checksum,
+                    source: &definition,
+                    child_sources: &child_sources,
                 },
             )
             .await?;
@@ -834,7 +903,7 @@ impl CompilationService {
         let execution_graph = parse_execution_graph(&definition).map_err(|e| {
             ServiceError::CompilationError(format!("Failed to parse execution graph: {e}"))
         })?;
-        let child_workflows = self
+        let (child_workflows, _) = self
             .load_child_workflows_as_input(tenant_id, workflow_id, version, &definition)
             .await?;
 
@@ -923,14 +992,16 @@ impl CompilationService {
         }))
     }
 
-    /// Load child workflows from database and convert to ChildWorkflowInput
+    /// Load child workflows from database and convert to ChildWorkflowInput,
+    /// alongside their unparsed definitions for the image provenance (one per
+    /// distinct workflow version).
     async fn load_child_workflows_as_input(
         &self,
         tenant_id: &str,
         workflow_id: &str,
         version: i32,
         definition: &serde_json::Value,
-    ) -> Result<Vec<ChildWorkflowInput>, ServiceError> {
+    ) -> Result<(Vec<ChildWorkflowInput>, Vec<ImageChildSource>), ServiceError> {
         let child_workflows_list = load_child_workflows(
             self.repository.pool(),
             tenant_id,
@@ -955,6 +1026,7 @@ impl CompilationService {
 
         // Convert to ChildWorkflowInput
         let mut child_workflows = Vec::new();
+        let mut child_sources: Vec<ImageChildSource> = Vec::new();
         for info in child_workflows_list {
             let graph = parse_execution_graph(&info.execution_graph).map_err(|e| {
                 ServiceError::CompilationError(format!(
@@ -963,6 +1035,17 @@ impl CompilationService {
                 ))
             })?;
 
+            if !child_sources.iter().any(|source| {
+                source.workflow_id == info.workflow_ref.workflow_id
+                    && source.version == info.workflow_ref.version
+            }) {
+                child_sources.push(ImageChildSource {
+                    workflow_id: info.workflow_ref.workflow_id.clone(),
+                    version: info.workflow_ref.version,
+                    source: info.execution_graph,
+                });
+            }
+
             child_workflows.push(ChildWorkflowInput {
                 step_id: info.step_id,
                 workflow_id: info.workflow_ref.workflow_id,
@@ -972,7 +1055,7 @@ impl CompilationService {
             });
         }
 
-        Ok(child_workflows)
+        Ok((child_workflows, child_sources))
     }
 
     /// Register a compiled binary with runtara-environment using streaming upload
@@ -1016,7 +1099,9 @@ impl CompilationService {
                     registration.version,
                     registration.source_checksum,
                     direct_artifact.as_ref(),
-                ));
+                ))
+                .with_warnings(image_warnings(compilation_result))
+                .with_provenance(image_provenance(registration, direct_artifact.as_ref()));
 
         // Open the binary file for streaming
         let file = tokio::fs::File::open(&binary_path).await.map_err(|e| {
@@ -1183,6 +1268,7 @@ mod tests {
             child_dependencies: vec![],
             default_variables: serde_json::json!({ "limit": 5 }),
             compiler_mode: WorkflowCompilerMode::DirectWasm,
+            warnings: vec![],
         };
 
         let metadata = workflow_image_metadata(&result, "workflow-a", 7, "source-sha256", None);
@@ -1201,6 +1287,82 @@ mod tests {
         assert_eq!(metadata["workflow"]["directWasm"]["reason"], "none");
     }
 
+    #[test]
+    fn image_warnings_carry_compile_warnings_to_the_registration() {
+        let mut result =
+            native_result_with_mode(WorkflowCompilerMode::DirectWasm, "/tmp/build".into());
+        result.warnings = vec![runtara_workflows::CompileWarning {
+            code: "W090".to_string(),
+            step_id: Some("finish".to_string()),
+            message: "large literal".to_string(),
+            severity: runtara_workflows::WarningSeverity::Warning,
+        }];
+
+        let warnings = image_warnings(&result);
+
+        assert_eq!(
+            warnings,
+            vec![ImageWarning {
+                code: "W090".to_string(),
+                step_id: Some("finish".to_string()),
+                message: "large literal".to_string(),
+                severity: WarningSeverity::Warning,
+            }]
+        );
+    }
+
+    #[test]
+    fn image_provenance_records_sources_and_toolchain_fingerprint() {
+        let definition = serde_json::json!({"entryPoint": "finish", "steps": {}});
+        let child_sources = vec![ImageChildSource {
+            workflow_id: "child".to_string(),
+            version: 3,
+            source: serde_json::json!({"entryPoint": "done", "steps": {}}),
+        }];
+        let registration = WorkflowImageRegistration {
+            tenant_id: "tenant-a",
+            workflow_id: "workflow-a",
+            version: 7,
+            source_checksum: "source-sha256",
+            source: &definition,
+            child_sources: &child_sources,
+        };
+        let artifact = direct_artifact_metadata_fixture();
+
+        let provenance = image_provenance(registration, Some(&artifact));
+
+        assert_eq!(provenance.source.as_ref(), Some(&definition));
+        assert_eq!(provenance.child_sources, child_sources);
+        assert_eq!(provenance.graph_hash.as_deref(), Some("source-sha256"));
+        assert_eq!(
+            provenance.compiler_version.as_deref(),
+            Some(COMPILER_VERSION)
+        );
+        assert!(
+            provenance
+                .rustc_version
+                .as_deref()
+                .is_some_and(|version| version.starts_with("rustc "))
+        );
+
+        // The agent library checksum ignores component resolution order and
+        // is absent without direct artifact metadata.
+        let mut reordered = artifact.clone();
+        reordered
+            .shared_components
+            .append(&mut reordered.agent_components);
+        reordered.shared_components.reverse();
+        assert_eq!(
+            provenance.agent_library_checksum,
+            agent_library_checksum(&reordered)
+        );
+        assert!(
+            image_provenance(registration, None)
+                .agent_library_checksum
+                .is_none()
+        );
+    }
+
     #[test]
     fn workflow_image_metadata_records_direct_artifact_provenance() {
         let result = native_result_with_mode(WorkflowCompilerMode::DirectWasm, "/tmp/build".into());
@@ -1270,6 +1432,7 @@ mod tests {
             child_dependencies: vec![],
             default_variables: serde_json::json!({}),
             compiler_mode,
+            warnings: vec![],
         }
     }
 
@@ -1282,6 +1445,9 @@ mod tests {
             runner_type: RunnerType::Wasm,
             created_at: chrono::Utc::now(),
             metadata: Some(metadata),
+            warning_count: 0,
+            warnings: None,
+            provenance: None,
         }
     }
 
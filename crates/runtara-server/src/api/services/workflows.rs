@@ -114,6 +114,28 @@ fn json_value_kind(value: &Value) -> &'static str {
     }
 }
 
+/// Give every note in `definition.notes` an ID, generating one where it is
+/// missing or empty.
+fn assign_missing_note_ids(definition: &mut Value) {
+    if let Some(notes_array) = definition.get_mut("notes").and_then(|n| n.as_array_mut()) {
+        for note_value in notes_array.iter_mut() {
+            if let Some(note_obj) = note_value.as_object_mut() {
+                let needs_id = note_obj
+                    .get("id")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.is_empty())
+                    .unwrap_or(true);
+                if needs_id {
+                    note_obj.insert(
+                        "id".to_string(),
+                        serde_json::Value::String(Uuid::new_v4().to_string()),
+                    );
+                }
+            }
+        }
+    }
+}
+
 impl WorkflowService {
     pub fn new(
         repository: Arc<WorkflowRepository>,
@@ -533,25 +555,7 @@ impl WorkflowService {
 
         // Process notes: ensure all notes have IDs (generate if missing)
         let mut definition = definition;
-        if let Some(notes_array) = definition.get_mut("notes").and_then(|n| n.as_array_mut()) {
-            for note_value in notes_array.iter_mut() {
-                if let Some(note_obj) = note_value.as_object_mut() {
-                    // Check if id is missing or empty
-                    let needs_id = note_obj
-                        .get("id")
-                        .and_then(|v| v.as_str())
-                        .map(|s| s.is_empty())
-                        .unwrap_or(true);
-
-                    if needs_id {
-                        note_obj.insert(
-                            "id".to_string(),
-                            serde_json::Value::String(Uuid::new_v4().to_string()),
-                        );
-                    }
-                }
-            }
-        }
+        assign_missing_note_ids(&mut definition);
 
         // Collect warnings from all validation stages
         let mut all_warnings: Vec<String> = Vec::new();
@@ -812,23 +816,7 @@ impl WorkflowService {
 
         // Process notes: ensure all notes have IDs
         let mut definition = definition;
-        if let Some(notes_array) = definition.get_mut("notes").and_then(|n| n.as_array_mut()) {
-            for note_value in notes_array.iter_mut() {
-                if let Some(note_obj) = note_value.as_object_mut() {
-                    let needs_id = note_obj
-                        .get("id")
-                        .and_then(|v| v.as_str())
-                        .map(|s| s.is_empty())
-                        .unwrap_or(true);
-                    if needs_id {
-                        note_obj.insert(
-                            "id".to_string(),
-                            serde_json::Value::String(Uuid::new_v4().to_string()),
-                        );
-                    }
-                }
-            }
-        }
+        assign_missing_note_ids(&mut definition);
 
         let all_warnings: Vec<String> = Vec::new();
 
@@ -871,6 +859,48 @@ impl WorkflowService {
         Ok(all_warnings)
     }
 
+    /// Save a version's editor layout (`notes`/`nodes`/`edges`) without
+    /// touching its executable definition.
+    ///
+    /// No version is created and the compiled image stays valid. The layout
+    /// may not carry any other key, so steps cannot be smuggled past
+    /// compilation this way.
+    pub async fn update_version_layout(
+        &self,
+        tenant_id: &str,
+        workflow_id: &str,
+        version: i32,
+        layout: Value,
+    ) -> Result<(), ServiceError> {
+        if !is_valid_identifier(workflow_id) {
+            return Err(ServiceError::ValidationError(
+                "Workflow ID must contain only alphanumeric characters, hyphens, and underscores. It cannot start or end with a hyphen or underscore.".to_string()
+            ));
+        }
+
+        let mut layout = layout;
+        serde_json::from_value::<runtara_dsl::WorkflowLayout>(layout.clone())
+            .map_err(|e| ServiceError::ValidationError(format!("Invalid layout format: {}", e)))?;
+        assign_missing_note_ids(&mut layout);
+
+        let rows = self
+            .repository
+            .update_version_layout(tenant_id, workflow_id, version, &layout)
+            .await
+            .map_err(|e| {
+                ServiceError::DatabaseError(format!("Failed to save version layout: {}", e))
+            })?;
+
+        if rows == 0 {
+            return Err(ServiceError::NotFound(format!(
+                "Version {} not found for workflow '{}'",
+                version, workflow_id
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Toggle track-events mode for a specific workflow version.
     /// When toggled, the compiled binary is invalidated to force recompilation.
     pub async fn toggle_track_events(
@@ -1414,6 +1444,24 @@ mod tests {
     // ServiceError Display tests
     // =========================================================================
 
+    #[test]
+    fn test_assign_missing_note_ids_keeps_existing_ids() {
+        let mut definition = serde_json::json!({
+            "notes": [
+                { "id": "keep", "text": "a" },
+                { "id": "", "text": "b" },
+                { "text": "c", "color": "yellow" }
+            ]
+        });
+        assign_missing_note_ids(&mut definition);
+
+        let notes = definition["notes"].as_array().unwrap();
+        assert_eq!(notes[0]["id"], "keep");
+        assert!(!notes[1]["id"].as_str().unwrap().is_empty());
+        assert!(!notes[2]["id"].as_str().unwrap().is_empty());
+        assert_eq!(notes[2]["color"], "yellow");
+    }
+
     #[test]
     fn test_service_error_validation_display() {
         let error = ServiceError::ValidationError("Name cannot be empty".to_string());
@@ -18,6 +18,7 @@ use std::sync::Arc;
 use runtara_core::persistence::Persistence;
 use runtara_core::persistence::postgres::PostgresPersistence;
 use runtara_core::runtime::CoreRuntime;
+use runtara_core::session::InstanceSessions;
 use runtara_environment::runtime::EnvironmentRuntime;
 use sqlx::PgPool;
 use sqlx::postgres::PgPoolOptions;
@@ -78,6 +79,7 @@ impl EmbeddedRuntara {
         let core = CoreRuntime::builder()
             .persistence(persistence.clone())
             .bind_addr(core_http_addr)
+            .instance_sessions(InstanceSessions::from_env())
             .build()?
             .start()
             .await?;
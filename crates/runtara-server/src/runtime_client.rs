@@ -314,9 +314,9 @@ impl RuntimeClient {
         }
 
         let result = sdk.start_instance(options).await.map_err(|e| match e {
-            runtara_management_sdk::SdkError::ImageNotFound(message) => {
-                RuntimeError::ImageNotFound(message)
-            }
+            runtara_management_sdk::SdkError::NotFound {
+                resource, message, ..
+            } if resource == "image" => RuntimeError::ImageNotFound(message),
             other => RuntimeError::StartFailed(other.to_string()),
         })?;
 
@@ -48,6 +48,7 @@ use runtime_client::RuntimeClient;
         api::handlers::workflows::create_workflow_handler,
         api::handlers::workflows::update_workflow_handler,
         api::handlers::workflows::toggle_track_events_handler,
+        api::handlers::workflows::update_version_layout_handler,
         api::handlers::workflows::update_workflow_slug_handler,
         api::handlers::workflows::publish_workflow_agent_handler,
         api::handlers::workflows::list_workflows_handler,
@@ -211,7 +212,11 @@ use runtime_client::RuntimeClient;
             runtara_dsl::ExecutionGraph,
             runtara_dsl::ExecutionPlanEdge,
             runtara_dsl::Note,
+            runtara_dsl::NoteMetadata,
             runtara_dsl::Position,
+            runtara_dsl::WorkflowLayout,
+            runtara_dsl::LayoutNode,
+            runtara_dsl::LayoutEdge,
             runtara_dsl::Step,
             runtara_dsl::StepCommon,
             runtara_dsl::FinishStep,
@@ -1621,6 +1626,10 @@ pub async fn start(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
             "/api/runtime/workflows/{id}/versions/{version}/graph",
             put(api::handlers::workflows::patch_version_graph_handler),
         )
+        .route(
+            "/api/runtime/workflows/{id}/versions/{version}/layout",
+            put(api::handlers::workflows::update_version_layout_handler),
+        )
         .route(
             "/api/runtime/workflows/{id}/versions/{version}/track-events",
             put(api::handlers::workflows::toggle_track_events_handler),
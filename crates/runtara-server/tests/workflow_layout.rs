@@ -0,0 +1,197 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Editor layout stored apart from the executable definition.
+//!
+//! Saving a layout must neither create a version nor make the compiled image
+//! stale, while reads still return the saved layout. Patching the whole graph
+//! supersedes a separately saved layout.
+//!
+//! Requires the explicit `db-integration-tests` feature and a live Postgres.
+
+use runtara_server::api::repositories::workflows::{
+    WorkflowRepository, workflow_definition_checksum,
+};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+macro_rules! skip_if_no_db {
+    () => {
+        assert!(
+            std::env::var("TEST_RUNTARA_SERVER_DATABASE_URL").is_ok()
+                || std::env::var("RUNTARA_SERVER_DATABASE_URL").is_ok(),
+            "db-integration-tests requires TEST_RUNTARA_SERVER_DATABASE_URL or RUNTARA_SERVER_DATABASE_URL"
+        );
+    };
+}
+
+static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
+
+async fn get_test_pool() -> PgPool {
+    let url = std::env::var("TEST_RUNTARA_SERVER_DATABASE_URL")
+        .or_else(|_| std::env::var("RUNTARA_SERVER_DATABASE_URL"))
+        .expect("db-integration-tests requires a server database URL");
+    let pool = PgPool::connect(&url)
+        .await
+        .expect("required server test database must accept connections");
+    MIGRATOR
+        .run(&pool)
+        .await
+        .expect("required server migrations must succeed");
+    pool
+}
+
+fn definition() -> Value {
+    json!({
+        "name": "Layout",
+        "entryPoint": "finish",
+        "steps": {
+            "finish": { "stepType": "Finish", "id": "finish" }
+        },
+        "nodes": [{ "id": "finish", "type": "BasicNode", "position": { "x": 0, "y": 0 } }]
+    })
+}
+
+/// Insert a workflow with one version of `definition` and a successful,
+/// registered compilation of exactly that definition.
+async fn seed_compiled_workflow(pool: &PgPool, definition: &Value) -> (String, String) {
+    let tenant = format!("t-{}", Uuid::new_v4());
+    let workflow_id = Uuid::new_v4().to_string();
+
+    sqlx::query(
+        "INSERT INTO workflows (tenant_id, workflow_id, version_count, latest_version)
+         VALUES ($1, $2, 1, 1)",
+    )
+    .bind(&tenant)
+    .bind(&workflow_id)
+    .execute(pool)
+    .await
+    .expect("seeding a workflow must succeed");
+
+    let file_size = serde_json::to_vec(definition)
+        .expect("definition must serialize")
+        .len() as i32;
+    sqlx::query(
+        "INSERT INTO workflow_definitions (tenant_id, workflow_id, version, definition, file_size)
+         VALUES ($1, $2, 1, $3, $4)",
+    )
+    .bind(&tenant)
+    .bind(&workflow_id)
+    .bind(definition)
+    .bind(file_size)
+    .execute(pool)
+    .await
+    .expect("seeding a workflow definition must succeed");
+
+    sqlx::query(
+        "INSERT INTO workflow_compilations
+            (tenant_id, workflow_id, version, compilation_status, translated_path,
+             source_checksum, registered_image_id)
+         VALUES ($1, $2, 1, 'success', '', $3, 'image-1')",
+    )
+    .bind(&tenant)
+    .bind(&workflow_id)
+    .bind(workflow_definition_checksum(definition))
+    .execute(pool)
+    .await
+    .expect("recording a compilation must succeed");
+
+    (tenant, workflow_id)
+}
+
+async fn version_count(pool: &PgPool, tenant: &str, workflow_id: &str) -> i64 {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM workflow_definitions WHERE tenant_id = $1 AND workflow_id = $2",
+    )
+    .bind(tenant)
+    .bind(workflow_id)
+    .fetch_one(pool)
+    .await
+    .expect("counting versions must succeed")
+}
+
+#[tokio::test]
+async fn saving_a_layout_keeps_the_version_and_the_compiled_image() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+    let (tenant, workflow_id) = seed_compiled_workflow(&pool, &definition()).await;
+    let repo = WorkflowRepository::new(pool.clone());
+
+    let layout = json!({
+        "nodes": [{
+            "id": "finish",
+            "type": "BasicNode",
+            "position": { "x": 320.5, "y": 80 },
+            "collapsed": true
+        }],
+        "notes": [{ "id": "n1", "text": "moved", "position": { "x": 1, "y": 2 } }]
+    });
+    let rows = repo
+        .update_version_layout(&tenant, &workflow_id, 1, &layout)
+        .await
+        .expect("saving a layout must succeed");
+    assert_eq!(rows, 1);
+
+    assert_eq!(version_count(&pool, &tenant, &workflow_id).await, 1);
+    assert_eq!(
+        repo.get_fresh_registered_image_id(&tenant, &workflow_id, 1)
+            .await
+            .expect("freshness check must succeed")
+            .as_deref(),
+        Some("image-1"),
+        "a layout change must not make the compiled image stale"
+    );
+
+    let workflow = repo
+        .get_by_id(&tenant, &workflow_id, Some(1))
+        .await
+        .expect("reading the workflow must succeed")
+        .expect("the workflow exists");
+    assert_eq!(workflow.execution_graph["nodes"], layout["nodes"]);
+    assert_eq!(workflow.execution_graph["notes"], layout["notes"]);
+    assert_eq!(workflow.execution_graph["steps"], definition()["steps"]);
+}
+
+#[tokio::test]
+async fn patching_the_graph_supersedes_a_saved_layout() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+    let (tenant, workflow_id) = seed_compiled_workflow(&pool, &definition()).await;
+    let repo = WorkflowRepository::new(pool.clone());
+
+    repo.update_version_layout(
+        &tenant,
+        &workflow_id,
+        1,
+        &json!({ "nodes": [{ "id": "finish", "position": { "x": 9, "y": 9 } }] }),
+    )
+    .await
+    .expect("saving a layout must succeed");
+
+    let mut patched = definition();
+    patched["nodes"] = json!([{ "id": "finish", "position": { "x": 50, "y": 60 } }]);
+    repo.update_version_graph(&tenant, &workflow_id, 1, &patched)
+        .await
+        .expect("patching the graph must succeed");
+
+    let workflow = repo
+        .get_by_id(&tenant, &workflow_id, Some(1))
+        .await
+        .expect("reading the workflow must succeed")
+        .expect("the workflow exists");
+    assert_eq!(workflow.execution_graph["nodes"], patched["nodes"]);
+}
+
+#[tokio::test]
+async fn saving_a_layout_for_a_missing_version_touches_nothing() {
+    skip_if_no_db!();
+    let pool = get_test_pool().await;
+    let (tenant, workflow_id) = seed_compiled_workflow(&pool, &definition()).await;
+    let repo = WorkflowRepository::new(pool.clone());
+
+    let rows = repo
+        .update_version_layout(&tenant, &workflow_id, 2, &json!({ "nodes": [] }))
+        .await
+        .expect("the update itself must succeed");
+    assert_eq!(rows, 0);
+}
@@ -232,7 +232,7 @@ pub fn blocking_sleep(ms: u64) -> Result<(), String> {
 }
 
 pub fn get_checkpoint(checkpoint_id: &str) -> Result<Option<Vec<u8>>, String> {
-    with_sdk(|sdk| sdk.get_checkpoint(checkpoint_id).map_err(sdk_error))
+    with_sdk(|sdk| sdk.get_checkpoint_cached(checkpoint_id).map_err(sdk_error))
 }
 
 pub fn checkpoint(checkpoint_id: &str, state: &[u8]) -> Result<RuntimeCheckpointResult, String> {
@@ -243,6 +243,28 @@ pub fn checkpoint(checkpoint_id: &str, state: &[u8]) -> Result<RuntimeCheckpoint
     })
 }
 
+fn compaction_prefixes(prefixes: &[u8]) -> Result<Vec<String>, String> {
+    serde_json::from_slice(prefixes)
+        .map_err(|err| format!("checkpoint compaction prefixes must be a JSON string array: {err}"))
+}
+
+pub fn compact_checkpoints(prefixes: &[u8]) -> Result<u64, String> {
+    // Debug runs keep every per-iteration checkpoint for time-travel inspection.
+    if debug_mode_enabled()? {
+        return Ok(0);
+    }
+    let prefixes = compaction_prefixes(prefixes)?;
+    if prefixes.is_empty() {
+        return Ok(0);
+    }
+
+    with_sdk(|sdk| {
+        prefixes.iter().try_fold(0, |deleted, prefix| {
+            Ok(deleted + sdk.compact_checkpoints(prefix).map_err(sdk_error)?)
+        })
+    })
+}
+
 pub fn handle_checkpoint_signal(signal_type: &str) -> Result<bool, String> {
     match checkpoint_signal_action(signal_type) {
         Some(CheckpointSignalAction::Cancel) => {
@@ -378,6 +400,10 @@ mod component {
             super::checkpoint(&checkpoint_id, &state).map(checkpoint_result)
         }
 
+        fn compact_checkpoints(prefixes: Vec<u8>) -> Result<u64, String> {
+            super::compact_checkpoints(&prefixes)
+        }
+
         fn handle_checkpoint_signal(signal_type: String) -> Result<bool, String> {
             super::handle_checkpoint_signal(&signal_type)
         }
@@ -410,8 +436,8 @@ mod tests {
 
     use super::{
         CheckpointSignalAction, blocking_sleep, breakpoint_pause, checkpoint_signal_action,
-        debug_mode_enabled, now_ms, runtime_checkpoint_result, sdk_error, signal_is_cancel,
-        signal_type_name,
+        compact_checkpoints, compaction_prefixes, debug_mode_enabled, now_ms,
+        runtime_checkpoint_result, sdk_error, signal_is_cancel, signal_type_name,
     };
 
     #[test]
@@ -463,6 +489,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn compaction_prefixes_parse_a_json_string_array() {
+        assert_eq!(
+            compaction_prefixes(br#"["wf::split::s::[","wf::s["]"#).expect("prefixes"),
+            vec!["wf::split::s::[".to_string(), "wf::s[".to_string()]
+        );
+        assert!(compaction_prefixes(b"{}").is_err());
+    }
+
+    #[test]
+    fn compact_checkpoints_without_prefixes_does_not_need_sdk() {
+        assert_eq!(compact_checkpoints(b"[]").expect("empty compaction"), 0);
+    }
+
     #[test]
     fn breakpoint_pause_is_best_effort_without_sdk() {
         breakpoint_pause().expect("breakpoint pause should ignore missing SDK state");
@@ -9,9 +9,9 @@
 
 use std::borrow::Cow;
 use std::cell::{Cell, RefCell};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::rc::Rc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use serde::Deserialize;
 use serde_json::{Map, Value};
@@ -341,9 +341,13 @@ pub struct DirectJsonManifest {
     group_bys: BTreeMap<u32, DirectJsonGroupBy>,
     delays: BTreeMap<u32, DirectJsonDelay>,
     logs: BTreeMap<u32, DirectJsonLog>,
+    set_variables: BTreeMap<u32, DirectJsonSetVariable>,
     errors: BTreeMap<u32, DirectJsonError>,
     agents: BTreeMap<u32, DirectJsonAgent>,
     debug_start_ms: RefCell<BTreeMap<String, i64>>,
+    /// Step start times for [`crate::hooks`] durations, kept apart from
+    /// `debug_start_ms` since hooks fire whether or not debug events do.
+    hook_start_ms: RefCell<BTreeMap<String, i64>>,
     /// Lazily-populated cache of compiled conditions, keyed by a stable string
     /// (`c{id}` Conditional/edge, `w{id}` While, `f{id}` Filter). A condition is
     /// compiled once on first evaluation and reused across every element /
@@ -364,6 +368,15 @@ pub struct DirectJsonAgentRetryError {
     pub rate_limited: bool,
 }
 
+/// Next window of a Split's adaptive concurrency controller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectJsonSplitWindow {
+    pub window: u32,
+    pub baseline_ms: u32,
+    /// `split_concurrency` custom-event payload; empty when the window held.
+    pub event: Vec<u8>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct DirectJsonWorkflowRetryInfo {
     retryable: bool,
@@ -411,9 +424,11 @@ impl DirectJsonManifest {
             group_bys: collections.group_bys,
             delays: collections.delays,
             logs: collections.logs,
+            set_variables: collections.set_variables,
             errors: collections.errors,
             agents: collections.agents,
             debug_start_ms: RefCell::new(BTreeMap::new()),
+            hook_start_ms: RefCell::new(BTreeMap::new()),
             compiled_conditions: RefCell::new(BTreeMap::new()),
             compiled_mappings: RefCell::new(BTreeMap::new()),
         })
@@ -737,6 +752,76 @@ impl DirectJsonManifest {
         Ok(split_cache_key(split, &source).into_bytes())
     }
 
+    /// JSON array of checkpoint-id prefixes covering the per-iteration
+    /// checkpoints of a durable Split's body, compacted once the Split's
+    /// aggregate checkpoint is saved.
+    pub fn split_compaction_prefixes(
+        &self,
+        split_id: u32,
+        source: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        let source: Value = serde_json::from_slice(source)
+            .map_err(|err| format!("failed to parse Split compaction source: {err}"))?;
+        let split = self
+            .splits
+            .get(&split_id)
+            .ok_or_else(|| format!("unknown direct Split id {split_id}"))?;
+
+        serde_json::to_vec(&split_compaction_prefixes(split, &source))
+            .map_err(|err| format!("failed to serialize Split compaction prefixes: {err}"))
+    }
+
+    /// Advance a Split's adaptive concurrency controller past one chunk.
+    ///
+    /// `window` is the width of the chunk that just drained, `rate_limited`
+    /// whether any of its items failed with a `RATE_LIMITED` error, and
+    /// `elapsed_ms` its launch-to-drain wall time. `baseline_ms` is the
+    /// fastest healthy chunk so far (0 before the first). A Split without
+    /// `adaptiveParallelism` keeps its window and never reports an event.
+    pub fn split_next_window(
+        &self,
+        split_id: u32,
+        window: u32,
+        rate_limited: bool,
+        elapsed_ms: u32,
+        baseline_ms: u32,
+    ) -> Result<DirectJsonSplitWindow, String> {
+        let split = self
+            .splits
+            .get(&split_id)
+            .ok_or_else(|| format!("unknown direct Split id {split_id}"))?;
+        let Some(bounds) = split_adaptive_bounds(split) else {
+            return Ok(DirectJsonSplitWindow {
+                window,
+                baseline_ms,
+                event: Vec::new(),
+            });
+        };
+        let step = adaptive_window_step(bounds, window, rate_limited, elapsed_ms, baseline_ms);
+        let event = if step.window == window {
+            Vec::new()
+        } else {
+            serde_json::to_vec(&serde_json::json!({
+                "step_id": split.step_id,
+                "step_name": split.name.as_deref().unwrap_or("Unnamed"),
+                "previous_window": window,
+                "window": step.window,
+                "min": bounds.0,
+                "max": bounds.1,
+                "reason": step.reason,
+                "elapsed_ms": elapsed_ms,
+                "baseline_ms": step.baseline_ms,
+                "timestamp_ms": timestamp_ms(),
+            }))
+            .map_err(|err| format!("failed to serialize split concurrency event: {err}"))?
+        };
+        Ok(DirectJsonSplitWindow {
+            window: step.window,
+            baseline_ms: step.baseline_ms,
+            event,
+        })
+    }
+
     /// Build the final generated-code-compatible Split step result.
     pub fn split_result(
         &self,
@@ -1133,6 +1218,27 @@ impl DirectJsonManifest {
         })
     }
 
+    /// Checkpoint key for a durable SetVariable's variable state:
+    /// `scenario_vars::{step_id}`, with the `_loop_indices` suffix and the
+    /// child `_cache_key_prefix` folded in like [`Self::delay_sleep_key`].
+    /// Resume replays the graph in order, so each SetVariable restores its
+    /// own snapshot and the last one reached is the live state.
+    pub fn set_variable_key(&self, step_id: &str, source: &[u8]) -> Result<String, String> {
+        let source: Value = serde_json::from_slice(source)
+            .map_err(|err| format!("failed to parse set-variable-key source: {err}"))?;
+        self.steps
+            .get(step_id)
+            .ok_or_else(|| format!("unknown direct SetVariable step '{step_id}'"))?;
+        let base = format!(
+            "scenario_vars::{step_id}{}",
+            wait_loop_indices_suffix(&source)
+        );
+        Ok(match Self::source_cache_key_prefix(&source) {
+            Some(prefix) => format!("{prefix}::{base}"),
+            None => base,
+        })
+    }
+
     /// Build the generated-code-compatible custom event payload for a step breakpoint.
     pub fn breakpoint_event(&self, step_id: &str, source: &[u8]) -> Result<Vec<u8>, String> {
         let source: Value = serde_json::from_slice(source)
@@ -2875,6 +2981,121 @@ impl DirectJsonManifest {
             .map_err(|err| format!("failed to serialize log steps context: {err}"))
     }
 
+    /// Apply a SetVariable step's resolved `values` to the scenario variables.
+    ///
+    /// Assignments land under `variables._scenario_vars`, which [`build_source`]
+    /// overlays after the runtime envelope merge, so a mutation wins over both
+    /// the declared default and a start-input override. Values are materialized
+    /// because the result is checkpointed and must outlive the value store.
+    pub fn set_variables(
+        &self,
+        set_id: u32,
+        values: &[u8],
+        variables: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        let values: Value = serde_json::from_slice(values)
+            .map_err(|err| format!("failed to parse set-variables values: {err}"))?;
+        let mut variables: Value = serde_json::from_slice(variables)
+            .map_err(|err| format!("failed to parse set-variables variables: {err}"))?;
+        let set_variable = self
+            .set_variables
+            .get(&set_id)
+            .ok_or_else(|| format!("unknown direct SetVariable id {set_id}"))?;
+        let Value::Object(values) = values else {
+            return Err(format!(
+                "SetVariable step '{}': values must resolve to an object",
+                set_variable.step_id
+            ));
+        };
+        let Value::Object(variables_map) = &mut variables else {
+            return Err(format!(
+                "SetVariable step '{}': variables must be an object",
+                set_variable.step_id
+            ));
+        };
+        let scenario = variables_map
+            .entry(SCENARIO_VARS_KEY.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !scenario.is_object() {
+            *scenario = Value::Object(Map::new());
+        }
+        let Value::Object(scenario) = scenario else {
+            unreachable!("scenario variables were just normalized to an object");
+        };
+        for (name, value) in values {
+            let declared = set_variable.types.get(&name).ok_or_else(|| {
+                format!(
+                    "SetVariable step '{}': variable '{}' is not declared",
+                    set_variable.step_id, name
+                )
+            })?;
+            let value = materialize(value);
+            let expected = if declared == "file" {
+                "object"
+            } else {
+                declared.as_str()
+            };
+            if !split_schema_type_matches(expected, &value) {
+                return Err(format!(
+                    "SetVariable step '{}': variable '{}' expects type '{}' but got '{}'",
+                    set_variable.step_id,
+                    name,
+                    declared,
+                    json_type_name(&value)
+                ));
+            }
+            scenario.insert(name, value);
+        }
+        serde_json::to_vec(&variables)
+            .map_err(|err| format!("failed to serialize set-variables variables: {err}"))
+    }
+
+    /// Store a SetVariable output (the assigned names and their current
+    /// values) in the generated-code-compatible steps context.
+    pub fn set_variable(
+        &self,
+        set_id: u32,
+        source: &[u8],
+        variables: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        let source: Value = serde_json::from_slice(source)
+            .map_err(|err| format!("failed to parse set-variable source: {err}"))?;
+        let variables: Value = serde_json::from_slice(variables)
+            .map_err(|err| format!("failed to parse set-variable variables: {err}"))?;
+        let set_variable = self
+            .set_variables
+            .get(&set_id)
+            .ok_or_else(|| format!("unknown direct SetVariable id {set_id}"))?;
+        let steps = insert_step_output(
+            &source,
+            &set_variable.step_id,
+            set_variable.name.as_deref(),
+            "SetVariable",
+            Value::Object(self.set_variable_output(set_variable, &variables)),
+            None,
+        );
+        serde_json::to_vec(&Value::Object(steps))
+            .map_err(|err| format!("failed to serialize set-variable steps context: {err}"))
+    }
+
+    fn set_variable_output(
+        &self,
+        set_variable: &DirectJsonSetVariable,
+        variables: &Value,
+    ) -> Map<String, Value> {
+        let scenario = variables.get(SCENARIO_VARS_KEY).and_then(Value::as_object);
+        self.mappings
+            .get(&set_variable.mapping_id)
+            .and_then(|mapping| mapping.value.as_object())
+            .into_iter()
+            .flat_map(|mapping| mapping.keys())
+            .filter_map(|name| {
+                let value = scenario.and_then(|scenario| scenario.get(name))?;
+                Some((name.clone(), value.clone()))
+            })
+            .collect()
+    }
+
     /// Build the payload for a manifest Error step's runtime custom event.
     pub fn error_event(&self, error_id: u32, source: &[u8]) -> Result<Vec<u8>, String> {
         let source: Value = serde_json::from_slice(source)
@@ -3012,6 +3233,59 @@ impl DirectJsonManifest {
             .map_err(|err| format!("failed to serialize step-debug-end payload: {err}"))
     }
 
+    /// Report a step start to the registered [`crate::hooks`].
+    ///
+    /// Hooks are best-effort: an unparseable source or unknown step is skipped
+    /// rather than failing the workflow.
+    pub fn step_hook_before(&self, step_id: &str, source: &[u8]) {
+        let Some(step) = self.steps.get(step_id) else {
+            return;
+        };
+        self.hook_start_ms
+            .borrow_mut()
+            .insert(step_id.to_string(), timestamp_ms());
+        let inputs = serde_json::from_slice::<Value>(source)
+            .ok()
+            .and_then(|source| self.debug_start_data(step, &source).ok())
+            .map(|(inputs, _)| inputs)
+            .unwrap_or(Value::Null);
+        crate::hooks::dispatch_before_step(step_id, &step.step_type, &inputs);
+    }
+
+    /// Report a successful step completion to the registered [`crate::hooks`].
+    pub fn step_hook_after(&self, step_id: &str, source: &[u8]) {
+        let Some(step) = self.steps.get(step_id) else {
+            return;
+        };
+        let duration = self.take_hook_duration(step_id);
+        let result = serde_json::from_slice::<Value>(source)
+            .ok()
+            .and_then(|source| self.debug_end_output(step, &source).ok())
+            .unwrap_or(Value::Null);
+        crate::hooks::dispatch_after_step(step_id, &result, duration);
+    }
+
+    /// Report a step failure to the registered [`crate::hooks`].
+    pub fn step_hook_error(&self, step_id: &str, error: &[u8]) {
+        self.hook_start_ms.borrow_mut().remove(step_id);
+        crate::hooks::dispatch_on_error(step_id, &hook_error_value(error));
+    }
+
+    /// Report an Agent step failure to the registered [`crate::hooks`].
+    pub fn agent_hook_error(&self, agent_id: u32, error: &[u8]) {
+        if let Some(agent) = self.agents.get(&agent_id) {
+            self.step_hook_error(&agent.step_id, error);
+        }
+    }
+
+    fn take_hook_duration(&self, step_id: &str) -> Duration {
+        self.hook_start_ms
+            .borrow_mut()
+            .remove(step_id)
+            .map(|start| Duration::from_millis(timestamp_ms().saturating_sub(start).max(0) as u64))
+            .unwrap_or_default()
+    }
+
     fn debug_start_data(
         &self,
         step: &DirectJsonStep,
@@ -3121,6 +3395,14 @@ impl DirectJsonManifest {
                 ))
             }
             "Error" => Ok((Value::Null, None)),
+            "SetVariable" => {
+                let mapping = self.set_variable_mapping(step.id.as_str());
+                // Tolerate an unresolvable mapping (see the Agent arm).
+                let inputs = mapping
+                    .and_then(|mapping| apply_input_mapping(&mapping.value, source).ok())
+                    .unwrap_or_else(|| Value::Object(Map::new()));
+                Ok((inputs, mapping.map(|mapping| mapping.value.clone())))
+            }
             "Log" => {
                 let log = self
                     .log_by_step(step.id.as_str())
@@ -3268,6 +3550,10 @@ impl DirectJsonManifest {
                 .pointer(&format!("/steps/{}", escape_json_pointer_token(&step.id)))
                 .cloned()
                 .ok_or_else(|| format!("missing direct WaitForSignal output for '{}'", step.id)),
+            "SetVariable" => source
+                .pointer(&format!("/steps/{}", escape_json_pointer_token(&step.id)))
+                .cloned()
+                .ok_or_else(|| format!("missing direct SetVariable output for '{}'", step.id)),
             "Error" => {
                 let error = self
                     .error_by_step(step.id.as_str())
@@ -3301,6 +3587,12 @@ impl DirectJsonManifest {
             .find(|mapping| mapping.step_id == step_id && mapping.purpose == "finish.inputMapping")
     }
 
+    fn set_variable_mapping(&self, step_id: &str) -> Option<&DirectJsonMapping> {
+        self.mappings
+            .values()
+            .find(|mapping| mapping.step_id == step_id && mapping.purpose == "setVariable.values")
+    }
+
     fn embed_workflow_mapping(&self, step_id: &str) -> Option<&DirectJsonMapping> {
         self.mappings.values().find(|mapping| {
             mapping.step_id == step_id && mapping.purpose == "embedWorkflow.inputMapping"
@@ -3421,6 +3713,10 @@ fn inject_runtime_identity_variables(variables: &mut Value) {
     });
 }
 
+/// Variables key holding SetVariable assignments until [`build_source`]
+/// overlays them. `_`-prefixed, so start input can never supply it.
+const SCENARIO_VARS_KEY: &str = "_scenario_vars";
+
 /// Build the source envelope consumed by direct mapping/condition helpers.
 pub fn build_source(data: &[u8], variables: &[u8], steps: &[u8]) -> Result<Vec<u8>, String> {
     let mut data: Value =
@@ -3460,6 +3756,13 @@ pub fn build_source(data: &[u8], variables: &[u8], steps: &[u8]) -> Result<Vec<u
     if let Some(inner) = inner_data {
         data = inner;
     }
+    // SetVariable assignments sit apart from the defaults so the runtime merge
+    // above cannot undo them; apply them last.
+    if let Value::Object(map) = &mut variables
+        && let Some(Value::Object(assigned)) = map.remove(SCENARIO_VARS_KEY)
+    {
+        map.extend(assigned);
+    }
     inject_runtime_identity_variables(&mut variables);
     let mut steps: Value =
         serde_json::from_slice(steps).map_err(|err| format!("failed to parse steps: {err}"))?;
@@ -3660,6 +3963,116 @@ fn split_cache_key(split: &DirectJsonSplit, source: &Value) -> String {
     }
 }
 
+/// Collect the durable key families of every step nested (at any depth)
+/// under the Split `step_id` of `graph`. Embedded child graphs are not
+/// walked: their keys all live under the embed step's child namespace.
+fn split_compaction_stems(graph: &GraphWire, step_id: &str) -> Vec<DirectJsonCompactionStem> {
+    fn collect(graph: &GraphWire, stems: &mut BTreeSet<DirectJsonCompactionStem>) {
+        for agent in &graph.agents {
+            stems.insert(DirectJsonCompactionStem::Agent {
+                agent_id: agent.agent_id.clone(),
+                capability_id: agent.capability_id.clone(),
+                step_id: agent.step_id.clone(),
+            });
+        }
+        for split in &graph.splits {
+            stems.insert(DirectJsonCompactionStem::Split {
+                step_id: split.step_id.clone(),
+            });
+        }
+        for step in &graph.steps {
+            if step.step_type == "EmbedWorkflow" {
+                stems.insert(DirectJsonCompactionStem::EmbedWorkflow {
+                    step_id: step.id.clone(),
+                });
+            }
+            for nested in &step.nested_graphs {
+                collect(&nested.graph, stems);
+            }
+        }
+    }
+
+    let mut stems = BTreeSet::new();
+    for step in graph.steps.iter().filter(|step| step.id == step_id) {
+        for nested in &step.nested_graphs {
+            collect(&nested.graph, &mut stems);
+        }
+    }
+    stems.into_iter().collect()
+}
+
+/// Checkpoint-id prefixes matching every key a Split's body writes for the
+/// Split's iterations under `source` (the Split's parent scope).
+///
+/// Each iteration appends its index to the parent `_loop_indices`, so the
+/// body keys of this Split all share `{stem}::[{parent indices,}` — a prefix
+/// no sibling Split iteration or outer scope can produce. Breakpoint, delay,
+/// wait, and AI-turn keys use other shapes and are left in place.
+fn split_compaction_prefixes(split: &DirectJsonSplit, source: &Value) -> Vec<String> {
+    let variables = source.get("variables").and_then(Value::as_object);
+    let prefix = variables
+        .and_then(|vars| vars.get("_cache_key_prefix"))
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let workflow_id = variables
+        .and_then(|vars| vars.get("_workflow_id"))
+        .and_then(Value::as_str)
+        .unwrap_or("root");
+    let namespace = if prefix.is_empty() {
+        workflow_id
+    } else {
+        prefix
+    };
+    let parent_indices = variables
+        .and_then(|vars| vars.get("_loop_indices"))
+        .and_then(Value::as_array)
+        .map(|indices| {
+            indices
+                .iter()
+                .map(|index| format!("{index},"))
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+    let child_namespace = |step_id: &str| {
+        if prefix.is_empty() {
+            format!("{workflow_id}::{step_id}[{parent_indices}")
+        } else {
+            format!("{prefix}__{step_id}[{parent_indices}")
+        }
+    };
+
+    let mut prefixes = BTreeSet::new();
+    for stem in &split.compaction_stems {
+        match stem {
+            DirectJsonCompactionStem::Agent {
+                agent_id,
+                capability_id,
+                step_id,
+            } => {
+                prefixes.insert(format!(
+                    "{namespace}::agent::{agent_id}::{capability_id}::{step_id}::[{parent_indices}"
+                ));
+                prefixes.insert(child_namespace(step_id));
+            }
+            DirectJsonCompactionStem::Split { step_id } => {
+                prefixes.insert(format!("{namespace}::split::{step_id}::[{parent_indices}"));
+            }
+            DirectJsonCompactionStem::EmbedWorkflow { step_id } => {
+                let embed_namespace = if prefix.is_empty() {
+                    String::new()
+                } else {
+                    format!("{prefix}::")
+                };
+                prefixes.insert(format!(
+                    "{embed_namespace}embed_workflow::{step_id}::[{parent_indices}"
+                ));
+                prefixes.insert(child_namespace(step_id));
+            }
+        }
+    }
+    prefixes.into_iter().collect()
+}
+
 #[derive(Default)]
 struct DirectJsonManifestCollections {
     steps: BTreeMap<String, DirectJsonStep>,
@@ -3673,6 +4086,7 @@ struct DirectJsonManifestCollections {
     group_bys: BTreeMap<u32, DirectJsonGroupBy>,
     delays: BTreeMap<u32, DirectJsonDelay>,
     logs: BTreeMap<u32, DirectJsonLog>,
+    set_variables: BTreeMap<u32, DirectJsonSetVariable>,
     errors: BTreeMap<u32, DirectJsonError>,
     agents: BTreeMap<u32, DirectJsonAgent>,
 }
@@ -3735,6 +4149,7 @@ fn collect_graph_manifest(
                     value: split.value.clone(),
                     input_schema: split.input_schema.clone(),
                     output_schema: split.output_schema.clone(),
+                    compaction_stems: split_compaction_stems(graph, &split.step_id),
                 },
             )
             .is_some()
@@ -3839,6 +4254,26 @@ fn collect_graph_manifest(
             return Err(format!("duplicate direct Log id {}", log.id));
         }
     }
+    for set_variable in &graph.set_variables {
+        if collections
+            .set_variables
+            .insert(
+                set_variable.id,
+                DirectJsonSetVariable {
+                    step_id: set_variable.step_id.clone(),
+                    name: set_variable.name.clone(),
+                    mapping_id: set_variable.mapping_id,
+                    types: set_variable.types.clone(),
+                },
+            )
+            .is_some()
+        {
+            return Err(format!(
+                "duplicate direct SetVariable id {}",
+                set_variable.id
+            ));
+        }
+    }
     for error in &graph.errors {
         if collections
             .errors
@@ -4101,6 +4536,70 @@ fn split_iteration_variables(
     Ok(variables)
 }
 
+/// A chunk slower than this multiple of the fastest healthy chunk counts as
+/// saturated: the window stops growing, but only a rate-limit error shrinks it.
+const ADAPTIVE_LATENCY_DEGRADED_FACTOR: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AdaptiveWindowStep {
+    window: u32,
+    baseline_ms: u32,
+    reason: &'static str,
+}
+
+/// `(min, max)` of a Split's `adaptiveParallelism`, normalized so `min >= 1`
+/// and `max >= min` (validation rejects anything else before compile).
+fn split_adaptive_bounds(split: &DirectJsonSplit) -> Option<(u32, u32)> {
+    let adaptive = split.value.get("adaptiveParallelism")?;
+    let bound = |key: &str| {
+        adaptive
+            .get(key)
+            .and_then(Value::as_u64)
+            .map(|value| u32::try_from(value).unwrap_or(u32::MAX))
+    };
+    let min = bound("min")?.max(1);
+    let max = bound("max")?.max(min);
+    Some((min, max))
+}
+
+/// AIMD step: halve on rate limiting, grow by one while chunk latency stays
+/// within [`ADAPTIVE_LATENCY_DEGRADED_FACTOR`] of the baseline, hold otherwise.
+fn adaptive_window_step(
+    (min, max): (u32, u32),
+    window: u32,
+    rate_limited: bool,
+    elapsed_ms: u32,
+    baseline_ms: u32,
+) -> AdaptiveWindowStep {
+    let window = window.clamp(min, max);
+    if rate_limited {
+        return AdaptiveWindowStep {
+            window: (window / 2).max(min),
+            baseline_ms,
+            reason: "rate_limited",
+        };
+    }
+    let healthy = baseline_ms == 0
+        || elapsed_ms <= baseline_ms.saturating_mul(ADAPTIVE_LATENCY_DEGRADED_FACTOR);
+    let baseline_ms = match baseline_ms {
+        0 => elapsed_ms,
+        baseline => baseline.min(elapsed_ms.max(1)),
+    };
+    if healthy {
+        AdaptiveWindowStep {
+            window: window.saturating_add(1).min(max),
+            baseline_ms,
+            reason: "healthy",
+        }
+    } else {
+        AdaptiveWindowStep {
+            window,
+            baseline_ms,
+            reason: "latency_degraded",
+        }
+    }
+}
+
 fn split_dont_stop_on_failed(split: &DirectJsonSplit) -> bool {
     split_bool_config(&split.value, "dontStopOnFailed")
 }
@@ -4735,8 +5234,7 @@ fn apply_error(config: &Value, source: &Value) -> Result<DirectErrorResult, Stri
     let message = config
         .get("message")
         .and_then(Value::as_str)
-        .ok_or_else(|| "Error step missing message".to_string())?
-        .to_string();
+        .ok_or_else(|| "Error step missing message".to_string())?;
     let severity = config
         .get("severity")
         .and_then(Value::as_str)
@@ -4749,6 +5247,13 @@ fn apply_error(config: &Value, source: &Value) -> Result<DirectErrorResult, Stri
         .map(|context| apply_input_mapping(&Value::Object(context.clone()), source))
         .transpose()?
         .unwrap_or_else(|| Value::Object(Map::new()));
+    // `{{ key }}` placeholders name resolved `context` entries. Messages
+    // without one are used verbatim, so literal braces elsewhere stay valid.
+    let message = if message.contains("{{") {
+        render_template(message, &context).map_err(|err| format!("Error step message: {err}"))?
+    } else {
+        message.to_string()
+    };
 
     Ok(DirectErrorResult {
         category,
@@ -4892,6 +5397,13 @@ fn agent_error_code_is_rate_limited(code: &str) -> bool {
     code.contains("RATE_LIMITED")
 }
 
+/// Structured error for hooks: the JSON value when `error` carries JSON,
+/// otherwise its text.
+fn hook_error_value(error: &[u8]) -> Value {
+    serde_json::from_slice(error)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(error).into_owned()))
+}
+
 fn timestamp_ms() -> i64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -5670,6 +6182,31 @@ fn unwrap_finish_outputs(output: Value) -> Value {
     }
 }
 
+/// Resolve an authored `inputMapping` object against a plain source envelope.
+///
+/// Manifest-free entry point for host-side evaluation (the environment's
+/// scenario simulator), so a simulated step resolves its inputs exactly as the
+/// emitted workflow would. Agent-input envelope unwrapping is not applied.
+pub fn evaluate_input_mapping(mapping: &Value, source: &Value) -> Result<Value, String> {
+    apply_input_mapping(mapping, source)
+}
+
+/// Resolve a single authored `MappingValue` against a plain source envelope.
+pub fn evaluate_mapping_value(value: &Value, source: &Value) -> Result<Value, String> {
+    apply_mapping_value(value, source)
+}
+
+/// Evaluate an authored `ConditionExpression` against a plain source envelope.
+pub fn evaluate_condition(condition: &Value, source: &Value) -> Result<bool, String> {
+    compile_condition(condition).eval(source)
+}
+
+/// Evaluate an authored `SwitchConfig`, returning the processed output and the
+/// selected route (`"default"` when no case routes).
+pub fn evaluate_switch(config: &Value, source: &Value) -> Result<(Value, String), String> {
+    apply_switch(config, source).map(|result| (result.output, result.route))
+}
+
 fn apply_input_mapping(mapping: &Value, source: &Value) -> Result<Value, String> {
     let Value::Object(entries) = mapping else {
         return Err("input mapping must be a JSON object".to_string());
@@ -6855,6 +7392,8 @@ struct GraphWire {
     #[serde(default)]
     logs: Vec<LogWire>,
     #[serde(default)]
+    set_variables: Vec<SetVariableWire>,
+    #[serde(default)]
     errors: Vec<ErrorWire>,
     #[serde(default)]
     agents: Vec<AgentWire>,
@@ -6976,6 +7515,18 @@ struct LogWire {
     value: Value,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SetVariableWire {
+    id: u32,
+    step_id: String,
+    #[serde(default)]
+    name: Option<String>,
+    mapping_id: u32,
+    #[serde(default)]
+    types: BTreeMap<String, String>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ErrorWire {
@@ -7027,6 +7578,25 @@ struct DirectJsonSplit {
     value: Value,
     input_schema: Value,
     output_schema: Value,
+    /// Durable key families written per iteration by the body subgraph.
+    compaction_stems: Vec<DirectJsonCompactionStem>,
+}
+
+/// A durable checkpoint key family written by a step inside a Split body,
+/// once per iteration. See [`split_compaction_prefixes`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum DirectJsonCompactionStem {
+    /// `agent::{agent}::{capability}::{step}` keys plus the step's child
+    /// namespace (workflow-agent targets).
+    Agent {
+        agent_id: String,
+        capability_id: String,
+        step_id: String,
+    },
+    /// A nested Split's aggregate keys.
+    Split { step_id: String },
+    /// `embed_workflow::{step}` keys plus the embedded child's namespace.
+    EmbedWorkflow { step_id: String },
 }
 
 #[derive(Debug, Clone)]
@@ -7088,6 +7658,15 @@ struct DirectJsonLog {
     value: Value,
 }
 
+#[derive(Debug, Clone)]
+struct DirectJsonSetVariable {
+    step_id: String,
+    name: Option<String>,
+    mapping_id: u32,
+    /// Declared type per assignable variable name.
+    types: BTreeMap<String, String>,
+}
+
 #[derive(Debug, Clone)]
 struct DirectJsonError {
     step_id: String,
@@ -9240,6 +9819,219 @@ mod tests {
         );
     }
 
+    #[test]
+    fn split_compaction_prefixes_cover_body_keys_of_this_split_only() {
+        let manifest = DirectJsonManifest::parse(
+            &serde_json::to_vec(&json!({
+                "graph": {
+                    "splits": [{ "id": 0, "stepId": "outer", "value": {} }],
+                    "steps": [{
+                        "id": "outer",
+                        "stepType": "Split",
+                        "nestedGraphs": [{
+                            "graph": {
+                                "splits": [{ "id": 1, "stepId": "inner", "value": {} }],
+                                "steps": [
+                                    { "id": "call", "stepType": "EmbedWorkflow" },
+                                    {
+                                        "id": "inner",
+                                        "stepType": "Split",
+                                        "nestedGraphs": [{
+                                            "graph": {
+                                                "steps": [{ "id": "fetch", "stepType": "Agent" }],
+                                                "agents": [{
+                                                    "id": 0,
+                                                    "stepId": "fetch",
+                                                    "agentId": "http",
+                                                    "capabilityId": "get",
+                                                    "inputMappingId": 0
+                                                }]
+                                            }
+                                        }]
+                                    }
+                                ]
+                            }
+                        }]
+                    }]
+                }
+            }))
+            .expect("manifest json"),
+        );
+        let manifest = manifest.expect("manifest");
+        let prefixes = |split_id: u32, variables: &[u8]| -> Vec<String> {
+            let source = build_source(b"{}", variables, b"{}").expect("source");
+            serde_json::from_slice(
+                &manifest
+                    .split_compaction_prefixes(split_id, &source)
+                    .expect("prefixes"),
+            )
+            .expect("prefixes json")
+        };
+
+        assert_eq!(
+            prefixes(0, br#"{"_workflow_id":"wf"}"#),
+            vec![
+                "embed_workflow::call::[",
+                "wf::agent::http::get::fetch::[",
+                "wf::call[",
+                "wf::fetch[",
+                "wf::split::inner::[",
+            ]
+        );
+        // Inside a parent iteration the prefixes stay scoped to that
+        // iteration, and a child namespace replaces the workflow id.
+        assert_eq!(
+            prefixes(1, br#"{"_cache_key_prefix":"p","_loop_indices":[3]}"#),
+            vec!["p::agent::http::get::fetch::[3,", "p__fetch[3,"]
+        );
+    }
+
+    #[test]
+    fn split_next_window_grows_and_backs_off_within_bounds() {
+        let manifest = DirectJsonManifest::parse(&split_manifest(json!({
+            "value": { "valueType": "reference", "value": "data.items" },
+            "adaptiveParallelism": { "min": 2, "max": 4 }
+        })))
+        .expect("manifest");
+
+        let grown = manifest
+            .split_next_window(0, 2, false, 100, 0)
+            .expect("grow");
+        assert_eq!((grown.window, grown.baseline_ms), (3, 100));
+        let event: Value = serde_json::from_slice(&grown.event).expect("event json");
+        assert_eq!(event["step_id"], "split");
+        assert_eq!(event["previous_window"], 2);
+        assert_eq!(event["window"], 3);
+        assert_eq!(event["reason"], "healthy");
+
+        let capped = manifest
+            .split_next_window(0, 4, false, 100, 100)
+            .expect("capped");
+        assert_eq!(capped.window, 4);
+        assert!(capped.event.is_empty(), "unchanged window emits no event");
+
+        let slow = manifest
+            .split_next_window(0, 3, false, 500, 100)
+            .expect("slow");
+        assert_eq!((slow.window, slow.baseline_ms), (3, 100));
+        assert!(slow.event.is_empty());
+
+        let halved = manifest
+            .split_next_window(0, 4, true, 20, 100)
+            .expect("halve");
+        assert_eq!((halved.window, halved.baseline_ms), (2, 100));
+        let event: Value = serde_json::from_slice(&halved.event).expect("event json");
+        assert_eq!(event["reason"], "rate_limited");
+
+        let floored = manifest
+            .split_next_window(0, 2, true, 20, 100)
+            .expect("floor");
+        assert_eq!(floored.window, 2, "never below min");
+    }
+
+    #[test]
+    fn split_next_window_is_a_no_op_without_adaptive_config() {
+        let manifest = DirectJsonManifest::parse(&split_manifest(json!({
+            "value": { "valueType": "reference", "value": "data.items" },
+            "parallelism": 4
+        })))
+        .expect("manifest");
+
+        let next = manifest
+            .split_next_window(0, 4, true, 10, 0)
+            .expect("fixed window");
+        assert_eq!(next.window, 4);
+        assert!(next.event.is_empty());
+    }
+
+    /// Token-bucket API capped at `rps` requests per second. Admitted requests
+    /// take `latency_ms`; rejected ones return a 429 almost immediately.
+    struct MockRateLimitedServer {
+        rps: u32,
+        latency_ms: u32,
+        tokens: f64,
+        now_ms: u64,
+    }
+
+    impl MockRateLimitedServer {
+        fn new(rps: u32, latency_ms: u32) -> Self {
+            Self {
+                rps,
+                latency_ms,
+                tokens: f64::from(rps),
+                now_ms: 0,
+            }
+        }
+
+        /// Issue `window` concurrent requests; returns (admitted, rejected,
+        /// chunk wall time in ms) and advances the clock.
+        fn chunk(&mut self, window: u32) -> (u32, u32, u32) {
+            let mut admitted = 0;
+            for _ in 0..window {
+                if self.tokens >= 1.0 {
+                    self.tokens -= 1.0;
+                    admitted += 1;
+                }
+            }
+            let elapsed = if admitted > 0 { self.latency_ms } else { 5 };
+            self.now_ms += u64::from(elapsed);
+            self.tokens = (self.tokens + f64::from(self.rps) * f64::from(elapsed) / 1000.0)
+                .min(f64::from(self.rps));
+            (admitted, window - admitted, elapsed)
+        }
+    }
+
+    #[test]
+    fn split_adaptive_window_converges_against_10_rps_server() {
+        let manifest = DirectJsonManifest::parse(&split_manifest(json!({
+            "value": { "valueType": "reference", "value": "data.items" },
+            "adaptiveParallelism": { "min": 1, "max": 16 }
+        })))
+        .expect("manifest");
+        // 10 rps with 200 ms per request sustains exactly two in flight.
+        let mut server = MockRateLimitedServer::new(10, 200);
+        let (mut window, mut baseline) = (1u32, 0u32);
+        let mut remaining = 600u32;
+        let mut history = Vec::new();
+
+        while remaining > 0 {
+            let width = window.min(remaining);
+            let (admitted, rejected, elapsed) = server.chunk(width);
+            remaining -= admitted;
+            history.push((width, rejected));
+            let next = manifest
+                .split_next_window(0, window, rejected > 0, elapsed, baseline)
+                .expect("next window");
+            window = next.window;
+            baseline = next.baseline_ms;
+        }
+
+        let steady = &history[history.len() / 2..];
+        // Converged: the window oscillates tightly around the sustainable 2.
+        assert!(
+            steady.iter().all(|(width, _)| *width <= 4),
+            "window did not converge: {steady:?}"
+        );
+        // No sustained 429s: never two throttled chunks in a row. Additive
+        // probing still brushes the cap now and then, so a bounded share of
+        // steady-state requests is rejected.
+        assert!(
+            steady
+                .windows(2)
+                .all(|pair| pair[0].1 == 0 || pair[1].1 == 0),
+            "consecutive throttled chunks: {steady:?}"
+        );
+        let sent: u32 = steady.iter().map(|(width, _)| width).sum();
+        let rejected: u32 = steady.iter().map(|(_, rejected)| rejected).sum();
+        assert!(
+            rejected * 4 <= sent,
+            "too many 429s: {rejected}/{sent} in {steady:?}"
+        );
+        // Throughput tracks the cap instead of crawling at the minimum.
+        let achieved_rps = 600.0 * 1000.0 / server.now_ms as f64;
+        assert!(achieved_rps >= 7.0, "achieved {achieved_rps:.1} rps");
+    }
+
     #[test]
     fn split_result_can_be_inserted_into_steps_context() {
         let manifest = DirectJsonManifest::parse(&split_manifest(json!({
@@ -12424,6 +13216,41 @@ mod tests {
         assert!(payload["duration_ms"].as_i64().is_some());
     }
 
+    #[test]
+    fn step_hooks_receive_agent_step_lifecycle() {
+        use std::sync::{Arc, Mutex};
+
+        struct Record(Arc<Mutex<Vec<String>>>);
+        impl crate::hooks::StepHooks for Record {
+            fn before_step(&self, step_id: &str, step_type: &str, _inputs: &Value) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("before {step_id} {step_type}"));
+            }
+            fn on_error(&self, step_id: &str, error: &Value) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("error {step_id} {error}"));
+            }
+        }
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        crate::hooks::register_step_hooks(Box::new(Record(calls.clone())));
+        let manifest = DirectJsonManifest::parse(&agent_manifest(json!({}))).expect("manifest");
+        let source = build_source(b"{}", b"{}", b"{}").expect("source");
+
+        manifest.step_hook_before("agent", &source);
+        manifest.agent_hook_error(0, br#"{"code":"BOOM"}"#);
+        manifest.step_hook_before("not-a-step", &source);
+
+        let calls = calls.lock().unwrap();
+        assert!(calls.contains(&"before agent Agent".to_string()));
+        assert!(calls.contains(&r#"error agent {"code":"BOOM"}"#.to_string()));
+        assert!(!calls.iter().any(|call| call.contains("not-a-step")));
+    }
+
     #[test]
     fn debug_events_carry_iteration_scope_from_source_variables() {
         // Regression: step-debug start/end (and the failed-agent debug-end) must
@@ -12572,6 +13399,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn error_message_interpolates_context() {
+        let manifest = DirectJsonManifest::parse(&error_manifest(json!({
+            "id": "fail",
+            "stepType": "Error",
+            "code": "ORDER_ALREADY_SHIPPED",
+            "message": "Order {{ order_id }} already shipped, aborting",
+            "context": {
+                "order_id": { "valueType": "reference", "value": "data.input" }
+            }
+        })))
+        .expect("manifest");
+        let source = build_source(br#"{"input":"A-17"}"#, b"{}", b"{}").expect("source");
+
+        let failure = manifest.error(0, &source).expect("failure payload");
+        let failure: Value = serde_json::from_slice(&failure).expect("failure json");
+        assert_eq!(
+            failure["message"],
+            json!("Order A-17 already shipped, aborting")
+        );
+        assert_eq!(failure["context"], json!({ "order_id": "A-17" }));
+    }
+
     #[test]
     fn error_defaults_to_permanent_error_and_empty_context() {
         let manifest = DirectJsonManifest::parse(&error_manifest(json!({
@@ -12860,4 +13710,93 @@ mod invoke_error_and_delay_key_tests {
         // Unknown steps fail loudly.
         assert!(manifest.delay_sleep_key("nope", b"{}").is_err());
     }
+
+    fn set_variable_manifest() -> DirectJsonManifest {
+        let manifest_bytes = serde_json::to_vec(&json!({
+            "graph": {
+                "steps": [{
+                    "id": "set",
+                    "stepType": "SetVariable",
+                    "name": "Set Total"
+                }],
+                "mappings": [{
+                    "id": 0,
+                    "stepId": "set",
+                    "purpose": "setVariable.values",
+                    "value": {
+                        "total": { "valueType": "reference", "value": "data.amount" }
+                    }
+                }],
+                "setVariables": [{
+                    "id": 0,
+                    "stepId": "set",
+                    "name": "Set Total",
+                    "mappingId": 0,
+                    "types": { "total": "number", "label": "string" }
+                }]
+            }
+        }))
+        .expect("manifest json");
+        DirectJsonManifest::parse(&manifest_bytes).expect("manifest parses")
+    }
+
+    #[test]
+    fn set_variables_overlay_wins_over_defaults_and_start_input() {
+        let manifest = set_variable_manifest();
+        let variables = manifest
+            .set_variables(0, br#"{"total":7}"#, br#"{"total":0,"label":"x"}"#)
+            .expect("assignment");
+
+        let source = build_source(
+            br#"{"data":{"amount":7},"variables":{"total":99}}"#,
+            &variables,
+            b"{}",
+        )
+        .expect("source");
+        let source: Value = serde_json::from_slice(&source).expect("source json");
+        assert_eq!(source["variables"]["total"], json!(7));
+        assert_eq!(source["variables"]["label"], json!("x"));
+        assert!(source["variables"].get(SCENARIO_VARS_KEY).is_none());
+
+        let source = serde_json::to_vec(&source).expect("source bytes");
+        let steps = manifest
+            .set_variable(0, &source, &variables)
+            .expect("steps context");
+        let steps: Value = serde_json::from_slice(&steps).expect("steps json");
+        assert_eq!(steps["set"]["stepType"], json!("SetVariable"));
+        assert_eq!(steps["set"]["outputs"], json!({ "total": 7 }));
+    }
+
+    #[test]
+    fn set_variables_rejects_type_mismatch_and_undeclared_names() {
+        let manifest = set_variable_manifest();
+        let error = manifest
+            .set_variables(0, br#"{"total":"seven"}"#, b"{}")
+            .expect_err("string into number variable");
+        assert!(error.contains("'total' expects type 'number'"), "{error}");
+
+        let error = manifest
+            .set_variables(0, br#"{"other":1}"#, b"{}")
+            .expect_err("undeclared variable");
+        assert!(error.contains("'other' is not declared"), "{error}");
+    }
+
+    #[test]
+    fn set_variable_key_folds_loop_indices_and_cache_key_prefix() {
+        let manifest = set_variable_manifest();
+        let top = manifest
+            .set_variable_key("set", br#"{"data":{},"variables":{}}"#)
+            .expect("top-level key");
+        assert_eq!(top, "scenario_vars::set");
+
+        let child = manifest
+            .set_variable_key(
+                "set",
+                br#"{"data":{},"variables":{"_cache_key_prefix":"parent::embed"}}"#,
+            )
+            .expect("child key");
+        assert_eq!(child, "parent::embed::scenario_vars::set");
+
+        assert!(manifest.set_variable_key("nope", b"{}").is_err());
+    }
 }
@@ -0,0 +1,289 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Step execution hooks.
+//!
+//! Generated workflow code reports every step's start, completion and failure
+//! to the stdlib at the same points it emits debug events, but independently of
+//! debug mode. When at least one [`StepHooks`] implementation is registered the
+//! stdlib forwards those reports to it, so cross-cutting concerns (metrics,
+//! tracing, auditing) can be added without touching codegen. With nothing
+//! registered the generated code only pays one `step-hooks-enabled` call per
+//! step, which reads an atomic flag.
+//!
+//! Hooks are process-global and registered from a custom stdlib build, before
+//! the first step runs:
+//!
+//! ```no_run
+//! use runtara_workflow_stdlib::hooks::{register_step_hooks, statsd::StatsdStepHooks};
+//!
+//! let statsd = StatsdStepHooks::new("127.0.0.1:8125", "runtara.workflow")
+//!     .expect("statsd socket");
+//! register_step_hooks(Box::new(statsd));
+//! ```
+//!
+//! For the component build (`direct-component`), a product stdlib that wraps
+//! this crate does the registration in its `init-manifest` export before
+//! delegating; for native hosts, at startup.
+//!
+//! A panicking hook is caught and logged; it never fails the step. Note that
+//! `wasm32` builds usually abort on panic, so hooks running inside a workflow
+//! component should not rely on that and must not panic.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+
+use serde_json::Value;
+
+/// Callbacks invoked around every step a workflow executes.
+///
+/// All methods default to no-ops, so implementations only override what they
+/// need. They run synchronously on the workflow's thread: keep them cheap.
+pub trait StepHooks: Send + Sync {
+    /// Called before the step runs, with its resolved inputs (`Value::Null`
+    /// when they could not be resolved).
+    fn before_step(&self, _step_id: &str, _step_type: &str, _inputs: &Value) {}
+
+    /// Called after the step completed successfully.
+    fn after_step(&self, _step_id: &str, _result: &Value, _duration: Duration) {}
+
+    /// Called when the step failed. `error` is the structured error when the
+    /// failure carried JSON, otherwise its message as a string.
+    fn on_error(&self, _step_id: &str, _error: &Value) {}
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+fn registry() -> &'static RwLock<Vec<Box<dyn StepHooks>>> {
+    static REGISTRY: OnceLock<RwLock<Vec<Box<dyn StepHooks>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Register a hook implementation. Hooks run in registration order.
+pub fn register_step_hooks(hooks: Box<dyn StepHooks>) {
+    registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push(hooks);
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Whether any hooks are registered. Generated code checks this before
+/// building hook payloads.
+pub fn hooks_registered() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// Dispatch [`StepHooks::before_step`] to every registered hook.
+pub fn dispatch_before_step(step_id: &str, step_type: &str, inputs: &Value) {
+    dispatch(step_id, "before_step", |hooks| {
+        hooks.before_step(step_id, step_type, inputs)
+    });
+}
+
+/// Dispatch [`StepHooks::after_step`] to every registered hook.
+pub fn dispatch_after_step(step_id: &str, result: &Value, duration: Duration) {
+    dispatch(step_id, "after_step", |hooks| {
+        hooks.after_step(step_id, result, duration)
+    });
+}
+
+/// Dispatch [`StepHooks::on_error`] to every registered hook.
+pub fn dispatch_on_error(step_id: &str, error: &Value) {
+    dispatch(step_id, "on_error", |hooks| hooks.on_error(step_id, error));
+}
+
+fn dispatch(step_id: &str, callback: &str, call: impl Fn(&dyn StepHooks)) {
+    if !hooks_registered() {
+        return;
+    }
+    let hooks = registry()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    for hook in hooks.iter() {
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| call(hook.as_ref()))) {
+            let reason = payload
+                .downcast_ref::<&str>()
+                .map(|s| (*s).to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "non-string panic payload".to_string());
+            eprintln!("step hook {callback} panicked for step '{step_id}': {reason}");
+        }
+    }
+}
+
+/// Reference [`StepHooks`] implementation reporting statsd metrics over UDP.
+pub mod statsd {
+    use std::io;
+    use std::net::{ToSocketAddrs, UdpSocket};
+    use std::time::Duration;
+
+    use serde_json::Value;
+
+    use super::StepHooks;
+
+    /// Sends `<prefix>.<step_id>.started`, `.duration` and `.error` metrics.
+    ///
+    /// Send failures are ignored: metrics are best-effort and must never slow
+    /// down or fail a step.
+    pub struct StatsdStepHooks {
+        socket: UdpSocket,
+        prefix: String,
+    }
+
+    impl StatsdStepHooks {
+        /// Bind an ephemeral local socket and connect it to the statsd `addr`.
+        pub fn new(addr: impl ToSocketAddrs, prefix: impl Into<String>) -> io::Result<Self> {
+            let socket = UdpSocket::bind("0.0.0.0:0")?;
+            socket.connect(addr)?;
+            socket.set_nonblocking(true)?;
+            Ok(Self {
+                socket,
+                prefix: prefix.into(),
+            })
+        }
+
+        fn metric_name(&self, step_id: &str, metric: &str) -> String {
+            let step: String = step_id
+                .chars()
+                .map(|c| match c {
+                    ':' | '|' | '@' | '\n' | ' ' => '_',
+                    c => c,
+                })
+                .collect();
+            format!("{}.{}.{}", self.prefix, step, metric)
+        }
+
+        fn send(&self, line: String) {
+            let _ = self.socket.send(line.as_bytes());
+        }
+    }
+
+    impl StepHooks for StatsdStepHooks {
+        fn before_step(&self, step_id: &str, _step_type: &str, _inputs: &Value) {
+            self.send(format!("{}:1|c", self.metric_name(step_id, "started")));
+        }
+
+        fn after_step(&self, step_id: &str, _result: &Value, duration: Duration) {
+            self.send(format!(
+                "{}:{}|ms",
+                self.metric_name(step_id, "duration"),
+                duration.as_millis()
+            ));
+        }
+
+        fn on_error(&self, step_id: &str, _error: &Value) {
+            self.send(format!("{}:1|c", self.metric_name(step_id, "error")));
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn sends_statsd_lines() {
+            let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+            server
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            let hooks = StatsdStepHooks::new(server.local_addr().unwrap(), "wf").unwrap();
+
+            hooks.before_step("fetch order", "Agent", &Value::Null);
+            hooks.after_step("fetch order", &Value::Null, Duration::from_millis(42));
+            hooks.on_error("fetch order", &Value::Null);
+
+            let mut buf = [0u8; 256];
+            let mut lines = Vec::new();
+            for _ in 0..3 {
+                let n = server.recv(&mut buf).unwrap();
+                lines.push(String::from_utf8_lossy(&buf[..n]).to_string());
+            }
+            assert_eq!(
+                lines,
+                [
+                    "wf.fetch_order.started:1|c",
+                    "wf.fetch_order.duration:42|ms",
+                    "wf.fetch_order.error:1|c",
+                ]
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: Mutex<Vec<String>>,
+    }
+
+    struct Forward(std::sync::Arc<Recorder>);
+
+    impl StepHooks for Forward {
+        fn before_step(&self, step_id: &str, step_type: &str, inputs: &Value) {
+            self.0
+                .calls
+                .lock()
+                .unwrap()
+                .push(format!("before {step_id} {step_type} {inputs}"));
+        }
+
+        fn after_step(&self, step_id: &str, result: &Value, duration: Duration) {
+            self.0
+                .calls
+                .lock()
+                .unwrap()
+                .push(format!("after {step_id} {result} {}", duration.as_millis()));
+        }
+
+        fn on_error(&self, step_id: &str, error: &Value) {
+            self.0
+                .calls
+                .lock()
+                .unwrap()
+                .push(format!("error {step_id} {error}"));
+        }
+    }
+
+    struct Panicking;
+
+    impl StepHooks for Panicking {
+        fn before_step(&self, _step_id: &str, _step_type: &str, _inputs: &Value) {
+            panic!("hook bug");
+        }
+    }
+
+    // The registry is process-global, so everything that registers lives in
+    // one test.
+    #[test]
+    fn registered_hooks_receive_events_and_panics_are_contained() {
+        let recorder = std::sync::Arc::new(Recorder::default());
+        register_step_hooks(Box::new(Panicking));
+        register_step_hooks(Box::new(Forward(recorder.clone())));
+        assert!(hooks_registered());
+
+        dispatch_before_step("s1", "Agent", &serde_json::json!({ "a": 1 }));
+        dispatch_after_step("s1", &serde_json::json!(true), Duration::from_millis(7));
+        dispatch_on_error("s2", &serde_json::json!("boom"));
+
+        let calls = recorder.calls.lock().unwrap();
+        assert!(calls.contains(&r#"before s1 Agent {"a":1}"#.to_string()));
+        assert!(calls.contains(&"after s1 true 7".to_string()));
+        assert!(calls.contains(&r#"error s2 "boom""#.to_string()));
+    }
+
+    #[test]
+    fn default_methods_are_no_ops() {
+        struct Nothing;
+        impl StepHooks for Nothing {}
+        Nothing.before_step("s", "Log", &Value::Null);
+        Nothing.after_step("s", &Value::Null, Duration::ZERO);
+        Nothing.on_error("s", &Value::Null);
+    }
+}
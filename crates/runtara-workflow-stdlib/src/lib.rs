@@ -71,6 +71,9 @@ pub mod template;
 // JSON helpers for direct-emitted workflow components
 pub mod direct_json;
 
+// Step execution hooks for cross-cutting concerns (metrics, tracing)
+pub mod hooks;
+
 // Child workflow input validation (runtime)
 pub mod child_input_validation;
 
@@ -133,7 +136,7 @@ mod component {
     use std::cell::RefCell;
 
     use super::bindings::exports::runtara::workflow_stdlib::json::{
-        AgentRetryError, Guest, InvokeError,
+        AgentRetryError, Guest, InvokeError, SplitWindow,
     };
     use super::direct_json::{self, DirectJsonManifest};
 
@@ -336,6 +339,43 @@ mod component {
             })
         }
 
+        fn split_compaction_prefixes(split_id: u32, source: Vec<u8>) -> Result<Vec<u8>, String> {
+            MANIFEST.with(|slot| {
+                let slot = slot.borrow();
+                let manifest = slot
+                    .as_ref()
+                    .ok_or_else(|| "direct stdlib manifest was not initialized".to_string())?;
+                manifest.split_compaction_prefixes(split_id, &source)
+            })
+        }
+
+        fn split_next_window(
+            split_id: u32,
+            window: u32,
+            rate_limited: bool,
+            elapsed_ms: u32,
+            baseline_ms: u32,
+        ) -> Result<SplitWindow, String> {
+            MANIFEST.with(|slot| {
+                let slot = slot.borrow();
+                let manifest = slot
+                    .as_ref()
+                    .ok_or_else(|| "direct stdlib manifest was not initialized".to_string())?;
+                let next = manifest.split_next_window(
+                    split_id,
+                    window,
+                    rate_limited,
+                    elapsed_ms,
+                    baseline_ms,
+                )?;
+                Ok(SplitWindow {
+                    window: next.window,
+                    baseline_ms: next.baseline_ms,
+                    event: next.event,
+                })
+            })
+        }
+
         fn split_result(
             split_id: u32,
             source: Vec<u8>,
@@ -476,6 +516,46 @@ mod component {
             })
         }
 
+        fn set_variables(
+            set_id: u32,
+            values: Vec<u8>,
+            variables: Vec<u8>,
+        ) -> Result<Vec<u8>, String> {
+            MANIFEST.with(|slot| {
+                let slot = slot.borrow();
+                let manifest = slot
+                    .as_ref()
+                    .ok_or_else(|| "direct stdlib manifest was not initialized".to_string())?;
+                manifest.set_variables(set_id, &values, &variables)
+            })
+        }
+
+        fn set_variable(
+            set_id: u32,
+            source: Vec<u8>,
+            variables: Vec<u8>,
+        ) -> Result<Vec<u8>, String> {
+            MANIFEST.with(|slot| {
+                let slot = slot.borrow();
+                let manifest = slot
+                    .as_ref()
+                    .ok_or_else(|| "direct stdlib manifest was not initialized".to_string())?;
+                manifest.set_variable(set_id, &source, &variables)
+            })
+        }
+
+        fn set_variable_key(step_id: String, source: Vec<u8>) -> Result<Vec<u8>, String> {
+            MANIFEST.with(|slot| {
+                let slot = slot.borrow();
+                let manifest = slot
+                    .as_ref()
+                    .ok_or_else(|| "direct stdlib manifest was not initialized".to_string())?;
+                manifest
+                    .set_variable_key(&step_id, &source)
+                    .map(String::into_bytes)
+            })
+        }
+
         fn error_event(error_id: u32, source: Vec<u8>) -> Result<Vec<u8>, String> {
             MANIFEST.with(|slot| {
                 let slot = slot.borrow();
@@ -1342,6 +1422,42 @@ mod component {
                 manifest.step_debug_error(&step_id, &source, &error)
             })
         }
+
+        fn step_hooks_enabled() -> bool {
+            super::hooks::hooks_registered()
+        }
+
+        fn step_hook_before(step_id: String, source: Vec<u8>) {
+            MANIFEST.with(|slot| {
+                if let Some(manifest) = slot.borrow().as_ref() {
+                    manifest.step_hook_before(&step_id, &source);
+                }
+            })
+        }
+
+        fn step_hook_after(step_id: String, source: Vec<u8>) {
+            MANIFEST.with(|slot| {
+                if let Some(manifest) = slot.borrow().as_ref() {
+                    manifest.step_hook_after(&step_id, &source);
+                }
+            })
+        }
+
+        fn step_hook_error(step_id: String, error: Vec<u8>) {
+            MANIFEST.with(|slot| {
+                if let Some(manifest) = slot.borrow().as_ref() {
+                    manifest.step_hook_error(&step_id, &error);
+                }
+            })
+        }
+
+        fn agent_hook_error(agent_id: u32, error: Vec<u8>) {
+            MANIFEST.with(|slot| {
+                if let Some(manifest) = slot.borrow().as_ref() {
+                    manifest.agent_hook_error(agent_id, &error);
+                }
+            })
+        }
     }
 
     super::bindings::export!(Component with_types_in super::bindings);
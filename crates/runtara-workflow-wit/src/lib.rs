@@ -88,6 +88,8 @@ mod tests {
             "split-append-error",
             "split-output",
             "split-cache-key",
+            "split-compaction-prefixes",
+            "split-next-window",
             "split-result",
             "split-output-from-result",
             "while-max-iterations",
@@ -107,6 +109,9 @@ mod tests {
             "delay-duration-ms",
             "delay",
             "delay-sleep-key",
+            "set-variables",
+            "set-variable",
+            "set-variable-key",
             "invoke-error-fields",
             "breakpoint-key",
             "breakpoint-event",
@@ -140,6 +145,12 @@ mod tests {
             "agent-debug-error",
             "step-debug-start",
             "step-debug-end",
+            "step-debug-error",
+            "step-hooks-enabled",
+            "step-hook-before",
+            "step-hook-after",
+            "step-hook-error",
+            "agent-hook-error",
         ] {
             assert!(
                 interface.functions.contains_key(function),
@@ -277,6 +288,7 @@ mod tests {
             "blocking-sleep",
             "get-checkpoint",
             "checkpoint",
+            "compact-checkpoints",
             "handle-checkpoint-signal",
             "record-retry-attempt",
             "durable-sleep-checkpoint",
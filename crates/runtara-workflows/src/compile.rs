@@ -21,6 +21,7 @@ use std::path::PathBuf;
 use runtara_dsl::ExecutionGraph;
 use serde_json::Value;
 
+use crate::compile_warnings::{CompileWarning, collect_compile_warnings};
 use crate::direct_wasm::{
     DIRECT_WORKFLOW_ARTIFACT_METADATA_FILENAME, DirectCompilationInput, DirectCompileError,
     compile_direct_workflow, compose_direct_workflow_with_extra_dirs,
@@ -31,6 +32,13 @@ use crate::direct_wasm::{
 /// minor versions don't invalidate — they're assumed source-compatible.
 pub const TEMPLATE_MAJOR_VERSION: &str = env!("CARGO_PKG_VERSION_MAJOR");
 
+/// Full version of the workflow compiler, recorded in image build provenance.
+pub const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// `rustc --version` of the toolchain that built the compiler, recorded in
+/// image build provenance.
+pub const RUSTC_VERSION: &str = env!("RUNTARA_RUSTC_VERSION");
+
 // ============================================================================
 // Compilation input/output types
 // ============================================================================
@@ -189,6 +197,9 @@ pub struct NativeCompilationResult {
     pub default_variables: Value,
     /// Compiler path that produced the artifact.
     pub compiler_mode: WorkflowCompilerMode,
+    /// Structured warnings collected during validation and codegen. Callers
+    /// persist these with the registered image.
+    pub warnings: Vec<CompileWarning>,
 }
 
 /// Compile a workflow through the production direct WebAssembly emitter into a
@@ -216,6 +227,7 @@ pub fn compile_workflow_direct(
 
     let child_dependencies = child_dependencies_from_inputs(&child_workflows);
     let default_variables = serde_json::to_value(&execution_graph.variables).unwrap_or(Value::Null);
+    let warnings = collect_compile_warnings(&execution_graph, agent_catalog.as_deref());
 
     report_progress(
         &progress_callback,
@@ -258,6 +270,7 @@ pub fn compile_workflow_direct(
         child_dependencies,
         default_variables,
         compiler_mode: WorkflowCompilerMode::DirectWasm,
+        warnings,
     })
 }
 
@@ -0,0 +1,310 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Structured compile warnings.
+//!
+//! A workflow that compiles can still carry problems worth surfacing after the
+//! compile dialog is closed: validation warnings, oversized inline literals, a
+//! missing description. They are collected here as [`CompileWarning`]s, returned
+//! on [`NativeCompilationResult`](crate::NativeCompilationResult), and persisted
+//! with the registered image so deployment tooling can inspect (or refuse) them.
+
+use std::fmt;
+use std::str::FromStr;
+
+use runtara_dsl::ExecutionGraph;
+use runtara_dsl::agent_meta::AgentCatalog;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::validation::validate_workflow;
+
+/// Immediate values larger than this (serialized) are reported as W090.
+pub const LARGE_IMMEDIATE_BYTES: usize = 64 * 1024;
+
+/// How serious a compile warning is. Ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WarningSeverity {
+    /// Informational; nothing is wrong at runtime.
+    Info,
+    /// Likely authoring mistake or behavior that differs from what the DSL suggests.
+    Warning,
+    /// Tolerated by the compiler but expected to fail or misbehave at runtime.
+    Error,
+}
+
+impl WarningSeverity {
+    /// Stable wire value (`info`, `warning`, `error`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for WarningSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for WarningSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            other => Err(format!(
+                "unknown warning severity '{}' (expected info, warning or error)",
+                other
+            )),
+        }
+    }
+}
+
+/// One structured warning produced while compiling a workflow.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompileWarning {
+    /// Stable code (`W0xx`), shared with validation warnings.
+    pub code: String,
+    /// Step the warning is about; `None` for workflow-level warnings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_id: Option<String>,
+    /// Human-readable message.
+    pub message: String,
+    /// Severity of the warning.
+    pub severity: WarningSeverity,
+}
+
+impl CompileWarning {
+    fn new(
+        code: &str,
+        step_id: Option<&str>,
+        message: impl Into<String>,
+        severity: WarningSeverity,
+    ) -> Self {
+        Self {
+            code: code.to_string(),
+            step_id: step_id.map(str::to_string),
+            message: message.into(),
+            severity,
+        }
+    }
+}
+
+/// Collect the compile warnings for `graph`.
+///
+/// Validation warnings are included only when an agent catalog is available,
+/// since the capability-aware checks need one; the codegen checks (W090, W091)
+/// always run. The result is sorted by severity (most severe first), then code
+/// and step id, so it is stable across compiles.
+pub fn collect_compile_warnings(
+    graph: &ExecutionGraph,
+    catalog: Option<&AgentCatalog>,
+) -> Vec<CompileWarning> {
+    let mut warnings = Vec::new();
+
+    if let Some(catalog) = catalog {
+        for warning in validate_workflow(graph, catalog).warnings {
+            warnings.push(CompileWarning::new(
+                warning.code(),
+                Some(warning.step_id()),
+                warning.to_string(),
+                WarningSeverity::Warning,
+            ));
+        }
+    }
+
+    if graph
+        .description
+        .as_deref()
+        .is_none_or(|d| d.trim().is_empty())
+    {
+        warnings.push(CompileWarning::new(
+            "W091",
+            None,
+            "[W091] Workflow has no description. Describe what it does so operators can tell images apart.",
+            WarningSeverity::Info,
+        ));
+    }
+
+    match serde_json::to_value(&graph.steps) {
+        Ok(steps) => collect_large_immediates(&steps, None, &mut warnings),
+        Err(e) => tracing::debug!(error = %e, "Skipping W090 check: steps did not serialize"),
+    }
+
+    warnings.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.code.cmp(&b.code))
+            .then_with(|| a.step_id.cmp(&b.step_id))
+    });
+    warnings
+}
+
+/// Walk serialized steps looking for oversized `immediate` mapping values.
+/// Nested `steps` maps (Split/While subgraphs) attribute findings to the inner
+/// step.
+fn collect_large_immediates(value: &Value, step_id: Option<&str>, out: &mut Vec<CompileWarning>) {
+    match value {
+        Value::Object(map) => {
+            if map.get("valueType").and_then(Value::as_str) == Some("immediate")
+                && let Some(literal) = map.get("value")
+            {
+                let size = serde_json::to_vec(literal).map(|v| v.len()).unwrap_or(0);
+                if size > LARGE_IMMEDIATE_BYTES {
+                    out.push(CompileWarning::new(
+                        "W090",
+                        step_id,
+                        format!(
+                            "[W090] Step '{}' embeds an immediate value of {} bytes (limit {}). \
+                             Large literals are baked into the image and every checkpoint; pass \
+                             them as workflow input or load them from a connection instead.",
+                            step_id.unwrap_or("?"),
+                            size,
+                            LARGE_IMMEDIATE_BYTES
+                        ),
+                        WarningSeverity::Warning,
+                    ));
+                }
+                return;
+            }
+            for (key, child) in map {
+                if key == "steps"
+                    && let Value::Object(steps) = child
+                {
+                    for (inner_id, step) in steps {
+                        collect_large_immediates(step, Some(inner_id), out);
+                    }
+                } else {
+                    collect_large_immediates(child, step_id, out);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_large_immediates(item, step_id, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn graph(value: Value) -> ExecutionGraph {
+        serde_json::from_value(value).expect("valid graph")
+    }
+
+    fn finish_with_immediate(literal: Value) -> Value {
+        json!({
+            "stepType": "Finish",
+            "id": "finish",
+            "inputMapping": {
+                "result": { "valueType": "immediate", "value": literal }
+            }
+        })
+    }
+
+    #[test]
+    fn severity_orders_and_round_trips() {
+        assert!(WarningSeverity::Info < WarningSeverity::Warning);
+        assert!(WarningSeverity::Warning < WarningSeverity::Error);
+        for severity in [
+            WarningSeverity::Info,
+            WarningSeverity::Warning,
+            WarningSeverity::Error,
+        ] {
+            assert_eq!(severity.as_str().parse::<WarningSeverity>(), Ok(severity));
+            assert_eq!(
+                serde_json::to_value(severity).unwrap(),
+                json!(severity.as_str())
+            );
+        }
+        assert!("fatal".parse::<WarningSeverity>().is_err());
+    }
+
+    #[test]
+    fn missing_description_is_info() {
+        let g = graph(json!({
+            "name": "wf",
+            "entryPoint": "finish",
+            "steps": { "finish": finish_with_immediate(json!(1)) },
+            "executionPlan": []
+        }));
+        let warnings = collect_compile_warnings(&g, None);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, "W091");
+        assert_eq!(warnings[0].severity, WarningSeverity::Info);
+        assert_eq!(warnings[0].step_id, None);
+    }
+
+    #[test]
+    fn large_immediate_is_reported_on_the_inner_step() {
+        let big = "x".repeat(LARGE_IMMEDIATE_BYTES + 1);
+        let g = graph(json!({
+            "name": "wf",
+            "description": "splits things",
+            "entryPoint": "split",
+            "steps": {
+                "split": {
+                    "stepType": "Split",
+                    "id": "split",
+                    "config": {
+                        "value": { "valueType": "immediate", "value": [1, 2] }
+                    },
+                    "subgraph": {
+                        "name": "body",
+                        "entryPoint": "finish",
+                        "steps": { "finish": finish_with_immediate(json!(big)) },
+                        "executionPlan": []
+                    }
+                }
+            },
+            "executionPlan": []
+        }));
+        let warnings = collect_compile_warnings(&g, None);
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert_eq!(warnings[0].code, "W090");
+        assert_eq!(warnings[0].step_id.as_deref(), Some("finish"));
+        assert_eq!(warnings[0].severity, WarningSeverity::Warning);
+    }
+
+    #[test]
+    fn validation_warnings_are_included_with_a_catalog() {
+        let g = graph(json!({
+            "name": "wf",
+            "description": "dangles",
+            "entryPoint": "log",
+            "steps": {
+                "log": { "stepType": "Log", "id": "log", "message": "hi" }
+            },
+            "executionPlan": []
+        }));
+        let warnings = collect_compile_warnings(&g, Some(&AgentCatalog::new()));
+        let dangling = warnings
+            .iter()
+            .find(|w| w.code == "W003")
+            .expect("dangling step warning");
+        assert_eq!(dangling.step_id.as_deref(), Some("log"));
+        assert!(dangling.message.starts_with("[W003]"));
+        assert_eq!(dangling.severity, WarningSeverity::Warning);
+    }
+
+    #[test]
+    fn warning_serializes_camel_case() {
+        let warning = CompileWarning::new("W090", Some("s1"), "big", WarningSeverity::Warning);
+        assert_eq!(
+            serde_json::to_value(&warning).unwrap(),
+            json!({ "code": "W090", "stepId": "s1", "message": "big", "severity": "warning" })
+        );
+    }
+}
@@ -42,6 +42,7 @@ mod embed_workflow;
 mod error_step;
 mod log;
 mod mapping;
+mod set_variable;
 mod split;
 mod split_parallel;
 mod split_retry;
@@ -190,6 +191,10 @@ const DIRECT_AGENT_RETRY_INFO_PAYLOAD_PTR_OFFSET: u64 = 4;
 const DIRECT_AGENT_RETRY_INFO_PAYLOAD_LEN_OFFSET: u64 = 8;
 const DIRECT_AGENT_RETRY_INFO_RETRYABLE_OFFSET: u64 = 12;
 const DIRECT_AGENT_RETRY_INFO_RATE_LIMITED_OFFSET: u64 = 13;
+const DIRECT_SPLIT_WINDOW_WINDOW_OFFSET: u64 = 4;
+const DIRECT_SPLIT_WINDOW_BASELINE_OFFSET: u64 = 8;
+const DIRECT_SPLIT_WINDOW_EVENT_PTR_OFFSET: u64 = 12;
+const DIRECT_SPLIT_WINDOW_EVENT_LEN_OFFSET: u64 = 16;
 const DIRECT_AGENT_RETRY_ATTEMPT_LOCAL: u32 = 10;
 const DIRECT_AGENT_RETRY_ERROR_PTR_LOCAL: u32 = 11;
 const DIRECT_AGENT_RETRY_ERROR_LEN_LOCAL: u32 = 12;
@@ -400,6 +405,21 @@ const DIRECT_PSPLIT_ROUND_CURSOR_LOCAL: u32 = 124;
 /// Set when a retry round fired at least one backoff timer — drives the
 /// round-loop exit (0 => every item settled, stop).
 const DIRECT_PSPLIT_TIMERS_FIRED_LOCAL: u32 = 125;
+/// Adaptive parallelism (`adaptiveParallelism`): the current chunk window, a
+/// flag set when any item in the chunk was rate limited, the controller's
+/// latency baseline, and the chunk's start timestamp. Unused by fixed-window
+/// splits.
+const DIRECT_PSPLIT_WINDOW_LOCAL: u32 = 126;
+const DIRECT_PSPLIT_THROTTLED_LOCAL: u32 = 127;
+const DIRECT_PSPLIT_BASELINE_MS_LOCAL: u32 = 128;
+const DIRECT_PSPLIT_CHUNK_START_MS_LOCAL: u32 = 129;
+
+/// Root scenario variables (`SetVariable`). When the root plan assigns
+/// variables, the baked variables segment is copied into these locals up front
+/// and every root-level step reads `variables` from them, so an assignment is
+/// visible to all later steps. Embedded children keep their own variables.
+const DIRECT_SCENARIO_VARIABLES_PTR_LOCAL: u32 = 130;
+const DIRECT_SCENARIO_VARIABLES_LEN_LOCAL: u32 = 131;
 
 /// Per-item slot for the parallel window's concurrent-retry state machine
 /// (§3.4): `{ state:u32, attempts:u32, input_ptr:u32, input_len:u32, _pad:u64,
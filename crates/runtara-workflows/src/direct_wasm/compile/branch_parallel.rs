@@ -206,6 +206,7 @@ fn chain_next(node: &DirectRunPlan) -> Option<&DirectRunPlan> {
     match node {
         DirectRunPlan::Agent { next_plan, .. }
         | DirectRunPlan::Log { next_plan, .. }
+        | DirectRunPlan::SetVariable { next_plan, .. }
         | DirectRunPlan::Filter { next_plan, .. }
         | DirectRunPlan::SwitchValue { next_plan, .. }
         | DirectRunPlan::GroupBy { next_plan, .. } => Some(next_plan),
@@ -252,6 +253,21 @@ fn with_next_join(node: &DirectRunPlan) -> DirectRunPlan {
             breakpoint: *breakpoint,
             next_plan,
         },
+        DirectRunPlan::SetVariable {
+            step_id,
+            set_id,
+            mapping_id,
+            durable,
+            breakpoint,
+            ..
+        } => DirectRunPlan::SetVariable {
+            step_id: step_id.clone(),
+            set_id: *set_id,
+            mapping_id: *mapping_id,
+            durable: *durable,
+            breakpoint: *breakpoint,
+            next_plan,
+        },
         DirectRunPlan::Filter {
             step_id,
             filter_id,
@@ -355,6 +371,7 @@ fn with_next_join(node: &DirectRunPlan) -> DirectRunPlan {
             retry_delay_ms,
             dont_stop_on_failed,
             parallel_window,
+            adaptive_min_window,
             nested_plan,
             error_plan,
             timeout_ms,
@@ -368,6 +385,7 @@ fn with_next_join(node: &DirectRunPlan) -> DirectRunPlan {
             retry_delay_ms: *retry_delay_ms,
             dont_stop_on_failed: *dont_stop_on_failed,
             parallel_window: *parallel_window,
+            adaptive_min_window: *adaptive_min_window,
             nested_plan: nested_plan.clone(),
             next_plan,
             error_plan: error_plan.clone(),
@@ -36,6 +36,7 @@ pub(super) struct DirectCoreImportIndices {
     runtime_now_ms: Option<u32>,
     runtime_get_checkpoint: Option<u32>,
     runtime_checkpoint: Option<u32>,
+    runtime_compact_checkpoints: Option<u32>,
     runtime_handle_checkpoint_signal: Option<u32>,
     runtime_record_retry_attempt: Option<u32>,
     runtime_durable_sleep: Option<u32>,
@@ -66,6 +67,8 @@ pub(super) struct DirectCoreImportIndices {
     stdlib_split_append_error: Option<u32>,
     stdlib_split_output: Option<u32>,
     stdlib_split_cache_key: Option<u32>,
+    stdlib_split_compaction_prefixes: Option<u32>,
+    stdlib_split_next_window: Option<u32>,
     stdlib_split_result: Option<u32>,
     stdlib_split_output_from_result: Option<u32>,
     stdlib_while_max_iterations: Option<u32>,
@@ -78,6 +81,9 @@ pub(super) struct DirectCoreImportIndices {
     stdlib_delay_duration_ms: Option<u32>,
     stdlib_delay: Option<u32>,
     stdlib_delay_sleep_key: Option<u32>,
+    stdlib_set_variables: Option<u32>,
+    stdlib_set_variable: Option<u32>,
+    stdlib_set_variable_key: Option<u32>,
     stdlib_invoke_error_fields: Option<u32>,
     stdlib_breakpoint_key: Option<u32>,
     stdlib_breakpoint_event: Option<u32>,
@@ -145,6 +151,11 @@ pub(super) struct DirectCoreImportIndices {
     stdlib_step_debug_start: Option<u32>,
     stdlib_step_debug_end: Option<u32>,
     stdlib_step_debug_error: Option<u32>,
+    stdlib_step_hooks_enabled: Option<u32>,
+    stdlib_step_hook_before: Option<u32>,
+    stdlib_step_hook_after: Option<u32>,
+    stdlib_step_hook_error: Option<u32>,
+    stdlib_agent_hook_error: Option<u32>,
     agent_invokes: BTreeMap<String, DirectAgentInvokeImport>,
     // Parallel-split surface (docs/wasip3-parallelism.md Phase 3): the CM-async
     // builtins and per-agent async-lowered invokes, populated directly by
@@ -238,6 +249,11 @@ impl DirectCoreImportIndices {
                 "runtime.checkpoint",
                 omit_runtime,
             )?,
+            runtime_compact_checkpoints: require_runtime(
+                self.runtime_compact_checkpoints,
+                "runtime.compact-checkpoints",
+                omit_runtime,
+            )?,
             runtime_handle_checkpoint_signal: require_runtime(
                 self.runtime_handle_checkpoint_signal,
                 "runtime.handle-checkpoint-signal",
@@ -326,6 +342,14 @@ impl DirectCoreImportIndices {
                 self.stdlib_split_cache_key,
                 "stdlib.split-cache-key",
             )?,
+            stdlib_split_compaction_prefixes: require_import(
+                self.stdlib_split_compaction_prefixes,
+                "stdlib.split-compaction-prefixes",
+            )?,
+            stdlib_split_next_window: require_import(
+                self.stdlib_split_next_window,
+                "stdlib.split-next-window",
+            )?,
             stdlib_split_result: require_import(self.stdlib_split_result, "stdlib.split-result")?,
             stdlib_split_output_from_result: require_import(
                 self.stdlib_split_output_from_result,
@@ -365,6 +389,15 @@ impl DirectCoreImportIndices {
                 self.stdlib_delay_sleep_key,
                 "stdlib.delay-sleep-key",
             )?,
+            stdlib_set_variables: require_import(
+                self.stdlib_set_variables,
+                "stdlib.set-variables",
+            )?,
+            stdlib_set_variable: require_import(self.stdlib_set_variable, "stdlib.set-variable")?,
+            stdlib_set_variable_key: require_import(
+                self.stdlib_set_variable_key,
+                "stdlib.set-variable-key",
+            )?,
             stdlib_invoke_error_fields: require_import(
                 self.stdlib_invoke_error_fields,
                 "stdlib.invoke-error-fields",
@@ -617,6 +650,26 @@ impl DirectCoreImportIndices {
                 self.stdlib_step_debug_error,
                 "stdlib.step-debug-error",
             )?,
+            stdlib_step_hooks_enabled: require_import(
+                self.stdlib_step_hooks_enabled,
+                "stdlib.step-hooks-enabled",
+            )?,
+            stdlib_step_hook_before: require_import(
+                self.stdlib_step_hook_before,
+                "stdlib.step-hook-before",
+            )?,
+            stdlib_step_hook_after: require_import(
+                self.stdlib_step_hook_after,
+                "stdlib.step-hook-after",
+            )?,
+            stdlib_step_hook_error: require_import(
+                self.stdlib_step_hook_error,
+                "stdlib.step-hook-error",
+            )?,
+            stdlib_agent_hook_error: require_import(
+                self.stdlib_agent_hook_error,
+                "stdlib.agent-hook-error",
+            )?,
             agent_invokes: self.agent_invokes,
             waitable_set_new: self.waitable_set_new,
             waitable_set_wait: self.waitable_set_wait,
@@ -665,6 +718,7 @@ pub(super) struct DirectCoreFunctionIndices {
     pub(super) runtime_now_ms: u32,
     pub(super) runtime_get_checkpoint: u32,
     pub(super) runtime_checkpoint: u32,
+    pub(super) runtime_compact_checkpoints: u32,
     pub(super) runtime_handle_checkpoint_signal: u32,
     pub(super) runtime_record_retry_attempt: u32,
     pub(super) runtime_durable_sleep: u32,
@@ -694,6 +748,8 @@ pub(super) struct DirectCoreFunctionIndices {
     pub(super) stdlib_split_append_error: u32,
     pub(super) stdlib_split_output: u32,
     pub(super) stdlib_split_cache_key: u32,
+    pub(super) stdlib_split_compaction_prefixes: u32,
+    pub(super) stdlib_split_next_window: u32,
     pub(super) stdlib_split_result: u32,
     pub(super) stdlib_split_output_from_result: u32,
     pub(super) stdlib_while_max_iterations: u32,
@@ -706,6 +762,9 @@ pub(super) struct DirectCoreFunctionIndices {
     pub(super) stdlib_delay_duration_ms: u32,
     pub(super) stdlib_delay: u32,
     pub(super) stdlib_delay_sleep_key: u32,
+    pub(super) stdlib_set_variables: u32,
+    pub(super) stdlib_set_variable: u32,
+    pub(super) stdlib_set_variable_key: u32,
     pub(super) stdlib_invoke_error_fields: u32,
     pub(super) stdlib_breakpoint_key: u32,
     pub(super) stdlib_breakpoint_event: u32,
@@ -772,6 +831,11 @@ pub(super) struct DirectCoreFunctionIndices {
     pub(super) stdlib_step_debug_start: u32,
     pub(super) stdlib_step_debug_end: u32,
     pub(super) stdlib_step_debug_error: u32,
+    pub(super) stdlib_step_hooks_enabled: u32,
+    pub(super) stdlib_step_hook_before: u32,
+    pub(super) stdlib_step_hook_after: u32,
+    pub(super) stdlib_step_hook_error: u32,
+    pub(super) stdlib_agent_hook_error: u32,
     pub(super) agent_invokes: BTreeMap<String, DirectAgentInvokeImport>,
     /// CM-async builtins — present only when the plan contains an eligible
     /// parallel Split (kept `Option` so sequential-only workflows emit
@@ -988,6 +1052,8 @@ pub(super) fn import_core_function(
         import_indices.runtime_get_checkpoint = Some(function_index);
     } else if is_runtime_import(resolve, interface, function, "checkpoint") {
         import_indices.runtime_checkpoint = Some(function_index);
+    } else if is_runtime_import(resolve, interface, function, "compact-checkpoints") {
+        import_indices.runtime_compact_checkpoints = Some(function_index);
     } else if is_runtime_import(resolve, interface, function, "handle-checkpoint-signal") {
         import_indices.runtime_handle_checkpoint_signal = Some(function_index);
     } else if is_runtime_import(resolve, interface, function, "record-retry-attempt") {
@@ -1046,6 +1112,10 @@ pub(super) fn import_core_function(
         import_indices.stdlib_split_output = Some(function_index);
     } else if is_stdlib_import(resolve, interface, function, "split-cache-key") {
         import_indices.stdlib_split_cache_key = Some(function_index);
+    } else if is_stdlib_import(resolve, interface, function, "split-compaction-prefixes") {
+        import_indices.stdlib_split_compaction_prefixes = Some(function_index);
+    } else if is_stdlib_import(resolve, interface, function, "split-next-window") {
+        import_indices.stdlib_split_next_window = Some(function_index);
     } else if is_stdlib_import(resolve, interface, function, "split-result") {
         import_indices.stdlib_split_result = Some(function_index);
     } else if is_stdlib_import(resolve, interface, function, "split-output-from-result") {
@@ -1070,6 +1140,12 @@ pub(super) fn import_core_function(
         import_indices.stdlib_delay = Some(function_index);
     } else if is_stdlib_import(resolve, interface, function, "delay-sleep-key") {
         import_indices.stdlib_delay_sleep_key = Some(function_index);
+    } else if is_stdlib_import(resolve, interface, function, "set-variables") {
+        import_indices.stdlib_set_variables = Some(function_index);
+    } else if is_stdlib_import(resolve, interface, function, "set-variable") {
+        import_indices.stdlib_set_variable = Some(function_index);
+    } else if is_stdlib_import(resolve, interface, function, "set-variable-key") {
+        import_indices.stdlib_set_variable_key = Some(function_index);
     } else if is_stdlib_import(resolve, interface, function, "invoke-error-fields") {
         import_indices.stdlib_invoke_error_fields = Some(function_index);
     } else if is_stdlib_import(resolve, interface, function, "breakpoint-key") {
@@ -1214,6 +1290,16 @@ pub(super) fn import_core_function(
         import_indices.stdlib_step_debug_end = Some(function_index);
     } else if is_stdlib_import(resolve, interface, function, "step-debug-error") {
         import_indices.stdlib_step_debug_error = Some(function_index);
+    } else if is_stdlib_import(resolve, interface, function, "step-hooks-enabled") {
+        import_indices.stdlib_step_hooks_enabled = Some(function_index);
+    } else if is_stdlib_import(resolve, interface, function, "step-hook-before") {
+        import_indices.stdlib_step_hook_before = Some(function_index);
+    } else if is_stdlib_import(resolve, interface, function, "step-hook-after") {
+        import_indices.stdlib_step_hook_after = Some(function_index);
+    } else if is_stdlib_import(resolve, interface, function, "step-hook-error") {
+        import_indices.stdlib_step_hook_error = Some(function_index);
+    } else if is_stdlib_import(resolve, interface, function, "agent-hook-error") {
+        import_indices.stdlib_agent_hook_error = Some(function_index);
     } else if function.name == "invoke"
         && let Some(agent_id) = agent_id_for_import(resolve, interface)
     {
@@ -33,9 +33,10 @@ use super::core_imports::{
 use super::dispatcher::emit_run_plan_mapping;
 use super::mapping::emit_build_source;
 use super::{
-    DIRECT_EMPTY_STEPS_CONTEXT, DirectCompileError, DirectCoreStaticData, DirectDataSegment,
-    DirectRunPlan, DirectWorkflowManifest, WASM_PAGE_SIZE, direct_core_variables_json,
-    direct_run_plan,
+    DIRECT_EMPTY_STEPS_CONTEXT, DIRECT_SCENARIO_VARIABLES_LEN_LOCAL,
+    DIRECT_SCENARIO_VARIABLES_PTR_LOCAL, DirectCompileError, DirectCoreStaticData,
+    DirectDataSegment, DirectRunPlan, DirectWorkflowManifest, WASM_PAGE_SIZE,
+    direct_core_variables_json, direct_run_plan,
 };
 
 #[derive(Debug, Clone)]
@@ -57,6 +58,10 @@ pub(super) struct DirectCoreConfig {
     /// the invoke return value. Only valid for a pure workflow under the invoke
     /// export (see [`Self::with_omit_runtime`]).
     pub(super) omit_runtime: bool,
+    /// When true, the root graph contains `SetVariable` steps, so the run body
+    /// threads its variables through the mutable scenario-variable locals
+    /// instead of the baked static segment.
+    pub(super) scenario_variables: bool,
 }
 
 impl DirectCoreConfig {
@@ -117,6 +122,7 @@ impl DirectCoreConfig {
             abi: crate::direct_wasm::component::WorkflowAbi::default(),
             store_freeing_sleep: false,
             omit_runtime: false,
+            scenario_variables: !manifest.graph.set_variables.is_empty(),
             run_plan: direct_run_plan(manifest)?,
             static_data: DirectCoreStaticData::new_with_child_workflows(
                 &manifest.graph,
@@ -648,6 +654,13 @@ const CANONICAL_LOCAL_GROUPS: &[(u32, ValType)] = &[
     // parallel-Split scratch (DIRECT_PSPLIT_*, docs/wasip3-parallelism.md);
     // 124-125 are the concurrent-retry-round cursor + timers-fired flag.
     (22, ValType::I32),
+    // 126-128 = adaptive parallel-Split window, throttled flag and latency
+    // baseline; 129 = the chunk's start timestamp (`runtime.now-ms`).
+    (3, ValType::I32),
+    (1, ValType::I64),
+    // 130-131 = root scenario variables (DIRECT_SCENARIO_VARIABLES_*), the
+    // `SetVariable`-mutable copy of the baked variables segment.
+    (2, ValType::I32),
 ];
 
 /// Drop `n` leading local slots from `groups`, splitting (never merging) the
@@ -738,10 +751,25 @@ fn direct_run_function(
     body.instruction(&Instruction::I32Const(config.static_data.steps.len_i32()));
     body.instruction(&Instruction::LocalSet(STEPS_LEN_LOCAL));
 
+    let variables = if config.scenario_variables {
+        body.instruction(&Instruction::I32Const(config.static_data.variables.offset));
+        body.instruction(&Instruction::LocalSet(DIRECT_SCENARIO_VARIABLES_PTR_LOCAL));
+        body.instruction(&Instruction::I32Const(
+            config.static_data.variables.len_i32(),
+        ));
+        body.instruction(&Instruction::LocalSet(DIRECT_SCENARIO_VARIABLES_LEN_LOCAL));
+        DirectVariables::Locals {
+            ptr_local: DIRECT_SCENARIO_VARIABLES_PTR_LOCAL,
+            len_local: DIRECT_SCENARIO_VARIABLES_LEN_LOCAL,
+        }
+    } else {
+        DirectVariables::Segment(&config.static_data.variables)
+    };
+
     emit_build_source(
         &mut body,
         indices,
-        DirectVariables::Segment(&config.static_data.variables),
+        variables,
         DATA_PTR_LOCAL,
         DATA_LEN_LOCAL,
         STEPS_PTR_LOCAL,
@@ -756,7 +784,7 @@ fn direct_run_function(
         indices,
         &config.static_data,
         config.track_events,
-        DirectVariables::Segment(&config.static_data.variables),
+        variables,
         &config.run_plan,
         DATA_PTR_LOCAL,
         DATA_LEN_LOCAL,
@@ -5,7 +5,9 @@
 //! Cross-cutting observability that every step type needs identically: step
 //! start/end events, breakpoint pause/resume, and agent-error events. Each helper
 //! early-returns when its gate (`track_events` / `breakpoint`) is off, so a
-//! non-debug build pays nothing. `emit_step_breakpoint` is checkpoint-guarded — it
+//! non-debug build pays nothing. Step hooks (`runtara_workflow_stdlib::hooks`)
+//! are the exception: they fire at the same points whatever the gates say, behind
+//! a runtime `step-hooks-enabled` check. `emit_step_breakpoint` is checkpoint-guarded — it
 //! pauses via `runtime_breakpoint_pause` and returns to suspend the instance,
 //! giving breakpoints the same durable suspend/resume semantics as the generated
 //! compiler. Isolating these keeps the per-step lowerers focused on semantics.
@@ -33,6 +35,40 @@ fn push_slot_i64(body: &mut WasmFunction, slot_ptr_local: u32, offset: i32) {
     }));
 }
 
+/// Which stdlib step hook a hook call site reports to.
+#[derive(Clone, Copy)]
+enum StepHook {
+    Before,
+    After,
+}
+
+/// Call `step-hook-before`/`step-hook-after` for `step_id` when the stdlib has
+/// hooks registered. Emitted regardless of `track_events`; the stdlib swallows
+/// hook failures, so there is no result to check.
+fn emit_step_hook(
+    body: &mut WasmFunction,
+    indices: &DirectCoreFunctionIndices,
+    static_data: &DirectCoreStaticData,
+    hook: StepHook,
+    step_id: &str,
+    source_ptr_local: u32,
+    source_len_local: u32,
+) {
+    let step_id = static_data
+        .step_id(step_id)
+        .expect("run plan step ids are present in static data");
+    body.instruction(&Instruction::Call(indices.stdlib_step_hooks_enabled));
+    body.instruction(&Instruction::If(BlockType::Empty));
+    push_segment_args(body, step_id);
+    body.instruction(&Instruction::LocalGet(source_ptr_local));
+    body.instruction(&Instruction::LocalGet(source_len_local));
+    body.instruction(&Instruction::Call(match hook {
+        StepHook::Before => indices.stdlib_step_hook_before,
+        StepHook::After => indices.stdlib_step_hook_after,
+    }));
+    body.instruction(&Instruction::End);
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(super) fn emit_step_debug_event(
     body: &mut WasmFunction,
@@ -46,6 +82,19 @@ pub(super) fn emit_step_debug_event(
     output_ptr_local: u32,
     output_len_local: u32,
 ) {
+    emit_step_hook(
+        body,
+        indices,
+        static_data,
+        if start {
+            StepHook::Before
+        } else {
+            StepHook::After
+        },
+        step_id,
+        source_ptr_local,
+        source_len_local,
+    );
     if !track_events {
         return;
     }
@@ -94,6 +143,15 @@ pub(super) fn emit_step_debug_end_timed(
     output_len_local: u32,
     interval_slot_ptr_local: u32,
 ) {
+    emit_step_hook(
+        body,
+        indices,
+        static_data,
+        StepHook::After,
+        step_id,
+        source_ptr_local,
+        source_len_local,
+    );
     if !track_events {
         return;
     }
@@ -358,6 +416,15 @@ pub(super) fn emit_wait_debug_start_event(
     output_len_local: u32,
     failure_target: Option<DirectFailureTarget>,
 ) {
+    emit_step_hook(
+        body,
+        indices,
+        static_data,
+        StepHook::Before,
+        step_id,
+        source_ptr_local,
+        source_len_local,
+    );
     if !track_events {
         return;
     }
@@ -486,6 +553,13 @@ pub(super) fn emit_agent_debug_error(
     debug_ptr_local: u32,
     debug_len_local: u32,
 ) {
+    body.instruction(&Instruction::Call(indices.stdlib_step_hooks_enabled));
+    body.instruction(&Instruction::If(BlockType::Empty));
+    body.instruction(&Instruction::I32Const(agent_id as i32));
+    body.instruction(&Instruction::LocalGet(error_ptr_local));
+    body.instruction(&Instruction::LocalGet(error_len_local));
+    body.instruction(&Instruction::Call(indices.stdlib_agent_hook_error));
+    body.instruction(&Instruction::End);
     if !track_events {
         return;
     }
@@ -531,13 +605,20 @@ pub(super) fn emit_step_debug_error(
     debug_ptr_local: u32,
     debug_len_local: u32,
 ) {
+    let step_id = static_data
+        .step_id(step_id)
+        .expect("run plan step ids are present in static data");
+    body.instruction(&Instruction::Call(indices.stdlib_step_hooks_enabled));
+    body.instruction(&Instruction::If(BlockType::Empty));
+    push_segment_args(body, step_id);
+    body.instruction(&Instruction::LocalGet(error_ptr_local));
+    body.instruction(&Instruction::LocalGet(error_len_local));
+    body.instruction(&Instruction::Call(indices.stdlib_step_hook_error));
+    body.instruction(&Instruction::End);
     if !track_events {
         return;
     }
 
-    let step_id = static_data
-        .step_id(step_id)
-        .expect("run plan step ids are present in static data");
     push_segment_args(body, step_id);
     body.instruction(&Instruction::LocalGet(source_ptr_local));
     body.instruction(&Instruction::LocalGet(source_len_local));
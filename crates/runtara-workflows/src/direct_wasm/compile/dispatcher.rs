@@ -24,6 +24,7 @@ use super::embed_workflow::emit_embed_workflow_plan;
 use super::error_step::emit_error_plan;
 use super::log::emit_log_plan;
 use super::mapping::emit_apply_mapping_step_error;
+use super::set_variable::emit_set_variable_plan;
 use super::split::emit_split_plan;
 use super::step_context::emit_step_context_plan;
 use super::switch_route::emit_switch_route_plan;
@@ -343,6 +344,7 @@ pub(super) fn emit_run_plan_mapping(
             retry_delay_ms,
             dont_stop_on_failed,
             parallel_window,
+            adaptive_min_window,
             nested_plan,
             next_plan,
             error_plan,
@@ -362,6 +364,7 @@ pub(super) fn emit_run_plan_mapping(
                 *retry_delay_ms,
                 *dont_stop_on_failed,
                 *parallel_window,
+                *adaptive_min_window,
                 nested_plan,
                 next_plan,
                 error_plan.as_ref(),
@@ -562,6 +565,42 @@ pub(super) fn emit_run_plan_mapping(
                 handled_target,
             );
         }
+        DirectRunPlan::SetVariable {
+            step_id,
+            set_id,
+            mapping_id,
+            durable,
+            breakpoint,
+            next_plan,
+        } => {
+            emit_set_variable_plan(
+                body,
+                indices,
+                static_data,
+                track_events,
+                variables,
+                step_id,
+                *set_id,
+                *mapping_id,
+                *durable,
+                *breakpoint,
+                next_plan,
+                data_ptr_local,
+                data_len_local,
+                steps_ptr_local,
+                steps_len_local,
+                source_ptr_local,
+                source_len_local,
+                output_ptr_local,
+                output_len_local,
+                route_ptr_local,
+                route_len_local,
+                workflow_log_kind,
+                workflow_error_kind,
+                failure_target,
+                handled_target,
+            );
+        }
         DirectRunPlan::Agent {
             step_id,
             agent_id,
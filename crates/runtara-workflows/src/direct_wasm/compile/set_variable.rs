@@ -0,0 +1,290 @@
+// Copyright (C) 2025 SyncMyOrders Sp. z o.o.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! SetVariable step lowering for the direct workflow core Wasm emitter.
+//!
+//! Resolves the step's `values` mapping, has the stdlib type-check and merge the
+//! assignments into the scenario variables (`stdlib_set_variables`), and writes
+//! the new variables list back into the caller's variables locals so every later
+//! step rebuilds its source from it. A durable SetVariable checkpoints the merged
+//! variables under `scenario_vars::{step_id}`: on resume the replay re-reaches
+//! the step, the lookup HITs and restores the exact variables the original run
+//! assigned instead of re-resolving the mapping against a newer source.
+
+use wasm_encoder::{Function as WasmFunction, Instruction};
+
+use super::abi::{
+    emit_retptr_error_or_step_fail, load_retptr_list, push_retptr_arg, push_segment_args,
+    return_if_retptr_error,
+};
+use super::checkpoint::{emit_checkpoint_lookup, emit_checkpoint_save};
+use super::debug::{emit_step_breakpoint, emit_step_debug_event};
+use super::dispatcher::emit_run_plan_mapping;
+use super::mapping::{emit_apply_mapping_step_error, emit_build_source};
+use super::{
+    DIRECT_WAIT_SIGNAL_ID_LEN_LOCAL, DIRECT_WAIT_SIGNAL_ID_PTR_LOCAL, DirectCoreFunctionIndices,
+    DirectCoreStaticData, DirectDataSegment, DirectFailureTarget, DirectHandledTarget,
+    DirectRunPlan, DirectVariables,
+};
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn emit_set_variable_plan(
+    body: &mut WasmFunction,
+    indices: &DirectCoreFunctionIndices,
+    static_data: &DirectCoreStaticData,
+    track_events: bool,
+    variables: DirectVariables<'_>,
+    step_id: &str,
+    set_id: u32,
+    mapping_id: u32,
+    durable: bool,
+    breakpoint: bool,
+    next_plan: &DirectRunPlan,
+    data_ptr_local: u32,
+    data_len_local: u32,
+    steps_ptr_local: u32,
+    steps_len_local: u32,
+    source_ptr_local: u32,
+    source_len_local: u32,
+    output_ptr_local: u32,
+    output_len_local: u32,
+    route_ptr_local: u32,
+    route_len_local: u32,
+    workflow_log_kind: &DirectDataSegment,
+    workflow_error_kind: &DirectDataSegment,
+    failure_target: Option<DirectFailureTarget>,
+    handled_target: Option<DirectHandledTarget>,
+) {
+    // The root body switches to the scenario-variable locals whenever the graph
+    // has a SetVariable, and embedded children always run on their own locals;
+    // a baked segment here means the plan and the config disagree.
+    let DirectVariables::Locals {
+        ptr_local: variables_ptr_local,
+        len_local: variables_len_local,
+    } = variables
+    else {
+        panic!("SetVariable step '{step_id}' lowered without mutable variables locals");
+    };
+
+    emit_step_breakpoint(
+        body,
+        indices,
+        static_data,
+        breakpoint,
+        step_id,
+        source_ptr_local,
+        source_len_local,
+        output_ptr_local,
+        output_len_local,
+        route_ptr_local,
+        route_len_local,
+    );
+
+    emit_step_debug_event(
+        body,
+        indices,
+        static_data,
+        track_events,
+        true,
+        step_id,
+        source_ptr_local,
+        source_len_local,
+        output_ptr_local,
+        output_len_local,
+    );
+
+    if durable {
+        let step_id_segment = static_data
+            .step_id(step_id)
+            .expect("run plan step ids are present in static data");
+        // The variables-state key lives in the wait signal-id scratch locals —
+        // nothing else is in flight across this step.
+        push_segment_args(body, step_id_segment);
+        body.instruction(&Instruction::LocalGet(source_ptr_local));
+        body.instruction(&Instruction::LocalGet(source_len_local));
+        push_retptr_arg(body);
+        body.instruction(&Instruction::Call(indices.stdlib_set_variable_key));
+        return_if_retptr_error(body, indices);
+        load_retptr_list(
+            body,
+            DIRECT_WAIT_SIGNAL_ID_PTR_LOCAL,
+            DIRECT_WAIT_SIGNAL_ID_LEN_LOCAL,
+        );
+
+        // HIT: the checkpointed variables ARE the assignment — restore them.
+        emit_checkpoint_lookup(
+            body,
+            indices,
+            DIRECT_WAIT_SIGNAL_ID_PTR_LOCAL,
+            DIRECT_WAIT_SIGNAL_ID_LEN_LOCAL,
+            variables_ptr_local,
+            variables_len_local,
+        );
+        body.instruction(&Instruction::Else);
+        emit_assign_variables(
+            body,
+            indices,
+            static_data,
+            track_events,
+            step_id,
+            set_id,
+            mapping_id,
+            variables_ptr_local,
+            variables_len_local,
+            source_ptr_local,
+            source_len_local,
+            output_ptr_local,
+            output_len_local,
+            route_ptr_local,
+            route_len_local,
+            failure_target.map(|target| target.nested(1)),
+        );
+        emit_checkpoint_save(
+            body,
+            indices,
+            DIRECT_WAIT_SIGNAL_ID_PTR_LOCAL,
+            DIRECT_WAIT_SIGNAL_ID_LEN_LOCAL,
+            variables_ptr_local,
+            variables_len_local,
+        );
+        body.instruction(&Instruction::End);
+    } else {
+        emit_assign_variables(
+            body,
+            indices,
+            static_data,
+            track_events,
+            step_id,
+            set_id,
+            mapping_id,
+            variables_ptr_local,
+            variables_len_local,
+            source_ptr_local,
+            source_len_local,
+            output_ptr_local,
+            output_len_local,
+            route_ptr_local,
+            route_len_local,
+            failure_target,
+        );
+    }
+
+    body.instruction(&Instruction::I32Const(set_id as i32));
+    body.instruction(&Instruction::LocalGet(source_ptr_local));
+    body.instruction(&Instruction::LocalGet(source_len_local));
+    body.instruction(&Instruction::LocalGet(variables_ptr_local));
+    body.instruction(&Instruction::LocalGet(variables_len_local));
+    push_retptr_arg(body);
+    body.instruction(&Instruction::Call(indices.stdlib_set_variable));
+    return_if_retptr_error(body, indices);
+    load_retptr_list(body, steps_ptr_local, steps_len_local);
+
+    emit_build_source(
+        body,
+        indices,
+        variables,
+        data_ptr_local,
+        data_len_local,
+        steps_ptr_local,
+        steps_len_local,
+        source_ptr_local,
+        source_len_local,
+        failure_target,
+    );
+
+    emit_step_debug_event(
+        body,
+        indices,
+        static_data,
+        track_events,
+        false,
+        step_id,
+        source_ptr_local,
+        source_len_local,
+        output_ptr_local,
+        output_len_local,
+    );
+
+    emit_run_plan_mapping(
+        body,
+        indices,
+        static_data,
+        track_events,
+        variables,
+        next_plan,
+        data_ptr_local,
+        data_len_local,
+        steps_ptr_local,
+        steps_len_local,
+        source_ptr_local,
+        source_len_local,
+        output_ptr_local,
+        output_len_local,
+        route_ptr_local,
+        route_len_local,
+        workflow_log_kind,
+        workflow_error_kind,
+        failure_target,
+        handled_target,
+    );
+}
+
+/// Resolve the `values` mapping against the current source and merge it into
+/// the variables locals. A mapping or type error fails the step.
+#[allow(clippy::too_many_arguments)]
+fn emit_assign_variables(
+    body: &mut WasmFunction,
+    indices: &DirectCoreFunctionIndices,
+    static_data: &DirectCoreStaticData,
+    track_events: bool,
+    step_id: &str,
+    set_id: u32,
+    mapping_id: u32,
+    variables_ptr_local: u32,
+    variables_len_local: u32,
+    source_ptr_local: u32,
+    source_len_local: u32,
+    output_ptr_local: u32,
+    output_len_local: u32,
+    route_ptr_local: u32,
+    route_len_local: u32,
+    failure_target: Option<DirectFailureTarget>,
+) {
+    emit_apply_mapping_step_error(
+        body,
+        indices,
+        static_data,
+        track_events,
+        mapping_id,
+        step_id,
+        source_ptr_local,
+        source_len_local,
+        output_ptr_local,
+        output_len_local,
+        route_ptr_local,
+        route_len_local,
+        failure_target,
+    );
+
+    body.instruction(&Instruction::I32Const(set_id as i32));
+    body.instruction(&Instruction::LocalGet(output_ptr_local));
+    body.instruction(&Instruction::LocalGet(output_len_local));
+    body.instruction(&Instruction::LocalGet(variables_ptr_local));
+    body.instruction(&Instruction::LocalGet(variables_len_local));
+    push_retptr_arg(body);
+    body.instruction(&Instruction::Call(indices.stdlib_set_variables));
+    emit_retptr_error_or_step_fail(
+        body,
+        indices,
+        static_data,
+        track_events,
+        failure_target,
+        step_id,
+        source_ptr_local,
+        source_len_local,
+        route_ptr_local,
+        route_len_local,
+        output_ptr_local,
+        output_len_local,
+    );
+    load_retptr_list(body, variables_ptr_local, variables_len_local);
+}
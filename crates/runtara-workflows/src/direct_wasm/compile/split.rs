@@ -315,6 +315,7 @@ pub(super) fn emit_split_plan(
     retry_delay_ms: u64,
     dont_stop_on_failed: bool,
     parallel_window: Option<u32>,
+    adaptive_min_window: Option<u32>,
     nested_plan: &DirectRunPlan,
     next_plan: &DirectRunPlan,
     error_plan: Option<&DirectErrorRoutePlan>,
@@ -550,6 +551,7 @@ pub(super) fn emit_split_plan(
             track_events,
             split_id,
             parallel_window.expect("parallel body implies a window"),
+            adaptive_min_window,
             dont_stop_on_failed,
             has_error_plan,
             parallel,
@@ -859,6 +861,27 @@ pub(super) fn emit_split_plan(
             output_ptr_local,
             output_len_local,
         );
+        // The aggregate is durable, so the body's per-iteration checkpoints are
+        // now dead weight: compact them. Compaction is garbage collection only
+        // (the runtime skips it in debug mode and it is idempotent), so its
+        // result is ignored rather than failing the Split.
+        body.instruction(&Instruction::I32Const(split_id as i32));
+        body.instruction(&Instruction::LocalGet(DIRECT_SPLIT_PARENT_SOURCE_PTR_LOCAL));
+        body.instruction(&Instruction::LocalGet(DIRECT_SPLIT_PARENT_SOURCE_LEN_LOCAL));
+        push_retptr_arg(body);
+        body.instruction(&Instruction::Call(indices.stdlib_split_compaction_prefixes));
+        emit_retptr_error_or_return(
+            body,
+            indices,
+            fresh_failure_target,
+            route_ptr_local,
+            route_len_local,
+        );
+        load_retptr_list(body, route_ptr_local, route_len_local);
+        body.instruction(&Instruction::LocalGet(route_ptr_local));
+        body.instruction(&Instruction::LocalGet(route_len_local));
+        push_retptr_arg(body);
+        body.instruction(&Instruction::Call(indices.runtime_compact_checkpoints));
         // Close the checkpoint-lookup `if`/`else` and build the step output from
         // the (cached or computed) result. Under retry this is deferred until
         // after the retry blocks (the inner-attempt block must stay open so
@@ -25,6 +25,14 @@
 //! export's task is async-TYPED (ABI v2); proven end-to-end by
 //! `spikes/wasip3-stackful` (`run-both-sync`).
 //!
+//! With `adaptiveParallelism` the chunk width is a runtime local instead of a
+//! constant: it starts at `min`, and after each chunk drains the stdlib
+//! controller (`split-next-window`) grows it by one while chunk latency stays
+//! healthy, halves it when any item came back with a structured RATE_LIMITED
+//! error (the host surfaces connection-level throttling and upstream 429s that
+//! way), and caps it at `max` — which also sizes the agent pool. Window changes
+//! are recorded as `split_concurrency` custom events.
+//!
 //! V1 eligibility (anything else degrades to the sequential lowering):
 //!   - Split: not durable, no retries, no timeout, any `dontStopOnFailed`.
 //!   - Body: exactly one Agent step (terminal next), no retries, not durable,
@@ -39,7 +47,8 @@ use wasm_encoder::{BlockType, Function as WasmFunction, Instruction};
 use super::abi::{
     emit_entry_suspend_return, emit_get_checkpoint_has_value, emit_retptr_error_or_return,
     load_retptr_list, load_retptr_option_list, load_retptr_tag, push_retptr_arg,
-    push_retptr_u8_load, push_segment_args, push_variables_args, return_if_retptr_error,
+    push_retptr_i32_load, push_retptr_i64_load, push_retptr_u8_load, push_segment_args,
+    push_variables_args, return_if_retptr_error,
 };
 use super::agent::emit_agent_plan;
 use super::agent_io::emit_agent_cache_key;
@@ -57,19 +66,24 @@ use super::{
     DIRECT_AGENT_RETRY_ATTEMPT_LOCAL, DIRECT_AGENT_RETRY_ERROR_LEN_LOCAL,
     DIRECT_AGENT_RETRY_ERROR_PTR_LOCAL, DIRECT_AGENT_RETRY_SLEEP_MS_LOCAL,
     DIRECT_AGENT_RETRY_SLEEP_TAG_LOCAL, DIRECT_AGENT_RETRYABLE_LOCAL,
-    DIRECT_PSPLIT_CHUNK_END_LOCAL, DIRECT_PSPLIT_CHUNK_START_LOCAL, DIRECT_PSPLIT_EVENT_OFFSET,
-    DIRECT_PSPLIT_LAUNCH_LOCAL, DIRECT_PSPLIT_PENDING_LOCAL, DIRECT_PSPLIT_ROUND_CURSOR_LOCAL,
-    DIRECT_PSPLIT_SIGNAL_LOCAL, DIRECT_PSPLIT_SLOT_ATTEMPTS_OFFSET, DIRECT_PSPLIT_SLOT_HIT_OFFSET,
+    DIRECT_PSPLIT_BASELINE_MS_LOCAL, DIRECT_PSPLIT_CHUNK_END_LOCAL,
+    DIRECT_PSPLIT_CHUNK_START_LOCAL, DIRECT_PSPLIT_CHUNK_START_MS_LOCAL,
+    DIRECT_PSPLIT_EVENT_OFFSET, DIRECT_PSPLIT_LAUNCH_LOCAL, DIRECT_PSPLIT_PENDING_LOCAL,
+    DIRECT_PSPLIT_ROUND_CURSOR_LOCAL, DIRECT_PSPLIT_SIGNAL_LOCAL,
+    DIRECT_PSPLIT_SLOT_ATTEMPTS_OFFSET, DIRECT_PSPLIT_SLOT_HIT_OFFSET,
     DIRECT_PSPLIT_SLOT_INPUT_LEN_OFFSET, DIRECT_PSPLIT_SLOT_INPUT_PTR_OFFSET,
     DIRECT_PSPLIT_SLOT_KEY_LEN_OFFSET, DIRECT_PSPLIT_SLOT_KEY_PTR_OFFSET,
     DIRECT_PSPLIT_SLOT_RESULT_LEN, DIRECT_PSPLIT_SLOT_RESULT_OFFSET, DIRECT_PSPLIT_SLOT_STRIDE,
-    DIRECT_PSPLIT_SLOT_WAIT_TOTAL_OFFSET, DIRECT_PSPLIT_SLOTS_LOCAL,
-    DIRECT_PSPLIT_TIMERS_FIRED_LOCAL, DIRECT_PSPLIT_WS_LOCAL, DIRECT_RET_BOOL_OK_OFFSET,
-    DIRECT_SPLIT_COUNT_LOCAL, DIRECT_SPLIT_HEAP_BASE_LOCAL, DIRECT_SPLIT_INDEX_LOCAL,
-    DIRECT_SPLIT_ITEM_LEN_LOCAL, DIRECT_SPLIT_ITEM_PTR_LOCAL, DIRECT_SPLIT_PARENT_SOURCE_LEN_LOCAL,
+    DIRECT_PSPLIT_SLOT_WAIT_TOTAL_OFFSET, DIRECT_PSPLIT_SLOTS_LOCAL, DIRECT_PSPLIT_THROTTLED_LOCAL,
+    DIRECT_PSPLIT_TIMERS_FIRED_LOCAL, DIRECT_PSPLIT_WINDOW_LOCAL, DIRECT_PSPLIT_WS_LOCAL,
+    DIRECT_RET_BOOL_OK_OFFSET, DIRECT_RET_U64_OK_OFFSET, DIRECT_SPLIT_COUNT_LOCAL,
+    DIRECT_SPLIT_HEAP_BASE_LOCAL, DIRECT_SPLIT_INDEX_LOCAL, DIRECT_SPLIT_ITEM_LEN_LOCAL,
+    DIRECT_SPLIT_ITEM_PTR_LOCAL, DIRECT_SPLIT_PARENT_SOURCE_LEN_LOCAL,
     DIRECT_SPLIT_PARENT_SOURCE_PTR_LOCAL, DIRECT_SPLIT_RESULTS_LEN_LOCAL,
     DIRECT_SPLIT_RESULTS_PTR_LOCAL, DIRECT_SPLIT_VARIABLES_LEN_LOCAL,
-    DIRECT_SPLIT_VARIABLES_PTR_LOCAL, DirectCoreFunctionIndices, DirectCoreStaticData,
+    DIRECT_SPLIT_VARIABLES_PTR_LOCAL, DIRECT_SPLIT_WINDOW_BASELINE_OFFSET,
+    DIRECT_SPLIT_WINDOW_EVENT_LEN_OFFSET, DIRECT_SPLIT_WINDOW_EVENT_PTR_OFFSET,
+    DIRECT_SPLIT_WINDOW_WINDOW_OFFSET, DirectCoreFunctionIndices, DirectCoreStaticData,
     DirectRunPlan, DirectVariables,
 };
 
@@ -268,7 +282,8 @@ fn collect_parallel_agent_components(
         | P::SwitchValue { next_plan, .. }
         | P::GroupBy { next_plan, .. }
         | P::Delay { next_plan, .. }
-        | P::Log { next_plan, .. } => {
+        | P::Log { next_plan, .. }
+        | P::SetVariable { next_plan, .. } => {
             collect_parallel_agent_components(static_data, next_plan, out);
         }
         P::SwitchRoute {
@@ -678,6 +693,7 @@ pub(super) fn emit_parallel_split_items(
     track_events: bool,
     split_id: u32,
     window: u32,
+    adaptive_min_window: Option<u32>,
     dont_stop_on_failed: bool,
     has_error_plan: bool,
     parallel: &ParallelAgentBody<'_>,
@@ -731,6 +747,13 @@ pub(super) fn emit_parallel_split_items(
     // item cursor starts at 0 (set by the caller, mirroring sequential).
     body.instruction(&Instruction::I32Const(0));
     body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_SIGNAL_LOCAL));
+    // Adaptive: the first chunk runs at the floor with no latency baseline.
+    if let Some(min_window) = adaptive_min_window {
+        body.instruction(&Instruction::I32Const(min_window as i32));
+        body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_WINDOW_LOCAL));
+        body.instruction(&Instruction::I32Const(0));
+        body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_BASELINE_MS_LOCAL));
+    }
     body.instruction(&Instruction::Block(BlockType::Empty)); // $chunks_done
     body.instruction(&Instruction::Loop(BlockType::Empty)); // $chunks
     body.instruction(&Instruction::LocalGet(DIRECT_SPLIT_INDEX_LOCAL));
@@ -760,7 +783,35 @@ pub(super) fn emit_parallel_split_items(
     body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_CHUNK_START_LOCAL));
     body.instruction(&Instruction::LocalGet(DIRECT_SPLIT_INDEX_LOCAL));
     // window == u32::MAX means "unlimited": saturate instead of wrapping.
-    if window == u32::MAX {
+    if adaptive_min_window.is_some() {
+        // Runtime window: chunk_end = COUNT - INDEX > window ? INDEX + window
+        // : COUNT (compared on the remaining span, so it cannot wrap).
+        body.instruction(&Instruction::Drop);
+        body.instruction(&Instruction::LocalGet(DIRECT_SPLIT_COUNT_LOCAL));
+        body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_CHUNK_END_LOCAL));
+        body.instruction(&Instruction::LocalGet(DIRECT_SPLIT_COUNT_LOCAL));
+        body.instruction(&Instruction::LocalGet(DIRECT_SPLIT_INDEX_LOCAL));
+        body.instruction(&Instruction::I32Sub);
+        body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_WINDOW_LOCAL));
+        body.instruction(&Instruction::I32GtU);
+        body.instruction(&Instruction::If(BlockType::Empty));
+        body.instruction(&Instruction::LocalGet(DIRECT_SPLIT_INDEX_LOCAL));
+        body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_WINDOW_LOCAL));
+        body.instruction(&Instruction::I32Add);
+        body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_CHUNK_END_LOCAL));
+        body.instruction(&Instruction::End);
+
+        // Per-chunk feedback: throttled = 0; chunk_start_ms = now.
+        body.instruction(&Instruction::I32Const(0));
+        body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_THROTTLED_LOCAL));
+        if !indices.omit_runtime {
+            push_retptr_arg(body);
+            body.instruction(&Instruction::Call(indices.runtime_now_ms));
+            emit_retptr_error_or_return(body, indices, None, route_ptr_local, route_len_local);
+            push_retptr_i64_load(body, DIRECT_RET_U64_OK_OFFSET);
+            body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_CHUNK_START_MS_LOCAL));
+        }
+    } else if window == u32::MAX {
         body.instruction(&Instruction::LocalGet(DIRECT_SPLIT_COUNT_LOCAL));
         body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_CHUNK_END_LOCAL));
         body.instruction(&Instruction::Drop);
@@ -1180,6 +1231,9 @@ pub(super) fn emit_parallel_split_items(
             body.instruction(&Instruction::LocalGet(DIRECT_AGENT_ATTEMPT_ERR_FLAG_LOCAL));
             body.instruction(&Instruction::If(BlockType::Empty));
             {
+                if adaptive_min_window.is_some() {
+                    emit_adaptive_note_rate_limited(body);
+                }
                 // Load this item's attempt counter + rate-limit budget into the
                 // shared locals (safe: classify is synchronous, no yield).
                 body.instruction(&Instruction::LocalGet(route_ptr_local));
@@ -1404,11 +1458,30 @@ pub(super) fn emit_parallel_split_items(
         body.instruction(&Instruction::Br(0)); // -> $rounds
         body.instruction(&Instruction::End); // loop $rounds
         body.instruction(&Instruction::End); // $rounds_done
+    } else if adaptive_min_window.is_some() {
+        // No retry rounds to classify in: scan the drained slots once so the
+        // controller still sees this chunk's rate-limited results.
+        emit_adaptive_rate_limit_scan(body, indices, route_ptr_local);
     }
 
     body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_WS_LOCAL));
     body.instruction(&Instruction::Call(ws_drop));
 
+    // ── ADAPTIVE WINDOW ─────────────────────────────────────────────────────
+    // Every subtask of the chunk has resolved: feed its outcome to the
+    // controller and size the NEXT chunk. Only scheduling changes — assemble
+    // below still consumes the memoized results in input order.
+    if adaptive_min_window.is_some() {
+        emit_adaptive_next_window(
+            body,
+            indices,
+            static_data,
+            split_id,
+            route_ptr_local,
+            route_len_local,
+        );
+    }
+
     // ── ASSEMBLE pass ───────────────────────────────────────────────────────
     // The EXACT sequential per-item pipeline, in input order, with the invoke
     // memoized. Failure semantics (dontStopOnFailed buckets, onError routing,
@@ -1482,6 +1555,119 @@ pub(super) fn emit_parallel_split_items(
     body.instruction(&Instruction::End); // $chunks_done
 }
 
+/// `throttled |= rate_limited` for the attempt just classified into the shared
+/// retry locals.
+fn emit_adaptive_note_rate_limited(body: &mut WasmFunction) {
+    body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_THROTTLED_LOCAL));
+    body.instruction(&Instruction::LocalGet(DIRECT_AGENT_RATE_LIMITED_LOCAL));
+    body.instruction(&Instruction::I32Or);
+    body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_THROTTLED_LOCAL));
+}
+
+/// No-retry bodies: classify every AGENT-READY slot that holds an error (the
+/// same `agent-retry-error-info` classification the retry rounds use) and fold
+/// its rate-limited bit into the chunk's throttled flag. Slot results are left
+/// untouched for assemble.
+fn emit_adaptive_rate_limit_scan(
+    body: &mut WasmFunction,
+    indices: &DirectCoreFunctionIndices,
+    slot_ptr_local: u32,
+) {
+    body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_CHUNK_START_LOCAL));
+    body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_ROUND_CURSOR_LOCAL));
+    body.instruction(&Instruction::Block(BlockType::Empty)); // $scan_done
+    body.instruction(&Instruction::Loop(BlockType::Empty)); // $scan
+    body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_ROUND_CURSOR_LOCAL));
+    body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_CHUNK_END_LOCAL));
+    body.instruction(&Instruction::I32GeU);
+    body.instruction(&Instruction::BrIf(1));
+
+    emit_slot_ptr(
+        body,
+        DIRECT_PSPLIT_ROUND_CURSOR_LOCAL,
+        DIRECT_PSPLIT_CHUNK_START_LOCAL,
+        DIRECT_PSPLIT_SLOTS_LOCAL,
+        slot_ptr_local,
+    );
+    body.instruction(&Instruction::LocalGet(slot_ptr_local));
+    body.instruction(&Instruction::I32Load(mem32()));
+    body.instruction(&Instruction::I32Const(SLOT_AGENT_READY));
+    body.instruction(&Instruction::I32Eq);
+    body.instruction(&Instruction::If(BlockType::Empty));
+    emit_copy_slot_result_to_retptr(body, slot_ptr_local);
+    load_retptr_tag(body);
+    body.instruction(&Instruction::If(BlockType::Empty));
+    emit_agent_retry_error_info(
+        body,
+        indices,
+        DIRECT_AGENT_RETRY_ERROR_PTR_LOCAL,
+        DIRECT_AGENT_RETRY_ERROR_LEN_LOCAL,
+    );
+    emit_adaptive_note_rate_limited(body);
+    body.instruction(&Instruction::End);
+    body.instruction(&Instruction::End);
+
+    body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_ROUND_CURSOR_LOCAL));
+    body.instruction(&Instruction::I32Const(1));
+    body.instruction(&Instruction::I32Add);
+    body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_ROUND_CURSOR_LOCAL));
+    body.instruction(&Instruction::Br(0)); // -> $scan
+    body.instruction(&Instruction::End); // loop
+    body.instruction(&Instruction::End); // $scan_done
+}
+
+/// `split-next-window(split_id, window, throttled, elapsed_ms, baseline)`:
+/// adopt the returned window/baseline and, when the window moved, record the
+/// stdlib-built payload as a `split_concurrency` custom event.
+fn emit_adaptive_next_window(
+    body: &mut WasmFunction,
+    indices: &DirectCoreFunctionIndices,
+    static_data: &DirectCoreStaticData,
+    split_id: u32,
+    scratch_ptr_local: u32,
+    scratch_len_local: u32,
+) {
+    body.instruction(&Instruction::I32Const(split_id as i32));
+    body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_WINDOW_LOCAL));
+    body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_THROTTLED_LOCAL));
+    if indices.omit_runtime {
+        // No clock: every chunk reads as healthy latency.
+        body.instruction(&Instruction::I32Const(0));
+    } else {
+        push_retptr_arg(body);
+        body.instruction(&Instruction::Call(indices.runtime_now_ms));
+        emit_retptr_error_or_return(body, indices, None, scratch_ptr_local, scratch_len_local);
+        push_retptr_i64_load(body, DIRECT_RET_U64_OK_OFFSET);
+        body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_CHUNK_START_MS_LOCAL));
+        body.instruction(&Instruction::I64Sub);
+        body.instruction(&Instruction::I32WrapI64);
+    }
+    body.instruction(&Instruction::LocalGet(DIRECT_PSPLIT_BASELINE_MS_LOCAL));
+    push_retptr_arg(body);
+    body.instruction(&Instruction::Call(indices.stdlib_split_next_window));
+    emit_retptr_error_or_return(body, indices, None, scratch_ptr_local, scratch_len_local);
+    push_retptr_i32_load(body, DIRECT_SPLIT_WINDOW_WINDOW_OFFSET);
+    body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_WINDOW_LOCAL));
+    push_retptr_i32_load(body, DIRECT_SPLIT_WINDOW_BASELINE_OFFSET);
+    body.instruction(&Instruction::LocalSet(DIRECT_PSPLIT_BASELINE_MS_LOCAL));
+    if indices.omit_runtime {
+        return;
+    }
+    push_retptr_i32_load(body, DIRECT_SPLIT_WINDOW_EVENT_PTR_OFFSET);
+    body.instruction(&Instruction::LocalSet(scratch_ptr_local));
+    push_retptr_i32_load(body, DIRECT_SPLIT_WINDOW_EVENT_LEN_OFFSET);
+    body.instruction(&Instruction::LocalSet(scratch_len_local));
+    body.instruction(&Instruction::LocalGet(scratch_len_local));
+    body.instruction(&Instruction::If(BlockType::Empty));
+    push_segment_args(body, &static_data.split_concurrency_kind);
+    body.instruction(&Instruction::LocalGet(scratch_ptr_local));
+    body.instruction(&Instruction::LocalGet(scratch_len_local));
+    push_retptr_arg(body);
+    body.instruction(&Instruction::Call(indices.runtime_custom_event));
+    return_if_retptr_error(body, indices);
+    body.instruction(&Instruction::End);
+}
+
 /// Assemble-phase agent body: compute the item's slot pointer into a scratch
 /// local and run the standard Agent lowering with the memoized invoke.
 #[allow(clippy::too_many_arguments)]
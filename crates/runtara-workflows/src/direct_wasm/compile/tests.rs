@@ -191,6 +191,7 @@ fn fixture(name: &str) -> ExecutionGraph {
         "parallel_branches_sync_diamond" => {
             include_str!("../../../tests/fixtures/parallel_branches_sync_diamond.json")
         }
+        "set_variable" => include_str!("../../../tests/fixtures/set_variable_workflow.json"),
         "transform" => include_str!("../../../tests/fixtures/transform_workflow.json"),
         other => panic!("unknown fixture {other}"),
     };
@@ -211,6 +212,7 @@ fn enable_step_breakpoint(graph: &mut ExecutionGraph, step_id: &str) {
         runtara_dsl::Step::EmbedWorkflow(step) => step.breakpoint = Some(true),
         runtara_dsl::Step::While(step) => step.breakpoint = Some(true),
         runtara_dsl::Step::Log(step) => step.breakpoint = Some(true),
+        runtara_dsl::Step::SetVariable(step) => step.breakpoint = Some(true),
         runtara_dsl::Step::Error(step) => step.breakpoint = Some(true),
         runtara_dsl::Step::Filter(step) => step.breakpoint = Some(true),
         runtara_dsl::Step::GroupBy(step) => step.breakpoint = Some(true),
@@ -507,6 +509,14 @@ fn collect_run_plan_ids(
         DirectRunPlan::Log { next_plan, .. } => {
             collect_run_plan_ids(next_plan, condition_ids, mapping_ids);
         }
+        DirectRunPlan::SetVariable {
+            mapping_id,
+            next_plan,
+            ..
+        } => {
+            mapping_ids.push(*mapping_id);
+            collect_run_plan_ids(next_plan, condition_ids, mapping_ids);
+        }
         DirectRunPlan::AiAgentLoop {
             input_mapping_id,
             next_plan,
@@ -755,6 +765,7 @@ fn direct_run_plan_breakpoint(run_plan: &DirectRunPlan) -> Option<bool> {
         | DirectRunPlan::Delay { breakpoint, .. }
         | DirectRunPlan::WaitForSignal { breakpoint, .. }
         | DirectRunPlan::Log { breakpoint, .. }
+        | DirectRunPlan::SetVariable { breakpoint, .. }
         | DirectRunPlan::Agent { breakpoint, .. }
         | DirectRunPlan::AiAgent { breakpoint, .. }
         | DirectRunPlan::AiAgentLoop { breakpoint, .. }
@@ -4082,6 +4093,42 @@ fn direct_compile_supports_log_finish_graph() {
     assert_eq!(manifest.graph.mappings.len(), 1);
 }
 
+#[test]
+fn direct_compile_supports_set_variable_graph() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let result = compile_direct_workflow(DirectCompilationInput {
+        workflow_id: "set-variable".to_string(),
+        version: 1,
+        source_checksum: None,
+        execution_graph: fixture("set_variable"),
+        child_workflows: vec![],
+        output_dir: temp.path().to_path_buf(),
+        track_events: false,
+        agent_catalog: None,
+        agent_slug: None,
+    })
+    .expect("direct SetVariable compile should succeed");
+
+    let wasm = fs::read(&result.wasm_path).expect("wasm");
+    Validator::new_with_features(wasmparser::WasmFeatures::all())
+        .validate_all(&wasm)
+        .expect("direct SetVariable artifact should validate");
+    assert!(result.support_report.supported);
+    assert_eq!(result.support_report.unsupported, vec![]);
+
+    let manifest: DirectWorkflowManifest =
+        serde_json::from_slice(&fs::read(&result.manifest_path).expect("manifest"))
+            .expect("manifest json");
+    assert_eq!(manifest.graph.set_variables.len(), 1);
+    let set_variable = &manifest.graph.set_variables[0];
+    assert_eq!(set_variable.step_id, "set_total");
+    assert!(set_variable.durable);
+    assert_eq!(
+        set_variable.types.get("total").map(String::as_str),
+        Some("number")
+    );
+}
+
 #[test]
 fn direct_compile_supports_error_entry_graph() {
     let temp = tempfile::tempdir().expect("tempdir");
@@ -4738,6 +4785,10 @@ fn direct_core_run_lowers_finish_mapping_through_stdlib() {
     let mut complete_index = None;
     let mut fail_index = None;
     let mut custom_event_index = None;
+    let mut step_hooks_enabled_index = None;
+    let mut step_hook_before_index = None;
+    let mut step_hook_after_index = None;
+    let mut step_hook_error_index = None;
     let mut saw_manifest_data = false;
     let mut saw_variables_data = false;
     let mut saw_steps_data = false;
@@ -4792,6 +4843,18 @@ fn direct_core_run_lowers_finish_mapping_through_stdlib() {
                             ("cm32p2|runtara:workflow-runtime/runtime@0.1", "custom-event") => {
                                 custom_event_index = Some(next_function_index)
                             }
+                            ("cm32p2|runtara:workflow-stdlib/json@0.1", "step-hooks-enabled") => {
+                                step_hooks_enabled_index = Some(next_function_index)
+                            }
+                            ("cm32p2|runtara:workflow-stdlib/json@0.1", "step-hook-before") => {
+                                step_hook_before_index = Some(next_function_index)
+                            }
+                            ("cm32p2|runtara:workflow-stdlib/json@0.1", "step-hook-after") => {
+                                step_hook_after_index = Some(next_function_index)
+                            }
+                            ("cm32p2|runtara:workflow-stdlib/json@0.1", "step-hook-error") => {
+                                step_hook_error_index = Some(next_function_index)
+                            }
                             _ => {}
                         }
                         next_function_index += 1;
@@ -4837,7 +4900,8 @@ fn direct_core_run_lowers_finish_mapping_through_stdlib() {
 
     // Each setup/stdlib call is followed by a fail-on-error guard (`runtime.fail`
     // inside an `if error` block) so an unhandled error surfaces as a `failed`
-    // SDK event instead of a silent non-zero exit.
+    // SDK event instead of a silent non-zero exit. The Finish step's hook calls
+    // run behind a `step-hooks-enabled` check, independent of debug tracking.
     let expected_call_order = [
         init_manifest_index.expect("init-manifest import"),
         fail_index.expect("fail import"),
@@ -4845,8 +4909,14 @@ fn direct_core_run_lowers_finish_mapping_through_stdlib() {
         fail_index.expect("fail import"),
         build_source_index.expect("build-source import"),
         fail_index.expect("fail import"),
+        step_hooks_enabled_index.expect("step-hooks-enabled import"),
+        step_hook_before_index.expect("step-hook-before import"),
         apply_mapping_index.expect("apply-mapping import"),
+        step_hooks_enabled_index.expect("step-hooks-enabled import"),
+        step_hook_error_index.expect("step-hook-error import"),
         fail_index.expect("fail import"),
+        step_hooks_enabled_index.expect("step-hooks-enabled import"),
+        step_hook_after_index.expect("step-hook-after import"),
         complete_index.expect("complete import"),
     ];
     assert!(
@@ -6900,6 +6970,10 @@ fn direct_core_run_emits_step_debug_events_when_tracking_enabled() {
     let mut step_debug_start_index = None;
     let mut step_debug_end_index = None;
     let mut step_debug_error_index = None;
+    let mut step_hooks_enabled_index = None;
+    let mut step_hook_before_index = None;
+    let mut step_hook_after_index = None;
+    let mut step_hook_error_index = None;
     let mut saw_step_debug_start_kind = false;
     let mut saw_step_debug_end_kind = false;
     let mut saw_finish_step_id = false;
@@ -6943,6 +7017,18 @@ fn direct_core_run_emits_step_debug_events_when_tracking_enabled() {
                             ("cm32p2|runtara:workflow-stdlib/json@0.1", "step-debug-error") => {
                                 step_debug_error_index = Some(next_function_index)
                             }
+                            ("cm32p2|runtara:workflow-stdlib/json@0.1", "step-hooks-enabled") => {
+                                step_hooks_enabled_index = Some(next_function_index)
+                            }
+                            ("cm32p2|runtara:workflow-stdlib/json@0.1", "step-hook-before") => {
+                                step_hook_before_index = Some(next_function_index)
+                            }
+                            ("cm32p2|runtara:workflow-stdlib/json@0.1", "step-hook-after") => {
+                                step_hook_after_index = Some(next_function_index)
+                            }
+                            ("cm32p2|runtara:workflow-stdlib/json@0.1", "step-hook-error") => {
+                                step_hook_error_index = Some(next_function_index)
+                            }
                             _ => {}
                         }
                         next_function_index += 1;
@@ -6982,6 +7068,8 @@ fn direct_core_run_emits_step_debug_events_when_tracking_enabled() {
         fail_index.expect("fail import"),
         build_source_index.expect("build-source import"),
         fail_index.expect("fail import"),
+        step_hooks_enabled_index.expect("step-hooks-enabled import"),
+        step_hook_before_index.expect("step-hook-before import"),
         step_debug_start_index.expect("step-debug-start import"),
         fail_index.expect("fail import"),
         custom_event_index.expect("custom-event import"),
@@ -6991,11 +7079,15 @@ fn direct_core_run_emits_step_debug_events_when_tracking_enabled() {
         // error step-debug-end (step-debug-error builder + custom-event) and then
         // runtime.fail. These execute only on the error branch; the success path
         // falls through to the end event below.
+        step_hooks_enabled_index.expect("step-hooks-enabled import"),
+        step_hook_error_index.expect("step-hook-error import"),
         step_debug_error_index.expect("step-debug-error import"),
         fail_index.expect("fail import"),
         custom_event_index.expect("custom-event import"),
         fail_index.expect("fail import"),
         fail_index.expect("fail import"),
+        step_hooks_enabled_index.expect("step-hooks-enabled import"),
+        step_hook_after_index.expect("step-hook-after import"),
         step_debug_end_index.expect("step-debug-end import"),
         fail_index.expect("fail import"),
         custom_event_index.expect("custom-event import"),
@@ -7882,6 +7974,51 @@ fn direct_core_lowers_durable_split_checkpoint_path() {
     );
 }
 
+#[test]
+fn direct_core_run_compacts_durable_split_iteration_checkpoints() {
+    const RUNTIME_MODULE: &str = "cm32p2|runtara:workflow-runtime/runtime@0.1";
+    const STDLIB_MODULE: &str = "cm32p2|runtara:workflow-stdlib/json@0.1";
+
+    for durable in [true, false] {
+        let mut graph = fixture("split");
+        graph.durable = Some(durable);
+        let manifest = build_direct_workflow_manifest(&graph).expect("manifest");
+        let manifest_json = manifest.to_canonical_json().expect("manifest json");
+        let core_config =
+            DirectCoreConfig::new(&manifest, &manifest_json, false).expect("core config");
+        let (resolve, world) =
+            build_direct_component_resolve_with_agents(&manifest.feature_summary.agent_ids)
+                .expect("agent resolve");
+        let core = emit_direct_core_module(&resolve, world, &core_config).expect("core module");
+        let (imports, run_calls) = direct_core_imports_and_run_calls(&core);
+        let compact = direct_core_import(&imports, RUNTIME_MODULE, "compact-checkpoints");
+
+        if !durable {
+            assert!(
+                !run_calls.contains(&compact),
+                "a non-durable Split has no aggregate checkpoint to compact behind"
+            );
+            continue;
+        }
+
+        let split_result = direct_core_call_position(
+            &run_calls,
+            direct_core_import(&imports, STDLIB_MODULE, "split-result"),
+        );
+        let checkpoint = direct_core_call_position_after(
+            &run_calls,
+            direct_core_import(&imports, RUNTIME_MODULE, "checkpoint"),
+            split_result,
+        );
+        let prefixes = direct_core_call_position_after(
+            &run_calls,
+            direct_core_import(&imports, STDLIB_MODULE, "split-compaction-prefixes"),
+            checkpoint,
+        );
+        direct_core_call_position_after(&run_calls, compact, prefixes);
+    }
+}
+
 #[test]
 fn direct_core_run_lowers_while_loop_through_stdlib() {
     let graph = fixture("while_simple");
@@ -9958,6 +10095,78 @@ fn direct_core_run_lowers_log_finish_through_stdlib_and_runtime() {
     );
 }
 
+#[test]
+fn direct_core_run_lowers_durable_set_variable_through_checkpointed_variables() {
+    let graph = fixture("set_variable");
+    let manifest = build_direct_workflow_manifest(&graph).expect("manifest");
+    let manifest_json = manifest.to_canonical_json().expect("manifest json");
+    let core_config = DirectCoreConfig::new(&manifest, &manifest_json, false).expect("core config");
+    assert!(core_config.scenario_variables);
+    let DirectRunPlan::SetVariable {
+        set_id,
+        durable,
+        next_plan,
+        ..
+    } = &core_config.run_plan
+    else {
+        panic!("expected SetVariable run plan");
+    };
+    assert!(*durable);
+    assert!(matches!(next_plan.as_ref(), DirectRunPlan::Finish { .. }));
+
+    let (resolve, world) = build_direct_component_resolve().expect("resolve");
+    let core = emit_direct_core_module(&resolve, world, &core_config).expect("core module");
+    Validator::new_with_features(wasmparser::WasmFeatures::all())
+        .validate_all(&core)
+        .expect("SetVariable core module validates");
+
+    let mut next_function_index = 0;
+    let mut imports = HashMap::new();
+    let mut run_calls = Vec::new();
+    let mut saw_set_id = false;
+    let mut code_body_index = 0;
+
+    for payload in Parser::new(0).parse_all(&core) {
+        match payload.expect("core wasm payload") {
+            Payload::ImportSection(reader) => {
+                for import in reader.into_imports() {
+                    let import = import.expect("core import");
+                    if matches!(import.ty, TypeRef::Func(_)) {
+                        imports.insert(import.name.to_string(), next_function_index);
+                        next_function_index += 1;
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                if code_body_index == 0 {
+                    for operator in body.get_operators_reader().expect("operators") {
+                        match operator.expect("operator") {
+                            Operator::Call { function_index } => run_calls.push(function_index),
+                            Operator::I32Const { value } => saw_set_id |= value == *set_id as i32,
+                            _ => {}
+                        }
+                    }
+                }
+                code_body_index += 1;
+            }
+            _ => {}
+        }
+    }
+
+    let position = |name: &str| {
+        let index = imports[name];
+        run_calls
+            .iter()
+            .position(|&call| call == index)
+            .unwrap_or_else(|| panic!("run body should call {name}"))
+    };
+    assert!(saw_set_id, "SetVariable id should be passed to stdlib");
+    assert!(position("set-variable-key") < position("get-checkpoint"));
+    assert!(position("get-checkpoint") < position("set-variables"));
+    assert!(position("set-variables") < position("checkpoint"));
+    assert!(position("checkpoint") < position("set-variable"));
+}
+
 #[test]
 fn direct_core_run_lowers_error_through_stdlib_and_runtime() {
     let graph = fixture("error");
@@ -10769,6 +10978,86 @@ fn direct_compile_parallel_split_emits_async_lowered_invoke() {
     assert!(has("[async-lower]invoke"), "async-lowered invoke missing");
 }
 
+/// Core module for the `split_parallel` fixture under the invoke ABI (the
+/// shape that enables concurrent windows), plus its agent pools.
+fn split_parallel_core_module(
+    graph: &ExecutionGraph,
+) -> (Vec<u8>, std::collections::BTreeMap<String, u32>) {
+    let manifest = build_direct_workflow_manifest(graph).expect("manifest");
+    let manifest_json = manifest.to_canonical_json().expect("manifest json");
+    let core_config =
+        DirectCoreConfig::new_with_workflow_id(&manifest, &manifest_json, false, "split-parallel")
+            .expect("core config")
+            .with_abi(crate::direct_wasm::component::WorkflowAbi::InvokeHostImports);
+    let pools = super::split_parallel::parallel_agent_pools(
+        &core_config.static_data,
+        &core_config.run_plan,
+    );
+    let (resolve, world) = super::build_direct_component_resolve_configured(
+        &manifest.feature_summary.agent_ids,
+        crate::direct_wasm::component::WorkflowAbi::InvokeHostImports,
+        false,
+        None,
+        &pools,
+        core_config.static_data.has_connections(),
+    )
+    .expect("resolve");
+    let core = emit_direct_core_module(&resolve, world, &core_config).expect("core module");
+    Validator::new_with_features(wasmparser::WasmFeatures::all())
+        .validate_all(&core)
+        .expect("parallel split core module validates");
+    (core, pools)
+}
+
+/// `adaptiveParallelism` replaces the fixed window: the split still lowers to
+/// the concurrent pipeline (pool sized by `max`), and each chunk feeds the
+/// stdlib controller via `split-next-window`. A fixed window never calls it.
+#[test]
+fn direct_core_adaptive_split_calls_split_next_window() {
+    let mut graph = fixture("split_parallel");
+    let Some(runtara_dsl::Step::Split(split)) = graph.steps.get_mut("split") else {
+        panic!("split step missing");
+    };
+    let config = split.config.as_mut().expect("split config");
+    config.parallelism = None;
+    config.adaptive_parallelism = Some(runtara_dsl::AdaptiveParallelism { min: 1, max: 3 });
+
+    let manifest = build_direct_workflow_manifest(&graph).expect("manifest");
+    let plan = direct_run_plan(&manifest).expect("plan");
+    let DirectRunPlan::Split {
+        parallel_window,
+        adaptive_min_window,
+        ..
+    } = &plan
+    else {
+        panic!("expected Split run plan");
+    };
+    assert_eq!(*parallel_window, Some(3));
+    assert_eq!(*adaptive_min_window, Some(1));
+
+    let (core, pools) = split_parallel_core_module(&graph);
+    assert_eq!(pools.values().copied().max(), Some(3), "pool sized by max");
+    let (imports, run_calls) = direct_core_imports_and_run_calls(&core);
+    let next_window = direct_core_import(
+        &imports,
+        "cm32p2|runtara:workflow-stdlib/json@0.1",
+        "split-next-window",
+    );
+    direct_core_call_position(&run_calls, next_window);
+
+    let (core, _) = split_parallel_core_module(&fixture("split_parallel"));
+    let (imports, run_calls) = direct_core_imports_and_run_calls(&core);
+    let next_window = direct_core_import(
+        &imports,
+        "cm32p2|runtara:workflow-stdlib/json@0.1",
+        "split-next-window",
+    );
+    assert!(
+        !run_calls.contains(&next_window),
+        "fixed-window split should not consult the adaptive controller"
+    );
+}
+
 /// The SAME graph without `parallelism` stays on the sequential lowering:
 /// no CM-async imports appear (byte-preservation of the sequential path).
 #[test]
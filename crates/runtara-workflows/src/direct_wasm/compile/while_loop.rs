@@ -10,6 +10,11 @@
 //! capture pattern. Timeout and cancellation are enforced per-iteration with early
 //! returns (not delegated to the host), which is what keeps an unbounded loop
 //! durably interruptible.
+//!
+//! Unlike Split, a While writes no aggregate checkpoint, so its iterations'
+//! checkpoints are the only record of completed work and are never compacted:
+//! deleting them would make a resumed While re-run those iterations' side
+//! effects.
 
 use wasm_encoder::{BlockType, Function as WasmFunction, Instruction};
 
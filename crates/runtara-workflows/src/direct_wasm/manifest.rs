@@ -113,6 +113,9 @@ pub struct DirectGraphManifest {
     /// Log definitions addressable by generated direct Wasm.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub logs: Vec<DirectLogManifest>,
+    /// SetVariable definitions addressable by generated direct Wasm.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub set_variables: Vec<DirectSetVariableManifest>,
     /// Error definitions addressable by generated direct Wasm.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub errors: Vec<DirectErrorManifest>,
@@ -361,6 +364,30 @@ pub struct DirectLogManifest {
     pub value: serde_json::Value,
 }
 
+/// Deterministic SetVariable definition referenced by direct-emitted Wasm.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectSetVariableManifest {
+    /// Manifest-wide SetVariable identifier.
+    pub id: u32,
+    /// Step that owns this SetVariable config.
+    pub step_id: String,
+    /// Human-readable step name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Step type that owns this SetVariable config.
+    pub step_type: String,
+    /// Config role within the step.
+    pub purpose: String,
+    /// Whether assignments are checkpointed (the owning graph's durability).
+    pub durable: bool,
+    /// Mapping (`setVariable.values`) producing the new values.
+    pub mapping_id: u32,
+    /// Declared type of each assigned variable, keyed by variable name.
+    /// Undeclared names are absent and rejected at runtime.
+    pub types: BTreeMap<String, String>,
+}
+
 /// Deterministic Error definition referenced by direct-emitted Wasm.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -611,6 +638,7 @@ struct DirectManifestBuildState {
     next_group_by_id: u32,
     next_delay_id: u32,
     next_log_id: u32,
+    next_set_variable_id: u32,
     next_error_id: u32,
     next_agent_id: u32,
 }
@@ -670,6 +698,12 @@ impl DirectManifestBuildState {
         id
     }
 
+    fn allocate_set_variable_id(&mut self) -> u32 {
+        let id = self.next_set_variable_id;
+        self.next_set_variable_id += 1;
+        id
+    }
+
     fn allocate_error_id(&mut self) -> u32 {
         let id = self.next_error_id;
         self.next_error_id += 1;
@@ -708,6 +742,7 @@ fn graph_manifest(
     collections.group_bys.sort_by_key(|left| left.id);
     collections.delays.sort_by_key(|left| left.id);
     collections.logs.sort_by_key(|left| left.id);
+    collections.set_variables.sort_by_key(|left| left.id);
     collections.errors.sort_by_key(|left| left.id);
     collections.agents.sort_by_key(|left| left.id);
 
@@ -737,6 +772,7 @@ fn graph_manifest(
         group_bys: collections.group_bys,
         delays: collections.delays,
         logs: collections.logs,
+        set_variables: collections.set_variables,
         errors: collections.errors,
         agents: collections.agents,
         edges,
@@ -754,6 +790,7 @@ struct DirectGraphManifestCollections {
     group_bys: Vec<DirectGroupByManifest>,
     delays: Vec<DirectDelayManifest>,
     logs: Vec<DirectLogManifest>,
+    set_variables: Vec<DirectSetVariableManifest>,
     errors: Vec<DirectErrorManifest>,
     agents: Vec<DirectAgentManifest>,
 }
@@ -918,6 +955,35 @@ fn step_manifest(
                 value: canonical_json(step)?,
             });
         }
+        Step::SetVariable(step) => {
+            let mapping_id = state.allocate_mapping_id();
+            collections.mappings.push(DirectMappingManifest {
+                id: mapping_id,
+                step_id: step.id.clone(),
+                step_type: "SetVariable".to_string(),
+                purpose: "setVariable.values".to_string(),
+                value: canonical_json(&step.values)?,
+            });
+            let types = step
+                .values
+                .keys()
+                .filter_map(|name| {
+                    let declared = graph.variables.get(name)?;
+                    let ty = serde_json::to_value(&declared.var_type).ok()?;
+                    Some((name.clone(), ty.as_str()?.to_string()))
+                })
+                .collect();
+            collections.set_variables.push(DirectSetVariableManifest {
+                id: state.allocate_set_variable_id(),
+                step_id: step.id.clone(),
+                name: step.name.clone(),
+                step_type: "SetVariable".to_string(),
+                purpose: "setVariable.config".to_string(),
+                durable: inherited_durable,
+                mapping_id,
+                types,
+            });
+        }
         Step::Error(step) => {
             collections.errors.push(DirectErrorManifest {
                 id: state.allocate_error_id(),
@@ -1574,6 +1640,7 @@ fn step_id(step: &Step) -> &str {
         Step::Filter(step) => &step.id,
         Step::GroupBy(step) => &step.id,
         Step::Delay(step) => &step.id,
+        Step::SetVariable(step) => &step.id,
         Step::WaitForSignal(step) => &step.id,
         Step::AiAgent(step) => &step.id,
     }
@@ -1593,6 +1660,7 @@ fn step_name(step: &Step) -> Option<&str> {
         Step::Filter(step) => step.name.as_deref(),
         Step::GroupBy(step) => step.name.as_deref(),
         Step::Delay(step) => step.name.as_deref(),
+        Step::SetVariable(step) => step.name.as_deref(),
         Step::WaitForSignal(step) => step.name.as_deref(),
         Step::AiAgent(step) => step.name.as_deref(),
     }
@@ -1612,6 +1680,7 @@ fn step_type_name(step: &Step) -> &'static str {
         Step::Filter(_) => "Filter",
         Step::GroupBy(_) => "GroupBy",
         Step::Delay(_) => "Delay",
+        Step::SetVariable(_) => "SetVariable",
         Step::WaitForSignal(_) => "WaitForSignal",
         Step::AiAgent(_) => "AiAgent",
     }
@@ -29,7 +29,8 @@ use std::rc::Rc;
 use super::error::DirectCompileError;
 use super::manifest::{
     DirectAgentManifest, DirectChildWorkflowGraphManifest, DirectDelayManifest, DirectEdgeManifest,
-    DirectGraphManifest, DirectSplitManifest, DirectStepManifest, DirectWorkflowManifest,
+    DirectGraphManifest, DirectSetVariableManifest, DirectSplitManifest, DirectStepManifest,
+    DirectWorkflowManifest,
 };
 
 #[derive(Debug, Clone)]
@@ -88,6 +89,10 @@ pub(super) enum DirectRunPlan {
         /// in `split.rs` (docs/wasip3-parallelism.md Phase 3); ineligible
         /// bodies degrade to the sequential lowering.
         parallel_window: Option<u32>,
+        /// Starting window when `adaptiveParallelism` is configured; the
+        /// window then moves between this and `parallel_window` (its max)
+        /// chunk by chunk via `split-next-window`. None = fixed window.
+        adaptive_min_window: Option<u32>,
         nested_plan: Box<DirectRunPlan>,
         next_plan: Box<DirectRunPlan>,
         error_plan: Option<DirectErrorRoutePlan>,
@@ -135,6 +140,14 @@ pub(super) enum DirectRunPlan {
         breakpoint: bool,
         next_plan: Box<DirectRunPlan>,
     },
+    SetVariable {
+        step_id: String,
+        set_id: u32,
+        mapping_id: u32,
+        durable: bool,
+        breakpoint: bool,
+        next_plan: Box<DirectRunPlan>,
+    },
     Agent {
         step_id: String,
         agent_id: u32,
@@ -402,8 +415,8 @@ pub(super) fn direct_run_plan(
 
     match entry.step_type.as_str() {
         "Finish" | "Filter" | "Switch" | "GroupBy" | "Split" | "While" | "Delay"
-        | "EmbedWorkflow" | "WaitForSignal" | "Log" | "Agent" | "AiAgent" | "Error"
-        | "Conditional" => step_run_plan(
+        | "EmbedWorkflow" | "WaitForSignal" | "Log" | "SetVariable" | "Agent" | "AiAgent"
+        | "Error" | "Conditional" => step_run_plan(
             &manifest.graph,
             &manifest.child_workflows,
             &manifest.graph.entry_point,
@@ -673,6 +686,7 @@ fn step_run_plan_inner(
                 retry_delay_ms: split_effective_retry_delay_ms(split),
                 dont_stop_on_failed,
                 parallel_window: split_parallel_window(graph, step_id)?,
+                adaptive_min_window: split_adaptive_min_window(graph, step_id)?,
                 nested_plan: Box::new(nested_plan),
                 next_plan: Box::new(next_plan),
                 error_plan,
@@ -830,6 +844,28 @@ fn step_run_plan_inner(
                 next_plan: Box::new(next_plan),
             })
         }
+        "SetVariable" => {
+            let set_variable = set_variable_config(graph, step_id)?;
+            let next_plan = normal_flow_plan(
+                graph,
+                child_workflows,
+                step_id,
+                stack,
+                include_on_error,
+                stop_at,
+                region_root,
+                orders,
+            )?;
+
+            Ok(DirectRunPlan::SetVariable {
+                step_id: step_id.to_string(),
+                set_id: set_variable.id,
+                mapping_id: set_variable.mapping_id,
+                durable: set_variable.durable,
+                breakpoint: step_breakpoint_enabled(graph, step),
+                next_plan: Box::new(next_plan),
+            })
+        }
         "Agent" => {
             let agent = agent_config(graph, step_id)?;
             let durable_checkpoint = agent.durable;
@@ -1831,6 +1867,9 @@ fn chain_step_ids(plan: &DirectRunPlan, out: &mut Vec<String>) {
             | DirectRunPlan::Log {
                 step_id, next_plan, ..
             }
+            | DirectRunPlan::SetVariable {
+                step_id, next_plan, ..
+            }
             | DirectRunPlan::Filter {
                 step_id, next_plan, ..
             }
@@ -2032,6 +2071,7 @@ pub(super) fn node_has_breakpoint(node: &DirectRunPlan) -> bool {
         | P::Delay { breakpoint, .. }
         | P::WaitForSignal { breakpoint, .. }
         | P::Log { breakpoint, .. }
+        | P::SetVariable { breakpoint, .. }
         | P::Agent { breakpoint, .. }
         | P::AiAgent { breakpoint, .. }
         | P::AiAgentLoop { breakpoint, .. }
@@ -2080,7 +2120,8 @@ pub(super) fn plan_contains_suspension(plan: &DirectRunPlan) -> bool {
         P::Filter { next_plan, .. }
         | P::SwitchValue { next_plan, .. }
         | P::GroupBy { next_plan, .. }
-        | P::Log { next_plan, .. } => plan_contains_suspension(next_plan),
+        | P::Log { next_plan, .. }
+        | P::SetVariable { next_plan, .. } => plan_contains_suspension(next_plan),
         P::Conditional {
             true_plan,
             false_plan,
@@ -2223,8 +2264,8 @@ pub(super) fn node_body_suspends(node: &DirectRunPlan) -> bool {
 ///   `plan_branch_diamond`'s durable gate keeps this replay-safe (and breakpoints
 ///   only exist on durable graphs). A workflow-agent target is excluded at emission
 ///   time, not here.
-/// - 4c.1: the chain may also contain SYNC non-Agent steps (Log, Filter,
-///   SwitchValue, GroupBy) — those depths run assemble-only in the wavefront (no
+/// - 4c.1: the chain may also contain SYNC non-Agent steps (Log, SetVariable,
+///   Filter, SwitchValue, GroupBy) — those depths run assemble-only in the wavefront (no
 ///   async launch).
 /// - 4c.3 / T2.0: the chain may also contain COMPOSITE nodes (Conditional, Switch,
 ///   Edge, While, Split, Embed, AiAgent, AiAgentLoop) that run BLOCKING at their
@@ -2254,6 +2295,7 @@ fn is_linear_chain_branch(plan: &DirectRunPlan) -> bool {
             }
             // Sync non-Agent steps: no async op, no onError to worry about.
             DirectRunPlan::Log { next_plan, .. }
+            | DirectRunPlan::SetVariable { next_plan, .. }
             | DirectRunPlan::Filter { next_plan, .. }
             | DirectRunPlan::SwitchValue { next_plan, .. }
             | DirectRunPlan::GroupBy { next_plan, .. } => {
@@ -2606,12 +2648,17 @@ fn split_config<'a>(
 
 /// The Split's requested `parallelism` window. 0 means "unlimited" per the
 /// DSL contract — normalized here to u32::MAX and clamped at emission time to
-/// the item count; absent/1 = sequential.
+/// the item count; absent/1 = sequential. An `adaptiveParallelism` config
+/// takes precedence: its `max` is the window ceiling.
 fn split_parallel_window(
     graph: &DirectGraphManifest,
     step_id: &str,
 ) -> Result<Option<u32>, DirectCompileError> {
-    Ok(split_config(graph, step_id)?
+    let config = split_config(graph, step_id)?;
+    if let Some((_, max)) = split_adaptive_bounds(config) {
+        return Ok(Some(max).filter(|window| *window > 1));
+    }
+    Ok(config
         .get("parallelism")
         .and_then(serde_json::Value::as_u64)
         .map(|window| {
@@ -2624,6 +2671,32 @@ fn split_parallel_window(
         .filter(|window| *window > 1))
 }
 
+/// Starting window for an adaptive Split, or None when the window is fixed (or
+/// the adaptive ceiling leaves it sequential).
+fn split_adaptive_min_window(
+    graph: &DirectGraphManifest,
+    step_id: &str,
+) -> Result<Option<u32>, DirectCompileError> {
+    Ok(split_adaptive_bounds(split_config(graph, step_id)?)
+        .filter(|(_, max)| *max > 1)
+        .map(|(min, _)| min))
+}
+
+/// `adaptiveParallelism` bounds normalized the same way as the stdlib
+/// controller: min at least 1, max at least min.
+fn split_adaptive_bounds(config: &serde_json::Value) -> Option<(u32, u32)> {
+    let adaptive = config.get("adaptiveParallelism")?;
+    let bound = |key: &str| {
+        adaptive
+            .get(key)
+            .and_then(serde_json::Value::as_u64)
+            .map(|value| u32::try_from(value).unwrap_or(u32::MAX))
+    };
+    let min = bound("min")?.max(1);
+    let max = bound("max")?.max(min);
+    Some((min, max))
+}
+
 fn split_dont_stop_on_failed(
     graph: &DirectGraphManifest,
     step_id: &str,
@@ -2784,6 +2857,33 @@ fn child_workflow_graph<'a>(
         })
 }
 
+fn set_variable_config<'a>(
+    graph: &'a DirectGraphManifest,
+    step_id: &str,
+) -> Result<&'a DirectSetVariableManifest, DirectCompileError> {
+    if !graph
+        .steps
+        .iter()
+        .any(|step| step.id == step_id && step.step_type == "SetVariable")
+    {
+        return Err(DirectCompileError::Component(format!(
+            "direct step '{step_id}' is not a SetVariable step"
+        )));
+    }
+
+    graph
+        .set_variables
+        .iter()
+        .find(|set_variable| {
+            set_variable.step_id == step_id && set_variable.purpose == "setVariable.config"
+        })
+        .ok_or_else(|| {
+            DirectCompileError::Component(format!(
+                "missing SetVariable config for step '{step_id}'"
+            ))
+        })
+}
+
 fn log_id(graph: &DirectGraphManifest, step_id: &str) -> Result<u32, DirectCompileError> {
     if !graph
         .steps
@@ -2976,6 +3076,9 @@ mod tests {
             }
             | DirectRunPlan::Log {
                 step_id, next_plan, ..
+            }
+            | DirectRunPlan::SetVariable {
+                step_id, next_plan, ..
             } => {
                 out.push(step_id.clone());
                 collect_plan_steps(next_plan, out);
@@ -33,6 +33,7 @@ const DIRECT_AGENT_EMPTY_PARAMETERS: &[u8] = b"{}";
 /// `Finish`); matches the generated compiler's `Ok(Value::Null)`.
 const DIRECT_OUTPUT_NULL: &[u8] = b"null";
 pub(super) const DIRECT_AGENT_RATE_LIMIT_WAIT: &[u8] = b"rate_limit_wait";
+pub(super) const DIRECT_SPLIT_CONCURRENCY_KIND: &[u8] = b"split_concurrency";
 /// Structured failure payload emitted when a `While` step exceeds its configured
 /// timeout. Generated Rust parses `WhileConfig.timeout` but does not enforce it;
 /// direct mode is the first to honor the documented "if exceeded, step fails"
@@ -137,6 +138,7 @@ pub(super) struct DirectCoreStaticData {
     pub(super) agent_rate_limit_wait: DirectDataSegment,
     pub(super) while_timeout_error: DirectDataSegment,
     pub(super) split_timeout_error: DirectDataSegment,
+    pub(super) split_concurrency_kind: DirectDataSegment,
     step_ids: BTreeMap<String, DirectDataSegment>,
     agent_capability_ids: BTreeMap<u32, DirectDataSegment>,
     /// Agents with a literal `connection_id`. Not baked — the stdlib injects the
@@ -266,6 +268,12 @@ impl DirectCoreStaticData {
             16,
         );
 
+        let split_concurrency_kind = DirectDataSegment::new(offset, DIRECT_SPLIT_CONCURRENCY_KIND);
+        offset = align_i32(
+            checked_offset_add(offset, DIRECT_SPLIT_CONCURRENCY_KIND.len())?,
+            16,
+        );
+
         let mut step_ids = BTreeMap::new();
         collect_static_step_ids(graph, &mut offset, &mut step_ids)?;
         for child in child_workflows {
@@ -315,6 +323,7 @@ impl DirectCoreStaticData {
             agent_rate_limit_wait,
             while_timeout_error,
             split_timeout_error,
+            split_concurrency_kind,
             step_ids,
             agent_capability_ids,
             agent_connection_literals,
@@ -380,6 +389,7 @@ impl DirectCoreStaticData {
             &self.agent_rate_limit_wait,
             &self.while_timeout_error,
             &self.split_timeout_error,
+            &self.split_concurrency_kind,
         ];
         segments.extend(self.step_ids.values());
         segments.extend(self.agent_capability_ids.values());
@@ -648,6 +658,7 @@ mod tests {
             group_bys: vec![],
             delays: vec![],
             logs: vec![],
+            set_variables: vec![],
             errors: vec![],
             agents,
             edges: vec![],
@@ -678,7 +678,7 @@ fn supports_direct_control_step_inner(
                 include_on_error,
             )
         }
-        Step::Log(_) => supports_normal_flow_step(
+        Step::Log(_) | Step::SetVariable(_) => supports_normal_flow_step(
             graph,
             child_workflows,
             step_id,
@@ -1399,6 +1399,7 @@ fn collect_step_support(
         Step::Switch(_) if direct_control => {}
         Step::GroupBy(_) if direct_control => {}
         Step::Log(_) if direct_control => {}
+        Step::SetVariable(_) if direct_control => {}
         Step::Error(_) if direct_control => {}
         Step::Split(split) => {
             if !supports_split_step_baseline(split) {
@@ -1463,6 +1464,12 @@ fn collect_step_support(
             "Log steps require runtime custom-event support",
             unsupported,
         ),
+        Step::SetVariable(_) => unsupported_step(
+            step,
+            "set-variable",
+            "SetVariable steps require direct-control graph lowering",
+            unsupported,
+        ),
         Step::Error(_) => unsupported_step(
             step,
             "explicit-error",
@@ -1639,6 +1646,7 @@ fn step_id(step: &Step) -> &str {
         Step::Filter(step) => &step.id,
         Step::GroupBy(step) => &step.id,
         Step::Delay(step) => &step.id,
+        Step::SetVariable(step) => &step.id,
         Step::WaitForSignal(step) => &step.id,
         Step::AiAgent(step) => &step.id,
     }
@@ -1658,6 +1666,7 @@ fn step_type_name(step: &Step) -> &'static str {
         Step::Filter(_) => "Filter",
         Step::GroupBy(_) => "GroupBy",
         Step::Delay(_) => "Delay",
+        Step::SetVariable(_) => "SetVariable",
         Step::WaitForSignal(_) => "WaitForSignal",
         Step::AiAgent(_) => "AiAgent",
     }
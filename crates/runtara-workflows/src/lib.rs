@@ -55,6 +55,7 @@
 //! # Modules
 //!
 //! - [`compile`]: Public compile entry point (direct WebAssembly emitter)
+//! - [`compile_warnings`]: Structured compile warnings persisted with images
 //! - [`direct_wasm`]: Direct WebAssembly emitter
 //! - [`dependency_analysis`]: Dependency resolution for child workflows
 //! - [`paths`]: File path utilities for workflows and data
@@ -68,6 +69,9 @@
 ))]
 pub mod compile;
 
+/// Structured compile warnings persisted with registered images.
+pub mod compile_warnings;
+
 /// Dependency analysis for child workflows.
 pub mod dependency_analysis;
 
@@ -108,9 +112,11 @@ pub mod workflow_features;
     not(all(target_family = "wasm", not(target_os = "wasi")))
 ))]
 pub use compile::{
-    ChildDependency, ChildWorkflowInput, CompilationInput, DirectWorkflowCompileOptions,
-    NativeCompilationResult, TEMPLATE_MAJOR_VERSION, WorkflowCompilerMode, compile_workflow_direct,
+    COMPILER_VERSION, ChildDependency, ChildWorkflowInput, CompilationInput,
+    DirectWorkflowCompileOptions, NativeCompilationResult, RUSTC_VERSION, TEMPLATE_MAJOR_VERSION,
+    WorkflowCompilerMode, compile_workflow_direct,
 };
+pub use compile_warnings::{CompileWarning, WarningSeverity, collect_compile_warnings};
 pub use dependency_analysis::{DependencyGraph, WorkflowReference};
 pub use input_validation::{
     WorkflowInputValidationError, is_empty_schema, validate_inputs, validate_workflow_inputs,
@@ -23,6 +23,7 @@
 //! | 4 | Configuration warnings |
 //! | 5 | Child workflow validation (version format) |
 //! | 7.5 | Data and variable reference validation |
+//! | 7.6 | SetVariable target validation |
 //! | 8 | Step name validation (duplicates) |
 //! | 9 | Compensation validation (warnings) |
 //! | 10 | Edge condition validation (priorities) |
@@ -62,6 +63,7 @@
 //! | E127 | ReferenceRootOutOfScope | `iteration`/`loop`/`item` root used where the runtime never populates it |
 //! | E070 | UnknownVariable | Variable doesn't exist |
 //! | E072 | InvalidConditionalEdge | Conditional outgoing edge is not a true/false branch |
+//! | E074 | ErrorStepNormalEdge | Error step has an outgoing edge other than `onError` |
 //! | E080 | TypeMismatch | Value type doesn't match expected |
 //! | E081 | InvalidEnumValue | Enum value not in allowed set |
 //! | E090 | DuplicateStepName | Multiple steps with same name |
@@ -69,6 +71,9 @@
 //! | E101 | MultipleDefaultEdges | Multiple unconditional edges |
 //! | E117 | FinishOutputMissingName | Finish output has no name |
 //! | E118 | FinishOutputMissingSource | Finish output has no source |
+//! | E128 | InvalidAdaptiveParallelism | Split `adaptiveParallelism` bounds are unusable |
+//! | E129 | SetVariableUnknownVariable | SetVariable assigns a variable the graph does not declare |
+//! | E130 | SetVariableInSubgraph | SetVariable used inside a Split/While/onWait subgraph |
 
 use crate::dependency_analysis::{DependencyGraph, WorkflowReference};
 use runtara_dsl::{
@@ -309,6 +314,27 @@ pub enum ValidationError {
     /// never compile (`embed-workflow-duplicate-child`).
     DuplicateEmbedStepId { step_id: String },
 
+    /// A Split step's `adaptiveParallelism` has `min == 0` or `min > max`, so
+    /// the controller has no valid window to start from.
+    InvalidAdaptiveParallelism { step_id: String, min: u32, max: u32 },
+
+    /// A SetVariable step assigns a name absent from the graph's `variables`.
+    /// Scenario variables must be declared (with a type) before they can be
+    /// mutated.
+    SetVariableUnknownVariable {
+        step_id: String,
+        variable_name: String,
+        available_variables: Vec<String>,
+    },
+
+    /// A SetVariable step sits inside a Split/While/onWait subgraph. Subgraphs
+    /// run against their own copy of the variables, so the assignment could
+    /// never be observed once the subgraph returns.
+    SetVariableInSubgraph {
+        step_id: String,
+        parent_step_id: String,
+    },
+
     /// Circular dependency detected between workflows.
     CircularDependency { cycle_path: Vec<String> },
 
@@ -396,6 +422,13 @@ pub enum ValidationError {
         label: Option<String>,
         reason: String,
     },
+    /// Error step has an outgoing edge other than `onError`. An Error step
+    /// always fails, so normal-flow successors can never run.
+    ErrorStepNormalEdge {
+        from_step: String,
+        to_step: String,
+        label: Option<String>,
+    },
 
     // === AI Agent Errors ===
     /// AI Agent step has duplicate tool edge labels.
@@ -477,6 +510,9 @@ impl ValidationError {
             Self::ChildMissingInputSchema { .. } => "E054",
             Self::MissingChildWorkflow { .. } => "E124",
             Self::DuplicateEmbedStepId { .. } => "E125",
+            Self::InvalidAdaptiveParallelism { .. } => "E128",
+            Self::SetVariableUnknownVariable { .. } => "E129",
+            Self::SetVariableInSubgraph { .. } => "E130",
             Self::MissingChildRequiredInputs { .. } => "E055",
             Self::CircularDependency { .. } => "E056",
             Self::StepNotYetExecuted { .. } => "E012",
@@ -490,6 +526,7 @@ impl ValidationError {
             Self::MultipleDefaultEdges { .. } => "E071",
             Self::ParallelFanoutNoMerge { .. } => "E073",
             Self::InvalidConditionalEdge { .. } => "E072",
+            Self::ErrorStepNormalEdge { .. } => "E074",
             Self::AiAgentDuplicateToolLabel { .. } => "E110",
             Self::AiAgentInvalidToolLabel { .. } => "E111",
             Self::AiAgentMissingConnection { .. } => "E112",
@@ -847,6 +884,38 @@ impl std::fmt::Display for ValidationError {
                     step_id
                 )
             }
+            ValidationError::InvalidAdaptiveParallelism { step_id, min, max } => {
+                write!(
+                    f,
+                    "[E128] Split step '{}' has adaptiveParallelism min={} max={}.\n       Both bounds must be at least 1 and min must not exceed max.",
+                    step_id, min, max
+                )
+            }
+            ValidationError::SetVariableUnknownVariable {
+                step_id,
+                variable_name,
+                available_variables,
+            } => {
+                let suggestion = find_similar_name(variable_name, available_variables);
+                let suggestion_text = suggestion
+                    .map(|s| format!(". Did you mean '{}'?", s))
+                    .unwrap_or_default();
+                write!(
+                    f,
+                    "[E129] SetVariable step '{}' assigns undeclared variable '{}'{}\n       Declare it under the workflow's variables first.",
+                    step_id, variable_name, suggestion_text
+                )
+            }
+            ValidationError::SetVariableInSubgraph {
+                step_id,
+                parent_step_id,
+            } => {
+                write!(
+                    f,
+                    "[E130] SetVariable step '{}' is inside the subgraph of '{}'.\n       Subgraphs run against a copy of the variables; move the step to the top-level graph.",
+                    step_id, parent_step_id
+                )
+            }
             ValidationError::MissingChildRequiredInputs {
                 step_id,
                 child_workflow_id,
@@ -1047,6 +1116,19 @@ impl std::fmt::Display for ValidationError {
                     from_step, to_step, label_str, reason
                 )
             }
+            ValidationError::ErrorStepNormalEdge {
+                from_step,
+                to_step,
+                label,
+            } => {
+                let label_str = label.as_deref().unwrap_or("(default)");
+                write!(
+                    f,
+                    "[E074] Error step '{}' has an edge to '{}' with label '{}'. \
+                     An Error step always fails, so only 'onError' edges can leave it.",
+                    from_step, to_step, label_str
+                )
+            }
             ValidationError::AiAgentDuplicateToolLabel { step_id, label } => {
                 write!(
                     f,
@@ -1265,6 +1347,67 @@ pub enum ValidationWarning {
     /// top-level While) makes the reference checkable — otherwise a typo
     /// silently resolves to null at runtime.
     UnverifiedDataReference { step_id: String, reference: String },
+    /// An Agent step calls a capability whose result differs on every call
+    /// (random numbers, the current time) while the step retries or runs
+    /// inside a Split/While body. A retried attempt or a resumed iteration can
+    /// observe a different value than the attempt that already started side
+    /// effects with it.
+    NonDeterministicCapability {
+        step_id: String,
+        agent_id: String,
+        capability_id: String,
+        max_retries: u32,
+        in_loop: bool,
+    },
+}
+
+impl ValidationWarning {
+    /// Stable machine-readable code for this warning — the `[WXXX]` prefix of
+    /// the [`Display`](std::fmt::Display) message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownInputField { .. } => "W020",
+            Self::HighRetryCount { .. } => "W030",
+            Self::LongRetryDelay { .. } => "W031",
+            Self::SplitParallelismIgnored { .. } => "W073",
+            Self::HighMaxIterations { .. } => "W033",
+            Self::LongTimeout { .. } => "W034",
+            Self::SelfReference { .. } => "W050",
+            Self::DanglingStep { .. } => "W003",
+            Self::DuplicateEdgeToTarget { .. } => "W040",
+            Self::CompensationNotEnforced { .. } => "W070",
+            Self::TimeoutNotEnforced { .. } => "W071",
+            Self::OnWaitIgnoredForAiAgentTool { .. } => "W072",
+            Self::PartiallyUnverifiedReference { .. } => "W051",
+            Self::TemplateReferenceIssue { .. } => "W052",
+            Self::BareErrorReference { .. } => "W053",
+            Self::UnverifiedDataReference { .. } => "W080",
+            Self::NonDeterministicCapability { .. } => "W074",
+        }
+    }
+
+    /// Step the warning is about. Edge warnings report the source step.
+    pub fn step_id(&self) -> &str {
+        match self {
+            Self::UnknownInputField { step_id, .. }
+            | Self::HighRetryCount { step_id, .. }
+            | Self::LongRetryDelay { step_id, .. }
+            | Self::SplitParallelismIgnored { step_id, .. }
+            | Self::HighMaxIterations { step_id, .. }
+            | Self::LongTimeout { step_id, .. }
+            | Self::SelfReference { step_id, .. }
+            | Self::DanglingStep { step_id, .. }
+            | Self::CompensationNotEnforced { step_id }
+            | Self::TimeoutNotEnforced { step_id, .. }
+            | Self::OnWaitIgnoredForAiAgentTool { step_id, .. }
+            | Self::PartiallyUnverifiedReference { step_id, .. }
+            | Self::TemplateReferenceIssue { step_id, .. }
+            | Self::BareErrorReference { step_id, .. }
+            | Self::UnverifiedDataReference { step_id, .. }
+            | Self::NonDeterministicCapability { step_id, .. } => step_id,
+            Self::DuplicateEdgeToTarget { from_step, .. } => from_step,
+        }
+    }
 }
 
 impl std::fmt::Display for ValidationWarning {
@@ -1458,6 +1601,29 @@ impl std::fmt::Display for ValidationWarning {
                     step_id, reference
                 )
             }
+            ValidationWarning::NonDeterministicCapability {
+                step_id,
+                agent_id,
+                capability_id,
+                max_retries,
+                in_loop,
+            } => {
+                let context = match (*in_loop, *max_retries) {
+                    (true, 0) => "runs inside a loop".to_string(),
+                    (true, n) => format!("runs inside a loop with maxRetries={}", n),
+                    (false, n) => format!("has maxRetries={}", n),
+                };
+                let alternative = if capability_id.starts_with("random") {
+                    "utils:random-seeded with a seed mapped from variables._instance_id and the step id (plus variables._loop_indices in loops)"
+                } else {
+                    "a timestamp captured once by a maxRetries=0 step outside the loop, referenced from its outputs"
+                };
+                write!(
+                    f,
+                    "[W074] Step '{}': '{}:{}' returns a different value on every call, and this step {}. A retry or resumed iteration can see a different value than the attempt that already ran; side-effecting logic should use {} instead.",
+                    step_id, agent_id, capability_id, context, alternative
+                )
+            }
         }
     }
 }
@@ -1515,6 +1681,9 @@ pub fn validate_workflow(
     // Phase 7.5: Reference validation (data.* and variables.* definitions)
     validate_data_and_variable_references(graph, &mut result);
 
+    // Phase 7.6: SetVariable targets (declared, type-compatible, top level only)
+    validate_set_variable_steps(graph, &mut result);
+
     // Phase 8: Step name validation
     validate_step_names(graph, &mut result);
 
@@ -1524,6 +1693,9 @@ pub fn validate_workflow(
     // Phase 9.5: Timeout validation (W071 — Agent/EmbedWorkflow timeouts are not enforced)
     validate_unenforced_timeouts(graph, &mut result);
 
+    // Phase 9.6: Non-deterministic capabilities under retry/loop (W074)
+    validate_nondeterministic_capabilities(graph, false, &mut result);
+
     // Phase 10: Edge condition validation (unique priorities, at most one default)
     validate_edge_conditions(graph, &mut result);
 
@@ -2065,8 +2237,8 @@ fn validate_graph_structure(graph: &ExecutionGraph, result: &mut ValidationResul
         .collect();
 
     for (step_id, step) in &graph.steps {
-        // Finish steps are allowed to have no outgoing edges
-        if matches!(step, Step::Finish(_)) {
+        // Finish and Error steps end the workflow, so they need no outgoing edges
+        if matches!(step, Step::Finish(_) | Step::Error(_)) {
             continue;
         }
 
@@ -2621,6 +2793,9 @@ fn collect_step_mappings(step: &Step) -> Vec<&InputMapping> {
                 mappings.push(m);
             }
         }
+        Step::SetVariable(set_step) => {
+            mappings.push(&set_step.values);
+        }
         Step::Split(split_step) => {
             if let Some(config) = &split_step.config
                 && let Some(m) = &config.variables
@@ -3756,6 +3931,18 @@ fn validate_configuration(graph: &ExecutionGraph, result: &mut ValidationResult)
 
             Step::Split(split_step) => {
                 if let Some(config) = &split_step.config {
+                    if let Some(adaptive) = config.adaptive_parallelism
+                        && (adaptive.min == 0 || adaptive.min > adaptive.max)
+                    {
+                        result
+                            .errors
+                            .push(ValidationError::InvalidAdaptiveParallelism {
+                                step_id: step_id.clone(),
+                                min: adaptive.min,
+                                max: adaptive.max,
+                            });
+                    }
+
                     // W073: parallelism promises concurrency that the WASM
                     // runtime does not deliver — Split is always sequential.
                     // parallelism=1 matches actual behavior, so only other
@@ -3955,6 +4142,7 @@ fn collect_step_names(graph: &ExecutionGraph, name_to_step_ids: &mut HashMap<Str
             Step::Filter(s) => s.name.as_ref(),
             Step::GroupBy(s) => s.name.as_ref(),
             Step::Delay(s) => s.name.as_ref(),
+            Step::SetVariable(s) => s.name.as_ref(),
             Step::WaitForSignal(s) => s.name.as_ref(),
             Step::AiAgent(s) => s.name.as_ref(),
         };
@@ -4068,6 +4256,66 @@ fn validate_unenforced_timeouts(graph: &ExecutionGraph, result: &mut ValidationR
     }
 }
 
+/// Capabilities whose result depends on when (or how often) they run.
+const NON_DETERMINISTIC_CAPABILITIES: &[(&str, &str)] = &[
+    ("utils", "random-double"),
+    ("utils", "random-array"),
+    ("utils", "get-current-unix-timestamp"),
+    ("utils", "get-current-iso-datetime"),
+    ("utils", "get-current-formatted-datetime"),
+    ("datetime", "get-current-date"),
+];
+
+/// Agent steps default to 3 retries when `maxRetries` is omitted.
+const DEFAULT_AGENT_MAX_RETRIES: u32 = 3;
+
+/// W074: warn when a non-deterministic capability runs in a step that can
+/// execute more than once for the same logical position — it retries, or it
+/// sits in a Split/While body. Each attempt reads a fresh random value or
+/// clock, so a retry that follows a half-applied side effect works with a
+/// different value than the attempt that started it.
+fn validate_nondeterministic_capabilities(
+    graph: &ExecutionGraph,
+    in_loop: bool,
+    result: &mut ValidationResult,
+) {
+    for (step_id, step) in &graph.steps {
+        match step {
+            Step::Agent(agent_step)
+                if NON_DETERMINISTIC_CAPABILITIES.contains(&(
+                    agent_step.agent_id.as_str(),
+                    agent_step.capability_id.as_str(),
+                )) =>
+            {
+                let max_retries = agent_step.max_retries.unwrap_or(DEFAULT_AGENT_MAX_RETRIES);
+                if in_loop || max_retries > 0 {
+                    result
+                        .warnings
+                        .push(ValidationWarning::NonDeterministicCapability {
+                            step_id: step_id.clone(),
+                            agent_id: agent_step.agent_id.clone(),
+                            capability_id: agent_step.capability_id.clone(),
+                            max_retries,
+                            in_loop,
+                        });
+                }
+            }
+            Step::Split(split_step) => {
+                validate_nondeterministic_capabilities(&split_step.subgraph, true, result);
+            }
+            Step::While(while_step) => {
+                validate_nondeterministic_capabilities(&while_step.subgraph, true, result);
+            }
+            Step::WaitForSignal(wait_step) => {
+                if let Some(on_wait) = &wait_step.on_wait {
+                    validate_nondeterministic_capabilities(on_wait, in_loop, result);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 // ============================================================================
 // Phase 10: Edge Condition Validation
 // ============================================================================
@@ -4272,6 +4520,7 @@ fn validate_edge_endpoints(graph: &ExecutionGraph, result: &mut ValidationResult
 
 fn validate_edge_conditions_recursive(graph: &ExecutionGraph, result: &mut ValidationResult) {
     validate_conditional_branch_edges(graph, result);
+    validate_error_step_edges(graph, result);
 
     // Group edges by (from_step, label)
     let mut edges_by_from_label: HashMap<
@@ -4422,6 +4671,21 @@ fn validate_conditional_branch_edges(graph: &ExecutionGraph, result: &mut Valida
     }
 }
 
+fn validate_error_step_edges(graph: &ExecutionGraph, result: &mut ValidationResult) {
+    for edge in &graph.execution_plan {
+        if edge.label.as_deref() == Some("onError") {
+            continue;
+        }
+        if matches!(graph.steps.get(&edge.from_step), Some(Step::Error(_))) {
+            result.errors.push(ValidationError::ErrorStepNormalEdge {
+                from_step: edge.from_step.clone(),
+                to_step: edge.to_step.clone(),
+                label: edge.label.clone(),
+            });
+        }
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -4577,6 +4841,7 @@ fn get_step_type_name(step: &Step) -> &'static str {
         Step::Filter(_) => "Filter",
         Step::GroupBy(_) => "GroupBy",
         Step::Delay(_) => "Delay",
+        Step::SetVariable(_) => "SetVariable",
         Step::WaitForSignal(_) => "WaitForSignal",
         Step::AiAgent(_) => "AiAgent",
     }
@@ -5064,6 +5329,91 @@ fn extract_references_from_input_mapping(mapping: &InputMapping, refs: &mut Vec<
 // ============================================================================
 
 /// Validate that all data.* and variables.* references are defined.
+/// Validate SetVariable steps: every assigned name must be a declared graph
+/// variable (E129), immediate values must match the declared type (E023), and
+/// the step must not sit in a nested subgraph (E130).
+fn validate_set_variable_steps(graph: &ExecutionGraph, result: &mut ValidationResult) {
+    for (step_id, step) in &graph.steps {
+        match step {
+            Step::SetVariable(set_step) => {
+                for (name, value) in &set_step.values {
+                    let Some(declared) = graph.variables.get(name) else {
+                        result
+                            .errors
+                            .push(ValidationError::SetVariableUnknownVariable {
+                                step_id: step_id.clone(),
+                                variable_name: name.clone(),
+                                available_variables: graph.variables.keys().cloned().collect(),
+                            });
+                        continue;
+                    };
+                    if let MappingValue::Immediate(immediate) = value {
+                        let expected_type = match declared.var_type {
+                            runtara_dsl::VariableType::String => "string",
+                            runtara_dsl::VariableType::Number => "number",
+                            runtara_dsl::VariableType::Integer => "integer",
+                            runtara_dsl::VariableType::Boolean => "boolean",
+                            runtara_dsl::VariableType::Array => "array",
+                            runtara_dsl::VariableType::Object | runtara_dsl::VariableType::File => {
+                                "object"
+                            }
+                        };
+                        if let Some(error) = check_type_compatibility(
+                            step_id,
+                            &format!("values.{name}"),
+                            expected_type,
+                            &immediate.value,
+                        ) {
+                            result.errors.push(error);
+                        }
+                    }
+                }
+            }
+            Step::Split(split_step) => {
+                reject_nested_set_variables(&split_step.subgraph, step_id, result);
+            }
+            Step::While(while_step) => {
+                reject_nested_set_variables(&while_step.subgraph, step_id, result);
+            }
+            Step::WaitForSignal(wait_step) => {
+                if let Some(on_wait) = &wait_step.on_wait {
+                    reject_nested_set_variables(on_wait, step_id, result);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn reject_nested_set_variables(
+    subgraph: &ExecutionGraph,
+    parent_step_id: &str,
+    result: &mut ValidationResult,
+) {
+    for (step_id, step) in &subgraph.steps {
+        match step {
+            Step::SetVariable(_) => {
+                result.errors.push(ValidationError::SetVariableInSubgraph {
+                    step_id: step_id.clone(),
+                    parent_step_id: parent_step_id.to_string(),
+                });
+            }
+            Step::Split(split_step) => {
+                reject_nested_set_variables(&split_step.subgraph, step_id, result);
+            }
+            Step::While(while_step) => {
+                reject_nested_set_variables(&while_step.subgraph, step_id, result);
+            }
+            Step::WaitForSignal(wait_step) => {
+                if let Some(on_wait) = &wait_step.on_wait {
+                    reject_nested_set_variables(on_wait, step_id, result);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 fn validate_data_and_variable_references(graph: &ExecutionGraph, result: &mut ValidationResult) {
     // Start validation with no inherited variables and require inputSchema for data references
     validate_data_and_variable_references_with_context(
@@ -5504,6 +5854,9 @@ fn collect_references_from_step(step: &Step) -> Vec<String> {
                 extract_references_from_input_mapping(context, &mut refs);
             }
         }
+        Step::SetVariable(set_step) => {
+            extract_references_from_input_mapping(&set_step.values, &mut refs);
+        }
         Step::Conditional(cond_step) => {
             extract_references_from_condition(&cond_step.condition, &mut refs);
         }
@@ -5625,6 +5978,9 @@ fn collect_template_static_references_from_step(step: &Step) -> Vec<String> {
                 extract_template_static_references_from_input_mapping(context, &mut refs);
             }
         }
+        Step::SetVariable(set_step) => {
+            extract_template_static_references_from_input_mapping(&set_step.values, &mut refs);
+        }
         Step::Conditional(cond_step) => {
             extract_template_static_references_from_condition(&cond_step.condition, &mut refs);
         }
@@ -6033,7 +6389,8 @@ fn validate_template_syntax(template_str: &str) -> Option<String> {
 mod tests {
     use super::*;
     use runtara_dsl::{
-        AgentStep, AiAgentStep, EmbedWorkflowStep, FinishStep, LogLevel, LogStep, ReferenceValue,
+        AgentStep, AiAgentStep, EmbedWorkflowStep, ErrorStep, FinishStep, LogLevel, LogStep,
+        ReferenceValue,
     };
 
     /// Validator unit tests run against a committed snapshot of the real
@@ -6087,6 +6444,19 @@ mod tests {
         })
     }
 
+    fn create_error_step(id: &str) -> Step {
+        Step::Error(ErrorStep {
+            id: id.to_string(),
+            name: None,
+            category: Default::default(),
+            code: "ORDER_SHIPPED".to_string(),
+            message: "Order already shipped".to_string(),
+            severity: None,
+            context: None,
+            breakpoint: None,
+        })
+    }
+
     fn create_log_step(id: &str, context: Option<InputMapping>) -> Step {
         Step::Log(LogStep {
             id: id.to_string(),
@@ -7429,6 +7799,7 @@ mod tests {
                     allow_null: None,
                     convert_single_value: None,
                     batch_size: None,
+                    adaptive_parallelism: None,
                 }),
                 input_schema: HashMap::new(),
                 output_schema: HashMap::new(),
@@ -9263,6 +9634,134 @@ mod tests {
         );
     }
 
+    // --- SetVariable Validation Tests ---
+
+    fn set_variable_graph(steps: serde_json::Value, plan: serde_json::Value) -> ExecutionGraph {
+        serde_json::from_value(serde_json::json!({
+            "steps": steps,
+            "entryPoint": "set",
+            "executionPlan": plan,
+            "variables": { "total": { "type": "number", "value": 0 } }
+        }))
+        .expect("SetVariable graph should parse")
+    }
+
+    #[test]
+    fn test_set_variable_declared_variable_is_valid() {
+        let graph = set_variable_graph(
+            serde_json::json!({
+                "set": {
+                    "stepType": "SetVariable",
+                    "id": "set",
+                    "values": { "total": { "valueType": "immediate", "value": 42 } }
+                },
+                "finish": {
+                    "stepType": "Finish",
+                    "id": "finish",
+                    "inputMapping": {
+                        "total": { "valueType": "reference", "value": "variables.total" }
+                    }
+                }
+            }),
+            serde_json::json!([{ "fromStep": "set", "toStep": "finish" }]),
+        );
+
+        let result = validate_workflow(&graph, &test_catalog());
+        assert!(
+            !result.has_errors(),
+            "valid SetVariable should pass: {:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn test_set_variable_unknown_variable_error() {
+        let graph = set_variable_graph(
+            serde_json::json!({
+                "set": {
+                    "stepType": "SetVariable",
+                    "id": "set",
+                    "values": { "totl": { "valueType": "immediate", "value": 42 } }
+                }
+            }),
+            serde_json::json!([]),
+        );
+
+        let result = validate_workflow(&graph, &test_catalog());
+        let error = result
+            .errors
+            .iter()
+            .find(|e| matches!(e, ValidationError::SetVariableUnknownVariable { .. }))
+            .expect("Expected SetVariableUnknownVariable error");
+        assert_eq!(error.code(), "E129");
+        assert!(error.to_string().contains("totl"));
+    }
+
+    #[test]
+    fn test_set_variable_immediate_type_mismatch_error() {
+        let graph = set_variable_graph(
+            serde_json::json!({
+                "set": {
+                    "stepType": "SetVariable",
+                    "id": "set",
+                    "values": { "total": { "valueType": "immediate", "value": "many" } }
+                }
+            }),
+            serde_json::json!([]),
+        );
+
+        let result = validate_workflow(&graph, &test_catalog());
+        assert!(
+            result.errors.iter().any(|e| matches!(
+                e,
+                ValidationError::TypeMismatch { field_name, .. } if field_name == "values.total"
+            )),
+            "Expected TypeMismatch for values.total, got {:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn test_set_variable_in_while_subgraph_error() {
+        let graph = set_variable_graph(
+            serde_json::json!({
+                "set": {
+                    "stepType": "While",
+                    "id": "set",
+                    "condition": {
+                        "type": "operation",
+                        "op": "LT",
+                        "arguments": [
+                            { "valueType": "reference", "value": "_loop.index" },
+                            { "valueType": "immediate", "value": 3 }
+                        ]
+                    },
+                    "subgraph": {
+                        "entryPoint": "inner",
+                        "steps": {
+                            "inner": {
+                                "stepType": "SetVariable",
+                                "id": "inner",
+                                "values": { "total": { "valueType": "immediate", "value": 1 } }
+                            }
+                        },
+                        "executionPlan": []
+                    }
+                }
+            }),
+            serde_json::json!([]),
+        );
+
+        let result = validate_workflow(&graph, &test_catalog());
+        let error = result
+            .errors
+            .iter()
+            .find(|e| matches!(e, ValidationError::SetVariableInSubgraph { .. }))
+            .expect("Expected SetVariableInSubgraph error");
+        assert_eq!(error.code(), "E130");
+        assert!(error.to_string().contains("inner"));
+    }
+
     // --- Variable Existence Validation Tests ---
 
     #[test]
@@ -10363,6 +10862,7 @@ mod tests {
             allow_null: None,
             convert_single_value: None,
             batch_size: None,
+            adaptive_parallelism: None,
         };
 
         let mut steps = HashMap::new();
@@ -10466,6 +10966,7 @@ mod tests {
             allow_null: None,
             convert_single_value: None,
             batch_size: None,
+            adaptive_parallelism: None,
         };
 
         let mut steps = HashMap::new();
@@ -10581,6 +11082,7 @@ mod tests {
             allow_null: None,
             convert_single_value: None,
             batch_size: None,
+            adaptive_parallelism: None,
         };
 
         let mut steps = HashMap::new();
@@ -10678,6 +11180,7 @@ mod tests {
             allow_null: None,
             convert_single_value: None,
             batch_size: None,
+            adaptive_parallelism: None,
         };
 
         let mut steps = HashMap::new();
@@ -10801,6 +11304,7 @@ mod tests {
             allow_null: None,
             convert_single_value: None,
             batch_size: None,
+            adaptive_parallelism: None,
         };
 
         let mut steps = HashMap::new();
@@ -10898,6 +11402,7 @@ mod tests {
                 allow_null: None,
                 convert_single_value: None,
                 batch_size: None,
+                adaptive_parallelism: None,
             }),
             input_schema,
             output_schema: HashMap::new(),
@@ -11360,6 +11865,95 @@ mod tests {
         );
     }
 
+    // === Non-deterministic Capability Tests (W074) ===
+
+    fn w074_flags(result: &ValidationResult) -> Vec<(String, u32, bool)> {
+        let mut flags: Vec<_> = result
+            .warnings
+            .iter()
+            .filter_map(|w| match w {
+                ValidationWarning::NonDeterministicCapability {
+                    step_id,
+                    max_retries,
+                    in_loop,
+                    ..
+                } => Some((step_id.clone(), *max_retries, *in_loop)),
+                _ => None,
+            })
+            .collect();
+        flags.sort();
+        flags
+    }
+
+    #[test]
+    fn test_nondeterministic_capability_with_retries_warns_w074() {
+        let graph: ExecutionGraph = serde_json::from_str(
+            r##"{
+              "entryPoint": "rand",
+              "executionPlan": [
+                {"fromStep":"rand","toStep":"once"},
+                {"fromStep":"once","toStep":"seeded"},
+                {"fromStep":"seeded","toStep":"finish"}
+              ],
+              "steps": {
+                "rand": {"id":"rand","stepType":"Agent","agentId":"utils",
+                  "capabilityId":"random-double","inputMapping":{}},
+                "once": {"id":"once","stepType":"Agent","agentId":"utils",
+                  "capabilityId":"get-current-iso-datetime","inputMapping":{},"maxRetries":0},
+                "seeded": {"id":"seeded","stepType":"Agent","agentId":"utils",
+                  "capabilityId":"random-seeded","inputMapping":{
+                    "seed":{"valueType":"reference","value":"variables._instance_id"}}},
+                "finish": {"id":"finish","stepType":"Finish"}
+              }
+            }"##,
+        )
+        .unwrap();
+
+        let result = validate_workflow(&graph, &test_catalog());
+
+        // Default Agent retries (3) flag `rand`; a no-retry step outside any
+        // loop runs once, and the seeded capability is deterministic.
+        assert_eq!(w074_flags(&result), vec![("rand".to_string(), 3, false)]);
+        let display = result
+            .warnings
+            .iter()
+            .find(|w| matches!(w, ValidationWarning::NonDeterministicCapability { .. }))
+            .unwrap()
+            .to_string();
+        assert!(display.contains("[W074]"), "{display}");
+        assert!(display.contains("random-seeded"), "{display}");
+    }
+
+    #[test]
+    fn test_nondeterministic_capability_inside_loop_warns_w074() {
+        let graph: ExecutionGraph = serde_json::from_str(
+            r##"{
+              "entryPoint": "split",
+              "executionPlan": [{"fromStep":"split","toStep":"finish"}],
+              "steps": {
+                "split": {"id":"split","stepType":"Split","config":{
+                    "value": {"valueType":"immediate","value":[1,2]}
+                  },
+                  "subgraph": {
+                    "entryPoint": "now",
+                    "executionPlan": [{"fromStep":"now","toStep":"inner_finish"}],
+                    "steps": {
+                      "now": {"id":"now","stepType":"Agent","agentId":"utils",
+                        "capabilityId":"get-current-iso-datetime","inputMapping":{},"maxRetries":0},
+                      "inner_finish": {"id":"inner_finish","stepType":"Finish"}
+                    }
+                  }},
+                "finish": {"id":"finish","stepType":"Finish"}
+              }
+            }"##,
+        )
+        .unwrap();
+
+        let result = validate_workflow(&graph, &test_catalog());
+
+        assert_eq!(w074_flags(&result), vec![("now".to_string(), 0, true)]);
+    }
+
     // === Unenforced Timeout Tests (W071) ===
 
     #[test]
@@ -11763,6 +12357,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_split_adaptive_parallelism_bounds() {
+        let graph = split_graph_with_config(r#","adaptiveParallelism":{"min":1,"max":8}"#);
+        let result = validate_workflow(&graph, &test_catalog());
+        assert!(
+            !result
+                .errors
+                .iter()
+                .any(|e| matches!(e, ValidationError::InvalidAdaptiveParallelism { .. })),
+            "{:?}",
+            result.errors
+        );
+
+        for (extra, min, max) in [
+            (r#","adaptiveParallelism":{"min":0,"max":4}"#, 0u32, 4u32),
+            (r#","adaptiveParallelism":{"min":6,"max":2}"#, 6, 2),
+        ] {
+            let graph = split_graph_with_config(extra);
+            let result = validate_workflow(&graph, &test_catalog());
+            let e128: Vec<_> = result
+                .errors
+                .iter()
+                .filter(|e| e.code() == "E128")
+                .collect();
+            assert_eq!(e128.len(), 1, "extra={extra}: {:?}", result.errors);
+            assert!(matches!(
+                e128[0],
+                ValidationError::InvalidAdaptiveParallelism { step_id, min: m, max: x }
+                    if step_id == "split" && *m == min && *x == max
+            ));
+        }
+    }
+
     // === Edge Condition Tests ===
 
     fn create_condition_eq(left_ref: &str, right_val: &str) -> runtara_dsl::ConditionExpression {
@@ -12205,6 +12832,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_step_normal_edge_fails() {
+        let mut steps = HashMap::new();
+        steps.insert("abort".to_string(), create_error_step("abort"));
+        steps.insert("done".to_string(), create_finish_step("done", None));
+
+        let mut graph = create_basic_graph(steps, "abort");
+        graph.execution_plan = vec![runtara_dsl::ExecutionPlanEdge {
+            from_step: "abort".to_string(),
+            to_step: "done".to_string(),
+            label: None,
+            condition: None,
+            priority: None,
+        }];
+
+        let result = validate_workflow(&graph, &test_catalog());
+        let err = result
+            .errors
+            .iter()
+            .find(|e| matches!(e, ValidationError::ErrorStepNormalEdge { .. }))
+            .expect("Error step with a normal edge should be rejected");
+        assert_eq!(err.code(), "E074");
+        assert!(err.to_string().contains("'abort'"));
+    }
+
+    #[test]
+    fn test_error_step_on_error_edge_and_no_edges_pass() {
+        let mut steps = HashMap::new();
+        steps.insert("abort".to_string(), create_error_step("abort"));
+        steps.insert("cleanup".to_string(), create_finish_step("cleanup", None));
+
+        let mut graph = create_basic_graph(steps.clone(), "abort");
+        graph.execution_plan = vec![runtara_dsl::ExecutionPlanEdge {
+            from_step: "abort".to_string(),
+            to_step: "cleanup".to_string(),
+            label: Some("onError".to_string()),
+            condition: None,
+            priority: None,
+        }];
+        let result = validate_workflow(&graph, &test_catalog());
+        assert!(
+            !result
+                .errors
+                .iter()
+                .any(|e| matches!(e, ValidationError::ErrorStepNormalEdge { .. })),
+            "onError edges may leave an Error step. Errors: {:?}",
+            result.errors
+        );
+
+        // A terminal Error step is not a dangling step.
+        steps.remove("cleanup");
+        let result = validate_workflow(&create_basic_graph(steps, "abort"), &test_catalog());
+        assert!(
+            !result
+                .warnings
+                .iter()
+                .any(|w| matches!(w, ValidationWarning::DanglingStep { .. }))
+        );
+    }
+
     #[test]
     fn test_edge_condition_error_display() {
         let err = ValidationError::DuplicateEdgePriority {
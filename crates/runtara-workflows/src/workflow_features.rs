@@ -61,6 +61,8 @@ pub enum WorkflowFeature {
     GroupBy,
     /// A workflow step sleeps or delays.
     Delay,
+    /// A workflow step assigns workflow variables.
+    SetVariable,
     /// A workflow step waits for an external signal.
     WaitForSignal,
     /// A workflow step can suspend and resume later.
@@ -388,6 +390,12 @@ impl FeatureAnalyzer {
                     self.summary.features.insert(WorkflowFeature::SuspendResume);
                 }
             }
+            Step::SetVariable(_) => {
+                self.summary.features.insert(WorkflowFeature::SetVariable);
+                if graph_durable {
+                    self.summary.features.insert(WorkflowFeature::Durability);
+                }
+            }
             Step::WaitForSignal(step) => {
                 self.summary.features.insert(WorkflowFeature::WaitForSignal);
                 self.summary.features.insert(WorkflowFeature::SuspendResume);
@@ -443,6 +451,7 @@ fn step_has_breakpoint(step: &Step) -> bool {
         Step::Filter(step) => step.breakpoint.unwrap_or(false),
         Step::GroupBy(step) => step.breakpoint.unwrap_or(false),
         Step::Delay(step) => step.breakpoint.unwrap_or(false),
+        Step::SetVariable(step) => step.breakpoint.unwrap_or(false),
         Step::WaitForSignal(step) => step.breakpoint.unwrap_or(false),
         Step::AiAgent(step) => step.breakpoint.unwrap_or(false),
     }
@@ -462,6 +471,7 @@ pub(crate) fn step_type_name(step: &Step) -> &'static str {
         Step::Filter(_) => "Filter",
         Step::GroupBy(_) => "GroupBy",
         Step::Delay(_) => "Delay",
+        Step::SetVariable(_) => "SetVariable",
         Step::WaitForSignal(_) => "WaitForSignal",
         Step::AiAgent(_) => "AiAgent",
     }
@@ -454,6 +454,7 @@ fn shared_components_dir() -> PathBuf {
         .unwrap_or_else(|error| panic!("read required {stdlib_wasm:?}: {error}"));
     let required_stdlib_markers: &[&[u8]] = &[
         b"split-cache-key",
+        b"split-next-window",
         b"embed-workflow-cache-key",
         b"embed-workflow-variables",
         b"embed-workflow-result",
@@ -1554,6 +1555,10 @@ impl runtara_component_host::runtime_host::RuntimeHost for CapturingRuntimeHost
             .get(&checkpoint_id)
             .cloned())
     }
+
+    async fn compact_checkpoints(&self, _prefixes: Vec<u8>) -> Result<u64, String> {
+        Ok(0)
+    }
     async fn checkpoint(
         &self,
         checkpoint_id: String,
@@ -2038,6 +2043,10 @@ impl runtara_component_host::runtime_host::RuntimeHost for RecordingRuntimeHost
     async fn get_checkpoint(&self, _checkpoint_id: String) -> Result<Option<Vec<u8>>, String> {
         Ok(None)
     }
+
+    async fn compact_checkpoints(&self, _prefixes: Vec<u8>) -> Result<u64, String> {
+        Ok(0)
+    }
     async fn checkpoint(
         &self,
         _checkpoint_id: String,
@@ -2179,6 +2188,10 @@ impl runtara_component_host::runtime_host::RuntimeHost for PersistingRuntimeHost
             .get(&checkpoint_id)
             .cloned())
     }
+
+    async fn compact_checkpoints(&self, _prefixes: Vec<u8>) -> Result<u64, String> {
+        Ok(0)
+    }
     async fn checkpoint(
         &self,
         checkpoint_id: String,
@@ -7099,6 +7112,10 @@ impl runtara_component_host::runtime_host::RuntimeHost for CheckpointingRuntimeH
             .get(&checkpoint_id)
             .cloned())
     }
+
+    async fn compact_checkpoints(&self, _prefixes: Vec<u8>) -> Result<u64, String> {
+        Ok(0)
+    }
     async fn checkpoint(
         &self,
         checkpoint_id: String,